@@ -0,0 +1,52 @@
+use ckb_hash::blake2b_256;
+use ckb_pow::{pow_message, EaglesongLanes, EAGLESONG_LANES};
+use ckb_types::prelude::*;
+use criterion::{criterion_group, Criterion, Throughput};
+use eaglesong::eaglesong;
+
+const NONCES: u128 = 1024;
+
+// Compares the scalar Eaglesong of each nonce, as the `EaglesongSimple` miner worker does, with
+// the batched `EaglesongLanes` of the `EaglesongThreaded` worker.
+fn bench(c: &mut Criterion) {
+    let pow_hash = blake2b_256(&[]).pack();
+    let mut group = c.benchmark_group("eaglesong");
+    group.throughput(Throughput::Elements(NONCES as u64));
+
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            let mut found = 0;
+            for nonce in 0..NONCES {
+                let mut output = [0u8; 32];
+                eaglesong(&pow_message(&pow_hash, nonce), &mut output);
+                found += usize::from(output[0] == 0);
+            }
+            found
+        })
+    });
+
+    group.bench_function("lanes", |b| {
+        b.iter(|| {
+            let lanes = EaglesongLanes::new(&pow_hash);
+            let mut found = 0;
+            let mut nonces = [0u128; EAGLESONG_LANES];
+            for start in (0..NONCES).step_by(EAGLESONG_LANES) {
+                for (i, nonce) in nonces.iter_mut().enumerate() {
+                    *nonce = start + i as u128;
+                }
+                for output in lanes.hash(&nonces).iter() {
+                    found += usize::from(output[0] == 0);
+                }
+            }
+            found
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    name = hash;
+    config = Criterion::default().sample_size(10);
+    targets = bench
+);
@@ -1,4 +1,5 @@
 pub mod always_success;
+pub mod eaglesong;
 pub mod overall;
 pub mod resolve;
 pub mod secp_2in2out;
@@ -44,10 +44,13 @@ fn block_assembler_config() -> BlockAssemblerConfig {
         message: Default::default(),
         use_binary_version_as_message_prefix: false,
         binary_version: "BENCH".to_string(),
+        advanced: false,
         update_interval_millis: 800,
         notify: vec![],
         notify_scripts: vec![],
         notify_timeout_millis: 800,
+        payout_targets: vec![],
+        tx_selection: Default::default(),
     }
 }
 
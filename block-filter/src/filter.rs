@@ -0,0 +1,68 @@
+use ckb_hash::{blake2b_256, new_blake2b};
+use ckb_types::{
+    core::TransactionView,
+    packed::{Byte32, CellOutput, OutPoint},
+    prelude::*,
+};
+use golomb_coded_set::{GCSFilterReader, GCSFilterWriter, SipHasher24Builder, M, P};
+
+/// Builds the filter data of a block from its transactions.
+///
+/// The `input_cell` is used to load the previous outputs spent by the transactions. Returns the
+/// out point of the input cell which can not be loaded as the error.
+pub fn build_filter_data<F>(
+    transactions: &[TransactionView],
+    mut input_cell: F,
+) -> Result<Vec<u8>, OutPoint>
+where
+    F: FnMut(&OutPoint) -> Option<CellOutput>,
+{
+    let mut data = Vec::new();
+    {
+        let mut writer = GCSFilterWriter::new(&mut data, SipHasher24Builder::new(0, 0), M, P);
+        for tx in transactions {
+            if !tx.is_cellbase() {
+                for out_point in tx.input_pts_iter() {
+                    let output = input_cell(&out_point).ok_or(out_point)?;
+                    add_cell_output(&mut writer, &output);
+                }
+            }
+            for output in tx.outputs().into_iter() {
+                add_cell_output(&mut writer, &output);
+            }
+        }
+        writer
+            .finish()
+            .expect("write the filter data into memory should be ok");
+    }
+    Ok(data)
+}
+
+fn add_cell_output(writer: &mut GCSFilterWriter<'_, SipHasher24Builder>, output: &CellOutput) {
+    writer.add_element(output.calc_lock_hash().as_slice());
+    if let Some(type_script) = output.type_().to_opt() {
+        writer.add_element(type_script.calc_script_hash().as_slice());
+    }
+}
+
+/// Calculates the filter hash, which is `blake2b(parent_filter_hash | blake2b(filter_data))`.
+///
+/// The parent filter hash of the genesis block is all zeros.
+pub fn calc_filter_hash(parent_filter_hash: &Byte32, filter_data: &[u8]) -> Byte32 {
+    let mut hasher = new_blake2b();
+    hasher.update(parent_filter_hash.as_slice());
+    hasher.update(&blake2b_256(filter_data));
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash.pack()
+}
+
+/// Returns true if any of the script hashes may be in the filter.
+///
+/// A Golomb-coded set has false positives but no false negatives.
+pub fn filter_matches_any(filter_data: &[u8], script_hashes: &[Byte32]) -> bool {
+    let reader = GCSFilterReader::new(SipHasher24Builder::new(0, 0), M, P);
+    let mut input = filter_data;
+    let mut query = script_hashes.iter().map(|hash| hash.as_slice());
+    reader.match_any(&mut input, &mut query).unwrap_or(false)
+}
@@ -0,0 +1,16 @@
+//! The compact block filters for the light clients.
+//!
+//! A block filter is a Golomb-coded set over the lock script hashes and the type script hashes
+//! of the input cells and the output cells in a block, in the style of BIP-158. A light client
+//! downloads the filters to find out the blocks which may contain the cells it cares about.
+//!
+//! Each filter is committed by a filter hash, which chains the filter hash of the parent block,
+//! so a client can check the filters against the filter hashes obtained from several peers.
+mod filter;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use crate::filter::{build_filter_data, calc_filter_hash, filter_matches_any};
+pub use crate::service::{BlockFilterController, BlockFilterService};
@@ -0,0 +1,151 @@
+//! The service which builds the block filters of the canonical chain in a background thread.
+use crate::filter::{build_filter_data, calc_filter_hash};
+use ckb_channel::{self as channel, select, Receiver};
+use ckb_error::{Error, InternalErrorKind};
+use ckb_logger::{debug, error, info};
+use ckb_shared::{Shared, Snapshot};
+use ckb_stop_handler::{SignalSender, StopHandler};
+use ckb_store::ChainStore;
+use ckb_types::{core::BlockView, packed::Byte32, prelude::*};
+use std::thread;
+
+const NAME: &str = "BlockFilter";
+
+/// The block filter service.
+///
+/// The service builds the filters from the latest built block to the chain tip when it starts,
+/// so the filters of an existing chain are built in the background, and then builds the filter
+/// of every new block. When the latest built block is no longer in the canonical chain, the
+/// service restarts from the fork point.
+pub struct BlockFilterService {
+    shared: Shared,
+}
+
+impl BlockFilterService {
+    /// Creates the service.
+    pub fn new(shared: Shared) -> Self {
+        BlockFilterService { shared }
+    }
+
+    /// Starts the background thread.
+    pub fn start<S: ToString>(self, thread_name: Option<S>) -> BlockFilterController {
+        let (signal_sender, signal_receiver) = channel::bounded::<()>(1);
+        let new_block_receiver = self.shared.notify_controller().subscribe_new_block(NAME);
+
+        let mut thread_builder = thread::Builder::new();
+        if let Some(name) = thread_name {
+            thread_builder = thread_builder.name(name.to_string());
+        }
+        let thread = thread_builder
+            .spawn(move || loop {
+                if let Err(e) = self.build_filters(&signal_receiver) {
+                    error!("Build block filters error {}", e);
+                }
+                select! {
+                    recv(new_block_receiver) -> msg => if msg.is_err() {
+                        break;
+                    },
+                    recv(signal_receiver) -> _ => {
+                        info!("BlockFilter closing");
+                        break;
+                    }
+                }
+            })
+            .expect("Start BlockFilterService failed");
+
+        let stop = StopHandler::new(
+            SignalSender::Crossbeam(signal_sender),
+            Some(thread),
+            "block-filter".to_string(),
+        );
+        BlockFilterController { stop }
+    }
+
+    /// Builds the filters from the block after the latest built one to the chain tip.
+    fn build_filters(&self, signal_receiver: &Receiver<()>) -> Result<(), Error> {
+        let snapshot = self.shared.snapshot();
+        let start_number = match snapshot.get_latest_built_filter_data_block_hash() {
+            Some(mut block_hash) => {
+                while !snapshot.is_main_chain(&block_hash) {
+                    let header = snapshot
+                        .get_block_header(&block_hash)
+                        .expect("header stored");
+                    debug!(
+                        "Block filter of {} is not in the main chain",
+                        header.number()
+                    );
+                    block_hash = header.parent_hash();
+                }
+                snapshot
+                    .get_block_number(&block_hash)
+                    .expect("main chain block")
+                    + 1
+            }
+            None => 0,
+        };
+
+        for number in start_number..=snapshot.tip_number() {
+            if !signal_receiver.is_empty() {
+                return Ok(());
+            }
+            let block = snapshot
+                .get_block_hash(number)
+                .and_then(|hash| snapshot.get_block(&hash))
+                .ok_or_else(|| {
+                    InternalErrorKind::Database
+                        .other(format!("the main chain block {} is not found", number))
+                })?;
+            self.build_filter(&snapshot, &block)?;
+        }
+        Ok(())
+    }
+
+    fn build_filter(&self, snapshot: &Snapshot, block: &BlockView) -> Result<(), Error> {
+        let filter_data = build_filter_data(&block.transactions(), |out_point| {
+            snapshot
+                .get_transaction(&out_point.tx_hash())
+                .and_then(|(tx, _)| tx.output(out_point.index().unpack()))
+        })
+        .map_err(|out_point| {
+            InternalErrorKind::Database.other(format!(
+                "the input cell {} of block {} is not found",
+                out_point,
+                block.hash()
+            ))
+        })?;
+
+        let parent_filter_hash = if block.is_genesis() {
+            Byte32::zero()
+        } else {
+            snapshot
+                .get_block_filter_hash(&block.parent_hash())
+                .ok_or_else(|| {
+                    InternalErrorKind::Database.other(format!(
+                        "the filter hash of block {} is not found",
+                        block.parent_hash()
+                    ))
+                })?
+        };
+        let filter_hash = calc_filter_hash(&parent_filter_hash, &filter_data);
+
+        let db_txn = self.shared.store().begin_transaction();
+        db_txn.insert_block_filter(&block.hash(), &filter_data.pack(), &filter_hash)?;
+        db_txn.update_latest_built_filter_data_block_hash(&block.hash())?;
+        db_txn.commit()?;
+        if block.number() % 10_000 == 0 {
+            info!("Block filters built to {}", block.number());
+        }
+        Ok(())
+    }
+}
+
+/// Keeps the block filter service running, and stops it when dropped.
+pub struct BlockFilterController {
+    stop: StopHandler<()>,
+}
+
+impl Drop for BlockFilterController {
+    fn drop(&mut self) {
+        self.stop.try_send(());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{build_filter_data, calc_filter_hash, filter_matches_any};
+use ckb_types::{
+    bytes::Bytes,
+    core::{Capacity, ScriptHashType, TransactionBuilder},
+    packed::{self, CellInput, CellOutput, OutPoint, Script},
+    prelude::*,
+};
+
+fn script(code_hash: u8, args: &[u8]) -> Script {
+    Script::new_builder()
+        .code_hash(packed::Byte32::new([code_hash; 32]))
+        .hash_type(ScriptHashType::Data.into())
+        .args(Bytes::copy_from_slice(args).pack())
+        .build()
+}
+
+fn output(lock: Script, type_: Option<Script>) -> CellOutput {
+    CellOutput::new_builder()
+        .capacity(Capacity::shannons(1000).pack())
+        .lock(lock)
+        .type_(type_.pack())
+        .build()
+}
+
+#[test]
+fn build_and_match_filter() {
+    let previous = OutPoint::new(packed::Byte32::new([9; 32]), 0);
+    let tx = TransactionBuilder::default()
+        .input(CellInput::new(previous.clone(), 0))
+        .output(output(script(1, b"out"), Some(script(2, b"type"))))
+        .output_data(Bytes::new().pack())
+        .build();
+
+    let filter_data = build_filter_data(&[tx.clone()], |out_point| {
+        assert_eq!(out_point, &previous);
+        Some(output(script(1, b"in"), None))
+    })
+    .unwrap();
+
+    for matched in &[script(1, b"in"), script(1, b"out"), script(2, b"type")] {
+        assert!(filter_matches_any(
+            &filter_data,
+            &[matched.calc_script_hash()]
+        ));
+    }
+    assert!(!filter_matches_any(
+        &filter_data,
+        &[script(3, b"absent").calc_script_hash()]
+    ));
+
+    let missing = build_filter_data(&[tx], |_| None).unwrap_err();
+    assert_eq!(missing, previous);
+}
+
+#[test]
+fn filter_hash_chains_parent() {
+    let parent = calc_filter_hash(&packed::Byte32::zero(), b"parent");
+    let hash = calc_filter_hash(&parent, b"data");
+    assert_ne!(hash, calc_filter_hash(&packed::Byte32::zero(), b"data"));
+    assert_eq!(hash, calc_filter_hash(&parent, b"data"));
+}
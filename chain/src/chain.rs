@@ -11,7 +11,7 @@ use ckb_metrics::metrics;
 use ckb_proposal_table::ProposalTable;
 #[cfg(debug_assertions)]
 use ckb_rust_unstable_port::IsSorted;
-use ckb_shared::shared::Shared;
+use ckb_shared::{shared::Shared, Snapshot};
 use ckb_stop_handler::{SignalSender, StopHandler};
 use ckb_store::{attach_block_cell, detach_block_cell, ChainStore, StoreTransaction};
 use ckb_types::{
@@ -21,7 +21,7 @@ use ckb_types::{
             ResolveOptions, ResolvedTransaction,
         },
         hardfork::HardForkSwitch,
-        service::{Request, DEFAULT_CHANNEL_SIZE, SIGNAL_CHANNEL_SIZE},
+        service::{ChainReorg, Request, DEFAULT_CHANNEL_SIZE, SIGNAL_CHANNEL_SIZE},
         BlockExt, BlockNumber, BlockView, HeaderView,
     },
     packed::{Byte32, ProposalShortId},
@@ -514,6 +514,9 @@ impl ChainService {
                     .new_snapshot(tip_header, total_difficulty, epoch, new_proposals);
 
             self.shared.store_snapshot(Arc::clone(&new_snapshot));
+            if fork.has_detached() {
+                self.notify_chain_reorg(&fork, &new_snapshot);
+            }
 
             let tx_pool_controller = self.shared.tx_pool_controller();
             if tx_pool_controller.service_started() {
@@ -558,6 +561,29 @@ impl ChainService {
         Ok(true)
     }
 
+    fn notify_chain_reorg(&self, fork: &ForkChanges, snapshot: &Snapshot) {
+        let ancestor = fork
+            .attached_blocks()
+            .front()
+            .and_then(|block| snapshot.get_block_header(&block.parent_hash()));
+        if let Some(ancestor) = ancestor {
+            let reorg = ChainReorg {
+                ancestor,
+                detached_blocks: fork
+                    .detached_blocks()
+                    .iter()
+                    .map(BlockView::header)
+                    .collect(),
+                attached_blocks: fork
+                    .attached_blocks()
+                    .iter()
+                    .map(BlockView::header)
+                    .collect(),
+            };
+            self.shared.notify_controller().notify_chain_reorg(reorg);
+        }
+    }
+
     pub(crate) fn update_proposal_table(&mut self, fork: &ForkChanges) {
         for blk in fork.detached_blocks() {
             self.proposal_table.remove(blk.header().number());
@@ -758,6 +784,7 @@ impl ChainService {
         for b in fork.attached_blocks().iter().take(verified_len) {
             txn.attach_block(b)?;
             attach_block_cell(txn, b)?;
+            txn.attach_header_digest(&b.header())?;
         }
 
         let verify_context = VerifyContext::new(txn, consensus);
@@ -820,6 +847,7 @@ impl ChainService {
                                         cache_entries.iter().map(|entry| entry.fee).collect();
                                     txn.attach_block(b)?;
                                     attach_block_cell(txn, b)?;
+                                    txn.attach_header_digest(&b.header())?;
                                     let mut mut_ext = ext.clone();
                                     mut_ext.verified = Some(true);
                                     mut_ext.txs_fees = txs_fees;
@@ -880,6 +908,7 @@ impl ChainService {
             } else {
                 txn.attach_block(b)?;
                 attach_block_cell(txn, b)?;
+                txn.attach_header_digest(&b.header())?;
                 let mut mut_ext = ext.clone();
                 mut_ext.verified = Some(true);
                 txn.insert_block_ext(&b.header().hash(), &mut_ext)?;
@@ -36,10 +36,13 @@ fn start_chain(consensus: Option<Consensus>) -> (ChainController, Shared) {
         message: Default::default(),
         use_binary_version_as_message_prefix: true,
         binary_version: "TEST".to_string(),
+        advanced: false,
         update_interval_millis: 800,
         notify: vec![],
         notify_scripts: vec![],
         notify_timeout_millis: 800,
+        payout_targets: vec![],
+        tx_selection: Default::default(),
     };
     let (shared, mut pack) = builder
         .block_assembler_config(Some(config))
@@ -0,0 +1,135 @@
+use crate::chain::ChainService;
+use crate::tests::util::{MockChain, MockStore};
+use ckb_chain_spec::consensus::{Consensus, ConsensusBuilder};
+use ckb_error::assert_error_eq;
+use ckb_launcher::SharedBuilder;
+use ckb_merkle_mountain_range::util::MemMMR;
+use ckb_shared::shared::Shared;
+use ckb_store::ChainStore;
+use ckb_types::{
+    core::{hardfork::HardForkSwitch, BlockView, HeaderView},
+    packed,
+    prelude::*,
+    utilities::MergeHeaderDigest,
+};
+use ckb_verification::BlockErrorKind;
+use ckb_verification_traits::Switch;
+use std::sync::Arc;
+
+fn genesis_header(shared: &Shared) -> HeaderView {
+    shared
+        .store()
+        .get_block_header(&shared.store().get_block_hash(0).unwrap())
+        .unwrap()
+}
+
+// Checks the chain root MMR of every block against the one built from the expected chain.
+fn assert_chain_root_mmr(shared: &Shared, blocks: &[BlockView]) {
+    let snapshot = shared.snapshot();
+    assert_eq!(snapshot.tip_number(), blocks.len() as u64);
+
+    let mut mmr = MemMMR::<packed::HeaderDigest, MergeHeaderDigest>::default();
+    let headers = Some(genesis_header(shared))
+        .into_iter()
+        .chain(blocks.iter().map(BlockView::header));
+    for header in headers {
+        mmr.push(header.digest()).unwrap();
+        let chain_root = snapshot.chain_root_mmr(header.number()).get_root().unwrap();
+        assert_eq!(chain_root, mmr.get_root().unwrap());
+    }
+}
+
+// 0--1--2--3--4
+//        \
+//         \
+//          3--4--5--6
+#[test]
+fn test_chain_root_mmr_after_reorg() {
+    let (shared, mut pack) = SharedBuilder::with_temp_db()
+        .consensus(Consensus::default())
+        .build()
+        .unwrap();
+    let mut chain_service = ChainService::new(shared.clone(), pack.take_proposal_table());
+
+    let genesis = genesis_header(&shared);
+    let mock_store = MockStore::new(&genesis, shared.store());
+    let mut fork1 = MockChain::new(genesis, shared.consensus());
+    for _ in 0..2 {
+        fork1.gen_empty_block_with_diff(100u64, &mock_store);
+    }
+    let mut fork2 = fork1.clone();
+    for _ in 0..2 {
+        fork1.gen_empty_block_with_diff(100u64, &mock_store);
+    }
+    for _ in 0..4 {
+        fork2.gen_empty_block_with_diff(90u64, &mock_store);
+    }
+
+    // fork1 total_difficulty 400
+    for blk in fork1.blocks() {
+        chain_service
+            .process_block(Arc::new(blk.clone()), Switch::DISABLE_ALL)
+            .unwrap();
+    }
+    assert_chain_root_mmr(&shared, fork1.blocks());
+
+    // fork2 total_difficulty 560, the digests of the blocks 3 and 4 are overwritten
+    for blk in fork2.blocks() {
+        chain_service
+            .process_block(Arc::new(blk.clone()), Switch::DISABLE_ALL)
+            .unwrap();
+    }
+    assert_chain_root_mmr(&shared, fork2.blocks());
+}
+
+#[test]
+fn test_chain_root_verifier() {
+    let hardfork_switch = HardForkSwitch::new_without_any_enabled()
+        .as_builder()
+        .rfc_0031(0)
+        .rfc_0044(0)
+        .build()
+        .unwrap();
+    let consensus = ConsensusBuilder::default()
+        .hardfork_switch(hardfork_switch)
+        .build();
+    let (shared, mut pack) = SharedBuilder::with_temp_db()
+        .consensus(consensus)
+        .build()
+        .unwrap();
+    let mut chain_service = ChainService::new(shared.clone(), pack.take_proposal_table());
+
+    let genesis = genesis_header(&shared);
+    let mock_store = MockStore::new(&genesis, shared.store());
+    let mut chain = MockChain::new(genesis, shared.consensus());
+    chain.gen_empty_block_with_diff(100u64, &mock_store);
+    let block = chain.tip().clone();
+    let with_extension = |extension: packed::Bytes| {
+        Arc::new(
+            block
+                .as_advanced_builder()
+                .extension(Some(extension))
+                .build(),
+        )
+    };
+
+    assert_error_eq!(
+        BlockErrorKind::NoBlockExtension,
+        chain_service
+            .process_block(Arc::new(block.clone()), Switch::ONLY_SCRIPT)
+            .unwrap_err(),
+    );
+    assert_error_eq!(
+        BlockErrorKind::InvalidChainRoot,
+        chain_service
+            .process_block(with_extension(vec![0u8; 32].pack()), Switch::ONLY_SCRIPT)
+            .unwrap_err(),
+    );
+
+    let chain_root = shared.snapshot().chain_root_mmr(0).get_root().unwrap();
+    let extension = chain_root.calc_mmr_hash().as_bytes().pack();
+    chain_service
+        .process_block(with_extension(extension), Switch::ONLY_SCRIPT)
+        .unwrap();
+    assert_eq!(shared.snapshot().tip_number(), 1);
+}
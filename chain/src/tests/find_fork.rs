@@ -13,6 +13,7 @@ use ckb_verification_traits::Switch;
 use faketime::unix_time_as_millis;
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 
 // 0--1--2--3--4
 // \
@@ -473,3 +474,57 @@ fn test_fork_proposal_table() {
         proposals.gap()
     );
 }
+
+// 0--1--2--3
+// \
+//  \
+//   1--2--3--4
+#[test]
+fn test_notify_chain_reorg() {
+    let builder = SharedBuilder::with_temp_db();
+    let (shared, mut pack) = builder.consensus(Consensus::default()).build().unwrap();
+    let mut chain_service = ChainService::new(shared.clone(), pack.take_proposal_table());
+    let reorg_receiver = shared.notify_controller().subscribe_chain_reorg("test");
+    let genesis = shared
+        .store()
+        .get_block_header(&shared.store().get_block_hash(0).unwrap())
+        .unwrap();
+
+    let mock_store = MockStore::new(&genesis, shared.store());
+    let mut fork1 = MockChain::new(genesis.clone(), shared.consensus());
+    let mut fork2 = MockChain::new(genesis.clone(), shared.consensus());
+    for _ in 0..3 {
+        fork1.gen_empty_block_with_diff(100u64, &mock_store);
+    }
+    for _ in 0..4 {
+        fork2.gen_empty_block_with_diff(90u64, &mock_store);
+    }
+
+    for blk in fork1.blocks().iter().chain(fork2.blocks().iter()) {
+        chain_service
+            .process_block(Arc::new(blk.clone()), Switch::DISABLE_ALL)
+            .unwrap();
+    }
+
+    let reorg = reorg_receiver
+        .recv_timeout(Duration::from_secs(5))
+        .expect("chain reorg notified");
+    assert_eq!(reorg.ancestor, genesis);
+    assert_eq!(
+        reorg.detached_blocks,
+        fork1
+            .blocks()
+            .iter()
+            .map(BlockView::header)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        reorg.attached_blocks,
+        fork2
+            .blocks()
+            .iter()
+            .map(BlockView::header)
+            .collect::<Vec<_>>()
+    );
+    assert!(reorg_receiver.try_recv().is_err());
+}
@@ -1,6 +1,7 @@
 mod basic;
 mod block_assembler;
 mod cell;
+mod chain_root;
 mod delay_verify;
 mod dep_cell;
 mod find_fork;
@@ -8,6 +9,7 @@ mod load_code_with_snapshot;
 mod load_input_cell_data;
 mod load_input_data_hash_cell;
 mod non_contextual_block_txs_verify;
+mod prune;
 mod reward;
 mod truncate;
 mod txs_verify_cache;
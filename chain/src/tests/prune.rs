@@ -0,0 +1,70 @@
+use crate::chain::ChainService;
+use crate::tests::util::{MockChain, MockStore};
+use ckb_chain_spec::consensus::Consensus;
+use ckb_launcher::SharedBuilder;
+use ckb_store::ChainStore;
+use ckb_verification_traits::Switch;
+use std::sync::Arc;
+
+// 0--1--...--5--6--...--30
+//             \
+//              6
+#[test]
+fn test_prune() {
+    let (shared, mut pack) = SharedBuilder::with_temp_db()
+        .consensus(Consensus::default())
+        .build()
+        .unwrap();
+    let mut chain_service = ChainService::new(shared.clone(), pack.take_proposal_table());
+
+    let genesis = shared
+        .store()
+        .get_block_header(&shared.store().get_block_hash(0).unwrap())
+        .unwrap();
+    let mock_store = MockStore::new(&genesis, shared.store());
+    let mut main = MockChain::new(genesis, shared.consensus());
+    for _ in 0..5 {
+        main.gen_empty_block_with_diff(100u64, &mock_store);
+    }
+    let mut side = main.clone();
+    side.gen_empty_block_with_diff(90u64, &mock_store);
+    for _ in 0..25 {
+        main.gen_empty_block_with_diff(100u64, &mock_store);
+    }
+    for blk in main.blocks().iter().chain(side.blocks()) {
+        chain_service
+            .process_block(Arc::new(blk.clone()), Switch::DISABLE_ALL)
+            .unwrap();
+    }
+    let side_block = side.tip().clone();
+    assert!(shared
+        .store()
+        .get_block_header(&side_block.hash())
+        .is_some());
+
+    // the blocks below 20 are pruned
+    shared.prune(10).unwrap();
+    let store = shared.store();
+    assert_eq!(store.get_pruned_number(), 20);
+    assert!(store.is_pruned(19));
+    assert!(!store.is_pruned(20));
+    for blk in &main.blocks()[..19] {
+        // the headers of the main chain blocks are kept
+        assert!(store.get_block_header(&blk.hash()).is_some());
+        assert!(store.get_block(&blk.hash()).is_none());
+        assert!(store
+            .get_transaction(&blk.transactions()[0].hash())
+            .is_none());
+    }
+    for blk in &main.blocks()[19..] {
+        assert_eq!(store.get_block(&blk.hash()).as_ref(), Some(blk));
+    }
+    // the side chain blocks are removed entirely, including the cached headers
+    assert!(store.get_block_header(&side_block.hash()).is_none());
+    // the genesis block is never pruned
+    assert!(store.get_block(&store.get_block_hash(0).unwrap()).is_some());
+
+    // nothing to prune until the chain grows
+    shared.prune(10).unwrap();
+    assert_eq!(store.get_pruned_number(), 20);
+}
@@ -167,10 +167,13 @@ fn start_chain(consensus: Consensus, lock_script: &packed::Script) -> (ChainCont
         message: Default::default(),
         use_binary_version_as_message_prefix: true,
         binary_version: "TEST".to_string(),
+        advanced: false,
         update_interval_millis: 800,
         notify: vec![],
         notify_scripts: vec![],
         notify_timeout_millis: 800,
+        payout_targets: vec![],
+        tx_selection: Default::default(),
     };
     let (shared, mut pack) = SharedBuilder::with_temp_db()
         .consensus(consensus)
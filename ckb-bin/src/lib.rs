@@ -74,6 +74,16 @@ pub fn run_app(version: Version) -> Result<(), ExitCode> {
         cli::CMD_RESET_DATA => subcommand::reset_data(setup.reset_data(matches)?),
         cli::CMD_MIGRATE => subcommand::migrate(setup.migrate(matches)?),
         cli::CMD_DB_REPAIR => subcommand::db_repair(setup.db_repair(matches)?),
+        cli::CMD_DB_CHECK => subcommand::db_check(setup.db_check(matches)?, handle),
+        cli::CMD_SNAPSHOT => match matches.subcommand() {
+            Some((cli::CMD_EXPORT, matches)) => {
+                subcommand::snapshot_export(setup.snapshot_export(matches)?, handle)
+            }
+            Some((cli::CMD_IMPORT, matches)) => {
+                subcommand::snapshot_import(setup.snapshot_import(matches)?, handle)
+            }
+            _ => unreachable!(),
+        },
         _ => unreachable!(),
     };
 
@@ -91,6 +101,8 @@ fn is_silent_logging(cmd: &str) -> Silent {
             | cli::CMD_STATS
             | cli::CMD_MIGRATE
             | cli::CMD_DB_REPAIR
+            | cli::CMD_DB_CHECK
             | cli::CMD_RESET_DATA
+            | cli::CMD_SNAPSHOT
     )
 }
@@ -0,0 +1,54 @@
+use ckb_app_config::{DbCheckArgs, ExitCode};
+use ckb_async_runtime::Handle;
+use ckb_launcher::SharedBuilder;
+use ckb_store::integrity::{check, CheckOptions};
+
+pub fn db_check(args: DbCheckArgs, async_handle: Handle) -> Result<(), ExitCode> {
+    let builder = SharedBuilder::new(
+        &args.config.bin_name,
+        args.config.root_dir.as_path(),
+        &args.config.db,
+        None,
+        async_handle,
+    )?;
+    let (shared, _) = builder.consensus(args.consensus).build()?;
+    let options = CheckOptions {
+        from: args.from.unwrap_or(0),
+        to: args.to,
+        check_cells: args.check_cells,
+    };
+    let report = check(shared.store(), &options).map_err(|err| {
+        eprintln!("db-check error: {}", err);
+        ExitCode::Failure
+    })?;
+
+    for finding in &report.findings {
+        println!("{}", finding);
+    }
+    println!(
+        "checked blocks from {} to {}{}, {} inconsistencies found, {} fixable",
+        report.from,
+        report.to,
+        if report.cells_checked {
+            " with the live cells"
+        } else {
+            ""
+        },
+        report.findings.len(),
+        report.fixable()
+    );
+
+    let mut remaining = report.findings.len();
+    if args.fix && report.fixable() > 0 {
+        let fixed = report.fix(shared.store()).map_err(|err| {
+            eprintln!("db-check fix error: {}", err);
+            ExitCode::Failure
+        })?;
+        println!("{} inconsistencies fixed", fixed);
+        remaining -= fixed;
+    }
+    if remaining > 0 {
+        return Err(ExitCode::Failure);
+    }
+    Ok(())
+}
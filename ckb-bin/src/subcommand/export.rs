@@ -1,6 +1,6 @@
 use ckb_app_config::{ExitCode, ExportArgs};
 use ckb_async_runtime::Handle;
-use ckb_instrument::Export;
+use ckb_instrument::{Compression, Export, Format};
 use ckb_launcher::SharedBuilder;
 
 pub fn export(args: ExportArgs, async_handle: Handle) -> Result<(), ExitCode> {
@@ -11,9 +11,18 @@ pub fn export(args: ExportArgs, async_handle: Handle) -> Result<(), ExitCode> {
         None,
         async_handle,
     )?;
+    let format = match args.format.as_str() {
+        "binary" => Format::Binary(args.compression.parse::<Compression>().map_err(|err| {
+            eprintln!("Export error: {}", err);
+            ExitCode::Cli
+        })?),
+        _ => Format::Json,
+    };
     let (shared, _) = builder.consensus(args.consensus).build()?;
-    Export::new(shared, args.target).execute().map_err(|err| {
-        eprintln!("Export error: {:?}", err);
-        ExitCode::Failure
-    })
+    Export::new(shared, args.target, format, args.from.unwrap_or(0), args.to)
+        .execute()
+        .map_err(|err| {
+            eprintln!("Export error: {}", err);
+            ExitCode::Failure
+        })
 }
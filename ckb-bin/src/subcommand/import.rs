@@ -14,17 +14,17 @@ pub fn import(args: ImportArgs, async_handle: Handle) -> Result<(), ExitCode> {
     )?;
     let (shared, mut pack) = builder.consensus(args.consensus).build()?;
 
-    let chain_service = ChainService::new(shared, pack.take_proposal_table());
+    let chain_service = ChainService::new(shared.clone(), pack.take_proposal_table());
     let chain_controller = chain_service.start::<&str>(Some("ImportChainService"));
 
     // manual drop tx_pool_builder and relay_tx_receiver
     pack.take_tx_pool_builder();
     pack.take_relay_tx_receiver();
 
-    Import::new(chain_controller, args.source)
+    Import::new(chain_controller, shared, args.source)
         .execute()
         .map_err(|err| {
-            eprintln!("Import error: {:?}", err);
+            eprintln!("Import error: {}", err);
             ExitCode::Failure
         })
 }
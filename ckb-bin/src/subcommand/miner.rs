@@ -7,7 +7,11 @@ use std::thread;
 
 pub fn miner(args: MinerArgs, async_handle: Handle) -> Result<(), ExitCode> {
     let (new_work_tx, new_work_rx) = unbounded();
-    let MinerConfig { client, workers } = args.config;
+    let MinerConfig {
+        client,
+        workers,
+        stratum,
+    } = args.config;
     let exit_handler = DefaultExitHandler::default();
 
     let client = Client::new(new_work_tx, client, async_handle);
@@ -16,6 +20,7 @@ pub fn miner(args: MinerArgs, async_handle: Handle) -> Result<(), ExitCode> {
         client.clone(),
         new_work_rx,
         &workers,
+        stratum.as_ref(),
         args.limit,
     );
 
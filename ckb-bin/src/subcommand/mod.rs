@@ -1,3 +1,4 @@
+mod db_check;
 mod db_repair;
 mod export;
 mod import;
@@ -9,8 +10,10 @@ mod peer_id;
 mod replay;
 mod reset_data;
 mod run;
+mod snapshot;
 mod stats;
 
+pub use self::db_check::db_check;
 pub use self::db_repair::db_repair;
 pub use self::export::export;
 pub use self::import::import;
@@ -22,4 +25,5 @@ pub use self::peer_id::peer_id;
 pub use self::replay::replay;
 pub use self::reset_data::reset_data;
 pub use self::run::run;
+pub use self::snapshot::{snapshot_export, snapshot_import};
 pub use self::stats::stats;
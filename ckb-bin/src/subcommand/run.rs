@@ -43,12 +43,20 @@ pub fn run(args: RunArgs, version: Version, async_handle: Handle) -> Result<(),
 
     let chain_controller = launcher.start_chain_service(&shared, pack.take_proposal_table());
 
+    let indexer_controller = launcher.start_indexer(&shared);
+
+    let block_filter_controller = launcher.start_block_filter(&shared);
+
+    let snapshot_validation = launcher.start_snapshot_validation(&shared)?;
+
     let (network_controller, rpc_server) = launcher.start_network_and_rpc(
         &shared,
         chain_controller.non_owning_clone(),
         &exit_handler,
         miner_enable,
         pack.take_relay_tx_receiver(),
+        indexer_controller.as_ref().map(|indexer| indexer.handle()),
+        snapshot_validation,
     );
 
     let tx_pool_builder = pack.take_tx_pool_builder();
@@ -68,6 +76,8 @@ pub fn run(args: RunArgs, version: Version, async_handle: Handle) -> Result<(),
     })?;
 
     drop(rpc_server);
+    drop(indexer_controller);
+    drop(block_filter_controller);
     drop(network_controller);
     drop(chain_controller);
     Ok(())
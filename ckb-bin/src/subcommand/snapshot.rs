@@ -0,0 +1,58 @@
+use ckb_app_config::{ExitCode, SnapshotExportArgs, SnapshotImportArgs};
+use ckb_async_runtime::Handle;
+use ckb_instrument::{SnapshotExport, SnapshotImport};
+use ckb_launcher::SharedBuilder;
+
+pub fn snapshot_export(args: SnapshotExportArgs, async_handle: Handle) -> Result<(), ExitCode> {
+    let builder = SharedBuilder::new(
+        &args.config.bin_name,
+        args.config.root_dir.as_path(),
+        &args.config.db,
+        Some(args.config.ancient.clone()),
+        async_handle,
+    )?;
+    // the frozen block bodies are required to export the recent blocks or to roll back
+    let (shared, _) = builder
+        .consensus(args.consensus)
+        .store_config(args.config.store)
+        .build()?;
+    let hash = SnapshotExport::new(shared, args.target, args.number)
+        .execute()
+        .map_err(|err| {
+            eprintln!("Snapshot export error: {}", err);
+            ExitCode::Failure
+        })?;
+    println!("snapshot hash: {:#x}", hash);
+    Ok(())
+}
+
+pub fn snapshot_import(args: SnapshotImportArgs, async_handle: Handle) -> Result<(), ExitCode> {
+    let hash = args.hash.ok_or_else(|| {
+        eprintln!(
+            "Snapshot import error: the snapshot hash is not pinned, \
+             specify it by `--hash` or `snapshot_hash` in [chain] of ckb.toml"
+        );
+        ExitCode::Cli
+    })?;
+    let builder = SharedBuilder::new(
+        &args.config.bin_name,
+        args.config.root_dir.as_path(),
+        &args.config.db,
+        None,
+        async_handle,
+    )?;
+    let (shared, mut pack) = builder.consensus(args.consensus).build()?;
+
+    // manual drop tx_pool_builder and relay_tx_receiver
+    pack.take_tx_pool_builder();
+    pack.take_relay_tx_receiver();
+
+    SnapshotImport::new(shared, args.source, hash)
+        .execute()
+        .map_err(|err| {
+            eprintln!("Snapshot import error: {}", err);
+            ExitCode::Failure
+        })?;
+    println!("snapshot imported, the node syncs from the snapshot block on the next run");
+    Ok(())
+}
@@ -3,7 +3,7 @@
 /// Column families alias type
 pub type Col = &'static str;
 /// Total column number
-pub const COLUMNS: u32 = 16;
+pub const COLUMNS: u32 = 19;
 /// Column store chain index
 pub const COLUMN_INDEX: Col = "0";
 /// Column store block's header
@@ -38,11 +38,26 @@ pub const COLUMN_NUMBER_HASH: Col = "13";
 pub const COLUMN_CELL_DATA_HASH: Col = "14";
 /// Column store block extension data
 pub const COLUMN_BLOCK_EXTENSION: Col = "15";
+/// Column store block filter data
+pub const COLUMN_BLOCK_FILTER: Col = "16";
+/// Column store block filter hash, which commits to the filter hash of the parent block
+pub const COLUMN_BLOCK_FILTER_HASH: Col = "17";
+/// Column store the nodes of the chain root MMR, keyed by the position in big endian
+pub const COLUMN_CHAIN_ROOT_MMR: Col = "18";
 
 /// META_TIP_HEADER_KEY tracks the latest known best block header
 pub const META_TIP_HEADER_KEY: &[u8] = b"TIP_HEADER";
 /// META_CURRENT_EPOCH_KEY tracks the latest known epoch
 pub const META_CURRENT_EPOCH_KEY: &[u8] = b"CURRENT_EPOCH";
+/// META_LATEST_BUILT_FILTER_DATA_KEY tracks the latest block which the filter data is built for
+pub const META_LATEST_BUILT_FILTER_DATA_KEY: &[u8] = b"LATEST_BUILT_FILTER_DATA";
+/// META_PRUNED_NUMBER_KEY tracks the first main chain block whose body is not pruned
+pub const META_PRUNED_NUMBER_KEY: &[u8] = b"PRUNED_NUMBER";
+/// META_SNAPSHOT_KEY tracks the imported snapshot whose history is not validated yet, the value is
+/// the snapshot block hash followed by the hash of the live cell set at the block
+pub const META_SNAPSHOT_KEY: &[u8] = b"SNAPSHOT";
+/// META_INVALID_SNAPSHOT_KEY tracks the imported snapshot whose history fails the validation
+pub const META_INVALID_SNAPSHOT_KEY: &[u8] = b"INVALID_SNAPSHOT";
 
 /// CHAIN_SPEC_HASH_KEY tracks the hash of chain spec which created current database
 pub const CHAIN_SPEC_HASH_KEY: &[u8] = b"chain-spec-hash";
@@ -24,6 +24,12 @@ CKB JSON-RPC only supports HTTP now. If you need SSL, please set up a proxy via
 
 Subscriptions require a full duplex connection. CKB offers such connections in the form of TCP (enable with `rpc.tcp_listen_address` configuration option) and WebSockets (enable with `rpc.ws_listen_address`).
 
+## Authentication
+
+The RPC requests are not authenticated by default. When any credential is configured in `rpc.credentials`, every request must carry one of them, and can only call the methods in the modules or the methods allowed by the credential. Otherwise the request fails with the error [`Unauthorized`](#error-unauthorized) or [`RPCMethodIsNotAllowed`](#error-rpcmethodisnotallowed).
+
+HTTP requests carry the credential in the `Authorization` header, either `Bearer <token>` or `Basic <base64(username:password)>`. TCP and WebSocket connections call the method `auth` with the same value as the only param once, for example, `{"id": 1, "jsonrpc": "2.0", "method": "auth", "params": ["Bearer <token>"]}`. The method `ping` is always allowed.
+
 ## JSONRPC Deprecation Process
 
 A CKB RPC method is deprecated in three steps.
@@ -740,6 +746,7 @@ class RPCDoc(object):
         # Referenced by subscription RPC.
         self.collect_type('ckb_jsonrpc_types/struct.PoolTransactionEntry.html')
         self.collect_type('ckb_jsonrpc_types/enum.PoolTransactionReject.html')
+        self.collect_type('ckb_jsonrpc_types/struct.ChainReorg.html')
         # Referenced by RawTxPool
         self.collect_type('ckb_jsonrpc_types/struct.TxPoolIds.html')
         self.collect_type('ckb_jsonrpc_types/struct.TxPoolEntries.html')
@@ -47,6 +47,9 @@ pub enum InternalErrorKind {
     /// The feature is disabled or is conflicted with the configuration
     Config,
 
+    /// Error occurs when operating the chain root MMR
+    MMR,
+
     /// Other system error
     Other,
 }
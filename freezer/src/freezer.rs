@@ -144,6 +144,26 @@ impl Freezer {
         self.number.load(Ordering::SeqCst)
     }
 
+    /// Prune discards the data files which only contain the blocks below the provided number,
+    /// returns the number of the first block kept in the freezer.
+    pub fn prune(&self, number: BlockNumber) -> Result<BlockNumber, Error> {
+        self.inner
+            .lock()
+            .files
+            .prune(number)
+            .map_err(internal_error)
+    }
+
+    /// Cross checks the index and the data files of the freezer, returns the descriptions of the
+    /// inconsistencies found.
+    pub fn check_index(&self) -> Result<Vec<String>, Error> {
+        self.inner
+            .lock()
+            .files
+            .check_index()
+            .map_err(internal_error)
+    }
+
     /// Truncate discards any recent data above the provided threshold number.
     pub fn truncate(&self, item: u64) -> Result<(), Error> {
         if item > 0 && ((item + 1) < self.number()) {
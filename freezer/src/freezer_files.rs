@@ -2,9 +2,10 @@ use ckb_metrics::metrics;
 use fail::fail_point;
 use lru::LruCache;
 use snap::raw::{Decoder as SnappyDecoder, Encoder as SnappyEncoder};
+use std::collections::HashMap;
 use std::fs::{self, File};
+use std::io::{BufReader, Read, Write};
 use std::io::{Error as IoError, ErrorKind as IoErrorKind};
-use std::io::{Read, Write};
 use std::io::{Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -189,6 +190,10 @@ impl FreezerFiles {
 
         let bounds = self.get_bounds(item)?;
         if let Some((start_offset, end_offset, file_id)) = bounds {
+            // the item is pruned
+            if file_id < self.tail_id {
+                return Ok(None);
+            }
             let open_read_only;
 
             let mut file = if let Some(file) = self.files.get(&file_id) {
@@ -299,6 +304,116 @@ impl FreezerFiles {
         Ok(())
     }
 
+    /// Deletes the data files which only contain the items below the provided number, returns the
+    /// first item kept in the files.
+    ///
+    /// The head file is never deleted, and the index is kept so the item numbers stay unchanged.
+    pub fn prune(&mut self, item: u64) -> Result<u64, IoError> {
+        let number = self.number();
+        if number <= 1 {
+            return Ok(1);
+        }
+        let item = item.clamp(1, number - 1);
+        let file_id = self.read_index(item)?.file_id;
+        if file_id > self.tail_id {
+            ckb_logger::trace!("Freezer prune files {}..{}", self.tail_id, file_id);
+            // the tail is persisted in the first entry, which is never used as a bound, before
+            // the files are deleted, so the freezer can be reopened after a crash.
+            let tail = IndexEntry { file_id, offset: 0 };
+            self.index.seek(SeekFrom::Start(0))?;
+            self.index.write_all(&tail.encode())?;
+            self.index.sync_all()?;
+
+            let pruned = self.tail_id..file_id;
+            for id in pruned.clone() {
+                self.release(id);
+            }
+            self.tail_id = file_id;
+            self.delete_files_by_id(pruned)?;
+        }
+        self.first_item_in(self.tail_id)
+    }
+
+    /// Finds the first item stored in the file by binary search, the file ids in the index are
+    /// non-decreasing.
+    fn first_item_in(&self, file_id: FileId) -> Result<u64, IoError> {
+        let (mut low, mut high) = (1, self.number());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.read_index(mid)?.file_id < file_id {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Cross checks the index and the data files, returns the descriptions of the
+    /// inconsistencies found.
+    pub fn check_index(&self) -> Result<Vec<String>, IoError> {
+        let mut issues = Vec::new();
+        let mut sizes = HashMap::new();
+        for id in self.tail_id..=self.head_id {
+            let name = helper::file_name(id);
+            match fs::metadata(self.file_path.join(&name)) {
+                Ok(metadata) => {
+                    sizes.insert(id, metadata.len());
+                }
+                Err(e) if e.kind() == IoErrorKind::NotFound => {
+                    issues.push(format!("data file {} is missing", name));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut index = &self.index;
+        index.seek(SeekFrom::Start(INDEX_ENTRY_SIZE))?;
+        let mut reader = BufReader::new(index);
+        let mut buffer = [0; INDEX_ENTRY_SIZE as usize];
+        let mut prev: Option<IndexEntry> = None;
+        for item in 1..self.number() {
+            reader.read_exact(&mut buffer)?;
+            let entry = IndexEntry::decode(&buffer)?;
+            if let Some(prev) = prev {
+                if entry.file_id < prev.file_id {
+                    issues.push(format!(
+                        "item {} is in file {} but the previous item is in file {}",
+                        item, entry.file_id, prev.file_id
+                    ));
+                } else if entry.file_id == prev.file_id && entry.offset < prev.offset {
+                    issues.push(format!(
+                        "item {} ends at offset {} before the previous item ends at {}",
+                        item, entry.offset, prev.offset
+                    ));
+                }
+            }
+            if entry.file_id > self.head_id {
+                issues.push(format!(
+                    "item {} is in file {} after the head file {}",
+                    item, entry.file_id, self.head_id
+                ));
+            } else if let Some(size) = sizes.get(&entry.file_id) {
+                if entry.offset > *size {
+                    issues.push(format!(
+                        "item {} ends at offset {} beyond the size {} of file {}",
+                        item, entry.offset, size, entry.file_id
+                    ));
+                }
+            }
+            prev = Some(entry);
+        }
+        Ok(issues)
+    }
+
+    fn read_index(&self, item: u64) -> Result<IndexEntry, IoError> {
+        let mut buffer = [0; INDEX_ENTRY_SIZE as usize];
+        let mut index = &self.index;
+        index.seek(SeekFrom::Start(item * INDEX_ENTRY_SIZE))?;
+        index.read_exact(&mut buffer)?;
+        IndexEntry::decode(&buffer)
+    }
+
     /// Attempts to open files, initialize fd map
     pub fn preopen(&mut self) -> Result<(), IoError> {
         self.release_all();
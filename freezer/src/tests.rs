@@ -256,6 +256,91 @@ fn truncate() {
     assert_eq!(None, retrieve_out_of_bound);
 }
 
+#[test]
+fn prune() {
+    let tempdir = tempfile::Builder::new().tempdir().unwrap();
+    {
+        let mut freezer = FreezerFilesBuilder::new(tempdir.path().to_path_buf())
+            .enable_compression(false)
+            .max_file_size(50)
+            .build()
+            .unwrap();
+        freezer.preopen().unwrap();
+        for i in 1..30 {
+            let data = make_bytes(15, i);
+            freezer.append(i.into(), &data).unwrap();
+        }
+
+        // each file contains 3 items, item 10 is the first one in the file 3
+        assert_eq!(freezer.prune(11).unwrap(), 10);
+        assert_eq!(freezer.tail_id, 3);
+        assert_eq!(freezer.prune(5).unwrap(), 10);
+        assert_eq!(freezer.number(), 30);
+    }
+
+    let mut freezer = FreezerFilesBuilder::new(tempdir.path().to_path_buf())
+        .enable_compression(false)
+        .max_file_size(50)
+        .build()
+        .unwrap();
+    freezer.preopen().unwrap();
+    assert_eq!(freezer.tail_id, 3);
+    for i in 1..10 {
+        assert_eq!(None, freezer.retrieve(i).unwrap());
+    }
+    for i in 10..30 {
+        let expect = make_bytes(15, i);
+        let actual = freezer.retrieve(i.into()).unwrap();
+        assert_eq!(Some(expect), actual);
+    }
+
+    // the head file is never deleted
+    assert_eq!(freezer.prune(100).unwrap(), 28);
+    let data = make_bytes(15, 30);
+    freezer.append(30, &data).unwrap();
+    assert_eq!(Some(data), freezer.retrieve(30).unwrap());
+}
+
+#[test]
+fn check_index() {
+    let tempdir = tempfile::Builder::new().tempdir().unwrap();
+    let mut freezer = FreezerFilesBuilder::new(tempdir.path().to_path_buf())
+        .enable_compression(false)
+        .max_file_size(50)
+        .build()
+        .unwrap();
+    freezer.preopen().unwrap();
+    for i in 1..30 {
+        let data = make_bytes(15, i);
+        freezer.append(i.into(), &data).unwrap();
+    }
+    assert!(freezer.check_index().unwrap().is_empty());
+
+    // the pruned files are not checked
+    freezer.prune(11).unwrap();
+    assert!(freezer.check_index().unwrap().is_empty());
+
+    // items 16, 17 and 18 are in the file 5
+    let file = std::fs::OpenOptions::new()
+        .write(true)
+        .open(tempdir.path().join("blk000005"))
+        .unwrap();
+    file.set_len(20).unwrap();
+    std::fs::remove_file(tempdir.path().join("blk000006")).unwrap();
+
+    let issues = freezer.check_index().unwrap();
+    assert_eq!(issues.len(), 3);
+    assert_eq!(issues[0], "data file blk000006 is missing");
+    assert_eq!(
+        issues[1],
+        "item 17 ends at offset 30 beyond the size 20 of file 5"
+    );
+    assert_eq!(
+        issues[2],
+        "item 18 ends at offset 45 beyond the size 20 of file 5"
+    );
+}
+
 #[test]
 fn open_files_limit_retrieve() {
     let tempdir = tempfile::Builder::new().tempdir().unwrap();
@@ -1,3 +1,4 @@
+use crate::stats::MinerStats;
 use crate::Work;
 use ckb_app_config::MinerClientConfig;
 use ckb_async_runtime::Handle;
@@ -149,7 +150,8 @@ pub struct Client {
     pub config: MinerClientConfig,
     /// TODO(doc): @quake
     pub rpc: Rpc,
-    handle: Handle,
+    pub(crate) handle: Handle,
+    pub(crate) stats: Arc<MinerStats>,
 }
 
 impl Client {
@@ -163,6 +165,7 @@ impl Client {
             new_work_tx,
             config,
             handle,
+            stats: Arc::new(MinerStats::default()),
         }
     }
 
@@ -185,13 +188,22 @@ impl Client {
             .and_then(parse_response::<H256>);
 
         if self.config.block_on_submit {
-            self.handle.block_on(future).map(|_| ())
+            let result = self.handle.block_on(future).map(|_| ());
+            match result {
+                Ok(_) => self.stats.accept(),
+                Err(_) => self.stats.reject(),
+            }
+            result
         } else {
             let sender = self.new_work_tx.clone();
+            let stats = Arc::clone(&self.stats);
             self.handle.spawn(async move {
                 if let Err(e) = future.await {
                     error!("rpc call submit_block error: {:?}", e);
+                    stats.reject();
                     sender.send(Works::FailSubmit(parent)).unwrap()
+                } else {
+                    stats.accept();
                 }
             });
             Ok(())
@@ -316,6 +328,14 @@ impl Client {
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
 async fn handle(client: Client, req: Request<Body>) -> Result<Response<Body>, Error> {
+    if req.method() == Method::GET && req.uri().path() == "/stats" {
+        let stats = serde_json::to_vec(&client.stats.snapshot())?;
+        let response = Response::builder()
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .body(Body::from(stats))?;
+        return Ok(response);
+    }
+
     let body = hyper::body::aggregate(req).await?;
 
     if let Ok(template) = serde_json::from_reader(body.reader()) {
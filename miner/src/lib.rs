@@ -1,8 +1,13 @@
 //! TODO(doc): @quake
 mod client;
 mod miner;
+mod stats;
+mod stratum;
 mod worker;
 
+#[cfg(test)]
+mod tests;
+
 pub use crate::client::Client;
 pub use crate::miner::Miner;
 
@@ -1,7 +1,8 @@
 use crate::client::{Client, Works};
+use crate::stratum::{start_stratum, StratumController};
 use crate::worker::{start_worker, WorkerController, WorkerMessage};
 use crate::Work;
-use ckb_app_config::MinerWorkerConfig;
+use ckb_app_config::{MinerStratumConfig, MinerWorkerConfig};
 use ckb_channel::{select, unbounded, Receiver};
 use ckb_logger::{debug, error, info};
 use ckb_pow::PowEngine;
@@ -25,6 +26,7 @@ pub struct Miner {
     /// Tasks's parent's hash that have already been submitted
     pub(crate) legacy_work: LruCache<Byte32, ()>,
     pub(crate) worker_controllers: Vec<WorkerController>,
+    pub(crate) stratum: Option<StratumController>,
     pub(crate) work_rx: Receiver<Works>,
     pub(crate) nonce_rx: Receiver<(Byte32, Work, u128)>,
     pub(crate) stop_rx: Receiver<()>,
@@ -41,6 +43,7 @@ impl Miner {
         client: Client,
         work_rx: Receiver<Works>,
         workers: &[MinerWorkerConfig],
+        stratum: Option<&MinerStratumConfig>,
         limit: u128,
     ) -> (Miner, StopHandler<()>) {
         let (nonce_tx, nonce_rx) = unbounded();
@@ -49,8 +52,19 @@ impl Miner {
 
         let worker_controllers = workers
             .iter()
-            .map(|config| start_worker(Arc::clone(&pow), config, nonce_tx.clone(), &mp))
+            .map(|config| {
+                start_worker(
+                    Arc::clone(&pow),
+                    config,
+                    nonce_tx.clone(),
+                    &mp,
+                    &client.stats,
+                )
+            })
             .collect();
+        let stratum = stratum.map(|config| {
+            start_stratum(config, Arc::clone(&pow), nonce_tx.clone(), &client.handle)
+        });
 
         let pb = mp.add(ProgressBar::new(100));
         pb.set_style(ProgressStyle::default_bar().template("{msg:.green}"));
@@ -69,6 +83,7 @@ impl Miner {
             _pow: pow,
             client,
             worker_controllers,
+            stratum,
             work_rx,
             nonce_rx,
             stop_rx,
@@ -152,6 +167,7 @@ impl Miner {
                 pow_hash,
                 block.header()
             );
+            self.client.stats.stale();
             self.notify_workers(WorkerMessage::Start);
             return;
         } else {
@@ -198,5 +214,8 @@ impl Miner {
         for controller in self.worker_controllers.iter() {
             controller.send_message(message.clone());
         }
+        if let Some(ref stratum) = self.stratum {
+            stratum.send_message(message);
+        }
     }
 }
@@ -0,0 +1,123 @@
+//! The statistics of the miner, which are served as JSON by the `listen` endpoint.
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// The hash counters of a worker thread.
+#[derive(Debug)]
+pub struct ThreadStats {
+    name: String,
+    hashes: AtomicU64,
+    // The bits of the `f64` hash rate, measured over the last report interval.
+    hash_rate: AtomicU64,
+}
+
+impl ThreadStats {
+    /// Records the hashes computed in `secs` seconds.
+    pub fn report(&self, hashes: u64, secs: f64) {
+        self.hashes.fetch_add(hashes, Ordering::Relaxed);
+        let hash_rate = if secs > 0.0 {
+            hashes as f64 / secs
+        } else {
+            0.0
+        };
+        self.hash_rate.store(hash_rate.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Resets the hash rate when the thread is idle.
+    pub fn reset_hash_rate(&self) {
+        self.hash_rate.store(0f64.to_bits(), Ordering::Relaxed);
+    }
+
+    fn hash_rate(&self) -> f64 {
+        f64::from_bits(self.hash_rate.load(Ordering::Relaxed))
+    }
+}
+
+/// The statistics shared by the workers, the miner and the client.
+#[derive(Debug, Default)]
+pub struct MinerStats {
+    threads: RwLock<Vec<Arc<ThreadStats>>>,
+    accepted: AtomicU64,
+    rejected: AtomicU64,
+    stale: AtomicU64,
+}
+
+impl MinerStats {
+    /// Registers a worker thread and returns its counters.
+    pub fn register_thread(&self, name: String) -> Arc<ThreadStats> {
+        let stats = Arc::new(ThreadStats {
+            name,
+            hashes: AtomicU64::new(0),
+            hash_rate: AtomicU64::new(0f64.to_bits()),
+        });
+        self.threads
+            .write()
+            .expect("acquire lock")
+            .push(Arc::clone(&stats));
+        stats
+    }
+
+    /// Counts a solution accepted by the node.
+    pub fn accept(&self) {
+        self.accepted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a solution rejected by the node.
+    pub fn reject(&self) {
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a solution found for the work whose parent has already been solved.
+    pub fn stale(&self) {
+        self.stale.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Takes a snapshot of the statistics.
+    pub fn snapshot(&self) -> MinerStatsSnapshot {
+        let threads: Vec<ThreadStatsSnapshot> = self
+            .threads
+            .read()
+            .expect("acquire lock")
+            .iter()
+            .map(|stats| ThreadStatsSnapshot {
+                name: stats.name.clone(),
+                hashes: stats.hashes.load(Ordering::Relaxed),
+                hash_rate: stats.hash_rate(),
+            })
+            .collect();
+        MinerStatsSnapshot {
+            hash_rate: threads.iter().map(|stats| stats.hash_rate).sum(),
+            threads,
+            accepted: self.accepted.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            stale: self.stale.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The snapshot of the hash counters of a worker thread.
+#[derive(Debug, Serialize)]
+pub struct ThreadStatsSnapshot {
+    /// The thread name.
+    pub name: String,
+    /// The hashes computed since the thread started.
+    pub hashes: u64,
+    /// The hashes per second.
+    pub hash_rate: f64,
+}
+
+/// The snapshot of the miner statistics.
+#[derive(Debug, Serialize)]
+pub struct MinerStatsSnapshot {
+    /// The total hashes per second of all the threads.
+    pub hash_rate: f64,
+    /// The statistics of each thread.
+    pub threads: Vec<ThreadStatsSnapshot>,
+    /// The solutions accepted by the node.
+    pub accepted: u64,
+    /// The solutions rejected by the node.
+    pub rejected: u64,
+    /// The solutions found for the stale work.
+    pub stale: u64,
+}
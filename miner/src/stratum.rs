@@ -0,0 +1,609 @@
+//! A Stratum v1 compatible mining server.
+//!
+//! The server serves the work fed by the block templates to the remote miners, and sends the
+//! shares which solve the block back to the miner, which submits the blocks.
+//!
+//! The messages are newline-delimited JSON-RPC, the methods are:
+//!
+//! - `mining.subscribe`, returns `[null, extranonce1, extranonce2_size]`;
+//! - `mining.authorize`, with the params `[worker_name, password]`;
+//! - `mining.submit`, with the params `[worker_name, job_id, extranonce2]`;
+//! - `mining.set_target` notification, with the params `[target]`;
+//! - `mining.notify` notification, with the params
+//!   `[job_id, pow_hash, block_number, parent_hash, clean_jobs]`.
+//!
+//! Each connection is assigned a unique extranonce1, so the workers search in the disjoint nonce
+//! ranges. The nonce is the concatenation of extranonce1 and extranonce2 as a big endian `u128`.
+use crate::worker::WorkerMessage;
+use crate::Work;
+use ckb_app_config::MinerStratumConfig;
+use ckb_async_runtime::Handle;
+use ckb_channel::Sender;
+use ckb_logger::{debug, error, info};
+use ckb_pow::PowEngine;
+use ckb_types::{packed::Byte32, U256};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, watch};
+use tokio_util::codec::{FramedRead, LinesCodec};
+
+pub(crate) const EXTRANONCE1_SIZE: usize = 4;
+pub(crate) const EXTRANONCE2_SIZE: usize = 12;
+/// The number of the recent jobs of the same block number which accept the shares.
+pub(crate) const MAX_JOBS: usize = 8;
+pub(crate) const MIN_DIFFICULTY: u64 = 1;
+/// The share difficulty is adjusted by at most 4 times each time.
+pub(crate) const MAX_RETARGET_RATIO: f64 = 4.0;
+pub(crate) const RETARGET_INTERVAL: Duration = Duration::from_secs(60);
+const STATS_INTERVAL: Duration = Duration::from_secs(60);
+/// The connection is closed once a request line exceeds the length.
+const MAX_LINE_LENGTH: usize = 16 * 1024;
+
+pub(crate) const ERR_UNKNOWN: i64 = 20;
+pub(crate) const ERR_JOB_NOT_FOUND: i64 = 21;
+pub(crate) const ERR_DUPLICATE_SHARE: i64 = 22;
+pub(crate) const ERR_LOW_DIFFICULTY: i64 = 23;
+pub(crate) const ERR_UNAUTHORIZED: i64 = 24;
+pub(crate) const ERR_NOT_SUBSCRIBED: i64 = 25;
+
+/// A job sent to the workers.
+pub(crate) struct Job {
+    pub(crate) id: String,
+    pub(crate) pow_hash: Byte32,
+    pub(crate) work: Work,
+    /// The block target.
+    pub(crate) target: U256,
+}
+
+impl Job {
+    fn notify(&self, clean_jobs: bool) -> String {
+        let header = self.work.block.header().into_view();
+        notification(
+            "mining.notify",
+            json!([
+                self.id,
+                format!("{:x}", self.pow_hash),
+                header.number(),
+                format!("{:x}", header.parent_hash()),
+                clean_jobs,
+            ]),
+        )
+    }
+}
+
+/// The jobs which accept the shares.
+#[derive(Default)]
+struct Jobs {
+    recent: VecDeque<Arc<Job>>,
+}
+
+impl Jobs {
+    /// Adds the job and returns whether the older jobs are cleaned.
+    fn push(&mut self, job: Arc<Job>) -> bool {
+        let parent_hash = job.work.block.header().raw().parent_hash();
+        let clean_jobs = self
+            .recent
+            .back()
+            .map(|last| last.work.block.header().raw().parent_hash() != parent_hash)
+            .unwrap_or(true);
+        if clean_jobs {
+            self.recent.clear();
+        }
+        if self.recent.len() >= MAX_JOBS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(job);
+        clean_jobs
+    }
+
+    fn get(&self, id: &str) -> Option<Arc<Job>> {
+        self.recent.iter().find(|job| job.id == id).cloned()
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.recent.iter().any(|job| job.id == id)
+    }
+
+    fn latest(&self) -> Option<Arc<Job>> {
+        self.recent.back().cloned()
+    }
+}
+
+/// The share statistics of a worker.
+struct WorkerStats {
+    difficulty: u64,
+    accepted: u64,
+    rejected: u64,
+    /// The expected number of the hashes to find the accepted shares.
+    hashes: f64,
+}
+
+pub(crate) struct Shared {
+    pow: Arc<dyn PowEngine>,
+    nonce_tx: Sender<(Byte32, Work, u128)>,
+    jobs: Mutex<Jobs>,
+    /// Sends the new jobs and whether the older jobs are cleaned to the connections.
+    ///
+    /// Unlike a watch channel, every job is delivered, so a cleaning job is never overwritten by
+    /// the following one before the connection sees it.
+    job_tx: broadcast::Sender<(Arc<Job>, bool)>,
+    stats: Mutex<HashMap<String, WorkerStats>>,
+    next_extranonce1: AtomicU32,
+    difficulty: u64,
+    share_interval: Duration,
+}
+
+/// Publishes the new work to the Stratum server, which stops once the controller is dropped.
+pub struct StratumController {
+    shared: Arc<Shared>,
+    // The server stops once it is dropped.
+    _stop_tx: watch::Sender<()>,
+    next_job_id: AtomicU64,
+}
+
+impl StratumController {
+    pub(crate) fn send_message(&self, message: WorkerMessage) {
+        // The remote workers keep working on the current job until they are notified a new one.
+        if let WorkerMessage::NewWork {
+            pow_hash,
+            work,
+            target,
+        } = message
+        {
+            let job = Arc::new(Job {
+                id: format!("{:x}", self.next_job_id.fetch_add(1, Ordering::SeqCst)),
+                pow_hash,
+                work,
+                target,
+            });
+            self.shared.publish(job);
+        }
+    }
+}
+
+/// Starts the Stratum server in the background.
+pub fn start_stratum(
+    config: &MinerStratumConfig,
+    pow: Arc<dyn PowEngine>,
+    nonce_tx: Sender<(Byte32, Work, u128)>,
+    handle: &Handle,
+) -> StratumController {
+    let shared = Arc::new(Shared::new(
+        pow,
+        nonce_tx,
+        config.difficulty,
+        Duration::from_secs(config.share_interval),
+    ));
+    let (stop_tx, stop_rx) = watch::channel(());
+
+    let addr = config.listen;
+    let mut stats_stop_rx = stop_rx.clone();
+    let server_shared = Arc::clone(&shared);
+    let server_handle = handle.clone();
+    handle.spawn(async move {
+        let listener = match TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!("stratum server failed to listen on {}: {}", addr, err);
+                return;
+            }
+        };
+        info!("stratum server listens on {}", addr);
+        let mut stop_rx = stop_rx;
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        let shared = Arc::clone(&server_shared);
+                        let job_rx = shared.job_tx.subscribe();
+                        let connection =
+                            handle_connection(shared, stream, peer, job_rx, stop_rx.clone());
+                        server_handle.spawn(connection);
+                    }
+                    Err(err) => error!("stratum server failed to accept: {}", err),
+                },
+                changed = stop_rx.changed() => if changed.is_err() {
+                    break;
+                },
+            }
+        }
+    });
+
+    let stats_shared = Arc::clone(&shared);
+    handle.spawn(async move {
+        let mut interval = tokio::time::interval(STATS_INTERVAL);
+        interval.tick().await;
+        loop {
+            tokio::select! {
+                _ = interval.tick() => stats_shared.log_stats(),
+                changed = stats_stop_rx.changed() => if changed.is_err() {
+                    break;
+                },
+            }
+        }
+    });
+
+    StratumController {
+        shared,
+        _stop_tx: stop_tx,
+        next_job_id: AtomicU64::new(0),
+    }
+}
+
+impl Shared {
+    pub(crate) fn new(
+        pow: Arc<dyn PowEngine>,
+        nonce_tx: Sender<(Byte32, Work, u128)>,
+        difficulty: u64,
+        share_interval: Duration,
+    ) -> Self {
+        let (job_tx, _) = broadcast::channel(MAX_JOBS);
+        Shared {
+            pow,
+            nonce_tx,
+            jobs: Mutex::new(Jobs::default()),
+            job_tx,
+            stats: Mutex::new(HashMap::new()),
+            next_extranonce1: AtomicU32::new(rand::random()),
+            difficulty: difficulty.max(MIN_DIFFICULTY),
+            share_interval: share_interval.max(Duration::from_secs(1)),
+        }
+    }
+
+    /// Adds the job which accepts the shares and sends it to the connections.
+    pub(crate) fn publish(&self, job: Arc<Job>) {
+        let clean_jobs = self.jobs.lock().expect("jobs lock").push(Arc::clone(&job));
+        if self.job_tx.send((job, clean_jobs)).is_err() {
+            debug!("stratum server has no connection");
+        }
+    }
+
+    fn log_stats(&self) {
+        let mut stats = self.stats.lock().expect("stats lock");
+        stats.retain(|_, stats| stats.accepted + stats.rejected > 0);
+        for (worker, stats) in stats.iter_mut() {
+            info!(
+                "stratum worker {} difficulty: {}, shares accepted: {}, rejected: {}, hash rate: {:.3}",
+                worker,
+                stats.difficulty,
+                stats.accepted,
+                stats.rejected,
+                stats.hashes / STATS_INTERVAL.as_secs_f64(),
+            );
+            stats.accepted = 0;
+            stats.rejected = 0;
+            stats.hashes = 0.0;
+        }
+    }
+
+    fn record_share(&self, worker: &str, difficulty: u64, accepted: bool) {
+        let mut stats = self.stats.lock().expect("stats lock");
+        let stats = stats
+            .entry(worker.to_owned())
+            .or_insert_with(|| WorkerStats {
+                difficulty,
+                accepted: 0,
+                rejected: 0,
+                hashes: 0.0,
+            });
+        stats.difficulty = difficulty;
+        if accepted {
+            stats.accepted += 1;
+            stats.hashes += difficulty as f64;
+        } else {
+            stats.rejected += 1;
+        }
+    }
+}
+
+async fn handle_connection(
+    shared: Arc<Shared>,
+    stream: TcpStream,
+    peer: SocketAddr,
+    mut job_rx: broadcast::Receiver<(Arc<Job>, bool)>,
+    mut stop_rx: watch::Receiver<()>,
+) {
+    debug!("stratum connection from {}", peer);
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = FramedRead::new(reader, LinesCodec::new_with_max_length(MAX_LINE_LENGTH));
+    let mut session = Session::new(shared, Instant::now());
+    let mut retarget = tokio::time::interval(RETARGET_INTERVAL);
+    retarget.tick().await;
+    // Some jobs are missed, the next job must clean the jobs in case the missed jobs did.
+    let mut lagged = false;
+    loop {
+        let messages = tokio::select! {
+            line = lines.next() => match line {
+                Some(Ok(line)) => session.handle_line(&line, Instant::now()),
+                None => break,
+                Some(Err(err)) => {
+                    debug!("stratum connection {} read error: {}", peer, err);
+                    break;
+                }
+            },
+            job = job_rx.recv() => match job {
+                Ok((job, clean_jobs)) => {
+                    let clean_jobs = clean_jobs || lagged;
+                    lagged = false;
+                    session.notify_job(job, clean_jobs)
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    lagged = true;
+                    Vec::new()
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            changed = stop_rx.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                Vec::new()
+            },
+            _ = retarget.tick() => session.retarget(Instant::now()),
+        };
+        for message in messages {
+            if let Err(err) = writer.write_all(message.as_bytes()).await {
+                debug!("stratum connection {} write error: {}", peer, err);
+                return;
+            }
+        }
+    }
+    debug!("stratum connection {} closed", peer);
+}
+
+/// The state of a connection.
+pub(crate) struct Session {
+    shared: Arc<Shared>,
+    extranonce1: Option<[u8; EXTRANONCE1_SIZE]>,
+    worker: Option<String>,
+    pub(crate) difficulty: u64,
+    /// The submitted nonces of the recent jobs, keyed by the job ids.
+    pub(crate) submitted: HashMap<String, HashSet<u128>>,
+    current_job: Option<Arc<Job>>,
+    pub(crate) shares_since_retarget: u64,
+    retarget_at: Instant,
+}
+
+impl Session {
+    pub(crate) fn new(shared: Arc<Shared>, now: Instant) -> Self {
+        let difficulty = shared.difficulty;
+        // the connection starts working on the latest job once it is authorized
+        let current_job = shared.jobs.lock().expect("jobs lock").latest();
+        Session {
+            shared,
+            extranonce1: None,
+            worker: None,
+            difficulty,
+            submitted: HashMap::new(),
+            current_job,
+            shares_since_retarget: 0,
+            retarget_at: now,
+        }
+    }
+
+    fn share_target(&self) -> U256 {
+        U256::max_value() / U256::from(self.difficulty)
+    }
+
+    fn set_target(&self) -> String {
+        let mut target = [0u8; 32];
+        self.share_target()
+            .into_big_endian(&mut target)
+            .expect("bound checked");
+        notification("mining.set_target", json!([to_hex(&target)]))
+    }
+
+    fn is_ready(&self) -> bool {
+        self.extranonce1.is_some() && self.worker.is_some()
+    }
+
+    pub(crate) fn notify_job(&mut self, job: Arc<Job>, clean_jobs: bool) -> Vec<String> {
+        if clean_jobs {
+            self.submitted.clear();
+        } else {
+            // forgets the jobs which no longer accept the shares
+            let jobs = self.shared.jobs.lock().expect("jobs lock");
+            self.submitted.retain(|id, _| jobs.contains(id));
+        }
+        self.current_job = Some(Arc::clone(&job));
+        if self.is_ready() {
+            vec![job.notify(clean_jobs)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub(crate) fn handle_line(&mut self, line: &str, now: Instant) -> Vec<String> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(err) => {
+                debug!("stratum malformed request: {}", err);
+                return Vec::new();
+            }
+        };
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let params = request
+            .get("params")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+        let was_ready = self.is_ready();
+        let result = match method {
+            "mining.subscribe" => Ok(self.subscribe()),
+            "mining.authorize" => self.authorize(&params),
+            "mining.submit" => self.submit(&params),
+            "mining.extranonce.subscribe" => Ok(Value::Bool(false)),
+            _ => Err((ERR_UNKNOWN, format!("unknown method {}", method))),
+        };
+        let mut messages = vec![response(id, result)];
+        if !was_ready && self.is_ready() {
+            messages.push(self.set_target());
+            if let Some(ref job) = self.current_job {
+                messages.push(job.notify(true));
+            }
+        }
+        messages.extend(self.retarget(now));
+        messages
+    }
+
+    fn subscribe(&mut self) -> Value {
+        let extranonce1 = *self.extranonce1.get_or_insert_with(|| {
+            self.shared
+                .next_extranonce1
+                .fetch_add(1, Ordering::SeqCst)
+                .to_be_bytes()
+        });
+        json!([Value::Null, to_hex(&extranonce1), EXTRANONCE2_SIZE])
+    }
+
+    fn authorize(&mut self, params: &[Value]) -> Result<Value, (i64, String)> {
+        let worker = params
+            .get(0)
+            .and_then(Value::as_str)
+            .ok_or_else(|| (ERR_UNKNOWN, "missing worker name".to_owned()))?;
+        self.worker = Some(worker.to_owned());
+        Ok(Value::Bool(true))
+    }
+
+    fn submit(&mut self, params: &[Value]) -> Result<Value, (i64, String)> {
+        let worker = self
+            .worker
+            .clone()
+            .ok_or_else(|| (ERR_UNAUTHORIZED, "unauthorized worker".to_owned()))?;
+        let extranonce1 = self
+            .extranonce1
+            .ok_or_else(|| (ERR_NOT_SUBSCRIBED, "not subscribed".to_owned()))?;
+        let ret = self.check_share(params, extranonce1);
+        self.shared
+            .record_share(&worker, self.difficulty, ret.is_ok());
+        ret
+    }
+
+    fn check_share(
+        &mut self,
+        params: &[Value],
+        extranonce1: [u8; EXTRANONCE1_SIZE],
+    ) -> Result<Value, (i64, String)> {
+        let (job_id, extranonce2) = match (
+            params.get(1).and_then(Value::as_str),
+            params.get(2).and_then(Value::as_str),
+        ) {
+            (Some(job_id), Some(extranonce2)) => (job_id, extranonce2),
+            _ => return Err((ERR_UNKNOWN, "invalid params".to_owned())),
+        };
+        let job = self
+            .shared
+            .jobs
+            .lock()
+            .expect("jobs lock")
+            .get(job_id)
+            .ok_or_else(|| (ERR_JOB_NOT_FOUND, "job not found".to_owned()))?;
+        let extranonce2 = from_hex(extranonce2)
+            .filter(|bytes| bytes.len() == EXTRANONCE2_SIZE)
+            .ok_or_else(|| (ERR_UNKNOWN, "invalid extranonce2".to_owned()))?;
+        let mut nonce = [0u8; 16];
+        nonce[..EXTRANONCE1_SIZE].copy_from_slice(&extranonce1);
+        nonce[EXTRANONCE1_SIZE..].copy_from_slice(&extranonce2);
+        let nonce = u128::from_be_bytes(nonce);
+
+        if self
+            .submitted
+            .get(&job.id)
+            .map_or(false, |nonces| nonces.contains(&nonce))
+        {
+            return Err((ERR_DUPLICATE_SHARE, "duplicate share".to_owned()));
+        }
+        // A block is always a valid share, even if the share difficulty is above the block's.
+        let pow = &self.shared.pow;
+        let found_block = pow.verify_target(&job.pow_hash, nonce, &job.target);
+        if !found_block && !pow.verify_target(&job.pow_hash, nonce, &self.share_target()) {
+            return Err((ERR_LOW_DIFFICULTY, "low difficulty share".to_owned()));
+        }
+        // Only the accepted nonces are recorded, a rejected one may be valid after a retarget.
+        self.submitted
+            .entry(job.id.clone())
+            .or_default()
+            .insert(nonce);
+        self.shares_since_retarget += 1;
+        if found_block {
+            debug!(
+                "stratum found block, pow_hash {:#x}, nonce {:#x}",
+                job.pow_hash, nonce
+            );
+            if let Err(err) =
+                self.shared
+                    .nonce_tx
+                    .send((job.pow_hash.clone(), job.work.clone(), nonce))
+            {
+                error!("nonce_tx send error {:?}", err);
+            }
+        }
+        Ok(Value::Bool(true))
+    }
+
+    /// Adjusts the share difficulty towards the expected share interval.
+    pub(crate) fn retarget(&mut self, now: Instant) -> Vec<String> {
+        let elapsed = now.saturating_duration_since(self.retarget_at);
+        if elapsed < RETARGET_INTERVAL || !self.is_ready() {
+            return Vec::new();
+        }
+        let expected = elapsed.as_secs_f64() / self.shared.share_interval.as_secs_f64();
+        let ratio = (self.shares_since_retarget as f64 / expected)
+            .max(1.0 / MAX_RETARGET_RATIO)
+            .min(MAX_RETARGET_RATIO);
+        let difficulty = ((self.difficulty as f64 * ratio) as u64).max(MIN_DIFFICULTY);
+        self.shares_since_retarget = 0;
+        self.retarget_at = now;
+        if difficulty == self.difficulty {
+            return Vec::new();
+        }
+        debug!(
+            "stratum worker {:?} difficulty {} -> {}",
+            self.worker, self.difficulty, difficulty
+        );
+        self.difficulty = difficulty;
+        vec![self.set_target()]
+    }
+}
+
+fn response(id: Value, result: Result<Value, (i64, String)>) -> String {
+    let message = match result {
+        Ok(result) => json!({ "id": id, "result": result, "error": Value::Null }),
+        Err((code, message)) => json!({
+            "id": id,
+            "result": Value::Null,
+            "error": [code, message, Value::Null],
+        }),
+    };
+    format!("{}\n", message)
+}
+
+fn notification(method: &str, params: Value) -> String {
+    format!(
+        "{}\n",
+        json!({ "id": Value::Null, "method": method, "params": params })
+    )
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.trim_start_matches("0x");
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,131 @@
+use crate::stats::MinerStats;
+use crate::worker::eaglesong_threaded::{EaglesongThreaded, Job, SharedWork};
+use crate::Work;
+use ckb_app_config::ExtraHashFunction;
+use ckb_pow::{
+    EaglesongBlake2bPowEngine, EaglesongLanes, EaglesongPowEngine, PowEngine, EAGLESONG_LANES,
+};
+use ckb_types::{core::BlockBuilder, h256, packed::Byte32, prelude::*, U256};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+const CHECK_INTERVAL: usize = 64;
+
+// Hashes `CHECK_INTERVAL` nonces from `start`, and checks that exactly the found nonces pass
+// the verification of the pow engine.
+fn check_solve_interval(
+    pow: &dyn PowEngine,
+    extra_hash_function: Option<ExtraHashFunction>,
+    start: u128,
+) {
+    let stats = MinerStats::default();
+    let (nonce_tx, nonce_rx) = ckb_channel::unbounded();
+    let mut worker = EaglesongThreaded::new(
+        Arc::new(SharedWork::default()),
+        0..u128::max_value(),
+        CHECK_INTERVAL,
+        nonce_tx,
+        stats.register_thread("test".to_string()),
+        extra_hash_function,
+    );
+    let pow_hash: Byte32 =
+        h256!("0x7a3bd9a05ab6b2f4fba3a3e9c0fbcd4b3c8bec2da2c3c2b3b1d3e0a5f0e0c0d1").pack();
+    // about a quarter of the nonces solve the target
+    let target = U256::max_value() / U256::from(4u64);
+    let job = Job {
+        pow_hash: pow_hash.clone(),
+        work: Work {
+            work_id: 1,
+            block: BlockBuilder::default().build().data(),
+        },
+        target,
+    };
+
+    let next = worker.solve_interval(&job, &EaglesongLanes::new(&pow_hash), start);
+    assert_eq!(next, start + CHECK_INTERVAL as u128);
+
+    let found: HashSet<u128> = nonce_rx
+        .try_iter()
+        .map(|(found_pow_hash, work, nonce)| {
+            assert_eq!(found_pow_hash, pow_hash);
+            assert_eq!(work.work_id, 1);
+            nonce
+        })
+        .collect();
+    assert!(!found.is_empty());
+    for nonce in start..next {
+        assert_eq!(
+            pow.verify_target(&pow_hash, nonce, &job.target),
+            found.contains(&nonce),
+            "nonce {}",
+            nonce
+        );
+    }
+}
+
+#[test]
+fn test_solve_interval() {
+    check_solve_interval(&EaglesongPowEngine, None, 0);
+    check_solve_interval(&EaglesongPowEngine, None, u128::from(u64::max_value()));
+}
+
+#[test]
+fn test_solve_interval_blake2b() {
+    check_solve_interval(
+        &EaglesongBlake2bPowEngine,
+        Some(ExtraHashFunction::Blake2b),
+        1024,
+    );
+}
+
+#[test]
+fn test_solve_interval_wraps_around() {
+    let (nonce_tx, _nonce_rx) = ckb_channel::unbounded();
+    let stats = MinerStats::default();
+    let mut worker = EaglesongThreaded::new(
+        Arc::new(SharedWork::default()),
+        100..110,
+        CHECK_INTERVAL,
+        nonce_tx,
+        stats.register_thread("test".to_string()),
+        None,
+    );
+    let job = Job {
+        pow_hash: Byte32::zero(),
+        work: Work {
+            work_id: 1,
+            block: BlockBuilder::default().build().data(),
+        },
+        target: U256::zero(),
+    };
+    let lanes = EaglesongLanes::new(&job.pow_hash);
+    // the nonces wrap around in the range, 105 + 64 = 169 is 109 in 100..110
+    assert_eq!(worker.solve_interval(&job, &lanes, 105), 109);
+}
+
+#[test]
+fn test_check_interval_rounds_up_to_lanes() {
+    let (nonce_tx, _nonce_rx) = ckb_channel::unbounded();
+    let stats = MinerStats::default();
+    let mut worker = EaglesongThreaded::new(
+        Arc::new(SharedWork::default()),
+        0..u128::max_value(),
+        10,
+        nonce_tx,
+        stats.register_thread("test".to_string()),
+        None,
+    );
+    let job = Job {
+        pow_hash: Byte32::zero(),
+        work: Work {
+            work_id: 1,
+            block: BlockBuilder::default().build().data(),
+        },
+        target: U256::zero(),
+    };
+    let lanes = EaglesongLanes::new(&job.pow_hash);
+    assert_eq!(
+        worker.solve_interval(&job, &lanes, 0),
+        2 * EAGLESONG_LANES as u128
+    );
+}
@@ -0,0 +1,3 @@
+mod eaglesong_threaded;
+mod stats;
+mod stratum;
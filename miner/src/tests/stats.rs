@@ -0,0 +1,70 @@
+use crate::stats::MinerStats;
+
+fn assert_rate(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < f64::EPSILON,
+        "hash rate {} != {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_snapshot() {
+    let stats = MinerStats::default();
+    let snapshot = stats.snapshot();
+    assert!(snapshot.threads.is_empty());
+    assert_rate(snapshot.hash_rate, 0.0);
+
+    let thread0 = stats.register_thread("worker-0".to_string());
+    let thread1 = stats.register_thread("worker-1".to_string());
+    thread0.report(1000, 2.0);
+    thread0.report(300, 1.0);
+    thread1.report(500, 0.5);
+    stats.accept();
+    stats.accept();
+    stats.reject();
+    stats.stale();
+
+    let snapshot = stats.snapshot();
+    let names: Vec<_> = snapshot.threads.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["worker-0", "worker-1"]);
+    // the hashes accumulate, and the hash rate is measured over the last report
+    assert_eq!(snapshot.threads[0].hashes, 1300);
+    assert_rate(snapshot.threads[0].hash_rate, 300.0);
+    assert_eq!(snapshot.threads[1].hashes, 500);
+    assert_rate(snapshot.threads[1].hash_rate, 1000.0);
+    assert_rate(snapshot.hash_rate, 1300.0);
+    assert_eq!(snapshot.accepted, 2);
+    assert_eq!(snapshot.rejected, 1);
+    assert_eq!(snapshot.stale, 1);
+
+    // an idle thread keeps its hashes
+    thread1.reset_hash_rate();
+    thread0.report(100, 0.0);
+    let snapshot = stats.snapshot();
+    assert_eq!(snapshot.threads[0].hashes, 1400);
+    assert_rate(snapshot.threads[0].hash_rate, 0.0);
+    assert_eq!(snapshot.threads[1].hashes, 500);
+    assert_rate(snapshot.hash_rate, 0.0);
+}
+
+#[test]
+fn test_snapshot_json() {
+    let stats = MinerStats::default();
+    stats
+        .register_thread("worker-0".to_string())
+        .report(10, 1.0);
+    stats.accept();
+    let json = serde_json::to_value(stats.snapshot()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "hash_rate": 10.0,
+            "threads": [{ "name": "worker-0", "hashes": 10, "hash_rate": 10.0 }],
+            "accepted": 1,
+            "rejected": 0,
+            "stale": 0,
+        })
+    );
+}
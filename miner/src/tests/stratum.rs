@@ -0,0 +1,266 @@
+use crate::stratum::{
+    Job, Session, Shared, ERR_DUPLICATE_SHARE, ERR_JOB_NOT_FOUND, ERR_LOW_DIFFICULTY,
+    ERR_UNAUTHORIZED, EXTRANONCE2_SIZE, MAX_JOBS, MAX_RETARGET_RATIO, MIN_DIFFICULTY,
+    RETARGET_INTERVAL,
+};
+use crate::Work;
+use ckb_channel::Receiver;
+use ckb_pow::PowEngine;
+use ckb_types::{
+    core::BlockBuilder,
+    packed::{Byte32, Header},
+    prelude::*,
+    U256,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const SHARE_INTERVAL: Duration = Duration::from_secs(10);
+
+// The PoW output of a nonce is `U256::MAX / n`, where `n` is the lowest 8 bytes of the nonce, so
+// the nonce solves the difficulty `n`.
+struct TestPowEngine;
+
+impl PowEngine for TestPowEngine {
+    fn verify(&self, _header: &Header) -> bool {
+        true
+    }
+
+    fn verify_target(&self, _pow_hash: &Byte32, nonce: u128, target: &U256) -> bool {
+        let difficulty = (nonce as u64).max(1);
+        U256::max_value() / U256::from(difficulty) <= *target
+    }
+}
+
+fn new_shared(difficulty: u64) -> (Arc<Shared>, Receiver<(Byte32, Work, u128)>) {
+    let (nonce_tx, nonce_rx) = ckb_channel::unbounded();
+    let shared = Shared::new(
+        Arc::new(TestPowEngine),
+        nonce_tx,
+        difficulty,
+        SHARE_INTERVAL,
+    );
+    (Arc::new(shared), nonce_rx)
+}
+
+fn new_job(id: u64, parent_hash: &Byte32, block_difficulty: u64) -> Arc<Job> {
+    let block = BlockBuilder::default()
+        .parent_hash(parent_hash.clone())
+        .build()
+        .data();
+    Arc::new(Job {
+        id: format!("{:x}", id),
+        pow_hash: Byte32::zero(),
+        work: Work { work_id: id, block },
+        target: U256::max_value() / U256::from(block_difficulty),
+    })
+}
+
+// The extranonce2 whose nonce solves the difficulty in `TestPowEngine`.
+fn extranonce2(difficulty: u64) -> String {
+    let mut bytes = [0u8; EXTRANONCE2_SIZE];
+    bytes[EXTRANONCE2_SIZE - 8..].copy_from_slice(&difficulty.to_be_bytes());
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn call(session: &mut Session, now: Instant, method: &str, params: Value) -> Vec<Value> {
+    let request = json!({ "id": 1, "method": method, "params": params });
+    session
+        .handle_line(&request.to_string(), now)
+        .iter()
+        .map(|message| serde_json::from_str(message).unwrap())
+        .collect()
+}
+
+fn submit(session: &mut Session, now: Instant, job_id: &str, difficulty: u64) -> Value {
+    let messages = call(
+        session,
+        now,
+        "mining.submit",
+        json!(["worker", job_id, extranonce2(difficulty)]),
+    );
+    assert_eq!(messages.len(), 1);
+    messages[0].clone()
+}
+
+fn error_code(response: &Value) -> Option<i64> {
+    response["error"][0].as_i64()
+}
+
+// Subscribes and authorizes the session, returns the notifications after the authorization.
+fn handshake(session: &mut Session, now: Instant) -> Vec<Value> {
+    let subscribed = call(session, now, "mining.subscribe", json!([]));
+    assert_eq!(subscribed.len(), 1);
+    let mut authorized = call(session, now, "mining.authorize", json!(["worker", "x"]));
+    assert_eq!(authorized.remove(0)["result"], json!(true));
+    authorized
+}
+
+#[test]
+fn test_session_handshake() {
+    let (shared, _nonce_rx) = new_shared(4);
+    shared.publish(new_job(0, &Byte32::zero(), 1000));
+    let now = Instant::now();
+    let mut session = Session::new(shared, now);
+
+    // the shares are rejected before the authorization
+    let response = submit(&mut session, now, "0", 4);
+    assert_eq!(error_code(&response), Some(ERR_UNAUTHORIZED));
+
+    let subscribed = call(&mut session, now, "mining.subscribe", json!([]));
+    assert_eq!(subscribed.len(), 1);
+    let result = subscribed[0]["result"].as_array().unwrap();
+    assert_eq!(result[0], Value::Null);
+    assert_eq!(result[1].as_str().unwrap().len(), 8);
+    assert_eq!(result[2], json!(EXTRANONCE2_SIZE));
+
+    // the target and the current job are sent once the worker is authorized
+    let authorized = call(
+        &mut session,
+        now,
+        "mining.authorize",
+        json!(["worker", "x"]),
+    );
+    assert_eq!(authorized.len(), 3);
+    assert_eq!(authorized[0]["result"], json!(true));
+    assert_eq!(authorized[1]["method"], "mining.set_target");
+    assert_eq!(authorized[2]["method"], "mining.notify");
+    assert_eq!(authorized[2]["params"][0], "0");
+    assert_eq!(authorized[2]["params"][4], json!(true));
+
+    let response = submit(&mut session, now, "0", 4);
+    assert_eq!(response["result"], json!(true));
+    assert_eq!(response["error"], Value::Null);
+}
+
+#[test]
+fn test_check_share() {
+    let (shared, nonce_rx) = new_shared(4);
+    shared.publish(new_job(0, &Byte32::zero(), 1000));
+    let now = Instant::now();
+    let mut session = Session::new(shared, now);
+    handshake(&mut session, now);
+
+    let response = submit(&mut session, now, "1", 4);
+    assert_eq!(error_code(&response), Some(ERR_JOB_NOT_FOUND));
+
+    let response = submit(&mut session, now, "0", 2);
+    assert_eq!(error_code(&response), Some(ERR_LOW_DIFFICULTY));
+    // the rejected nonce is not recorded, it is accepted once the difficulty drops
+    session.difficulty = 2;
+    let response = submit(&mut session, now, "0", 2);
+    assert_eq!(response["result"], json!(true));
+    session.difficulty = 4;
+
+    // a valid share which does not solve the block
+    let response = submit(&mut session, now, "0", 5);
+    assert_eq!(response["result"], json!(true));
+    assert!(nonce_rx.try_recv().is_err());
+
+    let response = submit(&mut session, now, "0", 5);
+    assert_eq!(error_code(&response), Some(ERR_DUPLICATE_SHARE));
+
+    // the share which solves the block is sent to the miner
+    let response = submit(&mut session, now, "0", 1000);
+    assert_eq!(response["result"], json!(true));
+    let (_, work, nonce) = nonce_rx.try_recv().unwrap();
+    assert_eq!(work.work_id, 0);
+    assert_eq!(nonce as u64, 1000);
+}
+
+#[test]
+fn test_share_difficulty_above_block() {
+    let (shared, nonce_rx) = new_shared(2000);
+    shared.publish(new_job(0, &Byte32::zero(), 1000));
+    let now = Instant::now();
+    let mut session = Session::new(shared, now);
+    handshake(&mut session, now);
+
+    // the share solves the block but not the share difficulty, it is still accepted
+    let response = submit(&mut session, now, "0", 1000);
+    assert_eq!(response["result"], json!(true));
+    let (_, _, nonce) = nonce_rx.try_recv().unwrap();
+    assert_eq!(nonce as u64, 1000);
+
+    let response = submit(&mut session, now, "0", 999);
+    assert_eq!(error_code(&response), Some(ERR_LOW_DIFFICULTY));
+    assert!(nonce_rx.try_recv().is_err());
+}
+
+#[test]
+fn test_prune_submitted() {
+    let (shared, _nonce_rx) = new_shared(1);
+    let parent_hash = Byte32::zero();
+    shared.publish(new_job(0, &parent_hash, 1000));
+    let now = Instant::now();
+    let mut session = Session::new(Arc::clone(&shared), now);
+    handshake(&mut session, now);
+    assert_eq!(submit(&mut session, now, "0", 1)["result"], json!(true));
+
+    // the jobs of the same parent are kept until they fall out of the recent jobs
+    for id in 1..MAX_JOBS as u64 {
+        let job = new_job(id, &parent_hash, 1000);
+        shared.publish(Arc::clone(&job));
+        session.notify_job(job, false);
+        assert!(session.submitted.contains_key("0"));
+    }
+    let job = new_job(MAX_JOBS as u64, &parent_hash, 1000);
+    shared.publish(Arc::clone(&job));
+    session.notify_job(job, false);
+    assert!(!session.submitted.contains_key("0"));
+    let response = submit(&mut session, now, "0", 1);
+    assert_eq!(error_code(&response), Some(ERR_JOB_NOT_FOUND));
+
+    assert_eq!(submit(&mut session, now, "1", 1)["result"], json!(true));
+    let job = new_job(
+        MAX_JOBS as u64 + 1,
+        &Byte32::zero().as_builder().nth0(1.into()).build(),
+        1000,
+    );
+    shared.publish(Arc::clone(&job));
+    let messages = session.notify_job(job, true);
+    assert!(session.submitted.is_empty());
+    let notify: Value = serde_json::from_str(&messages[0]).unwrap();
+    assert_eq!(notify["params"][4], json!(true));
+}
+
+#[test]
+fn test_retarget_clamps() {
+    let (shared, _nonce_rx) = new_shared(16);
+    let start = Instant::now();
+    let mut session = Session::new(shared, start);
+    handshake(&mut session, start);
+    let expected_shares = RETARGET_INTERVAL.as_secs() / SHARE_INTERVAL.as_secs();
+
+    // not retargeted before the interval
+    session.shares_since_retarget = 1000;
+    assert!(session.retarget(start).is_empty());
+    assert_eq!(session.difficulty, 16);
+
+    // too many shares, the difficulty is raised by at most `MAX_RETARGET_RATIO` times
+    let mut now = start + RETARGET_INTERVAL;
+    let messages = session.retarget(now);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("mining.set_target"));
+    assert_eq!(session.difficulty, 16 * MAX_RETARGET_RATIO as u64);
+    assert_eq!(session.shares_since_retarget, 0);
+
+    // the expected shares keep the difficulty
+    now += RETARGET_INTERVAL;
+    session.shares_since_retarget = expected_shares;
+    assert!(session.retarget(now).is_empty());
+    assert_eq!(session.difficulty, 64);
+
+    // no share, the difficulty is lowered by at most `MAX_RETARGET_RATIO` times
+    now += RETARGET_INTERVAL;
+    assert_eq!(session.retarget(now).len(), 1);
+    assert_eq!(session.difficulty, 16);
+
+    // and never below `MIN_DIFFICULTY`
+    for _ in 0..4 {
+        now += RETARGET_INTERVAL;
+        session.retarget(now);
+    }
+    assert_eq!(session.difficulty, MIN_DIFFICULTY);
+}
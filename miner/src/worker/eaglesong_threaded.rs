@@ -0,0 +1,205 @@
+use super::{Worker, WorkerMessage};
+use crate::stats::ThreadStats;
+use crate::Work;
+use ckb_app_config::ExtraHashFunction;
+use ckb_channel::{Receiver, Sender};
+use ckb_hash::blake2b_256;
+use ckb_logger::{debug, error};
+use ckb_pow::{EaglesongLanes, EAGLESONG_LANES};
+use ckb_types::{packed::Byte32, U256};
+use indicatif::ProgressBar;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+const STATE_UPDATE_DURATION_MILLIS: u128 = 500;
+const IDLE_PARK_DURATION: Duration = Duration::from_millis(100);
+
+pub(crate) struct Job {
+    pub(crate) pow_hash: Byte32,
+    pub(crate) work: Work,
+    pub(crate) target: U256,
+}
+
+/// The work shared by all the threads of the worker.
+///
+/// Every change bumps the generation, which the threads check every `check_interval` nonces, so
+/// they drop the outdated work quickly.
+#[derive(Default)]
+pub struct SharedWork {
+    generation: AtomicU64,
+    running: AtomicBool,
+    job: RwLock<Option<Arc<Job>>>,
+}
+
+impl SharedWork {
+    fn current(&self) -> Option<(u64, Arc<Job>)> {
+        if !self.running.load(Ordering::Acquire) {
+            return None;
+        }
+        let generation = self.generation.load(Ordering::Acquire);
+        let job = self.job.read().expect("acquire lock").clone()?;
+        Some((generation, job))
+    }
+
+    fn is_outdated(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::Acquire) != generation
+    }
+}
+
+/// Receives the messages of the miner and dispatches them to the threads through `SharedWork`.
+pub fn dispatch(worker_rx: Receiver<WorkerMessage>, shared: Arc<SharedWork>, threads: Vec<Thread>) {
+    shared.running.store(true, Ordering::Release);
+    while let Ok(msg) = worker_rx.recv() {
+        match msg {
+            WorkerMessage::NewWork {
+                pow_hash,
+                work,
+                target,
+            } => {
+                *shared.job.write().expect("acquire lock") = Some(Arc::new(Job {
+                    pow_hash,
+                    work,
+                    target,
+                }));
+            }
+            WorkerMessage::Stop => {
+                shared.running.store(false, Ordering::Release);
+            }
+            WorkerMessage::Start => {
+                shared.running.store(true, Ordering::Release);
+            }
+        }
+        shared.generation.fetch_add(1, Ordering::AcqRel);
+        for thread in threads.iter() {
+            thread.unpark();
+        }
+    }
+}
+
+/// A thread of the threaded Eaglesong worker.
+///
+/// The thread starts from a random nonce in its range for each new work, then hashes the
+/// following nonces in batches of `EAGLESONG_LANES` with `EaglesongLanes`, which shares the
+/// absorption of the PoW hash and hashes the batch in SIMD-friendly lanes. It checks the
+/// generation of the shared work and reports the hash rate every `check_interval` nonces,
+/// instead of polling a channel for each nonce like `EaglesongSimple`.
+pub struct EaglesongThreaded {
+    shared: Arc<SharedWork>,
+    nonce_range: Range<u128>,
+    check_interval: usize,
+    nonce_tx: Sender<(Byte32, Work, u128)>,
+    stats: Arc<ThreadStats>,
+    nonces_found: u128,
+    extra_hash_function: Option<ExtraHashFunction>,
+}
+
+impl EaglesongThreaded {
+    /// The `check_interval` is rounded up to a multiple of `EAGLESONG_LANES`.
+    pub fn new(
+        shared: Arc<SharedWork>,
+        nonce_range: Range<u128>,
+        check_interval: usize,
+        nonce_tx: Sender<(Byte32, Work, u128)>,
+        stats: Arc<ThreadStats>,
+        extra_hash_function: Option<ExtraHashFunction>,
+    ) -> Self {
+        Self {
+            shared,
+            nonce_range,
+            check_interval: (check_interval.max(1) + EAGLESONG_LANES - 1) / EAGLESONG_LANES
+                * EAGLESONG_LANES,
+            nonce_tx,
+            stats,
+            nonces_found: 0,
+            extra_hash_function,
+        }
+    }
+
+    /// Hashes `check_interval` consecutive nonces from `nonce` with the `lanes` of the job's PoW
+    /// hash, and returns the next nonce.
+    pub(crate) fn solve_interval(
+        &mut self,
+        job: &Job,
+        lanes: &EaglesongLanes,
+        mut nonce: u128,
+    ) -> u128 {
+        let mut nonces = [0u128; EAGLESONG_LANES];
+        for _ in 0..self.check_interval / EAGLESONG_LANES {
+            for lane in nonces.iter_mut() {
+                *lane = nonce;
+                nonce = if nonce + 1 >= self.nonce_range.end {
+                    self.nonce_range.start
+                } else {
+                    nonce + 1
+                };
+            }
+            for (nonce, output) in nonces.iter().zip(lanes.hash(&nonces).iter()) {
+                let output = match self.extra_hash_function {
+                    Some(ExtraHashFunction::Blake2b) => blake2b_256(output),
+                    None => *output,
+                };
+                if U256::from_big_endian(&output[..]).expect("bound checked") <= job.target {
+                    debug!(
+                        "send new found nonce, pow_hash {}, nonce {:?}",
+                        job.pow_hash, nonce
+                    );
+                    if let Err(err) =
+                        self.nonce_tx
+                            .send((job.pow_hash.clone(), job.work.clone(), *nonce))
+                    {
+                        error!("nonce_tx send error {:?}", err);
+                    }
+                    self.nonces_found += 1;
+                }
+            }
+        }
+        nonce
+    }
+}
+
+impl Worker for EaglesongThreaded {
+    fn run<G: FnMut() -> u128>(&mut self, mut rng: G, progress_bar: ProgressBar) {
+        let mut hashes = 0u64;
+        let mut start = Instant::now();
+        loop {
+            let (generation, job) = match self.shared.current() {
+                Some(current) => current,
+                None => {
+                    // count the hashes of the dropped work, reset state and wait for the work
+                    if hashes > 0 {
+                        self.stats.report(hashes, start.elapsed().as_secs_f64());
+                        hashes = 0;
+                    }
+                    start = Instant::now();
+                    self.stats.reset_hash_rate();
+                    thread::park_timeout(IDLE_PARK_DURATION);
+                    continue;
+                }
+            };
+
+            let lanes = EaglesongLanes::new(&job.pow_hash);
+            let mut nonce = rng();
+            while !self.shared.is_outdated(generation) {
+                nonce = self.solve_interval(&job, &lanes, nonce);
+                hashes += self.check_interval as u64;
+
+                let elapsed = Instant::now().saturating_duration_since(start);
+                if elapsed.as_millis() > STATE_UPDATE_DURATION_MILLIS {
+                    let secs = elapsed.as_secs_f64();
+                    self.stats.report(hashes, secs);
+                    progress_bar.set_message(format!(
+                        "hash rate: {:>10.3} / nonces found: {:>10}",
+                        hashes as f64 / secs,
+                        self.nonces_found,
+                    ));
+                    progress_bar.inc(1);
+                    hashes = 0;
+                    start = Instant::now();
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 mod dummy;
 mod eaglesong_simple;
+pub(crate) mod eaglesong_threaded;
 
+use crate::stats::MinerStats;
 use crate::Work;
 use ckb_app_config::MinerWorkerConfig;
 use ckb_channel::{unbounded, Sender};
@@ -9,6 +11,7 @@ use ckb_pow::{DummyPowEngine, EaglesongBlake2bPowEngine, EaglesongPowEngine, Pow
 use ckb_types::{packed::Byte32, U256};
 use dummy::Dummy;
 use eaglesong_simple::EaglesongSimple;
+use eaglesong_threaded::{dispatch, EaglesongThreaded, SharedWork};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use rand::{random, Rng};
 use std::ops::Range;
@@ -68,6 +71,7 @@ pub fn start_worker(
     config: &MinerWorkerConfig,
     nonce_tx: Sender<(Byte32, Work, u128)>,
     mp: &MultiProgress,
+    stats: &MinerStats,
 ) -> WorkerController {
     match config {
         MinerWorkerConfig::Dummy(config) => {
@@ -131,6 +135,52 @@ pub fn start_worker(
                 panic!("incompatible pow engine and worker type");
             }
         }
+        MinerWorkerConfig::EaglesongThreaded(config) => {
+            if pow.as_any().downcast_ref::<EaglesongPowEngine>().is_some()
+                || pow
+                    .as_any()
+                    .downcast_ref::<EaglesongBlake2bPowEngine>()
+                    .is_some()
+            {
+                let shared = Arc::new(SharedWork::default());
+                let threads = (0..config.threads)
+                    .map(|i| {
+                        let worker_name = format!("EaglesongThreaded-Worker-{}", i);
+                        let nonce_range = partition_nonce(i as u128, config.threads as u128);
+                        let pb = mp.add(ProgressBar::new(100));
+                        pb.set_style(ProgressStyle::default_bar().template(PROGRESS_BAR_TEMPLATE));
+                        pb.set_prefix(worker_name.clone());
+
+                        let mut worker = EaglesongThreaded::new(
+                            Arc::clone(&shared),
+                            nonce_range.clone(),
+                            config.check_interval.max(1),
+                            nonce_tx.clone(),
+                            stats.register_thread(worker_name.clone()),
+                            config.extra_hash_function,
+                        );
+                        thread::Builder::new()
+                            .name(worker_name)
+                            .spawn(move || {
+                                let rng = nonce_generator(nonce_range);
+                                worker.run(rng, pb);
+                            })
+                            .expect("Start `EaglesongThreaded` worker thread failed")
+                            .thread()
+                            .clone()
+                    })
+                    .collect();
+
+                let (worker_tx, worker_rx) = unbounded();
+                thread::Builder::new()
+                    .name("EaglesongThreaded-Dispatcher".to_string())
+                    .spawn(move || dispatch(worker_rx, shared, threads))
+                    .expect("Start `EaglesongThreaded` dispatcher thread failed");
+                WorkerController::new(vec![worker_tx])
+            } else {
+                panic!("incompatible pow engine and worker type");
+            }
+        }
     }
 }
 
@@ -4,9 +4,16 @@ use crate::Score;
 /// we maintain a score to each peer
 /// report peer behaviour will affects peer's score
 ///
-/// Currently this feature is disabled, maybe someday we will add it back or totally remove it.
+/// The score is kept in the peer store by the ip of the peer, the peer will be banned for a while
+/// once its score falls below the ban score, see `PeerScoreConfig`.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Behaviour {
+    /// The peer sent us a message which we have not requested
+    UnexpectedMessage,
+    /// The peer did not respond to our request in time
+    Timeout,
+    /// The peer delivered a block we requested
+    UsefulBlock,
     #[cfg(test)]
     TestGood,
     #[cfg(test)]
@@ -16,12 +23,14 @@ pub enum Behaviour {
 impl Behaviour {
     /// Behaviour score
     pub fn score(self) -> Score {
-        #[cfg(test)]
         match self {
+            Behaviour::UnexpectedMessage => -5,
+            Behaviour::Timeout => -10,
+            Behaviour::UsefulBlock => 1,
+            #[cfg(test)]
             Behaviour::TestGood => 10,
+            #[cfg(test)]
             Behaviour::TestBad => -10,
         }
-        #[cfg(not(test))]
-        0
     }
 }
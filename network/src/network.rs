@@ -82,6 +82,8 @@ pub struct NetworkState {
     /// Node supported protocols
     /// fields: ProtocolId, Protocol Name, Supported Versions
     pub(crate) protocols: RwLock<Vec<(ProtocolId, String, Vec<String>)>>,
+    /// Whether the node prunes the old blocks, which is advertised in the identify protocol
+    pub(crate) pruned: bool,
 }
 
 impl NetworkState {
@@ -132,9 +134,16 @@ impl NetworkState {
             local_peer_id,
             active: AtomicBool::new(true),
             protocols: RwLock::new(Vec::new()),
+            pruned: false,
         })
     }
 
+    /// Advertises to the peers that the node cannot serve the old blocks because they are pruned.
+    pub fn pruned(mut self, pruned: bool) -> Self {
+        self.pruned = pruned;
+        self
+    }
+
     pub(crate) fn report_session(
         &self,
         p2p_control: &ServiceControl,
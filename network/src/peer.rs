@@ -9,6 +9,8 @@ use std::time::{Duration, Instant};
 pub struct PeerIdentifyInfo {
     /// Node version
     pub client_version: String,
+    /// Whether the node prunes the old blocks and cannot serve them
+    pub pruned: bool,
 }
 
 /// Peer info
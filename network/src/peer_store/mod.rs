@@ -11,6 +11,7 @@ pub mod addr_manager;
 pub mod ban_list;
 mod peer_store_db;
 mod peer_store_impl;
+pub mod score_list;
 pub mod types;
 
 pub(crate) use crate::Behaviour;
@@ -29,6 +30,10 @@ pub(crate) const ADDR_TRY_TIMEOUT_MS: u64 = 3 * 24 * 3600 * 1000;
 pub(crate) const DIAL_INTERVAL: u64 = 15 * 1000;
 const ADDR_MAX_RETRIES: u32 = 3;
 const ADDR_MAX_FAILURES: u32 = 10;
+/// The score list evicts scores after reach this limitation
+pub(crate) const SCORE_COUNT_LIMIT: usize = 16384;
+/// A peer score moves back towards the default score by one point every interval
+pub(crate) const SCORE_RECOVER_INTERVAL_MS: u64 = 3600 * 1000;
 
 /// Alias score
 pub type Score = i32;
@@ -40,6 +45,8 @@ pub struct PeerScoreConfig {
     pub default_score: Score,
     /// Ban score
     pub ban_score: Score,
+    /// Max score, so a peer can not save up scores to misbehave later
+    pub max_score: Score,
     /// Ban time
     pub ban_timeout_ms: u64,
 }
@@ -49,6 +56,7 @@ impl Default for PeerScoreConfig {
         PeerScoreConfig {
             default_score: 100,
             ban_score: 40,
+            max_score: 200,
             ban_timeout_ms: 24 * 3600 * 1000, // 1 day
         }
     }
@@ -3,8 +3,9 @@ use crate::{
     peer_store::{
         addr_manager::AddrManager,
         ban_list::BanList,
-        types::{AddrInfo, BannedAddr},
-        PeerStore,
+        score_list::ScoreList,
+        types::{AddrInfo, BannedAddr, PeerScore},
+        PeerScoreConfig, PeerStore,
     },
 };
 use ckb_logger::{debug, error};
@@ -16,6 +17,7 @@ use std::{
 
 const DEFAULT_ADDR_MANAGER_DB: &str = "addr_manager.db";
 const DEFAULT_BAN_LIST_DB: &str = "ban_list.db";
+const DEFAULT_SCORE_LIST_DB: &str = "score_list.db";
 
 impl AddrManager {
     /// Load address list from disk
@@ -64,11 +66,37 @@ impl BanList {
     }
 }
 
+impl ScoreList {
+    /// Load score list from disk
+    pub fn load<R: Read>(r: R) -> Result<Self, Error> {
+        let peer_scores: Vec<PeerScore> =
+            serde_json::from_reader(r).map_err(PeerStoreError::Serde)?;
+        let mut score_list = ScoreList::default();
+        peer_scores
+            .into_iter()
+            .for_each(|peer_score| score_list.insert(peer_score));
+        Ok(score_list)
+    }
+
+    /// Dump score list to disk, the scores already recovered to the default score are skipped
+    pub fn dump(&self, mut file: File, config: &PeerScoreConfig) -> Result<(), Error> {
+        let peer_scores = self.get_peer_scores(faketime::unix_time_as_millis(), config);
+        debug!("dump {} peer scores", peer_scores.len());
+        // empty file and dump the json string to it
+        file.set_len(0)
+            .and_then(|_| serde_json::to_string(&peer_scores).map_err(Into::into))
+            .and_then(|json_string| file.write_all(json_string.as_bytes()))
+            .and_then(|_| file.sync_all())
+            .map_err(Into::into)
+    }
+}
+
 impl PeerStore {
     /// Init peer store from disk
     pub fn load_from_dir_or_default<P: AsRef<Path>>(path: P) -> Self {
         let addr_manager_path = path.as_ref().join(DEFAULT_ADDR_MANAGER_DB);
         let ban_list_path = path.as_ref().join(DEFAULT_BAN_LIST_DB);
+        let score_list_path = path.as_ref().join(DEFAULT_SCORE_LIST_DB);
 
         let addr_manager = File::open(&addr_manager_path)
             .map_err(|err| {
@@ -104,7 +132,24 @@ impl PeerStore {
             })
             .unwrap_or_default();
 
-        PeerStore::new(addr_manager, ban_list)
+        let score_list = File::open(&score_list_path)
+            .map_err(|err| {
+                debug!(
+                    "Failed to open ScoreList db, file: {:?}, error: {:?}",
+                    score_list_path, err
+                )
+            })
+            .and_then(|file| {
+                ScoreList::load(file).map_err(|err| {
+                    error!(
+                        "Failed to load ScoreList db, file: {:?}, error: {:?}",
+                        score_list_path, err
+                    )
+                })
+            })
+            .unwrap_or_default();
+
+        PeerStore::new(addr_manager, ban_list, score_list)
     }
 
     /// Dump all info to disk
@@ -116,6 +161,7 @@ impl PeerStore {
         create_dir_all(&tmp_dir)?;
         let tmp_addr_manager = tmp_dir.join(DEFAULT_ADDR_MANAGER_DB);
         let tmp_ban_list = tmp_dir.join(DEFAULT_BAN_LIST_DB);
+        let tmp_score_list = tmp_dir.join(DEFAULT_SCORE_LIST_DB);
         self.addr_manager().dump(
             OpenOptions::new()
                 .write(true)
@@ -135,6 +181,15 @@ impl PeerStore {
                 .open(&tmp_ban_list)?,
         )?;
         move_file(tmp_ban_list, path.as_ref().join(DEFAULT_BAN_LIST_DB))?;
+        self.score_list().dump(
+            OpenOptions::new()
+                .write(true)
+                .create(true)
+                .append(false)
+                .open(&tmp_score_list)?,
+            self.score_config(),
+        )?;
+        move_file(tmp_score_list, path.as_ref().join(DEFAULT_SCORE_LIST_DB))?;
         Ok(())
     }
 }
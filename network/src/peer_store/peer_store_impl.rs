@@ -5,11 +5,12 @@ use crate::{
     peer_store::{
         addr_manager::AddrManager,
         ban_list::BanList,
+        score_list::ScoreList,
         types::{ip_to_network, AddrInfo, BannedAddr, PeerInfo},
         Behaviour, Multiaddr, PeerScoreConfig, ReportResult, Status, ADDR_COUNT_LIMIT,
         ADDR_TIMEOUT_MS, ADDR_TRY_TIMEOUT_MS, DIAL_INTERVAL,
     },
-    PeerId, SessionType,
+    PeerId, Score, SessionType,
 };
 use ipnetwork::IpNetwork;
 use rand::prelude::IteratorRandom;
@@ -23,16 +24,18 @@ use std::collections::{hash_map::Entry, HashMap};
 pub struct PeerStore {
     addr_manager: AddrManager,
     ban_list: BanList,
+    score_list: ScoreList,
     connected_peers: HashMap<PeerId, PeerInfo>,
     score_config: PeerScoreConfig,
 }
 
 impl PeerStore {
-    /// New with address list and ban list
-    pub fn new(addr_manager: AddrManager, ban_list: BanList) -> Self {
+    /// New with address list, ban list and score list
+    pub fn new(addr_manager: AddrManager, ban_list: BanList, score_list: ScoreList) -> Self {
         PeerStore {
             addr_manager,
             ban_list,
+            score_list,
             connected_peers: Default::default(),
             score_config: Default::default(),
         }
@@ -65,7 +68,7 @@ impl PeerStore {
             return Ok(());
         }
         self.check_purge()?;
-        let score = self.score_config.default_score;
+        let score = self.addr_score(&addr);
         self.addr_manager.add(AddrInfo::new(addr, 0, score));
         Ok(())
     }
@@ -75,7 +78,7 @@ impl PeerStore {
         if self.ban_list.is_addr_banned(&addr) {
             return;
         }
-        let score = self.score_config.default_score;
+        let score = self.addr_score(&addr);
         self.addr_manager
             .add(AddrInfo::new(addr, faketime::unix_time_as_millis(), score));
     }
@@ -90,19 +93,57 @@ impl PeerStore {
         &mut self.addr_manager
     }
 
+    /// Get score list
+    pub fn score_list(&self) -> &ScoreList {
+        &self.score_list
+    }
+
+    /// Get mut score list
+    pub fn mut_score_list(&mut self) -> &mut ScoreList {
+        &mut self.score_list
+    }
+
+    /// Get score config
+    pub fn score_config(&self) -> &PeerScoreConfig {
+        &self.score_config
+    }
+
+    /// Get the current score of the address
+    pub fn addr_score(&self, addr: &Multiaddr) -> Score {
+        match multiaddr_to_socketaddr(addr) {
+            Some(socket_addr) => self.score_list.score(
+                &socket_addr.ip(),
+                faketime::unix_time_as_millis(),
+                &self.score_config,
+            ),
+            None => self.score_config.default_score,
+        }
+    }
+
     /// Report peer behaviours
+    ///
+    /// The peer is banned for `ban_timeout_ms` once its score falls below the ban score, and its
+    /// score is reset to the default score.
     pub fn report(&mut self, addr: &Multiaddr, behaviour: Behaviour) -> ReportResult {
+        let ip = match multiaddr_to_socketaddr(addr) {
+            Some(socket_addr) => socket_addr.ip(),
+            None => return ReportResult::Ok,
+        };
+        let now_ms = faketime::unix_time_as_millis();
+        let score = self
+            .score_list
+            .report(ip, behaviour.score(), now_ms, &self.score_config);
         if let Some(peer_addr) = self.addr_manager.get_mut(addr) {
-            let score = peer_addr.score.saturating_add(behaviour.score());
             peer_addr.score = score;
-            if score < self.score_config.ban_score {
-                self.ban_addr(
-                    addr,
-                    self.score_config.ban_timeout_ms,
-                    format!("report behaviour {:?}", behaviour),
-                );
-                return ReportResult::Banned;
-            }
+        }
+        if score < self.score_config.ban_score {
+            self.ban_addr(
+                addr,
+                self.score_config.ban_timeout_ms,
+                format!("report behaviour {:?}, score {}", behaviour, score),
+            );
+            self.score_list.remove(&ip);
+            return ReportResult::Banned;
         }
         ReportResult::Ok
     }
@@ -121,7 +162,8 @@ impl PeerStore {
         }
     }
 
-    /// Get peers for outbound connection, this method randomly return recently connected peer addrs
+    /// Get peers for outbound connection, this method randomly return recently connected peer addrs,
+    /// the addrs with higher scores are preferred
     pub fn fetch_addrs_to_attempt(&mut self, count: usize) -> Vec<AddrInfo> {
         // Get info:
         // 1. Not already connected
@@ -130,16 +172,28 @@ impl PeerStore {
         let now_ms = faketime::unix_time_as_millis();
         let peers = &self.connected_peers;
         let addr_expired_ms = now_ms.saturating_sub(ADDR_TRY_TIMEOUT_MS);
-        // get addrs that can attempt.
-        self.addr_manager
-            .fetch_random(count, |peer_addr: &AddrInfo| {
-                extract_peer_id(&peer_addr.addr)
-                    .map(|peer_id| !peers.contains_key(&peer_id))
-                    .unwrap_or_default()
-                    && peer_addr.connected(|t| {
-                        t > addr_expired_ms && t <= now_ms.saturating_sub(DIAL_INTERVAL)
-                    })
-            })
+        // get addrs that can attempt, twice as many as needed to choose the better scored ones.
+        let mut addrs =
+            self.addr_manager
+                .fetch_random(count.saturating_mul(2), |peer_addr: &AddrInfo| {
+                    extract_peer_id(&peer_addr.addr)
+                        .map(|peer_id| !peers.contains_key(&peer_id))
+                        .unwrap_or_default()
+                        && peer_addr.connected(|t| {
+                            t > addr_expired_ms && t <= now_ms.saturating_sub(DIAL_INTERVAL)
+                        })
+                });
+        let score_list = &self.score_list;
+        let score_config = &self.score_config;
+        for addr in addrs.iter_mut() {
+            if let Some(socket_addr) = multiaddr_to_socketaddr(&addr.addr) {
+                addr.score = score_list.score(&socket_addr.ip(), now_ms, score_config);
+            }
+        }
+        // stable sort keeps the random order among the same scores
+        addrs.sort_by_key(|addr| std::cmp::Reverse(addr.score));
+        addrs.truncate(count);
+        addrs
     }
 
     /// Get peers for feeler connection, this method randomly return peer addrs that we never
@@ -0,0 +1,138 @@
+//! Score list
+use crate::peer_store::{
+    ban_list::CLEAR_INTERVAL_COUNTER, types::PeerScore, PeerScoreConfig, Score, SCORE_COUNT_LIMIT,
+    SCORE_RECOVER_INTERVAL_MS,
+};
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// Score list
+///
+/// Keeps the scores of the peers which have been reported, the peers not in the list have the
+/// default score. A score moves back towards the default score by one point every
+/// `SCORE_RECOVER_INTERVAL_MS`, so the old behaviours are forgotten eventually.
+///
+/// The recovered scores are cleared periodically, and the list keeps at most `SCORE_COUNT_LIMIT`
+/// scores.
+#[derive(Default)]
+pub struct ScoreList {
+    inner: HashMap<IpAddr, PeerScore>,
+    insert_count: usize,
+}
+
+impl ScoreList {
+    /// Init
+    pub fn new() -> Self {
+        ScoreList {
+            inner: HashMap::default(),
+            insert_count: 0,
+        }
+    }
+
+    /// Get the current score of the ip
+    pub fn score(&self, ip: &IpAddr, now_ms: u64, config: &PeerScoreConfig) -> Score {
+        self.inner
+            .get(ip)
+            .map(|peer_score| recovered_score(peer_score, now_ms, config))
+            .unwrap_or(config.default_score)
+    }
+
+    /// Add the delta to the score of the ip, returns the new score
+    pub fn report(
+        &mut self,
+        ip: IpAddr,
+        delta: Score,
+        now_ms: u64,
+        config: &PeerScoreConfig,
+    ) -> Score {
+        let score = self
+            .score(&ip, now_ms, config)
+            .saturating_add(delta)
+            .min(config.max_score);
+        self.inner.insert(
+            ip,
+            PeerScore {
+                address: ip,
+                score,
+                updated_at: now_ms,
+            },
+        );
+        let (insert_count, _) = self.insert_count.overflowing_add(1);
+        self.insert_count = insert_count;
+        if self.insert_count % CLEAR_INTERVAL_COUNTER == 0 {
+            self.clear_recovered(now_ms, config);
+        }
+        if self.inner.len() > SCORE_COUNT_LIMIT {
+            self.evict(now_ms, config);
+        }
+        score
+    }
+
+    /// Insert a score loaded from disk
+    pub fn insert(&mut self, peer_score: PeerScore) {
+        self.inner.insert(peer_score.address, peer_score);
+    }
+
+    /// Remove the score of the ip, it will have the default score again
+    pub fn remove(&mut self, ip: &IpAddr) -> Option<PeerScore> {
+        self.inner.remove(ip)
+    }
+
+    /// Get the scores which have not recovered to the default score yet
+    pub fn get_peer_scores(&self, now_ms: u64, config: &PeerScoreConfig) -> Vec<PeerScore> {
+        self.inner
+            .values()
+            .filter(|peer_score| {
+                recovered_score(peer_score, now_ms, config) != config.default_score
+            })
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+
+    /// Get the numbers of scored address
+    pub fn count(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn clear_recovered(&mut self, now_ms: u64, config: &PeerScoreConfig) {
+        self.inner.retain(|_, peer_score| {
+            recovered_score(peer_score, now_ms, config) != config.default_score
+        });
+    }
+
+    // Evicts the highest score, so a misbehaving peer can not get its score back to the default
+    // by flooding the list with the reports of other addresses.
+    fn evict(&mut self, now_ms: u64, config: &PeerScoreConfig) {
+        self.clear_recovered(now_ms, config);
+        while self.inner.len() > SCORE_COUNT_LIMIT {
+            let ip = self
+                .inner
+                .values()
+                .max_by_key(|peer_score| {
+                    (
+                        recovered_score(peer_score, now_ms, config),
+                        std::cmp::Reverse(peer_score.updated_at),
+                    )
+                })
+                .map(|peer_score| peer_score.address)
+                .expect("score list is not empty");
+            self.inner.remove(&ip);
+        }
+    }
+}
+
+fn recovered_score(peer_score: &PeerScore, now_ms: u64, config: &PeerScoreConfig) -> Score {
+    let elapsed = now_ms.saturating_sub(peer_score.updated_at) / SCORE_RECOVER_INTERVAL_MS;
+    let recovered = Score::try_from(elapsed).unwrap_or(Score::MAX);
+    if peer_score.score < config.default_score {
+        peer_score
+            .score
+            .saturating_add(recovered)
+            .min(config.default_score)
+    } else {
+        peer_score
+            .score
+            .saturating_sub(recovered)
+            .max(config.default_score)
+    }
+}
@@ -117,6 +117,17 @@ pub struct BannedAddr {
     pub created_at: u64,
 }
 
+/// Peer score info
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PeerScore {
+    /// Ip address
+    pub address: IpAddr,
+    /// Score
+    pub score: Score,
+    /// Last reported time
+    pub updated_at: u64,
+}
+
 /// Convert multiaddr to IpNetwork
 pub fn multiaddr_to_ip_network(multiaddr: &Multiaddr) -> Option<IpNetwork> {
     for addr_component in multiaddr {
@@ -332,7 +332,10 @@ impl IdentifyCallback {
         name: String,
         client_version: String,
     ) -> IdentifyCallback {
-        let flags = Flags(Flag::FullNode as u64);
+        let mut flags = Flags::from(Flag::FullNode);
+        if network_state.pruned {
+            flags.insert(Flag::Pruned.into());
+        }
 
         IdentifyCallback {
             network_state,
@@ -401,11 +404,13 @@ impl Callback for IdentifyCallback {
                 MisbehaveResult::Disconnect
             }
             Some((flags, client_version)) => {
+                let pruned = flags.contains(Flag::Pruned.into());
                 let registry_client_version = |version: String| {
                     self.network_state.with_peer_registry_mut(|registry| {
                         if let Some(peer) = registry.get_peer_mut(context.session.id) {
                             peer.identify_info = Some(PeerIdentifyInfo {
                                 client_version: version,
+                                pruned,
                             })
                         }
                     });
@@ -420,7 +425,7 @@ impl Callback for IdentifyCallback {
                             context.session.id,
                             TargetProtocol::Single(SupportProtocols::Feeler.protocol_id()),
                         );
-                    } else if flags.contains(self.identify.flags) {
+                    } else if flags.contains(Flag::FullNode.into()) {
                         registry_client_version(client_version);
 
                         // The remote end can support all local protocols.
@@ -576,6 +581,8 @@ impl Identify {
 enum Flag {
     /// Support all protocol
     FullNode = 0x1,
+    /// The old blocks are pruned and cannot be served
+    Pruned = 0x2,
 }
 
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
@@ -586,6 +593,11 @@ impl Flags {
     fn contains(self, flags: Flags) -> bool {
         (self.0 & flags.0) == flags.0
     }
+
+    /// Adds the flags
+    fn insert(&mut self, flags: Flags) {
+        self.0 |= flags.0;
+    }
 }
 
 impl From<Flag> for Flags {
@@ -49,6 +49,12 @@ pub enum SupportProtocols {
     /// Alert: A protocol reserved by the Nervos Foundation to publish network-wide announcements.
     /// Any information sent from the protocol is verified by multi-signature
     Alert,
+    /// Filter: A protocol used for light clients to download the compact block filters and the
+    /// block filter hashes.
+    Filter,
+    /// LightClient: A protocol used for light clients to verify the chain with the sampled headers
+    /// and the chain root MMR proofs, and to get the proofs of transactions.
+    LightClient,
 }
 
 impl SupportProtocols {
@@ -64,6 +70,8 @@ impl SupportProtocols {
             SupportProtocols::RelayV2 => 101,
             SupportProtocols::Time => 102,
             SupportProtocols::Alert => 110,
+            SupportProtocols::LightClient => 120,
+            SupportProtocols::Filter => 121,
         }
         .into()
     }
@@ -80,6 +88,8 @@ impl SupportProtocols {
             SupportProtocols::RelayV2 => "/ckb/relay",
             SupportProtocols::Time => "/ckb/tim",
             SupportProtocols::Alert => "/ckb/alt",
+            SupportProtocols::LightClient => "/ckb/lightclient",
+            SupportProtocols::Filter => "/ckb/filter",
         }
         .to_owned()
     }
@@ -99,21 +109,25 @@ impl SupportProtocols {
             SupportProtocols::Time => vec![LASTEST_VERSION.to_owned()],
             SupportProtocols::Alert => vec![LASTEST_VERSION.to_owned()],
             SupportProtocols::RelayV2 => vec![LASTEST_VERSION.to_owned()],
+            SupportProtocols::LightClient => vec![LASTEST_VERSION.to_owned()],
+            SupportProtocols::Filter => vec![LASTEST_VERSION.to_owned()],
         }
     }
 
     /// Protocol message max length
     pub fn max_frame_length(&self) -> usize {
         match self {
-            SupportProtocols::Ping => 1024,               // 1   KB
-            SupportProtocols::Discovery => 512 * 1024,    // 512 KB
-            SupportProtocols::Identify => 2 * 1024,       // 2   KB
-            SupportProtocols::Feeler => 1024,             // 1   KB
-            SupportProtocols::DisconnectMessage => 1024,  // 1   KB
-            SupportProtocols::Sync => 2 * 1024 * 1024,    // 2   MB
-            SupportProtocols::RelayV2 => 4 * 1024 * 1024, // 4   MB
-            SupportProtocols::Time => 1024,               // 1   KB
-            SupportProtocols::Alert => 128 * 1024,        // 128 KB
+            SupportProtocols::Ping => 1024,                   // 1   KB
+            SupportProtocols::Discovery => 512 * 1024,        // 512 KB
+            SupportProtocols::Identify => 2 * 1024,           // 2   KB
+            SupportProtocols::Feeler => 1024,                 // 1   KB
+            SupportProtocols::DisconnectMessage => 1024,      // 1   KB
+            SupportProtocols::Sync => 2 * 1024 * 1024,        // 2   MB
+            SupportProtocols::RelayV2 => 4 * 1024 * 1024,     // 4   MB
+            SupportProtocols::Time => 1024,                   // 1   KB
+            SupportProtocols::Alert => 128 * 1024,            // 128 KB
+            SupportProtocols::LightClient => 2 * 1024 * 1024, // 2   MB
+            SupportProtocols::Filter => 2 * 1024 * 1024,      // 2   MB
         }
     }
 
@@ -126,7 +140,9 @@ impl SupportProtocols {
             | SupportProtocols::Feeler
             | SupportProtocols::DisconnectMessage
             | SupportProtocols::Time
-            | SupportProtocols::Alert => {
+            | SupportProtocols::Alert
+            | SupportProtocols::LightClient
+            | SupportProtocols::Filter => {
                 let mut no_blocking_flag = BlockingFlag::default();
                 no_blocking_flag.disable_all();
                 no_blocking_flag
@@ -3,12 +3,14 @@ use crate::{
     extract_peer_id,
     multiaddr::Multiaddr,
     peer_store::{
-        ban_list::CLEAR_INTERVAL_COUNTER, types::multiaddr_to_ip_network, PeerStore, Status,
-        ADDR_COUNT_LIMIT, ADDR_TRY_TIMEOUT_MS,
+        ban_list::CLEAR_INTERVAL_COUNTER, score_list::ScoreList, types::multiaddr_to_ip_network,
+        PeerScoreConfig, PeerStore, Status, ADDR_COUNT_LIMIT, ADDR_TRY_TIMEOUT_MS,
+        SCORE_COUNT_LIMIT, SCORE_RECOVER_INTERVAL_MS,
     },
     Behaviour, PeerId, SessionType,
 };
 use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr};
 
 #[test]
 fn test_add_connected_peer() {
@@ -376,3 +378,106 @@ fn test_eviction() {
     peer_store.add_addr(new_peer_addr.clone()).unwrap();
     assert!(peer_store.mut_addr_manager().get(&new_peer_addr).is_some());
 }
+
+#[cfg(not(disable_faketime))]
+#[test]
+fn test_report_score_recover() {
+    let faketime_file = faketime::millis_tempfile(0).expect("create faketime file");
+    faketime::enable(&faketime_file);
+
+    faketime::write_millis(&faketime_file, 1).expect("write millis");
+
+    let mut peer_store: PeerStore = Default::default();
+    let default_score = peer_store.score_config().default_score;
+    let addr = random_addr();
+    peer_store.add_addr(addr.clone()).unwrap();
+    assert!(peer_store.report(&addr, Behaviour::TestBad).is_ok());
+    assert!(peer_store.report(&addr, Behaviour::TestBad).is_ok());
+    assert_eq!(peer_store.addr_score(&addr), default_score - 20);
+    assert_eq!(
+        peer_store.addr_manager().get(&addr).unwrap().score,
+        default_score - 20
+    );
+
+    faketime::write_millis(&faketime_file, 1 + 5 * SCORE_RECOVER_INTERVAL_MS)
+        .expect("write millis");
+    assert_eq!(peer_store.addr_score(&addr), default_score - 15);
+
+    faketime::write_millis(&faketime_file, 1 + 100 * SCORE_RECOVER_INTERVAL_MS)
+        .expect("write millis");
+    assert_eq!(peer_store.addr_score(&addr), default_score);
+}
+
+#[test]
+fn test_report_max_score() {
+    let mut peer_store: PeerStore = Default::default();
+    let max_score = peer_store.score_config().max_score;
+    let addr = random_addr();
+    for _ in 0..100 {
+        assert!(peer_store.report(&addr, Behaviour::TestGood).is_ok());
+    }
+    assert_eq!(peer_store.addr_score(&addr), max_score);
+}
+
+#[test]
+fn test_score_list_limit() {
+    let config = PeerScoreConfig::default();
+    let mut score_list = ScoreList::new();
+    let bad_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    score_list.report(bad_ip, -10, 0, &config);
+    for index in 0..SCORE_COUNT_LIMIT as u32 {
+        let ip = IpAddr::V4(Ipv4Addr::from(0x0b00_0000 + index));
+        score_list.report(ip, 5, 0, &config);
+    }
+    assert_eq!(score_list.count(), SCORE_COUNT_LIMIT);
+    // the good scores are evicted first
+    assert_eq!(
+        score_list.score(&bad_ip, 0, &config),
+        config.default_score - 10
+    );
+
+    // the recovered scores are cleared
+    let now_ms = 100 * SCORE_RECOVER_INTERVAL_MS;
+    for index in 0..CLEAR_INTERVAL_COUNTER as u32 {
+        let ip = IpAddr::V4(Ipv4Addr::from(0x0c00_0000 + index));
+        score_list.report(ip, -5, now_ms, &config);
+    }
+    assert_eq!(score_list.count(), CLEAR_INTERVAL_COUNTER);
+}
+
+#[cfg(not(disable_faketime))]
+#[test]
+fn test_fetch_addrs_to_attempt_prefer_higher_score() {
+    let faketime_file = faketime::millis_tempfile(0).expect("create faketime file");
+    faketime::enable(&faketime_file);
+
+    faketime::write_millis(&faketime_file, 1).expect("write millis");
+
+    let mut peer_store: PeerStore = Default::default();
+    let good_addr: Multiaddr =
+        format!("/ip4/127.0.0.2/tcp/42/p2p/{}", PeerId::random().to_base58())
+            .parse()
+            .unwrap();
+    let bad_addr: Multiaddr = format!("/ip4/127.0.0.3/tcp/42/p2p/{}", PeerId::random().to_base58())
+        .parse()
+        .unwrap();
+    for addr in &[good_addr.clone(), bad_addr.clone()] {
+        peer_store.add_addr(addr.clone()).unwrap();
+        peer_store
+            .mut_addr_manager()
+            .get_mut(addr)
+            .unwrap()
+            .mark_connected(faketime::unix_time_as_millis());
+    }
+    assert!(peer_store.report(&good_addr, Behaviour::TestGood).is_ok());
+    assert!(peer_store.report(&bad_addr, Behaviour::TestBad).is_ok());
+
+    faketime::write_millis(&faketime_file, 100_000).expect("write millis");
+
+    for _ in 0..10 {
+        let addrs = peer_store.fetch_addrs_to_attempt(1);
+        assert_eq!(addrs.len(), 1);
+        assert_eq!(addrs[0].addr, good_addr);
+    }
+    assert_eq!(peer_store.fetch_addrs_to_attempt(2).len(), 2);
+}
@@ -4,7 +4,7 @@ use crate::{
         types::{multiaddr_to_ip_network, AddrInfo, BannedAddr},
         PeerStore,
     },
-    PeerId,
+    Behaviour, PeerId,
 };
 
 use std::fs::File;
@@ -62,6 +62,12 @@ fn test_peer_store_persistent() {
     ban_list.ban(ban2.clone());
     ban_list.ban(ban3.clone());
 
+    // report a peer to score list
+    let addr6: Multiaddr = "/ip4/127.0.0.3/tcp/42".parse().unwrap();
+    assert!(peer_store.report(&addr6, Behaviour::TestBad).is_ok());
+    let score = peer_store.addr_score(&addr6);
+    assert!(score < peer_store.score_config().default_score);
+
     // dump and load
     let dir = tempfile::tempdir().unwrap();
     peer_store.dump_to_dir(&dir.path()).unwrap();
@@ -88,6 +94,10 @@ fn test_peer_store_persistent() {
             .collect::<HashSet<_>>(),
         vec![ban1, ban2, ban3].into_iter().collect::<HashSet<_>>()
     );
+
+    // check score list
+    assert_eq!(peer_store2.score_list().count(), 1);
+    assert_eq!(peer_store2.addr_score(&addr6), score);
 }
 
 #[test]
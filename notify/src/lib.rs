@@ -1,12 +1,24 @@
 //! TODO(doc): @quake
-use ckb_app_config::NotifyConfig;
+mod webhook;
+
+#[cfg(test)]
+mod tests;
+
+use crate::webhook::{Notification, Webhook};
+use ckb_app_config::{NotifyConfig, NotifyWebhookTopic};
 use ckb_channel::{bounded, select, Receiver, RecvError, Sender};
 use ckb_logger::{debug, error, trace};
 use ckb_stop_handler::{SignalSender, StopHandler};
 use ckb_types::{
-    core::{service::Request, tx_pool::Reject, BlockView},
+    core::{
+        service::{ChainReorg, Request},
+        tx_pool::Reject,
+        BlockView,
+    },
     packed::Alert,
 };
+use hyper::body::Bytes;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::process::Command;
 use std::thread;
@@ -37,6 +49,8 @@ pub struct NotifyController {
     reject_transaction_notifier: Sender<(PoolTransactionEntry, Reject)>,
     network_alert_register: NotifyRegister<Alert>,
     network_alert_notifier: Sender<Alert>,
+    chain_reorg_register: NotifyRegister<ChainReorg>,
+    chain_reorg_notifier: Sender<ChainReorg>,
 }
 
 impl Drop for NotifyController {
@@ -53,6 +67,8 @@ pub struct NotifyService {
     proposed_transaction_subscribers: HashMap<String, Sender<PoolTransactionEntry>>,
     reject_transaction_subscribers: HashMap<String, Sender<(PoolTransactionEntry, Reject)>>,
     network_alert_subscribers: HashMap<String, Sender<Alert>>,
+    chain_reorg_subscribers: HashMap<String, Sender<ChainReorg>>,
+    webhooks: Vec<Webhook>,
 }
 
 impl NotifyService {
@@ -65,6 +81,8 @@ impl NotifyService {
             proposed_transaction_subscribers: HashMap::default(),
             reject_transaction_subscribers: HashMap::default(),
             network_alert_subscribers: HashMap::default(),
+            chain_reorg_subscribers: HashMap::default(),
+            webhooks: Vec::new(),
         }
     }
 
@@ -92,6 +110,17 @@ impl NotifyService {
             bounded(REGISTER_CHANNEL_SIZE);
         let (network_alert_sender, network_alert_receiver) = bounded(NOTIFY_CHANNEL_SIZE);
 
+        let (chain_reorg_register, chain_reorg_register_receiver) = bounded(REGISTER_CHANNEL_SIZE);
+        let (chain_reorg_sender, chain_reorg_receiver) = bounded(NOTIFY_CHANNEL_SIZE);
+
+        self.webhooks = self
+            .config
+            .webhooks
+            .iter()
+            .cloned()
+            .map(Webhook::start)
+            .collect();
+
         let mut thread_builder = thread::Builder::new();
         if let Some(name) = thread_name {
             thread_builder = thread_builder.name(name.to_string());
@@ -112,6 +141,8 @@ impl NotifyService {
                     recv(reject_transaction_receiver) -> msg => self.handle_notify_reject_transaction(msg),
                     recv(network_alert_register_receiver) -> msg => self.handle_register_network_alert(msg),
                     recv(network_alert_receiver) -> msg => self.handle_notify_network_alert(msg),
+                    recv(chain_reorg_register_receiver) -> msg => self.handle_register_chain_reorg(msg),
+                    recv(chain_reorg_receiver) -> msg => self.handle_notify_chain_reorg(msg),
                 }
             })
             .expect("Start notify service failed");
@@ -127,6 +158,8 @@ impl NotifyService {
             reject_transaction_notifier: reject_transaction_sender,
             network_alert_register,
             network_alert_notifier: network_alert_sender,
+            chain_reorg_register,
+            chain_reorg_notifier: chain_reorg_sender,
             stop: StopHandler::new(
                 SignalSender::Crossbeam(signal_sender),
                 Some(join_handle),
@@ -161,6 +194,9 @@ impl NotifyService {
                 for subscriber in self.new_block_subscribers.values() {
                     let _ = subscriber.send(block.clone());
                 }
+                self.post_webhooks(NotifyWebhookTopic::NewBlock, || {
+                    ckb_jsonrpc_types::BlockView::from(block.clone())
+                });
                 // notify script
                 if let Some(script) = self.config.new_block_notify_script.as_ref() {
                     let args = [format!("{:#x}", block.hash())];
@@ -202,6 +238,9 @@ impl NotifyService {
                 for subscriber in self.new_transaction_subscribers.values() {
                     let _ = subscriber.send(tx_entry.clone());
                 }
+                self.post_webhooks(NotifyWebhookTopic::NewTransaction, || {
+                    ckb_jsonrpc_types::PoolTransactionEntry::from(tx_entry.clone())
+                });
             }
             _ => debug!("new transaction channel is closed"),
         }
@@ -233,6 +272,9 @@ impl NotifyService {
                 for subscriber in self.proposed_transaction_subscribers.values() {
                     let _ = subscriber.send(tx_entry.clone());
                 }
+                self.post_webhooks(NotifyWebhookTopic::ProposedTransaction, || {
+                    ckb_jsonrpc_types::PoolTransactionEntry::from(tx_entry.clone())
+                });
             }
             _ => debug!("proposed transaction channel is closed"),
         }
@@ -267,6 +309,13 @@ impl NotifyService {
                 for subscriber in self.reject_transaction_subscribers.values() {
                     let _ = subscriber.send(tx_entry.clone());
                 }
+                self.post_webhooks(NotifyWebhookTopic::RejectedTransaction, || {
+                    let (entry, reject) = tx_entry.clone();
+                    (
+                        ckb_jsonrpc_types::PoolTransactionEntry::from(entry),
+                        ckb_jsonrpc_types::PoolTransactionReject::from(reject),
+                    )
+                });
             }
             _ => debug!("reject transaction channel is closed"),
         }
@@ -298,6 +347,9 @@ impl NotifyService {
                 for subscriber in self.network_alert_subscribers.values() {
                     let _ = subscriber.send(alert.clone());
                 }
+                self.post_webhooks(NotifyWebhookTopic::NetworkAlert, || {
+                    ckb_jsonrpc_types::Alert::from(alert.clone())
+                });
                 // notify script
                 if let Some(script) = self.config.network_alert_notify_script.as_ref() {
                     let args = [alert
@@ -318,6 +370,69 @@ impl NotifyService {
             _ => debug!("network alert channel is closed"),
         }
     }
+
+    fn handle_register_chain_reorg(
+        &mut self,
+        msg: Result<Request<String, Receiver<ChainReorg>>, RecvError>,
+    ) {
+        match msg {
+            Ok(Request {
+                responder,
+                arguments: name,
+            }) => {
+                debug!("Register chain_reorg {:?}", name);
+                let (sender, receiver) = bounded(NOTIFY_CHANNEL_SIZE);
+                self.chain_reorg_subscribers.insert(name, sender);
+                let _ = responder.send(receiver);
+            }
+            _ => debug!("Register chain_reorg channel is closed"),
+        }
+    }
+
+    fn handle_notify_chain_reorg(&mut self, msg: Result<ChainReorg, RecvError>) {
+        match msg {
+            Ok(reorg) => {
+                trace!("event chain reorg {:?}", reorg);
+                // notify all subscribers
+                for subscriber in self.chain_reorg_subscribers.values() {
+                    let _ = subscriber.send(reorg.clone());
+                }
+                self.post_webhooks(NotifyWebhookTopic::ChainReorg, || {
+                    ckb_jsonrpc_types::ChainReorg::from(reorg.clone())
+                });
+            }
+            _ => debug!("chain reorg channel is closed"),
+        }
+    }
+
+    /// Queues the notification to the webhooks which accept the topic.
+    ///
+    /// The data is only converted and serialized when there is such a webhook.
+    fn post_webhooks<T, F>(&self, topic: NotifyWebhookTopic, data: F)
+    where
+        T: Serialize,
+        F: FnOnce() -> T,
+    {
+        let mut webhooks = self
+            .webhooks
+            .iter()
+            .filter(|webhook| webhook.accepts(topic))
+            .peekable();
+        if webhooks.peek().is_none() {
+            return;
+        }
+        let data = data();
+        let body = match serde_json::to_vec(&Notification { topic, data: &data }) {
+            Ok(body) => Bytes::from(body),
+            Err(err) => {
+                error!("failed to serialize the {:?} notification: {}", topic, err);
+                return;
+            }
+        };
+        for webhook in webhooks {
+            webhook.post(topic, body.clone());
+        }
+    }
 }
 
 impl NotifyController {
@@ -384,4 +499,15 @@ impl NotifyController {
     pub fn notify_network_alert(&self, alert: Alert) {
         let _ = self.network_alert_notifier.send(alert);
     }
+
+    /// Subscribes to the chain reorganizations, where some main chain blocks are rolled back.
+    pub fn subscribe_chain_reorg<S: ToString>(&self, name: S) -> Receiver<ChainReorg> {
+        Request::call(&self.chain_reorg_register, name.to_string())
+            .expect("Subscribe chain reorg should be OK")
+    }
+
+    /// Notifies that some main chain blocks are rolled back.
+    pub fn notify_chain_reorg(&self, reorg: ChainReorg) {
+        let _ = self.chain_reorg_notifier.send(reorg);
+    }
 }
@@ -0,0 +1,147 @@
+use crate::webhook::Webhook;
+use crate::NotifyService;
+use ckb_app_config::{NotifyConfig, NotifyWebhookConfig, NotifyWebhookTopic};
+use ckb_channel::{unbounded, Receiver};
+use ckb_types::{core::BlockBuilder, packed};
+use hyper::{
+    body::Bytes,
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Starts an HTTP server which answers all the requests with `status`, returns its url and the
+/// receiver of the posted bodies.
+fn start_receiver(status: StatusCode) -> (String, Receiver<Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    listener.set_nonblocking(true).expect("set nonblocking");
+    let url = format!(
+        "http://{}/notify",
+        listener.local_addr().expect("local addr")
+    );
+    let (sender, receiver) = unbounded();
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("build runtime");
+        runtime.block_on(async move {
+            let make_service = make_service_fn(move |_| {
+                let sender = sender.clone();
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                        let sender = sender.clone();
+                        async move {
+                            let body = hyper::body::to_bytes(req.into_body()).await?;
+                            let _ = sender.send(serde_json::from_slice(&body).expect("json body"));
+                            let resp = Response::builder()
+                                .status(status)
+                                .body(Body::empty())
+                                .expect("build response");
+                            Ok::<_, hyper::Error>(resp)
+                        }
+                    }))
+                }
+            });
+            Server::from_tcp(listener)
+                .expect("start server")
+                .serve(make_service)
+                .await
+                .expect("serve");
+        });
+    });
+    (url, receiver)
+}
+
+fn webhook_config(url: &str, topics: Vec<NotifyWebhookTopic>) -> NotifyWebhookConfig {
+    NotifyWebhookConfig {
+        url: url.parse().expect("parse url"),
+        topics,
+        timeout_millis: 1000,
+        max_retries: 2,
+        retry_interval_millis: 10,
+        queue_size: 16,
+    }
+}
+
+#[test]
+fn test_payload() {
+    let (url, bodies) = start_receiver(StatusCode::OK);
+    let config = NotifyConfig {
+        webhooks: vec![webhook_config(&url, vec![])],
+        ..Default::default()
+    };
+    let controller = NotifyService::new(config).start(Some("NotifyService"));
+
+    let block = BlockBuilder::default().build();
+    controller.notify_new_block(block.clone());
+    let body = bodies.recv_timeout(TIMEOUT).expect("post notification");
+    assert_eq!(
+        body,
+        json!({
+            "topic": "new_block",
+            "data": ckb_jsonrpc_types::BlockView::from(block),
+        })
+    );
+}
+
+#[test]
+fn test_topic_filter() {
+    let (url, bodies) = start_receiver(StatusCode::OK);
+    let config = NotifyConfig {
+        webhooks: vec![webhook_config(&url, vec![NotifyWebhookTopic::NetworkAlert])],
+        ..Default::default()
+    };
+    let controller = NotifyService::new(config).start(Some("NotifyService"));
+
+    controller.notify_new_block(BlockBuilder::default().build());
+    controller.notify_network_alert(packed::Alert::default());
+    let body = bodies.recv_timeout(TIMEOUT).expect("post notification");
+    assert_eq!(body["topic"], "network_alert");
+    assert!(bodies.recv_timeout(Duration::from_millis(500)).is_err());
+}
+
+#[test]
+fn test_retry_cap() {
+    let (url, bodies) = start_receiver(StatusCode::INTERNAL_SERVER_ERROR);
+    let webhook = Webhook::start(webhook_config(&url, vec![]));
+
+    webhook.post(
+        NotifyWebhookTopic::NewBlock,
+        Bytes::from_static(b"{\"id\":1}"),
+    );
+    // the first attempt and 2 retries
+    for _ in 0..3 {
+        let body = bodies.recv_timeout(TIMEOUT).expect("post notification");
+        assert_eq!(body["id"], 1);
+    }
+    assert!(bodies.recv_timeout(Duration::from_millis(500)).is_err());
+}
+
+#[test]
+fn test_retry_in_background() {
+    let (url, bodies) = start_receiver(StatusCode::INTERNAL_SERVER_ERROR);
+    let mut config = webhook_config(&url, vec![]);
+    config.retry_interval_millis = 1000;
+    let webhook = Webhook::start(config);
+
+    webhook.post(
+        NotifyWebhookTopic::NewBlock,
+        Bytes::from_static(b"{\"id\":1}"),
+    );
+    webhook.post(
+        NotifyWebhookTopic::NewBlock,
+        Bytes::from_static(b"{\"id\":2}"),
+    );
+    // the second notification is posted before the first one is retried
+    let ids: Vec<_> = (0..4)
+        .map(|_| bodies.recv_timeout(TIMEOUT).expect("post notification")["id"].clone())
+        .collect();
+    assert_eq!(ids, vec![1, 2, 1, 2]);
+}
@@ -0,0 +1,161 @@
+//! Posts the notifications to the HTTP endpoints configured in `notify.webhooks`.
+use ckb_app_config::{NotifyWebhookConfig, NotifyWebhookTopic};
+use ckb_logger::{debug, error, warn};
+use hyper::{body::Bytes, client::HttpConnector, header, Body, Client, Method, Request};
+use serde::Serialize;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    Semaphore,
+};
+
+/// The max interval between two retries.
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The body posted to the webhooks.
+///
+/// The type of `data` is the same as the `params.result` in the push message of the corresponding
+/// subscription topic.
+#[derive(Serialize)]
+pub(crate) struct Notification<'a, T> {
+    pub(crate) topic: NotifyWebhookTopic,
+    pub(crate) data: &'a T,
+}
+
+/// A webhook with its queue of the notifications waiting to be posted.
+pub(crate) struct Webhook {
+    config: NotifyWebhookConfig,
+    sender: mpsc::Sender<Bytes>,
+}
+
+impl Webhook {
+    /// Starts a thread which posts the queued notifications to the endpoint one by one.
+    ///
+    /// A failed notification is retried in the background, so it never holds up the queue. The
+    /// thread exits once the webhook is dropped and the queue is drained.
+    pub(crate) fn start(config: NotifyWebhookConfig) -> Self {
+        let (sender, receiver) = mpsc::channel(config.queue_size.max(1));
+        let worker_config = config.clone();
+        thread::Builder::new()
+            .name("NotifyWebhook".to_string())
+            .spawn(move || run(worker_config, receiver))
+            .expect("Start notify webhook failed");
+        Webhook { config, sender }
+    }
+
+    /// Whether the topic is posted to the endpoint.
+    pub(crate) fn accepts(&self, topic: NotifyWebhookTopic) -> bool {
+        self.config.accepts(topic)
+    }
+
+    /// Queues the notification, it is dropped when the queue is full.
+    pub(crate) fn post(&self, topic: NotifyWebhookTopic, body: Bytes) {
+        match self.sender.try_send(body) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => warn!(
+                "the queue of webhook {} is full, drop the {:?} notification",
+                self.config.url, topic
+            ),
+            Err(TrySendError::Closed(_)) => error!(
+                "the worker of webhook {} has exited, drop the {:?} notification",
+                self.config.url, topic
+            ),
+        }
+    }
+}
+
+fn run(config: NotifyWebhookConfig, receiver: mpsc::Receiver<Bytes>) {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            error!(
+                "failed to start the worker of webhook {}: {}",
+                config.url, err
+            );
+            return;
+        }
+    };
+    runtime.block_on(deliver(&config, receiver));
+    debug!("webhook {} exits", config.url);
+}
+
+async fn deliver(config: &NotifyWebhookConfig, mut receiver: mpsc::Receiver<Bytes>) {
+    let client = Client::new();
+    // The notifications being retried are bounded by the queue size as well.
+    let retrying = Arc::new(Semaphore::new(config.queue_size.max(1)));
+    while let Some(body) = receiver.recv().await {
+        let err = match post(&client, config, body.clone()).await {
+            Ok(()) => continue,
+            Err(err) => err,
+        };
+        if config.max_retries == 0 {
+            warn!("failed to post to webhook {}: {}", config.url, err);
+            continue;
+        }
+        match Arc::clone(&retrying).try_acquire_owned() {
+            Ok(permit) => {
+                debug!(
+                    "failed to post to webhook {}: {}, retry in the background",
+                    config.url, err
+                );
+                let client = client.clone();
+                let config = config.clone();
+                tokio::spawn(async move {
+                    retry(&client, &config, body).await;
+                    drop(permit);
+                });
+            }
+            Err(_) => warn!(
+                "failed to post to webhook {}: {}, drop it since too many notifications are \
+                 being retried",
+                config.url, err
+            ),
+        }
+    }
+}
+
+async fn retry(client: &Client<HttpConnector, Body>, config: &NotifyWebhookConfig, body: Bytes) {
+    let mut interval = Duration::from_millis(config.retry_interval_millis);
+    for retries in 1..=config.max_retries {
+        tokio::time::sleep(interval).await;
+        match post(client, config, body.clone()).await {
+            Ok(()) => return,
+            Err(err) if retries < config.max_retries => {
+                interval = (interval * 2).min(MAX_RETRY_INTERVAL);
+                debug!(
+                    "failed to post to webhook {}: {}, retry in {:?}",
+                    config.url, err, interval
+                );
+            }
+            Err(err) => warn!(
+                "failed to post to webhook {} after {} retries: {}",
+                config.url, retries, err
+            ),
+        }
+    }
+}
+
+async fn post(
+    client: &Client<HttpConnector, Body>,
+    config: &NotifyWebhookConfig,
+    body: Bytes,
+) -> Result<(), String> {
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(config.url.as_str())
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .map_err(|err| err.to_string())?;
+    let timeout = Duration::from_millis(config.timeout_millis);
+    match tokio::time::timeout(timeout, client.request(req)).await {
+        Ok(Ok(resp)) if resp.status().is_success() => Ok(()),
+        Ok(Ok(resp)) => Err(format!("unexpected status {}", resp.status())),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(_) => Err("timed out".to_string()),
+    }
+}
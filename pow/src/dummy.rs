@@ -1,5 +1,8 @@
 use super::PowEngine;
-use ckb_types::packed::Header;
+use ckb_types::{
+    packed::{Byte32, Header},
+    U256,
+};
 
 /// TODO(doc): @quake
 pub struct DummyPowEngine;
@@ -8,4 +11,8 @@ impl PowEngine for DummyPowEngine {
     fn verify(&self, _header: &Header) -> bool {
         true
     }
+
+    fn verify_target(&self, _pow_hash: &Byte32, _nonce: u128, _target: &U256) -> bool {
+        true
+    }
 }
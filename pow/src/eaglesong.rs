@@ -1,5 +1,10 @@
 use super::PowEngine;
-use ckb_types::{packed::Header, prelude::*, utilities::compact_to_target, U256};
+use ckb_types::{
+    packed::{Byte32, Header},
+    prelude::*,
+    utilities::compact_to_target,
+    U256,
+};
 use eaglesong::eaglesong;
 use log::Level::Debug;
 use log::{debug, log_enabled};
@@ -59,4 +64,10 @@ impl PowEngine for EaglesongPowEngine {
 
         true
     }
+    fn verify_target(&self, pow_hash: &Byte32, nonce: u128, target: &U256) -> bool {
+        let input = crate::pow_message(pow_hash, nonce);
+        let mut output = [0u8; 32];
+        eaglesong(&input, &mut output);
+        U256::from_big_endian(&output[..]).expect("bound checked") <= *target
+    }
 }
@@ -1,6 +1,11 @@
 use super::PowEngine;
 use ckb_hash::blake2b_256;
-use ckb_types::{packed::Header, prelude::*, utilities::compact_to_target, U256};
+use ckb_types::{
+    packed::{Byte32, Header},
+    prelude::*,
+    utilities::compact_to_target,
+    U256,
+};
 use eaglesong::eaglesong;
 use log::Level::Debug;
 use log::{debug, log_enabled};
@@ -71,4 +76,13 @@ impl PowEngine for EaglesongBlake2bPowEngine {
 
         true
     }
+    fn verify_target(&self, pow_hash: &Byte32, nonce: u128, target: &U256) -> bool {
+        let input = crate::pow_message(pow_hash, nonce);
+        let output = {
+            let mut output_tmp = [0u8; 32];
+            eaglesong(&input, &mut output_tmp);
+            blake2b_256(&output_tmp)
+        };
+        U256::from_big_endian(&output[..]).expect("bound checked") <= *target
+    }
 }
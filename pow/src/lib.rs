@@ -3,6 +3,7 @@ use byteorder::{ByteOrder, LittleEndian};
 use ckb_types::{
     packed::{Byte32, Header},
     prelude::*,
+    U256,
 };
 use serde::{Deserialize, Serialize};
 use std::any::Any;
@@ -12,6 +13,7 @@ use std::sync::Arc;
 mod dummy;
 mod eaglesong;
 mod eaglesong_blake2b;
+mod eaglesong_lanes;
 
 #[cfg(test)]
 mod tests;
@@ -19,6 +21,7 @@ mod tests;
 pub use crate::dummy::DummyPowEngine;
 pub use crate::eaglesong::EaglesongPowEngine;
 pub use crate::eaglesong_blake2b::EaglesongBlake2bPowEngine;
+pub use crate::eaglesong_lanes::{EaglesongLanes, EAGLESONG_LANES};
 
 /// TODO(doc): @quake
 #[derive(Clone, Serialize, Deserialize, Eq, PartialEq, Hash, Debug)]
@@ -70,6 +73,12 @@ pub fn pow_message(pow_hash: &Byte32, nonce: u128) -> [u8; 48] {
 pub trait PowEngine: Send + Sync + AsAny {
     /// TODO(doc): @quake
     fn verify(&self, header: &Header) -> bool;
+
+    /// Checks whether the PoW output of the nonce is not above the target.
+    ///
+    /// Unlike `verify`, the target is not the one in the header, it is used to check the shares
+    /// whose target is easier than the block's.
+    fn verify_target(&self, pow_hash: &Byte32, nonce: u128, target: &U256) -> bool;
 }
 
 /// TODO(doc): @quake
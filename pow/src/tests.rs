@@ -1,6 +1,6 @@
-use crate::pow_message;
+use crate::{pow_message, EaglesongLanes, Pow, EAGLESONG_LANES};
 use ckb_hash::blake2b_256;
-use ckb_types::prelude::*;
+use ckb_types::{prelude::*, U256};
 
 #[test]
 fn test_pow_message() {
@@ -17,3 +17,30 @@ fn test_pow_message() {
         .to_vec()
     );
 }
+
+#[test]
+fn test_verify_target() {
+    let pow_hash = blake2b_256(&[]).pack();
+    for engine in &[Pow::Eaglesong.engine(), Pow::EaglesongBlake2b.engine()] {
+        assert!(engine.verify_target(&pow_hash, 0, &U256::max_value()));
+        assert!(!engine.verify_target(&pow_hash, 0, &U256::zero()));
+    }
+}
+
+#[test]
+fn test_eaglesong_lanes() {
+    for pow_hash in &[blake2b_256(&[]).pack(), blake2b_256(&[1]).pack()] {
+        let lanes = EaglesongLanes::new(pow_hash);
+        let mut nonces = [0u128; EAGLESONG_LANES];
+        for (i, nonce) in nonces.iter_mut().enumerate() {
+            *nonce = u128::max_value() - i as u128 * 0x0101_0101_0101_0101_0101;
+        }
+        nonces[0] = 0;
+        let outputs = lanes.hash(&nonces);
+        for (nonce, output) in nonces.iter().zip(outputs.iter()) {
+            let mut expected = [0u8; 32];
+            eaglesong::eaglesong(&pow_message(pow_hash, *nonce), &mut expected);
+            assert_eq!(output, &expected, "nonce {}", nonce);
+        }
+    }
+}
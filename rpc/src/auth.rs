@@ -0,0 +1,284 @@
+//! Authentication and access control of the RPC requests.
+use crate::error::RPCError;
+use crate::module::SubscriptionSession;
+use crate::rate_limit::{Client, RateLimiter, Remote};
+use ckb_app_config::{RpcCredential, RpcModule};
+use ckb_util::RwLock;
+use jsonrpc_core::{
+    futures::future::{self, Either},
+    middleware::{NoopCallFuture, NoopFuture},
+    Call, Error, Metadata, Middleware, Output, Params, Value,
+};
+use jsonrpc_pubsub::{PubSubMetadata, Session};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// The method used by the TCP and WebSocket connections to authenticate themselves.
+pub(crate) const AUTH_METHOD: &str = "auth";
+/// The methods which can be called without authentication.
+const PUBLIC_METHODS: &[&str] = &["ping"];
+/// The failed authentications allowed for each remote end in a burst.
+const FAILED_AUTH_BURST: u32 = 10;
+/// The failed authentications allowed for each remote end every second after the burst.
+const FAILED_AUTH_PER_SECOND: f64 = 1.0;
+
+/// The metadata of an RPC request.
+#[derive(Clone, Debug, Default)]
+pub struct RpcSession {
+    /// The subscription session of the TCP and WebSocket connections.
+    pub(crate) subscription: Option<SubscriptionSession>,
+    /// The value of the `Authorization` header of the HTTP request, or the authorization
+    /// accepted by the `auth` method of the TCP and WebSocket connections.
+    pub(crate) authorization: Arc<RwLock<Option<String>>>,
+    /// The index of the credential in `rpc.credentials` which the call is authenticated by.
+    pub(crate) credential: Option<usize>,
+    /// The remote end of the request.
+    pub(crate) remote: Remote,
+}
+
+impl RpcSession {
+    /// Creates the metadata of a TCP or WebSocket connection.
+    pub(crate) fn with_subscription(subscription: SubscriptionSession, remote: Remote) -> Self {
+        RpcSession {
+            subscription: Some(subscription),
+            authorization: Default::default(),
+            credential: None,
+            remote,
+        }
+    }
+
+    /// Creates the metadata of an HTTP request.
+    pub(crate) fn with_authorization(authorization: Option<String>, remote: Remote) -> Self {
+        RpcSession {
+            subscription: None,
+            authorization: Arc::new(RwLock::new(authorization)),
+            credential: None,
+            remote,
+        }
+    }
+}
+
+impl Metadata for RpcSession {}
+
+impl PubSubMetadata for RpcSession {
+    fn session(&self) -> Option<Arc<Session>> {
+        self.subscription
+            .as_ref()
+            .and_then(|subscription| subscription.session())
+    }
+}
+
+struct Access {
+    token: Option<String>,
+    basic: Option<String>,
+    modules: HashSet<RpcModule>,
+    methods: HashSet<String>,
+}
+
+impl Access {
+    fn matches(&self, authorization: &str) -> bool {
+        let (scheme, value) = match authorization.trim().split_once(' ') {
+            Some(parts) => parts,
+            None => return false,
+        };
+        let value = value.trim();
+        if scheme.eq_ignore_ascii_case("Bearer") {
+            self.token
+                .as_ref()
+                .map(|token| constant_time_eq(token.as_bytes(), value.as_bytes()))
+                .unwrap_or(false)
+        } else if scheme.eq_ignore_ascii_case("Basic") {
+            match (self.basic.as_ref(), base64::decode(value)) {
+                (Some(basic), Ok(decoded)) => constant_time_eq(basic.as_bytes(), &decoded),
+                _ => false,
+            }
+        } else {
+            false
+        }
+    }
+
+    fn allows(&self, method: &str, module: Option<&RpcModule>) -> bool {
+        self.methods.contains(method)
+            || module
+                .map(|module| self.modules.contains(module))
+                .unwrap_or(false)
+    }
+}
+
+/// Checks the credential of every RPC call against `rpc.credentials`.
+pub(crate) struct Authenticator {
+    accesses: Vec<Access>,
+    method_modules: HashMap<String, RpcModule>,
+    // the budget of the failed authentications of every remote end, which is always applied
+    // regardless of `rpc.rate_limit` to slow down guessing the credentials
+    failures: RateLimiter,
+}
+
+impl Authenticator {
+    /// Creates the authenticator from the credentials and the modules of all the RPC methods.
+    pub(crate) fn new(
+        credentials: &[RpcCredential],
+        method_modules: HashMap<String, RpcModule>,
+    ) -> Self {
+        let accesses = credentials
+            .iter()
+            .map(|credential| Access {
+                token: credential.token.clone(),
+                basic: match (&credential.username, &credential.password) {
+                    (Some(username), Some(password)) => Some(format!("{}:{}", username, password)),
+                    _ => None,
+                },
+                modules: credential.modules.iter().cloned().collect(),
+                methods: credential.methods.iter().cloned().collect(),
+            })
+            .collect();
+        Authenticator {
+            accesses,
+            method_modules,
+            failures: RateLimiter::with_rate(FAILED_AUTH_PER_SECOND, FAILED_AUTH_BURST),
+        }
+    }
+
+    /// Checks whether the remote end of the request has used up its failed authentications, in
+    /// which case the methods requiring authentication are rejected without checking the
+    /// credential.
+    fn is_blocked(&self, method: &str, meta: &RpcSession) -> bool {
+        !PUBLIC_METHODS.contains(&method)
+            && !self
+                .failures
+                .has_token(Client::Remote(meta.remote), Instant::now())
+    }
+
+    fn record_failure(&self, meta: &RpcSession) {
+        self.failures
+            .take(Client::Remote(meta.remote), 1, Instant::now());
+    }
+
+    fn access(&self, authorization: &str) -> Option<(usize, &Access)> {
+        self.accesses
+            .iter()
+            .enumerate()
+            .find(|(_, access)| access.matches(authorization))
+    }
+
+    /// Authenticates a TCP or WebSocket connection by the `auth` method.
+    fn auth(&self, params: Params, meta: &RpcSession) -> Result<Value, Error> {
+        let (authorization,): (String,) = params.parse()?;
+        if self.access(&authorization).is_none() {
+            self.record_failure(meta);
+            return Err(RPCError::unauthorized("The credential is invalid"));
+        }
+        *meta.authorization.write() = Some(authorization);
+        Ok(Value::Bool(true))
+    }
+
+    /// Checks whether the request is allowed to call the method.
+    ///
+    /// Returns the index of the credential which the request is authenticated by, or `None` for
+    /// the public methods.
+    pub(crate) fn check(&self, method: &str, meta: &RpcSession) -> Result<Option<usize>, Error> {
+        if PUBLIC_METHODS.contains(&method) {
+            return Ok(None);
+        }
+        let authorization = meta.authorization.read();
+        let (index, access) = authorization
+            .as_ref()
+            .and_then(|authorization| {
+                let access = self.access(authorization);
+                if access.is_none() {
+                    self.record_failure(meta);
+                }
+                access
+            })
+            .ok_or_else(|| {
+                RPCError::unauthorized(
+                    "The request must be authenticated via the `Authorization` header of HTTP, \
+                     or the `auth` method of the TCP and WebSocket connections",
+                )
+            })?;
+        if access.allows(method, self.method_modules.get(method)) {
+            Ok(Some(index))
+        } else {
+            Err(RPCError::rpc_method_is_not_allowed(method))
+        }
+    }
+}
+
+/// The middleware which rejects the unauthorized calls.
+///
+/// It does nothing when no credential is configured.
+#[derive(Clone, Default)]
+pub struct AuthMiddleware {
+    authenticator: Option<Arc<Authenticator>>,
+}
+
+impl AuthMiddleware {
+    pub(crate) fn new(authenticator: Option<Authenticator>) -> Self {
+        AuthMiddleware {
+            authenticator: authenticator.map(Arc::new),
+        }
+    }
+}
+
+impl Middleware<RpcSession> for AuthMiddleware {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(
+        &self,
+        call: Call,
+        mut meta: RpcSession,
+        next: F,
+    ) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, RpcSession) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let authenticator = match self.authenticator {
+            Some(ref authenticator) => authenticator,
+            None => return Either::Right(next(call, meta)),
+        };
+        let (method, params, id, jsonrpc) = match call {
+            Call::MethodCall(ref method_call) => (
+                &method_call.method,
+                &method_call.params,
+                Some(method_call.id.clone()),
+                method_call.jsonrpc,
+            ),
+            Call::Notification(ref notification) => (
+                &notification.method,
+                &notification.params,
+                None,
+                notification.jsonrpc,
+            ),
+            Call::Invalid { .. } => return Either::Right(next(call, meta)),
+        };
+        let result = if authenticator.is_blocked(method, &meta) {
+            Err(RPCError::too_many_failed_authentications(method))
+        } else if method == AUTH_METHOD {
+            authenticator.auth(params.clone(), &meta)
+        } else {
+            match authenticator.check(method, &meta) {
+                Ok(credential) => {
+                    meta.credential = credential;
+                    return Either::Right(next(call, meta));
+                }
+                Err(err) => Err(err),
+            }
+        };
+        let output = id.map(|id| Output::from(result, id, jsonrpc));
+        Either::Left(Box::pin(future::ready(output)))
+    }
+}
+
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    lhs.iter()
+        .zip(rhs.iter())
+        .fold(0u8, |acc, (l, r)| acc | (l ^ r))
+        == 0
+}
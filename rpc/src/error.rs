@@ -56,6 +56,21 @@ pub enum RPCError {
     ///
     /// Users have to edit the config file to fix the error.
     ConfigError = -7,
+    /// (-8): The request is not authenticated.
+    ///
+    /// The RPC server requires authentication when `rpc.credentials` is configured. HTTP requests
+    /// are authenticated by the `Authorization` header, and TCP and WebSocket connections are
+    /// authenticated by the `auth` method.
+    Unauthorized = -8,
+    /// (-9): The credential of the request is not allowed to call the RPC method.
+    RPCMethodIsNotAllowed = -9,
+    /// (-10): The client has exceeded the rate limit.
+    ///
+    /// Every client has a token bucket when `rpc.rate_limit` is configured, and each call takes
+    /// the tokens of its method cost. When authentication is enabled, the clients failing the
+    /// authentication too many times are also rejected for a while, regardless of
+    /// `rpc.rate_limit`. Please retry later.
+    RateLimitExceeded = -10,
     /// (-101): The CKB local node failed to broadcast a message to its peers.
     P2PFailedToBroadcast = -101,
     /// (-200): Internal database error.
@@ -76,6 +91,16 @@ pub enum RPCError {
     /// This is a fatal error usually caused by the underlying database used by CKB. Please back up
     /// the data directory and re-sync the chain from scratch.
     DatabaseIsCorrupt = -202,
+    /// (-203): The block is in the canonical chain, but its body has been pruned.
+    ///
+    /// The node runs in the pruned mode, which only keeps the bodies of the recent blocks. Please
+    /// query a node which keeps the full history.
+    BlockIsPruned = -203,
+    /// (-204): The block is in the canonical chain, but its block filter has not been built yet.
+    ///
+    /// The filters of the existing blocks are built in the background after the `Filter` protocol
+    /// is enabled. Please retry after the filters have been built to the block.
+    BlockFilterIsNotBuilt = -204,
     /// (-301): Failed to resolve the referenced cells and headers used in the transaction, as inputs or
     /// dependencies.
     TransactionFailedToResolve = -301,
@@ -101,6 +126,10 @@ pub enum RPCError {
     /// Pool rejects a large package of chained transactions to avoid certain kinds of DoS attacks.
     PoolRejectedTransactionByMaxAncestorsCountLimit = -1105,
     /// (-1106): The transaction is rejected because the pool has reached its limit.
+    ///
+    /// A full pool evicts the pending transactions with the lowest fee rates to make room for a
+    /// transaction paying a higher fee rate. The evicted transactions are rejected with this
+    /// error as well.
     PoolIsFull = -1106,
     /// (-1107): The transaction is already in the pool.
     PoolRejectedDuplicatedTransaction = -1107,
@@ -110,6 +139,21 @@ pub enum RPCError {
     PoolRejectedMalformedTransaction = -1108,
     /// (-1109): The transaction is expired from tx-pool after `expiry_hours`.
     TransactionExpired = -1109,
+    /// (-1110): The transaction conflicts with transactions in the pool and fails to replace
+    /// them by the replace-by-fee rules.
+    ///
+    /// See the error message for the reason, for example, the transaction does not pay enough
+    /// fee, or it conflicts with a proposed transaction.
+    PoolRejectedRBF = -1110,
+    /// (-1111): The transaction is removed from the pool because a conflicting transaction paying
+    /// a higher fee replaces it.
+    TransactionReplaced = -1111,
+    /// (-1200): The subscription cannot be resumed from the cursor, because the events after it
+    /// are no longer kept.
+    ///
+    /// The node keeps the recent events in memory, which are lost when the node restarts. Please
+    /// catch up by the other RPC methods and subscribe again without the cursor.
+    SubscriptionCursorTooOld = -1200,
 }
 
 impl RPCError {
@@ -170,6 +214,8 @@ impl RPCError {
             Reject::Resolve(_) => RPCError::TransactionFailedToResolve,
             Reject::Verification(_) => RPCError::TransactionFailedToVerify,
             Reject::Expiry(_) => RPCError::TransactionExpired,
+            Reject::RBFRejected(_) => RPCError::PoolRejectedRBF,
+            Reject::RBFReplaced(_) => RPCError::TransactionReplaced,
         };
         RPCError::custom_with_error(code, reject)
     }
@@ -235,6 +281,47 @@ impl RPCError {
         )
     }
 
+    /// RPC error which indicates that the request is not authenticated.
+    pub fn unauthorized<T: Display>(message: T) -> Error {
+        Self::custom(RPCError::Unauthorized, message)
+    }
+
+    /// RPC error which indicates that the credential is not allowed to call the method.
+    pub fn rpc_method_is_not_allowed(method: &str) -> Error {
+        Self::custom(
+            RPCError::RPCMethodIsNotAllowed,
+            format!(
+                "The credential is not allowed to call the RPC method `{}`. \
+                 Please add the method or its module to the credential in `rpc.credentials` of ckb.toml.",
+                method
+            ),
+        )
+    }
+
+    /// RPC error which indicates that the client has exceeded the rate limit.
+    pub fn rate_limit_exceeded(method: &str) -> Error {
+        Self::custom(
+            RPCError::RateLimitExceeded,
+            format!(
+                "The client has exceeded the rate limit `rpc.rate_limit` when calling the RPC method `{}`. \
+                 Please retry later.",
+                method
+            ),
+        )
+    }
+
+    /// RPC error which indicates that the client has failed the authentication too many times.
+    pub fn too_many_failed_authentications(method: &str) -> Error {
+        Self::custom(
+            RPCError::RateLimitExceeded,
+            format!(
+                "The client has failed the authentication too many times when calling the RPC method `{}`. \
+                 Please retry later.",
+                method
+            ),
+        )
+    }
+
     /// RPC error which indicates that the method is deprecated.
     ///
     /// Deprecated methods are disabled by default unless they are enabled via the config options
@@ -1,6 +1,9 @@
 //! See [module](module/index.html) for the RPC methods documentation.
 
+pub(crate) mod auth;
 pub(crate) mod error;
+pub(crate) mod metrics;
+pub(crate) mod rate_limit;
 pub(crate) mod server;
 pub(crate) mod service_builder;
 
@@ -14,4 +17,11 @@ pub use crate::server::RpcServer;
 pub use crate::service_builder::ServiceBuilder;
 
 #[doc(hidden)]
-pub type IoHandler = jsonrpc_pubsub::PubSubHandler<Option<crate::module::SubscriptionSession>>;
+pub type IoHandler = jsonrpc_pubsub::PubSubHandler<
+    crate::auth::RpcSession,
+    (
+        crate::metrics::MetricsMiddleware,
+        crate::auth::AuthMiddleware,
+        crate::rate_limit::RateLimitMiddleware,
+    ),
+>;
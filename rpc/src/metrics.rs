@@ -0,0 +1,71 @@
+//! Per-method metrics of the RPC calls.
+use crate::auth::RpcSession;
+use ckb_metrics::{metrics, Timer};
+use jsonrpc_core::{
+    futures::future::Either,
+    middleware::{NoopCallFuture, NoopFuture},
+    Call, Middleware, Output,
+};
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::Arc;
+
+/// The label of the calls to the methods which are not mounted, so the clients cannot flood the
+/// metrics with arbitrary method names.
+const UNKNOWN_METHOD: &str = "unknown";
+
+/// The middleware which counts the calls, the failed calls and the latency of each method.
+#[derive(Clone, Default)]
+pub struct MetricsMiddleware {
+    methods: Arc<HashSet<String>>,
+}
+
+impl MetricsMiddleware {
+    pub(crate) fn new(methods: HashSet<String>) -> Self {
+        MetricsMiddleware {
+            methods: Arc::new(methods),
+        }
+    }
+
+    fn label(&self, method: &str) -> String {
+        if self.methods.contains(method) {
+            method.to_owned()
+        } else {
+            UNKNOWN_METHOD.to_owned()
+        }
+    }
+}
+
+impl Middleware<RpcSession> for MetricsMiddleware {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: RpcSession, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, RpcSession) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let method = match call {
+            Call::MethodCall(ref method_call) => self.label(&method_call.method),
+            Call::Notification(ref notification) => self.label(&notification.method),
+            Call::Invalid { .. } => return Either::Right(next(call, meta)),
+        };
+        let timer = Timer::start();
+        let future = next(call, meta);
+        Either::Left(Box::pin(async move {
+            let output = future.await;
+            metrics!(timing, "ckb.rpc.latency", timer.stop(), "method" => method.clone());
+            metrics!(counter, "ckb.rpc.requests", 1, "method" => method.clone());
+            if let Some(Output::Failure(ref failure)) = output {
+                metrics!(
+                    counter,
+                    "ckb.rpc.errors",
+                    1,
+                    "method" => method,
+                    "code" => failure.error.code.code(),
+                );
+            }
+            output
+        }))
+    }
+}
@@ -1,8 +1,8 @@
 use crate::error::RPCError;
 use ckb_jsonrpc_types::{
-    BlockEconomicState, BlockNumber, BlockView, CellWithStatus, Consensus, EpochNumber, EpochView,
-    HeaderView, MerkleProof as JsonMerkleProof, OutPoint, ResponseFormat, Timestamp,
-    TransactionProof, TransactionWithStatus, Uint32,
+    BlockEconomicState, BlockFilter, BlockNumber, BlockView, CellWithStatus, Consensus,
+    EpochNumber, EpochView, HeaderView, MerkleProof as JsonMerkleProof, OutPoint, ResponseFormat,
+    Timestamp, TransactionProof, TransactionWithStatus, Uint32,
 };
 use ckb_logger::error;
 use ckb_reward_calculator::RewardCalculator;
@@ -68,6 +68,10 @@ pub trait ChainRpc {
     /// When `verbosity` is 0, it returns a 0x-prefixed hex string as the `result`. The string
     /// encodes the block serialized by molecule using schema `table Block`.
     ///
+    /// ## Errors
+    ///
+    /// * [`BlockIsPruned (-203)`](../enum.RPCError.html#variant.BlockIsPruned) - The block is in the canonical chain, but its body has been pruned by this node.
+    ///
     /// ## Examples
     ///
     /// Request
@@ -186,6 +190,7 @@ pub trait ChainRpc {
     ///
     /// * [`ChainIndexIsInconsistent (-201)`](../enum.RPCError.html#variant.ChainIndexIsInconsistent) - The index is inconsistent. It says a block hash is in the main chain, but cannot read it from the database.
     /// * [`DatabaseIsCorrupt (-202)`](../enum.RPCError.html#variant.DatabaseIsCorrupt) - The data read from database is dirty. Please report it as a bug.
+    /// * [`BlockIsPruned (-203)`](../enum.RPCError.html#variant.BlockIsPruned) - The block body has been pruned by this node.
     ///
     /// ## Examples
     ///
@@ -462,6 +467,10 @@ pub trait ChainRpc {
     /// When verbosity is 2: if tx_status.status is pending, proposed, or committed,
     /// the RPC returns the transaction content as field transaction, otherwise the field is null.
     ///
+    /// ## Errors
+    ///
+    /// * [`BlockIsPruned (-203)`](../enum.RPCError.html#variant.BlockIsPruned) - The transaction is committed, but the block body has been pruned by this node. It is only returned when verbosity is 0 or 2.
+    ///
     /// ## Examples
     ///
     /// Request
@@ -1133,7 +1142,8 @@ pub trait ChainRpc {
     ///             { "rfc": "0031", "epoch_number": null },
     ///             { "rfc": "0032", "epoch_number": null },
     ///             { "rfc": "0036", "epoch_number": null },
-    ///             { "rfc": "0038", "epoch_number": null }
+    ///             { "rfc": "0038", "epoch_number": null },
+    ///             { "rfc": "0044", "epoch_number": null }
     ///         ],
     ///         "id": "main",
     ///         "initial_primary_epoch_reward": "0x71afd498d000",
@@ -1206,6 +1216,54 @@ pub trait ChainRpc {
     /// ```
     #[rpc(name = "get_block_median_time")]
     fn get_block_median_time(&self, block_hash: H256) -> Result<Option<Timestamp>>;
+
+    /// Returns the compact block filter by block hash.
+    ///
+    /// The filters are built in the background when the `Filter` protocol is enabled in
+    /// `network.support_protocols`.
+    ///
+    /// ## Params
+    ///
+    /// * `block_hash` - the block hash.
+    ///
+    /// ## Returns
+    ///
+    /// The block filter data and the filter hash. Returns null when the block is not on the
+    /// canonical chain.
+    ///
+    /// ## Errors
+    ///
+    /// * [`BlockFilterIsNotBuilt (-204)`](../enum.RPCError.html#variant.BlockFilterIsNotBuilt) - The block is in the canonical chain, but its filter has not been built yet.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "get_block_filter",
+    ///   "params": [
+    ///     "0xa5f5c85987a15de25661e5a214f2c1449cd803f071acc7999820f25246471f40"
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": {
+    ///     "data": "0x0228a4c0f4e09a6b8e00",
+    ///     "hash": "0x4a3f4fa24b4d3a2aee0e4b7d0e2e8f6e0d3e35e71f2c0bbbbb4e46a2b0c8f3d1"
+    ///   }
+    /// }
+    /// ```
+    #[rpc(name = "get_block_filter")]
+    fn get_block_filter(&self, block_hash: H256) -> Result<Option<BlockFilter>>;
 }
 
 pub(crate) struct ChainRpcImpl {
@@ -1232,16 +1290,26 @@ impl ChainRpc for ChainRpcImpl {
             .map(|v| v.value())
             .unwrap_or(DEFAULT_BLOCK_VERBOSITY_LEVEL);
         // TODO: verbosity level == 1, output block only contains tx_hash in JSON format
-        if verbosity == 2 {
-            Ok(snapshot
+        let result = if verbosity == 2 {
+            snapshot
                 .get_block(&block_hash)
-                .map(|block| ResponseFormat::Json(block.into())))
+                .map(|block| ResponseFormat::Json(block.into()))
         } else if verbosity == 0 {
-            Ok(snapshot
+            snapshot
                 .get_packed_block(&block_hash)
-                .map(ResponseFormat::Hex))
+                .map(ResponseFormat::Hex)
         } else {
-            Err(RPCError::invalid_params("invalid verbosity level"))
+            return Err(RPCError::invalid_params("invalid verbosity level"));
+        };
+
+        match result {
+            None => match snapshot.get_block_number(&block_hash) {
+                Some(block_number) if snapshot.is_pruned(block_number) => {
+                    Err(self.block_is_pruned(block_number, &block_hash))
+                }
+                _ => Ok(None),
+            },
+            result => Ok(result),
         }
     }
 
@@ -1273,6 +1341,9 @@ impl ChainRpc for ChainRpcImpl {
         };
 
         result.ok_or_else(|| {
+            if snapshot.is_pruned(block_number.into()) {
+                return self.block_is_pruned(block_number.into(), &block_hash);
+            }
             let message = format!(
                 "Chain Index says block #{} is {:#x}, but that block is not in the database",
                 block_number, block_hash
@@ -1664,9 +1735,73 @@ impl ChainRpc for ChainRpcImpl {
         );
         Ok(Some(median_time.into()))
     }
+
+    fn get_block_filter(&self, block_hash: H256) -> Result<Option<BlockFilter>> {
+        let block_hash = block_hash.pack();
+        let snapshot = self.shared.snapshot();
+        if !snapshot.is_main_chain(&block_hash) {
+            return Ok(None);
+        }
+
+        match (
+            snapshot.get_block_filter(&block_hash),
+            snapshot.get_block_filter_hash(&block_hash),
+        ) {
+            (Some(data), Some(hash)) => Ok(Some(BlockFilter {
+                data: data.into(),
+                hash: hash.unpack(),
+            })),
+            _ => {
+                let block_number = snapshot
+                    .get_block_number(&block_hash)
+                    .expect("main chain block");
+                let latest_built = snapshot
+                    .get_latest_built_filter_data_block_hash()
+                    .and_then(|hash| snapshot.get_block_number(&hash));
+                let message = match latest_built {
+                    Some(latest_built) => format!(
+                        "the filter of block #{} {:#x} has not been built yet, the filters are built to #{}",
+                        block_number, block_hash, latest_built
+                    ),
+                    None => format!(
+                        "the filter of block #{} {:#x} has not been built yet, \
+                        please enable the Filter protocol to build the filters",
+                        block_number, block_hash
+                    ),
+                };
+                Err(RPCError::custom(RPCError::BlockFilterIsNotBuilt, message))
+            }
+        }
+    }
 }
 
 impl ChainRpcImpl {
+    fn check_transaction_pruned(&self, tx_hash: &packed::Byte32) -> Result<()> {
+        let snapshot = self.shared.snapshot();
+        match snapshot.get_transaction_info(tx_hash) {
+            Some(tx_info) if snapshot.is_pruned(tx_info.block_number) => {
+                Err(self.block_is_pruned(tx_info.block_number, &tx_info.block_hash))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn block_is_pruned(
+        &self,
+        block_number: core::BlockNumber,
+        block_hash: &packed::Byte32,
+    ) -> jsonrpc_core::Error {
+        RPCError::custom(
+            RPCError::BlockIsPruned,
+            format!(
+                "block #{} {:#x} has been pruned, this node only keeps the blocks since #{}",
+                block_number,
+                block_hash,
+                self.shared.snapshot().get_pruned_number()
+            ),
+        )
+    }
+
     fn get_transaction_verbosity0(
         &self,
         tx_hash: packed::Byte32,
@@ -1677,6 +1812,7 @@ impl ChainRpcImpl {
                 block_hash.unpack(),
             )));
         }
+        self.check_transaction_pruned(&tx_hash)?;
 
         let tx_pool = self.shared.tx_pool_controller();
         let fetch_tx_for_rpc = tx_pool.fetch_tx_for_rpc(tx_hash);
@@ -1733,6 +1869,7 @@ impl ChainRpcImpl {
                 block_hash.unpack(),
             )));
         }
+        self.check_transaction_pruned(&tx_hash)?;
 
         let tx_pool = self.shared.tx_pool_controller();
         let transaction_with_status = tx_pool.get_transaction_with_status(tx_hash);
@@ -1,8 +1,17 @@
-use ckb_jsonrpc_types::{ExtraLoggerConfig, MainLoggerConfig};
+use ckb_jsonrpc_types::{
+    BlockNumber, DbCheckFinding, DbCheckFindingKind, DbCheckReport, DbCheckState, DbCheckStatus,
+    ExtraLoggerConfig, MainLoggerConfig,
+};
 use ckb_logger_service::Logger;
+use ckb_shared::shared::Shared;
+use ckb_store::integrity::{self, CheckOptions, Finding, FindingKind, Report};
+use ckb_types::prelude::*;
+use ckb_util::Mutex;
 use jsonrpc_core::{Error, ErrorCode::InternalError, Result};
 use jsonrpc_derive::rpc;
-use std::time;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::{thread, time};
 
 /// RPC Module Debug for internal RPC methods.
 ///
@@ -33,9 +42,53 @@ pub trait DebugRpc {
     /// Removes the logger when this is null.
     #[rpc(name = "set_extra_logger")]
     fn set_extra_logger(&self, name: String, config_opt: Option<ExtraLoggerConfig>) -> Result<()>;
+    /// Starts checking the integrity of the chain data in the database and the freezer.
+    ///
+    /// The main chain blocks in the range are cross checked against the indexes, the headers,
+    /// the bodies and the freezer files.
+    ///
+    /// The check may read the whole database, so it runs in the background and the RPC returns
+    /// once it is started. Poll `get_db_check_status` for the report. Only one check runs at a
+    /// time, the RPC fails if a check is still running.
+    ///
+    /// The check runs on a snapshot and never modifies the database. Stop the node and run
+    /// `ckb db-check --fix` to rebuild the corrupted indexes.
+    ///
+    /// ## Params
+    ///
+    /// * `from` - The number of the first checked block, 0 when it is null.
+    /// * `to` - The number of the last checked block, the tip when it is null.
+    /// * `check_cells` - Whether to recompute the live cells created in the checked blocks, it is
+    /// false when it is null.
+    #[rpc(name = "check_db")]
+    fn check_db(
+        &self,
+        from: Option<BlockNumber>,
+        to: Option<BlockNumber>,
+        check_cells: Option<bool>,
+    ) -> Result<()>;
+    /// Returns the status of the database integrity check started by `check_db`.
+    #[rpc(name = "get_db_check_status")]
+    fn get_db_check_status(&self) -> Result<DbCheckStatus>;
 }
 
-pub(crate) struct DebugRpcImpl {}
+pub(crate) struct DebugRpcImpl {
+    shared: Shared,
+    db_check: Arc<Mutex<DbCheckStatus>>,
+}
+
+impl DebugRpcImpl {
+    pub fn new(shared: Shared) -> Self {
+        DebugRpcImpl {
+            shared,
+            db_check: Arc::new(Mutex::new(DbCheckStatus {
+                state: DbCheckState::Idle,
+                report: None,
+                error: None,
+            })),
+        }
+    }
+}
 
 impl DebugRpc for DebugRpcImpl {
     fn jemalloc_profiling_dump(&self) -> Result<String> {
@@ -90,4 +143,96 @@ impl DebugRpc for DebugRpcImpl {
             data: None,
         })
     }
+
+    fn check_db(
+        &self,
+        from: Option<BlockNumber>,
+        to: Option<BlockNumber>,
+        check_cells: Option<bool>,
+    ) -> Result<()> {
+        let options = CheckOptions {
+            from: from.map(Into::into).unwrap_or(0),
+            to: to.map(Into::into),
+            check_cells: check_cells.unwrap_or(false),
+        };
+        let mut status = self.db_check.lock();
+        if status.state == DbCheckState::Running {
+            return Err(Error {
+                code: InternalError,
+                message: "the database check is still running".to_string(),
+                data: None,
+            });
+        }
+
+        let snapshot = self.shared.snapshot();
+        let db_check = Arc::clone(&self.db_check);
+        thread::Builder::new()
+            .name("DbCheck".to_string())
+            .spawn(move || {
+                // A panic must not leave the status running forever.
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    integrity::check(snapshot.as_ref(), &options)
+                }));
+                let mut status = db_check.lock();
+                *status = match result {
+                    Ok(Ok(report)) => DbCheckStatus {
+                        state: DbCheckState::Done,
+                        report: Some(report_to_json(&report)),
+                        error: None,
+                    },
+                    Ok(Err(err)) => DbCheckStatus {
+                        state: DbCheckState::Failed,
+                        report: None,
+                        error: Some(err.to_string()),
+                    },
+                    Err(_) => DbCheckStatus {
+                        state: DbCheckState::Failed,
+                        report: None,
+                        error: Some("the database check panicked".to_string()),
+                    },
+                };
+            })
+            .map_err(|err| Error {
+                code: InternalError,
+                message: err.to_string(),
+                data: None,
+            })?;
+        *status = DbCheckStatus {
+            state: DbCheckState::Running,
+            report: None,
+            error: None,
+        };
+        Ok(())
+    }
+
+    fn get_db_check_status(&self) -> Result<DbCheckStatus> {
+        Ok(self.db_check.lock().clone())
+    }
+}
+
+fn report_to_json(report: &Report) -> DbCheckReport {
+    DbCheckReport {
+        from: report.from.into(),
+        to: report.to.into(),
+        cells_checked: report.cells_checked,
+        findings: report.findings.iter().map(finding_to_json).collect(),
+    }
+}
+
+fn finding_to_json(finding: &Finding) -> DbCheckFinding {
+    let kind = match finding.kind {
+        FindingKind::Index => DbCheckFindingKind::Index,
+        FindingKind::Header => DbCheckFindingKind::Header,
+        FindingKind::Body => DbCheckFindingKind::Body,
+        FindingKind::TransactionInfo => DbCheckFindingKind::TransactionInfo,
+        FindingKind::Freezer => DbCheckFindingKind::Freezer,
+        FindingKind::Cell => DbCheckFindingKind::Cell,
+    };
+    DbCheckFinding {
+        kind,
+        block_number: finding.block_number.map(Into::into),
+        block_hash: finding.block_hash.as_ref().map(Unpack::unpack),
+        message: finding.message.clone(),
+        fixable: finding.is_fixable(),
+    }
 }
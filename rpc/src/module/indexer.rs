@@ -0,0 +1,270 @@
+use crate::error::RPCError;
+use ckb_indexer::IndexerHandle;
+use ckb_jsonrpc_types::{
+    IndexerCell, IndexerCellsCapacity, IndexerOrder, IndexerPagination, IndexerSearchKey,
+    IndexerTx, JsonBytes, Uint32,
+};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+
+/// RPC Module Indexer which queries the live cells and the transactions by scripts.
+///
+/// The module requires the built-in indexer, which follows the canonical chain in a background
+/// thread. The indexer may lag behind the chain tip, use `get_cells_capacity` to get the block
+/// the indexer has synchronized to.
+#[rpc(server)]
+pub trait IndexerRpc {
+    /// Returns the live cells collection by the lock or type script.
+    ///
+    /// ## Params
+    ///
+    /// * `search_key` - The search conditions.
+    /// * `order` - The order of the returned cells, sorted by the block number, the transaction
+    ///   index and the output index.
+    /// * `limit` - The max number of the returned cells, it must be greater than 0.
+    /// * `after_cursor` - Pagination parameter, pass the `last_cursor` of the previous page to
+    ///   get the next page. (**Optional**)
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "get_cells",
+    ///   "params": [
+    ///     {
+    ///       "script": {
+    ///         "code_hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    ///         "hash_type": "data",
+    ///         "args": "0x"
+    ///       },
+    ///       "script_type": "lock",
+    ///       "with_data": false
+    ///     },
+    ///     "asc",
+    ///     "0x1"
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": {
+    ///     "last_cursor": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+    ///     "objects": [
+    ///       {
+    ///         "block_number": "0x0",
+    ///         "out_point": {
+    ///           "index": "0x0",
+    ///           "tx_hash": "0xa4037a893eb48e18ed4ef61034ce26eba9c585f15c9cee102ae58505565eccc3"
+    ///         },
+    ///         "output": {
+    ///           "capacity": "0x802665800",
+    ///           "lock": {
+    ///             "args": "0x",
+    ///             "code_hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    ///             "hash_type": "data"
+    ///           },
+    ///           "type": null
+    ///         },
+    ///         "output_data": null,
+    ///         "tx_index": "0x0"
+    ///       }
+    ///     ]
+    ///   }
+    /// }
+    /// ```
+    #[rpc(name = "get_cells")]
+    fn get_cells(
+        &self,
+        search_key: IndexerSearchKey,
+        order: IndexerOrder,
+        limit: Uint32,
+        after_cursor: Option<JsonBytes>,
+    ) -> Result<IndexerPagination<IndexerCell>>;
+
+    /// Returns the transactions collection by the lock or type script.
+    ///
+    /// A transaction is returned once per each of its inputs and outputs matched by the search
+    /// key. Only `block_range` is supported in the `filter` of the search key.
+    ///
+    /// ## Params
+    ///
+    /// * `search_key` - The search conditions.
+    /// * `order` - The order of the returned transactions, sorted by the block number, the
+    ///   transaction index, the input or output index and the input or output type.
+    /// * `limit` - The max number of the returned transactions, it must be greater than 0.
+    /// * `after_cursor` - Pagination parameter, pass the `last_cursor` of the previous page to
+    ///   get the next page. (**Optional**)
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "get_transactions",
+    ///   "params": [
+    ///     {
+    ///       "script": {
+    ///         "code_hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    ///         "hash_type": "data",
+    ///         "args": "0x"
+    ///       },
+    ///       "script_type": "lock",
+    ///       "filter": {
+    ///         "block_range": ["0x0", "0x1"]
+    ///       }
+    ///     },
+    ///     "asc",
+    ///     "0x1"
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": {
+    ///     "last_cursor": "0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001",
+    ///     "objects": [
+    ///       {
+    ///         "block_number": "0x0",
+    ///         "io_index": "0x0",
+    ///         "io_type": "output",
+    ///         "tx_hash": "0xa4037a893eb48e18ed4ef61034ce26eba9c585f15c9cee102ae58505565eccc3",
+    ///         "tx_index": "0x0"
+    ///       }
+    ///     ]
+    ///   }
+    /// }
+    /// ```
+    #[rpc(name = "get_transactions")]
+    fn get_transactions(
+        &self,
+        search_key: IndexerSearchKey,
+        order: IndexerOrder,
+        limit: Uint32,
+        after_cursor: Option<JsonBytes>,
+    ) -> Result<IndexerPagination<IndexerTx>>;
+
+    /// Returns the total capacity of the live cells by the lock or type script.
+    ///
+    /// Returns null if the indexer has not indexed any block yet.
+    ///
+    /// ## Params
+    ///
+    /// * `search_key` - The search conditions.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "get_cells_capacity",
+    ///   "params": [
+    ///     {
+    ///       "script": {
+    ///         "code_hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    ///         "hash_type": "data",
+    ///         "args": "0x"
+    ///       },
+    ///       "script_type": "lock"
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": {
+    ///     "block_hash": "0xa5f5c85987a15de25661e5a214f2c1449cd803f071acc7999820f25246471f40",
+    ///     "block_number": "0x400",
+    ///     "capacity": "0x802665800"
+    ///   }
+    /// }
+    /// ```
+    #[rpc(name = "get_cells_capacity")]
+    fn get_cells_capacity(
+        &self,
+        search_key: IndexerSearchKey,
+    ) -> Result<Option<IndexerCellsCapacity>>;
+}
+
+pub(crate) struct IndexerRpcImpl {
+    pub handle: IndexerHandle,
+}
+
+impl IndexerRpc for IndexerRpcImpl {
+    fn get_cells(
+        &self,
+        search_key: IndexerSearchKey,
+        order: IndexerOrder,
+        limit: Uint32,
+        after_cursor: Option<JsonBytes>,
+    ) -> Result<IndexerPagination<IndexerCell>> {
+        let limit = check_limit(limit)?;
+        self.handle
+            .get_cells(search_key, order, limit, after_cursor)
+            .map_err(RPCError::from_ckb_error)
+    }
+
+    fn get_transactions(
+        &self,
+        search_key: IndexerSearchKey,
+        order: IndexerOrder,
+        limit: Uint32,
+        after_cursor: Option<JsonBytes>,
+    ) -> Result<IndexerPagination<IndexerTx>> {
+        let limit = check_limit(limit)?;
+        if let Some(filter) = &search_key.filter {
+            if filter.script.is_some()
+                || filter.output_data.is_some()
+                || filter.output_data_len_range.is_some()
+                || filter.output_capacity_range.is_some()
+            {
+                return Err(RPCError::invalid_params(
+                    "Only block_range is supported in the filter of get_transactions",
+                ));
+            }
+        }
+        self.handle
+            .get_transactions(search_key, order, limit, after_cursor)
+            .map_err(RPCError::from_ckb_error)
+    }
+
+    fn get_cells_capacity(
+        &self,
+        search_key: IndexerSearchKey,
+    ) -> Result<Option<IndexerCellsCapacity>> {
+        self.handle
+            .get_cells_capacity(search_key)
+            .map_err(RPCError::from_ckb_error)
+    }
+}
+
+fn check_limit(limit: Uint32) -> Result<usize> {
+    match limit.value() {
+        0 => Err(RPCError::invalid_params("limit should be greater than 0")),
+        limit => Ok(limit as usize),
+    }
+}
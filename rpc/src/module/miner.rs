@@ -1,9 +1,10 @@
 use crate::error::RPCError;
 use ckb_chain::chain::ChainController;
-use ckb_jsonrpc_types::{Block, BlockTemplate, Uint64, Version};
+use ckb_jsonrpc_types::{Block, BlockTemplate, PayoutTarget, Uint64, Version};
 use ckb_logger::{debug, error, info};
 use ckb_network::{NetworkController, SupportProtocols};
 use ckb_shared::{shared::Shared, Snapshot};
+use ckb_tx_pool::block_assembler::{self, PayoutTarget as BlockAssemblerPayoutTarget};
 use ckb_types::{core, packed, prelude::*, H256};
 use ckb_verification::HeaderVerifier;
 use ckb_verification_traits::Verifier;
@@ -221,6 +222,166 @@ pub trait MinerRpc {
     /// ```
     #[rpc(name = "submit_block")]
     fn submit_block(&self, work_id: String, block: Block) -> Result<H256>;
+
+    /// Returns the payout targets which receive the block rewards in turn.
+    ///
+    /// An empty list means all the block rewards go to the lock in the `block_assembler` config.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "get_payout_targets",
+    ///   "params": []
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": [
+    ///     {
+    ///       "lock": {
+    ///         "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+    ///         "hash_type": "type",
+    ///         "args": "0x0000000000000000000000000000000000000001"
+    ///       },
+    ///       "weight": "0x3"
+    ///     },
+    ///     {
+    ///       "lock": {
+    ///         "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+    ///         "hash_type": "type",
+    ///         "args": "0x0000000000000000000000000000000000000002"
+    ///       },
+    ///       "weight": "0x1"
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    #[rpc(name = "get_payout_targets")]
+    fn get_payout_targets(&self) -> Result<Vec<PayoutTarget>>;
+
+    /// Replaces the payout targets which receive the block rewards in turn.
+    ///
+    /// This is a payout rotation rather than a split of each reward: the consensus only allows the
+    /// cellbase transaction to pay the whole block reward to a single lock, so no block can pay
+    /// several locks, fixed amounts, or a remainder. Instead the targets take turns in the blocks
+    /// found by this node. In every `sum(weight)` found blocks, each target receives the rewards
+    /// of `weight` blocks. The current block template is rebuilt with the new targets, and the
+    /// rotation restarts from the first target.
+    ///
+    /// Like the `block_assembler` config, the locks must be the recommended secp256k1 lock unless
+    /// the node is started with `ckb run --ba-advanced`.
+    ///
+    /// The change is not persisted, the targets in the `block_assembler` config are restored
+    /// after restarting, and their rotation restarts from the first target.
+    ///
+    /// ## Params
+    ///
+    /// * `payout_targets` - The new payout targets. The weights must be positive. An empty list
+    /// pays all the block rewards to the lock in the `block_assembler` config.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "set_payout_targets",
+    ///   "params": [
+    ///     [
+    ///       {
+    ///         "lock": {
+    ///           "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+    ///           "hash_type": "type",
+    ///           "args": "0x0000000000000000000000000000000000000001"
+    ///         },
+    ///         "weight": "0x3"
+    ///       },
+    ///       {
+    ///         "lock": {
+    ///           "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+    ///           "hash_type": "type",
+    ///           "args": "0x0000000000000000000000000000000000000002"
+    ///         },
+    ///         "weight": "0x1"
+    ///       }
+    ///     ]
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": null
+    /// }
+    /// ```
+    #[rpc(name = "set_payout_targets")]
+    fn set_payout_targets(&self, payout_targets: Vec<PayoutTarget>) -> Result<()>;
+
+    /// Changes the rank of a transaction in the block templates, like `prioritisetransaction` in
+    /// bitcoind.
+    ///
+    /// The fee delta is added to the fee of the transaction when the block assembler ranks the
+    /// proposals and the commitments, the fee actually paid is unchanged. The deltas of the same
+    /// transaction accumulate, and they are dropped once the transaction is committed or the
+    /// delta has not been updated in 24 hours. At most 10000 transactions can be prioritised.
+    ///
+    /// This RPC requires the `prioritised` policy in `block_assembler.tx_selection`.
+    ///
+    /// ## Params
+    ///
+    /// * `tx_hash` - The transaction hash. The transaction does not have to be in the pool yet.
+    /// * `fee_delta` - The fee delta in shannons.
+    /// * `decrease` - Subtracts the fee delta instead when it is true. The default is false.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "prioritise_transaction",
+    ///   "params": [
+    ///     "0x365698b50ca0da75dca2c87f9e7b563811d3b5813736b8cc62cc3b106faceb17",
+    ///     "0x5f5e100",
+    ///     false
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": null
+    /// }
+    /// ```
+    #[rpc(name = "prioritise_transaction")]
+    fn prioritise_transaction(
+        &self,
+        tx_hash: H256,
+        fee_delta: Uint64,
+        decrease: Option<bool>,
+    ) -> Result<()>;
 }
 
 pub(crate) struct MinerRpcImpl {
@@ -311,6 +472,68 @@ impl MinerRpc for MinerRpcImpl {
 
         Ok(header.hash().unpack())
     }
+
+    fn get_payout_targets(&self) -> Result<Vec<PayoutTarget>> {
+        let payout_targets = self
+            .shared
+            .tx_pool_controller()
+            .get_payout_targets()
+            .map_err(|err| {
+                error!("send get_payout_targets request error {}", err);
+                RPCError::ckb_internal_error(err)
+            })?
+            .map_err(RPCError::from_any_error)?;
+        Ok(payout_targets
+            .into_iter()
+            .map(|target| PayoutTarget {
+                lock: target.lock.into(),
+                weight: target.weight.into(),
+            })
+            .collect())
+    }
+
+    fn set_payout_targets(&self, payout_targets: Vec<PayoutTarget>) -> Result<()> {
+        let payout_targets: Vec<_> = payout_targets
+            .into_iter()
+            .map(|target| BlockAssemblerPayoutTarget {
+                lock: target.lock.into(),
+                weight: target.weight.into(),
+            })
+            .collect();
+        block_assembler::check_payout_targets(&payout_targets)
+            .map_err(|err| RPCError::invalid_params(err.to_string()))?;
+        self.shared
+            .tx_pool_controller()
+            .set_payout_targets(payout_targets)
+            .map_err(|err| {
+                error!("send set_payout_targets request error {}", err);
+                RPCError::ckb_internal_error(err)
+            })?
+            .map_err(RPCError::from_any_error)
+    }
+
+    fn prioritise_transaction(
+        &self,
+        tx_hash: H256,
+        fee_delta: Uint64,
+        decrease: Option<bool>,
+    ) -> Result<()> {
+        let fee_delta = i64::try_from(fee_delta.value())
+            .map_err(|_| RPCError::invalid_params("fee_delta is too large"))?;
+        let fee_delta = if decrease.unwrap_or(false) {
+            -fee_delta
+        } else {
+            fee_delta
+        };
+        self.shared
+            .tx_pool_controller()
+            .prioritise_transaction(tx_hash.pack(), fee_delta)
+            .map_err(|err| {
+                error!("send prioritise_transaction request error {}", err);
+                RPCError::ckb_internal_error(err)
+            })?
+            .map_err(RPCError::from_any_error)
+    }
 }
 
 fn handle_submit_error<E: std::fmt::Display + Debug>(work_id: &str, err: &E) -> Error {
@@ -114,17 +114,19 @@ mod alert;
 mod chain;
 mod debug;
 mod experiment;
+mod indexer;
 mod miner;
 mod net;
 pub(crate) mod pool;
 mod stats;
-mod subscription;
+pub(crate) mod subscription;
 mod test;
 
 pub(crate) use self::alert::AlertRpcImpl;
 pub(crate) use self::chain::ChainRpcImpl;
 pub(crate) use self::debug::DebugRpcImpl;
 pub(crate) use self::experiment::ExperimentRpcImpl;
+pub(crate) use self::indexer::IndexerRpcImpl;
 pub(crate) use self::miner::MinerRpcImpl;
 pub(crate) use self::net::NetRpcImpl;
 pub(crate) use self::pool::PoolRpcImpl;
@@ -136,6 +138,7 @@ pub use self::alert::AlertRpc;
 pub use self::chain::ChainRpc;
 pub use self::debug::DebugRpc;
 pub use self::experiment::ExperimentRpc;
+pub use self::indexer::IndexerRpc;
 pub use self::miner::MinerRpc;
 pub use self::net::NetRpc;
 pub use self::pool::PoolRpc;
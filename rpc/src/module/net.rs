@@ -1,13 +1,14 @@
 use crate::error::RPCError;
 use ckb_jsonrpc_types::{
-    BannedAddr, LocalNode, LocalNodeProtocol, NodeAddress, PeerSyncState, RemoteNode,
-    RemoteNodeProtocol, SyncState, Timestamp,
+    BannedAddr, LocalNode, LocalNodeProtocol, NodeAddress, PeerDownloadState, PeerSyncState,
+    RemoteNode, RemoteNodeProtocol, SyncState, Timestamp,
 };
 use ckb_network::{extract_peer_id, multiaddr::Multiaddr, NetworkController};
 use ckb_sync::SyncShared;
 use faketime::unix_time_as_millis;
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 const MAX_ADDRS: usize = 50;
@@ -361,6 +362,7 @@ pub trait NetRpc {
     ///   "result": {
     ///     "best_known_block_number": "0x400",
     ///     "best_known_block_timestamp": "0x5cd2b117",
+    ///     "download_peers": [],
     ///     "fast_time": "0x3e8",
     ///     "ibd": true,
     ///     "inflight_blocks_count": "0x0",
@@ -716,6 +718,32 @@ impl NetRpc for NetRpcImpl {
         let state = chain.shared().state();
         let (fast_time, normal_time, low_time) = state.read_inflight_blocks().division_point();
         let best_known = state.shared_best_header();
+        let node_ids: HashMap<_, _> = self
+            .network_controller
+            .connected_peers()
+            .into_iter()
+            .filter_map(|(peer_index, peer)| {
+                extract_peer_id(&peer.connected_addr)
+                    .map(|peer_id| (peer_index, peer_id.to_base58()))
+            })
+            .collect();
+        let download_peers = state
+            .read_inflight_blocks()
+            .download_stats()
+            .into_iter()
+            .filter_map(|stats| {
+                node_ids.get(&stats.peer).map(|node_id| PeerDownloadState {
+                    node_id: node_id.clone(),
+                    inflight_count: (stats.inflight_count as u64).into(),
+                    window_size: (stats.task_count as u64).into(),
+                    latency: stats.latency.into(),
+                    throughput: stats.throughput.into(),
+                    received_count: stats.received_count.into(),
+                    stalled_count: stats.stalled_count.into(),
+                    timeout_count: stats.timeout_count.into(),
+                })
+            })
+            .collect();
         let sync_state = SyncState {
             ibd: chain.is_initial_block_download(),
             best_known_block_number: best_known.number().into(),
@@ -726,6 +754,7 @@ impl NetRpc for NetRpcImpl {
             fast_time: fast_time.into(),
             normal_time: normal_time.into(),
             low_time: low_time.into(),
+            download_peers,
         };
 
         Ok(sync_state)
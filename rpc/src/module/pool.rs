@@ -1,10 +1,13 @@
 use crate::error::RPCError;
 use ckb_chain_spec::consensus::Consensus;
 use ckb_constant::hardfork::{mainnet, testnet};
-use ckb_jsonrpc_types::{OutputsValidator, RawTxPool, Script, Transaction, TxPoolInfo};
+use ckb_jsonrpc_types::{
+    EstimatedFeeRate, OutputsValidator, RawTxPool, Script, Transaction, TxPoolInfo, Uint32, Uint64,
+};
 use ckb_logger::error;
 use ckb_script::IllTransactionChecker;
 use ckb_shared::{shared::Shared, Snapshot};
+use ckb_tx_pool::{DEFAULT_CONFIDENCE, MAX_TARGET_BLOCKS};
 use ckb_types::{core, packed, prelude::*, H256};
 use ckb_verification::{Since, SinceMetric, TxVerifyEnv};
 use jsonrpc_core::Result;
@@ -30,6 +33,7 @@ pub trait PoolRpc {
     /// * [`PoolRejectedTransactionByMaxAncestorsCountLimit (-1105)`](../enum.RPCError.html#variant.PoolRejectedTransactionByMaxAncestorsCountLimit) - The ancestors count must be greater than or equal to the config option `tx_pool.max_ancestors_count`.
     /// * [`PoolIsFull (-1106)`](../enum.RPCError.html#variant.PoolIsFull) - Pool is full.
     /// * [`PoolRejectedDuplicatedTransaction (-1107)`](../enum.RPCError.html#variant.PoolRejectedDuplicatedTransaction) - The transaction is already in the pool.
+    /// * [`PoolRejectedRBF (-1110)`](../enum.RPCError.html#variant.PoolRejectedRBF) - The transaction conflicts with transactions in the pool and fails to replace them. It only happens when the config option `tx_pool.enable_rbf` is true.
     /// * [`TransactionFailedToResolve (-301)`](../enum.RPCError.html#variant.TransactionFailedToResolve) - Failed to resolve the referenced cells and headers used in the transaction, as inputs or dependencies.
     /// * [`TransactionFailedToVerify (-302)`](../enum.RPCError.html#variant.TransactionFailedToVerify) - Failed to verify the transaction.
     ///
@@ -274,11 +278,62 @@ pub trait PoolRpc {
     /// ```
     #[rpc(name = "tx_pool_ready")]
     fn tx_pool_ready(&self) -> Result<bool>;
+
+    /// Estimates the fee rate required to get a transaction committed within the target blocks.
+    ///
+    /// The estimation is based on the recent history of the transactions entering the pending
+    /// pool and the blocks in which they were committed. The transactions are grouped by fee
+    /// rate, the lowest group which has at least `confidence` percent of transactions committed
+    /// within `target_blocks` blocks determines the result.
+    ///
+    /// ## Params
+    ///
+    /// * `target_blocks` - The number of blocks the transaction is expected to be committed
+    ///   within. It must be in the range `[1, 128]`.
+    /// * `confidence` - The confidence in percentage, it must be in the range `[1, 99]`.
+    ///   (**Optional**, default is 85)
+    ///
+    /// ## Returns
+    ///
+    /// Returns null if the node has not collected enough samples yet, for example, the node has
+    /// just started or the chain is idle.
+    ///
+    /// ## Examples
+    ///
+    /// Request
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "method": "estimate_fee_rate",
+    ///   "params": ["0xa", "0x55"]
+    /// }
+    /// ```
+    ///
+    /// Response
+    ///
+    /// ```json
+    /// {
+    ///   "id": 42,
+    ///   "jsonrpc": "2.0",
+    ///   "result": {
+    ///     "confidence": "0x5a",
+    ///     "fee_rate": "0x7d0",
+    ///     "target_blocks": "0xa"
+    ///   }
+    /// }
+    /// ```
+    #[rpc(name = "estimate_fee_rate")]
+    fn estimate_fee_rate(
+        &self,
+        target_blocks: Uint64,
+        confidence: Option<Uint32>,
+    ) -> Result<Option<EstimatedFeeRate>>;
 }
 
 pub(crate) struct PoolRpcImpl {
     shared: Shared,
-    min_fee_rate: core::FeeRate,
     reject_ill_transactions: bool,
     well_known_lock_scripts: Vec<packed::Script>,
     well_known_type_scripts: Vec<packed::Script>,
@@ -287,7 +342,6 @@ pub(crate) struct PoolRpcImpl {
 impl PoolRpcImpl {
     pub fn new(
         shared: Shared,
-        min_fee_rate: core::FeeRate,
         reject_ill_transactions: bool,
         mut extra_well_known_lock_scripts: Vec<packed::Script>,
         mut extra_well_known_type_scripts: Vec<packed::Script>,
@@ -302,7 +356,6 @@ impl PoolRpcImpl {
 
         PoolRpcImpl {
             shared,
-            min_fee_rate,
             reject_ill_transactions,
             well_known_lock_scripts,
             well_known_type_scripts,
@@ -478,7 +531,7 @@ impl PoolRpc for PoolRpcImpl {
             orphan: (tx_pool_info.orphan_size as u64).into(),
             total_tx_size: (tx_pool_info.total_tx_size as u64).into(),
             total_tx_cycles: tx_pool_info.total_tx_cycles.into(),
-            min_fee_rate: self.min_fee_rate.as_u64().into(),
+            min_fee_rate: tx_pool_info.min_fee_rate.as_u64().into(),
             last_txs_updated_at: tx_pool_info.last_txs_updated_at.into(),
         })
     }
@@ -509,6 +562,39 @@ impl PoolRpc for PoolRpcImpl {
         };
         Ok(raw)
     }
+
+    fn estimate_fee_rate(
+        &self,
+        target_blocks: Uint64,
+        confidence: Option<Uint32>,
+    ) -> Result<Option<EstimatedFeeRate>> {
+        let target_blocks = target_blocks.value();
+        if target_blocks == 0 || target_blocks > MAX_TARGET_BLOCKS {
+            return Err(RPCError::invalid_params(format!(
+                "target_blocks should be in the range [1, {}]",
+                MAX_TARGET_BLOCKS
+            )));
+        }
+        let confidence = confidence.map_or(DEFAULT_CONFIDENCE, |c| c.value());
+        if confidence == 0 || confidence >= 100 {
+            return Err(RPCError::invalid_params(
+                "confidence should be in the range [1, 99]",
+            ));
+        }
+
+        let tx_pool = self.shared.tx_pool_controller();
+        let estimate = tx_pool
+            .estimate_fee_rate(target_blocks, confidence)
+            .map_err(|err| {
+                error!("send estimate_fee_rate request error {}", err);
+                RPCError::ckb_internal_error(err)
+            })?;
+        Ok(estimate.map(|estimate| EstimatedFeeRate {
+            fee_rate: estimate.fee_rate.as_u64().into(),
+            target_blocks: estimate.target_blocks.into(),
+            confidence: estimate.confidence.into(),
+        }))
+    }
 }
 
 pub(crate) struct WellKnownScriptsOnlyValidator<'a> {
@@ -1,15 +1,30 @@
+use crate::auth::RpcSession;
+use crate::error::RPCError;
 use ckb_channel::select;
-use ckb_jsonrpc_types::Topic;
+use ckb_jsonrpc_types::{
+    JsonBytes, MatchedBlock, MatchedCell, MatchedTransaction, PoolTransactionReject,
+    SubscriptionCursor, SubscriptionFilter, Topic,
+};
 use ckb_logger::error;
-use ckb_notify::NotifyController;
+use ckb_shared::Shared;
+use ckb_store::{ChainDB, ChainStore};
+use ckb_types::{
+    bytes::Bytes,
+    core::{BlockNumber, BlockView, TransactionView},
+    packed,
+    prelude::*,
+};
+use faketime::unix_time_as_millis;
 use jsonrpc_core::{Metadata, Result};
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
     PubSubMetadata, Session, SubscriptionId,
 };
+use serde::Serialize;
+use std::cmp;
 use std::collections::HashMap;
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
 use std::sync::{
     atomic::{AtomicUsize, Ordering},
@@ -86,7 +101,9 @@ pub trait SubscriptionRpc {
     ///
     /// ## Params
     ///
-    /// * `topic` - Subscription topic (enum: new_tip_header | new_tip_block | new_transaction | proposed_transaction | rejected_transaction)
+    /// * `topic` - Subscription topic (enum: new_tip_header | new_tip_block | new_transaction | proposed_transaction | rejected_transaction | chain_reorg)
+    /// * `from` - Resumes the subscription after the last event the subscriber has received (**Optional**).
+    /// * `filter` - Only pushes the transactions and the cells matching the filter (**Optional**).
     ///
     /// ## Returns
     ///
@@ -107,6 +124,55 @@ pub trait SubscriptionRpc {
     /// }
     /// ```
     ///
+    /// ## Resumable Subscriptions
+    ///
+    /// When `from` is given, the events published after it are replayed before the live events,
+    /// and the `params.result` in the push message is wrapped with the sequence number of the event:
+    ///
+    /// ```json+skip
+    /// {
+    ///   "seq": "0x2a",
+    ///   "data": { ... }
+    /// }
+    /// ```
+    ///
+    /// The sequence numbers of each topic increase monotonically.
+    ///
+    /// The sequence number of the block topics `new_tip_header` and `new_tip_block` is the block
+    /// number. When the chain is reorganized to a tip whose number is not greater than the last
+    /// published one, the new tip takes the last sequence number plus one instead, so the
+    /// sequence numbers never go back. The recent 1000 published blocks can be replayed,
+    /// including the ones rolled back since. The shifted sequence numbers are not kept when the
+    /// node restarts, use the block hash as the cursor to resume exactly across restarts.
+    ///
+    /// The other topics replay the recent events kept in memory, at most 1024 events and 16 MiB
+    /// for each topic. Their sequence numbers start from a value derived from the time the node
+    /// starts, so the cursors published before a restart are detected.
+    ///
+    /// When the events after `from` are no longer kept, for example, the node has restarted or
+    /// the subscriber has fallen too far behind, the subscription is rejected with the error
+    /// [`SubscriptionCursorTooOld (-1200)`](../enum.RPCError.html#variant.SubscriptionCursorTooOld).
+    /// If the events published during the replay are dropped before they are sent, the
+    /// subscription is closed by an error notification with the same code instead. The
+    /// subscriber should catch up by the other RPC methods and subscribe without `from`.
+    ///
+    /// The `from` is a [`SubscriptionCursor`](../../ckb_jsonrpc_types/enum.SubscriptionCursor.html).
+    ///
+    /// ## Filtered Subscriptions
+    ///
+    /// The topics `new_tip_block`, `new_transaction`, `proposed_transaction` and
+    /// `rejected_transaction` accept a
+    /// [`SubscriptionFilter`](../../ckb_jsonrpc_types/struct.SubscriptionFilter.html), which matches
+    /// the cells created by the outputs and the cells consumed by the inputs.
+    ///
+    /// The filtered subscriptions only push the matched cells. The transaction topics push a
+    /// [`MatchedTransaction`](../../ckb_jsonrpc_types/struct.MatchedTransaction.html) instead of
+    /// the `PoolTransactionEntry`, and the `rejected_transaction` topic pushes a two-elements
+    /// array of it and the `PoolTransactionReject`. The `new_tip_block` topic pushes a
+    /// [`MatchedBlock`](../../ckb_jsonrpc_types/struct.MatchedBlock.html) instead of the block. The
+    /// events without matched cells are not pushed, and their sequence numbers are skipped in a
+    /// resumable subscription.
+    ///
     /// ## Topics
     ///
     /// ### `new_tip_header`
@@ -146,6 +212,16 @@ pub trait SubscriptionRpc {
     /// -   the first item type is [`PoolTransactionEntry`](../../ckb_jsonrpc_types/struct.PoolTransactionEntry.html), and
     /// -   the second item type is [`PoolTransactionReject`](../../ckb_jsonrpc_types/struct.PoolTransactionReject.html).
     ///
+    /// ### `chain_reorg`
+    ///
+    /// Subscribers will get notified when some blocks in the canonical chain are rolled back and
+    /// replaced by the blocks in a fork with more accumulated difficulty.
+    ///
+    /// The notification is published before the `new_tip_header` and `new_tip_block` of the new tip,
+    /// but the order of the messages in different topics is not guaranteed.
+    ///
+    /// The type of the `params.result` in the push message is [`ChainReorg`](../../ckb_jsonrpc_types/struct.ChainReorg.html).
+    ///
     /// ## Examples
     ///
     /// Request
@@ -171,7 +247,14 @@ pub trait SubscriptionRpc {
     /// }
     /// ```
     #[pubsub(subscription = "subscribe", subscribe, name = "subscribe")]
-    fn subscribe(&self, meta: Self::Metadata, subscriber: Subscriber<String>, topic: Topic);
+    fn subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<String>,
+        topic: Topic,
+        from: Option<SubscriptionCursor>,
+        filter: Option<SubscriptionFilter>,
+    );
 
     /// Unsubscribes from a subscribed topic.
     ///
@@ -207,32 +290,608 @@ pub trait SubscriptionRpc {
     fn unsubscribe(&self, meta: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
 }
 
-type Subscribers = HashMap<SubscriptionId, Sink<String>>;
+/// The max number of the recent events kept for each topic other than the block topics.
+const RECENT_EVENTS_LIMIT: usize = 1024;
+/// The max total size in bytes of the recent events kept for each topic.
+const RECENT_EVENTS_SIZE_LIMIT: usize = 16 * 1024 * 1024;
+/// The max number of the recent blocks kept for the block topics.
+const RECENT_BLOCKS_LIMIT: usize = 1000;
+/// The sequence numbers of the events start from the start time in milliseconds shifted by it,
+/// which leaves room for 1024 events every millisecond.
+const EVENTS_BASE_SEQ_SHIFT: u32 = 10;
+
+/// The filter of a subscription, converted from `SubscriptionFilter`.
+pub(crate) struct CellFilter {
+    lock: Option<packed::Script>,
+    type_script: Option<packed::Script>,
+    type_code_hash_prefix: Option<Bytes>,
+    output_data_prefix: Option<Bytes>,
+}
+
+impl From<SubscriptionFilter> for CellFilter {
+    fn from(filter: SubscriptionFilter) -> Self {
+        CellFilter {
+            lock: filter.lock.map(Into::into),
+            type_script: filter.type_.map(Into::into),
+            type_code_hash_prefix: filter
+                .type_code_hash_prefix
+                .map(|prefix| prefix.into_bytes()),
+            output_data_prefix: filter.output_data_prefix.map(|prefix| prefix.into_bytes()),
+        }
+    }
+}
+
+impl CellFilter {
+    /// Whether the cell matches all the conditions.
+    pub(crate) fn matches_cell(&self, output: &packed::CellOutput, data: &[u8]) -> bool {
+        let type_script = output.type_().to_opt();
+        self.lock
+            .as_ref()
+            .map_or(true, |lock| lock.as_slice() == output.lock().as_slice())
+            && self.type_script.as_ref().map_or(true, |script| {
+                type_script.as_ref().map_or(false, |type_script| {
+                    type_script.as_slice() == script.as_slice()
+                })
+            })
+            && self.type_code_hash_prefix.as_ref().map_or(true, |prefix| {
+                type_script.as_ref().map_or(false, |type_script| {
+                    type_script.code_hash().as_slice().starts_with(prefix)
+                })
+            })
+            && self
+                .output_data_prefix
+                .as_ref()
+                .map_or(true, |prefix| data.starts_with(prefix))
+    }
+
+    /// Returns the matched cells of the transaction, or `None` when no cell matches.
+    ///
+    /// The `input_cells` are the cells consumed by the inputs, in the same order.
+    pub(crate) fn match_transaction(
+        &self,
+        tx: &TransactionView,
+        input_cells: &[InputCell],
+    ) -> Option<MatchedTransaction> {
+        let inputs: Vec<MatchedCell> = tx
+            .input_pts_iter()
+            .zip(input_cells)
+            .filter_map(|(out_point, cell)| match cell {
+                Some((output, data)) if self.matches_cell(output, data) => {
+                    Some(matched_cell(out_point, output.clone(), data.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        let tx_hash = tx.hash();
+        let outputs: Vec<MatchedCell> = tx
+            .outputs_with_data_iter()
+            .enumerate()
+            .filter(|(_, (output, data))| self.matches_cell(output, data))
+            .map(|(index, (output, data))| {
+                let out_point = packed::OutPoint::new(tx_hash.clone(), index as u32);
+                matched_cell(out_point, output, data)
+            })
+            .collect();
+        if inputs.is_empty() && outputs.is_empty() {
+            return None;
+        }
+        Some(MatchedTransaction {
+            tx_hash: tx_hash.unpack(),
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Returns the matched cells of the block, or `None` when no cell matches.
+    ///
+    /// The `input_cells` are the cells consumed by the inputs of each transaction.
+    pub(crate) fn match_block(
+        &self,
+        block: &BlockView,
+        input_cells: &[Vec<InputCell>],
+    ) -> Option<MatchedBlock> {
+        let transactions: Vec<MatchedTransaction> = block
+            .transactions()
+            .iter()
+            .zip(input_cells)
+            .filter_map(|(tx, input_cells)| self.match_transaction(tx, input_cells))
+            .collect();
+        if transactions.is_empty() {
+            return None;
+        }
+        Some(MatchedBlock {
+            header: block.header().into(),
+            transactions,
+        })
+    }
+}
+
+fn matched_cell(
+    out_point: packed::OutPoint,
+    output: packed::CellOutput,
+    data: Bytes,
+) -> MatchedCell {
+    MatchedCell {
+        out_point: out_point.into(),
+        output: output.into(),
+        output_data: JsonBytes::from_bytes(data),
+    }
+}
+
+/// The cell consumed by an input, `None` when it cannot be resolved.
+pub(crate) type InputCell = Option<(packed::CellOutput, Bytes)>;
+
+/// Resolves the cells consumed by the inputs of the transactions in the committed block.
+fn resolve_block_input_cells(store: &ChainDB, block: &BlockView) -> Vec<Vec<InputCell>> {
+    block
+        .transactions()
+        .iter()
+        .map(|tx| {
+            tx.input_pts_iter()
+                .map(|out_point| {
+                    let (parent, _) = store.get_transaction(&out_point.tx_hash())?;
+                    parent.output_with_data(out_point.index().unpack())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+struct Subscription {
+    sink: Sink<String>,
+    // whether the events are wrapped with their sequence numbers
+    resumable: bool,
+    filter: Option<CellFilter>,
+}
+
+impl Subscription {
+    /// Pushes the event, the filtered subscriptions only push the matched cells of the
+    /// transaction.
+    fn notify_event(&self, event: &Event) {
+        match (&self.filter, &event.transaction) {
+            (Some(filter), Some(transaction)) => {
+                if let Some(matched) =
+                    filter.match_transaction(&transaction.transaction, &transaction.input_cells)
+                {
+                    let json_string = match transaction.reject {
+                        Some(ref reject) => serde_json::to_string(&(matched, reject)),
+                        None => serde_json::to_string(&matched),
+                    }
+                    .expect("serialization should be ok");
+                    self.notify(event.seq, &json_string);
+                }
+            }
+            _ => self.notify(event.seq, &event.data),
+        }
+    }
+
+    /// Pushes the block to the `new_tip_block` subscription, the unfiltered block is serialized
+    /// once and shared by all the subscriptions without a filter.
+    ///
+    /// The `input_cells` are only used by the filtered subscriptions.
+    fn notify_block(
+        &self,
+        seq: u64,
+        block: &BlockView,
+        input_cells: &[Vec<InputCell>],
+        json_string: &mut Option<String>,
+    ) {
+        match self.filter {
+            Some(ref filter) => {
+                if let Some(matched) = filter.match_block(block, input_cells) {
+                    let json_string =
+                        serde_json::to_string(&matched).expect("serialization should be ok");
+                    self.notify(seq, &json_string);
+                }
+            }
+            None => {
+                let json_string = json_string.get_or_insert_with(|| {
+                    let block: ckb_jsonrpc_types::BlockView = block.clone().into();
+                    serde_json::to_string(&block).expect("serialization should be ok")
+                });
+                self.notify(seq, json_string);
+            }
+        }
+    }
+
+    fn notify(&self, seq: u64, data: &str) {
+        let message = if self.resumable {
+            format!(r#"{{"seq":"{:#x}","data":{}}}"#, seq, data)
+        } else {
+            data.to_owned()
+        };
+        let _ = self.sink.notify(Ok(message));
+    }
+}
+
+type Subscribers = HashMap<SubscriptionId, Subscription>;
+
+/// An event of the topics other than the block topics.
+#[derive(Clone)]
+pub(crate) struct Event {
+    pub(crate) seq: u64,
+    pub(crate) data: String,
+    // the transaction of the tx-pool events, which the filters are matched against
+    pub(crate) transaction: Option<FilterableTransaction>,
+}
+
+/// The transaction of a tx-pool event, with the cells consumed by its inputs.
+#[derive(Clone)]
+pub(crate) struct FilterableTransaction {
+    pub(crate) transaction: TransactionView,
+    pub(crate) input_cells: Vec<InputCell>,
+    // the reason of the `rejected_transaction` event
+    pub(crate) reject: Option<PoolTransactionReject>,
+}
+
+impl Event {
+    /// Returns the approximate size of the event in bytes.
+    fn size(&self) -> usize {
+        self.data.len()
+            + self.transaction.as_ref().map_or(0, |transaction| {
+                transaction.transaction.data().total_size()
+                    + transaction
+                        .input_cells
+                        .iter()
+                        .flatten()
+                        .map(|(output, data)| output.total_size() + data.len())
+                        .sum::<usize>()
+            })
+    }
+}
+
+/// The recent events of a topic, which are replayed to the resumed subscriptions.
+#[derive(Default)]
+pub(crate) struct RecentEvents {
+    last_seq: u64,
+    events: VecDeque<Event>,
+    // the total size of the kept events
+    size: usize,
+}
+
+impl RecentEvents {
+    /// Creates the recent events whose sequence numbers start after `last_seq`.
+    pub(crate) fn new(last_seq: u64) -> Self {
+        RecentEvents {
+            last_seq,
+            events: VecDeque::new(),
+            size: 0,
+        }
+    }
+
+    /// Keeps the event and returns its sequence number.
+    ///
+    /// The oldest events are dropped to keep the events within both the count limit and the
+    /// size limit, while the latest event is always kept.
+    pub(crate) fn push(&mut self, data: String, transaction: Option<FilterableTransaction>) -> u64 {
+        self.last_seq += 1;
+        let event = Event {
+            seq: self.last_seq,
+            data,
+            transaction,
+        };
+        let event_size = event.size();
+        while self.events.len() >= RECENT_EVENTS_LIMIT
+            || (!self.events.is_empty() && self.size + event_size > RECENT_EVENTS_SIZE_LIMIT)
+        {
+            if let Some(dropped) = self.events.pop_front() {
+                self.size -= dropped.size();
+            }
+        }
+        self.size += event_size;
+        self.events.push_back(event);
+        self.last_seq
+    }
+
+    /// Returns the kept events after the sequence number.
+    pub(crate) fn since(&self, seq: u64) -> impl Iterator<Item = &Event> {
+        self.events.iter().filter(move |event| event.seq > seq)
+    }
 
+    /// Checks whether all the events after the sequence number are kept.
+    pub(crate) fn check_cursor(&self, seq: u64) -> Result<()> {
+        let oldest = self
+            .events
+            .front()
+            .map_or(self.last_seq, |event| event.seq - 1);
+        check_cursor(seq, oldest, self.last_seq)
+    }
+}
+
+/// The recent blocks published to the block topics, which are replayed to the resumed
+/// subscriptions.
 #[derive(Default)]
+pub(crate) struct RecentBlocks {
+    last_seq: Option<u64>,
+    blocks: VecDeque<(u64, packed::Byte32)>,
+}
+
+impl RecentBlocks {
+    /// Keeps the block and returns its sequence number.
+    ///
+    /// The sequence number is the block number, or the last one plus one when the number is not
+    /// greater than the last one after a reorganization. The last block published again keeps
+    /// its sequence number.
+    pub(crate) fn push(&mut self, number: BlockNumber, hash: packed::Byte32) -> u64 {
+        if let Some((seq, last_hash)) = self.blocks.back() {
+            if *last_hash == hash {
+                return *seq;
+            }
+        }
+        let seq = self
+            .last_seq
+            .map_or(number, |last_seq| cmp::max(last_seq + 1, number));
+        self.last_seq = Some(seq);
+        if self.blocks.len() >= RECENT_BLOCKS_LIMIT {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back((seq, hash));
+        seq
+    }
+
+    /// Returns the sequence number of the latest kept block with the hash.
+    pub(crate) fn seq_of(&self, hash: &packed::Byte32) -> Option<u64> {
+        self.blocks
+            .iter()
+            .rev()
+            .find(|(_, block_hash)| block_hash == hash)
+            .map(|(seq, _)| *seq)
+    }
+
+    /// Returns the kept blocks after the sequence number.
+    pub(crate) fn since(&self, seq: u64) -> impl Iterator<Item = &(u64, packed::Byte32)> {
+        self.blocks
+            .iter()
+            .filter(move |(block_seq, _)| *block_seq > seq)
+    }
+
+    /// Checks whether all the blocks after the sequence number are kept.
+    pub(crate) fn check_cursor(&self, seq: u64) -> Result<()> {
+        let last_seq = self.last_seq.unwrap_or_default();
+        let oldest = self
+            .blocks
+            .front()
+            .map_or(last_seq, |(seq, _)| seq.saturating_sub(1));
+        check_cursor(seq, oldest, last_seq)
+    }
+}
+
+/// Checks whether the cursor is in `[oldest, last_seq]`.
+fn check_cursor(seq: u64, oldest: u64, last_seq: u64) -> Result<()> {
+    if seq > last_seq {
+        Err(RPCError::custom(
+            RPCError::SubscriptionCursorTooOld,
+            format!(
+                "the cursor {:#x} is ahead of the last event {:#x}, it may be published before \
+                 the node restarted",
+                seq, last_seq
+            ),
+        ))
+    } else if seq < oldest {
+        Err(RPCError::custom(
+            RPCError::SubscriptionCursorTooOld,
+            format!(
+                "the events after the cursor {:#x} are no longer kept, the oldest cursor is {:#x}",
+                seq, oldest
+            ),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// An event to be replayed to a resumed subscription.
+enum Replay {
+    Block(u64, packed::Byte32),
+    Event(Event),
+}
+
+pub(crate) struct SubscriptionState {
+    subscribers: HashMap<Topic, Subscribers>,
+    recent_events: HashMap<Topic, RecentEvents>,
+    recent_blocks: RecentBlocks,
+    // the sequence numbers of the events start after it, which is derived from the start time
+    events_base_seq: u64,
+}
+
+impl SubscriptionState {
+    fn publish_block(&mut self, store: &ChainDB, block: BlockView) {
+        let seq = self.recent_blocks.push(block.number(), block.hash());
+        if let Some(subscribers) = self.subscribers.get(&Topic::NewTipHeader) {
+            let header: ckb_jsonrpc_types::HeaderView = block.header().into();
+            let json_string = serde_json::to_string(&header).expect("serialization should be ok");
+            for subscription in subscribers.values() {
+                subscription.notify(seq, &json_string);
+            }
+        }
+        if let Some(subscribers) = self.subscribers.get(&Topic::NewTipBlock) {
+            // the consumed cells are only resolved for the filtered subscriptions
+            let input_cells = if subscribers
+                .values()
+                .any(|subscription| subscription.filter.is_some())
+            {
+                resolve_block_input_cells(store, &block)
+            } else {
+                Vec::new()
+            };
+            let mut json_string = None;
+            for subscription in subscribers.values() {
+                subscription.notify_block(seq, &block, &input_cells, &mut json_string);
+            }
+        }
+    }
+
+    /// Resolves the cells consumed by the inputs of the tx-pool transaction from the committed
+    /// transactions, or the transactions recently submitted to the pool.
+    fn filterable_transaction(
+        &self,
+        store: &ChainDB,
+        transaction: TransactionView,
+        reject: Option<PoolTransactionReject>,
+    ) -> FilterableTransaction {
+        let pool_transactions = self.recent_events.get(&Topic::NewTransaction);
+        let input_cells = transaction
+            .input_pts_iter()
+            .map(|out_point| {
+                let tx_hash = out_point.tx_hash();
+                let index: usize = out_point.index().unpack();
+                match store.get_transaction(&tx_hash) {
+                    Some((parent, _)) => parent.output_with_data(index),
+                    None => pool_transactions?
+                        .events
+                        .iter()
+                        .rev()
+                        .filter_map(|event| event.transaction.as_ref())
+                        .find(|parent| parent.transaction.hash() == tx_hash)?
+                        .transaction
+                        .output_with_data(index),
+                }
+            })
+            .collect();
+        FilterableTransaction {
+            transaction,
+            input_cells,
+            reject,
+        }
+    }
+
+    /// Checks the cursor and returns the events to be replayed after it.
+    fn replay_since(&self, topic: Topic, seq: u64) -> Result<Vec<Replay>> {
+        match topic {
+            Topic::NewTipHeader | Topic::NewTipBlock => {
+                self.recent_blocks.check_cursor(seq)?;
+                Ok(self
+                    .recent_blocks
+                    .since(seq)
+                    .map(|(seq, hash)| Replay::Block(*seq, hash.clone()))
+                    .collect())
+            }
+            _ => match self.recent_events.get(&topic) {
+                Some(recent_events) => {
+                    recent_events.check_cursor(seq)?;
+                    Ok(recent_events
+                        .since(seq)
+                        .cloned()
+                        .map(Replay::Event)
+                        .collect())
+                }
+                None => {
+                    check_cursor(seq, self.events_base_seq, self.events_base_seq)?;
+                    Ok(Vec::new())
+                }
+            },
+        }
+    }
+
+    fn publish<T: Serialize>(
+        &mut self,
+        topic: Topic,
+        data: &T,
+        transaction: Option<FilterableTransaction>,
+    ) {
+        let json_string = serde_json::to_string(data).expect("serialization should be ok");
+        let events_base_seq = self.events_base_seq;
+        let recent_events = self
+            .recent_events
+            .entry(topic)
+            .or_insert_with(|| RecentEvents::new(events_base_seq));
+        recent_events.push(json_string, transaction);
+        if let (Some(subscribers), Some(event)) =
+            (self.subscribers.get(&topic), recent_events.events.back())
+        {
+            for subscription in subscribers.values() {
+                subscription.notify_event(event);
+            }
+        }
+    }
+}
+
 pub struct SubscriptionRpcImpl {
     pub(crate) id_generator: AtomicUsize,
-    pub(crate) subscribers: Arc<RwLock<HashMap<Topic, Subscribers>>>,
+    pub(crate) state: Arc<RwLock<SubscriptionState>>,
+    shared: Shared,
 }
 
 impl SubscriptionRpc for SubscriptionRpcImpl {
-    type Metadata = Option<SubscriptionSession>;
+    type Metadata = RpcSession;
 
-    fn subscribe(&self, meta: Self::Metadata, subscriber: Subscriber<String>, topic: Topic) {
-        if let Some(session) = meta {
+    fn subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<String>,
+        topic: Topic,
+        from: Option<SubscriptionCursor>,
+        filter: Option<SubscriptionFilter>,
+    ) {
+        if let Some(session) = meta.subscription {
+            let filter = match filter.map(|filter| check_filter(topic, filter)) {
+                Some(Ok(filter)) => Some(filter),
+                Some(Err(err)) => {
+                    let _ = subscriber.reject(err);
+                    return;
+                }
+                None => None,
+            };
+            // the events are replayed without holding the write lock, which blocks the publishing
+            let replay = match from {
+                Some(cursor) => {
+                    let state = self
+                        .state
+                        .read()
+                        .expect("acquiring subscription state read lock");
+                    match self
+                        .resolve_cursor(&state, topic, cursor)
+                        .and_then(|seq| Ok((seq, state.replay_since(topic, seq)?)))
+                    {
+                        Ok(replay) => Some(replay),
+                        Err(err) => {
+                            let _ = subscriber.reject(err);
+                            return;
+                        }
+                    }
+                }
+                None => None,
+            };
             let id = SubscriptionId::String(format!(
                 "{:#x}",
                 self.id_generator.fetch_add(1, Ordering::SeqCst)
             ));
             if let Ok(sink) = subscriber.assign_id(id.clone()) {
-                let mut subscribers = self
+                let subscription = Subscription {
+                    sink,
+                    resumable: replay.is_some(),
+                    filter,
+                };
+                let mut state = match replay {
+                    Some((from, replay)) => {
+                        let replayed = self.replay(topic, from, replay, &subscription);
+                        let state = self
+                            .state
+                            .write()
+                            .expect("acquiring subscription state write lock");
+                        // splices the events published during the replay, the subscription is
+                        // closed if they have been dropped
+                        match state.replay_since(topic, replayed) {
+                            Ok(rest) => {
+                                self.replay(topic, replayed, rest, &subscription);
+                                state
+                            }
+                            Err(err) => {
+                                let _ = subscription.sink.notify(Err(err));
+                                return;
+                            }
+                        }
+                    }
+                    None => self
+                        .state
+                        .write()
+                        .expect("acquiring subscription state write lock"),
+                };
+                state
                     .subscribers
-                    .write()
-                    .expect("acquiring subscribers write lock");
-                subscribers
                     .entry(topic)
                     .or_default()
-                    .insert(id.clone(), sink);
+                    .insert(id.clone(), subscription);
 
                 session
                     .subscription_ids
@@ -244,13 +903,17 @@ impl SubscriptionRpc for SubscriptionRpcImpl {
     }
 
     fn unsubscribe(&self, meta: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
-        let mut subscribers = self
-            .subscribers
+        let mut state = self
+            .state
             .write()
-            .expect("acquiring subscribers write lock");
+            .expect("acquiring subscription state write lock");
+        let subscribers = &mut state.subscribers;
         match meta {
             // unsubscribe handler method is explicitly called.
-            Some(Some(session)) => {
+            Some(RpcSession {
+                subscription: Some(session),
+                ..
+            }) => {
                 if session
                     .subscription_ids
                     .write()
@@ -274,7 +937,8 @@ impl SubscriptionRpc for SubscriptionRpcImpl {
 }
 
 impl SubscriptionRpcImpl {
-    pub fn new<S: ToString>(notify_controller: NotifyController, name: S) -> Self {
+    pub fn new<S: ToString>(shared: Shared, name: S) -> Self {
+        let notify_controller = shared.notify_controller();
         let new_block_receiver = notify_controller.subscribe_new_block(name.to_string());
         let new_transaction_receiver =
             notify_controller.subscribe_new_transaction(name.to_string());
@@ -282,9 +946,32 @@ impl SubscriptionRpcImpl {
             notify_controller.subscribe_proposed_transaction(name.to_string());
         let reject_transaction_receiver =
             notify_controller.subscribe_reject_transaction(name.to_string());
+        let chain_reorg_receiver = notify_controller.subscribe_chain_reorg(name.to_string());
 
-        let subscription_rpc_impl = SubscriptionRpcImpl::default();
-        let subscribers = Arc::clone(&subscription_rpc_impl.subscribers);
+        let mut recent_blocks = RecentBlocks::default();
+        {
+            let snapshot = shared.snapshot();
+            let tip_number = snapshot.tip_number();
+            let start = tip_number.saturating_sub(RECENT_BLOCKS_LIMIT as BlockNumber - 1);
+            for number in start..=tip_number {
+                if let Some(hash) = snapshot.get_block_hash(number) {
+                    recent_blocks.push(number, hash);
+                }
+            }
+        }
+        let state = SubscriptionState {
+            subscribers: HashMap::default(),
+            recent_events: HashMap::default(),
+            recent_blocks,
+            events_base_seq: unix_time_as_millis() << EVENTS_BASE_SEQ_SHIFT,
+        };
+        let subscription_rpc_impl = SubscriptionRpcImpl {
+            id_generator: AtomicUsize::default(),
+            state: Arc::new(RwLock::new(state)),
+            shared,
+        };
+        let state = Arc::clone(&subscription_rpc_impl.state);
+        let shared = subscription_rpc_impl.shared.clone();
 
         let thread_builder = thread::Builder::new().name(name.to_string());
         thread_builder
@@ -292,21 +979,8 @@ impl SubscriptionRpcImpl {
                 select! {
                     recv(new_block_receiver) -> msg => match msg {
                         Ok(block) => {
-                            let subscribers = subscribers.read().expect("acquiring subscribers read lock");
-                            if let Some(new_tip_header_subscribers) = subscribers.get(&Topic::NewTipHeader) {
-                                let header: ckb_jsonrpc_types::HeaderView  = block.header().into();
-                                let json_string = Ok(serde_json::to_string(&header).expect("serialization should be ok"));
-                                for sink in new_tip_header_subscribers.values() {
-                                    let _ = sink.notify(json_string.clone());
-                                }
-                            }
-                            if let Some(new_tip_block_subscribers) = subscribers.get(&Topic::NewTipBlock) {
-                                let block: ckb_jsonrpc_types::BlockView  = block.into();
-                                let json_string = Ok(serde_json::to_string(&block).expect("serialization should be ok"));
-                                for sink in new_tip_block_subscribers.values() {
-                                    let _ = sink.notify(json_string.clone());
-                                }
-                            }
+                            let mut state = state.write().expect("acquiring subscription state write lock");
+                            state.publish_block(shared.store(), block);
                         },
                         _ => {
                             error!("new_block_receiver closed");
@@ -315,14 +989,11 @@ impl SubscriptionRpcImpl {
                     },
                     recv(new_transaction_receiver) -> msg => match msg {
                         Ok(tx_entry) => {
-                            let subscribers = subscribers.read().expect("acquiring subscribers read lock");
-                            if let Some(new_transaction_subscribers) = subscribers.get(&Topic::NewTransaction) {
-                                let entry: ckb_jsonrpc_types::PoolTransactionEntry = tx_entry.into();
-                                let json_string = Ok(serde_json::to_string(&entry).expect("serialization should be ok"));
-                                for sink in new_transaction_subscribers.values() {
-                                    let _ = sink.notify(json_string.clone());
-                                }
-                            }
+                            let transaction = tx_entry.transaction.clone();
+                            let entry: ckb_jsonrpc_types::PoolTransactionEntry = tx_entry.into();
+                            let mut state = state.write().expect("acquiring subscription state write lock");
+                            let transaction = state.filterable_transaction(shared.store(), transaction, None);
+                            state.publish(Topic::NewTransaction, &entry, Some(transaction));
                         },
                         _ => {
                             error!("new_transaction_receiver closed");
@@ -331,14 +1002,11 @@ impl SubscriptionRpcImpl {
                     },
                     recv(proposed_transaction_receiver) -> msg => match msg {
                         Ok(tx_entry) => {
-                            let subscribers = subscribers.read().expect("acquiring subscribers read lock");
-                            if let Some(new_transaction_subscribers) = subscribers.get(&Topic::ProposedTransaction) {
-                                let entry: ckb_jsonrpc_types::PoolTransactionEntry = tx_entry.into();
-                                let json_string = Ok(serde_json::to_string(&entry).expect("serialization should be ok"));
-                                for sink in new_transaction_subscribers.values() {
-                                    let _ = sink.notify(json_string.clone());
-                                }
-                            }
+                            let transaction = tx_entry.transaction.clone();
+                            let entry: ckb_jsonrpc_types::PoolTransactionEntry = tx_entry.into();
+                            let mut state = state.write().expect("acquiring subscription state write lock");
+                            let transaction = state.filterable_transaction(shared.store(), transaction, None);
+                            state.publish(Topic::ProposedTransaction, &entry, Some(transaction));
                         },
                         _ => {
                             error!("proposed_transaction_receiver closed");
@@ -348,25 +1016,137 @@ impl SubscriptionRpcImpl {
 
                     recv(reject_transaction_receiver) -> msg => match msg {
                         Ok((tx_entry, reject)) => {
-                            let subscribers = subscribers.read().expect("acquiring subscribers read lock");
-                            if let Some(new_transaction_subscribers) = subscribers.get(&Topic::RejectedTransaction) {
-                                let entry: ckb_jsonrpc_types::PoolTransactionEntry = tx_entry.into();
-                                let reject: ckb_jsonrpc_types::PoolTransactionReject = reject.into();
-                                let json_string = Ok(serde_json::to_string(&(entry, reject)).expect("serialization should be ok"));
-                                for sink in new_transaction_subscribers.values() {
-                                    let _ = sink.notify(json_string.clone());
-                                }
-                            }
+                            let transaction = tx_entry.transaction.clone();
+                            let entry: ckb_jsonrpc_types::PoolTransactionEntry = tx_entry.into();
+                            let reject: PoolTransactionReject = reject.into();
+                            let mut state = state.write().expect("acquiring subscription state write lock");
+                            let transaction = state.filterable_transaction(shared.store(), transaction, Some(reject.clone()));
+                            state.publish(Topic::RejectedTransaction, &(entry, reject), Some(transaction));
                         },
                         _ => {
                             error!("reject_transaction_receiver closed");
                             break;
                         },
                     },
+
+                    recv(chain_reorg_receiver) -> msg => match msg {
+                        Ok(reorg) => {
+                            let reorg: ckb_jsonrpc_types::ChainReorg = reorg.into();
+                            let mut state = state.write().expect("acquiring subscription state write lock");
+                            state.publish(Topic::ChainReorg, &reorg, None);
+                        },
+                        _ => {
+                            error!("chain_reorg_receiver closed");
+                            break;
+                        },
+                    },
                 }
             })
             .expect("Start SubscriptionRpc thread failed");
 
         subscription_rpc_impl
     }
+
+    /// Returns the sequence number of the cursor.
+    fn resolve_cursor(
+        &self,
+        state: &SubscriptionState,
+        topic: Topic,
+        cursor: SubscriptionCursor,
+    ) -> Result<u64> {
+        let is_block_topic = matches!(topic, Topic::NewTipHeader | Topic::NewTipBlock);
+        let hash = match cursor {
+            SubscriptionCursor::Seq(seq) => return Ok(seq.into()),
+            SubscriptionCursor::BlockNumber(number) if is_block_topic => self
+                .shared
+                .snapshot()
+                .get_block_hash(number.into())
+                .ok_or_else(|| RPCError::invalid_params("the block is not found"))?,
+            SubscriptionCursor::BlockHash(hash) if is_block_topic => {
+                let hash = hash.pack();
+                if !self.shared.snapshot().is_main_chain(&hash) {
+                    return Err(RPCError::invalid_params(format!(
+                        "the block {:#x} is not in the main chain",
+                        hash
+                    )));
+                }
+                hash
+            }
+            _ => {
+                return Err(RPCError::invalid_params(
+                    "only the block topics can be resumed from a block",
+                ))
+            }
+        };
+        state.recent_blocks.seq_of(&hash).ok_or_else(|| {
+            RPCError::custom(
+                RPCError::SubscriptionCursorTooOld,
+                format!("the block {:#x} is no longer kept", hash),
+            )
+        })
+    }
+
+    /// Replays the events after the sequence number, and returns the sequence number of the last
+    /// replayed one.
+    fn replay(
+        &self,
+        topic: Topic,
+        from: u64,
+        replay: Vec<Replay>,
+        subscription: &Subscription,
+    ) -> u64 {
+        let store = self.shared.store();
+        let mut last_seq = from;
+        for event in replay {
+            match event {
+                Replay::Block(seq, hash) => {
+                    last_seq = seq;
+                    let block = match store.get_block(&hash) {
+                        Some(block) => block,
+                        None => {
+                            error!("the replayed block {:#x} is not found", hash);
+                            continue;
+                        }
+                    };
+                    if topic == Topic::NewTipHeader {
+                        let header: ckb_jsonrpc_types::HeaderView = block.header().into();
+                        let json_string =
+                            serde_json::to_string(&header).expect("serialization should be ok");
+                        subscription.notify(seq, &json_string);
+                    } else {
+                        let input_cells = if subscription.filter.is_some() {
+                            resolve_block_input_cells(store, &block)
+                        } else {
+                            Vec::new()
+                        };
+                        subscription.notify_block(seq, &block, &input_cells, &mut None);
+                    }
+                }
+                Replay::Event(event) => {
+                    last_seq = event.seq;
+                    subscription.notify_event(&event);
+                }
+            }
+        }
+        last_seq
+    }
+}
+
+/// Checks whether the topic can be filtered, and converts the filter.
+fn check_filter(topic: Topic, filter: SubscriptionFilter) -> Result<CellFilter> {
+    if filter.is_empty() {
+        return Err(RPCError::invalid_params(
+            "the filter must have at least one condition",
+        ));
+    }
+    match topic {
+        Topic::NewTipBlock
+        | Topic::NewTransaction
+        | Topic::ProposedTransaction
+        | Topic::RejectedTransaction => Ok(filter.into()),
+        Topic::NewTipHeader | Topic::ChainReorg => Err(RPCError::invalid_params(
+            "only the topics new_tip_block, new_transaction, proposed_transaction and \
+             rejected_transaction can be filtered",
+        )),
+    }
 }
@@ -0,0 +1,239 @@
+//! Per-client rate limiting of the RPC calls.
+use crate::auth::RpcSession;
+use crate::error::RPCError;
+use ckb_app_config::RpcRateLimitConfig;
+use ckb_metrics::metrics;
+use ckb_util::Mutex;
+use jsonrpc_core::{
+    futures::future::{self, Either},
+    middleware::{NoopCallFuture, NoopFuture},
+    Call, Middleware, Output, Params, Value,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// The buckets which are full are removed once the number of the buckets exceeds it.
+const PRUNE_BUCKETS_THRESHOLD: usize = 1024;
+
+/// The built-in costs of the expensive methods, the other methods cost 1.
+const DEFAULT_METHOD_COSTS: &[(&str, u32)] = &[
+    ("get_block", 4),
+    ("get_block_by_number", 4),
+    ("get_transaction_proof", 4),
+    ("dry_run_transaction", 10),
+];
+
+/// The remote end of an RPC request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Remote {
+    /// The address of a TCP connection, or the forwarded address of an HTTP request.
+    Ip(IpAddr),
+    /// A WebSocket connection, whose address is not exposed by the server.
+    Connection(u64),
+    /// An HTTP request whose address is unknown.
+    Unknown,
+}
+
+impl Default for Remote {
+    fn default() -> Self {
+        Remote::Unknown
+    }
+}
+
+/// The client which a token bucket belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Client {
+    /// An authenticated client, identified by the index of its credential.
+    Credential(usize),
+    /// A client identified by its remote end.
+    Remote(Remote),
+}
+
+impl Client {
+    fn kind(&self) -> &'static str {
+        match self {
+            Client::Credential(_) => "credential",
+            Client::Remote(Remote::Ip(_)) => "ip",
+            Client::Remote(Remote::Connection(_)) => "connection",
+            Client::Remote(Remote::Unknown) => "unknown",
+        }
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Token buckets of all the clients.
+pub(crate) struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    method_costs: HashMap<String, u32>,
+    buckets: Mutex<HashMap<Client, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Creates the rate limiter from `rpc.rate_limit`.
+    pub(crate) fn new(config: &RpcRateLimitConfig) -> Self {
+        let mut method_costs: HashMap<String, u32> = DEFAULT_METHOD_COSTS
+            .iter()
+            .map(|(method, cost)| (method.to_string(), *cost))
+            .collect();
+        method_costs.extend(
+            config
+                .method_costs
+                .iter()
+                .map(|(method, cost)| (method.to_owned(), *cost)),
+        );
+        RateLimiter {
+            requests_per_second: f64::from(config.requests_per_second),
+            burst: f64::from(config.burst.max(1)),
+            method_costs,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates the rate limiter in which every call takes one token.
+    pub(crate) fn with_rate(requests_per_second: f64, burst: u32) -> Self {
+        RateLimiter {
+            requests_per_second,
+            burst: f64::from(burst.max(1)),
+            method_costs: HashMap::new(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the tokens the call takes.
+    ///
+    /// The blocks in the JSON form are much more expensive to serialize than the ones in the
+    /// hex form, so `get_block` and `get_block_by_number` only take one token with `verbosity` 0.
+    /// The cost is capped at `burst` so every method can be called.
+    pub(crate) fn cost(&self, method: &str, params: &Params) -> u32 {
+        let cost = match method {
+            "get_block" | "get_block_by_number" if is_hex_verbosity(params) => 1,
+            _ => self.method_costs.get(method).cloned().unwrap_or(1),
+        };
+        (f64::from(cost).min(self.burst)) as u32
+    }
+
+    /// Takes the tokens from the bucket of the client, returns false when there are not enough
+    /// tokens.
+    pub(crate) fn take(&self, client: Client, cost: u32, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock();
+        if buckets.len() > PRUNE_BUCKETS_THRESHOLD {
+            let (rate, burst) = (self.requests_per_second, self.burst);
+            buckets.retain(|_, bucket| refilled(bucket, rate, burst, now) < burst);
+        }
+        let burst = self.burst;
+        let bucket = buckets.entry(client).or_insert_with(|| Bucket {
+            tokens: burst,
+            updated_at: now,
+        });
+        bucket.tokens = refilled(bucket, self.requests_per_second, burst, now);
+        bucket.updated_at = now;
+        let cost = f64::from(cost);
+        if bucket.tokens >= cost {
+            bucket.tokens -= cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Checks whether the client has one token at least, without taking it.
+    pub(crate) fn has_token(&self, client: Client, now: Instant) -> bool {
+        self.buckets.lock().get(&client).map_or(true, |bucket| {
+            refilled(bucket, self.requests_per_second, self.burst, now) >= 1.0
+        })
+    }
+
+    /// Checks whether the request is allowed to call the method now.
+    pub(crate) fn check(&self, method: &str, params: &Params, meta: &RpcSession) -> bool {
+        let client = match meta.credential {
+            Some(credential) => Client::Credential(credential),
+            None => Client::Remote(meta.remote),
+        };
+        let allowed = self.take(client, self.cost(method, params), Instant::now());
+        if !allowed {
+            metrics!(
+                counter,
+                "ckb.rpc_rate_limited",
+                1,
+                "method" => method.to_owned(),
+                "client" => client.kind(),
+            );
+        }
+        allowed
+    }
+}
+
+fn refilled(bucket: &Bucket, requests_per_second: f64, burst: f64, now: Instant) -> f64 {
+    let elapsed = now.saturating_duration_since(bucket.updated_at);
+    (bucket.tokens + elapsed.as_secs_f64() * requests_per_second).min(burst)
+}
+
+fn is_hex_verbosity(params: &Params) -> bool {
+    let verbosity = match params {
+        Params::Array(values) => values.get(1),
+        Params::Map(map) => map.get("verbosity"),
+        Params::None => None,
+    };
+    matches!(verbosity, Some(Value::String(verbosity)) if verbosity == "0x0")
+}
+
+/// The middleware which rejects the calls from the clients exceeding `rpc.rate_limit`.
+///
+/// It does nothing when the rate limit is not configured.
+#[derive(Clone, Default)]
+pub struct RateLimitMiddleware {
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl RateLimitMiddleware {
+    pub(crate) fn new(rate_limiter: Option<RateLimiter>) -> Self {
+        RateLimitMiddleware {
+            rate_limiter: rate_limiter.map(Arc::new),
+        }
+    }
+}
+
+impl Middleware<RpcSession> for RateLimitMiddleware {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: RpcSession, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, RpcSession) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let rate_limiter = match self.rate_limiter {
+            Some(ref rate_limiter) => rate_limiter,
+            None => return Either::Right(next(call, meta)),
+        };
+        let (method, params, id, jsonrpc) = match call {
+            Call::MethodCall(ref method_call) => (
+                &method_call.method,
+                &method_call.params,
+                Some(method_call.id.clone()),
+                method_call.jsonrpc,
+            ),
+            Call::Notification(ref notification) => (
+                &notification.method,
+                &notification.params,
+                None,
+                notification.jsonrpc,
+            ),
+            Call::Invalid { .. } => return Either::Right(next(call, meta)),
+        };
+        if rate_limiter.check(method, params, &meta) {
+            return Either::Right(next(call, meta));
+        }
+        let result = Err(RPCError::rate_limit_exceeded(method));
+        let output = id.map(|id| Output::from(result, id, jsonrpc));
+        Either::Left(Box::pin(future::ready(output)))
+    }
+}
@@ -1,12 +1,15 @@
+use crate::auth::RpcSession;
 use crate::module::{SubscriptionRpc, SubscriptionRpcImpl, SubscriptionSession};
+use crate::rate_limit::Remote;
 use crate::IoHandler;
 use ckb_app_config::RpcConfig;
 use ckb_logger::info;
-use ckb_notify::NotifyController;
+use ckb_shared::Shared;
+use jsonrpc_http_server::hyper;
 use jsonrpc_pubsub::Session;
 use jsonrpc_server_utils::cors::AccessControlAllowOrigin;
 use jsonrpc_server_utils::hosts::DomainsValidation;
-use std::net::{SocketAddr, ToSocketAddrs};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 
 #[doc(hidden)]
 pub struct RpcServer {
@@ -21,30 +24,55 @@ impl RpcServer {
     /// ## Parameters
     ///
     /// * `config` - RPC config options.
+    ///
+    /// When `config.credentials` is not empty, the HTTP requests are authenticated by the
+    /// `Authorization` header, and the TCP and WebSocket connections are authenticated by calling
+    /// the `auth` method with the same value as the header.
+    ///
+    /// When `config.rate_limit` is set, the clients are limited by their credentials, or by their
+    /// addresses when the authentication is disabled. The WebSocket connections are limited one by
+    /// one, and the HTTP requests without a trusted forwarded address share one limit.
     /// * `io_handler` - RPC methods handler. See [ServiceBuilder](../service_builder/struct.ServiceBuilder.html).
-    /// * `notify_controller` - Controller emitting notifications.
-    pub fn new(
-        config: RpcConfig,
-        io_handler: IoHandler,
-        notify_controller: &NotifyController,
-    ) -> RpcServer {
-        let http = jsonrpc_http_server::ServerBuilder::new(io_handler.clone())
-            .cors(DomainsValidation::AllowOnly(vec![
-                AccessControlAllowOrigin::Null,
-                AccessControlAllowOrigin::Any,
-            ]))
-            .threads(config.threads.unwrap_or_else(num_cpus::get))
-            .max_request_body_size(config.max_request_body_size)
-            .health_api(("/ping", "ping"))
-            .start_http(
-                &config
-                    .listen_address
-                    .to_socket_addrs()
-                    .expect("config listen_address parsed")
-                    .next()
-                    .expect("config listen_address parsed"),
-            )
-            .expect("Start Jsonrpc HTTP service");
+    /// * `shared` - The shared state, whose notifications are pushed to the subscriptions.
+    pub fn new(config: RpcConfig, io_handler: IoHandler, shared: &Shared) -> RpcServer {
+        let trust_forwarded_for = config
+            .rate_limit
+            .as_ref()
+            .map(|rate_limit| rate_limit.trust_forwarded_for)
+            .unwrap_or(false);
+        let http = jsonrpc_http_server::ServerBuilder::with_meta_extractor(
+            io_handler.clone(),
+            move |req: &hyper::Request<hyper::Body>| {
+                let remote = if trust_forwarded_for {
+                    forwarded_for(req).map(Remote::Ip).unwrap_or_default()
+                } else {
+                    Remote::Unknown
+                };
+                RpcSession::with_authorization(
+                    req.headers()
+                        .get(hyper::header::AUTHORIZATION)
+                        .and_then(|value| value.to_str().ok())
+                        .map(ToOwned::to_owned),
+                    remote,
+                )
+            },
+        )
+        .cors(DomainsValidation::AllowOnly(vec![
+            AccessControlAllowOrigin::Null,
+            AccessControlAllowOrigin::Any,
+        ]))
+        .threads(config.threads.unwrap_or_else(num_cpus::get))
+        .max_request_body_size(config.max_request_body_size)
+        .health_api(("/ping", "ping"))
+        .start_http(
+            &config
+                .listen_address
+                .to_socket_addrs()
+                .expect("config listen_address parsed")
+                .next()
+                .expect("config listen_address parsed"),
+        )
+        .expect("Start Jsonrpc HTTP service");
         info!("Listen HTTP RPCServer on address {}", config.listen_address);
 
         let _tcp = config
@@ -52,7 +80,7 @@ impl RpcServer {
             .as_ref()
             .map(|tcp_listen_address| {
                 let subscription_rpc_impl =
-                    SubscriptionRpcImpl::new(notify_controller.clone(), "TcpSubscription");
+                    SubscriptionRpcImpl::new(shared.clone(), "TcpSubscription");
                 let mut handler = io_handler.clone();
                 if config.subscription_enable() {
                     handler.extend_with(subscription_rpc_impl.to_delegate());
@@ -60,9 +88,10 @@ impl RpcServer {
                 let tcp_server = jsonrpc_tcp_server::ServerBuilder::with_meta_extractor(
                     handler,
                     |context: &jsonrpc_tcp_server::RequestContext| {
-                        Some(SubscriptionSession::new(Session::new(
-                            context.sender.clone(),
-                        )))
+                        RpcSession::with_subscription(
+                            SubscriptionSession::new(Session::new(context.sender.clone())),
+                            Remote::Ip(context.peer_addr.ip()),
+                        )
                     },
                 )
                 .start(
@@ -79,8 +108,7 @@ impl RpcServer {
             });
 
         let _ws = config.ws_listen_address.as_ref().map(|ws_listen_address| {
-            let subscription_rpc_impl =
-                SubscriptionRpcImpl::new(notify_controller.clone(), "WsSubscription");
+            let subscription_rpc_impl = SubscriptionRpcImpl::new(shared.clone(), "WsSubscription");
             let mut handler = io_handler.clone();
             if config.subscription_enable() {
                 handler.extend_with(subscription_rpc_impl.to_delegate());
@@ -88,7 +116,10 @@ impl RpcServer {
             let ws_server = jsonrpc_ws_server::ServerBuilder::with_meta_extractor(
                 handler,
                 |context: &jsonrpc_ws_server::RequestContext| {
-                    Some(SubscriptionSession::new(Session::new(context.sender())))
+                    RpcSession::with_subscription(
+                        SubscriptionSession::new(Session::new(context.sender())),
+                        Remote::Connection(context.session_id),
+                    )
                 },
             )
             .start(
@@ -112,3 +143,21 @@ impl RpcServer {
         self.http.address()
     }
 }
+
+/// Gets the client address from the last entry of `X-Forwarded-For`, or from `X-Real-IP`.
+///
+/// The last entry is the one appended by the trusted proxy, while the others are sent by the
+/// client and can be forged.
+pub(crate) fn forwarded_for(req: &hyper::Request<hyper::Body>) -> Option<IpAddr> {
+    let headers = req.headers();
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .or_else(|| {
+            headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+        })
+        .and_then(|value| value.trim().parse().ok())
+}
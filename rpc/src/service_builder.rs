@@ -1,22 +1,26 @@
 #![allow(deprecated)]
+use crate::auth::{AuthMiddleware, Authenticator, RpcSession, AUTH_METHOD};
 use crate::error::RPCError;
-use crate::module::SubscriptionSession;
+use crate::metrics::MetricsMiddleware;
 use crate::module::{
     AlertRpc, AlertRpcImpl, ChainRpc, ChainRpcImpl, DebugRpc, DebugRpcImpl, ExperimentRpc,
-    ExperimentRpcImpl, IntegrationTestRpc, IntegrationTestRpcImpl, MinerRpc, MinerRpcImpl, NetRpc,
-    NetRpcImpl, PoolRpc, PoolRpcImpl, StatsRpc, StatsRpcImpl,
+    ExperimentRpcImpl, IndexerRpc, IndexerRpcImpl, IntegrationTestRpc, IntegrationTestRpcImpl,
+    MinerRpc, MinerRpcImpl, NetRpc, NetRpcImpl, PoolRpc, PoolRpcImpl, StatsRpc, StatsRpcImpl,
 };
+use crate::rate_limit::{RateLimitMiddleware, RateLimiter};
 use crate::IoHandler;
-use ckb_app_config::RpcConfig;
+use ckb_app_config::{RpcConfig, RpcModule};
 use ckb_chain::chain::ChainController;
+use ckb_indexer::IndexerHandle;
 use ckb_network::NetworkController;
 use ckb_network_alert::{notifier::Notifier as AlertNotifier, verifier::Verifier as AlertVerifier};
 use ckb_pow::Pow;
 use ckb_shared::shared::Shared;
 use ckb_sync::SyncShared;
-use ckb_types::{core::FeeRate, packed::Script};
+use ckb_types::packed::Script;
 use ckb_util::Mutex;
-use jsonrpc_core::RemoteProcedure;
+use jsonrpc_core::{MetaIoHandler, RemoteProcedure};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 const DEPRECATED_RPC_PREFIX: &str = "deprecated.";
@@ -24,7 +28,8 @@ const DEPRECATED_RPC_PREFIX: &str = "deprecated.";
 #[doc(hidden)]
 pub struct ServiceBuilder<'a> {
     config: &'a RpcConfig,
-    io_handler: IoHandler,
+    io_handler: MetaIoHandler<RpcSession>,
+    method_modules: HashMap<String, RpcModule>,
 }
 
 impl<'a> ServiceBuilder<'a> {
@@ -32,7 +37,8 @@ impl<'a> ServiceBuilder<'a> {
     pub fn new(config: &'a RpcConfig) -> Self {
         Self {
             config,
-            io_handler: IoHandler::default(),
+            io_handler: MetaIoHandler::default(),
+            method_modules: HashMap::default(),
         }
     }
 
@@ -40,9 +46,9 @@ impl<'a> ServiceBuilder<'a> {
     pub fn enable_chain(mut self, shared: Shared) -> Self {
         let rpc_methods = ChainRpcImpl { shared }.to_delegate();
         if self.config.chain_enable() {
-            self.add_methods(rpc_methods);
+            self.add_methods(RpcModule::Chain, rpc_methods);
         } else {
-            self.update_disabled_methods("Chain", rpc_methods);
+            self.update_disabled_methods(RpcModule::Chain, rpc_methods);
         }
         self
     }
@@ -51,23 +57,21 @@ impl<'a> ServiceBuilder<'a> {
     pub fn enable_pool(
         mut self,
         shared: Shared,
-        min_fee_rate: FeeRate,
         reject_ill_transactions: bool,
         extra_well_known_lock_scripts: Vec<Script>,
         extra_well_known_type_scripts: Vec<Script>,
     ) -> Self {
         let rpc_methods = PoolRpcImpl::new(
             shared,
-            min_fee_rate,
             reject_ill_transactions,
             extra_well_known_lock_scripts,
             extra_well_known_type_scripts,
         )
         .to_delegate();
         if self.config.pool_enable() {
-            self.add_methods(rpc_methods);
+            self.add_methods(RpcModule::Pool, rpc_methods);
         } else {
-            self.update_disabled_methods("Pool", rpc_methods);
+            self.update_disabled_methods(RpcModule::Pool, rpc_methods);
         }
         self
     }
@@ -87,9 +91,9 @@ impl<'a> ServiceBuilder<'a> {
         }
         .to_delegate();
         if enable && self.config.miner_enable() {
-            self.add_methods(rpc_methods);
+            self.add_methods(RpcModule::Miner, rpc_methods);
         } else {
-            self.update_disabled_methods("Miner", rpc_methods);
+            self.update_disabled_methods(RpcModule::Miner, rpc_methods);
         }
         self
     }
@@ -106,9 +110,9 @@ impl<'a> ServiceBuilder<'a> {
         }
         .to_delegate();
         if self.config.net_enable() {
-            self.add_methods(rpc_methods);
+            self.add_methods(RpcModule::Net, rpc_methods);
         } else {
-            self.update_disabled_methods("Net", rpc_methods);
+            self.update_disabled_methods(RpcModule::Net, rpc_methods);
         }
         self
     }
@@ -125,9 +129,9 @@ impl<'a> ServiceBuilder<'a> {
         }
         .to_delegate();
         if self.config.stats_enable() {
-            self.add_methods(rpc_methods);
+            self.add_methods(RpcModule::Stats, rpc_methods);
         } else {
-            self.update_disabled_methods("Stats", rpc_methods);
+            self.update_disabled_methods(RpcModule::Stats, rpc_methods);
         }
         self
     }
@@ -136,9 +140,9 @@ impl<'a> ServiceBuilder<'a> {
     pub fn enable_experiment(mut self, shared: Shared) -> Self {
         let rpc_methods = ExperimentRpcImpl { shared }.to_delegate();
         if self.config.experiment_enable() {
-            self.add_methods(rpc_methods);
+            self.add_methods(RpcModule::Experiment, rpc_methods);
         } else {
-            self.update_disabled_methods("Experiment", rpc_methods);
+            self.update_disabled_methods(RpcModule::Experiment, rpc_methods);
         }
         self
     }
@@ -165,9 +169,9 @@ impl<'a> ServiceBuilder<'a> {
                 "Only run integration test on Dummy PoW chain"
             );
 
-            self.add_methods(rpc_methods);
+            self.add_methods(RpcModule::IntegrationTest, rpc_methods);
         } else {
-            self.update_disabled_methods("IntegrationTest", rpc_methods);
+            self.update_disabled_methods(RpcModule::IntegrationTest, rpc_methods);
         }
         self
     }
@@ -182,45 +186,61 @@ impl<'a> ServiceBuilder<'a> {
         let rpc_methods =
             AlertRpcImpl::new(alert_verifier, alert_notifier, network_controller).to_delegate();
         if self.config.alert_enable() {
-            self.add_methods(rpc_methods);
+            self.add_methods(RpcModule::Alert, rpc_methods);
         } else {
-            self.update_disabled_methods("Alert", rpc_methods);
+            self.update_disabled_methods(RpcModule::Alert, rpc_methods);
         }
         self
     }
 
     /// Mounts methods from module Debug if it is enabled in the config.
-    pub fn enable_debug(mut self) -> Self {
+    pub fn enable_debug(mut self, shared: Shared) -> Self {
         if self.config.debug_enable() {
-            self.io_handler.extend_with(DebugRpcImpl {}.to_delegate());
+            self.add_methods(RpcModule::Debug, DebugRpcImpl::new(shared).to_delegate());
         }
         self
     }
 
-    fn update_disabled_methods<I, M>(&mut self, module: &str, rpc_methods: I)
+    /// Mounts methods from module Indexer if it is enabled in the config.
+    ///
+    /// The indexer handle is `None` when the indexer service is not started.
+    pub fn enable_indexer(mut self, indexer_handle: Option<IndexerHandle>) -> Self {
+        if let Some(handle) = indexer_handle {
+            if self.config.indexer_enable() {
+                self.add_methods(RpcModule::Indexer, IndexerRpcImpl { handle }.to_delegate());
+            }
+        }
+        self
+    }
+
+    fn update_disabled_methods<I, M>(&mut self, module: RpcModule, rpc_methods: I)
     where
         I: IntoIterator<Item = (String, M)>,
     {
         rpc_methods.into_iter().for_each(|(name, _method)| {
-            let error = Err(RPCError::rpc_module_is_disabled(module));
-            self.io_handler.add_sync_method(
-                name.split("deprecated.")
-                    .collect::<Vec<&str>>()
-                    .last()
-                    .unwrap(),
-                move |_param| error.clone(),
-            )
+            let error = Err(RPCError::rpc_module_is_disabled(&format!("{:?}", module)));
+            let name = name
+                .split("deprecated.")
+                .collect::<Vec<&str>>()
+                .last()
+                .unwrap()
+                .to_string();
+            self.io_handler
+                .add_sync_method(&name, move |_param| error.clone());
+            self.method_modules.insert(name, module);
         });
     }
 
-    fn add_methods<I>(&mut self, rpc_methods: I)
+    fn add_methods<I>(&mut self, module: RpcModule, rpc_methods: I)
     where
-        I: IntoIterator<Item = (String, RemoteProcedure<Option<SubscriptionSession>>)>,
+        I: IntoIterator<Item = (String, RemoteProcedure<RpcSession>)>,
     {
         let enable_deprecated_rpc = self.config.enable_deprecated_rpc;
+        let method_modules = &mut self.method_modules;
         self.io_handler
             .extend_with(rpc_methods.into_iter().map(|(name, method)| {
                 if let Some(deprecated_method_name) = name.strip_prefix(DEPRECATED_RPC_PREFIX) {
+                    method_modules.insert(deprecated_method_name.to_owned(), module);
                     (
                         deprecated_method_name.to_owned(),
                         if enable_deprecated_rpc {
@@ -232,16 +252,48 @@ impl<'a> ServiceBuilder<'a> {
                         },
                     )
                 } else {
+                    method_modules.insert(name.clone(), module);
                     (name, method)
                 }
             }));
     }
 
     /// Builds the RPC methods handler used in the RPC server.
+    ///
+    /// The calls are checked against `rpc.credentials` when any credential is configured, and then
+    /// against `rpc.rate_limit` when it is configured. The failed authentications are limited by
+    /// the authentication itself, since the rate limit of the authenticated calls is applied by
+    /// their credentials. The metrics of all the calls are collected,
+    /// including the rejected ones.
     pub fn build(self) -> IoHandler {
-        let mut io_handler = self.io_handler;
+        let mut method_modules = self.method_modules;
+        // the subscription methods are mounted by the TCP and WebSocket servers
+        for name in &["subscribe", "unsubscribe"] {
+            method_modules.insert(name.to_string(), RpcModule::Subscription);
+        }
+        let methods = method_modules
+            .keys()
+            .cloned()
+            .chain(vec!["ping".to_owned(), AUTH_METHOD.to_owned()])
+            .collect();
+        let authenticator = if self.config.auth_enable() {
+            Some(Authenticator::new(&self.config.credentials, method_modules))
+        } else {
+            None
+        };
+        let rate_limiter = self.config.rate_limit.as_ref().map(RateLimiter::new);
+        let mut io_handler = MetaIoHandler::with_middleware((
+            MetricsMiddleware::new(methods),
+            AuthMiddleware::new(authenticator),
+            RateLimitMiddleware::new(rate_limiter),
+        ));
+        io_handler.extend_with(self.io_handler);
         io_handler.add_sync_method("ping", |_| Ok("pong".into()));
+        if !self.config.auth_enable() {
+            // the calls are handled by the middleware when the authentication is enabled
+            io_handler.add_sync_method(AUTH_METHOD, |_| Ok(true.into()));
+        }
 
-        io_handler
+        IoHandler::new(io_handler)
     }
 }
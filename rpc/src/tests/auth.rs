@@ -0,0 +1,172 @@
+use crate::auth::{AuthMiddleware, Authenticator, RpcSession};
+use crate::error::RPCError;
+use crate::module::SubscriptionSession;
+use crate::rate_limit::Remote;
+use ckb_app_config::{RpcCredential, RpcModule};
+use jsonrpc_core::{futures::channel::mpsc, ErrorCode, MetaIoHandler};
+use jsonrpc_pubsub::Session;
+use std::collections::HashMap;
+
+fn authenticator() -> Authenticator {
+    let credentials = vec![
+        RpcCredential {
+            token: Some("reader".to_owned()),
+            modules: vec![RpcModule::Chain],
+            methods: vec!["send_transaction".to_owned()],
+            ..Default::default()
+        },
+        RpcCredential {
+            username: Some("admin".to_owned()),
+            password: Some("secret".to_owned()),
+            modules: vec![RpcModule::Chain, RpcModule::Pool],
+            ..Default::default()
+        },
+    ];
+    let method_modules: HashMap<String, RpcModule> = vec![
+        ("get_tip_header".to_owned(), RpcModule::Chain),
+        ("send_transaction".to_owned(), RpcModule::Pool),
+        ("clear_tx_pool".to_owned(), RpcModule::Pool),
+    ]
+    .into_iter()
+    .collect();
+    Authenticator::new(&credentials, method_modules)
+}
+
+fn error_code(error: RPCError) -> ErrorCode {
+    ErrorCode::ServerError(error as i64)
+}
+
+#[test]
+fn test_bearer_token() {
+    let authenticator = authenticator();
+    let meta = RpcSession::with_authorization(Some("Bearer reader".to_owned()), Remote::Unknown);
+    assert_eq!(authenticator.check("get_tip_header", &meta), Ok(Some(0)));
+    assert!(authenticator.check("send_transaction", &meta).is_ok());
+    assert_eq!(
+        authenticator
+            .check("clear_tx_pool", &meta)
+            .unwrap_err()
+            .code,
+        error_code(RPCError::RPCMethodIsNotAllowed)
+    );
+
+    let meta = RpcSession::with_authorization(Some("Bearer writer".to_owned()), Remote::Unknown);
+    assert_eq!(
+        authenticator
+            .check("get_tip_header", &meta)
+            .unwrap_err()
+            .code,
+        error_code(RPCError::Unauthorized)
+    );
+}
+
+#[test]
+fn test_basic_auth() {
+    let authenticator = authenticator();
+    let authorization = format!("Basic {}", base64::encode("admin:secret"));
+    let meta = RpcSession::with_authorization(Some(authorization), Remote::Unknown);
+    assert_eq!(authenticator.check("get_tip_header", &meta), Ok(Some(1)));
+    assert!(authenticator.check("clear_tx_pool", &meta).is_ok());
+
+    let authorization = format!("Basic {}", base64::encode("admin:wrong"));
+    let meta = RpcSession::with_authorization(Some(authorization), Remote::Unknown);
+    assert_eq!(
+        authenticator
+            .check("clear_tx_pool", &meta)
+            .unwrap_err()
+            .code,
+        error_code(RPCError::Unauthorized)
+    );
+}
+
+#[test]
+fn test_unauthenticated() {
+    let authenticator = authenticator();
+    let meta = RpcSession::default();
+    assert!(authenticator.check("ping", &meta).is_ok());
+    assert_eq!(
+        authenticator
+            .check("get_tip_header", &meta)
+            .unwrap_err()
+            .code,
+        error_code(RPCError::Unauthorized)
+    );
+    // the methods in no module are only allowed explicitly
+    let meta = RpcSession::with_authorization(Some("Bearer reader".to_owned()), Remote::Unknown);
+    assert_eq!(
+        authenticator
+            .check("unknown_method", &meta)
+            .unwrap_err()
+            .code,
+        error_code(RPCError::RPCMethodIsNotAllowed)
+    );
+}
+
+#[test]
+fn test_auth_method() {
+    let mut io_handler = MetaIoHandler::with_middleware(AuthMiddleware::new(Some(authenticator())));
+    io_handler.add_sync_method("get_tip_header", |_| Ok("tip".into()));
+    // the metadata is shared by all the requests in a TCP or WebSocket connection
+    let meta = RpcSession::default();
+
+    let request = r#"{"jsonrpc":"2.0","method":"get_tip_header","params":[],"id":1}"#;
+    let response = io_handler
+        .handle_request_sync(request, meta.clone())
+        .unwrap();
+    assert!(response.contains(r#""code":-8"#));
+
+    let auth = r#"{"jsonrpc":"2.0","method":"auth","params":["Bearer unknown"],"id":2}"#;
+    let response = io_handler.handle_request_sync(auth, meta.clone()).unwrap();
+    assert!(response.contains(r#""code":-8"#));
+
+    let auth = r#"{"jsonrpc":"2.0","method":"auth","params":["Bearer reader"],"id":2}"#;
+    assert_eq!(
+        io_handler.handle_request_sync(auth, meta.clone()),
+        Some(r#"{"jsonrpc":"2.0","result":true,"id":2}"#.to_owned())
+    );
+    assert_eq!(
+        io_handler.handle_request_sync(request, meta),
+        Some(r#"{"jsonrpc":"2.0","result":"tip","id":1}"#.to_owned())
+    );
+}
+
+#[test]
+fn test_failed_auth_limit() {
+    let mut io_handler = MetaIoHandler::with_middleware(AuthMiddleware::new(Some(authenticator())));
+    io_handler.add_sync_method("get_tip_header", |_| Ok("tip".into()));
+    io_handler.add_sync_method("ping", |_| Ok("pong".into()));
+    let remote = Remote::Ip("127.0.0.1".parse().unwrap());
+    let meta = RpcSession::with_subscription(
+        SubscriptionSession::new(Session::new(mpsc::unbounded().0)),
+        remote,
+    );
+
+    let auth = r#"{"jsonrpc":"2.0","method":"auth","params":["Bearer unknown"],"id":2}"#;
+    for _ in 0..10 {
+        let response = io_handler.handle_request_sync(auth, meta.clone()).unwrap();
+        assert!(response.contains(r#""code":-8"#));
+    }
+    // the valid credential is rejected without checking once the failures are used up
+    let auth = r#"{"jsonrpc":"2.0","method":"auth","params":["Bearer reader"],"id":2}"#;
+    let response = io_handler.handle_request_sync(auth, meta.clone()).unwrap();
+    assert!(response.contains(r#""code":-10"#));
+    // so are the HTTP requests from the same remote end
+    let request = r#"{"jsonrpc":"2.0","method":"get_tip_header","params":[],"id":1}"#;
+    let http_meta = RpcSession::with_authorization(Some("Bearer reader".to_owned()), remote);
+    let response = io_handler.handle_request_sync(request, http_meta).unwrap();
+    assert!(response.contains(r#""code":-10"#));
+    // the public methods are not affected
+    let ping = r#"{"jsonrpc":"2.0","method":"ping","params":[],"id":3}"#;
+    assert_eq!(
+        io_handler.handle_request_sync(ping, meta),
+        Some(r#"{"jsonrpc":"2.0","result":"pong","id":3}"#.to_owned())
+    );
+
+    // the other remote ends are not affected
+    let other = Remote::Ip("127.0.0.2".parse().unwrap());
+    let http_meta = RpcSession::with_authorization(Some("Bearer reader".to_owned()), other);
+    assert_eq!(
+        io_handler.handle_request_sync(request, http_meta),
+        Some(r#"{"jsonrpc":"2.0","result":"tip","id":1}"#.to_owned())
+    );
+}
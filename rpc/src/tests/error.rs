@@ -49,6 +49,12 @@ fn test_submit_transaction_error() {
         "PoolRejectedMalformedTransaction: Malformed cellbase like transaction",
         RPCError::from_submit_transaction_reject(&reject).message
     );
+
+    let reject = Reject::RBFRejected("conflicts with a proposed transaction".to_owned());
+    assert_eq!(
+        "PoolRejectedRBF: RBF rejected: conflicts with a proposed transaction",
+        RPCError::from_submit_transaction_reject(&reject).message
+    );
 }
 
 #[test]
@@ -3,11 +3,13 @@ use crate::{
     RpcServer, ServiceBuilder,
 };
 use ckb_app_config::{
-    BlockAssemblerConfig, NetworkAlertConfig, NetworkConfig, RpcConfig, RpcModule,
+    BlockAssemblerConfig, IndexerConfig, NetworkAlertConfig, NetworkConfig, RpcConfig, RpcModule,
+    TxPoolConfig, TxSelectionConfig,
 };
 use ckb_chain::chain::ChainService;
 use ckb_chain_spec::consensus::{Consensus, ConsensusBuilder};
 use ckb_dao_utils::genesis_dao_data;
+use ckb_indexer::IndexerService;
 use ckb_launcher::SharedBuilder;
 use ckb_network::{DefaultExitHandler, NetworkService, NetworkState};
 use ckb_network_alert::alert_relayer::AlertRelayer;
@@ -104,11 +106,19 @@ fn setup_rpc_test_suite(height: u64) -> RpcTestSuite {
             message: "message".pack().into(),
             use_binary_version_as_message_prefix: true,
             binary_version: "TEST".to_string(),
+            advanced: true,
             update_interval_millis: 800,
             notify: vec![],
             notify_scripts: vec![],
             notify_timeout_millis: 800,
+            payout_targets: vec![],
+            tx_selection: TxSelectionConfig::Prioritised,
         }))
+        // the example of `tx_pool_info` shows a zero fee rate threshold
+        .tx_pool_config(TxPoolConfig {
+            min_fee_rate: FeeRate::zero(),
+            ..Default::default()
+        })
         .build()
         .unwrap();
     let chain_controller =
@@ -216,17 +226,31 @@ fn setup_rpc_test_suite(height: u64) -> RpcTestSuite {
             RpcModule::Alert,
             RpcModule::Subscription,
             RpcModule::Debug,
+            RpcModule::Indexer,
         ],
         reject_ill_transactions: true,
         // enable deprecated rpc in unit test
         enable_deprecated_rpc: true,
         extra_well_known_lock_scripts: vec![],
         extra_well_known_type_scripts: vec![],
+        credentials: vec![],
+        rate_limit: None,
+    };
+
+    let indexer_handle = {
+        let indexer_config = IndexerConfig {
+            store: temp_dir.path().join("indexer"),
+        };
+        let indexer = IndexerService::new(shared.clone(), &indexer_config);
+        // Index the example chain before the fork block and the example transaction, so the
+        // indexer examples are checked against the real responses.
+        indexer.index_chain().expect("index the example chain");
+        indexer.handle()
     };
 
     let builder = ServiceBuilder::new(&rpc_config)
         .enable_chain(shared.clone())
-        .enable_pool(shared.clone(), FeeRate::zero(), true, vec![], vec![])
+        .enable_pool(shared.clone(), true, vec![], vec![])
         .enable_miner(
             shared.clone(),
             network_controller.clone(),
@@ -241,11 +265,12 @@ fn setup_rpc_test_suite(height: u64) -> RpcTestSuite {
             network_controller.clone(),
             chain_controller.clone(),
         )
-        .enable_debug()
+        .enable_debug(shared.clone())
+        .enable_indexer(Some(indexer_handle))
         .enable_alert(alert_verifier, alert_notifier, network_controller);
     let io_handler = builder.build();
 
-    let rpc_server = RpcServer::new(rpc_config, io_handler, shared.notify_controller());
+    let rpc_server = RpcServer::new(rpc_config, io_handler, &shared);
     let rpc_uri = format!(
         "http://{}:{}/",
         rpc_server.http_address().ip(),
@@ -588,7 +613,10 @@ where
 // * Use replace_rpc_response to skip the response matching assertions.
 // * Fix timestamp related fields.
 fn mock_rpc_response(example: &RpcTestExample, response: &mut RpcTestResponse) {
-    use ckb_jsonrpc_types::{BannedAddr, Capacity, LocalNode, RemoteNode, Uint64};
+    use ckb_jsonrpc_types::{
+        BannedAddr, BlockFilter, Capacity, EstimatedFeeRate, LocalNode, PayoutTarget, RemoteNode,
+        Uint64,
+    };
 
     let example_tx_hash = format!("{:#x}", EXAMPLE_TX_HASH);
 
@@ -620,6 +648,9 @@ fn mock_rpc_response(example: &RpcTestExample, response: &mut RpcTestResponse) {
         "generate_block" => replace_rpc_response::<H256>(example, response),
         "process_block_without_verify" => replace_rpc_response::<H256>(example, response),
         "notify_transaction" => replace_rpc_response::<H256>(example, response),
+        "estimate_fee_rate" => replace_rpc_response::<EstimatedFeeRate>(example, response),
+        "get_block_filter" => replace_rpc_response::<BlockFilter>(example, response),
+        "get_payout_targets" => replace_rpc_response::<Vec<PayoutTarget>>(example, response),
         _ => {}
     }
 }
@@ -662,6 +693,14 @@ fn after_rpc_example(suite: &RpcTestSuite, example: &RpcTestExample) {
             suite.send_example_transaction()
         }
         "remove_transaction" => suite.send_example_transaction(),
+        "set_payout_targets" => {
+            suite.rpc(&RpcTestRequest {
+                id: 42,
+                jsonrpc: "2.0".to_string(),
+                method: "set_payout_targets".to_string(),
+                params: vec![json!([])],
+            });
+        }
         _ => {}
     }
 }
@@ -0,0 +1,62 @@
+use ckb_types::core::TransactionBuilder;
+use opentelemetry_prometheus::PrometheusExporter;
+use serde_json::json;
+
+use crate::tests::{setup, RpcTestRequest};
+
+// Returns the value of the counter with the label, or 0 if the counter is not recorded.
+fn counter(exporter: &PrometheusExporter, name: &str, label: (&str, &str)) -> u64 {
+    exporter
+        .registry()
+        .gather()
+        .iter()
+        .filter(|family| family.get_name() == name)
+        .flat_map(|family| family.get_metric())
+        .filter(|metric| {
+            metric
+                .get_label()
+                .iter()
+                .any(|pair| (pair.get_name(), pair.get_value()) == label)
+        })
+        .map(|metric| metric.get_counter().get_value() as u64)
+        .sum()
+}
+
+#[test]
+fn test_rpc_and_reject_metrics() {
+    let exporter = opentelemetry_prometheus::exporter().init();
+    let suite = setup();
+
+    // an empty transaction is rejected by the non contextual verification
+    let tx = TransactionBuilder::default().build();
+    let response = suite.rpc(&RpcTestRequest {
+        id: 42,
+        jsonrpc: "2.0".to_string(),
+        method: "send_transaction".to_string(),
+        params: vec![json!(ckb_jsonrpc_types::Transaction::from(tx.data()))],
+    });
+    assert!(!response.error.is_null());
+    assert!(counter(&exporter, "ckb_tx_pool_reject", ("type", "verification")) >= 1);
+    assert!(
+        counter(
+            &exporter,
+            "ckb_rpc_requests",
+            ("method", "send_transaction")
+        ) >= 1
+    );
+    assert!(counter(&exporter, "ckb_rpc_errors", ("method", "send_transaction")) >= 1);
+
+    // the methods which are not mounted share one label
+    let response = suite.rpc(&RpcTestRequest {
+        id: 42,
+        jsonrpc: "2.0".to_string(),
+        method: "no_such_method".to_string(),
+        params: vec![],
+    });
+    assert!(!response.error.is_null());
+    assert!(counter(&exporter, "ckb_rpc_errors", ("method", "unknown")) >= 1);
+    assert_eq!(
+        counter(&exporter, "ckb_rpc_requests", ("method", "no_such_method")),
+        0
+    );
+}
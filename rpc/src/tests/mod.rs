@@ -13,8 +13,8 @@ use ckb_test_chain_utils::{
 };
 use ckb_types::{
     core::{
-        cell::resolve_transaction, BlockBuilder, BlockView, FeeRate, HeaderView,
-        TransactionBuilder, TransactionView,
+        cell::resolve_transaction, BlockBuilder, BlockView, HeaderView, TransactionBuilder,
+        TransactionView,
     },
     h256,
     packed::{CellInput, OutPoint},
@@ -25,9 +25,12 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{cmp, collections::HashSet, fmt, sync::Arc};
 
+mod auth;
 mod error;
 mod examples;
+mod metrics;
 mod module;
+mod rate_limit;
 
 #[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq, Default)]
 struct RpcTestRequest {
@@ -215,10 +218,13 @@ fn setup() -> RpcTestSuite {
             message: Default::default(),
             use_binary_version_as_message_prefix: false,
             binary_version: "TEST".to_string(),
+            advanced: false,
             update_interval_millis: 800,
             notify: vec![],
             notify_scripts: vec![],
             notify_timeout_millis: 800,
+            payout_targets: vec![],
+            tx_selection: Default::default(),
         }))
         .build()
         .unwrap();
@@ -282,11 +288,13 @@ fn setup() -> RpcTestSuite {
         enable_deprecated_rpc: false,
         extra_well_known_lock_scripts: vec![],
         extra_well_known_type_scripts: vec![],
+        credentials: vec![],
+        rate_limit: None,
     };
 
     let builder = ServiceBuilder::new(&rpc_config)
         .enable_chain(shared.clone())
-        .enable_pool(shared.clone(), FeeRate::zero(), true, vec![], vec![])
+        .enable_pool(shared.clone(), true, vec![], vec![])
         .enable_miner(
             shared.clone(),
             network_controller.clone(),
@@ -296,7 +304,7 @@ fn setup() -> RpcTestSuite {
         .enable_integration_test(shared.clone(), network_controller, chain_controller.clone());
     let io_handler = builder.build();
 
-    let rpc_server = RpcServer::new(rpc_config, io_handler, shared.notify_controller());
+    let rpc_server = RpcServer::new(rpc_config, io_handler, &shared);
     let rpc_uri = format!(
         "http://{}:{}/",
         rpc_server.http_address().ip(),
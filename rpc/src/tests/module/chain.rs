@@ -0,0 +1,40 @@
+use crate::tests::{next_block, setup, RpcTestRequest};
+use ckb_store::ChainStore;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[test]
+fn test_block_is_pruned() {
+    let suite = setup();
+    let request = |method: &str, params: Vec<Value>| {
+        suite.rpc(&RpcTestRequest {
+            id: 42,
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        })
+    };
+    let pruned_hash = format!("{:#x}", suite.shared.snapshot().get_block_hash(3).unwrap());
+
+    // the bodies of the blocks below 5 are pruned, which are seen by the snapshot of the next
+    // block
+    suite.shared.prune(15).unwrap();
+    let tip = suite.shared.snapshot().tip_header().clone();
+    suite
+        .chain_controller
+        .process_block(Arc::new(next_block(&suite.shared, &tip)))
+        .expect("processing new block should be ok");
+
+    for verbosity in ["0x0", "0x2"] {
+        let response = request("get_block", vec![json!(pruned_hash), json!(verbosity)]);
+        assert_eq!(response.error["code"], Value::from(-203));
+        let response = request("get_block_by_number", vec![json!("0x3"), json!(verbosity)]);
+        assert_eq!(response.error["code"], Value::from(-203));
+    }
+    // the headers are kept
+    let response = request("get_header", vec![json!(pruned_hash)]);
+    assert_eq!(response.result["number"], Value::from("0x3"));
+    // the recent blocks are kept
+    let response = request("get_block_by_number", vec![json!("0x5")]);
+    assert_eq!(response.result["header"]["number"], Value::from("0x5"));
+}
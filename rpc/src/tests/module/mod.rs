@@ -1,2 +1,4 @@
+mod chain;
 mod miner;
 mod pool;
+mod subscription;
@@ -0,0 +1,318 @@
+use crate::module::subscription::{CellFilter, RecentBlocks, RecentEvents};
+use crate::module::{SubscriptionRpc, SubscriptionRpcImpl, SubscriptionSession};
+use crate::tests::{next_block, setup};
+use crate::{auth::RpcSession, rate_limit::Remote};
+use ckb_jsonrpc_types::{OutPoint, SubscriptionFilter};
+use ckb_store::ChainStore;
+use ckb_types::{
+    bytes::Bytes,
+    core::{BlockBuilder, ScriptHashType, TransactionBuilder},
+    h256,
+    packed::{self, CellOutput},
+    prelude::*,
+};
+use jsonrpc_core::futures::channel::mpsc;
+use jsonrpc_pubsub::{PubSubHandler, Session};
+use serde_json::Value;
+use std::{sync::Arc, thread::sleep, time::Duration};
+
+#[test]
+fn test_recent_events() {
+    let mut recent_events = RecentEvents::default();
+    for i in 0..2000 {
+        assert_eq!(recent_events.push(i.to_string(), None), i + 1);
+    }
+
+    let replayed: Vec<u64> = recent_events.since(1990).map(|event| event.seq).collect();
+    assert_eq!(replayed, (1991..=2000).collect::<Vec<_>>());
+    let last = recent_events.since(1999).next().unwrap();
+    assert_eq!((last.seq, last.data.as_str()), (2000, "1999"));
+    assert!(recent_events.since(2000).next().is_none());
+
+    // only the recent events are kept
+    let replayed: Vec<u64> = recent_events.since(0).map(|event| event.seq).collect();
+    assert_eq!(replayed, (977..=2000).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_recent_events_size_limit() {
+    let mut recent_events = RecentEvents::default();
+    let data = "0".repeat(1024 * 1024);
+    for i in 0..20 {
+        assert_eq!(recent_events.push(data.clone(), None), i + 1);
+    }
+
+    // only the recent 16 MiB are kept
+    let replayed: Vec<u64> = recent_events.since(0).map(|event| event.seq).collect();
+    assert_eq!(replayed, (5..=20).collect::<Vec<_>>());
+    assert!(recent_events.check_cursor(3).is_err());
+    assert!(recent_events.check_cursor(4).is_ok());
+
+    // the latest event is kept even if it exceeds the limit
+    recent_events.push("0".repeat(17 * 1024 * 1024), None);
+    let replayed: Vec<u64> = recent_events.since(0).map(|event| event.seq).collect();
+    assert_eq!(replayed, vec![21]);
+}
+
+#[test]
+fn test_recent_blocks_reorg() {
+    let hash = |n: u64| packed::Byte32::new([n as u8; 32]);
+    let mut recent_blocks = RecentBlocks::default();
+    for number in 1..=10 {
+        assert_eq!(recent_blocks.push(number, hash(number)), number);
+    }
+
+    // the chain is reorganized to a shorter fork, the sequence numbers never go back
+    assert_eq!(recent_blocks.push(9, hash(109)), 11);
+    assert_eq!(recent_blocks.push(10, hash(110)), 12);
+    assert_eq!(recent_blocks.push(13, hash(113)), 13);
+    assert_eq!(recent_blocks.seq_of(&hash(110)), Some(12));
+
+    // the rolled back blocks are replayed as well
+    let replayed: Vec<u64> = recent_blocks.since(8).map(|(seq, _)| *seq).collect();
+    assert_eq!(replayed, vec![9, 10, 11, 12, 13]);
+    assert!(recent_blocks.check_cursor(8).is_ok());
+    // the cursor is ahead of the last published block
+    assert!(recent_blocks.check_cursor(14).is_err());
+}
+
+fn script(code_hash: packed::Byte32, args: &[u8]) -> packed::Script {
+    packed::ScriptBuilder::default()
+        .code_hash(code_hash)
+        .hash_type(ScriptHashType::Type.into())
+        .args(args.to_vec().pack())
+        .build()
+}
+
+#[test]
+fn test_cell_filter() {
+    let lock = script(h256!("0x1").pack(), &[1]);
+    let type_ = script(h256!("0xabcd").pack(), &[]);
+    let output = CellOutput::new_builder()
+        .lock(lock.clone())
+        .type_(Some(type_.clone()).pack())
+        .build();
+    let data = [0x12, 0x34, 0x56];
+
+    let matches =
+        |filter: SubscriptionFilter| CellFilter::from(filter).matches_cell(&output, &data);
+    assert!(matches(SubscriptionFilter {
+        lock: Some(lock.clone().into()),
+        ..Default::default()
+    }));
+    assert!(!matches(SubscriptionFilter {
+        lock: Some(script(h256!("0x1").pack(), &[2]).into()),
+        ..Default::default()
+    }));
+    assert!(matches(SubscriptionFilter {
+        type_: Some(type_.into()),
+        ..Default::default()
+    }));
+    assert!(matches(SubscriptionFilter {
+        type_code_hash_prefix: Some(Bytes::from(vec![0u8; 30]).pack().into()),
+        ..Default::default()
+    }));
+    assert!(!matches(SubscriptionFilter {
+        type_code_hash_prefix: Some(Bytes::from(vec![0xab]).pack().into()),
+        ..Default::default()
+    }));
+    assert!(matches(SubscriptionFilter {
+        output_data_prefix: Some(Bytes::from(vec![0x12, 0x34]).pack().into()),
+        ..Default::default()
+    }));
+
+    // all the conditions must match
+    assert!(!matches(SubscriptionFilter {
+        lock: Some(lock.clone().into()),
+        output_data_prefix: Some(Bytes::from(vec![0x34]).pack().into()),
+        ..Default::default()
+    }));
+
+    // the type conditions never match the cells without a type script
+    let output = CellOutput::new_builder().lock(lock).build();
+    let filter: CellFilter = SubscriptionFilter {
+        type_code_hash_prefix: Some(Bytes::new().pack().into()),
+        ..Default::default()
+    }
+    .into();
+    assert!(!filter.matches_cell(&output, &data));
+}
+
+#[test]
+fn test_match_block() {
+    let watched = script(h256!("0x1").pack(), &[1]);
+    let other = script(h256!("0x1").pack(), &[2]);
+    let output = |lock: &packed::Script| CellOutput::new_builder().lock(lock.clone()).build();
+    let tx = |lock: &packed::Script| {
+        TransactionBuilder::default()
+            .output(output(lock))
+            .output_data(Bytes::new().pack())
+            .build()
+    };
+    // spends a watched cell, and creates a cell of the other lock
+    let spending = TransactionBuilder::default()
+        .input(packed::CellInput::new(
+            packed::OutPoint::new(h256!("0xabcd").pack(), 1),
+            0,
+        ))
+        .input(packed::CellInput::new(
+            packed::OutPoint::new(h256!("0xabcd").pack(), 2),
+            0,
+        ))
+        .output(output(&other))
+        .output_data(Bytes::new().pack())
+        .build();
+    let block = BlockBuilder::default()
+        .transaction(tx(&other))
+        .transaction(tx(&watched))
+        .transaction(spending.clone())
+        .build();
+    let input_cells = vec![
+        vec![],
+        vec![],
+        vec![
+            Some((output(&watched), Bytes::from(vec![0x12]))),
+            Some((output(&other), Bytes::new())),
+        ],
+    ];
+
+    let filter: CellFilter = SubscriptionFilter {
+        lock: Some(watched.clone().into()),
+        ..Default::default()
+    }
+    .into();
+    let matched = filter.match_block(&block, &input_cells).unwrap();
+    assert_eq!(matched.header.hash, block.hash().unpack());
+    assert_eq!(matched.transactions.len(), 2);
+
+    // the created cell
+    let created = &matched.transactions[0];
+    assert_eq!(created.tx_hash, tx(&watched).hash().unpack());
+    assert!(created.inputs.is_empty());
+    assert_eq!(created.outputs.len(), 1);
+    assert_eq!(
+        created.outputs[0].out_point,
+        OutPoint::from(packed::OutPoint::new(tx(&watched).hash(), 0))
+    );
+
+    // only the consumed cell of the watched lock is pushed
+    let consumed = &matched.transactions[1];
+    assert_eq!(consumed.tx_hash, spending.hash().unpack());
+    assert!(consumed.outputs.is_empty());
+    assert_eq!(consumed.inputs.len(), 1);
+    assert_eq!(
+        consumed.inputs[0].out_point,
+        OutPoint::from(packed::OutPoint::new(h256!("0xabcd").pack(), 1))
+    );
+    assert_eq!(consumed.inputs[0].output_data.as_bytes(), &[0x12][..]);
+
+    // the unresolved cells never match
+    let unresolved = vec![vec![], vec![], vec![None, None]];
+    let matched = filter.match_block(&block, &unresolved).unwrap();
+    assert_eq!(matched.transactions.len(), 1);
+
+    let filter: CellFilter = SubscriptionFilter {
+        lock: Some(script(h256!("0x2").pack(), &[]).into()),
+        ..Default::default()
+    }
+    .into();
+    assert!(filter.match_block(&block, &input_cells).is_none());
+}
+
+// Reads the pushed messages and returns the sequence numbers of them.
+fn received_seqs(receiver: &mut mpsc::UnboundedReceiver<String>, count: usize) -> Vec<u64> {
+    let mut seqs = Vec::new();
+    for _ in 0..100 {
+        while let Ok(Some(message)) = receiver.try_next() {
+            let message: Value = serde_json::from_str(&message).unwrap();
+            let result: Value =
+                serde_json::from_str(message["params"]["result"].as_str().unwrap()).unwrap();
+            let seq = result["seq"].as_str().unwrap().trim_start_matches("0x");
+            seqs.push(u64::from_str_radix(seq, 16).unwrap());
+        }
+        if seqs.len() >= count {
+            break;
+        }
+        sleep(Duration::from_millis(100));
+    }
+    seqs
+}
+
+#[test]
+fn test_resume_subscription() {
+    let suite = setup();
+    let mut handler = PubSubHandler::<RpcSession>::default();
+    handler.extend_with(
+        SubscriptionRpcImpl::new(suite.shared.clone(), "TestSubscription").to_delegate(),
+    );
+    let (sender, mut receiver) = mpsc::unbounded();
+    let meta = RpcSession::with_subscription(
+        SubscriptionSession::new(Session::new(sender)),
+        Remote::Unknown,
+    );
+    let request = |method: &str, params: Value| {
+        let request = serde_json::json!({
+            "id": 42,
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        });
+        let response = handler
+            .handle_request_sync(&request.to_string(), meta.clone())
+            .unwrap();
+        serde_json::from_str::<Value>(&response).unwrap()
+    };
+    let process_next_block = || {
+        let tip = suite.shared.snapshot().tip_header().clone();
+        let block = next_block(&suite.shared, &tip);
+        suite
+            .chain_controller
+            .process_block(Arc::new(block))
+            .expect("processing new block should be ok");
+    };
+
+    let subscription = request(
+        "subscribe",
+        serde_json::json!(["new_tip_header", {"seq": "0x14"}]),
+    );
+    process_next_block();
+    assert_eq!(received_seqs(&mut receiver, 1), vec![21]);
+    let unsubscribed = request("unsubscribe", serde_json::json!([subscription["result"]]));
+    assert_eq!(unsubscribed["result"], Value::Bool(true));
+
+    // misses the blocks 22 and 23, and resumes from the block 21
+    process_next_block();
+    process_next_block();
+    let resumed = request(
+        "subscribe",
+        serde_json::json!(["new_tip_header", {"seq": "0x15"}]),
+    );
+    assert!(resumed["result"].is_string());
+    assert_eq!(received_seqs(&mut receiver, 2), vec![22, 23]);
+    process_next_block();
+    assert_eq!(received_seqs(&mut receiver, 1), vec![24]);
+
+    // resumes from a block hash
+    let hash = suite.shared.store().get_block_hash(22).unwrap();
+    let resumed = request(
+        "subscribe",
+        serde_json::json!(["new_tip_block", { "block_hash": format!("{:#x}", hash) }]),
+    );
+    assert!(resumed["result"].is_string());
+    assert_eq!(received_seqs(&mut receiver, 2), vec![23, 24]);
+
+    // the cursors of the pool topics published before the node started are rejected
+    for seq in ["0x1", "0xffffffffffffffff"] {
+        let rejected = request(
+            "subscribe",
+            serde_json::json!(["new_transaction", { "seq": seq }]),
+        );
+        assert_eq!(rejected["error"]["code"], Value::from(-1200));
+    }
+    // the cursors of the block topics ahead of the tip are rejected
+    let rejected = request(
+        "subscribe",
+        serde_json::json!(["new_tip_header", {"seq": "0x100"}]),
+    );
+    assert_eq!(rejected["error"]["code"], Value::from(-1200));
+}
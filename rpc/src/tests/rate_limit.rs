@@ -0,0 +1,128 @@
+use crate::auth::RpcSession;
+use crate::rate_limit::{Client, RateLimitMiddleware, RateLimiter, Remote};
+use crate::server::forwarded_for;
+use ckb_app_config::RpcRateLimitConfig;
+use jsonrpc_core::{MetaIoHandler, Params};
+use jsonrpc_http_server::hyper;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+fn rate_limiter() -> RateLimiter {
+    RateLimiter::new(&RpcRateLimitConfig {
+        requests_per_second: 2,
+        burst: 8,
+        trust_forwarded_for: false,
+        method_costs: vec![("get_tip_header".to_owned(), 2)].into_iter().collect(),
+    })
+}
+
+fn params(value: serde_json::Value) -> Params {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_method_cost() {
+    let rate_limiter = rate_limiter();
+    let hash = "0xa5f5c85987a15de25661e5a214f2c1449cd803f071acc7999820f25246471f40";
+    assert_eq!(rate_limiter.cost("get_block", &params(json!([hash]))), 4);
+    assert_eq!(
+        rate_limiter.cost("get_block", &params(json!([hash, "0x2"]))),
+        4
+    );
+    assert_eq!(
+        rate_limiter.cost("get_block", &params(json!([hash, "0x0"]))),
+        1
+    );
+    assert_eq!(
+        rate_limiter.cost(
+            "get_block_by_number",
+            &params(json!({"block_number": "0x1", "verbosity": "0x0"}))
+        ),
+        1
+    );
+    assert_eq!(rate_limiter.cost("get_transaction_proof", &Params::None), 4);
+    // capped at burst
+    assert_eq!(rate_limiter.cost("dry_run_transaction", &Params::None), 8);
+    assert_eq!(rate_limiter.cost("get_tip_header", &Params::None), 2);
+    assert_eq!(rate_limiter.cost("local_node_info", &Params::None), 1);
+}
+
+#[test]
+fn test_token_bucket() {
+    let rate_limiter = rate_limiter();
+    let now = Instant::now();
+    let alice = Client::Remote(Remote::Ip("127.0.0.1".parse().unwrap()));
+    let bob = Client::Remote(Remote::Ip("127.0.0.2".parse().unwrap()));
+
+    assert!(rate_limiter.take(alice, 4, now));
+    assert!(rate_limiter.take(alice, 4, now));
+    assert!(!rate_limiter.take(alice, 1, now));
+    // the other clients are not affected
+    assert!(rate_limiter.take(bob, 8, now));
+
+    let later = now + Duration::from_secs(1);
+    assert!(rate_limiter.take(alice, 2, later));
+    assert!(!rate_limiter.take(alice, 1, later));
+
+    // the bucket never holds more than burst tokens
+    let much_later = now + Duration::from_secs(3600);
+    assert!(rate_limiter.take(alice, 8, much_later));
+    assert!(!rate_limiter.take(alice, 1, much_later));
+}
+
+#[test]
+fn test_rate_limit_middleware() {
+    let mut io_handler =
+        MetaIoHandler::with_middleware(RateLimitMiddleware::new(Some(rate_limiter())));
+    io_handler.add_sync_method("get_tip_header", |_| Ok("tip".into()));
+    let meta = RpcSession::with_authorization(None, Remote::Unknown);
+
+    let request = r#"{"jsonrpc":"2.0","method":"get_tip_header","params":[],"id":1}"#;
+    for _ in 0..4 {
+        assert_eq!(
+            io_handler.handle_request_sync(request, meta.clone()),
+            Some(r#"{"jsonrpc":"2.0","result":"tip","id":1}"#.to_owned())
+        );
+    }
+    let response = io_handler.handle_request_sync(request, meta).unwrap();
+    assert!(response.contains(r#""code":-10"#));
+
+    // the authenticated clients are limited by their credentials
+    let mut meta = RpcSession::with_authorization(None, Remote::Unknown);
+    meta.credential = Some(0);
+    assert_eq!(
+        io_handler.handle_request_sync(request, meta),
+        Some(r#"{"jsonrpc":"2.0","result":"tip","id":1}"#.to_owned())
+    );
+}
+
+#[test]
+fn test_forwarded_for() {
+    let request = |headers: &[(&str, &str)]| {
+        let mut builder = hyper::Request::builder();
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(hyper::Body::empty()).unwrap()
+    };
+    let ip = |ip: &str| Some(ip.parse().unwrap());
+
+    // the entries before the last one are sent by the client
+    assert_eq!(
+        forwarded_for(&request(&[("X-Forwarded-For", "1.1.1.1, 10.0.0.1")])),
+        ip("10.0.0.1")
+    );
+    assert_eq!(
+        forwarded_for(&request(&[("X-Forwarded-For", "10.0.0.1")])),
+        ip("10.0.0.1")
+    );
+    assert_eq!(
+        forwarded_for(&request(&[("X-Real-IP", "10.0.0.2")])),
+        ip("10.0.0.2")
+    );
+    assert_eq!(
+        forwarded_for(&request(&[("X-Forwarded-For", "1.1.1.1, unknown")])),
+        None
+    );
+    assert_eq!(forwarded_for(&request(&[])), None);
+}
@@ -12,7 +12,7 @@ pub use crate::error::{ScriptError, TransactionScriptError};
 pub use crate::ill_transaction_checker::IllTransactionChecker;
 pub use crate::types::{
     CoreMachine, ScriptGroup, ScriptGroupType, ScriptVersion, TransactionSnapshot,
-    TransactionState, VerifyResult, VmIsa, VmVersion,
+    TransactionState, VerifyResult, VmIsa, VmVersion, TRANSACTION_SNAPSHOT_VERSION,
 };
 pub use crate::verify::TransactionScriptsVerifier;
 pub use crate::verify_env::TxVerifyEnv;
@@ -216,6 +216,9 @@ impl fmt::Display for ScriptGroupType {
     }
 }
 
+/// The version of the serialized `TransactionSnapshot`, see `TransactionSnapshot::to_bytes`.
+pub const TRANSACTION_SNAPSHOT_VERSION: u32 = 2;
+
 /// Struct specifies which script has verified so far.
 /// Snapshot is lifetime free, but capture snapshot need heavy memory copy
 pub struct TransactionSnapshot {
@@ -268,6 +271,156 @@ impl TransactionSnapshot {
             (remain, true)
         }
     }
+
+    /// Serializes the snapshot, including the suspended VM state.
+    ///
+    /// The data starts with `TRANSACTION_SNAPSHOT_VERSION` and the VM version of the latest
+    /// script version. All the integers are little endian, and the lengths of the vectors are
+    /// `u32` prefixes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&TRANSACTION_SNAPSHOT_VERSION.to_le_bytes());
+        data.extend_from_slice(&ScriptVersion::latest().vm_version().to_le_bytes());
+        data.extend_from_slice(&(self.current as u64).to_le_bytes());
+        data.extend_from_slice(&self.current_cycles.to_le_bytes());
+        data.extend_from_slice(&self.limit_cycles.to_le_bytes());
+        match self.snap {
+            Some((ref snap, cycles)) => {
+                data.push(1);
+                data.extend_from_slice(&cycles.to_le_bytes());
+                data.extend_from_slice(&snap.version.to_le_bytes());
+                for register in snap.registers.iter() {
+                    data.extend_from_slice(&register.to_le_bytes());
+                }
+                data.extend_from_slice(&snap.pc.to_le_bytes());
+                write_len(&mut data, snap.page_indices.len())?;
+                for index in snap.page_indices.iter() {
+                    data.extend_from_slice(&index.to_le_bytes());
+                }
+                write_len(&mut data, snap.page_flags.len())?;
+                data.extend_from_slice(&snap.page_flags);
+                write_len(&mut data, snap.pages.len())?;
+                for page in snap.pages.iter() {
+                    write_len(&mut data, page.len())?;
+                    data.extend_from_slice(page);
+                }
+            }
+            None => data.push(0),
+        }
+        Ok(data)
+    }
+
+    /// Deserializes the snapshot serialized by `to_bytes`.
+    ///
+    /// Returns `None` if the data is serialized by an incompatible version, or the VM version
+    /// has changed since then. The transaction should be verified from the beginning instead.
+    pub fn from_bytes(data: &[u8]) -> Result<Option<Self>, Error> {
+        let mut reader = SnapshotReader { data };
+        if reader.read_u32()? != TRANSACTION_SNAPSHOT_VERSION
+            || reader.read_u32()? != ScriptVersion::latest().vm_version()
+        {
+            return Ok(None);
+        }
+        let current = reader.read_u64()? as usize;
+        let current_cycles = reader.read_u64()?;
+        let limit_cycles = reader.read_u64()?;
+        let snap = match reader.read_bytes(1)?[0] {
+            0 => None,
+            1 => {
+                let cycles = reader.read_u64()?;
+                let version = reader.read_u32()?;
+                let registers = reader.read_u64_array()?;
+                let pc = reader.read_u64()?;
+                let page_indices = (0..reader.read_u32()?)
+                    .map(|_| reader.read_u64())
+                    .collect::<Result<_, _>>()?;
+                let len = reader.read_u32()? as usize;
+                let page_flags = reader.read_bytes(len)?.to_vec();
+                let pages = (0..reader.read_u32()?)
+                    .map(|_| {
+                        let len = reader.read_u32()? as usize;
+                        reader.read_bytes(len).map(<[u8]>::to_vec)
+                    })
+                    .collect::<Result<_, _>>()?;
+                let snap = Snapshot {
+                    version,
+                    registers,
+                    pc,
+                    page_indices,
+                    page_flags,
+                    pages,
+                };
+                Some((snap, cycles))
+            }
+            flag => {
+                return Err(snapshot_error(format!("invalid vm snapshot flag {}", flag)));
+            }
+        };
+        if !reader.data.is_empty() {
+            return Err(snapshot_error(format!(
+                "{} trailing bytes",
+                reader.data.len()
+            )));
+        }
+        Ok(Some(TransactionSnapshot {
+            current,
+            snap,
+            current_cycles,
+            limit_cycles,
+        }))
+    }
+}
+
+fn snapshot_error(message: String) -> Error {
+    ScriptError::VMInternalError(format!("malformed serialized snapshot: {}", message))
+        .unknown_source()
+        .into()
+}
+
+fn write_len(data: &mut Vec<u8>, len: usize) -> Result<(), Error> {
+    let len = u32::try_from(len)
+        .map_err(|_| snapshot_error(format!("the length {} overflows u32", len)))?;
+    data.extend_from_slice(&len.to_le_bytes());
+    Ok(())
+}
+
+struct SnapshotReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> SnapshotReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() < len {
+            return Err(snapshot_error(format!(
+                "expect {} more bytes, but only {} left",
+                len,
+                self.data.len()
+            )));
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.read_bytes(4)?);
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Error> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.read_bytes(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn read_u64_array<const N: usize>(&mut self) -> Result<[u64; N], Error> {
+        let mut array = [0u64; N];
+        for item in array.iter_mut() {
+            *item = self.read_u64()?;
+        }
+        Ok(array)
+    }
 }
 
 impl TryFrom<TransactionState<'_>> for TransactionSnapshot {
@@ -856,6 +856,47 @@ fn check_typical_secp256k1_blake160_2_in_2_out_tx_with_complete() {
     assert_eq!(cycles, cycles_once);
 }
 
+#[test]
+fn check_typical_secp256k1_blake160_2_in_2_out_tx_with_serialized_snap() {
+    let script_version = SCRIPT_VERSION;
+
+    let rtx = random_2_in_2_out_rtx();
+    let mut cycles = 0;
+    let verifier = TransactionScriptsVerifierWithEnv::new();
+    let result = verifier.verify_map(script_version, &rtx, |verifier| {
+        let snap: TransactionSnapshot = match verifier
+            .resumable_verify(TWO_IN_TWO_OUT_CYCLES / 10)
+            .unwrap()
+        {
+            VerifyResult::Suspended(state) => state.try_into().unwrap(),
+            VerifyResult::Completed(_) => unreachable!(),
+        };
+
+        let data = snap.to_bytes().unwrap();
+        let restored = TransactionSnapshot::from_bytes(&data)
+            .unwrap()
+            .expect("compatible");
+        assert_eq!(restored.current, snap.current);
+        assert_eq!(restored.current_cycles, snap.current_cycles);
+        assert_eq!(restored.limit_cycles, snap.limit_cycles);
+        assert_eq!(restored.to_bytes().unwrap(), data);
+        assert!(TransactionSnapshot::from_bytes(&data[..data.len() - 1]).is_err());
+
+        let mut incompatible = data;
+        incompatible[0] = incompatible[0].wrapping_add(1);
+        assert!(TransactionSnapshot::from_bytes(&incompatible)
+            .unwrap()
+            .is_none());
+
+        cycles = verifier.complete(&restored, TWO_IN_TWO_OUT_CYCLES).unwrap();
+
+        verifier.verify(TWO_IN_TWO_OUT_CYCLES)
+    });
+
+    let cycles_once = result.unwrap();
+    assert_eq!(cycles, cycles_once);
+}
+
 #[test]
 fn load_code_into_global() {
     let script_version = SCRIPT_VERSION;
@@ -7,7 +7,7 @@ use ckb_channel::Sender;
 use ckb_constant::store::TX_INDEX_UPPER_BOUND;
 use ckb_constant::sync::MAX_TIP_AGE;
 use ckb_db::{Direction, IteratorMode};
-use ckb_db_schema::{COLUMN_BLOCK_BODY, COLUMN_NUMBER_HASH};
+use ckb_db_schema::{COLUMN_BLOCK_BODY, COLUMN_META, COLUMN_NUMBER_HASH, META_PRUNED_NUMBER_KEY};
 use ckb_error::{AnyError, Error};
 use ckb_notify::NotifyController;
 use ckb_proposal_table::ProposalView;
@@ -23,7 +23,7 @@ use ckb_types::{
 use ckb_verification::cache::TxVerificationCache;
 use faketime::unix_time_as_millis;
 use std::cmp;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
@@ -32,6 +32,11 @@ use std::time::Duration;
 const FREEZER_INTERVAL: Duration = Duration::from_secs(60);
 const THRESHOLD_EPOCH: EpochNumber = 2;
 const MAX_FREEZE_LIMIT: BlockNumber = 30_000;
+/// The recent block bodies are always kept to handle the chain reorganizations.
+const MIN_PRUNE_DEPTH: BlockNumber = 10_000;
+const MAX_PRUNE_LIMIT: BlockNumber = 30_000;
+/// The block bodies are deleted in the write batches of this number of blocks.
+const PRUNE_BATCH_LIMIT: BlockNumber = 1_000;
 
 /// An owned permission to close on a freezer thread
 pub struct FreezerClose {
@@ -87,40 +92,68 @@ impl Shared {
         }
     }
     /// Spawn freeze background thread that periodically checks and moves ancient data from the kv database into the freezer.
+    ///
+    /// The thread also prunes the old block bodies when pruning is enabled.
     pub fn spawn_freeze(&self) -> Option<FreezerClose> {
-        if let Some(freezer) = self.store.freezer() {
+        let freezer = self.store.freezer();
+        let prune_depth = self.store.prune_depth();
+        if freezer.is_none() && prune_depth.is_none() {
+            return None;
+        }
+        if freezer.is_some() {
             ckb_logger::info!("Freezer enable");
-            let (signal_sender, signal_receiver) =
-                ckb_channel::bounded::<()>(service::SIGNAL_CHANNEL_SIZE);
-            let shared = self.clone();
-            let thread = thread::Builder::new()
-                .spawn(move || loop {
-                    match signal_receiver.recv_timeout(FREEZER_INTERVAL) {
-                        Err(_) => {
-                            if let Err(e) = shared.freeze() {
-                                ckb_logger::error!("Freezer error {}", e);
-                                break;
-                            }
-                        }
-                        Ok(_) => {
-                            ckb_logger::info!("Freezer closing");
+        }
+        if let Some(depth) = prune_depth {
+            if depth < MIN_PRUNE_DEPTH {
+                ckb_logger::warn!(
+                    "prune_depth {} is too small, the minimal depth {} is used",
+                    depth,
+                    MIN_PRUNE_DEPTH
+                );
+            }
+            ckb_logger::info!("Pruning enable, depth {}", depth.max(MIN_PRUNE_DEPTH));
+        }
+        let (signal_sender, signal_receiver) =
+            ckb_channel::bounded::<()>(service::SIGNAL_CHANNEL_SIZE);
+        let shared = self.clone();
+        let thread = thread::Builder::new()
+            .spawn(move || loop {
+                match signal_receiver.recv_timeout(FREEZER_INTERVAL) {
+                    Err(_) => {
+                        if let Err(e) = shared.freeze_and_prune() {
+                            ckb_logger::error!("Freezer error {}", e);
                             break;
                         }
                     }
-                })
-                .expect("Start FreezerService failed");
-
-            let stop = StopHandler::new(
-                SignalSender::Crossbeam(signal_sender),
-                Some(thread),
-                "freezer".to_string(),
-            );
-            return Some(FreezerClose {
-                stopped: Arc::clone(&freezer.stopped),
-                stop,
-            });
+                    Ok(_) => {
+                        ckb_logger::info!("Freezer closing");
+                        break;
+                    }
+                }
+            })
+            .expect("Start FreezerService failed");
+
+        let stop = StopHandler::new(
+            SignalSender::Crossbeam(signal_sender),
+            Some(thread),
+            "freezer".to_string(),
+        );
+        Some(FreezerClose {
+            stopped: freezer
+                .map(|freezer| Arc::clone(&freezer.stopped))
+                .unwrap_or_default(),
+            stop,
+        })
+    }
+
+    fn freeze_and_prune(&self) -> Result<(), Error> {
+        if self.store.freezer().is_some() {
+            self.freeze()?;
+        }
+        if let Some(depth) = self.store.prune_depth() {
+            self.prune(depth.max(MIN_PRUNE_DEPTH))?;
         }
-        None
+        Ok(())
     }
 
     fn freeze(&self) -> Result<(), Error> {
@@ -254,6 +287,96 @@ impl Shared {
         Ok(())
     }
 
+    /// Prunes the bodies of the main chain blocks deeper than `depth` from the tip, and the whole
+    /// side chain blocks at the same heights, at most `MAX_PRUNE_LIMIT` blocks each time.
+    ///
+    /// The pruning thread calls it with the depth no less than `MIN_PRUNE_DEPTH`.
+    pub fn prune(&self, depth: BlockNumber) -> Result<(), Error> {
+        let snapshot = self.snapshot();
+        // the genesis block is never pruned
+        let pruned_number = cmp::max(snapshot.get_pruned_number(), 1);
+        let threshold = cmp::min(
+            snapshot.tip_number().saturating_sub(depth),
+            pruned_number + MAX_PRUNE_LIMIT,
+        );
+        if threshold <= pruned_number {
+            ckb_logger::trace!("pruning skip, threshold {}", threshold);
+            return Ok(());
+        }
+
+        if let Some(freezer) = self.store.freezer() {
+            // The frozen blocks have been wiped out from the kv database, and the freezer is
+            // pruned by whole data files.
+            let new_pruned_number = freezer.prune(cmp::min(threshold, freezer.number()))?;
+            if new_pruned_number > pruned_number {
+                let mut batch = self.store.new_write_batch();
+                let value: packed::Uint64 = new_pruned_number.pack();
+                batch.put(COLUMN_META, META_PRUNED_NUMBER_KEY, value.as_slice())?;
+                self.store.write_sync(&batch)?;
+                ckb_logger::info!("pruned the frozen blocks below {}", new_pruned_number);
+            }
+            return Ok(());
+        }
+
+        let mut pruned = BTreeSet::new();
+        let mut start = pruned_number;
+        while start < threshold {
+            let end = cmp::min(start + PRUNE_BATCH_LIMIT, threshold);
+            let mut batch = self.store.new_write_batch();
+            for number in start..end {
+                let pack_number: packed::Uint64 = number.pack();
+                let prefix = pack_number.as_slice();
+                // the side chain blocks stored after the snapshot are iterated as well
+                for (key, value) in self
+                    .store
+                    .get_iter(
+                        COLUMN_NUMBER_HASH,
+                        IteratorMode::From(prefix, Direction::Forward),
+                    )
+                    .take_while(|(key, _)| key.starts_with(prefix))
+                {
+                    let reader = packed::NumberHashReader::from_slice_should_be_ok(key.as_ref());
+                    let block_hash = reader.block_hash().to_entity();
+                    let txs =
+                        packed::Uint32Reader::from_slice_should_be_ok(value.as_ref()).unpack();
+                    if snapshot.is_main_chain(&block_hash) {
+                        // remain header
+                        batch.delete_block_body(number, &block_hash, txs)?;
+                    } else {
+                        batch.delete_block(number, &block_hash, txs)?;
+                    }
+                    pruned.insert(block_hash);
+                }
+            }
+            // the pruned number is saved with every batch, so an interrupted pruning resumes
+            // from the last written batch
+            let value: packed::Uint64 = end.pack();
+            batch.put(COLUMN_META, META_PRUNED_NUMBER_KEY, value.as_slice())?;
+            self.store.write_sync(&batch).map_err(|e| {
+                ckb_logger::error!("pruning write_batch delete failed {}", e);
+                e
+            })?;
+            start = end;
+        }
+        ckb_logger::info!("pruned the blocks below {}", threshold);
+
+        // the cached sections of the pruned blocks would be read back with an empty body
+        if let Some(cache) = self.store.cache() {
+            for hash in &pruned {
+                cache.headers.lock().pop(hash);
+                cache.block_proposals.lock().pop(hash);
+                cache.block_tx_hashes.lock().pop(hash);
+                cache.block_uncles.lock().pop(hash);
+                cache.block_extensions.lock().pop(hash);
+            }
+        }
+
+        if let (Some(start), Some(end)) = (pruned.iter().next(), pruned.iter().next_back()) {
+            self.compact_block_body(start, end);
+        }
+        Ok(())
+    }
+
     fn compact_block_body(&self, start: &packed::Byte32, end: &packed::Byte32) {
         let start_t = packed::TransactionKey::new_builder()
             .block_hash(start.clone())
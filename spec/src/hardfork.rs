@@ -57,6 +57,11 @@ pub struct HardForkConfig {
     /// Ref: CKB RFC 0038
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rfc_0038: Option<EpochNumber>,
+    /// Commit the chain root in the block extension.
+    ///
+    /// Ref: CKB RFC 0044
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rfc_0044: Option<EpochNumber>,
 }
 
 macro_rules! check_default {
@@ -103,7 +108,8 @@ impl HardForkConfig {
             .rfc_0031(check_default!(self, rfc_0031, ckb2021))
             .rfc_0032(check_default!(self, rfc_0032, ckb2021))
             .rfc_0036(check_default!(self, rfc_0036, ckb2021))
-            .rfc_0038(check_default!(self, rfc_0038, ckb2021));
+            .rfc_0038(check_default!(self, rfc_0038, ckb2021))
+            .rfc_0044(check_default!(self, rfc_0044, EpochNumber::MAX));
         Ok(builder)
     }
 
@@ -119,6 +125,7 @@ impl HardForkConfig {
             .rfc_0032(self.rfc_0032.unwrap_or(default))
             .rfc_0036(self.rfc_0036.unwrap_or(default))
             .rfc_0038(self.rfc_0038.unwrap_or(default))
+            .rfc_0044(self.rfc_0044.unwrap_or(default))
             .build()
     }
 }
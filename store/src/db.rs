@@ -13,7 +13,11 @@ use ckb_db::{
 use ckb_db_schema::{Col, CHAIN_SPEC_HASH_KEY, MIGRATION_VERSION_KEY};
 use ckb_error::Error;
 use ckb_freezer::Freezer;
-use ckb_types::{core::BlockExt, packed, prelude::*};
+use ckb_types::{
+    core::{BlockExt, BlockNumber},
+    packed,
+    prelude::*,
+};
 use std::sync::Arc;
 
 /// TODO(doc): @quake
@@ -22,6 +26,7 @@ pub struct ChainDB {
     db: RocksDB,
     freezer: Option<Freezer>,
     cache: Arc<StoreCache>,
+    prune_depth: Option<BlockNumber>,
 }
 
 impl<'a> ChainStore<'a> for ChainDB {
@@ -54,6 +59,7 @@ impl ChainDB {
             db,
             freezer: None,
             cache: Arc::new(cache),
+            prune_depth: config.prune_depth,
         }
     }
 
@@ -64,9 +70,16 @@ impl ChainDB {
             db,
             freezer: Some(freezer),
             cache: Arc::new(cache),
+            prune_depth: config.prune_depth,
         }
     }
 
+    /// Returns the depth from the tip within which the block bodies are kept, `None` when pruning
+    /// is disabled.
+    pub fn prune_depth(&self) -> Option<BlockNumber> {
+        self.prune_depth
+    }
+
     /// TODO(doc): @quake
     pub fn db(&self) -> &RocksDB {
         &self.db
@@ -170,6 +183,7 @@ impl ChainDB {
         db_txn.insert_block_epoch_index(&genesis_hash, &last_block_hash_in_previous_epoch)?;
         db_txn.insert_epoch_ext(&last_block_hash_in_previous_epoch, epoch)?;
         db_txn.attach_block(genesis)?;
+        db_txn.attach_header_digest(&genesis.header())?;
         db_txn.commit()?;
         Ok(())
     }
@@ -0,0 +1,794 @@
+//! Checks the integrity of the chain data in the store and the freezer.
+//!
+//! The main chain is walked backward from the tip by the parent hashes, and each block is cross
+//! checked against the main chain index, the header, the body, the transaction index and the
+//! freezer. Optionally, the live cells created in the checked blocks are recomputed from the
+//! blocks and compared with the live cell set.
+//!
+//! The corruption which only affects the indexes can be rebuilt from the blocks, such findings
+//! carry the fixes which are applied by [`Report::fix`].
+use crate::{ChainDB, ChainStore};
+use ckb_db::IteratorMode;
+use ckb_db_schema::{Col, COLUMN_CELL, COLUMN_INDEX, COLUMN_NUMBER_HASH, COLUMN_TRANSACTION_INFO};
+use ckb_error::{Error, InternalErrorKind};
+use ckb_types::{
+    core::{BlockNumber, BlockView, HeaderView},
+    packed,
+    prelude::*,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The options of the integrity check.
+#[derive(Clone, Debug, Default)]
+pub struct CheckOptions {
+    /// The number of the first checked block.
+    pub from: BlockNumber,
+    /// The number of the last checked block, the tip when it is `None`.
+    pub to: Option<BlockNumber>,
+    /// Whether to recompute the live cells created in the checked blocks.
+    ///
+    /// It requires the bodies of all the blocks since `from`, which are read from the tip, and
+    /// keeps the spent cells created in the checked blocks in memory.
+    pub check_cells: bool,
+}
+
+/// The part of the store where an inconsistency is found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FindingKind {
+    /// The main chain index in `COLUMN_INDEX`.
+    Index,
+    /// The block headers and the block exts.
+    Header,
+    /// The block bodies, uncles, proposals and `COLUMN_NUMBER_HASH`.
+    Body,
+    /// The transaction index in `COLUMN_TRANSACTION_INFO`.
+    TransactionInfo,
+    /// The freezer files.
+    Freezer,
+    /// The live cell set.
+    Cell,
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            FindingKind::Index => "index",
+            FindingKind::Header => "header",
+            FindingKind::Body => "body",
+            FindingKind::TransactionInfo => "transaction_info",
+            FindingKind::Freezer => "freezer",
+            FindingKind::Cell => "cell",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Fix {
+    Put(Col, Vec<u8>, Vec<u8>),
+    Delete(Col, Vec<u8>),
+}
+
+/// An inconsistency found by the integrity check.
+#[derive(Clone, Debug)]
+pub struct Finding {
+    /// The part of the store where the inconsistency is found.
+    pub kind: FindingKind,
+    /// The number of the related block.
+    pub block_number: Option<BlockNumber>,
+    /// The hash of the related block.
+    pub block_hash: Option<packed::Byte32>,
+    /// The description of the inconsistency.
+    pub message: String,
+    fixes: Vec<Fix>,
+}
+
+impl Finding {
+    fn new(kind: FindingKind, message: String) -> Self {
+        Finding {
+            kind,
+            block_number: None,
+            block_hash: None,
+            message,
+            fixes: Vec::new(),
+        }
+    }
+
+    fn block(mut self, number: BlockNumber, hash: &packed::Byte32) -> Self {
+        self.block_number = Some(number);
+        self.block_hash = Some(hash.clone());
+        self
+    }
+
+    fn put(mut self, col: Col, key: &[u8], value: &[u8]) -> Self {
+        self.fixes.push(Fix::Put(col, key.to_vec(), value.to_vec()));
+        self
+    }
+
+    fn delete(mut self, col: Col, key: &[u8]) -> Self {
+        self.fixes.push(Fix::Delete(col, key.to_vec()));
+        self
+    }
+
+    /// Returns true if the inconsistency only affects the indexes, and can be fixed by
+    /// [`Report::fix`].
+    pub fn is_fixable(&self) -> bool {
+        !self.fixes.is_empty()
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.kind)?;
+        if let Some(number) = self.block_number {
+            write!(f, " #{}", number)?;
+        }
+        if let Some(ref hash) = self.block_hash {
+            write!(f, " {:#x}", hash)?;
+        }
+        write!(f, ": {}", self.message)?;
+        if self.is_fixable() {
+            write!(f, " (fixable)")?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of the integrity check.
+#[derive(Clone, Debug)]
+pub struct Report {
+    /// The number of the first checked block.
+    pub from: BlockNumber,
+    /// The number of the last checked block.
+    pub to: BlockNumber,
+    /// Whether the live cells have been recomputed.
+    pub cells_checked: bool,
+    /// The inconsistencies found.
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    /// Returns the number of the fixable findings.
+    pub fn fixable(&self) -> usize {
+        self.findings.iter().filter(|f| f.is_fixable()).count()
+    }
+
+    /// Rebuilds the corrupted indexes found in the report, returns the number of the fixed
+    /// findings.
+    ///
+    /// The report must be generated from the same database, and no block is processed since.
+    pub fn fix(&self, db: &ChainDB) -> Result<usize, Error> {
+        let mut batch = db.new_write_batch();
+        let mut fixed = 0;
+        for finding in self.findings.iter().filter(|f| f.is_fixable()) {
+            for fix in &finding.fixes {
+                match fix {
+                    Fix::Put(col, key, value) => batch.put(*col, key, value)?,
+                    Fix::Delete(col, key) => batch.delete(*col, key)?,
+                }
+            }
+            fixed += 1;
+        }
+        if !batch.is_empty() {
+            db.write_sync(&batch)?;
+        }
+        Ok(fixed)
+    }
+}
+
+/// Checks the integrity of the chain data in the store.
+///
+/// The check reads a lot of data, use a snapshot of the store to get consistent results while
+/// the node is running.
+pub fn check<'a, S: ChainStore<'a>>(store: &'a S, options: &CheckOptions) -> Result<Report, Error> {
+    let tip = store
+        .get_tip_header()
+        .ok_or_else(|| InternalErrorKind::Database.other("the tip header is not found"))?;
+    let to = options.to.map_or(tip.number(), |to| to.min(tip.number()));
+    if options.from > to {
+        return Err(InternalErrorKind::Database
+            .other(format!("no block to check from {} to {}", options.from, to))
+            .into());
+    }
+    let pruned_number = store.get_pruned_number();
+    if options.check_cells && options.from.max(1) < pruned_number {
+        return Err(InternalErrorKind::Database
+            .other(format!(
+                "the bodies of the blocks below {} are pruned, the live cells can only be checked since it",
+                pruned_number
+            ))
+            .into());
+    }
+
+    let mut checker = Checker {
+        store,
+        from: options.from,
+        to,
+        check_cells: options.check_cells,
+        pruned_number,
+        frozen_number: store.freezer().map(|freezer| freezer.number()).unwrap_or(0),
+        findings: Vec::new(),
+        main_chain: HashMap::new(),
+        spent: HashSet::new(),
+    };
+    if let Some(freezer) = store.freezer() {
+        for issue in freezer.check_index()? {
+            checker
+                .findings
+                .push(Finding::new(FindingKind::Freezer, issue));
+        }
+    }
+    if options.to.is_none() {
+        checker.check_above_tip(tip.number());
+    }
+    checker.walk(tip);
+    checker.check_reverse_index();
+    checker.check_transaction_info_column();
+    if options.check_cells {
+        checker.check_cell_column();
+    }
+
+    Ok(Report {
+        from: options.from,
+        to,
+        cells_checked: options.check_cells,
+        findings: checker.findings,
+    })
+}
+
+struct Checker<'a, S> {
+    store: &'a S,
+    from: BlockNumber,
+    to: BlockNumber,
+    check_cells: bool,
+    pruned_number: BlockNumber,
+    frozen_number: BlockNumber,
+    findings: Vec<Finding>,
+    // the main chain blocks whose index is inconsistent
+    main_chain: HashMap<BlockNumber, packed::Byte32>,
+    // the cells created in the range and spent by the walked blocks
+    spent: HashSet<Vec<u8>>,
+}
+
+impl<'a, S: ChainStore<'a>> Checker<'a, S> {
+    fn in_range(&self, number: BlockNumber) -> bool {
+        number >= self.from && number <= self.to
+    }
+
+    fn report(&mut self, finding: Finding) {
+        self.findings.push(finding);
+    }
+
+    /// Returns the main chain block hash, which is derived from the tip instead of the index.
+    fn main_chain_hash(&self, number: BlockNumber) -> Option<packed::Byte32> {
+        self.main_chain
+            .get(&number)
+            .cloned()
+            .or_else(|| self.store.get_block_hash(number))
+    }
+
+    fn walk(&mut self, tip: HeaderView) {
+        let mut number = tip.number();
+        let mut hash = tip.hash();
+        loop {
+            let in_range = self.in_range(number);
+            if in_range {
+                self.check_index(number, &hash);
+            }
+            let parent_hash = match self.store.get_block_header(&hash) {
+                Some(header) => {
+                    if in_range {
+                        self.check_header(number, &hash, &header);
+                    }
+                    // the cells spent in the blocks above the range are required as well
+                    let body_required = in_range || self.check_cells;
+                    if body_required && !self.is_pruned(number) {
+                        if let Some(block) = self.load_block(number, &hash, &header) {
+                            if in_range {
+                                self.check_transaction_infos(&block);
+                            }
+                            if self.check_cells {
+                                self.check_cells_of(&block, in_range);
+                            }
+                        }
+                    }
+                    Some(header.parent_hash())
+                }
+                None => {
+                    self.report(
+                        Finding::new(FindingKind::Header, "the header is missing".to_string())
+                            .block(number, &hash),
+                    );
+                    number
+                        .checked_sub(1)
+                        .and_then(|parent| self.store.get_block_hash(parent))
+                }
+            };
+
+            if number == 0 || number == self.from {
+                return;
+            }
+            number -= 1;
+            match parent_hash {
+                Some(parent_hash) => hash = parent_hash,
+                None => {
+                    self.report(Finding::new(
+                        FindingKind::Index,
+                        format!(
+                            "the main chain is broken, the blocks from {} to {} are not checked",
+                            self.from, number
+                        ),
+                    ));
+                    return;
+                }
+            }
+        }
+    }
+
+    fn is_pruned(&self, number: BlockNumber) -> bool {
+        number > 0 && number < self.pruned_number
+    }
+
+    fn check_above_tip(&mut self, tip_number: BlockNumber) {
+        let mut number = tip_number + 1;
+        while let Some(hash) = self.store.get_block_hash(number) {
+            let key: packed::Uint64 = number.pack();
+            let mut finding = Finding::new(
+                FindingKind::Index,
+                "the main chain index is above the tip".to_string(),
+            )
+            .block(number, &hash)
+            .delete(COLUMN_INDEX, key.as_slice());
+            if self.store.get_block_number(&hash) == Some(number) {
+                finding = finding.delete(COLUMN_INDEX, hash.as_slice());
+            }
+            self.report(finding);
+            number += 1;
+        }
+    }
+
+    fn check_index(&mut self, number: BlockNumber, hash: &packed::Byte32) {
+        let key: packed::Uint64 = number.pack();
+        match self.store.get_block_hash(number) {
+            Some(ref indexed) if indexed == hash => {}
+            indexed => {
+                let message = match indexed {
+                    Some(indexed) => format!("the main chain index points to {:#x}", indexed),
+                    None => "the main chain index is missing".to_string(),
+                };
+                self.main_chain.insert(number, hash.clone());
+                self.report(
+                    Finding::new(FindingKind::Index, message)
+                        .block(number, hash)
+                        .put(COLUMN_INDEX, key.as_slice(), hash.as_slice()),
+                );
+            }
+        }
+        if self.store.get_block_number(hash) != Some(number) {
+            self.report(
+                Finding::new(
+                    FindingKind::Index,
+                    "the block number index is missing or inconsistent".to_string(),
+                )
+                .block(number, hash)
+                .put(COLUMN_INDEX, hash.as_slice(), key.as_slice()),
+            );
+        }
+    }
+
+    fn check_header(&mut self, number: BlockNumber, hash: &packed::Byte32, header: &HeaderView) {
+        if header.number() != number {
+            self.report(
+                Finding::new(
+                    FindingKind::Header,
+                    format!("the header has the number {}", header.number()),
+                )
+                .block(number, hash),
+            );
+        }
+        let computed = header.data().calc_header_hash();
+        if &computed != hash {
+            self.report(
+                Finding::new(
+                    FindingKind::Header,
+                    format!("the header hash is computed as {:#x}", computed),
+                )
+                .block(number, hash),
+            );
+        }
+        if self.store.get_block_ext(hash).is_none() {
+            self.report(
+                Finding::new(FindingKind::Header, "the block ext is missing".to_string())
+                    .block(number, hash),
+            );
+        }
+    }
+
+    /// Loads the block and recomputes the hashes from the data, returns `None` if the block is
+    /// not available or corrupted.
+    fn load_block(
+        &mut self,
+        number: BlockNumber,
+        hash: &packed::Byte32,
+        header: &HeaderView,
+    ) -> Option<BlockView> {
+        if number > 0 && number < self.frozen_number {
+            return self.load_frozen_block(number, hash);
+        }
+
+        let uncles = self.store.get_block_uncles(hash);
+        let proposals = self.store.get_block_proposal_txs_ids(hash);
+        let body = self.store.get_block_body(hash);
+        let (uncles, proposals) = match (uncles, proposals) {
+            (Some(uncles), Some(proposals)) if !body.is_empty() => (uncles, proposals),
+            _ => {
+                self.report(
+                    Finding::new(FindingKind::Body, "the block body is missing".to_string())
+                        .block(number, hash),
+                );
+                return None;
+            }
+        };
+        let block = match self.store.get_block_extension(hash) {
+            Some(extension) => BlockView::new_unchecked_with_extension(
+                header.clone(),
+                uncles,
+                body,
+                proposals,
+                extension,
+            ),
+            None => BlockView::new_unchecked(header.clone(), uncles, body, proposals),
+        }
+        .data()
+        .into_view_without_reset_header();
+        if !self.check_block_hashes(FindingKind::Body, number, hash, &block) {
+            return None;
+        }
+
+        let key = packed::NumberHash::new_builder()
+            .number(number.pack())
+            .block_hash(hash.clone())
+            .build();
+        let txs_len: packed::Uint32 = (block.transactions().len() as u32).pack();
+        match self.store.get(COLUMN_NUMBER_HASH, key.as_slice()) {
+            Some(ref value) if value.as_ref() == txs_len.as_slice() => {}
+            value => {
+                let message = match value.map(|value| packed::Uint32::from_slice(value.as_ref())) {
+                    Some(Ok(recorded)) => format!(
+                        "the number hash index records {} transactions but the body has {}",
+                        Unpack::<u32>::unpack(&recorded),
+                        block.transactions().len()
+                    ),
+                    Some(Err(_)) => "the number hash index is malformed".to_string(),
+                    None => "the number hash index is missing".to_string(),
+                };
+                self.report(
+                    Finding::new(FindingKind::Body, message)
+                        .block(number, hash)
+                        .put(COLUMN_NUMBER_HASH, key.as_slice(), txs_len.as_slice()),
+                );
+            }
+        }
+        Some(block)
+    }
+
+    fn load_frozen_block(
+        &mut self,
+        number: BlockNumber,
+        hash: &packed::Byte32,
+    ) -> Option<BlockView> {
+        let freezer = self.store.freezer().expect("frozen number is checked");
+        let message = match freezer.retrieve(number) {
+            Ok(Some(raw_block)) => match packed::BlockReader::from_compatible_slice(&raw_block) {
+                Ok(reader) => {
+                    let block = reader.to_entity().into_view_without_reset_header();
+                    if &block.hash() != hash {
+                        format!("the frozen block is {:#x}", block.hash())
+                    } else if self.check_block_hashes(FindingKind::Freezer, number, hash, &block) {
+                        return Some(block);
+                    } else {
+                        return None;
+                    }
+                }
+                Err(err) => format!("the frozen block is malformed: {}", err),
+            },
+            Ok(None) => "the frozen block is missing".to_string(),
+            Err(err) => format!("failed to retrieve the frozen block: {}", err),
+        };
+        self.report(Finding::new(FindingKind::Freezer, message).block(number, hash));
+        None
+    }
+
+    /// Checks the block data against the hashes in the header.
+    fn check_block_hashes(
+        &mut self,
+        kind: FindingKind,
+        number: BlockNumber,
+        hash: &packed::Byte32,
+        block: &BlockView,
+    ) -> bool {
+        let mismatches: Vec<_> = [
+            (
+                "transactions root",
+                block.transactions_root(),
+                block.calc_transactions_root(),
+            ),
+            (
+                "proposals hash",
+                block.proposals_hash(),
+                block.calc_proposals_hash(),
+            ),
+            (
+                "extra hash",
+                block.extra_hash(),
+                block.calc_extra_hash().extra_hash(),
+            ),
+        ]
+        .iter()
+        .filter(|(_, expected, computed)| expected != computed)
+        .map(|(name, _, _)| *name)
+        .collect();
+        if mismatches.is_empty() {
+            return true;
+        }
+        self.report(
+            Finding::new(
+                kind,
+                format!(
+                    "the {} of the block data mismatch the header",
+                    mismatches.join(", ")
+                ),
+            )
+            .block(number, hash),
+        );
+        false
+    }
+
+    fn check_transaction_infos(&mut self, block: &BlockView) {
+        let header = block.data().header();
+        for (index, tx_hash) in block.tx_hashes().iter().enumerate() {
+            let key = packed::TransactionKey::new_builder()
+                .block_hash(block.hash())
+                .index(index.pack())
+                .build();
+            let info = packed::TransactionInfo::new_builder()
+                .key(key)
+                .block_number(header.raw().number())
+                .block_epoch(header.raw().epoch())
+                .build();
+            match self.store.get(COLUMN_TRANSACTION_INFO, tx_hash.as_slice()) {
+                Some(ref value) if value.as_ref() == info.as_slice() => {}
+                value => {
+                    let state = if value.is_some() {
+                        "inconsistent"
+                    } else {
+                        "missing"
+                    };
+                    self.report(
+                        Finding::new(
+                            FindingKind::TransactionInfo,
+                            format!("the transaction info of {:#x} is {}", tx_hash, state),
+                        )
+                        .block(block.number(), &block.hash())
+                        .put(
+                            COLUMN_TRANSACTION_INFO,
+                            tx_hash.as_slice(),
+                            info.as_slice(),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Recomputes the live cells, the blocks must be checked backward from the tip.
+    ///
+    /// Only the spent cells created in the range are remembered, so the memory is bounded by the
+    /// checked blocks instead of the blocks walked from the tip.
+    fn check_cells_of(&mut self, block: &BlockView, in_range: bool) {
+        // the inputs of the cellbase are not cells
+        for tx in block.transactions().iter().skip(1) {
+            for out_point in tx.input_pts_iter() {
+                let created_in_range = self
+                    .store
+                    .get_transaction_info(&out_point.tx_hash())
+                    .map_or(false, |info| self.in_range(info.block_number));
+                if created_in_range {
+                    self.spent.insert(out_point.to_cell_key());
+                }
+            }
+        }
+        if !in_range {
+            return;
+        }
+
+        for (tx_index, tx) in block.transactions().iter().enumerate() {
+            for (index, (output, data)) in tx.outputs_with_data_iter().enumerate() {
+                let out_point = packed::OutPoint::new_builder()
+                    .tx_hash(tx.hash())
+                    .index(index.pack())
+                    .build();
+                let key = out_point.to_cell_key();
+                // each cell is spent only once in the main chain
+                let spent = self.spent.remove(&key);
+                let message = match self.store.get(COLUMN_CELL, &key) {
+                    Some(_) if spent => "the spent cell is still live",
+                    None if spent => continue,
+                    None => "the live cell is missing",
+                    Some(value) => match packed::CellEntryReader::from_slice(value.as_ref()) {
+                        Ok(entry) => {
+                            let data_size: u64 = entry.data_size().unpack();
+                            let data_hash = packed::CellOutput::calc_data_hash(&data);
+                            if entry.output().as_slice() != output.as_slice()
+                                || entry.block_hash().as_slice() != block.hash().as_slice()
+                                || Unpack::<BlockNumber>::unpack(&entry.block_number())
+                                    != block.number()
+                                || Unpack::<u32>::unpack(&entry.index()) as usize != tx_index
+                                || data_size != data.len() as u64
+                                || self.store.get_cell_data_hash(&out_point) != Some(data_hash)
+                            {
+                                "the live cell is inconsistent with the block"
+                            } else {
+                                continue;
+                            }
+                        }
+                        Err(_) => "the live cell is malformed",
+                    },
+                };
+                self.report(
+                    Finding::new(
+                        FindingKind::Cell,
+                        format!("{}: {:#x}:{}", message, tx.hash(), index),
+                    )
+                    .block(block.number(), &block.hash()),
+                );
+            }
+        }
+    }
+
+    /// Checks the block number index of the blocks out of the main chain.
+    fn check_reverse_index(&mut self) {
+        let mut stale = Vec::new();
+        let mut malformed = Vec::new();
+        for (key, value) in self.store.get_iter(COLUMN_INDEX, IteratorMode::Start) {
+            // the keys of the main chain index are the block numbers
+            if key.len() != 32 {
+                continue;
+            }
+            let hash = packed::Byte32::from_slice(&key).expect("checked length");
+            let number: BlockNumber = match packed::Uint64::from_slice(value.as_ref()) {
+                Ok(number) => number.unpack(),
+                Err(_) => {
+                    malformed.push(Finding::new(
+                        FindingKind::Index,
+                        format!("the block number index of {:#x} is malformed", hash),
+                    ));
+                    continue;
+                }
+            };
+            if !self.in_range(number) {
+                continue;
+            }
+            if self.main_chain_hash(number).as_ref() != Some(&hash) {
+                stale.push((number, hash));
+            }
+        }
+        self.findings.extend(malformed);
+        for (number, hash) in stale {
+            self.report(
+                Finding::new(
+                    FindingKind::Index,
+                    "the block out of the main chain is indexed".to_string(),
+                )
+                .block(number, &hash)
+                .delete(COLUMN_INDEX, hash.as_slice()),
+            );
+        }
+    }
+
+    /// Checks the transaction index of the transactions out of the main chain.
+    fn check_transaction_info_column(&mut self) {
+        let mut stale = Vec::new();
+        let mut malformed = Vec::new();
+        for (key, value) in self
+            .store
+            .get_iter(COLUMN_TRANSACTION_INFO, IteratorMode::Start)
+        {
+            let tx_hash = match packed::Byte32::from_slice(&key) {
+                Ok(tx_hash) => tx_hash,
+                Err(_) => {
+                    malformed.push(Finding::new(
+                        FindingKind::TransactionInfo,
+                        "the transaction index has a malformed key".to_string(),
+                    ));
+                    continue;
+                }
+            };
+            let info = match packed::TransactionInfo::from_slice(value.as_ref()) {
+                Ok(info) => info,
+                Err(_) => {
+                    malformed.push(Finding::new(
+                        FindingKind::TransactionInfo,
+                        format!("the transaction index of {:#x} is malformed", tx_hash),
+                    ));
+                    continue;
+                }
+            };
+            let number: BlockNumber = info.block_number().unpack();
+            if !self.in_range(number) {
+                continue;
+            }
+            let block_hash = info.key().block_hash();
+            if self.main_chain_hash(number).as_ref() != Some(&block_hash) {
+                stale.push((number, block_hash, tx_hash));
+            }
+        }
+        self.findings.extend(malformed);
+        for (number, block_hash, tx_hash) in stale {
+            self.report(
+                Finding::new(
+                    FindingKind::TransactionInfo,
+                    format!(
+                        "the transaction {:#x} is indexed in the block out of the main chain",
+                        tx_hash
+                    ),
+                )
+                .block(number, &block_hash)
+                .delete(COLUMN_TRANSACTION_INFO, tx_hash.as_slice()),
+            );
+        }
+    }
+
+    /// Checks the live cells which are not created in the main chain.
+    fn check_cell_column(&mut self) {
+        let mut stale = Vec::new();
+        let mut malformed = Vec::new();
+        for (key, value) in self.store.get_iter(COLUMN_CELL, IteratorMode::Start) {
+            if key.len() != 36 {
+                malformed.push(Finding::new(
+                    FindingKind::Cell,
+                    "the live cell has a malformed key".to_string(),
+                ));
+                continue;
+            }
+            let entry = match packed::CellEntry::from_slice(value.as_ref()) {
+                Ok(entry) => entry,
+                Err(_) => {
+                    let (tx_hash, index) = key.split_at(32);
+                    let tx_hash = packed::Byte32::from_slice(tx_hash).expect("checked length");
+                    let index = u32::from_be_bytes(index.try_into().expect("checked length"));
+                    malformed.push(Finding::new(
+                        FindingKind::Cell,
+                        format!("the live cell is malformed: {:#x}:{}", tx_hash, index),
+                    ));
+                    continue;
+                }
+            };
+            let number: BlockNumber = entry.block_number().unpack();
+            if !self.in_range(number) {
+                continue;
+            }
+            let block_hash = entry.block_hash();
+            if self.main_chain_hash(number).as_ref() != Some(&block_hash) {
+                stale.push((number, block_hash, key));
+            }
+        }
+        self.findings.extend(malformed);
+        for (number, block_hash, key) in stale {
+            let (tx_hash, index) = key.split_at(32);
+            let tx_hash = packed::Byte32::from_slice(tx_hash).expect("checked length");
+            let index = u32::from_be_bytes(index.try_into().expect("checked length"));
+            self.report(
+                Finding::new(
+                    FindingKind::Cell,
+                    format!(
+                        "the live cell is created out of the main chain: {:#x}:{}",
+                        tx_hash, index
+                    ),
+                )
+                .block(number, &block_hash),
+            );
+        }
+    }
+}
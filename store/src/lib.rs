@@ -3,6 +3,7 @@ mod cache;
 mod cell;
 pub mod data_loader_wrapper;
 mod db;
+pub mod integrity;
 mod snapshot;
 mod store;
 mod transaction;
@@ -3,11 +3,15 @@ use crate::data_loader_wrapper::DataLoaderWrapper;
 use ckb_db::iter::{DBIter, Direction, IteratorMode};
 use ckb_db_schema::{
     Col, COLUMN_BLOCK_BODY, COLUMN_BLOCK_EPOCH, COLUMN_BLOCK_EXT, COLUMN_BLOCK_EXTENSION,
-    COLUMN_BLOCK_HEADER, COLUMN_BLOCK_PROPOSAL_IDS, COLUMN_BLOCK_UNCLE, COLUMN_CELL,
-    COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH, COLUMN_EPOCH, COLUMN_INDEX, COLUMN_META,
-    COLUMN_TRANSACTION_INFO, COLUMN_UNCLES, META_CURRENT_EPOCH_KEY, META_TIP_HEADER_KEY,
+    COLUMN_BLOCK_FILTER, COLUMN_BLOCK_FILTER_HASH, COLUMN_BLOCK_HEADER, COLUMN_BLOCK_PROPOSAL_IDS,
+    COLUMN_BLOCK_UNCLE, COLUMN_CELL, COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH,
+    COLUMN_CHAIN_ROOT_MMR, COLUMN_EPOCH, COLUMN_INDEX, COLUMN_META, COLUMN_TRANSACTION_INFO,
+    COLUMN_UNCLES, META_CURRENT_EPOCH_KEY, META_INVALID_SNAPSHOT_KEY,
+    META_LATEST_BUILT_FILTER_DATA_KEY, META_PRUNED_NUMBER_KEY, META_SNAPSHOT_KEY,
+    META_TIP_HEADER_KEY,
 };
 use ckb_freezer::Freezer;
+use ckb_hash::new_blake2b;
 use ckb_types::{
     bytes::Bytes,
     core::{
@@ -47,13 +51,10 @@ pub trait ChainStore<'a>: Send + Sync + Sized {
                 return Some(raw_block.into_view());
             }
         }
+        // Only the headers are stored for the blocks below the imported snapshot.
         let body = self.get_block_body(h);
-        let uncles = self
-            .get_block_uncles(h)
-            .expect("block uncles must be stored");
-        let proposals = self
-            .get_block_proposal_txs_ids(h)
-            .expect("block proposal_ids must be stored");
+        let uncles = self.get_block_uncles(h)?;
+        let proposals = self.get_block_proposal_txs_ids(h)?;
         let extension_opt = self.get_block_extension(h);
 
         let block = if let Some(extension) = extension_opt {
@@ -239,6 +240,33 @@ pub trait ChainStore<'a>: Send + Sync + Sized {
         ret
     }
 
+    /// Get block filter data by block header hash
+    fn get_block_filter(&'a self, hash: &packed::Byte32) -> Option<packed::Bytes> {
+        self.get(COLUMN_BLOCK_FILTER, hash.as_slice())
+            .map(|slice| packed::BytesReader::from_slice_should_be_ok(slice.as_ref()).to_entity())
+    }
+
+    /// Get block filter hash by block header hash
+    fn get_block_filter_hash(&'a self, hash: &packed::Byte32) -> Option<packed::Byte32> {
+        self.get(COLUMN_BLOCK_FILTER_HASH, hash.as_slice())
+            .map(|slice| packed::Byte32Reader::from_slice_should_be_ok(slice.as_ref()).to_entity())
+    }
+
+    /// Get the hash of the latest block which the filter data is built for
+    fn get_latest_built_filter_data_block_hash(&'a self) -> Option<packed::Byte32> {
+        self.get(COLUMN_META, META_LATEST_BUILT_FILTER_DATA_KEY)
+            .map(|raw| packed::Byte32Reader::from_slice_should_be_ok(raw.as_ref()).to_entity())
+    }
+
+    /// Get the node of the chain root MMR by its position
+    fn get_header_digest(&'a self, position: u64) -> Option<packed::HeaderDigest> {
+        let position: packed::BeUint64 = position.pack();
+        self.get(COLUMN_CHAIN_ROOT_MMR, position.as_slice())
+            .map(|slice| {
+                packed::HeaderDigestReader::from_slice_should_be_ok(slice.as_ref()).to_entity()
+            })
+    }
+
     /// Get block ext by block header hash
     fn get_block_ext(&'a self, block_hash: &packed::Byte32) -> Option<BlockExt> {
         self.get(COLUMN_BLOCK_EXT, block_hash.as_slice())
@@ -263,6 +291,61 @@ pub trait ChainStore<'a>: Send + Sync + Sized {
         self.get(COLUMN_INDEX, hash.as_slice()).is_some()
     }
 
+    /// Returns the number of the first main chain block whose body is kept.
+    ///
+    /// The bodies of the main chain blocks below the number are pruned, except the genesis block.
+    fn get_pruned_number(&'a self) -> BlockNumber {
+        self.get(COLUMN_META, META_PRUNED_NUMBER_KEY)
+            .map(|raw| packed::Uint64Reader::from_slice_should_be_ok(raw.as_ref()).unpack())
+            .unwrap_or(0)
+    }
+
+    /// Returns true if the body of the main chain block with the number is pruned.
+    fn is_pruned(&'a self, number: BlockNumber) -> bool {
+        number > 0 && number < self.get_pruned_number()
+    }
+
+    /// Returns the hash of the imported snapshot block and the hash of its live cell set, if the
+    /// history below the snapshot block has not been validated yet.
+    fn get_unvalidated_snapshot(&'a self) -> Option<(packed::Byte32, packed::Byte32)> {
+        self.get(COLUMN_META, META_SNAPSHOT_KEY).map(|raw| {
+            let (block_hash, cells_hash) = raw.as_ref().split_at(32);
+            (
+                packed::Byte32Reader::from_slice_should_be_ok(block_hash).to_entity(),
+                packed::Byte32Reader::from_slice_should_be_ok(cells_hash).to_entity(),
+            )
+        })
+    }
+
+    /// Returns the hash of the imported snapshot block, if the history below it is invalid.
+    fn get_invalid_snapshot(&'a self) -> Option<packed::Byte32> {
+        self.get(COLUMN_META, META_INVALID_SNAPSHOT_KEY)
+            .map(|raw| packed::Byte32Reader::from_slice_should_be_ok(raw.as_ref()).to_entity())
+    }
+
+    /// Calculates the hash of the live cell set.
+    ///
+    /// It scans all the entries of `COLUMN_CELL`, `COLUMN_CELL_DATA` and `COLUMN_CELL_DATA_HASH`,
+    /// so two stores have the same hash if and only if they have the same live cell set.
+    fn calc_live_cells_hash(&'a self) -> packed::Byte32 {
+        let mut hasher = new_blake2b();
+        for (index, col) in [COLUMN_CELL, COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH]
+            .iter()
+            .enumerate()
+        {
+            for (key, value) in self.get_iter(col, IteratorMode::Start) {
+                hasher.update(&[index as u8]);
+                hasher.update(&(key.len() as u32).to_le_bytes());
+                hasher.update(&key);
+                hasher.update(&(value.len() as u32).to_le_bytes());
+                hasher.update(&value);
+            }
+        }
+        let mut hash = [0u8; 32];
+        hasher.finalize(&mut hash);
+        hash.pack()
+    }
+
     /// TODO(doc): @quake
     fn get_tip_header(&'a self) -> Option<HeaderView> {
         self.get(COLUMN_META, META_TIP_HEADER_KEY)
@@ -0,0 +1,71 @@
+use ckb_chain_spec::consensus::ConsensusBuilder;
+use ckb_db::RocksDB;
+use ckb_db_schema::{COLUMNS, COLUMN_CELL, COLUMN_INDEX, COLUMN_TRANSACTION_INFO};
+use ckb_types::{packed, prelude::*};
+use tempfile::TempDir;
+
+use crate::db::ChainDB;
+use crate::integrity::{check, CheckOptions, FindingKind};
+
+#[test]
+fn check_and_fix_index() {
+    let tmp_dir = TempDir::new().unwrap();
+    let db = RocksDB::open_in(&tmp_dir, COLUMNS);
+    let store = ChainDB::new(db, Default::default());
+    let consensus = ConsensusBuilder::default().build();
+    store.init(&consensus).unwrap();
+
+    let options = CheckOptions {
+        check_cells: true,
+        ..Default::default()
+    };
+    let report = check(&store, &options).unwrap();
+    assert_eq!((report.from, report.to), (0, 0));
+    assert!(report.findings.is_empty());
+
+    let genesis = consensus.genesis_block();
+    let cellbase_hash = genesis.transactions()[0].hash();
+    let mut batch = store.new_write_batch();
+    batch
+        .delete(COLUMN_TRANSACTION_INFO, cellbase_hash.as_slice())
+        .unwrap();
+    batch
+        .delete(COLUMN_INDEX, genesis.hash().as_slice())
+        .unwrap();
+    store.write(&batch).unwrap();
+
+    let report = check(&store, &options).unwrap();
+    assert_eq!(report.findings.len(), 2);
+    assert_eq!(report.fixable(), 2);
+    assert_eq!(report.fix(&store).unwrap(), 2);
+    assert!(check(&store, &options).unwrap().findings.is_empty());
+}
+
+#[test]
+fn check_live_cells() {
+    let tmp_dir = TempDir::new().unwrap();
+    let db = RocksDB::open_in(&tmp_dir, COLUMNS);
+    let store = ChainDB::new(db, Default::default());
+    let consensus = ConsensusBuilder::default().build();
+    store.init(&consensus).unwrap();
+
+    let out_point = packed::OutPoint::new(consensus.genesis_block().transactions()[0].hash(), 0);
+    let mut batch = store.new_write_batch();
+    batch.delete(COLUMN_CELL, &out_point.to_cell_key()).unwrap();
+    store.write(&batch).unwrap();
+
+    // the live cells are only checked on demand
+    assert!(check(&store, &Default::default())
+        .unwrap()
+        .findings
+        .is_empty());
+
+    let options = CheckOptions {
+        check_cells: true,
+        ..Default::default()
+    };
+    let report = check(&store, &options).unwrap();
+    assert_eq!(report.findings.len(), 1);
+    assert_eq!(report.findings[0].kind, FindingKind::Cell);
+    assert!(!report.findings[0].is_fixable());
+}
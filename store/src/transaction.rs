@@ -6,12 +6,13 @@ use ckb_db::{
 };
 use ckb_db_schema::{
     Col, COLUMN_BLOCK_BODY, COLUMN_BLOCK_EPOCH, COLUMN_BLOCK_EXT, COLUMN_BLOCK_EXTENSION,
-    COLUMN_BLOCK_HEADER, COLUMN_BLOCK_PROPOSAL_IDS, COLUMN_BLOCK_UNCLE, COLUMN_CELL,
-    COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH, COLUMN_EPOCH, COLUMN_INDEX, COLUMN_META,
-    COLUMN_NUMBER_HASH, COLUMN_TRANSACTION_INFO, COLUMN_UNCLES, META_CURRENT_EPOCH_KEY,
-    META_TIP_HEADER_KEY,
+    COLUMN_BLOCK_FILTER, COLUMN_BLOCK_FILTER_HASH, COLUMN_BLOCK_HEADER, COLUMN_BLOCK_PROPOSAL_IDS,
+    COLUMN_BLOCK_UNCLE, COLUMN_CELL, COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH,
+    COLUMN_CHAIN_ROOT_MMR, COLUMN_EPOCH, COLUMN_INDEX, COLUMN_META, COLUMN_NUMBER_HASH,
+    COLUMN_TRANSACTION_INFO, COLUMN_UNCLES, META_CURRENT_EPOCH_KEY, META_INVALID_SNAPSHOT_KEY,
+    META_LATEST_BUILT_FILTER_DATA_KEY, META_SNAPSHOT_KEY, META_TIP_HEADER_KEY,
 };
-use ckb_error::Error;
+use ckb_error::{Error, InternalErrorKind};
 use ckb_freezer::Freezer;
 use ckb_types::{
     core::{
@@ -20,6 +21,7 @@ use ckb_types::{
     },
     packed::{self, OutPoint},
     prelude::*,
+    utilities::{leaf_index_to_mmr_size, ChainRootMMR, MMRError, MMRResult, MMRStore},
 };
 use std::sync::Arc;
 
@@ -148,6 +150,31 @@ impl StoreTransaction {
         self.insert_raw(COLUMN_META, META_TIP_HEADER_KEY, h.hash().as_slice())
     }
 
+    /// Marks the imported snapshot as not validated, see `ChainStore::get_unvalidated_snapshot`.
+    pub fn insert_unvalidated_snapshot(
+        &self,
+        block_hash: &packed::Byte32,
+        cells_hash: &packed::Byte32,
+    ) -> Result<(), Error> {
+        let mut value = block_hash.as_slice().to_vec();
+        value.extend_from_slice(cells_hash.as_slice());
+        self.insert_raw(COLUMN_META, META_SNAPSHOT_KEY, &value)
+    }
+
+    /// Clears the mark once the history below the imported snapshot is validated.
+    pub fn delete_unvalidated_snapshot(&self) -> Result<(), Error> {
+        self.delete(COLUMN_META, META_SNAPSHOT_KEY)
+    }
+
+    /// Marks the imported snapshot as invalid.
+    pub fn insert_invalid_snapshot(&self, block_hash: &packed::Byte32) -> Result<(), Error> {
+        self.insert_raw(
+            COLUMN_META,
+            META_INVALID_SNAPSHOT_KEY,
+            block_hash.as_slice(),
+        )
+    }
+
     /// TODO(doc): @quake
     pub fn insert_block(&self, block: &BlockView) -> Result<(), Error> {
         let hash = block.hash();
@@ -196,6 +223,8 @@ impl StoreTransaction {
         self.delete(COLUMN_BLOCK_HEADER, hash.as_slice())?;
         self.delete(COLUMN_BLOCK_UNCLE, hash.as_slice())?;
         self.delete(COLUMN_BLOCK_EXTENSION, hash.as_slice())?;
+        self.delete(COLUMN_BLOCK_FILTER, hash.as_slice())?;
+        self.delete(COLUMN_BLOCK_FILTER_HASH, hash.as_slice())?;
         self.delete(COLUMN_BLOCK_PROPOSAL_IDS, hash.as_slice())?;
         self.delete(
             COLUMN_NUMBER_HASH,
@@ -230,6 +259,70 @@ impl StoreTransaction {
         )
     }
 
+    /// Insert the filter data and the filter hash of the block
+    pub fn insert_block_filter(
+        &self,
+        block_hash: &packed::Byte32,
+        filter_data: &packed::Bytes,
+        filter_hash: &packed::Byte32,
+    ) -> Result<(), Error> {
+        self.insert_raw(
+            COLUMN_BLOCK_FILTER,
+            block_hash.as_slice(),
+            filter_data.as_slice(),
+        )?;
+        self.insert_raw(
+            COLUMN_BLOCK_FILTER_HASH,
+            block_hash.as_slice(),
+            filter_hash.as_slice(),
+        )
+    }
+
+    /// Update the latest block which the filter data is built for
+    pub fn update_latest_built_filter_data_block_hash(
+        &self,
+        block_hash: &packed::Byte32,
+    ) -> Result<(), Error> {
+        self.insert_raw(
+            COLUMN_META,
+            META_LATEST_BUILT_FILTER_DATA_KEY,
+            block_hash.as_slice(),
+        )
+    }
+
+    /// Append the digest of a main chain header to the chain root MMR
+    ///
+    /// The leaves of the MMR must be the headers of all the blocks before it in the main chain.
+    pub fn attach_header_digest(&self, header: &HeaderView) -> Result<(), Error> {
+        let mmr_size = if header.is_genesis() {
+            0
+        } else {
+            leaf_index_to_mmr_size(header.number() - 1)
+        };
+        let mut mmr = ChainRootMMR::new(mmr_size, self);
+        mmr.push(header.digest())
+            .map_err(|err| InternalErrorKind::MMR.other(err))?;
+        mmr.commit()
+            .map_err(|err| InternalErrorKind::MMR.other(err).into())
+    }
+
+    /// Insert the nodes of the chain root MMR from the position
+    pub fn insert_header_digests(
+        &self,
+        position: u64,
+        digests: &[packed::HeaderDigest],
+    ) -> Result<(), Error> {
+        for (offset, digest) in digests.iter().enumerate() {
+            let position: packed::BeUint64 = (position + offset as u64).pack();
+            self.insert_raw(
+                COLUMN_CHAIN_ROOT_MMR,
+                position.as_slice(),
+                digest.as_slice(),
+            )?;
+        }
+        Ok(())
+    }
+
     /// TODO(doc): @quake
     pub fn attach_block(&self, block: &BlockView) -> Result<(), Error> {
         let header = block.data().header();
@@ -339,3 +432,14 @@ impl StoreTransaction {
         Ok(())
     }
 }
+
+impl MMRStore<packed::HeaderDigest> for &StoreTransaction {
+    fn get_elem(&self, position: u64) -> MMRResult<Option<packed::HeaderDigest>> {
+        Ok(self.get_header_digest(position))
+    }
+
+    fn append(&mut self, position: u64, digests: Vec<packed::HeaderDigest>) -> MMRResult<()> {
+        self.insert_header_digests(position, &digests)
+            .map_err(|err| MMRError::StoreError(err.to_string()))
+    }
+}
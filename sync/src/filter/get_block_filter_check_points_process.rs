@@ -0,0 +1,62 @@
+use crate::filter::{BlockFilter, BATCH_SIZE, CHECK_POINT_INTERVAL};
+use crate::utils::send_message_to;
+use crate::Status;
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_store::ChainStore;
+use ckb_types::{core::BlockNumber, packed, prelude::*};
+
+pub struct GetBlockFilterCheckPointsProcess<'a> {
+    message: packed::GetBlockFilterCheckPointsReader<'a>,
+    filter: &'a BlockFilter,
+    nc: &'a dyn CKBProtocolContext,
+    peer: PeerIndex,
+}
+
+impl<'a> GetBlockFilterCheckPointsProcess<'a> {
+    pub fn new(
+        message: packed::GetBlockFilterCheckPointsReader<'a>,
+        filter: &'a BlockFilter,
+        nc: &'a dyn CKBProtocolContext,
+        peer: PeerIndex,
+    ) -> Self {
+        Self {
+            message,
+            filter,
+            nc,
+            peer,
+        }
+    }
+
+    /// Responds the filter hashes of the blocks `start_number + CHECK_POINT_INTERVAL * i`.
+    pub fn execute(self) -> Status {
+        let snapshot = self.filter.snapshot();
+        let start_number: BlockNumber = self.message.start_number().unpack();
+        let latest = match BlockFilter::latest_built_filter_number(&snapshot) {
+            Some(latest) if latest >= start_number => latest,
+            _ => return Status::ignored(),
+        };
+
+        let mut block_filter_hashes = Vec::new();
+        for number in (start_number..=latest)
+            .step_by(CHECK_POINT_INTERVAL as usize)
+            .take(BATCH_SIZE as usize)
+        {
+            match snapshot
+                .get_block_hash(number)
+                .and_then(|block_hash| snapshot.get_block_filter_hash(&block_hash))
+            {
+                Some(hash) => block_filter_hashes.push(hash),
+                None => break,
+            }
+        }
+
+        let content = packed::BlockFilterCheckPoints::new_builder()
+            .start_number(start_number.pack())
+            .block_filter_hashes(block_filter_hashes.pack())
+            .build();
+        let message = packed::BlockFilterMessage::new_builder()
+            .set(content)
+            .build();
+        send_message_to(self.nc, self.peer, &message)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::filter::{BlockFilter, BATCH_SIZE};
+use crate::utils::send_message_to;
+use crate::Status;
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_store::ChainStore;
+use ckb_types::{core::BlockNumber, packed, prelude::*};
+
+pub struct GetBlockFilterHashesProcess<'a> {
+    message: packed::GetBlockFilterHashesReader<'a>,
+    filter: &'a BlockFilter,
+    nc: &'a dyn CKBProtocolContext,
+    peer: PeerIndex,
+}
+
+impl<'a> GetBlockFilterHashesProcess<'a> {
+    pub fn new(
+        message: packed::GetBlockFilterHashesReader<'a>,
+        filter: &'a BlockFilter,
+        nc: &'a dyn CKBProtocolContext,
+        peer: PeerIndex,
+    ) -> Self {
+        Self {
+            message,
+            filter,
+            nc,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        let snapshot = self.filter.snapshot();
+        let start_number: BlockNumber = self.message.start_number().unpack();
+        let latest = match BlockFilter::latest_built_filter_number(&snapshot) {
+            Some(latest) if latest >= start_number => latest,
+            _ => return Status::ignored(),
+        };
+
+        let filter_hash = |number| {
+            snapshot
+                .get_block_hash(number)
+                .and_then(|block_hash| snapshot.get_block_filter_hash(&block_hash))
+        };
+        let parent_block_filter_hash = if start_number == 0 {
+            packed::Byte32::zero()
+        } else {
+            match filter_hash(start_number - 1) {
+                Some(hash) => hash,
+                None => return Status::ignored(),
+            }
+        };
+        let mut block_filter_hashes = Vec::new();
+        for number in start_number..=latest.min(start_number + BATCH_SIZE * 2 - 1) {
+            match filter_hash(number) {
+                Some(hash) => block_filter_hashes.push(hash),
+                None => break,
+            }
+        }
+
+        let content = packed::BlockFilterHashes::new_builder()
+            .start_number(start_number.pack())
+            .parent_block_filter_hash(parent_block_filter_hash)
+            .block_filter_hashes(block_filter_hashes.pack())
+            .build();
+        let message = packed::BlockFilterMessage::new_builder()
+            .set(content)
+            .build();
+        send_message_to(self.nc, self.peer, &message)
+    }
+}
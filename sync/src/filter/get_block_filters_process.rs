@@ -0,0 +1,71 @@
+use crate::filter::{BlockFilter, BATCH_SIZE};
+use crate::utils::send_message_to;
+use crate::Status;
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_store::ChainStore;
+use ckb_types::{core::BlockNumber, packed, prelude::*};
+
+// Keep the response well below the max frame length of the filter protocol.
+const MAX_FILTERS_BYTES: usize = 1024 * 1024;
+
+pub struct GetBlockFiltersProcess<'a> {
+    message: packed::GetBlockFiltersReader<'a>,
+    filter: &'a BlockFilter,
+    nc: &'a dyn CKBProtocolContext,
+    peer: PeerIndex,
+}
+
+impl<'a> GetBlockFiltersProcess<'a> {
+    pub fn new(
+        message: packed::GetBlockFiltersReader<'a>,
+        filter: &'a BlockFilter,
+        nc: &'a dyn CKBProtocolContext,
+        peer: PeerIndex,
+    ) -> Self {
+        Self {
+            message,
+            filter,
+            nc,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        let snapshot = self.filter.snapshot();
+        let start_number: BlockNumber = self.message.start_number().unpack();
+        let latest = match BlockFilter::latest_built_filter_number(&snapshot) {
+            Some(latest) if latest >= start_number => latest,
+            _ => return Status::ignored(),
+        };
+
+        let mut block_hashes = Vec::new();
+        let mut filters = Vec::new();
+        let mut filters_bytes = 0;
+        for number in start_number..=latest.min(start_number + BATCH_SIZE - 1) {
+            let block_hash = match snapshot.get_block_hash(number) {
+                Some(block_hash) => block_hash,
+                None => break,
+            };
+            let filter = match snapshot.get_block_filter(&block_hash) {
+                Some(filter) => filter,
+                None => break,
+            };
+            filters_bytes += filter.as_slice().len();
+            if filters_bytes > MAX_FILTERS_BYTES && !filters.is_empty() {
+                break;
+            }
+            block_hashes.push(block_hash);
+            filters.push(filter);
+        }
+
+        let content = packed::BlockFilters::new_builder()
+            .start_number(start_number.pack())
+            .block_hashes(block_hashes.pack())
+            .filters(filters.pack())
+            .build();
+        let message = packed::BlockFilterMessage::new_builder()
+            .set(content)
+            .build();
+        send_message_to(self.nc, self.peer, &message)
+    }
+}
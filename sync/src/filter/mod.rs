@@ -0,0 +1,167 @@
+mod get_block_filter_check_points_process;
+mod get_block_filter_hashes_process;
+mod get_block_filters_process;
+
+use crate::filter::get_block_filter_check_points_process::GetBlockFilterCheckPointsProcess;
+use crate::filter::get_block_filter_hashes_process::GetBlockFilterHashesProcess;
+use crate::filter::get_block_filters_process::GetBlockFiltersProcess;
+use crate::types::SyncShared;
+use crate::{Status, StatusCode};
+use ckb_constant::sync::BAD_MESSAGE_BAN_TIME;
+use ckb_logger::{debug_target, error_target, info_target, warn_target};
+use ckb_metrics::metrics;
+use ckb_network::{
+    bytes::Bytes, CKBProtocolContext, CKBProtocolHandler, PeerIndex, SupportProtocols,
+};
+use ckb_shared::Snapshot;
+use ckb_store::ChainStore;
+use ckb_types::{core::BlockNumber, packed, prelude::*};
+use std::sync::Arc;
+use std::time::Instant;
+
+// The max number of the filters or the filter hashes in one response.
+const BATCH_SIZE: BlockNumber = 1000;
+// The interval of the block numbers between two check points.
+const CHECK_POINT_INTERVAL: BlockNumber = 2000;
+
+/// Block filter protocol handler, which serves the compact block filters to the light clients.
+///
+/// The filters are built by the `ckb-block-filter` service, the requests for the blocks whose
+/// filters are not built yet are ignored.
+pub struct BlockFilter {
+    shared: Arc<SyncShared>,
+}
+
+impl BlockFilter {
+    /// Create a new block filter protocol handler
+    pub fn new(shared: Arc<SyncShared>) -> Self {
+        BlockFilter { shared }
+    }
+
+    /// Returns the number of the latest block in the main chain whose filter has been built.
+    pub(crate) fn latest_built_filter_number(snapshot: &Snapshot) -> Option<BlockNumber> {
+        snapshot
+            .get_latest_built_filter_data_block_hash()
+            .and_then(|hash| snapshot.get_block_number(&hash))
+    }
+
+    pub(crate) fn snapshot(&self) -> Arc<Snapshot> {
+        Arc::clone(&self.shared.shared().snapshot())
+    }
+
+    fn try_process(
+        &self,
+        nc: &dyn CKBProtocolContext,
+        peer: PeerIndex,
+        message: packed::BlockFilterMessageUnionReader<'_>,
+    ) -> Status {
+        match message {
+            packed::BlockFilterMessageUnionReader::GetBlockFilters(reader) => {
+                GetBlockFiltersProcess::new(reader, self, nc, peer).execute()
+            }
+            packed::BlockFilterMessageUnionReader::GetBlockFilterHashes(reader) => {
+                GetBlockFilterHashesProcess::new(reader, self, nc, peer).execute()
+            }
+            packed::BlockFilterMessageUnionReader::GetBlockFilterCheckPoints(reader) => {
+                GetBlockFilterCheckPointsProcess::new(reader, self, nc, peer).execute()
+            }
+            _ => StatusCode::ProtocolMessageIsMalformed
+                .with_context("unexpected block filter message"),
+        }
+    }
+
+    fn process(
+        &self,
+        nc: &dyn CKBProtocolContext,
+        peer: PeerIndex,
+        message: packed::BlockFilterMessageUnionReader<'_>,
+    ) {
+        let item_name = message.item_name();
+        let item_bytes = message.as_slice().len() as u64;
+        let status = self.try_process(nc, peer, message);
+
+        metrics!(
+            counter,
+            "ckb.messages_bytes",
+            item_bytes,
+            "direction" => "in",
+            "protocol_id" => SupportProtocols::Filter.protocol_id().value().to_string(),
+            "item_id" => message.item_id().to_string(),
+            "status" => (status.code() as u16).to_string(),
+        );
+
+        if let Some(ban_time) = status.should_ban() {
+            error_target!(
+                crate::LOG_TARGET_FILTER,
+                "receive {} from {}, ban {:?} for {}",
+                item_name,
+                peer,
+                ban_time,
+                status
+            );
+            nc.ban_peer(peer, ban_time, status.to_string());
+        } else if let Some(behaviour) = status.behaviour() {
+            nc.report_peer(peer, behaviour);
+        } else if status.should_warn() {
+            warn_target!(
+                crate::LOG_TARGET_FILTER,
+                "receive {} from {}, {}",
+                item_name,
+                peer,
+                status
+            );
+        } else if !status.is_ok() {
+            debug_target!(
+                crate::LOG_TARGET_FILTER,
+                "receive {} from {}, {}",
+                item_name,
+                peer,
+                status
+            );
+        }
+    }
+}
+
+impl CKBProtocolHandler for BlockFilter {
+    fn init(&mut self, _nc: Arc<dyn CKBProtocolContext + Sync>) {}
+
+    fn received(
+        &mut self,
+        nc: Arc<dyn CKBProtocolContext + Sync>,
+        peer_index: PeerIndex,
+        data: Bytes,
+    ) {
+        let msg = match packed::BlockFilterMessageReader::from_slice(&data) {
+            Ok(msg) => msg.to_enum(),
+            _ => {
+                info_target!(
+                    crate::LOG_TARGET_FILTER,
+                    "Peer {} sends us a malformed message",
+                    peer_index
+                );
+                nc.ban_peer(
+                    peer_index,
+                    BAD_MESSAGE_BAN_TIME,
+                    String::from("send us a malformed message"),
+                );
+                return;
+            }
+        };
+
+        debug_target!(
+            crate::LOG_TARGET_FILTER,
+            "received msg {} from {}",
+            msg.item_name(),
+            peer_index
+        );
+        let start_time = Instant::now();
+        self.process(nc.as_ref(), peer_index, msg);
+        debug_target!(
+            crate::LOG_TARGET_FILTER,
+            "process message={}, peer={}, cost={:?}",
+            msg.item_name(),
+            peer_index,
+            Instant::now().saturating_duration_since(start_time),
+        );
+    }
+}
@@ -4,9 +4,12 @@
 //! https://github.com/nervosnetwork/rfcs/blob/master/rfcs/0004-ckb-block-sync/0004-ckb-block-sync.md
 
 mod block_status;
+mod filter;
+mod light_client;
 pub(crate) mod net_time_checker;
 pub(crate) mod orphan_block_pool;
 mod relayer;
+mod snapshot_validation;
 mod status;
 mod synchronizer;
 mod types;
@@ -15,8 +18,11 @@ mod utils;
 #[cfg(test)]
 mod tests;
 
+pub use crate::filter::BlockFilter;
+pub use crate::light_client::LightClientProtocol;
 pub use crate::net_time_checker::NetTimeProtocol;
 pub use crate::relayer::Relayer;
+pub use crate::snapshot_validation::{SnapshotValidation, SnapshotValidationStatus};
 pub use crate::status::{Status, StatusCode};
 pub use crate::synchronizer::Synchronizer;
 pub use crate::types::SyncShared;
@@ -33,3 +39,5 @@ pub(crate) const NORMAL_INDEX: usize = TIME_TRACE_SIZE * 4 / 5;
 pub(crate) const LOW_INDEX: usize = TIME_TRACE_SIZE * 9 / 10;
 
 pub(crate) const LOG_TARGET_RELAY: &str = "ckb_relay";
+pub(crate) const LOG_TARGET_FILTER: &str = "ckb_filter";
+pub(crate) const LOG_TARGET_LIGHT_CLIENT: &str = "ckb_light_client";
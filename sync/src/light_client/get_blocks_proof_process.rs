@@ -0,0 +1,90 @@
+use crate::light_client::{LightClientProtocol, GET_BLOCKS_PROOF_LIMIT};
+use crate::utils::send_message_to;
+use crate::{Status, StatusCode};
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_store::ChainStore;
+use ckb_types::{packed, prelude::*};
+use std::collections::HashSet;
+
+pub struct GetBlocksProofProcess<'a> {
+    message: packed::GetBlocksProofReader<'a>,
+    protocol: &'a LightClientProtocol,
+    nc: &'a dyn CKBProtocolContext,
+    peer: PeerIndex,
+}
+
+impl<'a> GetBlocksProofProcess<'a> {
+    pub fn new(
+        message: packed::GetBlocksProofReader<'a>,
+        protocol: &'a LightClientProtocol,
+        nc: &'a dyn CKBProtocolContext,
+        peer: PeerIndex,
+    ) -> Self {
+        Self {
+            message,
+            protocol,
+            nc,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        if self.message.block_hashes().len() > GET_BLOCKS_PROOF_LIMIT {
+            return StatusCode::ProtocolMessageIsMalformed.with_context(format!(
+                "too many blocks are requested, the limit is {}",
+                GET_BLOCKS_PROOF_LIMIT
+            ));
+        }
+
+        let snapshot = self.protocol.snapshot();
+        let last_hash = self.message.last_hash().to_entity();
+        let last_header = match LightClientProtocol::last_header(&snapshot, &last_hash) {
+            Some(last_header) => last_header,
+            None => return self.protocol.reply_last_state(self.nc, self.peer),
+        };
+        let last_number = last_header.number();
+
+        let mut uniq = HashSet::new();
+        let mut headers = Vec::new();
+        let mut missing_block_hashes = Vec::new();
+        for block_hash in self.message.block_hashes().iter() {
+            let block_hash = block_hash.to_entity();
+            if !uniq.insert(block_hash.clone()) {
+                return StatusCode::ProtocolMessageIsMalformed
+                    .with_context(format!("duplicated block hash {}", block_hash));
+            }
+            match snapshot
+                .get_block_header(&block_hash)
+                .filter(|header| header.number() < last_number)
+                .filter(|_| snapshot.is_main_chain(&block_hash))
+            {
+                Some(header) => headers.push(header),
+                None => missing_block_hashes.push(block_hash),
+            }
+        }
+
+        let numbers = headers
+            .iter()
+            .map(|header| header.number())
+            .collect::<Vec<_>>();
+        let proof = match LightClientProtocol::chain_root_proof(&snapshot, last_number, &numbers) {
+            Ok(proof) => proof,
+            Err(err) => return StatusCode::InternalError.with_context(err),
+        };
+        let last_header = match LightClientProtocol::verifiable_header(&snapshot, &last_header) {
+            Ok(last_header) => last_header,
+            Err(err) => return StatusCode::InternalError.with_context(err),
+        };
+
+        let content = packed::SendBlocksProof::new_builder()
+            .last_header(last_header)
+            .proof(proof)
+            .headers(headers.into_iter().map(|header| header.data()).pack())
+            .missing_block_hashes(missing_block_hashes.pack())
+            .build();
+        let message = packed::LightClientMessage::new_builder()
+            .set(content)
+            .build();
+        send_message_to(self.nc, self.peer, &message)
+    }
+}
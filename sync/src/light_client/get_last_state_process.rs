@@ -0,0 +1,41 @@
+use crate::light_client::LightClientProtocol;
+use crate::{Status, StatusCode};
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_types::{packed, prelude::*};
+
+pub struct GetLastStateProcess<'a> {
+    message: packed::GetLastStateReader<'a>,
+    protocol: &'a LightClientProtocol,
+    nc: &'a dyn CKBProtocolContext,
+    peer: PeerIndex,
+}
+
+impl<'a> GetLastStateProcess<'a> {
+    pub fn new(
+        message: packed::GetLastStateReader<'a>,
+        protocol: &'a LightClientProtocol,
+        nc: &'a dyn CKBProtocolContext,
+        peer: PeerIndex,
+    ) -> Self {
+        Self {
+            message,
+            protocol,
+            nc,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        // The peer may send any byte as the flag, so it is not unpacked directly.
+        let subscribe = match self.message.subscribe().as_slice()[0] {
+            0 => false,
+            1 => true,
+            _ => {
+                return StatusCode::ProtocolMessageIsMalformed
+                    .with_context("the subscribe flag should be 0 or 1")
+            }
+        };
+        self.protocol.subscribe(self.peer, subscribe);
+        self.protocol.reply_last_state(self.nc, self.peer)
+    }
+}
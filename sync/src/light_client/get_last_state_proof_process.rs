@@ -0,0 +1,204 @@
+use crate::light_client::{LightClientProtocol, GET_LAST_STATE_PROOF_LIMIT};
+use crate::utils::send_message_to;
+use crate::{Status, StatusCode};
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_shared::Snapshot;
+use ckb_store::ChainStore;
+use ckb_types::{
+    core::{BlockNumber, HeaderView},
+    packed,
+    prelude::*,
+    U256,
+};
+
+pub struct GetLastStateProofProcess<'a> {
+    message: packed::GetLastStateProofReader<'a>,
+    protocol: &'a LightClientProtocol,
+    nc: &'a dyn CKBProtocolContext,
+    peer: PeerIndex,
+}
+
+/// Finds the first block in `[start, end)` whose total difficulty is not less than the difficulty.
+pub(crate) fn first_block_reaching<F>(
+    start: BlockNumber,
+    end: BlockNumber,
+    difficulty: &U256,
+    total_difficulty: &F,
+) -> Result<Option<BlockNumber>, String>
+where
+    F: Fn(BlockNumber) -> Option<U256>,
+{
+    let (mut low, mut high) = (start, end);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let value = total_difficulty(mid)
+            .ok_or_else(|| format!("the total difficulty of block {} is not found", mid))?;
+        if &value < difficulty {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(if low < end { Some(low) } else { None })
+}
+
+/// Samples the blocks in `[start, end)` by the ascending total difficulties chosen by the client.
+///
+/// Each difficulty samples the first block which reaches it, the duplicated samples are removed.
+pub(crate) fn sample_block_numbers<F>(
+    start: BlockNumber,
+    end: BlockNumber,
+    difficulties: &[U256],
+    total_difficulty: &F,
+) -> Result<Vec<BlockNumber>, String>
+where
+    F: Fn(BlockNumber) -> Option<U256>,
+{
+    let mut numbers = Vec::new();
+    let mut low = start;
+    for difficulty in difficulties {
+        match first_block_reaching(low, end, difficulty, total_difficulty)? {
+            Some(number) => {
+                if numbers.last() != Some(&number) {
+                    numbers.push(number);
+                }
+                low = number;
+            }
+            None => break,
+        }
+    }
+    Ok(numbers)
+}
+
+impl<'a> GetLastStateProofProcess<'a> {
+    pub fn new(
+        message: packed::GetLastStateProofReader<'a>,
+        protocol: &'a LightClientProtocol,
+        nc: &'a dyn CKBProtocolContext,
+        peer: PeerIndex,
+    ) -> Self {
+        Self {
+            message,
+            protocol,
+            nc,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        let snapshot = self.protocol.snapshot();
+        let last_hash = self.message.last_hash().to_entity();
+        let last_header = match LightClientProtocol::last_header(&snapshot, &last_hash) {
+            Some(last_header) => last_header,
+            // The last block is not in the main chain any more, the client should start over
+            // with the new tip.
+            None => return self.protocol.reply_last_state(self.nc, self.peer),
+        };
+        let last_number = last_header.number();
+
+        let start_hash = self.message.start_hash().to_entity();
+        let start_number: BlockNumber = self.message.start_number().unpack();
+        let last_n_blocks: BlockNumber = self.message.last_n_blocks().unpack();
+        let difficulty_boundary: U256 = self.message.difficulty_boundary().unpack();
+        let difficulties: Vec<U256> = self.message.difficulties().unpack();
+
+        if start_number >= last_number {
+            return StatusCode::ProtocolMessageIsMalformed
+                .with_context("the start block should be before the last block");
+        }
+        if last_n_blocks as usize + difficulties.len() > GET_LAST_STATE_PROOF_LIMIT {
+            return StatusCode::ProtocolMessageIsMalformed.with_context(format!(
+                "too many headers are requested, the limit is {}",
+                GET_LAST_STATE_PROOF_LIMIT
+            ));
+        }
+        if difficulties.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return StatusCode::ProtocolMessageIsMalformed
+                .with_context("the difficulties should be sorted in ascending order");
+        }
+        if snapshot.get_block_hash(start_number).as_ref() != Some(&start_hash) {
+            return self.protocol.reply_last_state(self.nc, self.peer);
+        }
+
+        let total_difficulty = |number| {
+            snapshot
+                .get_block_hash(number)
+                .and_then(|hash| snapshot.get_block_ext(&hash))
+                .map(|ext| ext.total_difficulty)
+        };
+        let numbers = if last_number - start_number <= last_n_blocks {
+            // There are not enough blocks to sample, so all of them are sent.
+            (start_number..last_number).collect::<Vec<_>>()
+        } else {
+            let boundary_number = match first_block_reaching(
+                start_number,
+                last_number,
+                &difficulty_boundary,
+                &total_difficulty,
+            ) {
+                Ok(Some(number)) => number.min(last_number - last_n_blocks),
+                Ok(None) => {
+                    return StatusCode::ProtocolMessageIsMalformed.with_context(format!(
+                        "the difficulty boundary {:#x} is not in the blocks [{}, {})",
+                        difficulty_boundary, start_number, last_number
+                    ))
+                }
+                Err(err) => return StatusCode::InternalError.with_context(err),
+            };
+            if (last_number - boundary_number) as usize + difficulties.len()
+                > GET_LAST_STATE_PROOF_LIMIT
+            {
+                return StatusCode::ProtocolMessageIsMalformed.with_context(format!(
+                    "too many blocks after the difficulty boundary {:#x}",
+                    difficulty_boundary
+                ));
+            }
+            let mut numbers = match sample_block_numbers(
+                start_number,
+                boundary_number,
+                &difficulties,
+                &total_difficulty,
+            ) {
+                Ok(numbers) => numbers,
+                Err(err) => return StatusCode::InternalError.with_context(err),
+            };
+            numbers.extend(boundary_number..last_number);
+            numbers
+        };
+
+        match self.build_proof(&snapshot, &last_header, &numbers) {
+            Ok(content) => {
+                let message = packed::LightClientMessage::new_builder()
+                    .set(content)
+                    .build();
+                send_message_to(self.nc, self.peer, &message)
+            }
+            Err(err) => StatusCode::InternalError.with_context(err),
+        }
+    }
+
+    fn build_proof(
+        &self,
+        snapshot: &Snapshot,
+        last_header: &HeaderView,
+        numbers: &[BlockNumber],
+    ) -> Result<packed::SendLastStateProof, String> {
+        let headers = numbers
+            .iter()
+            .map(|number| {
+                snapshot
+                    .get_block_hash(*number)
+                    .and_then(|hash| snapshot.get_block_header(&hash))
+                    .ok_or_else(|| format!("the header of block {} is not found", number))
+                    .and_then(|header| LightClientProtocol::verifiable_header(snapshot, &header))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let proof = LightClientProtocol::chain_root_proof(snapshot, last_header.number(), numbers)?;
+        let last_header = LightClientProtocol::verifiable_header(snapshot, last_header)?;
+        Ok(packed::SendLastStateProof::new_builder()
+            .last_header(last_header)
+            .proof(proof)
+            .headers(headers.pack())
+            .build())
+    }
+}
@@ -0,0 +1,129 @@
+use crate::light_client::{LightClientProtocol, GET_TRANSACTIONS_PROOF_LIMIT};
+use crate::utils::send_message_to;
+use crate::{Status, StatusCode};
+use ckb_network::{CKBProtocolContext, PeerIndex};
+use ckb_store::ChainStore;
+use ckb_types::{core::BlockNumber, packed, prelude::*, utilities::CBMT};
+use std::collections::{BTreeMap, HashSet};
+
+pub struct GetTransactionsProofProcess<'a> {
+    message: packed::GetTransactionsProofReader<'a>,
+    protocol: &'a LightClientProtocol,
+    nc: &'a dyn CKBProtocolContext,
+    peer: PeerIndex,
+}
+
+impl<'a> GetTransactionsProofProcess<'a> {
+    pub fn new(
+        message: packed::GetTransactionsProofReader<'a>,
+        protocol: &'a LightClientProtocol,
+        nc: &'a dyn CKBProtocolContext,
+        peer: PeerIndex,
+    ) -> Self {
+        Self {
+            message,
+            protocol,
+            nc,
+            peer,
+        }
+    }
+
+    pub fn execute(self) -> Status {
+        if self.message.tx_hashes().len() > GET_TRANSACTIONS_PROOF_LIMIT {
+            return StatusCode::ProtocolMessageIsMalformed.with_context(format!(
+                "too many transactions are requested, the limit is {}",
+                GET_TRANSACTIONS_PROOF_LIMIT
+            ));
+        }
+
+        let snapshot = self.protocol.snapshot();
+        let last_hash = self.message.last_hash().to_entity();
+        let last_header = match LightClientProtocol::last_header(&snapshot, &last_hash) {
+            Some(last_header) => last_header,
+            None => return self.protocol.reply_last_state(self.nc, self.peer),
+        };
+        let last_number = last_header.number();
+
+        let mut uniq = HashSet::new();
+        // The indices of the found transactions, grouped by the blocks which contain them.
+        let mut blocks: BTreeMap<BlockNumber, (packed::Byte32, Vec<u32>)> = BTreeMap::new();
+        let mut missing_tx_hashes = Vec::new();
+        for tx_hash in self.message.tx_hashes().iter() {
+            let tx_hash = tx_hash.to_entity();
+            if !uniq.insert(tx_hash.clone()) {
+                return StatusCode::ProtocolMessageIsMalformed
+                    .with_context(format!("duplicated transaction hash {}", tx_hash));
+            }
+            match snapshot
+                .get_transaction_info(&tx_hash)
+                .filter(|info| info.block_number < last_number)
+                .filter(|info| snapshot.is_main_chain(&info.block_hash))
+            {
+                Some(info) => blocks
+                    .entry(info.block_number)
+                    .or_insert_with(|| (info.block_hash, Vec::new()))
+                    .1
+                    .push(info.index as u32),
+                None => missing_tx_hashes.push(tx_hash),
+            }
+        }
+
+        let mut filtered_blocks = Vec::with_capacity(blocks.len());
+        for (number, (block_hash, indices)) in blocks.iter_mut() {
+            indices.sort_unstable();
+            let block = match snapshot.get_block(block_hash) {
+                Some(block) => block,
+                None => {
+                    return StatusCode::InternalError
+                        .with_context(format!("block {} is not found", number))
+                }
+            };
+            let proof = match CBMT::build_merkle_proof(block.tx_hashes(), indices) {
+                Some(proof) => proof,
+                None => {
+                    return StatusCode::InternalError.with_context(format!(
+                        "failed to build the transactions proof of block {}",
+                        number
+                    ))
+                }
+            };
+            let transactions = indices
+                .iter()
+                .filter_map(|index| block.transaction(*index as usize))
+                .map(|tx| tx.data());
+            let merkle_proof = packed::MerkleProof::new_builder()
+                .indices(proof.indices().pack())
+                .lemmas(proof.lemmas().iter().cloned().pack())
+                .build();
+            filtered_blocks.push(
+                packed::FilteredBlock::new_builder()
+                    .header(block.header().data())
+                    .witnesses_root(block.calc_witnesses_root())
+                    .transactions(transactions.pack())
+                    .proof(merkle_proof)
+                    .build(),
+            );
+        }
+
+        let numbers = blocks.keys().cloned().collect::<Vec<_>>();
+        let proof = match LightClientProtocol::chain_root_proof(&snapshot, last_number, &numbers) {
+            Ok(proof) => proof,
+            Err(err) => return StatusCode::InternalError.with_context(err),
+        };
+        let last_header = match LightClientProtocol::verifiable_header(&snapshot, &last_header) {
+            Ok(last_header) => last_header,
+            Err(err) => return StatusCode::InternalError.with_context(err),
+        };
+
+        let content = packed::SendTransactionsProof::new_builder()
+            .last_header(last_header)
+            .proof(proof)
+            .filtered_blocks(filtered_blocks.pack())
+            .missing_tx_hashes(missing_tx_hashes.pack())
+            .build();
+        let message = packed::LightClientMessage::new_builder()
+            .set(content)
+            .build();
+        send_message_to(self.nc, self.peer, &message)
+    }
+}
@@ -0,0 +1,336 @@
+mod get_blocks_proof_process;
+mod get_last_state_process;
+mod get_last_state_proof_process;
+mod get_transactions_proof_process;
+
+#[cfg(test)]
+mod tests;
+
+use crate::light_client::get_blocks_proof_process::GetBlocksProofProcess;
+use crate::light_client::get_last_state_process::GetLastStateProcess;
+use crate::light_client::get_last_state_proof_process::GetLastStateProofProcess;
+use crate::light_client::get_transactions_proof_process::GetTransactionsProofProcess;
+use crate::types::SyncShared;
+use crate::utils::send_message_to;
+use crate::{Status, StatusCode};
+use ckb_constant::sync::BAD_MESSAGE_BAN_TIME;
+use ckb_logger::{debug_target, error_target, info_target, warn_target};
+use ckb_metrics::metrics;
+use ckb_network::{
+    bytes::Bytes, CKBProtocolContext, CKBProtocolHandler, PeerIndex, SupportProtocols,
+};
+use ckb_shared::Snapshot;
+use ckb_store::ChainStore;
+use ckb_types::{
+    core::{BlockNumber, HeaderView},
+    packed,
+    prelude::*,
+    utilities::leaf_index_to_pos,
+};
+use ckb_util::Mutex;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// The max number of the headers in one last state proof.
+const GET_LAST_STATE_PROOF_LIMIT: usize = 1000;
+// The max number of the block hashes in one blocks proof request.
+const GET_BLOCKS_PROOF_LIMIT: usize = 1000;
+// The max number of the transaction hashes in one transactions proof request.
+const GET_TRANSACTIONS_PROOF_LIMIT: usize = 1000;
+
+const NOTIFY_LAST_STATE_TOKEN: u64 = 0;
+const NOTIFY_LAST_STATE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Light client protocol handler, which serves the light clients with the verifiable headers.
+///
+/// Every header sent to the light clients comes with the root of the chain root MMR over all the
+/// headers before it, so the clients verify the sampled headers and the headers which contain
+/// the transactions they care about against the last header, in the style of FlyClient. The
+/// cells are proved by the transactions which create them.
+///
+/// The root is only trustworthy when it is committed in the extension of the last header, which
+/// is required since RFC 0044, so the requests are ignored until the tip reaches the epoch it is
+/// activated, and the last header of a proof is always a block which commits the chain root.
+pub struct LightClientProtocol {
+    shared: Arc<SyncShared>,
+    subscribers: Mutex<HashSet<PeerIndex>>,
+    last_notified_tip: Option<packed::Byte32>,
+}
+
+impl LightClientProtocol {
+    /// Create a new light client protocol handler
+    pub fn new(shared: Arc<SyncShared>) -> Self {
+        LightClientProtocol {
+            shared,
+            subscribers: Mutex::new(HashSet::new()),
+            last_notified_tip: None,
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> Arc<Snapshot> {
+        Arc::clone(&self.shared.shared().snapshot())
+    }
+
+    /// Sets whether the last state is sent to the peer when the chain tip changes.
+    pub(crate) fn subscribe(&self, peer: PeerIndex, subscribe: bool) {
+        let mut subscribers = self.subscribers.lock();
+        if subscribe {
+            subscribers.insert(peer);
+        } else {
+            subscribers.remove(&peer);
+        }
+    }
+
+    /// Builds the verifiable header, whose parent chain root is the root of the chain root MMR
+    /// over all the main chain headers before it.
+    ///
+    /// The parent chain root is committed in the extension only if the header is in an epoch
+    /// since RFC 0044 is activated.
+    pub(crate) fn verifiable_header(
+        snapshot: &Snapshot,
+        header: &HeaderView,
+    ) -> Result<packed::VerifiableHeader, String> {
+        let block_hash = header.hash();
+        let uncles_hash = snapshot
+            .get_block_uncles(&block_hash)
+            .map(|uncles| uncles.data().calc_uncles_hash())
+            .ok_or_else(|| format!("the uncles of block {} are not found", block_hash))?;
+        let extension = snapshot.get_block_extension(&block_hash);
+        let parent_chain_root = if header.is_genesis() {
+            packed::HeaderDigest::default()
+        } else {
+            snapshot
+                .chain_root_mmr(header.number() - 1)
+                .get_root()
+                .map_err(|err| {
+                    format!(
+                        "failed to get the chain root of block {}: {}",
+                        block_hash, err
+                    )
+                })?
+        };
+        Ok(packed::VerifiableHeader::new_builder()
+            .header(header.data())
+            .uncles_hash(uncles_hash)
+            .extension(packed::BytesOpt::new_builder().set(extension).build())
+            .parent_chain_root(parent_chain_root)
+            .build())
+    }
+
+    /// Generates the chain root MMR proof of the main chain blocks before the last block.
+    pub(crate) fn chain_root_proof(
+        snapshot: &Snapshot,
+        last_number: BlockNumber,
+        numbers: &[BlockNumber],
+    ) -> Result<packed::HeaderDigestVec, String> {
+        if numbers.is_empty() {
+            return Ok(Default::default());
+        }
+        let positions = numbers
+            .iter()
+            .map(|number| leaf_index_to_pos(*number))
+            .collect();
+        snapshot
+            .chain_root_mmr(last_number - 1)
+            .gen_proof(positions)
+            .map(|proof| proof.proof_items().iter().cloned().pack())
+            .map_err(|err| format!("failed to generate the chain root proof: {}", err))
+    }
+
+    /// Sends the current tip as the last state to the peer.
+    pub(crate) fn reply_last_state(&self, nc: &dyn CKBProtocolContext, peer: PeerIndex) -> Status {
+        let snapshot = self.snapshot();
+        let last_header = match Self::verifiable_header(&snapshot, snapshot.tip_header()) {
+            Ok(last_header) => last_header,
+            Err(err) => return StatusCode::InternalError.with_context(err),
+        };
+        let content = packed::SendLastState::new_builder()
+            .last_header(last_header)
+            .build();
+        let message = packed::LightClientMessage::new_builder()
+            .set(content)
+            .build();
+        send_message_to(nc, peer, &message)
+    }
+
+    /// Returns the main chain header of the last block in a request, which all the proofs are
+    /// generated against.
+    ///
+    /// The block has to commit the chain root, otherwise the proofs can't be verified.
+    pub(crate) fn last_header(
+        snapshot: &Snapshot,
+        last_hash: &packed::Byte32,
+    ) -> Option<HeaderView> {
+        if snapshot.is_main_chain(last_hash) {
+            snapshot
+                .get_block_header(last_hash)
+                .filter(|header| Self::is_chain_root_committed(snapshot, header))
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if the chain root is committed in the extension of the block.
+    pub(crate) fn is_chain_root_committed(snapshot: &Snapshot, header: &HeaderView) -> bool {
+        !header.is_genesis()
+            && snapshot
+                .consensus()
+                .hardfork_switch()
+                .is_commit_chain_root_in_extension_enabled(header.epoch().number())
+    }
+
+    fn try_process(
+        &self,
+        nc: &dyn CKBProtocolContext,
+        peer: PeerIndex,
+        message: packed::LightClientMessageUnionReader<'_>,
+    ) -> Status {
+        let snapshot = self.snapshot();
+        if !Self::is_chain_root_committed(&snapshot, snapshot.tip_header()) {
+            return StatusCode::Ignored
+                .with_context("the chain root isn't committed before RFC 0044 is activated");
+        }
+        match message {
+            packed::LightClientMessageUnionReader::GetLastState(reader) => {
+                GetLastStateProcess::new(reader, self, nc, peer).execute()
+            }
+            packed::LightClientMessageUnionReader::GetLastStateProof(reader) => {
+                GetLastStateProofProcess::new(reader, self, nc, peer).execute()
+            }
+            packed::LightClientMessageUnionReader::GetBlocksProof(reader) => {
+                GetBlocksProofProcess::new(reader, self, nc, peer).execute()
+            }
+            packed::LightClientMessageUnionReader::GetTransactionsProof(reader) => {
+                GetTransactionsProofProcess::new(reader, self, nc, peer).execute()
+            }
+            _ => StatusCode::ProtocolMessageIsMalformed
+                .with_context("unexpected light client message"),
+        }
+    }
+
+    fn process(
+        &self,
+        nc: &dyn CKBProtocolContext,
+        peer: PeerIndex,
+        message: packed::LightClientMessageUnionReader<'_>,
+    ) {
+        let item_name = message.item_name();
+        let item_bytes = message.as_slice().len() as u64;
+        let status = self.try_process(nc, peer, message);
+
+        metrics!(
+            counter,
+            "ckb.messages_bytes",
+            item_bytes,
+            "direction" => "in",
+            "protocol_id" => SupportProtocols::LightClient.protocol_id().value().to_string(),
+            "item_id" => message.item_id().to_string(),
+            "status" => (status.code() as u16).to_string(),
+        );
+
+        if let Some(ban_time) = status.should_ban() {
+            error_target!(
+                crate::LOG_TARGET_LIGHT_CLIENT,
+                "receive {} from {}, ban {:?} for {}",
+                item_name,
+                peer,
+                ban_time,
+                status
+            );
+            nc.ban_peer(peer, ban_time, status.to_string());
+        } else if let Some(behaviour) = status.behaviour() {
+            nc.report_peer(peer, behaviour);
+        } else if status.should_warn() {
+            warn_target!(
+                crate::LOG_TARGET_LIGHT_CLIENT,
+                "receive {} from {}, {}",
+                item_name,
+                peer,
+                status
+            );
+        } else if !status.is_ok() {
+            debug_target!(
+                crate::LOG_TARGET_LIGHT_CLIENT,
+                "receive {} from {}, {}",
+                item_name,
+                peer,
+                status
+            );
+        }
+    }
+}
+
+impl CKBProtocolHandler for LightClientProtocol {
+    fn init(&mut self, nc: Arc<dyn CKBProtocolContext + Sync>) {
+        nc.set_notify(NOTIFY_LAST_STATE_INTERVAL, NOTIFY_LAST_STATE_TOKEN)
+            .expect("set_notify at init is ok");
+    }
+
+    fn disconnected(&mut self, _nc: Arc<dyn CKBProtocolContext + Sync>, peer_index: PeerIndex) {
+        self.subscribe(peer_index, false);
+    }
+
+    fn received(
+        &mut self,
+        nc: Arc<dyn CKBProtocolContext + Sync>,
+        peer_index: PeerIndex,
+        data: Bytes,
+    ) {
+        let msg = match packed::LightClientMessageReader::from_slice(&data) {
+            Ok(msg) => msg.to_enum(),
+            _ => {
+                info_target!(
+                    crate::LOG_TARGET_LIGHT_CLIENT,
+                    "Peer {} sends us a malformed message",
+                    peer_index
+                );
+                nc.ban_peer(
+                    peer_index,
+                    BAD_MESSAGE_BAN_TIME,
+                    String::from("send us a malformed message"),
+                );
+                return;
+            }
+        };
+
+        debug_target!(
+            crate::LOG_TARGET_LIGHT_CLIENT,
+            "received msg {} from {}",
+            msg.item_name(),
+            peer_index
+        );
+        let start_time = Instant::now();
+        self.process(nc.as_ref(), peer_index, msg);
+        debug_target!(
+            crate::LOG_TARGET_LIGHT_CLIENT,
+            "process message={}, peer={}, cost={:?}",
+            msg.item_name(),
+            peer_index,
+            Instant::now().saturating_duration_since(start_time),
+        );
+    }
+
+    fn notify(&mut self, nc: Arc<dyn CKBProtocolContext + Sync>, token: u64) {
+        if token != NOTIFY_LAST_STATE_TOKEN {
+            return;
+        }
+        let tip_hash = self.shared.shared().snapshot().tip_hash();
+        if self.last_notified_tip.as_ref() == Some(&tip_hash) {
+            return;
+        }
+        let subscribers: Vec<PeerIndex> = self.subscribers.lock().iter().cloned().collect();
+        for peer in subscribers {
+            let status = self.reply_last_state(nc.as_ref(), peer);
+            if !status.is_ok() {
+                debug_target!(
+                    crate::LOG_TARGET_LIGHT_CLIENT,
+                    "notify the last state to {}, {}",
+                    peer,
+                    status
+                );
+            }
+        }
+        self.last_notified_tip = Some(tip_hash);
+    }
+}
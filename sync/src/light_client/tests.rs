@@ -0,0 +1,333 @@
+use crate::light_client::get_last_state_proof_process::{
+    first_block_reaching, sample_block_numbers,
+};
+use crate::light_client::LightClientProtocol;
+use crate::relayer::tests::helper::MockProtocolContext;
+use crate::tests::util::inherit_block;
+use crate::{Status, StatusCode, SyncShared};
+use ckb_chain::chain::{ChainController, ChainService};
+use ckb_launcher::SharedBuilder;
+use ckb_network::{PeerIndex, SupportProtocols};
+use ckb_store::ChainStore;
+use ckb_test_chain_utils::always_success_consensus;
+use ckb_types::{
+    core::{hardfork::HardForkSwitch, BlockNumber, EpochNumber, HeaderView},
+    packed,
+    prelude::*,
+    utilities::{leaf_index_to_mmr_size, leaf_index_to_pos, merkle_root, MMRProof, MerkleProof},
+    U256,
+};
+use ckb_verification_traits::Switch;
+use std::sync::Arc;
+
+// Every block has the difficulty 10.
+fn total_difficulty(number: BlockNumber) -> Option<U256> {
+    Some(U256::from((number + 1) * 10))
+}
+
+#[test]
+fn test_first_block_reaching() {
+    let find = |difficulty: u64| {
+        first_block_reaching(0, 100, &U256::from(difficulty), &total_difficulty).unwrap()
+    };
+    assert_eq!(find(0), Some(0));
+    assert_eq!(find(10), Some(0));
+    assert_eq!(find(11), Some(1));
+    assert_eq!(find(1000), Some(99));
+    assert_eq!(find(1001), None);
+
+    let missing = |_| None;
+    assert!(first_block_reaching(0, 100, &U256::from(10u64), &missing).is_err());
+}
+
+#[test]
+fn test_sample_block_numbers() {
+    let difficulties = [5u64, 9, 25, 26, 500, 2000]
+        .iter()
+        .map(|difficulty| U256::from(*difficulty))
+        .collect::<Vec<_>>();
+    let numbers = sample_block_numbers(0, 60, &difficulties, &total_difficulty).unwrap();
+    // The difficulties 5 and 9 sample the same block, and the last two are beyond the range.
+    assert_eq!(numbers, vec![0, 2, 49]);
+
+    let numbers = sample_block_numbers(10, 60, &difficulties, &total_difficulty).unwrap();
+    assert_eq!(numbers, vec![10, 49]);
+}
+
+// Builds a chain whose blocks commit the chain root since the epoch `rfc_0044`.
+fn build_chain(tip: BlockNumber, rfc_0044: EpochNumber) -> (LightClientProtocol, ChainController) {
+    let mut consensus = always_success_consensus();
+    consensus.hardfork_switch = HardForkSwitch::new_without_any_enabled()
+        .as_builder()
+        .rfc_0031(0)
+        .rfc_0044(rfc_0044)
+        .build()
+        .unwrap();
+    let (shared, mut pack) = SharedBuilder::with_temp_db()
+        .consensus(consensus)
+        .build()
+        .unwrap();
+    let chain_controller = {
+        let chain_service = ChainService::new(shared.clone(), pack.take_proposal_table());
+        chain_service.start::<&str>(None)
+    };
+    for _ in 0..tip {
+        let snapshot = shared.snapshot();
+        let parent = snapshot.tip_header().clone();
+        let mut builder = inherit_block(&shared, &parent.hash());
+        if rfc_0044 == 0 {
+            let chain_root = snapshot.chain_root_mmr(parent.number()).get_root().unwrap();
+            builder = builder.extension(Some(chain_root.calc_mmr_hash().as_bytes().pack()));
+        }
+        chain_controller
+            .internal_process_block(Arc::new(builder.build()), Switch::ONLY_SCRIPT)
+            .expect("processing block should be ok");
+    }
+    let sync_shared = SyncShared::new(shared, Default::default(), pack.take_relay_tx_receiver());
+    (
+        LightClientProtocol::new(Arc::new(sync_shared)),
+        chain_controller,
+    )
+}
+
+fn request<T: Into<packed::LightClientMessageUnion>>(
+    protocol: &LightClientProtocol,
+    content: T,
+) -> (Status, Vec<packed::LightClientMessageUnion>) {
+    let nc = MockProtocolContext::new(SupportProtocols::LightClient);
+    let message = packed::LightClientMessage::new_builder()
+        .set(content)
+        .build();
+    let status = protocol.try_process(&nc, PeerIndex::new(1), message.as_reader().to_enum());
+    let sent = nc
+        .sent_messages()
+        .into_iter()
+        .map(|(_, _, data)| {
+            packed::LightClientMessage::from_slice(&data)
+                .unwrap()
+                .to_enum()
+        })
+        .collect();
+    (status, sent)
+}
+
+fn header_of(protocol: &LightClientProtocol, number: BlockNumber) -> HeaderView {
+    let snapshot = protocol.snapshot();
+    let hash = snapshot.get_block_hash(number).unwrap();
+    snapshot.get_block_header(&hash).unwrap()
+}
+
+fn check_last_header(last_header: &packed::VerifiableHeader, expected: &HeaderView) {
+    assert_eq!(last_header.header().calc_header_hash(), expected.hash());
+    assert!(last_header.is_valid());
+    assert!(last_header.is_chain_root_committed());
+}
+
+fn check_chain_root_proof(
+    last_header: &packed::VerifiableHeader,
+    proof: packed::HeaderDigestVec,
+    headers: &[HeaderView],
+) {
+    let last_number: BlockNumber = last_header.header().raw().number().unpack();
+    let proof = MMRProof::new(
+        leaf_index_to_mmr_size(last_number - 1),
+        proof.into_iter().collect(),
+    );
+    let mut leaves = headers
+        .iter()
+        .map(|header| (leaf_index_to_pos(header.number()), header.digest()))
+        .collect::<Vec<_>>();
+    leaves.sort_by_key(|(position, _)| *position);
+    assert!(proof
+        .verify(last_header.parent_chain_root(), leaves)
+        .unwrap());
+}
+
+#[test]
+fn test_ignored_before_chain_root_is_committed() {
+    let (protocol, _chain) = build_chain(5, EpochNumber::MAX);
+    let content = packed::GetLastState::new_builder()
+        .subscribe(false.pack())
+        .build();
+    let (status, sent) = request(&protocol, content);
+    assert_eq!(status.code(), StatusCode::Ignored);
+    assert!(sent.is_empty());
+}
+
+#[test]
+fn test_get_last_state() {
+    let (protocol, _chain) = build_chain(10, 0);
+    let content = packed::GetLastState::new_builder()
+        .subscribe(false.pack())
+        .build();
+    let (status, sent) = request(&protocol, content);
+    assert!(status.is_ok(), "{}", status);
+    let last_state = match &sent[..] {
+        [packed::LightClientMessageUnion::SendLastState(last_state)] => last_state.clone(),
+        _ => panic!("unexpected messages"),
+    };
+    check_last_header(&last_state.last_header(), &header_of(&protocol, 10));
+}
+
+#[test]
+fn test_get_last_state_proof() {
+    let (protocol, _chain) = build_chain(30, 0);
+    let snapshot = protocol.snapshot();
+    let total_difficulty = |number| {
+        snapshot
+            .get_block_ext(&snapshot.get_block_hash(number).unwrap())
+            .unwrap()
+            .total_difficulty
+    };
+    let last_header = header_of(&protocol, 30);
+    let content = packed::GetLastStateProof::new_builder()
+        .last_hash(last_header.hash())
+        .start_hash(header_of(&protocol, 0).hash())
+        .start_number(0u64.pack())
+        .last_n_blocks(10u64.pack())
+        .difficulty_boundary(total_difficulty(20).pack())
+        .difficulties(vec![total_difficulty(5), total_difficulty(12)].pack())
+        .build();
+    let (status, sent) = request(&protocol, content);
+    assert!(status.is_ok(), "{}", status);
+    let proof = match &sent[..] {
+        [packed::LightClientMessageUnion::SendLastStateProof(proof)] => proof.clone(),
+        _ => panic!("unexpected messages"),
+    };
+    check_last_header(&proof.last_header(), &last_header);
+
+    // The sampled blocks and the last 10 blocks.
+    let expected_numbers = [5, 12].iter().cloned().chain(20..30).collect::<Vec<_>>();
+    let headers = proof
+        .headers()
+        .into_iter()
+        .map(|header| {
+            assert!(header.is_valid());
+            header.header().into_view()
+        })
+        .collect::<Vec<_>>();
+    let numbers = headers
+        .iter()
+        .map(|header| header.number())
+        .collect::<Vec<_>>();
+    assert_eq!(numbers, expected_numbers);
+    check_chain_root_proof(&proof.last_header(), proof.proof(), &headers);
+}
+
+#[test]
+fn test_get_blocks_proof() {
+    let (protocol, _chain) = build_chain(20, 0);
+    let last_header = header_of(&protocol, 20);
+    let missing = packed::Byte32::zero();
+    let block_hashes = vec![
+        header_of(&protocol, 11).hash(),
+        header_of(&protocol, 3).hash(),
+        missing.clone(),
+    ];
+    let content = packed::GetBlocksProof::new_builder()
+        .last_hash(last_header.hash())
+        .block_hashes(block_hashes.clone().pack())
+        .build();
+    let (status, sent) = request(&protocol, content);
+    assert!(status.is_ok(), "{}", status);
+    let proof = match &sent[..] {
+        [packed::LightClientMessageUnion::SendBlocksProof(proof)] => proof.clone(),
+        _ => panic!("unexpected messages"),
+    };
+    check_last_header(&proof.last_header(), &last_header);
+    let headers = proof
+        .headers()
+        .into_iter()
+        .map(|header| header.into_view())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        headers.iter().map(HeaderView::hash).collect::<Vec<_>>(),
+        block_hashes[..2].to_vec()
+    );
+    assert_eq!(
+        proof.missing_block_hashes().into_iter().collect::<Vec<_>>(),
+        vec![missing]
+    );
+    check_chain_root_proof(&proof.last_header(), proof.proof(), &headers);
+
+    // The last block is unknown, so the last state is sent instead.
+    let content = packed::GetBlocksProof::new_builder()
+        .last_hash(packed::Byte32::zero())
+        .block_hashes(block_hashes.pack())
+        .build();
+    let (status, sent) = request(&protocol, content);
+    assert!(status.is_ok(), "{}", status);
+    assert!(matches!(
+        &sent[..],
+        [packed::LightClientMessageUnion::SendLastState(_)]
+    ));
+}
+
+#[test]
+fn test_get_transactions_proof() {
+    let (protocol, _chain) = build_chain(20, 0);
+    let snapshot = protocol.snapshot();
+    let last_header = header_of(&protocol, 20);
+    let cellbase_of = |number| {
+        snapshot
+            .get_block(&snapshot.get_block_hash(number).unwrap())
+            .unwrap()
+            .transaction(0)
+            .unwrap()
+    };
+    let missing = packed::Byte32::zero();
+    let transactions = vec![cellbase_of(9), cellbase_of(4)];
+    let tx_hashes = vec![
+        transactions[0].hash(),
+        transactions[1].hash(),
+        missing.clone(),
+    ];
+    let content = packed::GetTransactionsProof::new_builder()
+        .last_hash(last_header.hash())
+        .tx_hashes(tx_hashes.pack())
+        .build();
+    let (status, sent) = request(&protocol, content);
+    assert!(status.is_ok(), "{}", status);
+    let proof = match &sent[..] {
+        [packed::LightClientMessageUnion::SendTransactionsProof(proof)] => proof.clone(),
+        _ => panic!("unexpected messages"),
+    };
+    check_last_header(&proof.last_header(), &last_header);
+    assert_eq!(
+        proof.missing_tx_hashes().into_iter().collect::<Vec<_>>(),
+        vec![missing]
+    );
+
+    // The filtered blocks are sorted by the block numbers.
+    let mut headers = Vec::new();
+    for (filtered_block, tx) in proof
+        .filtered_blocks()
+        .into_iter()
+        .zip(transactions.iter().rev())
+    {
+        let header = filtered_block.header().into_view();
+        assert_eq!(
+            filtered_block
+                .transactions()
+                .into_iter()
+                .map(|tx| tx.calc_tx_hash())
+                .collect::<Vec<_>>(),
+            vec![tx.hash()]
+        );
+        let merkle_proof = MerkleProof::new(
+            filtered_block.proof().indices().unpack(),
+            filtered_block.proof().lemmas().into_iter().collect(),
+        );
+        let raw_transactions_root = merkle_proof.root(&[tx.hash()]).unwrap();
+        assert_eq!(
+            merkle_root(&[raw_transactions_root, filtered_block.witnesses_root()]),
+            header.transactions_root()
+        );
+        headers.push(header);
+    }
+    assert_eq!(
+        headers.iter().map(HeaderView::number).collect::<Vec<_>>(),
+        vec![4, 9]
+    );
+    check_chain_root_proof(&proof.last_header(), proof.proof(), &headers);
+}
@@ -8,7 +8,7 @@ mod get_block_proposal_process;
 mod get_block_transactions_process;
 mod get_transactions_process;
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 mod transaction_hashes_process;
 mod transactions_process;
 
@@ -184,6 +184,8 @@ impl Relayer {
                 status
             );
             nc.ban_peer(peer, ban_time, status.to_string());
+        } else if let Some(behaviour) = status.behaviour() {
+            nc.report_peer(peer, behaviour);
         } else if status.should_warn() {
             warn_target!(
                 crate::LOG_TARGET_RELAY,
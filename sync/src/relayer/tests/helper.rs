@@ -229,6 +229,10 @@ impl MockProtocolContext {
             .borrow()
             .contains(&(protocol_id, peer_index, data))
     }
+
+    pub(crate) fn sent_messages(&self) -> Vec<(ProtocolId, PeerIndex, P2pBytes)> {
+        self.sent_messages.borrow().clone()
+    }
 }
 
 impl CKBProtocolContext for MockProtocolContext {
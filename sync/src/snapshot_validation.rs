@@ -0,0 +1,225 @@
+//! Validates the history below an imported snapshot in the background.
+//!
+//! A node bootstrapped from a snapshot syncs from the snapshot block at once, while the blocks
+//! below it are downloaded from the peers keeping the whole history and processed with the full
+//! verification by a separate chain service, in a separate database. The snapshot is valid once
+//! the separate chain reaches the snapshot block with the same live cell set.
+use crate::utils::is_internal_db_error;
+use crate::Status;
+use ckb_chain::chain::ChainController;
+use ckb_constant::sync::{BLOCK_DOWNLOAD_TIMEOUT, INIT_BLOCKS_IN_TRANSIT_PER_PEER};
+use ckb_logger::{error, info};
+use ckb_network::PeerIndex;
+use ckb_shared::Shared;
+use ckb_store::{ChainDB, ChainStore};
+use ckb_types::{
+    core::{BlockNumber, BlockView},
+    packed::Byte32,
+};
+use ckb_util::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// The maximum number of the blocks downloaded ahead of the next block to be processed.
+const VALIDATION_WINDOW: BlockNumber = 1024;
+
+/// The status of the background validation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotValidationStatus {
+    /// The history is being downloaded and verified.
+    Running,
+    /// The history is verified and results in the live cell set of the snapshot.
+    Validated,
+    /// The history is invalid, or results in a different live cell set.
+    Invalid,
+}
+
+struct ValidationState {
+    status: SnapshotValidationStatus,
+    /// The number of the next block to be processed.
+    next: BlockNumber,
+    /// The requested blocks, with the peer and the request time in milliseconds.
+    inflight: HashMap<Byte32, (PeerIndex, u64)>,
+    /// The downloaded blocks waiting for their parents.
+    received: BTreeMap<BlockNumber, Arc<BlockView>>,
+}
+
+/// Validates the history below an imported snapshot, see the module doc.
+pub struct SnapshotValidation {
+    /// The store of the node, which has all the headers below the snapshot block.
+    store: ChainDB,
+    number: BlockNumber,
+    hash: Byte32,
+    cells_hash: Byte32,
+    /// The separate chain replaying the history.
+    shared: Shared,
+    chain: ChainController,
+    state: Mutex<ValidationState>,
+}
+
+impl SnapshotValidation {
+    /// Resumes the validation of the snapshot imported into `store`, returns `None` if there is
+    /// no unvalidated snapshot.
+    ///
+    /// The history is replayed by the chain service `chain` of `shared`, whose database is only
+    /// used for the validation.
+    pub fn new(store: ChainDB, shared: Shared, chain: ChainController) -> Option<Self> {
+        let (hash, cells_hash) = store.get_unvalidated_snapshot()?;
+        let number = store
+            .get_block_number(&hash)
+            .expect("the snapshot block is in the main chain");
+        let next = shared.snapshot().tip_number() + 1;
+        let validation = SnapshotValidation {
+            store,
+            number,
+            hash,
+            cells_hash,
+            shared,
+            chain,
+            state: Mutex::new(ValidationState {
+                status: SnapshotValidationStatus::Running,
+                next,
+                inflight: HashMap::new(),
+                received: BTreeMap::new(),
+            }),
+        };
+        validation.process(&mut validation.state.lock());
+        Some(validation)
+    }
+
+    /// Returns the number of the snapshot block.
+    pub fn number(&self) -> BlockNumber {
+        self.number
+    }
+
+    /// Returns the status of the validation.
+    pub fn status(&self) -> SnapshotValidationStatus {
+        self.state.lock().status
+    }
+
+    /// Returns the hashes of the blocks to request from the peer, in ascending order.
+    ///
+    /// The requests timed out are assigned again.
+    pub fn blocks_to_fetch(&self, peer: PeerIndex, now: u64) -> Vec<Byte32> {
+        let mut state = self.state.lock();
+        if state.status != SnapshotValidationStatus::Running {
+            return Vec::new();
+        }
+        state
+            .inflight
+            .retain(|_, (_, time)| now < *time + BLOCK_DOWNLOAD_TIMEOUT);
+
+        let mut count = state.inflight.values().filter(|(p, _)| *p == peer).count();
+        let mut fetch = Vec::new();
+        let end = self.number.min(state.next + VALIDATION_WINDOW - 1);
+        for number in state.next..=end {
+            if count >= INIT_BLOCKS_IN_TRANSIT_PER_PEER {
+                break;
+            }
+            if state.received.contains_key(&number) {
+                continue;
+            }
+            let hash = self
+                .store
+                .get_block_hash(number)
+                .expect("the headers below the snapshot block are imported");
+            if state.inflight.contains_key(&hash) {
+                continue;
+            }
+            state.inflight.insert(hash.clone(), (peer, now));
+            fetch.push(hash);
+            count += 1;
+        }
+        fetch
+    }
+
+    /// Takes the block if it is in the main chain below the snapshot block, returns `None` to
+    /// leave the block to the synchronizer.
+    ///
+    /// The header of the block must have been reset with the roots calculated from the body, so a
+    /// block with the expected hash has the body committed in the trusted header.
+    pub fn accept(&self, block: &BlockView) -> Option<Status> {
+        let number = block.number();
+        let hash = block.hash();
+        if number > self.number || self.store.get_block_hash(number) != Some(hash.clone()) {
+            return None;
+        }
+        let mut state = self.state.lock();
+        if state.inflight.remove(&hash).is_none() {
+            // a late delivery of a timed out request is dropped
+            return Some(Status::ok());
+        }
+        state.received.insert(number, Arc::new(block.clone()));
+        self.process(&mut state);
+        Some(Status::ok())
+    }
+
+    /// Processes the consecutive downloaded blocks, and checks the live cell set at the snapshot
+    /// block.
+    fn process(&self, state: &mut ValidationState) {
+        while state.status == SnapshotValidationStatus::Running {
+            let block = match state.received.remove(&state.next) {
+                Some(block) => block,
+                None => break,
+            };
+            match self.chain.process_block(Arc::clone(&block)) {
+                Ok(_) => state.next += 1,
+                Err(err) if is_internal_db_error(&err) => {
+                    error!("snapshot validation failed to process block: {}", err);
+                    state.received.insert(block.number(), block);
+                    return;
+                }
+                Err(err) => {
+                    self.invalidate(
+                        state,
+                        format!(
+                            "block {} {} is invalid: {}",
+                            block.number(),
+                            block.hash(),
+                            err
+                        ),
+                    );
+                    return;
+                }
+            }
+        }
+        if state.status != SnapshotValidationStatus::Running || state.next <= self.number {
+            return;
+        }
+
+        if self.shared.store().calc_live_cells_hash() != self.cells_hash {
+            self.invalidate(
+                state,
+                "the history results in a different live cell set".into(),
+            );
+            return;
+        }
+        let txn = self.store.begin_transaction();
+        if let Err(err) = txn.delete_unvalidated_snapshot().and_then(|_| txn.commit()) {
+            error!("snapshot validation failed to clear the mark: {}", err);
+        }
+        info!(
+            "the history below the snapshot block {} {} is validated",
+            self.number, self.hash
+        );
+        state.status = SnapshotValidationStatus::Validated;
+    }
+
+    fn invalidate(&self, state: &mut ValidationState, reason: String) {
+        let txn = self.store.begin_transaction();
+        if let Err(err) = txn
+            .insert_invalid_snapshot(&self.hash)
+            .and_then(|_| txn.commit())
+        {
+            error!("snapshot validation failed to mark the snapshot: {}", err);
+        }
+        error!(
+            "the imported snapshot at block {} {} is invalid, {}, \
+             reset the data and sync from the genesis block",
+            self.number, self.hash, reason
+        );
+        state.status = SnapshotValidationStatus::Invalid;
+        state.inflight.clear();
+        state.received.clear();
+    }
+}
@@ -1,4 +1,5 @@
 use ckb_constant::sync::{BAD_MESSAGE_BAN_TIME, SYNC_USELESS_BAN_TIME};
+use ckb_network::Behaviour;
 use std::fmt::{self, Display, Formatter};
 use std::time::Duration;
 
@@ -111,6 +112,8 @@ pub enum StatusCode {
     ///////////////////////////////////
     //      Warning 5xx              //
     ///////////////////////////////////
+    /// Errors caused by the inconsistent local data
+    InternalError = 500,
     /// Errors returned from the tx-pool
     TxPool = 501,
     /// Errors returned from the network layer
@@ -157,7 +160,7 @@ impl Status {
 
     /// Whether should ban session
     pub fn should_ban(&self) -> Option<Duration> {
-        if !(400..500).contains(&(self.code as u16)) {
+        if !(400..500).contains(&(self.code as u16)) || self.behaviour().is_some() {
             return None;
         }
         match self.code {
@@ -166,6 +169,51 @@ impl Status {
         }
     }
 
+    /// The peer behaviour to report, which affects the score of the peer.
+    ///
+    /// Only the errors which an honest peer may make occasionally are reported, the peers
+    /// causing the other malformed errors are banned immediately, see `should_ban`.
+    pub fn behaviour(&self) -> Option<Behaviour> {
+        match self.code {
+            // An honest peer may request a block or a transaction again after a reconnection,
+            // or request the genesis block by mistake.
+            StatusCode::RequestGenesis | StatusCode::RequestDuplicate => {
+                Some(Behaviour::UnexpectedMessage)
+            }
+            StatusCode::OK
+            | StatusCode::Ignored
+            | StatusCode::CompactBlockIsAlreadyPending
+            | StatusCode::CompactBlockIsAlreadyInFlight
+            | StatusCode::CompactBlockAlreadyStored
+            | StatusCode::CompactBlockIsStaled
+            | StatusCode::CompactBlockRequiresParent
+            | StatusCode::CompactBlockRequiresFreshTransactions
+            | StatusCode::CompactBlockMeetsShortIdsCollision
+            | StatusCode::BlocksInFlightReachLimit
+            | StatusCode::TooManyRequests
+            | StatusCode::ProtocolMessageIsMalformed
+            | StatusCode::BlockIsInvalid
+            | StatusCode::CompactBlockHasInvalidHeader
+            | StatusCode::CompactBlockHasDuplicatedShortIds
+            | StatusCode::CompactBlockHasNotPrefilledCellbase
+            | StatusCode::CompactBlockHasDuplicatedPrefilledTransactions
+            | StatusCode::CompactBlockHasOutOfOrderPrefilledTransactions
+            | StatusCode::CompactBlockHasOutOfIndexPrefilledTransactions
+            | StatusCode::CompactBlockHasInvalidUncle
+            | StatusCode::CompactBlockHasUnmatchedTransactionRootWithReconstructedBlock
+            | StatusCode::BlockTransactionsLengthIsUnmatchedWithPendingCompactBlock
+            | StatusCode::BlockTransactionsShortIdsAreUnmatchedWithPendingCompactBlock
+            | StatusCode::BlockUnclesLengthIsUnmatchedWithPendingCompactBlock
+            | StatusCode::BlockUnclesAreUnmatchedWithPendingCompactBlock
+            | StatusCode::GetHeadersMissCommonAncestors
+            | StatusCode::HeadersIsInvalid
+            | StatusCode::TooManyUnknownTransactions
+            | StatusCode::InternalError
+            | StatusCode::TxPool
+            | StatusCode::Network => None,
+        }
+    }
+
     /// Whether should output a warning log
     pub fn should_warn(&self) -> bool {
         self.code as u16 >= 500
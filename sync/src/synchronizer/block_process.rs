@@ -1,12 +1,13 @@
 use crate::{synchronizer::Synchronizer, utils::is_internal_db_error, Status, StatusCode};
 use ckb_logger::debug;
-use ckb_network::PeerIndex;
+use ckb_network::{Behaviour, CKBProtocolContext, PeerIndex};
 use ckb_types::{packed, prelude::*};
 
 pub struct BlockProcess<'a> {
     message: packed::SendBlockReader<'a>,
     synchronizer: &'a Synchronizer,
-    _peer: PeerIndex,
+    peer: PeerIndex,
+    nc: &'a dyn CKBProtocolContext,
 }
 
 impl<'a> BlockProcess<'a> {
@@ -14,11 +15,13 @@ impl<'a> BlockProcess<'a> {
         message: packed::SendBlockReader<'a>,
         synchronizer: &'a Synchronizer,
         peer: PeerIndex,
+        nc: &'a dyn CKBProtocolContext,
     ) -> Self {
         BlockProcess {
             message,
             synchronizer,
-            _peer: peer,
+            peer,
+            nc,
         }
     }
 
@@ -29,10 +32,15 @@ impl<'a> BlockProcess<'a> {
             block.number(),
             block.hash(),
         );
+        if let Some(ref validation) = self.synchronizer.snapshot_validation {
+            if let Some(status) = validation.accept(&block) {
+                return status;
+            }
+        }
         let shared = self.synchronizer.shared();
         let state = shared.state();
 
-        if state.new_block_received(&block) {
+        if state.new_block_received(&block, self.peer) {
             if let Err(err) = self.synchronizer.process_new_block(block.clone()) {
                 if !is_internal_db_error(&err) {
                     return StatusCode::BlockIsInvalid.with_context(format!(
@@ -41,7 +49,16 @@ impl<'a> BlockProcess<'a> {
                         err,
                     ));
                 }
+            } else {
+                self.nc.report_peer(self.peer, Behaviour::UsefulBlock);
             }
+        } else if !state
+            .write_inflight_blocks()
+            .remove_released(self.peer, &(block.number(), block.hash()).into())
+        {
+            // the block has never been requested from this peer, a late delivery of a timed out
+            // or reassigned request is not penalized
+            self.nc.report_peer(self.peer, Behaviour::UnexpectedMessage);
         }
 
         Status::ok()
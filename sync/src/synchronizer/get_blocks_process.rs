@@ -76,6 +76,12 @@ impl<'a> GetBlocksProcess<'a> {
                 let message = packed::SyncMessage::new_builder().set(content).build();
 
                 attempt!(send_message_to(self.nc, self.peer, &message));
+            } else if active_chain.is_pruned(&block_hash) {
+                debug!(
+                    "getblocks stopping since {} is pruned, peer={}",
+                    block_hash, self.peer
+                );
+                break;
             } else {
                 // TODO response not found
                 // TODO add timeout check in synchronizer
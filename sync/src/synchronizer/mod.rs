@@ -13,6 +13,7 @@ pub(crate) use self::headers_process::HeadersProcess;
 pub(crate) use self::in_ibd_process::InIBDProcess;
 
 use crate::block_status::BlockStatus;
+use crate::snapshot_validation::{SnapshotValidation, SnapshotValidationStatus};
 use crate::types::{HeaderView, HeadersSyncController, IBDState, PeerFlags, Peers, SyncShared};
 use crate::utils::send_message_to;
 use crate::{Status, StatusCode};
@@ -27,7 +28,7 @@ use ckb_error::Error as CKBError;
 use ckb_logger::{debug, error, info, trace, warn};
 use ckb_metrics::metrics;
 use ckb_network::{
-    bytes::Bytes, CKBProtocolContext, CKBProtocolHandler, PeerIndex, ServiceControl,
+    bytes::Bytes, Behaviour, CKBProtocolContext, CKBProtocolHandler, PeerIndex, ServiceControl,
     SupportProtocols,
 };
 use ckb_types::{
@@ -46,6 +47,7 @@ pub const SEND_GET_HEADERS_TOKEN: u64 = 0;
 pub const IBD_BLOCK_FETCH_TOKEN: u64 = 1;
 pub const NOT_IBD_BLOCK_FETCH_TOKEN: u64 = 2;
 pub const TIMEOUT_EVICTION_TOKEN: u64 = 3;
+pub const SNAPSHOT_VALIDATION_TOKEN: u64 = 4;
 pub const NO_PEER_CHECK_TOKEN: u64 = 255;
 
 const SYNC_NOTIFY_INTERVAL: Duration = Duration::from_secs(1);
@@ -208,6 +210,7 @@ pub struct Synchronizer {
     /// Sync shared state
     pub shared: Arc<SyncShared>,
     fetch_channel: Option<channel::Sender<FetchCMD>>,
+    pub(crate) snapshot_validation: Option<Arc<SnapshotValidation>>,
 }
 
 impl Synchronizer {
@@ -219,9 +222,16 @@ impl Synchronizer {
             chain,
             shared,
             fetch_channel: None,
+            snapshot_validation: None,
         }
     }
 
+    /// Validates the history below the imported snapshot in the background
+    pub fn snapshot_validation(mut self, validation: Option<Arc<SnapshotValidation>>) -> Self {
+        self.snapshot_validation = validation;
+        self
+    }
+
     /// Get shared state
     pub fn shared(&self) -> &Arc<SyncShared> {
         &self.shared
@@ -245,7 +255,7 @@ impl Synchronizer {
             }
             packed::SyncMessageUnionReader::SendBlock(reader) => {
                 if reader.check_data() {
-                    BlockProcess::new(reader, self, peer).execute()
+                    BlockProcess::new(reader, self, peer, nc).execute()
                 } else {
                     StatusCode::ProtocolMessageIsMalformed.with_context("SendBlock is invalid")
                 }
@@ -281,6 +291,8 @@ impl Synchronizer {
                 item_name, peer, ban_time, status
             );
             nc.ban_peer(peer, ban_time, status.to_string());
+        } else if let Some(behaviour) = status.behaviour() {
+            nc.report_peer(peer, behaviour);
         } else if status.should_warn() {
             warn!("receive {} from {}, {}", item_name, peer, status);
         } else if !status.is_ok() {
@@ -343,16 +355,20 @@ impl Synchronizer {
 
     pub(crate) fn on_connected(&self, nc: &dyn CKBProtocolContext, peer: PeerIndex) {
         let pid = SupportProtocols::Sync.protocol_id();
-        let (is_outbound, is_whitelist, is_2021edition) = nc
+        let (is_outbound, is_whitelist, is_2021edition, is_pruned) = nc
             .get_peer(peer)
             .map(|peer| {
                 (
                     peer.is_outbound(),
                     peer.is_whitelist,
                     peer.protocols.get(&pid).map(|v| v == "2").unwrap_or(false),
+                    peer.identify_info
+                        .as_ref()
+                        .map(|info| info.pruned)
+                        .unwrap_or(false),
                 )
             })
-            .unwrap_or((false, false, false));
+            .unwrap_or((false, false, false, false));
 
         let sync_state = self.shared().state();
         let protect_outbound = is_outbound
@@ -374,6 +390,7 @@ impl Synchronizer {
                 is_whitelist,
                 is_protect: protect_outbound,
                 is_2021edition,
+                is_pruned,
             },
         );
     }
@@ -467,6 +484,7 @@ impl Synchronizer {
         }
         for peer in eviction {
             info!("timeout eviction peer={}", peer);
+            nc.report_peer(peer, Behaviour::Timeout);
             if let Err(err) = nc.disconnect(peer, "sync timeout eviction") {
                 debug!("synchronizer disconnect error: {:?}", err);
             }
@@ -539,10 +557,12 @@ impl Synchronizer {
                     return false;
                 };
                 match ibd {
+                    // the pruned peers cannot serve the old blocks
                     IBDState::In => {
-                        state.peer_flags.is_outbound
-                            || state.peer_flags.is_whitelist
-                            || state.peer_flags.is_protect
+                        !state.peer_flags.is_pruned
+                            && (state.peer_flags.is_outbound
+                                || state.peer_flags.is_whitelist
+                                || state.peer_flags.is_protect)
                     }
                     IBDState::Out => state.started_or_tip_synced(),
                 }
@@ -564,6 +584,13 @@ impl Synchronizer {
 
         let disconnect_list = {
             let mut list = self.shared().state().write_inflight_blocks().prune(tip);
+            // these peers did not deliver the requested blocks in time, the peers which have
+            // already disconnected are not reported
+            for peer in list.iter() {
+                if self.shared().state().peers().state.contains_key(peer) {
+                    nc.report_peer(*peer, Behaviour::Timeout);
+                }
+            }
             if let IBDState::In = ibd {
                 // best known < tip and in IBD state, and unknown list is empty,
                 // these node can be disconnect
@@ -642,6 +669,34 @@ impl Synchronizer {
         }
     }
 
+    fn fetch_snapshot_history(&self, nc: &dyn CKBProtocolContext) {
+        let validation = match self.snapshot_validation {
+            Some(ref validation) => validation,
+            None => return,
+        };
+        if validation.status() != SnapshotValidationStatus::Running {
+            if nc.remove_notify(SNAPSHOT_VALIDATION_TOKEN).is_err() {
+                trace!("remove snapshot validation fail");
+            }
+            return;
+        }
+        let now = unix_time_as_millis();
+        // the pruned peers cannot serve the old blocks
+        let peers: Vec<PeerIndex> = self
+            .peers()
+            .state
+            .iter()
+            .filter(|kv_pair| !kv_pair.value().peer_flags.is_pruned)
+            .map(|kv_pair| *kv_pair.key())
+            .collect();
+        for peer in peers {
+            let fetch = validation.blocks_to_fetch(peer, now);
+            if !fetch.is_empty() {
+                self.send_getblocks(fetch, nc, peer);
+            }
+        }
+    }
+
     fn send_getblocks(
         &self,
         v_fetch: Vec<packed::Byte32>,
@@ -671,6 +726,10 @@ impl CKBProtocolHandler for Synchronizer {
             .expect("set_notify at init is ok");
         nc.set_notify(Duration::from_secs(2), NO_PEER_CHECK_TOKEN)
             .expect("set_notify at init is ok");
+        if self.snapshot_validation.is_some() {
+            nc.set_notify(SYNC_NOTIFY_INTERVAL, SNAPSHOT_VALIDATION_TOKEN)
+                .expect("set_notify at init is ok");
+        }
     }
 
     fn received(
@@ -823,6 +882,9 @@ impl CKBProtocolHandler for Synchronizer {
                 TIMEOUT_EVICTION_TOKEN => {
                     self.eviction(nc.as_ref());
                 }
+                SNAPSHOT_VALIDATION_TOKEN => {
+                    self.fetch_snapshot_history(nc.as_ref());
+                }
                 // Here is just for NO_PEER_CHECK_TOKEN token, only handle it when there is no peer.
                 _ => {}
             }
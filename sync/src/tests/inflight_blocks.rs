@@ -1,6 +1,9 @@
 use crate::types::{BlockNumberAndHash, InflightBlocks};
-use ckb_constant::sync::BLOCK_DOWNLOAD_TIMEOUT;
+use ckb_constant::sync::{
+    BLOCK_DOWNLOAD_TIMEOUT, MAX_STALLED_BLOCKS_TO_REASSIGN, MIN_BLOCK_STALL_TIMEOUT,
+};
 use ckb_types::h256;
+use ckb_types::packed::Byte32;
 use ckb_types::prelude::*;
 use std::collections::HashSet;
 
@@ -138,6 +141,17 @@ fn inflight_blocks_timeout() {
             .map(|state| state.peer),
         Some(4.into())
     );
+
+    // the late delivery of a timed out request is not unexpected, but only once
+    assert!(inflight_blocks.remove_released(1.into(), &(1, h256!("0x1").pack()).into()));
+    assert!(!inflight_blocks.remove_released(1.into(), &(1, h256!("0x1").pack()).into()));
+    assert!(!inflight_blocks.remove_released(2.into(), &(1, h256!("0x1").pack()).into()));
+    assert!(inflight_blocks.remove_released(2.into(), &(3, h256!("0x3").pack()).into()));
+
+    // the released requests are forgotten once the tip passes the blocks
+    inflight_blocks.prune(4);
+    assert!(!inflight_blocks.remove_released(1.into(), &(4, h256!("0x4").pack()).into()));
+    assert!(inflight_blocks.remove_released(2.into(), &(5, h256!("0x5").pack()).into()));
 }
 
 #[cfg(not(disable_faketime))]
@@ -197,3 +211,131 @@ fn inflight_trace_number_state() {
     assert_eq!(inflight_blocks.peer_can_fetch_count(3.into()), 32 >> 1);
     assert_eq!(inflight_blocks.peer_can_fetch_count(4.into()), 32 >> 1);
 }
+
+#[cfg(not(disable_faketime))]
+#[test]
+fn inflight_blocks_stall() {
+    let faketime_file = faketime::millis_tempfile(0).expect("create faketime file");
+    faketime::enable(&faketime_file);
+    let mut inflight_blocks = InflightBlocks::default();
+
+    assert!(inflight_blocks.insert(1.into(), (1, h256!("0x1").pack()).into()));
+    assert!(inflight_blocks.insert(1.into(), (2, h256!("0x2").pack()).into()));
+    assert!(inflight_blocks.insert(2.into(), (3, h256!("0x3").pack()).into()));
+
+    // peer 1 responds in 100ms
+    faketime::write_millis(&faketime_file, 100).expect("write millis");
+    assert!(inflight_blocks.remove_by_block((2, h256!("0x2").pack()).into()));
+
+    faketime::write_millis(&faketime_file, MIN_BLOCK_STALL_TIMEOUT + 1).expect("write millis");
+    let peers = inflight_blocks.prune(0);
+    assert!(peers.is_empty());
+
+    // the block stalled on peer 1 is reassigned, while peer 2 has no measured latency yet
+    assert!(inflight_blocks
+        .inflight_state_by_block(&(1, h256!("0x1").pack()).into())
+        .is_none());
+    assert!(inflight_blocks.insert(2.into(), (1, h256!("0x1").pack()).into()));
+    assert_eq!(
+        inflight_blocks
+            .inflight_state_by_block(&(3, h256!("0x3").pack()).into())
+            .map(|state| state.peer),
+        Some(2.into())
+    );
+
+    let stats = inflight_blocks
+        .download_stats()
+        .into_iter()
+        .find(|stats| stats.peer == 1.into())
+        .expect("peer 1 stats");
+    assert_eq!(stats.latency, 100);
+    assert_eq!(stats.received_count, 1);
+    assert_eq!(stats.stalled_count, 1);
+    assert_eq!(stats.inflight_count, 0);
+    // increased by 2 for the fast response, then halved for the stalled one
+    assert_eq!(stats.task_count, (32 + 2) >> 1);
+}
+
+#[cfg(not(disable_faketime))]
+#[test]
+fn inflight_blocks_stall_lowest_only() {
+    let faketime_file = faketime::millis_tempfile(0).expect("create faketime file");
+    faketime::enable(&faketime_file);
+    let mut inflight_blocks = InflightBlocks::default();
+    let block =
+        |number: u64| -> BlockNumberAndHash { (number, Byte32::new([number as u8; 32])).into() };
+
+    for number in 1..=21 {
+        assert!(inflight_blocks.insert(1.into(), block(number)));
+    }
+    assert!(inflight_blocks.insert(2.into(), block(30)));
+
+    // peer 1 responds in 100ms
+    faketime::write_millis(&faketime_file, 100).expect("write millis");
+    assert!(inflight_blocks.remove_by_block(block(1)));
+
+    faketime::write_millis(&faketime_file, MIN_BLOCK_STALL_TIMEOUT + 1).expect("write millis");
+    assert!(inflight_blocks.prune(0).is_empty());
+
+    // only the lowest in-flight blocks are reassigned
+    let reassigned = 2..2 + MAX_STALLED_BLOCKS_TO_REASSIGN as u64;
+    for number in 2..=21 {
+        let peer = inflight_blocks
+            .inflight_state_by_block(&block(number))
+            .map(|state| state.peer);
+        if reassigned.contains(&number) {
+            assert_eq!(peer, None);
+        } else {
+            assert_eq!(peer, Some(1.into()));
+        }
+    }
+
+    let stats = inflight_blocks
+        .download_stats()
+        .into_iter()
+        .find(|stats| stats.peer == 1.into())
+        .expect("peer 1 stats");
+    assert_eq!(stats.stalled_count, MAX_STALLED_BLOCKS_TO_REASSIGN as u64);
+    // halved only once in a round
+    assert_eq!(stats.task_count, (32 + 2) >> 1);
+}
+
+#[cfg(not(disable_faketime))]
+#[test]
+fn inflight_blocks_late_delivery() {
+    let faketime_file = faketime::millis_tempfile(0).expect("create faketime file");
+    faketime::enable(&faketime_file);
+    let mut inflight_blocks = InflightBlocks::default();
+    let block1: BlockNumberAndHash = (1, h256!("0x1").pack()).into();
+
+    assert!(inflight_blocks.insert(1.into(), block1.clone()));
+    assert!(inflight_blocks.insert(1.into(), (2, h256!("0x2").pack()).into()));
+    assert!(inflight_blocks.insert(2.into(), (3, h256!("0x3").pack()).into()));
+
+    faketime::write_millis(&faketime_file, 100).expect("write millis");
+    assert!(inflight_blocks.remove_delivered(1.into(), (2, h256!("0x2").pack()).into()));
+
+    // the request stalled on peer 1 is reassigned to peer 2
+    faketime::write_millis(&faketime_file, MIN_BLOCK_STALL_TIMEOUT + 1).expect("write millis");
+    assert!(inflight_blocks.prune(0).is_empty());
+    assert!(inflight_blocks.insert(2.into(), block1.clone()));
+
+    // peer 1 delivers the block late, which is still new
+    assert!(inflight_blocks.remove_delivered(1.into(), block1.clone()));
+    assert!(inflight_blocks.inflight_state_by_block(&block1).is_none());
+    assert_eq!(inflight_blocks.peer_inflight_count(2.into()), 1);
+
+    // then peer 2 delivers it as requested, neither delivery is unexpected
+    assert!(!inflight_blocks.remove_delivered(2.into(), block1.clone()));
+    assert!(inflight_blocks.remove_released(2.into(), &block1));
+    assert!(inflight_blocks.remove_released(1.into(), &block1));
+    assert!(!inflight_blocks.remove_released(1.into(), &block1));
+
+    // peer 2 is not credited with the response delivered by peer 1
+    let stats = inflight_blocks
+        .download_stats()
+        .into_iter()
+        .find(|stats| stats.peer == 2.into())
+        .expect("peer 2 stats");
+    assert_eq!(stats.received_count, 0);
+}
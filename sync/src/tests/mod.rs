@@ -15,11 +15,12 @@ mod block_status;
 mod inflight_blocks;
 mod net_time_checker;
 mod orphan_block_pool;
+mod snapshot_validation;
 mod sync_shared;
 #[cfg(not(disable_faketime))]
 mod synchronizer;
 mod types;
-mod util;
+pub(crate) mod util;
 
 const DEFAULT_CHANNEL: usize = 128;
 
@@ -0,0 +1,117 @@
+use crate::tests::util::{build_chain, generate_blocks};
+use crate::{SnapshotValidation, SnapshotValidationStatus, Status};
+use ckb_chain::chain::{ChainController, ChainService};
+use ckb_constant::sync::BLOCK_DOWNLOAD_TIMEOUT;
+use ckb_launcher::SharedBuilder;
+use ckb_shared::Shared;
+use ckb_store::ChainStore;
+use ckb_types::{core::BlockView, packed::Byte32, prelude::*};
+
+// Marks the tip of the source chain as an imported snapshot, and replays its history in a fresh
+// database.
+fn start_validation(source: &Shared, cells_hash: &Byte32) -> SnapshotValidation {
+    let tip = source.snapshot().tip_header().clone();
+    let txn = source.store().begin_transaction();
+    txn.insert_unvalidated_snapshot(&tip.hash(), cells_hash)
+        .unwrap();
+    txn.commit().unwrap();
+
+    let (shared, mut pack) = SharedBuilder::with_temp_db()
+        .consensus(source.consensus().clone())
+        .build()
+        .unwrap();
+    let chain = ChainService::new(shared.clone(), pack.take_proposal_table()).start::<&str>(None);
+    SnapshotValidation::new(source.store().clone(), shared, chain).unwrap()
+}
+
+fn get_blocks(source: &Shared, hashes: &[Byte32]) -> Vec<BlockView> {
+    hashes
+        .iter()
+        .map(|hash| source.store().get_block(hash).unwrap())
+        .collect()
+}
+
+fn build_source_chain() -> (Shared, ChainController) {
+    let (sync_shared, chain) = build_chain(20);
+    (sync_shared.shared().clone(), chain)
+}
+
+#[test]
+fn test_snapshot_validation() {
+    let (source, chain) = build_source_chain();
+    let cells_hash = source.store().calc_live_cells_hash();
+    let validation = start_validation(&source, &cells_hash);
+    let (peer1, peer2) = (1.into(), 2.into());
+
+    let fetch = validation.blocks_to_fetch(peer1, 0);
+    assert_eq!(fetch.len(), 20);
+    assert!(validation.blocks_to_fetch(peer2, 0).is_empty());
+    // the timed out requests are assigned again
+    assert_eq!(
+        validation.blocks_to_fetch(peer2, BLOCK_DOWNLOAD_TIMEOUT),
+        fetch
+    );
+
+    // the blocks above the snapshot block are left to the synchronizer
+    generate_blocks(&source, &chain, 21);
+    let above = source.snapshot().tip_header().hash();
+    assert!(validation
+        .accept(&source.store().get_block(&above).unwrap())
+        .is_none());
+
+    // the blocks arrive in the reverse order
+    for block in get_blocks(&source, &fetch).iter().rev() {
+        assert_eq!(validation.status(), SnapshotValidationStatus::Running);
+        assert_eq!(validation.accept(block), Some(Status::ok()));
+    }
+    assert_eq!(validation.status(), SnapshotValidationStatus::Validated);
+    assert!(source.store().get_unvalidated_snapshot().is_none());
+    assert!(source.store().get_invalid_snapshot().is_none());
+    assert!(validation.blocks_to_fetch(peer1, 0).is_empty());
+}
+
+#[test]
+fn test_snapshot_validation_with_different_cells() {
+    let (source, _chain) = build_source_chain();
+    let validation = start_validation(&source, &Byte32::zero());
+
+    let fetch = validation.blocks_to_fetch(1.into(), 0);
+    for block in get_blocks(&source, &fetch) {
+        validation.accept(&block);
+    }
+    assert_eq!(validation.status(), SnapshotValidationStatus::Invalid);
+    assert_eq!(
+        source.store().get_invalid_snapshot(),
+        Some(source.snapshot().tip_header().hash())
+    );
+}
+
+#[test]
+fn test_snapshot_validation_with_mismatched_body() {
+    let (source, _chain) = build_source_chain();
+    let cells_hash = source.store().calc_live_cells_hash();
+    let validation = start_validation(&source, &cells_hash);
+
+    let fetch = validation.blocks_to_fetch(1.into(), 0);
+    let block = source.store().get_block(&fetch[0]).unwrap();
+    // the roots in the header are reset from the body, as `BlockProcess` does
+    let mismatched = block
+        .data()
+        .as_builder()
+        .transactions(Default::default())
+        .build()
+        .into_view();
+    assert!(validation.accept(&mismatched).is_none());
+    assert_eq!(validation.status(), SnapshotValidationStatus::Running);
+    assert_eq!(
+        validation.blocks_to_fetch(2.into(), BLOCK_DOWNLOAD_TIMEOUT),
+        fetch
+    );
+
+    // a duplicated delivery is dropped
+    assert_eq!(validation.accept(&block), Some(Status::ok()));
+    assert_eq!(validation.accept(&block), Some(Status::ok()));
+    assert!(validation
+        .blocks_to_fetch(3.into(), BLOCK_DOWNLOAD_TIMEOUT)
+        .is_empty());
+}
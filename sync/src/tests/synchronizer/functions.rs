@@ -595,7 +595,7 @@ fn test_sync_process() {
     for block in &fetched_blocks {
         let block = SendBlockBuilder::default().block(block.data()).build();
         assert_eq!(
-            BlockProcess::new(block.as_reader(), &synchronizer1, peer1).execute(),
+            BlockProcess::new(block.as_reader(), &synchronizer1, peer1, &mock_nc).execute(),
             Status::ok(),
         );
     }
@@ -7,10 +7,12 @@ use ckb_chain::chain::ChainController;
 use ckb_chain_spec::consensus::Consensus;
 use ckb_channel::Receiver;
 use ckb_constant::sync::{
-    BLOCK_DOWNLOAD_TIMEOUT, HEADERS_DOWNLOAD_HEADERS_PER_SECOND, HEADERS_DOWNLOAD_INSPECT_WINDOW,
+    BLOCK_DOWNLOAD_TIMEOUT, BLOCK_STALL_LATENCY_FACTOR, BLOCK_THROUGHPUT_PERIOD,
+    HEADERS_DOWNLOAD_HEADERS_PER_SECOND, HEADERS_DOWNLOAD_INSPECT_WINDOW,
     HEADERS_DOWNLOAD_TOLERABLE_BIAS_FOR_SINGLE_SAMPLE, INIT_BLOCKS_IN_TRANSIT_PER_PEER,
     MAX_BLOCKS_IN_TRANSIT_PER_PEER, MAX_HEADERS_LEN, MAX_OUTBOUND_PEERS_TO_PROTECT_FROM_DISCONNECT,
-    MAX_UNKNOWN_TX_HASHES_SIZE, MAX_UNKNOWN_TX_HASHES_SIZE_PER_PEER, POW_INTERVAL,
+    MAX_STALLED_BLOCKS_TO_REASSIGN, MAX_UNKNOWN_TX_HASHES_SIZE,
+    MAX_UNKNOWN_TX_HASHES_SIZE_PER_PEER, MIN_BLOCK_STALL_TIMEOUT, POW_INTERVAL,
     RETRY_ASK_TX_TIMEOUT_INCREASE, SUSPEND_SYNC_TIME,
 };
 use ckb_error::Error as CKBError;
@@ -141,6 +143,7 @@ pub struct PeerFlags {
     pub is_protect: bool,
     pub is_whitelist: bool,
     pub is_2021edition: bool,
+    pub is_pruned: bool,
 }
 
 #[derive(Clone, Default, Debug, Copy)]
@@ -472,11 +475,24 @@ impl TimeAnalyzer {
     }
 }
 
+/// The block download state of a peer.
+///
+/// The number of blocks in transit adapts to the measured response time of the peer: it grows
+/// additively on the fast responses and shrinks multiplicatively on the slow or the stalled ones.
 #[derive(Debug, Clone)]
 pub struct DownloadScheduler {
     task_count: usize,
     timeout_count: usize,
     hashes: HashSet<BlockNumberAndHash>,
+    // The moving average of the response time, in milliseconds.
+    latency: u64,
+    // The moving average of the blocks received per minute.
+    throughput: u64,
+    period_start: u64,
+    period_received: u64,
+    received_count: u64,
+    stalled_count: u64,
+    timeout_blocks_count: u64,
 }
 
 impl Default for DownloadScheduler {
@@ -485,6 +501,13 @@ impl Default for DownloadScheduler {
             hashes: HashSet::default(),
             task_count: INIT_BLOCKS_IN_TRANSIT_PER_PEER,
             timeout_count: 0,
+            latency: 0,
+            throughput: 0,
+            period_start: unix_time_as_millis(),
+            period_received: 0,
+            received_count: 0,
+            stalled_count: 0,
+            timeout_blocks_count: 0,
         }
     }
 }
@@ -512,7 +535,7 @@ impl DownloadScheduler {
     }
 
     fn decrease(&mut self, num: usize) {
-        self.timeout_count = self.task_count.saturating_add(num);
+        self.timeout_count = self.timeout_count.saturating_add(num);
         if self.timeout_count > 2 {
             self.task_count = self.task_count.saturating_sub(1);
             self.timeout_count = 0;
@@ -522,6 +545,86 @@ impl DownloadScheduler {
     fn punish(&mut self, exp: usize) {
         self.task_count >>= exp
     }
+
+    // Unlike `punish`, keeps at least one task, so the peer is not disconnected for being slow.
+    fn halve(&mut self) {
+        self.task_count = ::std::cmp::max(self.task_count >> 1, 1)
+    }
+
+    fn on_received(&mut self, elapsed: u64, now: u64) {
+        self.latency = if self.received_count == 0 {
+            elapsed
+        } else {
+            (self.latency.saturating_mul(7).saturating_add(elapsed)) >> 3
+        };
+        self.received_count += 1;
+        self.period_received += 1;
+        self.update_throughput(now);
+    }
+
+    fn update_throughput(&mut self, now: u64) {
+        let period = now.saturating_sub(self.period_start);
+        if period < BLOCK_THROUGHPUT_PERIOD {
+            return;
+        }
+        // Idle peers keep the last measurement.
+        if self.period_received > 0 || !self.hashes.is_empty() {
+            let throughput = self.period_received.saturating_mul(60 * 1000) / period;
+            self.throughput = if self.received_count == self.period_received {
+                throughput
+            } else {
+                (self.throughput.saturating_add(throughput)) >> 1
+            };
+        }
+        self.period_start = now;
+        self.period_received = 0;
+    }
+
+    /// The time before a block request is reassigned to the other peers.
+    fn stall_timeout(&self) -> u64 {
+        if self.received_count == 0 {
+            BLOCK_DOWNLOAD_TIMEOUT
+        } else {
+            self.latency
+                .saturating_mul(BLOCK_STALL_LATENCY_FACTOR)
+                .max(MIN_BLOCK_STALL_TIMEOUT)
+                .min(BLOCK_DOWNLOAD_TIMEOUT)
+        }
+    }
+
+    fn stats(&self, peer: PeerIndex) -> PeerDownloadStats {
+        PeerDownloadStats {
+            peer,
+            inflight_count: self.inflight_count(),
+            task_count: self.task_count,
+            latency: self.latency,
+            throughput: self.throughput,
+            received_count: self.received_count,
+            stalled_count: self.stalled_count,
+            timeout_count: self.timeout_blocks_count,
+        }
+    }
+}
+
+/// The block download statistics of a peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerDownloadStats {
+    /// The peer.
+    pub peer: PeerIndex,
+    /// The count of the blocks in transit.
+    pub inflight_count: usize,
+    /// The max count of the blocks in transit.
+    pub task_count: usize,
+    /// The moving average of the response time, in milliseconds.
+    pub latency: u64,
+    /// The moving average of the blocks received per minute.
+    pub throughput: u64,
+    /// The count of the received blocks.
+    pub received_count: u64,
+    /// The count of the block requests reassigned to the other peers because they stalled.
+    pub stalled_count: u64,
+    /// The count of the block requests which timed out.
+    pub timeout_count: u64,
 }
 
 #[derive(Clone)]
@@ -533,6 +636,10 @@ pub struct InflightBlocks {
     time_analyzer: TimeAnalyzer,
     pub(crate) adjustment: bool,
     pub(crate) protect_num: usize,
+    // The peers which the timed out or reassigned requests were sent to, or whose requested
+    // block has been delivered by another peer, so a late delivery of the requested block is not
+    // taken as an unexpected message. The blocks not higher than the tip are removed in `prune`.
+    released: HashMap<BlockNumberAndHash, HashSet<PeerIndex>>,
 }
 
 impl Default for InflightBlocks {
@@ -541,6 +648,7 @@ impl Default for InflightBlocks {
             download_schedulers: HashMap::default(),
             inflight_states: BTreeMap::default(),
             trace_number: HashMap::default(),
+            released: HashMap::default(),
             restart_number: 0,
             time_analyzer: TimeAnalyzer::default(),
             adjustment: true,
@@ -596,6 +704,14 @@ impl InflightBlocks {
         )
     }
 
+    /// Returns the block download statistics of each peer.
+    pub fn download_stats(&self) -> Vec<PeerDownloadStats> {
+        self.download_schedulers
+            .iter()
+            .map(|(peer, scheduler)| scheduler.stats(*peer))
+            .collect()
+    }
+
     pub fn peer_inflight_count(&self, peer: PeerIndex) -> usize {
         self.download_schedulers
             .get(&peer)
@@ -640,25 +756,32 @@ impl InflightBlocks {
         // In order to protect the decentralization of the network and ensure the survival of low-performance
         // nodes, the penalty mechanism will be closed when the number of download nodes is less than the number of protected nodes
         let should_punish = self.download_schedulers.len() > self.protect_num;
+        // The stalled requests are reassigned only if there are other peers to take them.
+        let should_reassign = self.download_schedulers.len() > 1;
         let adjustment = self.adjustment;
 
         let trace = &mut self.trace_number;
         let download_schedulers = &mut self.download_schedulers;
         let states = &mut self.inflight_states;
+        let released = &mut self.released;
+        released.retain(|key, _| key.number > tip);
 
         let mut remove_key = Vec::new();
+        // the peers whose task count has been halved for the stalled requests in this round
+        let mut stalled_peers = HashSet::new();
         // Since this is a btreemap, with the data already sorted,
         // we don't have to worry about missing points, and we don't need to
         // iterate through all the data each time, just check within tip + 20,
         // with the checkpoint marking possible blocking points, it's enough
         let end = tip + 20;
-        for (key, value) in states.iter() {
+        for (index, (key, value)) in states.iter().enumerate() {
             if key.number > end {
                 break;
             }
             if value.timestamp + BLOCK_DOWNLOAD_TIMEOUT < now {
                 if let Some(set) = download_schedulers.get_mut(&value.peer) {
                     set.hashes.remove(key);
+                    set.timeout_blocks_count += 1;
                     if should_punish && adjustment {
                         set.punish(2);
                     }
@@ -666,7 +789,27 @@ impl InflightBlocks {
                 if !trace.is_empty() {
                     trace.remove(key);
                 }
+                released.entry(key.clone()).or_default().insert(value.peer);
                 remove_key.push(key.clone());
+            } else if should_reassign && index < MAX_STALLED_BLOCKS_TO_REASSIGN {
+                // The lowest in-flight blocks block the others from being verified, so a request
+                // which takes much longer than the usual response time of the peer is reassigned
+                // before the download timeout. The peer is released, a late delivery of the block
+                // is not penalized, see `remove_released`.
+                if let Some(set) = download_schedulers.get_mut(&value.peer) {
+                    if value.timestamp + set.stall_timeout() < now {
+                        set.hashes.remove(key);
+                        set.stalled_count += 1;
+                        if adjustment && stalled_peers.insert(value.peer) {
+                            set.halve();
+                        }
+                        if !trace.is_empty() {
+                            trace.remove(key);
+                        }
+                        released.entry(key.clone()).or_default().insert(value.peer);
+                        remove_key.push(key.clone());
+                    }
+                }
             }
         }
 
@@ -674,6 +817,10 @@ impl InflightBlocks {
             states.remove(&key);
         }
 
+        for scheduler in download_schedulers.values_mut() {
+            scheduler.update_throughput(now);
+        }
+
         download_schedulers.retain(|k, v| {
             // task number zero means this peer's response is very slow
             if v.task_count == 0 {
@@ -707,6 +854,7 @@ impl InflightBlocks {
                         }
                         d.hashes.remove(key);
                     };
+                    released.entry(key.clone()).or_default().insert(state.peer);
                 }
 
                 if key.number > *restart_number {
@@ -743,7 +891,26 @@ impl InflightBlocks {
         download_scheduler.hashes.insert(block)
     }
 
+    /// Whether the block has been requested from the peer, but the request has timed out or been
+    /// reassigned to another peer. Returns true only once for each request.
+    pub fn remove_released(&mut self, peer: PeerIndex, block: &BlockNumberAndHash) -> bool {
+        match self.released.get_mut(block) {
+            Some(peers) => {
+                let removed = peers.remove(&peer);
+                if peers.is_empty() {
+                    self.released.remove(block);
+                }
+                removed
+            }
+            None => false,
+        }
+    }
+
     pub fn remove_by_peer(&mut self, peer: PeerIndex) -> bool {
+        self.released.retain(|_, peers| {
+            peers.remove(&peer);
+            !peers.is_empty()
+        });
         let trace = &mut self.trace_number;
         let state = &mut self.inflight_states;
 
@@ -760,6 +927,30 @@ impl InflightBlocks {
             .is_some()
     }
 
+    /// Removes the block delivered by the peer, returns false if it is not in flight.
+    ///
+    /// A block may be delivered by another peer than the one it is requested from, e.g., the late
+    /// delivery of a reassigned request. The requested peer is released then, without being
+    /// credited with the response, so its delivery later is not penalized either.
+    pub fn remove_delivered(&mut self, peer: PeerIndex, block: BlockNumberAndHash) -> bool {
+        let requested_peer = match self.inflight_states.get(&block) {
+            Some(state) if state.peer != peer => state.peer,
+            _ => return self.remove_by_block(block),
+        };
+        self.inflight_states.remove(&block);
+        if let Some(set) = self.download_schedulers.get_mut(&requested_peer) {
+            set.hashes.remove(&block);
+        }
+        if !self.trace_number.is_empty() {
+            self.trace_number.remove(&block);
+        }
+        self.released
+            .entry(block)
+            .or_default()
+            .insert(requested_peer);
+        true
+    }
+
     pub fn remove_by_block(&mut self, block: BlockNumberAndHash) -> bool {
         let should_punish = self.download_schedulers.len() > self.protect_num;
         let download_schedulers = &mut self.download_schedulers;
@@ -769,10 +960,14 @@ impl InflightBlocks {
         self.inflight_states
             .remove(&block)
             .map(|state| {
-                let elapsed = unix_time_as_millis().saturating_sub(state.timestamp);
+                let now = unix_time_as_millis();
+                let elapsed = now.saturating_sub(state.timestamp);
                 if let Some(set) = download_schedulers.get_mut(&state.peer) {
                     set.hashes.remove(&block);
+                    set.on_received(elapsed, now);
                     if adjustment {
+                        // Additive increase on the fast responses, multiplicative decrease on
+                        // the slowest ones.
                         match time_analyzer.push_time(elapsed) {
                             TimeQuantile::MinToFast => set.increase(2),
                             TimeQuantile::FastToNormal => set.increase(1),
@@ -783,7 +978,7 @@ impl InflightBlocks {
                             }
                             TimeQuantile::UpperToMax => {
                                 if should_punish {
-                                    set.decrease(2)
+                                    set.halve()
                                 }
                             }
                         }
@@ -1737,10 +1932,10 @@ impl SyncState {
     }
 
     // Return true when the block is that we have requested and received first time.
-    pub fn new_block_received(&self, block: &core::BlockView) -> bool {
+    pub fn new_block_received(&self, block: &core::BlockView, peer: PeerIndex) -> bool {
         if self
             .write_inflight_blocks()
-            .remove_by_block((block.number(), block.hash()).into())
+            .remove_delivered(peer, (block.number(), block.hash()).into())
         {
             self.insert_block_status(block.hash(), BlockStatus::BLOCK_RECEIVED);
             true
@@ -1903,6 +2098,14 @@ impl ActiveChain {
         self.store().get_block_header(h)
     }
 
+    /// Returns true if the block is in the main chain but its body has been pruned.
+    pub fn is_pruned(&self, h: &packed::Byte32) -> bool {
+        self.store()
+            .get_block_number(h)
+            .map(|number| self.store().is_pruned(number))
+            .unwrap_or(false)
+    }
+
     pub fn shared(&self) -> &SyncShared {
         &self.shared
     }
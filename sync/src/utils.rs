@@ -2,7 +2,9 @@ use crate::{Status, StatusCode};
 use ckb_error::{Error as CKBError, ErrorKind, InternalError, InternalErrorKind};
 use ckb_metrics::metrics;
 use ckb_network::{CKBProtocolContext, PeerIndex, ProtocolId, SupportProtocols};
-use ckb_types::packed::{RelayMessageReader, SyncMessageReader};
+use ckb_types::packed::{
+    BlockFilterMessageReader, LightClientMessageReader, RelayMessageReader, SyncMessageReader,
+};
 use ckb_types::prelude::*;
 
 /// Send network message into parameterized `protocol_id` protocol connection.
@@ -47,7 +49,8 @@ pub(crate) fn send_message_to<Message: Entity>(
     send_message(protocol_id, nc, peer_index, message)
 }
 
-// As for Sync protocol and Relay protocol, returns the internal item name;
+// As for Sync protocol, Relay protocol, LightClient protocol and Filter protocol, returns the
+// internal item name;
 // otherwise returns the entity name.
 fn message_name<Message: Entity>(protocol_id: ProtocolId, message: &Message) -> String {
     if protocol_id == SupportProtocols::Sync.protocol_id() {
@@ -60,18 +63,33 @@ fn message_name<Message: Entity>(protocol_id: ProtocolId, message: &Message) ->
             .to_enum()
             .item_name()
             .to_owned()
+    } else if protocol_id == SupportProtocols::LightClient.protocol_id() {
+        LightClientMessageReader::new_unchecked(message.as_slice())
+            .to_enum()
+            .item_name()
+            .to_owned()
+    } else if protocol_id == SupportProtocols::Filter.protocol_id() {
+        BlockFilterMessageReader::new_unchecked(message.as_slice())
+            .to_enum()
+            .item_name()
+            .to_owned()
     } else {
         Message::NAME.to_owned()
     }
 }
 
-// As for Sync protocol and Relay protocol, returns the internal item id;
+// As for Sync protocol, Relay protocol, LightClient protocol and Filter protocol, returns the
+// internal item id;
 // otherwise returns 0.
 fn item_id<Message: Entity>(protocol_id: ProtocolId, message: &Message) -> u32 {
     if protocol_id == SupportProtocols::Sync.protocol_id() {
         SyncMessageReader::new_unchecked(message.as_slice()).item_id()
     } else if protocol_id == SupportProtocols::RelayV2.protocol_id() {
         RelayMessageReader::new_unchecked(message.as_slice()).item_id()
+    } else if protocol_id == SupportProtocols::LightClient.protocol_id() {
+        LightClientMessageReader::new_unchecked(message.as_slice()).item_id()
+    } else if protocol_id == SupportProtocols::Filter.protocol_id() {
+        BlockFilterMessageReader::new_unchecked(message.as_slice()).item_id()
     } else {
         0
     }
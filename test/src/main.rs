@@ -435,6 +435,7 @@ fn all_specs() -> Vec<Box<dyn Spec>> {
         Box::new(CompactBlockRelayLessThenSharedBestKnown),
         Box::new(InvalidLocatorSize),
         Box::new(SizeLimit),
+        Box::new(SizeLimitEviction),
         Box::new(CyclesLimit),
         Box::new(SendDefectedBinary::new(
             "send_defected_binary_reject_known_bugs",
@@ -494,6 +495,8 @@ fn all_specs() -> Vec<Box<dyn Spec>> {
         Box::new(ConflictInProposed),
         Box::new(RemoveConflictFromPending),
         Box::new(SubmitConflict),
+        Box::new(RbfReplacePendingTransaction),
+        Box::new(RbfProtectProposedTransaction),
         Box::new(DAOVerify),
         Box::new(AvoidDuplicatedProposalsWithUncles),
         Box::new(BlockSyncRelayerCollaboration),
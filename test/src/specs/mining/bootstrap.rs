@@ -63,10 +63,13 @@ impl Spec for BootstrapCellbase {
             message: Default::default(),
             use_binary_version_as_message_prefix: false,
             binary_version: "TEST".to_string(),
+            advanced: false,
             update_interval_millis: 0,
             notify: vec![],
             notify_scripts: vec![],
             notify_timeout_millis: 800,
+            payout_targets: vec![],
+            tx_selection: Default::default(),
         });
     }
 }
@@ -1,8 +1,9 @@
+use crate::util::check::{is_transaction_pending, is_transaction_rejected};
 use crate::utils::assert_send_transaction_fail;
 use crate::{Node, Spec};
 
 use ckb_logger::info;
-use ckb_types::core::FeeRate;
+use ckb_types::core::{capacity_bytes, Capacity, FeeRate};
 
 pub struct SizeLimit;
 
@@ -119,3 +120,62 @@ impl Spec for CyclesLimit {
         config.tx_pool.min_fee_rate = FeeRate::zero();
     }
 }
+
+pub struct SizeLimitEviction;
+
+impl Spec for SizeLimitEviction {
+    fn run(&self, nodes: &mut Vec<Node>) {
+        let node = &nodes[0];
+
+        node.mine_until_out_bootstrap_period();
+        let high_fee_rate_tx = node.new_transaction_spend_tip_cellbase();
+        node.mine(1);
+
+        info!("Fill the pool with a chain of txs, each pays 1 CKB fee except the parent");
+        let parent = node.new_transaction_spend_tip_cellbase();
+        let mut hash = node.submit_transaction(&parent);
+        let one_tx_size = node.get_tip_tx_pool_info().total_tx_size.value();
+        let max_tx_num = (MAX_MEM_SIZE_FOR_SIZE_LIMIT as u64) / one_tx_size;
+        assert!(max_tx_num > 2);
+
+        let mut capacity = capacity_bytes!(100);
+        let mut txs = Vec::new();
+        (0..(max_tx_num - 1)).for_each(|_| {
+            capacity = capacity.safe_sub(capacity_bytes!(1)).unwrap();
+            let tx = node.new_transaction_with_since_capacity(hash.clone(), 0, capacity);
+            hash = node.submit_transaction(&tx);
+            txs.push(tx);
+        });
+
+        info!("The next tx paying the same fee rate can not evict others");
+        let tx = node.new_transaction_with_since_capacity(
+            hash,
+            0,
+            capacity.safe_sub(capacity_bytes!(1)).unwrap(),
+        );
+        assert_send_transaction_fail(node, &tx, "Transaction pool exceeded maximum size limit");
+
+        info!("The tx paying a higher fee rate evicts the low fee rate txs");
+        node.submit_transaction(&high_fee_rate_tx);
+        assert!(is_transaction_pending(node, &high_fee_rate_tx));
+        assert!(is_transaction_pending(node, &parent));
+        assert!(is_transaction_rejected(node, &txs[0]));
+        assert!(is_transaction_rejected(node, txs.last().unwrap()));
+
+        info!("The min fee rate rises above the evicted txs fee rate");
+        let evicted_fee_rate = FeeRate::calculate(capacity_bytes!(1), one_tx_size as usize);
+        let min_fee_rate = node.get_tip_tx_pool_info().min_fee_rate.value();
+        assert!(min_fee_rate > evicted_fee_rate.as_u64());
+        let tx = node.new_transaction_with_since_capacity(
+            parent.hash(),
+            0,
+            capacity_bytes!(99).safe_sub(Capacity::shannons(1)).unwrap(),
+        );
+        assert_send_transaction_fail(node, &tx, "The min fee rate is");
+    }
+
+    fn modify_app_config(&self, config: &mut ckb_app_config::CKBAppConfig) {
+        config.tx_pool.max_mem_size = MAX_MEM_SIZE_FOR_SIZE_LIMIT;
+        config.tx_pool.max_cycles = MAX_CYCLES_FOR_SIZE_LIMIT;
+    }
+}
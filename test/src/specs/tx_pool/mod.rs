@@ -16,6 +16,7 @@ mod proposal_expire_rule;
 mod reference_header_maturity;
 mod remove_tx;
 mod reorg_proposals;
+mod replace_by_fee;
 mod send_defected_binary;
 mod send_large_cycles_tx;
 mod send_low_fee_rate_tx;
@@ -44,6 +45,7 @@ pub use proposal_expire_rule::*;
 pub use reference_header_maturity::*;
 pub use remove_tx::*;
 pub use reorg_proposals::*;
+pub use replace_by_fee::*;
 pub use send_defected_binary::*;
 pub use send_large_cycles_tx::*;
 pub use send_low_fee_rate_tx::*;
@@ -78,9 +80,12 @@ fn new_block_assembler_config(lock_arg: Bytes, hash_type: ScriptHashType) -> Blo
         message: Default::default(),
         use_binary_version_as_message_prefix: false,
         binary_version: "TEST".to_string(),
+        advanced: false,
         update_interval_millis: 0,
         notify: vec![],
         notify_scripts: vec![],
         notify_timeout_millis: 800,
+        payout_targets: vec![],
+        tx_selection: Default::default(),
     }
 }
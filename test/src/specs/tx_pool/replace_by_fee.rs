@@ -0,0 +1,76 @@
+use crate::util::check::{is_transaction_pending, is_transaction_rejected};
+use crate::utils::{assert_send_transaction_fail, propose};
+use crate::{Node, Spec};
+use ckb_logger::info;
+use ckb_types::core::{capacity_bytes, Capacity};
+
+pub struct RbfReplacePendingTransaction;
+
+impl Spec for RbfReplacePendingTransaction {
+    fn run(&self, nodes: &mut Vec<Node>) {
+        let node0 = &nodes[0];
+
+        node0.mine_until_out_bootstrap_period();
+        let parent_hash = node0.generate_transaction();
+
+        // The parent output capacity is 100, so the fees are 0, 20, 21 and 50.
+        let tx1 =
+            node0.new_transaction_with_since_capacity(parent_hash.clone(), 0, capacity_bytes!(100));
+        let tx2 =
+            node0.new_transaction_with_since_capacity(parent_hash.clone(), 0, capacity_bytes!(80));
+        let tx3 =
+            node0.new_transaction_with_since_capacity(parent_hash.clone(), 0, capacity_bytes!(79));
+        let tx4 = node0.new_transaction_with_since_capacity(parent_hash, 0, capacity_bytes!(50));
+
+        node0.submit_transaction(&tx1);
+
+        info!("tx2 pays more fee and replaces tx1");
+        node0.submit_transaction(&tx2);
+        assert!(is_transaction_pending(node0, &tx2));
+        assert!(is_transaction_rejected(node0, &tx1));
+        let reason = node0
+            .rpc_client()
+            .get_transaction_with_verbosity(tx1.hash(), 1)
+            .and_then(|tx_with_status| tx_with_status.tx_status.reason)
+            .expect("rejected reason");
+        assert!(reason.contains("RBFReplaced"), "{}", reason);
+
+        info!("tx3 does not pay enough fee to replace tx2");
+        assert_send_transaction_fail(node0, &tx3, "RBF rejected");
+        assert!(is_transaction_pending(node0, &tx2));
+
+        info!("tx4 pays enough fee to replace tx2");
+        node0.submit_transaction(&tx4);
+        assert!(is_transaction_pending(node0, &tx4));
+        assert!(is_transaction_rejected(node0, &tx2));
+    }
+
+    fn modify_app_config(&self, config: &mut ckb_app_config::CKBAppConfig) {
+        config.tx_pool.enable_rbf = true;
+    }
+}
+
+pub struct RbfProtectProposedTransaction;
+
+impl Spec for RbfProtectProposedTransaction {
+    fn run(&self, nodes: &mut Vec<Node>) {
+        let node0 = &nodes[0];
+        let window = node0.consensus().tx_proposal_window();
+        node0.mine(window.farthest() + 2);
+
+        let parent = node0.new_transaction_spend_tip_cellbase();
+        node0.submit_transaction(&parent);
+        let tx1 = node0.new_transaction_with_since_capacity(parent.hash(), 0, capacity_bytes!(100));
+        let tx2 = node0.new_transaction_with_since_capacity(parent.hash(), 0, capacity_bytes!(50));
+
+        node0.submit_transaction(&tx1);
+        node0.submit_block(&propose(node0, &[&parent, &tx1]));
+
+        info!("tx1 has been proposed, tx2 can not replace it");
+        assert_send_transaction_fail(node0, &tx2, "RBF rejected");
+    }
+
+    fn modify_app_config(&self, config: &mut ckb_app_config::CKBAppConfig) {
+        config.tx_pool.enable_rbf = true;
+    }
+}
@@ -23,6 +23,13 @@ pub fn is_transaction_committed(node: &Node, transaction: &TransactionView) -> b
         .unwrap_or(false)
 }
 
+pub fn is_transaction_rejected(node: &Node, transaction: &TransactionView) -> bool {
+    node.rpc_client()
+        .get_transaction_with_verbosity(transaction.hash(), 1)
+        .map(|txstatus| txstatus.tx_status.status == Status::Rejected)
+        .unwrap_or(false)
+}
+
 pub fn is_transaction_unknown(node: &Node, transaction: &TransactionView) -> bool {
     node.rpc_client()
         .get_transaction(transaction.hash())
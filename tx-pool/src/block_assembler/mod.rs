@@ -8,21 +8,25 @@ mod tests;
 
 use crate::component::entry::TxEntry;
 use crate::error::BlockAssemblerError;
+use crate::persisted::{load_found_blocks, save_found_blocks};
+use crate::selection::{build_tx_selection_policy, TxSelectionPolicy};
 pub use candidate_uncles::CandidateUncles;
-use ckb_app_config::BlockAssemblerConfig;
+use ckb_app_config::{BlockAssemblerConfig, PayoutTargetConfig};
+use ckb_chain_spec::consensus::Consensus;
 use ckb_dao::DaoCalculator;
-use ckb_error::AnyError;
+use ckb_error::{AnyError, InternalErrorKind};
 use ckb_jsonrpc_types::{
     BlockTemplate as JsonBlockTemplate, CellbaseTemplate, TransactionTemplate, UncleTemplate,
 };
 use ckb_logger::{debug, error, trace};
+use ckb_metrics::{metrics, Timer};
 use ckb_reward_calculator::RewardCalculator;
 use ckb_snapshot::Snapshot;
 use ckb_store::ChainStore;
 use ckb_types::{
     core::{
         cell::{OverlayCellChecker, ResolveOptions, TransactionsChecker},
-        BlockNumber, Capacity, Cycle, EpochExt, EpochNumberWithFraction, ScriptHashType,
+        BlockNumber, BlockView, Capacity, Cycle, EpochExt, EpochNumberWithFraction, ScriptHashType,
         TransactionBuilder, TransactionView, UncleBlockView, Version,
     },
     packed::{
@@ -34,6 +38,7 @@ use ckb_types::{
 use faketime::unix_time_as_millis;
 use hyper::{client::HttpConnector, Body, Client, Method, Request};
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::{
     atomic::{AtomicU64, Ordering},
     Arc,
@@ -48,6 +53,8 @@ use tokio::time::timeout;
 use crate::TxPool;
 pub(crate) use process::process;
 
+const SECP256K1_BLAKE160_SIGHASH_ALL_ARG_LEN: usize = 20;
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub(crate) struct TemplateSize {
     pub(crate) txs: usize,
@@ -64,10 +71,68 @@ pub(crate) struct CurrentTemplate {
     pub(crate) epoch: EpochExt,
 }
 
+/// A lock which receives the block rewards of `weight` blocks in every `sum(weight)` blocks found
+/// by this node.
+///
+/// The consensus only allows the cellbase to pay the whole block reward to the lock in its
+/// witness, so a block can't be split into fixed amounts or pay the remainder to another lock.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PayoutTarget {
+    /// The lock script which receives the rewards.
+    pub lock: Script,
+    /// The number of found blocks paid to the lock in every `sum(weight)` found blocks.
+    pub weight: u64,
+}
+
+impl From<&PayoutTargetConfig> for PayoutTarget {
+    fn from(config: &PayoutTargetConfig) -> Self {
+        let hash_type: ScriptHashType = config.hash_type.clone().into();
+        let lock = Script::new_builder()
+            .args(config.args.as_bytes().pack())
+            .code_hash(config.code_hash.pack())
+            .hash_type(hash_type.into())
+            .build();
+        PayoutTarget {
+            lock,
+            weight: config.weight,
+        }
+    }
+}
+
+/// Checks that every payout target has a positive weight and the total weight does not overflow.
+pub fn check_payout_targets(payout_targets: &[PayoutTarget]) -> Result<(), BlockAssemblerError> {
+    let mut total_weight = 0u64;
+    for target in payout_targets {
+        if target.weight == 0 {
+            return Err(BlockAssemblerError::InvalidParams(
+                "the weight of a payout target should be positive".to_owned(),
+            ));
+        }
+        total_weight = total_weight
+            .checked_add(target.weight)
+            .ok_or(BlockAssemblerError::Overflow)?;
+    }
+    Ok(())
+}
+
+/// Checks whether the lock is the recommended secp256k1_blake160_sighash_all lock of the genesis
+/// system cell, which `ckb run` requires unless `--ba-advanced` is given.
+pub fn is_recommended_lock(consensus: &Consensus, lock: &Script) -> bool {
+    lock.is_hash_type_type()
+        && lock.args().raw_data().len() == SECP256K1_BLAKE160_SIGHASH_ALL_ARG_LEN
+        && consensus.secp256k1_blake160_sighash_all_type_hash() == Some(lock.code_hash())
+}
+
 /// Block generator
 #[derive(Clone)]
 pub struct BlockAssembler {
     pub(crate) config: Arc<BlockAssemblerConfig>,
+    pub(crate) payout_targets: Arc<RwLock<Vec<PayoutTarget>>>,
+    // The blocks found by this node since the payout targets are set, which rotates the targets.
+    pub(crate) found_blocks: Arc<AtomicU64>,
+    // The file keeping `found_blocks` across restarts.
+    pub(crate) payout_rotation_file: PathBuf,
+    pub(crate) selection_policy: Arc<dyn TxSelectionPolicy>,
     pub(crate) work_id: Arc<AtomicU64>,
     pub(crate) candidate_uncles: Arc<Mutex<CandidateUncles>>,
     pub(crate) current: Arc<Mutex<CurrentTemplate>>,
@@ -76,7 +141,14 @@ pub struct BlockAssembler {
 
 impl BlockAssembler {
     /// Construct new block generator
-    pub fn new(config: BlockAssemblerConfig, snapshot: Arc<Snapshot>) -> Self {
+    ///
+    /// The blocks found with the payout targets are kept in `payout_rotation_file`, so the
+    /// rotation continues after restarting.
+    pub fn new(
+        config: BlockAssemblerConfig,
+        snapshot: Arc<Snapshot>,
+        payout_rotation_file: PathBuf,
+    ) -> Self {
         let consensus = snapshot.consensus();
         let tip_header = snapshot.tip_header();
         let current_epoch = consensus
@@ -85,12 +157,23 @@ impl BlockAssembler {
             .epoch();
         let mut builder = BlockTemplateBuilder::new(&snapshot, &current_epoch);
 
-        let cellbase = Self::build_cellbase(&config, &snapshot)
+        let payout_targets: Vec<PayoutTarget> =
+            config.payout_targets.iter().map(Into::into).collect();
+        let found_blocks = load_found_blocks(&payout_rotation_file, &payout_targets)
+            .unwrap_or_else(|err| {
+                error!(
+                    "{}, the payout rotation restarts from the first target",
+                    err
+                );
+                0
+            });
+        let cellbase = Self::build_cellbase(&config, &payout_targets, found_blocks, &snapshot)
             .expect("build cellbase for BlockAssembler initial");
 
-        let extension: Option<packed::Bytes> = None;
+        let extension = Self::build_extension(&snapshot, &current_epoch)
+            .expect("build extension for BlockAssembler initial");
         let basic_block_size =
-            Self::basic_block_size(cellbase.data(), &[], iter::empty(), extension);
+            Self::basic_block_size(cellbase.data(), &[], iter::empty(), extension.clone());
 
         let dao = Self::calc_dao(&snapshot, &current_epoch, cellbase.clone(), vec![])
             .expect("calc_dao for BlockAssembler initial");
@@ -104,6 +187,9 @@ impl BlockAssembler {
             .work_id(work_id.fetch_add(1, Ordering::SeqCst))
             .current_time(cmp::max(unix_time_as_millis(), tip_header.timestamp() + 1))
             .dao(dao);
+        if let Some(extension) = extension {
+            builder.extension(extension);
+        }
         let template = builder.build();
 
         let size = TemplateSize {
@@ -120,8 +206,14 @@ impl BlockAssembler {
             epoch: current_epoch,
         };
 
+        let selection_policy = build_tx_selection_policy(&config.tx_selection);
+
         Self {
             config: Arc::new(config),
+            payout_targets: Arc::new(RwLock::new(payout_targets)),
+            found_blocks: Arc::new(AtomicU64::new(found_blocks)),
+            payout_rotation_file,
+            selection_policy,
             work_id: Arc::new(work_id),
             candidate_uncles: Arc::new(Mutex::new(CandidateUncles::new())),
             current: Arc::new(Mutex::new(current)),
@@ -130,6 +222,7 @@ impl BlockAssembler {
     }
 
     pub(crate) async fn update_full(&self, tx_pool: &RwLock<TxPool>) -> Result<(), AnyError> {
+        let timer = Timer::start();
         let mut current = self.current.lock().await;
         let consensus = current.snapshot.consensus();
         let max_block_bytes = consensus.max_block_bytes() as usize;
@@ -142,14 +235,17 @@ impl BlockAssembler {
             if current.snapshot.tip_hash() != tx_pool_reader.snapshot().tip_hash() {
                 return Ok(());
             }
-            let proposals =
-                tx_pool_reader.package_proposals(consensus.max_block_proposals_limit(), uncles);
+            let proposals = tx_pool_reader.package_proposals(
+                &*self.selection_policy,
+                consensus.max_block_proposals_limit(),
+                uncles,
+            );
 
             let basic_size = Self::basic_block_size(
                 current_template.cellbase.data(),
                 uncles,
                 proposals.iter(),
-                None,
+                current_template.extension.clone(),
             );
 
             let txs_size_limit = max_block_bytes
@@ -157,8 +253,11 @@ impl BlockAssembler {
                 .ok_or(BlockAssemblerError::Overflow)?;
 
             let max_block_cycles = consensus.max_block_cycles();
-            let (txs, txs_size, _cycles) =
-                tx_pool_reader.package_txs(max_block_cycles, txs_size_limit);
+            let (txs, txs_size, _cycles) = tx_pool_reader.package_txs(
+                &*self.selection_policy,
+                max_block_cycles,
+                txs_size_limit,
+            );
             (proposals, txs, txs_size, basic_size)
         };
 
@@ -195,11 +294,13 @@ impl BlockAssembler {
             current.template.proposals.len(),
             current.template.transactions.len(),
         );
+        metrics!(timing, "ckb.block_assembler.update_template", timer.stop(), "type" => "full");
 
         Ok(())
     }
 
     pub(crate) async fn update_blank(&self, snapshot: Arc<Snapshot>) -> Result<(), AnyError> {
+        let timer = Timer::start();
         let consensus = snapshot.consensus();
         let tip_header = snapshot.tip_header();
         let current_epoch = consensus
@@ -208,13 +309,17 @@ impl BlockAssembler {
             .epoch();
         let mut builder = BlockTemplateBuilder::new(&snapshot, &current_epoch);
 
-        let cellbase = Self::build_cellbase(&self.config, &snapshot)?;
+        let cellbase = {
+            let payout_targets = self.payout_targets.read().await;
+            let found_blocks = self.found_blocks.load(Ordering::SeqCst);
+            Self::build_cellbase(&self.config, &payout_targets, found_blocks, &snapshot)?
+        };
         let uncles = self.prepare_uncles(&snapshot, &current_epoch).await;
         let uncles_size = uncles.len() * UncleBlockView::serialized_size_in_block();
 
-        let extension: Option<packed::Bytes> = None;
+        let extension = Self::build_extension(&snapshot, &current_epoch)?;
         let basic_block_size =
-            Self::basic_block_size(cellbase.data(), &uncles, iter::empty(), extension);
+            Self::basic_block_size(cellbase.data(), &uncles, iter::empty(), extension.clone());
 
         let dao = Self::calc_dao(&snapshot, &current_epoch, cellbase.clone(), vec![])?;
 
@@ -226,6 +331,9 @@ impl BlockAssembler {
             .work_id(self.work_id.fetch_add(1, Ordering::SeqCst))
             .current_time(cmp::max(unix_time_as_millis(), tip_header.timestamp() + 1))
             .dao(dao);
+        if let Some(extension) = extension {
+            builder.extension(extension);
+        }
         let template = builder.build();
 
         trace!(
@@ -235,6 +343,7 @@ impl BlockAssembler {
             template.proposals.len(),
             template.transactions.len(),
         );
+        metrics!(timing, "ckb.block_assembler.update_template", timer.stop(), "type" => "blank");
 
         let size = TemplateSize {
             txs: 0,
@@ -255,6 +364,7 @@ impl BlockAssembler {
     }
 
     pub(crate) async fn update_uncles(&self) {
+        let timer = Timer::start();
         let mut current = self.current.lock().await;
         let consensus = current.snapshot.consensus();
         let max_block_bytes = consensus.max_block_bytes() as usize;
@@ -291,12 +401,14 @@ impl BlockAssembler {
                         current.template.proposals.len(),
                         current.template.transactions.len(),
                     );
+                    metrics!(timing, "ckb.block_assembler.update_template", timer.stop(), "type" => "uncles");
                 }
             }
         }
     }
 
     pub(crate) async fn update_proposals(&self, tx_pool: &RwLock<TxPool>) {
+        let timer = Timer::start();
         let mut current = self.current.lock().await;
         let consensus = current.snapshot.consensus();
         let uncles = &current.template.uncles;
@@ -305,7 +417,11 @@ impl BlockAssembler {
             if current.snapshot.tip_hash() != tx_pool_reader.snapshot().tip_hash() {
                 return;
             }
-            tx_pool_reader.package_proposals(consensus.max_block_proposals_limit(), uncles)
+            tx_pool_reader.package_proposals(
+                &*self.selection_policy,
+                consensus.max_block_proposals_limit(),
+                uncles,
+            )
         };
 
         let new_proposals_size = proposals.len() * ProposalShortId::serialized_size();
@@ -333,10 +449,12 @@ impl BlockAssembler {
                 current.template.proposals.len(),
                 current.template.transactions.len(),
             );
+            metrics!(timing, "ckb.block_assembler.update_template", timer.stop(), "type" => "proposals");
         }
     }
 
     pub(crate) async fn update_transactions(&self, tx_pool: &RwLock<TxPool>) {
+        let timer = Timer::start();
         let mut current = self.current.lock().await;
         let consensus = current.snapshot.consensus();
         let current_template = &current.template;
@@ -361,8 +479,11 @@ impl BlockAssembler {
             }
 
             let max_block_cycles = consensus.max_block_cycles();
-            let (txs, txs_size, _cycles) = tx_pool_reader
-                .package_txs(max_block_cycles, txs_size_limit.expect("overflow checked"));
+            let (txs, txs_size, _cycles) = tx_pool_reader.package_txs(
+                &*self.selection_policy,
+                max_block_cycles,
+                txs_size_limit.expect("overflow checked"),
+            );
             (txs, txs_size)
         };
 
@@ -396,6 +517,7 @@ impl BlockAssembler {
                 current.template.proposals.len(),
                 current.template.transactions.len(),
             );
+            metrics!(timing, "ckb.block_assembler.update_template", timer.stop(), "type" => "transactions");
         }
     }
 
@@ -404,13 +526,101 @@ impl BlockAssembler {
         (&current.template).into()
     }
 
-    pub(crate) fn build_cellbase_witness(config: &BlockAssemblerConfig) -> CellbaseWitness {
+    pub(crate) async fn get_payout_targets(&self) -> Vec<PayoutTarget> {
+        self.payout_targets.read().await.clone()
+    }
+
+    /// Replaces the payout targets, which take effect since the next blank template. The rotation
+    /// restarts from the first target.
+    pub(crate) async fn set_payout_targets(&self, targets: Vec<PayoutTarget>) {
+        let mut payout_targets = self.payout_targets.write().await;
+        *payout_targets = targets;
+        self.found_blocks.store(0, Ordering::SeqCst);
+        self.save_found_blocks(&payout_targets, 0);
+    }
+
+    /// Counts the attached blocks which pay the current payout target, so the next template pays
+    /// the next target in turn.
+    pub(crate) async fn record_found_blocks<'a>(
+        &self,
+        blocks: impl Iterator<Item = &'a BlockView>,
+    ) {
+        let payout_targets = self.payout_targets.read().await;
+        let found_blocks = self.found_blocks.load(Ordering::SeqCst);
+        let counted = Self::count_found_blocks(&self.config, &payout_targets, found_blocks, blocks);
+        if counted != found_blocks {
+            self.found_blocks.store(counted, Ordering::SeqCst);
+            self.save_found_blocks(&payout_targets, counted);
+        }
+    }
+
+    fn save_found_blocks(&self, payout_targets: &[PayoutTarget], found_blocks: u64) {
+        if let Err(err) =
+            save_found_blocks(&self.payout_rotation_file, payout_targets, found_blocks)
+        {
+            error!("{}", err);
+        }
+    }
+
+    /// Returns the found blocks after the `blocks` are attached.
+    ///
+    /// A block is found by this node when its cellbase witness pays the current payout target.
+    /// The blocks found by other miners don't pay it, thus they don't move the rotation forward.
+    pub(crate) fn count_found_blocks<'a>(
+        config: &BlockAssemblerConfig,
+        payout_targets: &[PayoutTarget],
+        mut found_blocks: u64,
+        blocks: impl Iterator<Item = &'a BlockView>,
+    ) -> u64 {
+        if payout_targets.is_empty() {
+            return found_blocks;
+        }
+        for block in blocks {
+            let witness_lock = block
+                .transaction(0)
+                .and_then(|cellbase| cellbase.witnesses().get(0))
+                .and_then(|witness| CellbaseWitness::from_slice(&witness.raw_data()).ok())
+                .map(|witness| witness.lock());
+            let current_lock = Self::payout_lock(config, payout_targets, found_blocks);
+            if witness_lock.as_ref() == Some(&current_lock) {
+                found_blocks += 1;
+            }
+        }
+        found_blocks
+    }
+
+    /// Selects the lock which receives the reward of the next block found by this node, after
+    /// `found_blocks` blocks have been found with the current payout targets.
+    ///
+    /// In every `sum(weight)` found blocks, each payout target receives the rewards of `weight`
+    /// blocks. The lock in the config is used when there is no payout target.
+    pub(crate) fn payout_lock(
+        config: &BlockAssemblerConfig,
+        payout_targets: &[PayoutTarget],
+        found_blocks: u64,
+    ) -> Script {
+        let total_weight: u64 = payout_targets.iter().map(|target| target.weight).sum();
+        if total_weight > 0 {
+            let mut slot = found_blocks % total_weight;
+            for target in payout_targets {
+                if slot < target.weight {
+                    return target.lock.clone();
+                }
+                slot -= target.weight;
+            }
+        }
         let hash_type: ScriptHashType = config.hash_type.clone().into();
-        let cellbase_lock = Script::new_builder()
+        Script::new_builder()
             .args(config.args.as_bytes().pack())
             .code_hash(config.code_hash.pack())
             .hash_type(hash_type.into())
-            .build();
+            .build()
+    }
+
+    pub(crate) fn build_cellbase_witness(
+        config: &BlockAssemblerConfig,
+        cellbase_lock: Script,
+    ) -> CellbaseWitness {
         let message = if config.use_binary_version_as_message_prefix {
             if config.message.is_empty() {
                 config.binary_version.as_bytes().pack()
@@ -438,11 +648,14 @@ impl BlockAssembler {
     /// miner should collect the block reward for finalize target H(max(0, c - w_far - 1))
     pub(crate) fn build_cellbase(
         config: &BlockAssemblerConfig,
+        payout_targets: &[PayoutTarget],
+        found_blocks: u64,
         snapshot: &Snapshot,
     ) -> Result<TransactionView, AnyError> {
-        let cellbase_witness = Self::build_cellbase_witness(config);
         let tip = snapshot.tip_header();
         let candidate_number = tip.number() + 1;
+        let cellbase_lock = Self::payout_lock(config, payout_targets, found_blocks);
+        let cellbase_witness = Self::build_cellbase_witness(config, cellbase_lock);
 
         let tx = {
             let (target_lock, block_reward) = block_in_place(|| {
@@ -510,6 +723,26 @@ impl BlockAssembler {
         block.serialized_size_without_uncle_proposals()
     }
 
+    /// Builds the block extension, which commits the chain root of all the blocks before the new
+    /// block since RFC 0044.
+    pub(crate) fn build_extension(
+        snapshot: &Snapshot,
+        current_epoch: &EpochExt,
+    ) -> Result<Option<packed::Bytes>, AnyError> {
+        let enabled = snapshot
+            .consensus()
+            .hardfork_switch()
+            .is_commit_chain_root_in_extension_enabled(current_epoch.number());
+        if !enabled {
+            return Ok(None);
+        }
+        let chain_root = snapshot
+            .chain_root_mmr(snapshot.tip_number())
+            .get_root()
+            .map_err(|err| InternalErrorKind::MMR.other(err))?;
+        Ok(Some(chain_root.calc_mmr_hash().as_bytes().pack()))
+    }
+
     fn calc_dao(
         snapshot: &Snapshot,
         current_epoch: &EpochExt,
@@ -744,7 +977,7 @@ impl BlockTemplateBuilder {
             work_id: None,
             dao: Some(template.dao.clone()),
             current_time: None,
-            extension: None,
+            extension: template.extension.clone(),
         }
     }
 
@@ -804,7 +1037,6 @@ impl BlockTemplateBuilder {
         self
     }
 
-    #[allow(dead_code)]
     pub(crate) fn extension(&mut self, extension: Bytes) -> &mut Self {
         self.extension = Some(extension);
         self
@@ -1,11 +1,16 @@
+use ckb_app_config::BlockAssemblerConfig;
+use ckb_jsonrpc_types::ScriptHashType;
 use ckb_types::{
-    core::{BlockBuilder, BlockNumber},
+    core::{BlockBuilder, BlockNumber, BlockView, TransactionBuilder},
+    packed::{CellbaseWitness, Script},
     prelude::*,
+    H256,
 };
 
 use crate::block_assembler::candidate_uncles::{
     CandidateUncles, MAX_CANDIDATE_UNCLES, MAX_PER_HEIGHT,
 };
+use crate::block_assembler::{check_payout_targets, BlockAssembler, PayoutTarget};
 
 #[test]
 fn test_candidate_uncles_basic() {
@@ -70,3 +75,118 @@ fn test_candidate_uncles_max_per_height() {
     assert_eq!(candidate_uncles.map.len(), 1);
     assert_eq!(candidate_uncles.len(), MAX_PER_HEIGHT);
 }
+
+fn lock_with_args(args: &[u8]) -> Script {
+    Script::new_builder().args(args.to_vec().pack()).build()
+}
+
+fn block_assembler_config() -> BlockAssemblerConfig {
+    BlockAssemblerConfig {
+        code_hash: H256::default(),
+        args: Default::default(),
+        hash_type: ScriptHashType::Data,
+        message: Default::default(),
+        use_binary_version_as_message_prefix: false,
+        binary_version: "TEST".to_string(),
+        advanced: false,
+        update_interval_millis: 800,
+        notify: vec![],
+        notify_scripts: vec![],
+        notify_timeout_millis: 800,
+        payout_targets: vec![],
+        tx_selection: Default::default(),
+    }
+}
+
+fn payout_targets() -> Vec<PayoutTarget> {
+    vec![
+        PayoutTarget {
+            lock: lock_with_args(&[1]),
+            weight: 3,
+        },
+        PayoutTarget {
+            lock: lock_with_args(&[2]),
+            weight: 1,
+        },
+    ]
+}
+
+fn block_paying(lock: Script) -> BlockView {
+    let witness = CellbaseWitness::new_builder().lock(lock).build();
+    let cellbase = TransactionBuilder::default()
+        .witness(witness.as_bytes().pack())
+        .build();
+    BlockBuilder::default().transaction(cellbase).build()
+}
+
+#[test]
+fn test_payout_lock() {
+    let config = block_assembler_config();
+    let default_lock = BlockAssembler::payout_lock(&config, &[], 1);
+    assert_eq!(default_lock.args().raw_data().len(), 0);
+
+    let targets = payout_targets();
+    assert!(check_payout_targets(&targets).is_ok());
+    let payees: Vec<_> = (0..8)
+        .map(|found_blocks| BlockAssembler::payout_lock(&config, &targets, found_blocks).args())
+        .map(|args| args.raw_data()[0])
+        .collect();
+    assert_eq!(payees, vec![1, 1, 1, 2, 1, 1, 1, 2]);
+}
+
+#[test]
+fn test_count_found_blocks() {
+    let config = block_assembler_config();
+    let targets = payout_targets();
+
+    // the blocks of other miners don't move the rotation forward
+    let others = vec![
+        block_paying(lock_with_args(&[3])),
+        BlockBuilder::default().build(),
+    ];
+    assert_eq!(
+        BlockAssembler::count_found_blocks(&config, &targets, 0, others.iter()),
+        0
+    );
+
+    let found: Vec<_> = [1, 1, 1, 2, 1]
+        .iter()
+        .map(|&arg| block_paying(lock_with_args(&[arg])))
+        .collect();
+    assert_eq!(
+        BlockAssembler::count_found_blocks(&config, &targets, 0, found.iter()),
+        5
+    );
+    // a stale template paying the previous target is not counted again
+    let stale = vec![block_paying(lock_with_args(&[1]))];
+    assert_eq!(
+        BlockAssembler::count_found_blocks(&config, &targets, 3, stale.iter()),
+        3
+    );
+    assert_eq!(
+        BlockAssembler::count_found_blocks(&config, &[], 0, found.iter()),
+        0
+    );
+}
+
+#[test]
+fn test_check_payout_targets() {
+    let zero_weight = vec![PayoutTarget {
+        lock: lock_with_args(&[1]),
+        weight: 0,
+    }];
+    assert!(check_payout_targets(&zero_weight).is_err());
+
+    let overflow = vec![
+        PayoutTarget {
+            lock: lock_with_args(&[1]),
+            weight: u64::MAX,
+        },
+        PayoutTarget {
+            lock: lock_with_args(&[2]),
+            weight: 1,
+        },
+    ];
+    assert!(check_payout_targets(&overflow).is_err());
+    assert!(check_payout_targets(&[]).is_ok());
+}
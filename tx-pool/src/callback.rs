@@ -1,6 +1,9 @@
 use super::component::TxEntry;
 use crate::error::Reject;
 use crate::pool::TxPool;
+use crate::util::record_reject;
+use ckb_metrics::metrics;
+use std::time::Duration;
 
 /// Callback boxed fn pointer wrapper
 pub type Callback = Box<dyn Fn(&mut TxPool, &TxEntry) + Sync + Send>;
@@ -63,6 +66,11 @@ impl Callbacks {
 
     /// Call on after proposed
     pub fn call_proposed(&self, tx_pool: &mut TxPool, entry: &TxEntry, new: bool) {
+        metrics!(
+            timing,
+            "ckb.tx_pool.proposed_latency",
+            elapsed_since_entered(entry)
+        );
         if let Some(call) = &self.proposed {
             call(tx_pool, entry, new)
         }
@@ -70,6 +78,11 @@ impl Callbacks {
 
     /// Call on after proposed
     pub fn call_committed(&self, tx_pool: &mut TxPool, entry: &TxEntry) {
+        metrics!(
+            timing,
+            "ckb.tx_pool.committed_latency",
+            elapsed_since_entered(entry)
+        );
         if let Some(call) = &self.committed {
             call(tx_pool, entry)
         }
@@ -77,8 +90,18 @@ impl Callbacks {
 
     /// Call on after reject
     pub fn call_reject(&self, tx_pool: &mut TxPool, entry: &TxEntry, reject: Reject) {
+        record_reject(&reject);
+        // The rejected transaction is removed from the pool without getting committed.
+        tx_pool
+            .fee_estimator
+            .untrack_tx(&entry.transaction().hash());
         if let Some(call) = &self.reject {
             call(tx_pool, entry, reject)
         }
     }
 }
+
+// The time elapsed since the entry was submitted to the pool.
+fn elapsed_since_entered(entry: &TxEntry) -> Duration {
+    Duration::from_millis(faketime::unix_time_as_millis().saturating_sub(entry.timestamp))
+}
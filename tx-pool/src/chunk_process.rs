@@ -43,6 +43,9 @@ pub(crate) struct ChunkProcess {
     recv: watch::Receiver<ChunkCommand>,
     signal: watch::Receiver<u8>,
     current_state: ChunkCommand,
+    // acknowledges the suspend command once the in-flight verification state is cached
+    suspended_ack: ckb_channel::Sender<()>,
+    acked: bool,
 }
 
 impl ChunkProcess {
@@ -50,12 +53,15 @@ impl ChunkProcess {
         service: TxPoolService,
         recv: watch::Receiver<ChunkCommand>,
         signal: watch::Receiver<u8>,
+        suspended_ack: ckb_channel::Sender<()>,
     ) -> Self {
         ChunkProcess {
             service,
             recv,
             signal,
             current_state: ChunkCommand::Resume,
+            suspended_ack,
+            acked: false,
         }
     }
 
@@ -66,6 +72,7 @@ impl ChunkProcess {
             tokio::select! {
                 _ = self.recv.changed() => {
                     self.current_state = self.recv.borrow().to_owned();
+                    self.acked = false;
                     if matches!(self.current_state, ChunkCommand::Resume) {
                         let stop = self.try_process().await;
                         if stop {
@@ -84,6 +91,17 @@ impl ChunkProcess {
                 },
                 else => break,
             }
+            self.ack_suspended();
+        }
+    }
+
+    // Every entry is either completed or cached as suspended when `try_process` returns, so it's
+    // safe to acknowledge the suspend command here.
+    fn ack_suspended(&mut self) {
+        if matches!(self.current_state, ChunkCommand::Suspend) && !self.acked {
+            // the channel is bounded to 1, a pending acknowledgement is enough
+            let _ = self.suspended_ack.try_send(());
+            self.acked = true;
         }
     }
 
@@ -139,6 +157,7 @@ impl ChunkProcess {
             }
             if self.recv.has_changed().unwrap_or(false) {
                 self.current_state = self.recv.borrow_and_update().to_owned();
+                self.acked = false;
             }
 
             if matches!(self.current_state, ChunkCommand::Suspend) {
@@ -244,10 +263,8 @@ impl ChunkProcess {
                     let completed = try_or_return_with_snapshot!(ret, snapshot);
 
                     let entry = TxEntry::new(rtx, completed.cycles, fee, tx_size);
-                    let (ret, submit_snapshot) = self
-                        .service
-                        .submit_entry(completed, tip_hash, entry, status)
-                        .await;
+                    let (ret, submit_snapshot) =
+                        self.service.submit_entry(tip_hash, entry, status).await;
                     try_or_return_with_snapshot!(ret, submit_snapshot);
                     self.service
                         .after_process(tx, remote, &submit_snapshot, &Ok(completed))
@@ -316,10 +333,7 @@ impl ChunkProcess {
         }
 
         let entry = TxEntry::new(rtx.clone(), completed.cycles, fee, tx_size);
-        let (ret, submit_snapshot) = self
-            .service
-            .submit_entry(completed, tip_hash, entry, status)
-            .await;
+        let (ret, submit_snapshot) = self.service.submit_entry(tip_hash, entry, status).await;
         try_or_return_with_snapshot!(ret, snapshot);
 
         self.service.notify_block_assembler(status).await;
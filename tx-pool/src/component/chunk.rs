@@ -57,6 +57,14 @@ impl ChunkQueue {
             || self.inner.contains_key(id)
     }
 
+    /// Returns the txs in the queue, including the one being processed.
+    pub fn txs(&self) -> impl Iterator<Item = &TransactionView> {
+        self.front
+            .iter()
+            .map(|entry| &entry.tx)
+            .chain(self.inner.values().map(|entry| &entry.tx))
+    }
+
     pub fn shrink_to_fit(&mut self) {
         shrink_to_fit!(self.inner, SHRINK_THRESHOLD);
     }
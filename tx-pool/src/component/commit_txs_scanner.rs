@@ -1,6 +1,11 @@
 use crate::component::{container::AncestorsScoreSortKey, entry::TxEntry, proposed::ProposedPool};
-use ckb_types::{core::Cycle, packed::ProposalShortId};
+use crate::selection::TxSelectionPolicy;
+use ckb_types::{
+    core::{Capacity, Cycle},
+    packed::ProposalShortId,
+};
 use ckb_util::LinkedHashMap;
+use std::borrow::Cow;
 use std::collections::{BTreeSet, HashMap, HashSet};
 
 // A template data struct used to store modified entries when package txs
@@ -57,6 +62,11 @@ const MAX_CONSECUTIVE_FAILURES: usize = 500;
 /// find txs to package into commitment
 pub struct CommitTxsScanner<'a> {
     proposed_pool: &'a ProposedPool,
+    policy: &'a dyn TxSelectionPolicy,
+    // entries whose fee or ancestors fee are adjusted by the fee deltas of the policy
+    adjusted_entries: HashMap<ProposalShortId, TxEntry>,
+    // entries left out by the policy, along with their descendants
+    excluded_txs: HashSet<ProposalShortId>,
     entries: Vec<TxEntry>,
     // modified_entries will store sorted packages after they are modified
     // because some of their txs are already in the block
@@ -68,9 +78,15 @@ pub struct CommitTxsScanner<'a> {
 }
 
 impl<'a> CommitTxsScanner<'a> {
-    pub fn new(proposed_pool: &'a ProposedPool) -> CommitTxsScanner<'a> {
+    pub fn new(
+        proposed_pool: &'a ProposedPool,
+        policy: &'a dyn TxSelectionPolicy,
+    ) -> CommitTxsScanner<'a> {
         CommitTxsScanner {
             proposed_pool,
+            policy,
+            adjusted_entries: HashMap::default(),
+            excluded_txs: HashSet::default(),
             entries: Vec::new(),
             modified_entries: TxModifiedEntries::default(),
             fetched_txs: HashSet::default(),
@@ -88,7 +104,8 @@ impl<'a> CommitTxsScanner<'a> {
         let mut cycles: Cycle = 0;
         let mut consecutive_failed = 0;
 
-        let mut iter = self.proposed_pool.score_sorted_iter().peekable();
+        let candidates = self.prepare_candidates();
+        let mut iter = candidates.iter().map(|entry| &**entry).peekable();
         loop {
             let mut using_modified = false;
 
@@ -183,10 +200,92 @@ impl<'a> CommitTxsScanner<'a> {
         (self.entries, size, cycles)
     }
 
+    /// Applies the policy, returns the entries sorted by the adjusted ancestor score from higher
+    /// to lower.
+    fn prepare_candidates(&mut self) -> Vec<Cow<'a, TxEntry>> {
+        let proposed_pool = self.proposed_pool;
+        if self.policy.is_noop() {
+            return proposed_pool
+                .score_sorted_iter()
+                .map(Cow::Borrowed)
+                .collect();
+        }
+        let mut fee_deltas = HashMap::new();
+        for entry in proposed_pool.score_sorted_iter() {
+            let short_id = entry.proposal_short_id();
+            if self.excluded_txs.contains(&short_id) {
+                continue;
+            }
+            if !self.policy.is_selectable(entry) {
+                // the descendants can not be committed without it
+                self.excluded_txs
+                    .extend(proposed_pool.calc_descendants(&short_id));
+                self.excluded_txs.insert(short_id);
+                continue;
+            }
+            let fee_delta = self.policy.fee_delta(entry);
+            if fee_delta != 0 {
+                fee_deltas.insert(short_id, fee_delta);
+            }
+        }
+
+        if fee_deltas.is_empty() {
+            return proposed_pool
+                .score_sorted_iter()
+                .filter(|entry| !self.excluded_txs.contains(&entry.proposal_short_id()))
+                .map(Cow::Borrowed)
+                .collect();
+        }
+
+        // the ancestors fee of an entry includes the fee deltas of itself and its ancestors
+        let mut ancestors_fee_deltas: HashMap<ProposalShortId, i64> = HashMap::new();
+        for (short_id, fee_delta) in &fee_deltas {
+            for id in proposed_pool
+                .calc_descendants(short_id)
+                .into_iter()
+                .chain(std::iter::once(short_id.clone()))
+            {
+                let total = ancestors_fee_deltas.entry(id).or_insert(0);
+                *total = total.saturating_add(*fee_delta);
+            }
+        }
+        for (short_id, ancestors_fee_delta) in ancestors_fee_deltas {
+            if let Some(entry) = proposed_pool.get(&short_id) {
+                let mut entry = entry.clone();
+                let fee_delta = fee_deltas.get(&short_id).cloned().unwrap_or(0);
+                entry.fee = apply_fee_delta(entry.fee, fee_delta);
+                entry.ancestors_fee = apply_fee_delta(entry.ancestors_fee, ancestors_fee_delta);
+                self.adjusted_entries.insert(short_id, entry);
+            }
+        }
+
+        let mut candidates: Vec<Cow<'a, TxEntry>> = proposed_pool
+            .score_sorted_iter()
+            .filter_map(|entry| {
+                let short_id = entry.proposal_short_id();
+                if self.excluded_txs.contains(&short_id) {
+                    None
+                } else if let Some(adjusted) = self.adjusted_entries.get(&short_id) {
+                    Some(Cow::Owned(adjusted.clone()))
+                } else {
+                    Some(Cow::Borrowed(entry))
+                }
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.cmp(a));
+        candidates
+    }
+
+    fn pool_entry(&self, short_id: &ProposalShortId) -> Option<&TxEntry> {
+        self.adjusted_entries
+            .get(short_id)
+            .or_else(|| self.proposed_pool.get(short_id))
+    }
+
     fn retrieve_entry(&self, short_id: &ProposalShortId) -> Option<&TxEntry> {
         self.modified_entries
             .get(short_id)
-            .or_else(|| self.proposed_pool.get(short_id))
+            .or_else(|| self.pool_entry(short_id))
     }
 
     // Skip entries in `proposed` that are already in a block or are present
@@ -197,6 +296,7 @@ impl<'a> CommitTxsScanner<'a> {
         self.fetched_txs.contains(short_id)
             || self.modified_entries.contains_key(short_id)
             || self.failed_txs.contains(short_id)
+            || self.excluded_txs.contains(short_id)
     }
 
     /// Add descendants of given transactions to `modified_entries` with ancestor
@@ -206,11 +306,10 @@ impl<'a> CommitTxsScanner<'a> {
             let descendants = self.proposed_pool.calc_descendants(id);
             for desc_id in descendants
                 .iter()
-                .filter(|id| !already_added.contains_key(id))
+                .filter(|id| !already_added.contains_key(id) && !self.excluded_txs.contains(id))
             {
                 let mut desc = self.modified_entries.remove(desc_id).unwrap_or_else(|| {
-                    self.proposed_pool
-                        .get(desc_id)
+                    self.pool_entry(desc_id)
                         .map(ToOwned::to_owned)
                         .expect("pool consistent")
                 });
@@ -220,3 +319,12 @@ impl<'a> CommitTxsScanner<'a> {
         }
     }
 }
+
+fn apply_fee_delta(fee: Capacity, fee_delta: i64) -> Capacity {
+    let shannons = if fee_delta >= 0 {
+        fee.as_u64().saturating_add(fee_delta as u64)
+    } else {
+        fee.as_u64().saturating_sub(fee_delta.unsigned_abs())
+    };
+    Capacity::shannons(shannons)
+}
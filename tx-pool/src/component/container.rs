@@ -159,7 +159,7 @@ impl TxLinksMap {
         self.inner.remove(short_id)
     }
 
-    fn remove_child(
+    pub(crate) fn remove_child(
         &mut self,
         short_id: &ProposalShortId,
         child: &ProposalShortId,
@@ -169,7 +169,7 @@ impl TxLinksMap {
             .map(|links| links.children.remove(child))
     }
 
-    fn remove_parent(
+    pub(crate) fn remove_parent(
         &mut self,
         short_id: &ProposalShortId,
         parent: &ProposalShortId,
@@ -179,13 +179,17 @@ impl TxLinksMap {
             .map(|links| links.parents.remove(parent))
     }
 
-    fn add_child(&mut self, short_id: &ProposalShortId, child: ProposalShortId) -> Option<bool> {
+    pub(crate) fn add_child(
+        &mut self,
+        short_id: &ProposalShortId,
+        child: ProposalShortId,
+    ) -> Option<bool> {
         self.inner
             .get_mut(short_id)
             .map(|links| links.children.insert(child))
     }
 
-    fn clear(&mut self) {
+    pub(crate) fn clear(&mut self) {
         self.inner.clear();
     }
 }
@@ -1,7 +1,9 @@
 use crate::component::container::AncestorsScoreSortKey;
 use crate::component::get_transaction_virtual_bytes;
 use ckb_types::{
-    core::{cell::ResolvedTransaction, tx_pool::TxEntryInfo, Capacity, Cycle, TransactionView},
+    core::{
+        cell::ResolvedTransaction, tx_pool::TxEntryInfo, Capacity, Cycle, FeeRate, TransactionView,
+    },
     packed::{OutPoint, ProposalShortId},
 };
 use faketime::unix_time_as_millis;
@@ -68,6 +70,12 @@ impl TxEntry {
         self.transaction().proposal_short_id()
     }
 
+    /// Returns the fee rate of the transaction itself, ancestors excluded
+    pub fn fee_rate(&self) -> FeeRate {
+        let vbytes = get_transaction_virtual_bytes(self.size, self.cycles);
+        FeeRate::calculate(self.fee, vbytes as usize)
+    }
+
     /// Returns a sorted_key
     pub fn as_sorted_key(&self) -> AncestorsScoreSortKey {
         AncestorsScoreSortKey::from(self)
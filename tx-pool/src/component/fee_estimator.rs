@@ -0,0 +1,300 @@
+//! Fee rate estimator based on the confirmation history of the pool transactions.
+//!
+//! The estimator tracks the transactions entering the pending pool, groups them into
+//! exponentially spaced fee rate buckets, and records the block which proposed each
+//! transaction and how many blocks it waited before it got committed. The historical data
+//! decays every block, so the recent blocks have more influence on the estimation.
+use ckb_types::{
+    core::{BlockNumber, FeeRate},
+    packed::{Byte32, ProposalShortId},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The max target blocks which the estimator is able to answer.
+pub const MAX_TARGET_BLOCKS: u64 = 128;
+/// The default confidence in percentage.
+pub const DEFAULT_CONFIDENCE: u32 = 85;
+
+/// The version of the persisted fee estimator data.
+pub(crate) const VERSION: u32 = 1;
+
+// The fee rate buckets range, in shannons per kilobytes.
+const MIN_BUCKET_FEE_RATE: f64 = 1_000f64;
+const MAX_BUCKET_FEE_RATE: f64 = 10_000_000f64;
+const BUCKET_SPACING: f64 = 1.1;
+// Half life is about 346 blocks.
+const DECAY: f64 = 0.998;
+// The minimal (decayed) count of transactions to evaluate a range of buckets.
+const SUFFICIENT_TXS: f64 = 1.0;
+
+/// The estimation result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeRateEstimate {
+    /// The estimated fee rate.
+    pub fee_rate: FeeRate,
+    /// The target blocks used in the estimation.
+    ///
+    /// It may differ from the requested one, because a transaction can not be committed before
+    /// it is proposed and the proposal window passes.
+    pub target_blocks: u64,
+    /// The percentage of the historical transactions around the estimated fee rate which were
+    /// committed within the target blocks.
+    pub confidence: u32,
+}
+
+#[derive(Clone, Debug)]
+struct TrackedTx {
+    bucket: usize,
+    fee_rate: FeeRate,
+    entered_at: BlockNumber,
+    proposed_at: Option<BlockNumber>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Stats {
+    best_height: BlockNumber,
+    // lower bounds of the buckets
+    buckets: Vec<u64>,
+    // decayed count of the finished transactions, committed or not, per bucket
+    txs: Vec<f64>,
+    // decayed sum of the finished transactions fee rates per bucket
+    fee_rates: Vec<f64>,
+    // decayed count of the transactions committed after exact `i + 1` blocks, per bucket
+    committed: Vec<Vec<f64>>,
+}
+
+impl Stats {
+    fn new() -> Self {
+        let mut buckets = Vec::new();
+        let mut bound = MIN_BUCKET_FEE_RATE;
+        while bound <= MAX_BUCKET_FEE_RATE {
+            buckets.push(bound as u64);
+            bound *= BUCKET_SPACING;
+        }
+        let len = buckets.len();
+        Stats {
+            best_height: 0,
+            buckets,
+            txs: vec![0f64; len],
+            fee_rates: vec![0f64; len],
+            committed: vec![vec![0f64; len]; MAX_TARGET_BLOCKS as usize],
+        }
+    }
+
+    fn is_compatible(&self, other: &Stats) -> bool {
+        self.buckets == other.buckets
+            && other.txs.len() == self.txs.len()
+            && other.fee_rates.len() == self.fee_rates.len()
+            && other.committed.len() == self.committed.len()
+            && other.committed.iter().all(|c| c.len() == self.txs.len())
+    }
+
+    fn bucket_index(&self, fee_rate: FeeRate) -> usize {
+        self.buckets
+            .partition_point(|bound| *bound <= fee_rate.as_u64())
+            .saturating_sub(1)
+    }
+
+    fn decay(&mut self) {
+        for count in self
+            .txs
+            .iter_mut()
+            .chain(self.fee_rates.iter_mut())
+            .chain(self.committed.iter_mut().flatten())
+        {
+            *count *= DECAY;
+        }
+    }
+
+    fn record(&mut self, tx: &TrackedTx, committed_blocks: Option<u64>) {
+        self.txs[tx.bucket] += 1f64;
+        self.fee_rates[tx.bucket] += tx.fee_rate.as_u64() as f64;
+        if let Some(blocks) = committed_blocks {
+            if (1..=MAX_TARGET_BLOCKS).contains(&blocks) {
+                self.committed[blocks as usize - 1][tx.bucket] += 1f64;
+            }
+        }
+    }
+}
+
+/// Estimates the fee rate required to get a transaction committed within the target blocks.
+#[derive(Clone, Debug)]
+pub struct FeeEstimator {
+    stats: Stats,
+    tracked: HashMap<ProposalShortId, TrackedTx>,
+    min_target_blocks: u64,
+}
+
+impl FeeEstimator {
+    /// Creates an empty estimator.
+    ///
+    /// The `proposal_window_closest` is used to determine the minimal blocks a transaction
+    /// waits before committed: one block to get proposed, then the closest proposal window.
+    pub fn new(proposal_window_closest: BlockNumber) -> Self {
+        FeeEstimator {
+            stats: Stats::new(),
+            tracked: HashMap::new(),
+            min_target_blocks: (proposal_window_closest + 1).min(MAX_TARGET_BLOCKS),
+        }
+    }
+
+    /// Starts tracking a transaction which enters the pending pool at the block `tip_number`.
+    pub fn track_tx(&mut self, tx_hash: Byte32, fee_rate: FeeRate, tip_number: BlockNumber) {
+        let bucket = self.stats.bucket_index(fee_rate);
+        self.tracked
+            .entry(ProposalShortId::from_tx_hash(&tx_hash))
+            .or_insert(TrackedTx {
+                bucket,
+                fee_rate,
+                entered_at: tip_number,
+                proposed_at: None,
+            });
+    }
+
+    /// Stops tracking a transaction which leaves the pool without getting committed, e.g.,
+    /// replaced, evicted, expired or removed manually.
+    ///
+    /// It is not recorded, because the fee rate is not the reason it is not committed.
+    pub fn untrack_tx(&mut self, tx_hash: &Byte32) {
+        self.tracked.remove(&ProposalShortId::from_tx_hash(tx_hash));
+    }
+
+    /// Stops tracking all the transactions, used when the pool is cleared.
+    pub fn clear_tracked(&mut self) {
+        self.tracked.clear();
+    }
+
+    /// Records the transactions proposed and committed in the block `number`.
+    ///
+    /// The tracked transactions which stay in the pool for more than `MAX_TARGET_BLOCKS`
+    /// blocks are recorded as failures and stop being tracked.
+    pub fn process_block<P, I>(&mut self, number: BlockNumber, proposals: P, committed_txs: I)
+    where
+        P: IntoIterator<Item = ProposalShortId>,
+        I: IntoIterator<Item = Byte32>,
+    {
+        // The block has been processed before a chain reorganization, only stop tracking the
+        // committed transactions to avoid counting them twice.
+        let is_new_block = number > self.stats.best_height;
+        if is_new_block {
+            self.stats.decay();
+            self.stats.best_height = number;
+        }
+
+        for id in proposals {
+            if let Some(tx) = self.tracked.get_mut(&id) {
+                tx.proposed_at.get_or_insert(number);
+            }
+        }
+
+        for tx_hash in committed_txs {
+            if let Some(tx) = self
+                .tracked
+                .remove(&ProposalShortId::from_tx_hash(&tx_hash))
+            {
+                if is_new_block {
+                    let blocks = number.saturating_sub(tx.entered_at).max(1);
+                    self.stats.record(&tx, Some(blocks));
+                }
+            }
+        }
+
+        let stats = &mut self.stats;
+        self.tracked.retain(|_, tx| {
+            if number.saturating_sub(tx.entered_at) > MAX_TARGET_BLOCKS {
+                stats.record(tx, None);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Estimates the lowest fee rate that the transactions were committed within
+    /// `target_blocks` blocks in at least `confidence` percent of cases.
+    ///
+    /// Returns `None` when there are not enough samples.
+    pub fn estimate(
+        &self,
+        target_blocks: u64,
+        confidence: u32,
+        tip_number: BlockNumber,
+    ) -> Option<FeeRateEstimate> {
+        let target_blocks = target_blocks.clamp(self.min_target_blocks, MAX_TARGET_BLOCKS);
+        let threshold = f64::from(confidence.min(100)) / 100f64;
+
+        // The transactions still in the pool which have waited longer than the target blocks
+        // are already failed to be committed in time. So are the ones not proposed yet, which
+        // can not be committed in time even if they are proposed in the next block.
+        let mut failed = vec![0f64; self.stats.buckets.len()];
+        for tx in self.tracked.values() {
+            let waited = tip_number.saturating_sub(tx.entered_at);
+            let min_wait = if tx.proposed_at.is_some() {
+                waited
+            } else {
+                waited + self.min_target_blocks
+            };
+            if min_wait > target_blocks {
+                failed[tx.bucket] += 1f64;
+            }
+        }
+
+        let mut estimate = None;
+        let (mut txs, mut fee_rates, mut committed, mut failed_in_pool) = (0f64, 0f64, 0f64, 0f64);
+        // Scan from the highest fee rate bucket, find the lowest range which still passes the
+        // threshold.
+        for bucket in (0..self.stats.buckets.len()).rev() {
+            txs += self.stats.txs[bucket];
+            fee_rates += self.stats.fee_rates[bucket];
+            failed_in_pool += failed[bucket];
+            committed += self.stats.committed[..target_blocks as usize]
+                .iter()
+                .map(|counts| counts[bucket])
+                .sum::<f64>();
+
+            let total = txs + failed_in_pool;
+            if total < SUFFICIENT_TXS {
+                continue;
+            }
+            let ratio = committed / total;
+            if ratio < threshold {
+                break;
+            }
+            if txs > 0f64 {
+                estimate = Some(FeeRateEstimate {
+                    fee_rate: FeeRate::from_u64((fee_rates / txs).round() as u64),
+                    target_blocks,
+                    confidence: (ratio * 100f64) as u32,
+                });
+            }
+            txs = 0f64;
+            fee_rates = 0f64;
+            committed = 0f64;
+            failed_in_pool = 0f64;
+        }
+
+        estimate
+    }
+
+    /// Serializes the historical data.
+    ///
+    /// The tracked transactions are not included, they are tracked again when the persisted
+    /// transactions are loaded into the pool.
+    pub(crate) fn dump(&self) -> Result<Vec<u8>, serde_json::Error> {
+        serde_json::to_vec(&self.stats)
+    }
+
+    /// Restores the historical data serialized by `dump`.
+    ///
+    /// Returns false if the data is incompatible with the current bucket layout.
+    pub(crate) fn restore(&mut self, data: &[u8]) -> Result<bool, serde_json::Error> {
+        let stats: Stats = serde_json::from_slice(data)?;
+        if self.stats.is_compatible(&stats) {
+            self.stats = stats;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
@@ -1,11 +1,13 @@
 pub mod commit_txs_scanner;
 pub mod entry;
+pub mod fee_estimator;
 
 pub(crate) mod chunk;
 pub(crate) mod container;
 pub(crate) mod orphan;
 pub(crate) mod pending;
 pub(crate) mod proposed;
+pub(crate) mod rbf;
 pub(crate) mod recent_reject;
 
 #[cfg(test)]
@@ -1,16 +1,17 @@
+use crate::component::container::{AncestorsScoreSortKey, TxLinks, TxLinksMap};
 use crate::component::entry::TxEntry;
 use ckb_types::{
     core::{
         cell::{CellChecker, CellMetaBuilder, CellProvider, CellStatus},
         error::OutPointError,
         tx_pool::Reject,
-        TransactionView,
+        Capacity, TransactionView,
     },
     packed::{Byte32, OutPoint, ProposalShortId},
     prelude::*,
 };
-use ckb_util::{LinkedHashMap, LinkedHashMapEntries};
-use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+use ckb_util::LinkedHashMap;
+use std::collections::{hash_map::Entry, BTreeSet, HashMap, HashSet};
 
 type ConflictEntry = (TxEntry, Reject);
 
@@ -25,6 +26,10 @@ pub(crate) struct PendingQueue {
     pub(crate) header_deps: HashMap<ProposalShortId, Vec<Byte32>>,
     // /// output-op<txid> map represent in-pool tx's outputs
     pub(crate) outputs: HashMap<OutPoint, HashSet<ProposalShortId>>,
+    /// A map track transaction ancestors and descendants
+    links: TxLinksMap,
+    /// The ancestors score keys of the entries, whose ancestors state is kept up to date
+    sorted_index: BTreeSet<AncestorsScoreSortKey>,
 }
 
 impl PendingQueue {
@@ -35,6 +40,8 @@ impl PendingQueue {
             inputs: Default::default(),
             header_deps: Default::default(),
             outputs: Default::default(),
+            links: Default::default(),
+            sorted_index: Default::default(),
         }
     }
 
@@ -63,7 +70,6 @@ impl PendingQueue {
     }
 
     pub(crate) fn add_entry(&mut self, entry: TxEntry) -> bool {
-        let inputs = entry.transaction().input_pts_iter();
         let tx_short_id = entry.proposal_short_id();
         let outputs = entry.transaction().output_pts();
 
@@ -71,7 +77,36 @@ impl PendingQueue {
             return false;
         }
 
-        for i in inputs {
+        // The in-pool transactions whose outputs are spent or used as cell deps by the entry.
+        let parents: HashSet<ProposalShortId> = entry
+            .transaction()
+            .input_pts_iter()
+            .chain(entry.related_dep_out_points().cloned())
+            .map(|out_point| ProposalShortId::from_tx_hash(&out_point.tx_hash()))
+            .filter(|id| self.inner.contains_key(id))
+            .collect();
+        // The in-pool transactions which spend or use the outputs of the entry. It happens when
+        // the entry returns to this queue, e.g., its proposal is detached.
+        let children: HashSet<ProposalShortId> = outputs
+            .iter()
+            .flat_map(|o| self.inputs.get(o).into_iter().chain(self.deps.get(o)))
+            .flatten()
+            .cloned()
+            .collect();
+
+        for parent in &parents {
+            self.links.add_child(parent, tx_short_id.clone());
+        }
+        for child in &children {
+            if let Some(links) = self.links.inner.get_mut(child) {
+                links.parents.insert(tx_short_id.clone());
+            }
+        }
+        self.links
+            .inner
+            .insert(tx_short_id.clone(), TxLinks { parents, children });
+
+        for i in entry.transaction().input_pts_iter() {
             self.inputs
                 .entry(i.to_owned())
                 .or_default()
@@ -94,9 +129,17 @@ impl PendingQueue {
             }
         }
 
-        // record tx unconsumed output
+        // record tx output, and the in-pool transactions consuming it
         for o in outputs {
-            self.outputs.insert(o, HashSet::new());
+            let consumers = self
+                .inputs
+                .get(&o)
+                .into_iter()
+                .chain(self.deps.get(&o))
+                .flatten()
+                .cloned()
+                .collect();
+            self.outputs.insert(o, consumers);
         }
 
         // record header_deps
@@ -106,10 +149,40 @@ impl PendingQueue {
                 .insert(tx_short_id.clone(), header_deps.into_iter().collect());
         }
 
-        self.inner.insert(tx_short_id, entry);
+        self.inner.insert(tx_short_id.clone(), entry);
+        // The entry may come from another queue with a stale ancestors state, and its
+        // descendants gain the entry and its ancestors as their ancestors.
+        let descendants = self.links.calc_descendants(&tx_short_id);
+        self.update_ancestors_state(&tx_short_id);
+        for id in &descendants {
+            self.update_ancestors_state(id);
+        }
         true
     }
 
+    // Recalculates the ancestors state of the entry from the links, and updates its sort key.
+    fn update_ancestors_state(&mut self, id: &ProposalShortId) {
+        let ancestors: Vec<_> = self
+            .links
+            .calc_ancestors(id)
+            .iter()
+            .filter_map(|ancestor_id| self.inner.get(ancestor_id))
+            .map(|ancestor| (ancestor.size, ancestor.cycles, ancestor.fee))
+            .collect();
+        if let Some(entry) = self.inner.get_mut(id) {
+            self.sorted_index.remove(&entry.as_sorted_key());
+            entry.reset_ancestors_state();
+            for (size, cycles, fee) in ancestors {
+                entry.ancestors_count = entry.ancestors_count.saturating_add(1);
+                entry.ancestors_size = entry.ancestors_size.saturating_add(size);
+                entry.ancestors_cycles = entry.ancestors_cycles.saturating_add(cycles);
+                entry.ancestors_fee =
+                    Capacity::shannons(entry.ancestors_fee.as_u64().saturating_add(fee.as_u64()));
+            }
+            self.sorted_index.insert(entry.as_sorted_key());
+        }
+    }
+
     pub(crate) fn resolve_conflict(&mut self, tx: &TransactionView) -> Vec<ConflictEntry> {
         let inputs = tx.input_pts_iter();
         let mut conflicts = Vec::new();
@@ -182,10 +255,21 @@ impl PendingQueue {
         self.inner.get(id).map(|entry| entry.transaction())
     }
 
+    // The descendants stay in the queue, their ancestors state is updated.
     pub(crate) fn remove_entry(&mut self, id: &ProposalShortId) -> Option<TxEntry> {
         let removed = self.inner.remove(id);
 
         if let Some(ref entry) = removed {
+            for desc_id in self.links.calc_descendants(id) {
+                if let Some(desc_entry) = self.inner.get_mut(&desc_id) {
+                    let deleted = self.sorted_index.remove(&desc_entry.as_sorted_key());
+                    debug_assert!(deleted, "pending inconsistent");
+                    desc_entry.sub_entry_weight(entry);
+                    self.sorted_index.insert(desc_entry.as_sorted_key());
+                }
+            }
+            self.sorted_index.remove(&entry.as_sorted_key());
+            self.remove_links(id);
             self.remove_entry_relation(entry);
         }
 
@@ -194,12 +278,14 @@ impl PendingQueue {
 
     pub(crate) fn remove_entry_and_descendants(&mut self, id: &ProposalShortId) -> Vec<TxEntry> {
         let mut removed = Vec::new();
-        if let Some(entry) = self.inner.remove(id) {
-            let descendants = self.get_descendants(&entry);
-            self.remove_entry_relation(&entry);
-            removed.push(entry);
-            for id in descendants {
-                if let Some(entry) = self.remove_entry(&id) {
+        if self.inner.contains_key(id) {
+            let mut removed_ids = vec![id.to_owned()];
+            removed_ids.extend(self.links.calc_descendants(id));
+            for id in removed_ids {
+                if let Some(entry) = self.inner.remove(&id) {
+                    self.sorted_index.remove(&entry.as_sorted_key());
+                    self.remove_links(&id);
+                    self.remove_entry_relation(&entry);
                     removed.push(entry);
                 }
             }
@@ -207,27 +293,36 @@ impl PendingQueue {
         removed
     }
 
-    pub(crate) fn get_descendants(&self, entry: &TxEntry) -> HashSet<ProposalShortId> {
-        let mut entries: VecDeque<&TxEntry> = VecDeque::new();
-        entries.push_back(entry);
-
-        let mut descendants = HashSet::new();
-        while let Some(entry) = entries.pop_front() {
-            let outputs = entry.transaction().output_pts();
-
-            for output in outputs {
-                if let Some(ids) = self.outputs.get(&output) {
-                    for id in ids {
-                        if descendants.insert(id.clone()) {
-                            if let Some(entry) = self.inner.get(id) {
-                                entries.push_back(entry);
-                            }
-                        }
-                    }
-                }
+    fn remove_links(&mut self, id: &ProposalShortId) {
+        if let Some(links) = self.links.remove(id) {
+            for parent in &links.parents {
+                self.links.remove_child(parent, id);
+            }
+            for child in &links.children {
+                self.links.remove_parent(child, id);
             }
         }
-        descendants
+    }
+
+    /// Returns the in-pool transactions which spend or use the outputs of the entry, directly
+    /// or indirectly.
+    pub(crate) fn get_descendants(&self, entry: &TxEntry) -> HashSet<ProposalShortId> {
+        self.links.calc_descendants(&entry.proposal_short_id())
+    }
+
+    /// Returns the in-pool transactions whose outputs are spent or used by the entry, directly
+    /// or indirectly.
+    #[cfg(test)]
+    pub(crate) fn get_ancestors(&self, entry: &TxEntry) -> HashSet<ProposalShortId> {
+        self.links.calc_ancestors(&entry.proposal_short_id())
+    }
+
+    /// Returns the ancestors score sort keys of all the entries, from the lowest to the highest.
+    ///
+    /// The index is updated when the entries are added or removed, the lowest one is the first
+    /// to evict.
+    pub(crate) fn score_sorted_keys(&self) -> impl Iterator<Item = &AncestorsScoreSortKey> {
+        self.sorted_index.iter()
     }
 
     pub(crate) fn remove_entry_relation(&mut self, entry: &TxEntry) {
@@ -273,22 +368,13 @@ impl PendingQueue {
         &mut self,
         mut predicate: P,
     ) -> Vec<TxEntry> {
-        let entries = self.entries();
-        let mut removed = Vec::new();
-        for entry in entries {
-            if predicate(entry.key(), entry.get()) {
-                removed.push(entry.remove());
-            }
-        }
-        for entry in &removed {
-            self.remove_entry_relation(entry);
-        }
-
-        removed
-    }
-
-    pub fn entries(&mut self) -> LinkedHashMapEntries<ProposalShortId, TxEntry> {
-        self.inner.entries()
+        let ids: Vec<_> = self
+            .inner
+            .iter()
+            .filter(|(id, entry)| predicate(id, entry))
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.iter().filter_map(|id| self.remove_entry(id)).collect()
     }
 
     // fill proposal txs
@@ -319,6 +405,8 @@ impl PendingQueue {
         self.inputs.clear();
         self.header_deps.clear();
         self.outputs.clear();
+        self.links.clear();
+        self.sorted_index.clear();
         txs
     }
 }
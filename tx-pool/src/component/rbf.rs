@@ -0,0 +1,108 @@
+//! Replace-by-fee rules for the fresh transactions conflicting with the pending transactions.
+use crate::component::{entry::TxEntry, pending::PendingQueue};
+use crate::error::Reject;
+use ckb_types::{
+    core::FeeRate,
+    packed::{OutPoint, ProposalShortId},
+};
+use std::collections::HashSet;
+
+/// The max count of the transactions which can be replaced by a single replace-by-fee
+/// transaction.
+pub(crate) const MAX_REPLACEMENT_CANDIDATES: usize = 100;
+
+/// Checks whether the fresh entry can replace the conflicting transactions in `pending`.
+///
+/// Returns the pending transactions to be replaced, which are the transactions spending the
+/// same inputs or using the inputs as cell deps, and all their descendants. The transactions in
+/// `gap` have been proposed and can not be replaced.
+pub(crate) fn check_replacement(
+    pending: &PendingQueue,
+    gap: &PendingQueue,
+    entry: &TxEntry,
+    min_fee_bump_percent: u64,
+    min_fee_rate_bump_percent: u64,
+) -> Result<HashSet<ProposalShortId>, Reject> {
+    let tx = entry.transaction();
+    let mut conflicts = HashSet::new();
+    for input in tx.input_pts_iter() {
+        // the proposed transactions are protected
+        if gap.inputs.contains_key(&input) || gap.deps.contains_key(&input) {
+            return Err(Reject::RBFRejected(format!(
+                "input {} is used by a proposed transaction",
+                input
+            )));
+        }
+        if let Some(ids) = pending.inputs.get(&input) {
+            conflicts.extend(ids.iter().cloned());
+        }
+        if let Some(ids) = pending.deps.get(&input) {
+            conflicts.extend(ids.iter().cloned());
+        }
+    }
+    if conflicts.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let mut replaced = HashSet::new();
+    let mut max_conflict_fee_rate = FeeRate::zero();
+    for id in &conflicts {
+        if let Some(conflict) = pending.get(id) {
+            max_conflict_fee_rate = max_conflict_fee_rate.max(conflict.fee_rate());
+            replaced.insert(id.clone());
+            replaced.extend(pending.get_descendants(conflict));
+        }
+    }
+    if replaced.len() > MAX_REPLACEMENT_CANDIDATES {
+        return Err(Reject::RBFRejected(format!(
+            "replaces {} transactions, exceeds the limit {}",
+            replaced.len(),
+            MAX_REPLACEMENT_CANDIDATES
+        )));
+    }
+    let is_replaced = |out_point: &OutPoint| {
+        replaced.contains(&ProposalShortId::from_tx_hash(&out_point.tx_hash()))
+    };
+    if tx.input_pts_iter().any(|input| is_replaced(&input))
+        || entry.related_dep_out_points().any(is_replaced)
+    {
+        return Err(Reject::RBFRejected(
+            "spends or depends on the transaction it replaces".to_owned(),
+        ));
+    }
+
+    let replaced_fee = replaced
+        .iter()
+        .filter_map(|id| pending.get(id))
+        .fold(0u64, |fee, replaced| {
+            fee.saturating_add(replaced.fee.as_u64())
+        });
+    let min_fee = bump(replaced_fee, min_fee_bump_percent).max(replaced_fee.saturating_add(1));
+    if entry.fee.as_u64() < min_fee {
+        return Err(Reject::RBFRejected(format!(
+            "the fee should be {} shannons at least to replace {} transactions, but only got {}",
+            min_fee,
+            replaced.len(),
+            entry.fee.as_u64()
+        )));
+    }
+
+    let min_fee_rate = bump(max_conflict_fee_rate.as_u64(), min_fee_rate_bump_percent)
+        .max(max_conflict_fee_rate.as_u64().saturating_add(1));
+    if entry.fee_rate().as_u64() < min_fee_rate {
+        return Err(Reject::RBFRejected(format!(
+            "the fee rate should be {} shannons/KB at least, but only got {}",
+            min_fee_rate,
+            entry.fee_rate()
+        )));
+    }
+
+    Ok(replaced)
+}
+
+// Increases the value by the percentage, rounding up.
+fn bump(value: u64, percent: u64) -> u64 {
+    let bumped = (value as u128) * (100 + percent as u128);
+    let bumped = (bumped + 99) / 100;
+    bumped.min(u64::MAX as u128) as u64
+}
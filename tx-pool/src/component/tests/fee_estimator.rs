@@ -0,0 +1,134 @@
+use ckb_hash::blake2b_256;
+use ckb_types::{
+    core::FeeRate,
+    packed::{Byte32, ProposalShortId},
+};
+
+use crate::component::fee_estimator::FeeEstimator;
+
+const HIGH_FEE_RATE: u64 = 10_000;
+const LOW_FEE_RATE: u64 = 2_000;
+
+fn tx_hash(fee_rate: u64, tip_number: u64) -> Byte32 {
+    let mut data = fee_rate.to_le_bytes().to_vec();
+    data.extend_from_slice(&tip_number.to_le_bytes());
+    Byte32::new(blake2b_256(&data))
+}
+
+// Every block, a high fee rate tx and a low fee rate tx enter the pool, the high one is
+// proposed in the next block and committed after 3 blocks, the low one is proposed after 18
+// blocks and committed after 20 blocks.
+fn build_estimator(blocks: u64) -> FeeEstimator {
+    let mut estimator = FeeEstimator::new(2);
+    for tip_number in 0..blocks {
+        for fee_rate in &[HIGH_FEE_RATE, LOW_FEE_RATE] {
+            estimator.track_tx(
+                tx_hash(*fee_rate, tip_number),
+                FeeRate::from_u64(*fee_rate),
+                tip_number,
+            );
+        }
+        let number = tip_number + 1;
+        let mut proposals = vec![ProposalShortId::from_tx_hash(&tx_hash(
+            HIGH_FEE_RATE,
+            tip_number,
+        ))];
+        if number >= 18 {
+            proposals.push(ProposalShortId::from_tx_hash(&tx_hash(
+                LOW_FEE_RATE,
+                number - 18,
+            )));
+        }
+        let mut committed = Vec::new();
+        if number >= 3 {
+            committed.push(tx_hash(HIGH_FEE_RATE, number - 3));
+        }
+        if number >= 20 {
+            committed.push(tx_hash(LOW_FEE_RATE, number - 20));
+        }
+        estimator.process_block(number, proposals, committed);
+    }
+    estimator
+}
+
+#[test]
+fn test_not_enough_samples() {
+    let estimator = FeeEstimator::new(2);
+    assert_eq!(estimator.estimate(10, 85, 0), None);
+}
+
+#[test]
+fn test_estimate() {
+    let estimator = build_estimator(100);
+
+    let estimate = estimator.estimate(5, 85, 100).unwrap();
+    assert_eq!(estimate.fee_rate, FeeRate::from_u64(HIGH_FEE_RATE));
+    assert_eq!(estimate.target_blocks, 5);
+    assert_eq!(estimate.confidence, 100);
+
+    let estimate = estimator.estimate(30, 85, 100).unwrap();
+    assert_eq!(estimate.fee_rate, FeeRate::from_u64(LOW_FEE_RATE));
+
+    // a tx can not be committed before the proposal window
+    let estimate = estimator.estimate(1, 85, 100).unwrap();
+    assert_eq!(estimate.target_blocks, 3);
+    assert_eq!(estimate.fee_rate, FeeRate::from_u64(HIGH_FEE_RATE));
+}
+
+#[test]
+fn test_processed_block_is_not_counted_twice() {
+    let mut estimator = build_estimator(100);
+    let expected = estimator.estimate(30, 85, 100);
+
+    // reorg: the block 100 is attached again with a low fee rate tx which waited 1 block
+    estimator.track_tx(
+        tx_hash(LOW_FEE_RATE, 1000),
+        FeeRate::from_u64(LOW_FEE_RATE),
+        99,
+    );
+    estimator.process_block(100, vec![], vec![tx_hash(LOW_FEE_RATE, 1000)]);
+    assert_eq!(estimator.estimate(30, 85, 100), expected);
+}
+
+#[test]
+fn test_untracked_tx_is_not_recorded() {
+    let mut estimator = FeeEstimator::new(2);
+    let hash = tx_hash(HIGH_FEE_RATE, 0);
+    estimator.track_tx(hash.clone(), FeeRate::from_u64(HIGH_FEE_RATE), 0);
+    // e.g. the tx is replaced, and a tx with the same hash is committed later
+    estimator.untrack_tx(&hash);
+    estimator.process_block(3, vec![], vec![hash]);
+    assert_eq!(estimator.estimate(5, 85, 3), None);
+}
+
+#[test]
+fn test_unproposed_tx_fails_before_target() {
+    let mut estimator = build_estimator(100);
+    let mut proposed_estimator = estimator.clone();
+    let hashes: Vec<_> = (0..20).map(|i| tx_hash(HIGH_FEE_RATE, 1000 + i)).collect();
+    for hash in &hashes {
+        estimator.track_tx(hash.clone(), FeeRate::from_u64(HIGH_FEE_RATE), 97);
+        proposed_estimator.track_tx(hash.clone(), FeeRate::from_u64(HIGH_FEE_RATE), 97);
+    }
+    proposed_estimator.process_block(98, hashes.iter().map(ProposalShortId::from_tx_hash), vec![]);
+
+    // The txs entered at 97 and not proposed by 100 can not be committed before 103.
+    assert_eq!(estimator.estimate(5, 85, 100), None);
+    let estimate = proposed_estimator.estimate(5, 85, 100).unwrap();
+    assert_eq!(estimate.fee_rate, FeeRate::from_u64(HIGH_FEE_RATE));
+}
+
+#[test]
+fn test_dump_and_restore() {
+    let estimator = build_estimator(100);
+    let data = estimator.dump().unwrap();
+
+    let mut restored = FeeEstimator::new(2);
+    assert!(restored.restore(&data).unwrap());
+    assert_eq!(
+        restored.estimate(30, 85, 100),
+        estimator.estimate(30, 85, 100)
+    );
+
+    assert!(restored.restore(b"{}").is_err());
+}
@@ -1,6 +1,9 @@
 mod chunk;
 mod container;
+mod fee_estimator;
 mod pending;
 mod proposed;
+mod rbf;
 mod recent_reject;
+mod selection;
 mod util;
@@ -2,7 +2,7 @@ use crate::component::tests::util::{
     build_tx, build_tx_with_dep, build_tx_with_header_dep, MOCK_CYCLES, MOCK_FEE, MOCK_SIZE,
 };
 use crate::component::{entry::TxEntry, pending::PendingQueue};
-use ckb_types::{h256, packed::Byte32, prelude::*};
+use ckb_types::{core::Capacity, h256, packed::Byte32, prelude::*};
 use std::collections::HashSet;
 
 #[test]
@@ -227,3 +227,91 @@ fn test_fill_proposals() {
     queue.fill_proposals(2, &exclusion, &mut ret);
     assert_eq!(ret, HashSet::from_iter(vec![id1, id3]));
 }
+
+#[test]
+fn test_ancestors_scores() {
+    let mut queue = PendingQueue::new();
+    let tx1 = build_tx(vec![(&Byte32::zero(), 1)], 1);
+    let tx2 = build_tx(vec![(&tx1.hash(), 0)], 1);
+    let tx3 = build_tx(vec![(&h256!("0x1").pack(), 1)], 1);
+
+    // tx2 pays a high fee, but its ancestor tx1 pays nothing
+    let entry1 = TxEntry::dummy_resolve(tx1.clone(), 0, Capacity::shannons(0), 100);
+    let entry2 = TxEntry::dummy_resolve(tx2.clone(), 0, Capacity::shannons(1000), 100);
+    let entry3 = TxEntry::dummy_resolve(tx3.clone(), 0, Capacity::shannons(200), 100);
+    assert!(queue.add_entry(entry1));
+    assert!(queue.add_entry(entry2));
+    assert!(queue.add_entry(entry3));
+
+    assert_eq!(
+        queue.get_ancestors(queue.get(&tx2.proposal_short_id()).unwrap()),
+        HashSet::from_iter(vec![tx1.proposal_short_id()])
+    );
+
+    let scores: Vec<_> = queue.score_sorted_keys().cloned().collect();
+    let key2 = scores
+        .iter()
+        .find(|key| key.id == tx2.proposal_short_id())
+        .unwrap();
+    assert_eq!(key2.ancestors_fee, Capacity::shannons(1000));
+    assert_eq!(key2.ancestors_size, 200);
+    assert_eq!(key2.ancestors_vbytes, 200);
+    assert_eq!(
+        scores.iter().map(|key| key.id.clone()).collect::<Vec<_>>(),
+        vec![
+            tx1.proposal_short_id(),
+            tx3.proposal_short_id(),
+            tx2.proposal_short_id()
+        ]
+    );
+}
+
+#[test]
+fn test_ancestors_scores_update() {
+    let mut queue = PendingQueue::new();
+    let tx1 = build_tx(vec![(&Byte32::zero(), 1)], 1);
+    let tx2 = build_tx(vec![(&tx1.hash(), 0)], 1);
+    let tx3 = build_tx(vec![(&tx2.hash(), 0)], 1);
+    let entry1 = TxEntry::dummy_resolve(tx1.clone(), 0, Capacity::shannons(0), 100);
+    let entry2 = TxEntry::dummy_resolve(tx2.clone(), 0, Capacity::shannons(1000), 100);
+    let entry3 = TxEntry::dummy_resolve(tx3.clone(), 0, Capacity::shannons(1000), 100);
+    assert!(queue.add_entry(entry1));
+    assert!(queue.add_entry(entry2));
+    assert!(queue.add_entry(entry3));
+
+    let entry3 = queue.get(&tx3.proposal_short_id()).unwrap();
+    assert_eq!(entry3.ancestors_count, 3);
+    assert_eq!(entry3.ancestors_fee, Capacity::shannons(2000));
+    assert_eq!(
+        queue.score_sorted_keys().next().unwrap().id,
+        tx1.proposal_short_id()
+    );
+
+    // tx1 leaves the queue, e.g., it is proposed
+    let entry1 = queue.remove_entry(&tx1.proposal_short_id()).unwrap();
+    let entry3 = queue.get(&tx3.proposal_short_id()).unwrap();
+    assert_eq!(entry3.ancestors_count, 2);
+    assert_eq!(entry3.ancestors_size, 200);
+    assert_eq!(queue.score_sorted_keys().count(), 2);
+
+    // tx1 returns, e.g., its proposal is detached
+    assert!(queue.add_entry(entry1));
+    let entry2 = queue.get(&tx2.proposal_short_id()).unwrap();
+    assert_eq!(entry2.ancestors_count, 2);
+    let entry3 = queue.get(&tx3.proposal_short_id()).unwrap();
+    assert_eq!(entry3.ancestors_count, 3);
+    assert_eq!(entry3.ancestors_fee, Capacity::shannons(2000));
+    assert_eq!(
+        queue.get_descendants(queue.get(&tx1.proposal_short_id()).unwrap()),
+        HashSet::from_iter(vec![tx2.proposal_short_id(), tx3.proposal_short_id()])
+    );
+
+    queue.remove_entry_and_descendants(&tx2.proposal_short_id());
+    assert_eq!(
+        queue
+            .score_sorted_keys()
+            .map(|key| key.id.clone())
+            .collect::<Vec<_>>(),
+        vec![tx1.proposal_short_id()]
+    );
+}
@@ -0,0 +1,147 @@
+use crate::component::tests::util::{build_tx, MOCK_CYCLES};
+use crate::component::{
+    entry::TxEntry,
+    pending::PendingQueue,
+    rbf::{check_replacement, MAX_REPLACEMENT_CANDIDATES},
+};
+use crate::error::Reject;
+use ckb_types::{
+    core::{Capacity, TransactionView},
+    h256,
+    packed::Byte32,
+    prelude::*,
+};
+
+// With the size 1000, the fee rate in shannons/KB equals to the fee.
+const SIZE: usize = 1000;
+const FEE_BUMP: u64 = 10;
+const FEE_RATE_BUMP: u64 = 10;
+
+fn entry(tx: TransactionView, fee: u64, size: usize) -> TxEntry {
+    TxEntry::dummy_resolve(tx, MOCK_CYCLES, Capacity::shannons(fee), size)
+}
+
+fn check(pending: &PendingQueue, gap: &PendingQueue, entry: &TxEntry) -> Result<bool, Reject> {
+    check_replacement(pending, gap, entry, FEE_BUMP, FEE_RATE_BUMP)
+        .map(|replaced| !replaced.is_empty())
+}
+
+#[test]
+fn test_no_conflict() {
+    let mut pending = PendingQueue::new();
+    let parent: Byte32 = h256!("0x1").pack();
+    assert!(pending.add_entry(entry(build_tx(vec![(&parent, 0)], 1), 1000, SIZE)));
+
+    let fresh = entry(build_tx(vec![(&parent, 1)], 1), 1000, SIZE);
+    assert!(!check(&pending, &PendingQueue::new(), &fresh).unwrap());
+}
+
+#[test]
+fn test_min_fee_bump() {
+    let mut pending = PendingQueue::new();
+    let parent: Byte32 = h256!("0x1").pack();
+    assert!(pending.add_entry(entry(build_tx(vec![(&parent, 0)], 1), 1000, SIZE)));
+    let gap = PendingQueue::new();
+
+    let same_fee = entry(build_tx(vec![(&parent, 0)], 2), 1000, SIZE);
+    assert!(matches!(
+        check(&pending, &gap, &same_fee),
+        Err(Reject::RBFRejected(_))
+    ));
+
+    let low_bump = entry(build_tx(vec![(&parent, 0)], 2), 1099, SIZE);
+    assert!(matches!(
+        check(&pending, &gap, &low_bump),
+        Err(Reject::RBFRejected(_))
+    ));
+
+    let enough_bump = entry(build_tx(vec![(&parent, 0)], 2), 1100, SIZE);
+    assert!(check(&pending, &gap, &enough_bump).unwrap());
+}
+
+#[test]
+fn test_min_fee_rate_bump() {
+    let mut pending = PendingQueue::new();
+    let parent: Byte32 = h256!("0x1").pack();
+    assert!(pending.add_entry(entry(build_tx(vec![(&parent, 0)], 1), 1000, SIZE)));
+
+    // pays a higher fee, but the fee rate is lower
+    let large = entry(build_tx(vec![(&parent, 0)], 2), 2000, SIZE * 3);
+    assert!(matches!(
+        check(&pending, &PendingQueue::new(), &large),
+        Err(Reject::RBFRejected(_))
+    ));
+}
+
+#[test]
+fn test_replaced_descendants_are_counted() {
+    let mut pending = PendingQueue::new();
+    let parent: Byte32 = h256!("0x1").pack();
+    let tx1 = build_tx(vec![(&parent, 0)], 1);
+    let tx2 = build_tx(vec![(&tx1.hash(), 0)], 1);
+    assert!(pending.add_entry(entry(tx1, 1000, SIZE)));
+    assert!(pending.add_entry(entry(tx2, 1000, SIZE)));
+    let gap = PendingQueue::new();
+
+    // must pay more than the fees of both tx1 and its descendant tx2
+    let fresh = entry(build_tx(vec![(&parent, 0)], 2), 1500, SIZE);
+    assert!(matches!(
+        check(&pending, &gap, &fresh),
+        Err(Reject::RBFRejected(_))
+    ));
+    let fresh = entry(build_tx(vec![(&parent, 0)], 2), 2200, SIZE);
+    assert!(check(&pending, &gap, &fresh).unwrap());
+}
+
+#[test]
+fn test_max_replacement_candidates() {
+    let mut pending = PendingQueue::new();
+    let parent: Byte32 = h256!("0x1").pack();
+    let count = MAX_REPLACEMENT_CANDIDATES + 1;
+    for index in 0..count {
+        let tx = build_tx(vec![(&parent, index as u32)], 1);
+        assert!(pending.add_entry(entry(tx, 1, SIZE)));
+    }
+
+    let inputs = (0..count).map(|index| (&parent, index as u32)).collect();
+    let fresh = entry(build_tx(inputs, 1), u64::from(u32::MAX), SIZE);
+    assert!(matches!(
+        check(&pending, &PendingQueue::new(), &fresh),
+        Err(Reject::RBFRejected(_))
+    ));
+}
+
+#[test]
+fn test_spend_replaced_ancestor() {
+    let mut pending = PendingQueue::new();
+    let parent: Byte32 = h256!("0x1").pack();
+    let tx1 = build_tx(vec![(&parent, 0)], 2);
+    let tx2 = build_tx(vec![(&tx1.hash(), 0)], 1);
+    let tx2_hash = tx2.hash();
+    assert!(pending.add_entry(entry(tx1, 1000, SIZE)));
+    assert!(pending.add_entry(entry(tx2, 1000, SIZE)));
+
+    // conflicts with tx1 and spends its descendant tx2, which is replaced as well
+    let fresh = entry(
+        build_tx(vec![(&parent, 0), (&tx2_hash, 0)], 1),
+        10_000,
+        SIZE,
+    );
+    assert!(matches!(
+        check(&pending, &PendingQueue::new(), &fresh),
+        Err(Reject::RBFRejected(_))
+    ));
+}
+
+#[test]
+fn test_proposed_tx_is_protected() {
+    let mut gap = PendingQueue::new();
+    let parent: Byte32 = h256!("0x1").pack();
+    assert!(gap.add_entry(entry(build_tx(vec![(&parent, 0)], 1), 1000, SIZE)));
+
+    let fresh = entry(build_tx(vec![(&parent, 0)], 2), 10_000, SIZE);
+    assert!(matches!(
+        check(&PendingQueue::new(), &gap, &fresh),
+        Err(Reject::RBFRejected(_))
+    ));
+}
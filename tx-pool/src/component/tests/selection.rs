@@ -0,0 +1,124 @@
+use crate::component::tests::util::{build_tx, DEFAULT_MAX_ANCESTORS_COUNT};
+use crate::component::{
+    commit_txs_scanner::CommitTxsScanner, entry::TxEntry, proposed::ProposedPool,
+};
+use crate::selection::{
+    AncestorFeeRatePolicy, PrioritisedPolicy, TxSelectionPolicy, FEE_DELTA_EXPIRY_MILLIS,
+    MAX_FEE_DELTAS,
+};
+use ckb_hash::blake2b_256;
+use ckb_types::{
+    core::{Capacity, TransactionView},
+    packed::Byte32,
+};
+use faketime::unix_time_as_millis;
+
+const CYCLES: u64 = 5_000_000;
+const SIZE: usize = 200;
+
+struct ExcludeTx(Byte32);
+
+impl TxSelectionPolicy for ExcludeTx {
+    fn is_selectable(&self, entry: &TxEntry) -> bool {
+        entry.transaction().hash() != self.0
+    }
+}
+
+fn add_entry(pool: &mut ProposedPool, tx: &TransactionView, fee: u64) {
+    pool.add_entry(TxEntry::dummy_resolve(
+        tx.clone(),
+        CYCLES,
+        Capacity::shannons(fee),
+        SIZE,
+    ))
+    .unwrap();
+}
+
+fn commit_txs(pool: &ProposedPool, policy: &dyn TxSelectionPolicy) -> Vec<Byte32> {
+    CommitTxsScanner::new(pool, policy)
+        .txs_to_commit(usize::MAX, u64::MAX)
+        .0
+        .iter()
+        .map(|entry| entry.transaction().hash())
+        .collect()
+}
+
+#[test]
+fn test_prioritised_policy() {
+    let tx1 = build_tx(vec![(&Byte32::zero(), 1)], 1);
+    let tx2 = build_tx(vec![(&Byte32::zero(), 2)], 1);
+    let tx3 = build_tx(vec![(&tx2.hash(), 0)], 1);
+
+    let mut pool = ProposedPool::new(DEFAULT_MAX_ANCESTORS_COUNT);
+    add_entry(&mut pool, &tx1, 300);
+    add_entry(&mut pool, &tx2, 100);
+    add_entry(&mut pool, &tx3, 100);
+
+    assert_eq!(
+        commit_txs(&pool, &AncestorFeeRatePolicy),
+        vec![tx1.hash(), tx2.hash(), tx3.hash()]
+    );
+
+    let policy = PrioritisedPolicy::default();
+    assert!(policy.prioritise(tx2.hash(), 1000).is_ok());
+    assert_eq!(
+        commit_txs(&pool, &policy),
+        vec![tx2.hash(), tx1.hash(), tx3.hash()]
+    );
+
+    // the deltas accumulate
+    assert!(policy.prioritise(tx2.hash(), -1000).is_ok());
+    assert!(policy.prioritise(tx1.hash(), -300).is_ok());
+    assert_eq!(
+        commit_txs(&pool, &policy),
+        vec![tx2.hash(), tx3.hash(), tx1.hash()]
+    );
+}
+
+#[test]
+fn test_excluded_with_descendants() {
+    let tx1 = build_tx(vec![(&Byte32::zero(), 1)], 1);
+    let tx2 = build_tx(vec![(&tx1.hash(), 0)], 1);
+    let tx3 = build_tx(vec![(&Byte32::zero(), 2)], 1);
+
+    let mut pool = ProposedPool::new(DEFAULT_MAX_ANCESTORS_COUNT);
+    add_entry(&mut pool, &tx1, 100);
+    add_entry(&mut pool, &tx2, 300);
+    add_entry(&mut pool, &tx3, 200);
+
+    assert_eq!(commit_txs(&pool, &ExcludeTx(tx1.hash())), vec![tx3.hash()]);
+    assert_eq!(
+        commit_txs(&pool, &ExcludeTx(tx2.hash())),
+        vec![tx3.hash(), tx1.hash()]
+    );
+}
+
+#[test]
+fn test_fee_deltas_bounded() {
+    let tx1 = build_tx(vec![(&Byte32::zero(), 1)], 1);
+    let tx2 = build_tx(vec![(&Byte32::zero(), 2)], 1);
+
+    let mut pool = ProposedPool::new(DEFAULT_MAX_ANCESTORS_COUNT);
+    add_entry(&mut pool, &tx1, 300);
+    add_entry(&mut pool, &tx2, 100);
+
+    let policy = PrioritisedPolicy::default();
+    assert!(policy.is_noop());
+    // an expired delta does not rank the transaction
+    assert!(policy.prioritise_at(tx2.hash(), 1000, 0).is_ok());
+    assert!(!policy.is_noop());
+    assert_eq!(commit_txs(&pool, &policy), vec![tx1.hash(), tx2.hash()]);
+
+    let now = unix_time_as_millis();
+    for index in 0..MAX_FEE_DELTAS {
+        let tx_hash = Byte32::new(blake2b_256(&index.to_le_bytes()));
+        assert!(policy.prioritise_at(tx_hash, 1, now).is_ok());
+    }
+    // the expired delta of tx2 has been dropped, and no more transactions can be prioritised
+    assert!(policy.prioritise_at(tx2.hash(), 1000, now).is_err());
+    // all the deltas set at `now` expire, which makes room for tx2
+    assert!(policy
+        .prioritise_at(tx2.hash(), 1000, now + FEE_DELTA_EXPIRY_MILLIS)
+        .is_ok());
+    assert_eq!(commit_txs(&pool, &policy), vec![tx2.hash(), tx1.hash()]);
+}
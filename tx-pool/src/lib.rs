@@ -9,11 +9,13 @@ pub mod error;
 mod persisted;
 pub mod pool;
 mod process;
+pub mod selection;
 pub mod service;
 mod util;
 
 pub use ckb_jsonrpc_types::BlockTemplate;
 pub use component::entry::TxEntry;
+pub use component::fee_estimator::{FeeRateEstimate, DEFAULT_CONFIDENCE, MAX_TARGET_BLOCKS};
 pub use pool::TxPool;
 pub use process::PlugTarget;
 pub use service::{TxPoolController, TxPoolServiceBuilder};
@@ -1,43 +1,37 @@
+use crate::block_assembler::PayoutTarget;
+use crate::component::fee_estimator;
 use crate::TxPool;
 use ckb_error::{AnyError, OtherError};
+use ckb_logger::info;
 use ckb_types::{
-    core::TransactionView,
-    packed::{TransactionVec, TransactionVecReader},
+    core::{Capacity, TransactionView},
+    packed::{TransactionReader, TransactionVec, TransactionVecReader},
     prelude::*,
 };
+use ckb_verification::{cache::Suspended, TransactionSnapshot};
 use std::{
     fs::OpenOptions,
     io::{Read as _, Write as _},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
 /// The version of the persisted tx-pool data.
 pub(crate) const VERSION: u32 = 1;
+/// The version of the persisted suspended txs data.
+pub(crate) const SUSPENDED_VERSION: u32 = 1;
+/// The version of the persisted payout rotation data.
+pub(crate) const PAYOUT_ROTATION_VERSION: u32 = 1;
+
+/// A tx waiting in the chunk queue, with its suspended script verification state if any.
+pub(crate) type SuspendedTx = (TransactionView, Option<Suspended>);
 
 impl TxPool {
     pub(crate) fn load_from_file(&self) -> Result<Vec<TransactionView>, AnyError> {
         let mut persisted_data_file = self.config.persisted_data.clone();
         persisted_data_file.set_extension(format!("v{}", VERSION));
 
-        if persisted_data_file.exists() {
-            let mut file = OpenOptions::new()
-                .read(true)
-                .open(&persisted_data_file)
-                .map_err(|err| {
-                    let errmsg = format!(
-                        "Failed to open the tx-pool persisted data file [{:?}], cause: {}",
-                        persisted_data_file, err
-                    );
-                    OtherError::new(errmsg)
-                })?;
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer).map_err(|err| {
-                let errmsg = format!(
-                    "Failed to read the tx-pool persisted data file [{:?}], cause: {}",
-                    persisted_data_file, err
-                );
-                OtherError::new(errmsg)
-            })?;
-
+        if let Some(buffer) = load_file(&persisted_data_file, "tx-pool")? {
             let persisted_data = TransactionVecReader::from_slice(&buffer)
                 .map_err(|err| {
                     let errmsg = format!(
@@ -61,37 +55,385 @@ impl TxPool {
         let mut persisted_data_file = self.config.persisted_data.clone();
         persisted_data_file.set_extension(format!("v{}", VERSION));
 
-        let mut file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(&persisted_data_file)
-            .map_err(|err| {
+        let txs = TransactionVec::new_builder()
+            .extend(self.drain_all_transactions().iter().map(|tx| tx.data()))
+            .build();
+
+        persist_file(&persisted_data_file, "tx-pool", txs.as_slice())
+    }
+
+    fn fee_estimator_file(&self) -> PathBuf {
+        let mut fee_estimator_file = self.config.persisted_data.clone();
+        fee_estimator_file.set_extension(format!("fee_estimator.v{}", fee_estimator::VERSION));
+        fee_estimator_file
+    }
+
+    pub(crate) fn load_fee_estimator(&mut self) -> Result<(), AnyError> {
+        let fee_estimator_file = self.fee_estimator_file();
+
+        if let Some(buffer) = load_file(&fee_estimator_file, "fee estimator")? {
+            let compatible = self.fee_estimator.restore(&buffer).map_err(|err| {
                 let errmsg = format!(
-                    "Failed to open the tx-pool persisted data file [{:?}], cause: {}",
-                    persisted_data_file, err
+                    "The fee estimator persisted data file [{:?}] is broken, cause: {}",
+                    fee_estimator_file, err
                 );
                 OtherError::new(errmsg)
             })?;
+            if !compatible {
+                let errmsg = format!(
+                    "The fee estimator persisted data file [{:?}] is incompatible",
+                    fee_estimator_file
+                );
+                return Err(OtherError::new(errmsg).into());
+            }
+        }
+        Ok(())
+    }
 
-        let txs = TransactionVec::new_builder()
-            .extend(self.drain_all_transactions().iter().map(|tx| tx.data()))
-            .build();
+    pub(crate) fn save_fee_estimator(&self) -> Result<(), AnyError> {
+        let fee_estimator_file = self.fee_estimator_file();
+
+        let data = self.fee_estimator.dump().map_err(|err| {
+            let errmsg = format!("Failed to serialize the fee estimator data, cause: {}", err);
+            OtherError::new(errmsg)
+        })?;
+
+        persist_file(&fee_estimator_file, "fee estimator", &data)
+    }
+
+    fn suspended_txs_file(&self) -> PathBuf {
+        let mut suspended_txs_file = self.config.persisted_data.clone();
+        suspended_txs_file.set_extension(format!("suspended.v{}", SUSPENDED_VERSION));
+        suspended_txs_file
+    }
 
-        file.write_all(txs.as_slice()).map_err(|err| {
+    /// Loads the txs in the chunk queue and their suspended verification states.
+    ///
+    /// The states serialized by an incompatible script VM are dropped, such txs are verified
+    /// from the beginning.
+    pub(crate) fn load_suspended_txs(&self) -> Result<Vec<SuspendedTx>, AnyError> {
+        let suspended_txs_file = self.suspended_txs_file();
+        let buffer = match load_file(&suspended_txs_file, "suspended txs")? {
+            Some(buffer) => buffer,
+            None => return Ok(Vec::new()),
+        };
+
+        let (txs, incompatible) = decode_suspended_txs(&buffer).map_err(|cause| {
             let errmsg = format!(
-                "Failed to write the tx-pool persisted data into file [{:?}], cause: {}",
-                persisted_data_file, err
+                "The suspended txs persisted data file [{:?}] is broken, cause: {}",
+                suspended_txs_file, cause
             );
             OtherError::new(errmsg)
         })?;
-        file.sync_all().map_err(|err| {
+        if incompatible > 0 {
+            info!(
+                "{} suspended verification states are incompatible with the current script VM, \
+                the txs will be verified from the beginning",
+                incompatible
+            );
+        }
+        Ok(txs)
+    }
+
+    pub(crate) fn save_suspended_txs(&self, txs: &[SuspendedTx]) -> Result<(), AnyError> {
+        let suspended_txs_file = self.suspended_txs_file();
+
+        let data = encode_suspended_txs(txs)?;
+
+        persist_file(&suspended_txs_file, "suspended txs", &data)
+    }
+}
+
+/// Returns the file keeping the blocks found with the payout targets, next to the tx-pool
+/// persisted data.
+pub(crate) fn payout_rotation_file(persisted_data: &Path) -> PathBuf {
+    let mut payout_rotation_file = persisted_data.to_path_buf();
+    payout_rotation_file.set_extension(format!("payout_rotation.v{}", PAYOUT_ROTATION_VERSION));
+    payout_rotation_file
+}
+
+/// Loads the number of the blocks found with the payout targets.
+///
+/// Returns 0 if the file does not exist, or the blocks were found with other payout targets.
+pub(crate) fn load_found_blocks(
+    path: &Path,
+    payout_targets: &[PayoutTarget],
+) -> Result<u64, AnyError> {
+    let buffer = match load_file(path, "payout rotation")? {
+        Some(buffer) => buffer,
+        None => return Ok(0),
+    };
+    let mut data = buffer.as_slice();
+    let found_blocks = read_u64(&mut data).ok_or_else(|| {
+        let errmsg = format!(
+            "The payout rotation persisted data file [{:?}] is broken, cause: truncated found blocks",
+            path
+        );
+        OtherError::new(errmsg)
+    })?;
+    if data == encode_payout_targets(payout_targets).as_slice() {
+        Ok(found_blocks)
+    } else {
+        Ok(0)
+    }
+}
+
+/// Saves the number of the blocks found with the payout targets.
+pub(crate) fn save_found_blocks(
+    path: &Path,
+    payout_targets: &[PayoutTarget],
+    found_blocks: u64,
+) -> Result<(), AnyError> {
+    let mut data = found_blocks.to_le_bytes().to_vec();
+    data.extend_from_slice(&encode_payout_targets(payout_targets));
+    persist_file(path, "payout rotation", &data)
+}
+
+// Reads the whole persisted data file, returns `None` if the file does not exist.
+fn load_file(path: &Path, name: &str) -> Result<Option<Vec<u8>>, AnyError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let mut file = OpenOptions::new().read(true).open(path).map_err(|err| {
+        let errmsg = format!(
+            "Failed to open the {} persisted data file [{:?}], cause: {}",
+            name, path, err
+        );
+        OtherError::new(errmsg)
+    })?;
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer).map_err(|err| {
+        let errmsg = format!(
+            "Failed to read the {} persisted data file [{:?}], cause: {}",
+            name, path, err
+        );
+        OtherError::new(errmsg)
+    })?;
+    Ok(Some(buffer))
+}
+
+// Replaces the persisted data file with the data and syncs it to the disk.
+fn persist_file(path: &Path, name: &str, data: &[u8]) -> Result<(), AnyError> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(path)
+        .map_err(|err| {
             let errmsg = format!(
-                "Failed to sync the tx-pool persisted data file [{:?}], cause: {}",
-                persisted_data_file, err
+                "Failed to open the {} persisted data file [{:?}], cause: {}",
+                name, path, err
             );
             OtherError::new(errmsg)
         })?;
-        Ok(())
+    file.write_all(data).map_err(|err| {
+        let errmsg = format!(
+            "Failed to write the {} persisted data into file [{:?}], cause: {}",
+            name, path, err
+        );
+        OtherError::new(errmsg)
+    })?;
+    file.sync_all().map_err(|err| {
+        let errmsg = format!(
+            "Failed to sync the {} persisted data file [{:?}], cause: {}",
+            name, path, err
+        );
+        OtherError::new(errmsg)
+    })?;
+    Ok(())
+}
+
+// Each tx is encoded as:
+// tx length (u32) | tx | fee (u64) | snapshot length (u32) | snapshot
+fn encode_suspended_txs(txs: &[SuspendedTx]) -> Result<Vec<u8>, AnyError> {
+    let mut data = Vec::new();
+    for (tx, suspended) in txs {
+        write_bytes(&mut data, tx.data().as_slice());
+        match suspended {
+            Some(suspended) => {
+                let snap_data = suspended.snap.to_bytes().map_err(|err| {
+                    let errmsg = format!(
+                        "Failed to serialize the suspended verification state of tx {}, cause: {}",
+                        tx.hash(),
+                        err
+                    );
+                    OtherError::new(errmsg)
+                })?;
+                data.extend_from_slice(&suspended.fee.as_u64().to_le_bytes());
+                write_bytes(&mut data, &snap_data);
+            }
+            None => {
+                data.extend_from_slice(&0u64.to_le_bytes());
+                write_bytes(&mut data, &[]);
+            }
+        }
+    }
+    Ok(data)
+}
+
+// Returns the txs and the count of the dropped incompatible verification states.
+fn decode_suspended_txs(mut data: &[u8]) -> Result<(Vec<SuspendedTx>, usize), String> {
+    let mut txs = Vec::new();
+    let mut incompatible = 0;
+    while !data.is_empty() {
+        let tx_data = read_bytes(&mut data).ok_or_else(|| "truncated tx".to_owned())?;
+        let tx = TransactionReader::from_slice(tx_data)
+            .map_err(|err| err.to_string())?
+            .to_entity()
+            .into_view();
+        let fee = read_u64(&mut data).ok_or_else(|| "truncated fee".to_owned())?;
+        let snap_data = read_bytes(&mut data).ok_or_else(|| "truncated snapshot".to_owned())?;
+        let suspended = if snap_data.is_empty() {
+            None
+        } else {
+            match TransactionSnapshot::from_bytes(snap_data).map_err(|err| err.to_string())? {
+                Some(snap) => Some(Suspended {
+                    fee: Capacity::shannons(fee),
+                    snap: Arc::new(snap),
+                }),
+                None => {
+                    incompatible += 1;
+                    None
+                }
+            }
+        };
+        txs.push((tx, suspended));
+    }
+    Ok((txs, incompatible))
+}
+
+// The payout rotation is encoded as:
+// found blocks (u64) | the payout targets
+// Each payout target is encoded as:
+// lock length (u32) | lock | weight (u64)
+fn encode_payout_targets(payout_targets: &[PayoutTarget]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for target in payout_targets {
+        write_bytes(&mut data, target.lock.as_slice());
+        data.extend_from_slice(&target.weight.to_le_bytes());
+    }
+    data
+}
+
+fn write_bytes(data: &mut Vec<u8>, bytes: &[u8]) {
+    data.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    data.extend_from_slice(bytes);
+}
+
+fn read_bytes<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let mut len = [0u8; 4];
+    len.copy_from_slice(data.get(..4)?);
+    let len = u32::from_le_bytes(len) as usize;
+    let bytes = data.get(4..4 + len)?;
+    *data = &data[4 + len..];
+    Some(bytes)
+}
+
+fn read_u64(data: &mut &[u8]) -> Option<u64> {
+    let mut value = [0u8; 8];
+    value.copy_from_slice(data.get(..8)?);
+    *data = &data[8..];
+    Some(u64::from_le_bytes(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ckb_types::{
+        bytes::Bytes,
+        core::TransactionBuilder,
+        packed::{CellInput, CellOutput, OutPoint, Script},
+    };
+
+    fn build_tx(index: u32) -> TransactionView {
+        TransactionBuilder::default()
+            .input(CellInput::new(OutPoint::new(Default::default(), index), 0))
+            .output(CellOutput::new_builder().build())
+            .output_data(Bytes::from(vec![index as u8]).pack())
+            .build()
+    }
+
+    fn suspended(current_cycles: u64) -> Suspended {
+        Suspended {
+            fee: Capacity::shannons(1000),
+            snap: Arc::new(TransactionSnapshot {
+                current: 1,
+                snap: None,
+                current_cycles,
+                limit_cycles: current_cycles * 2,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_suspended_txs_roundtrip() {
+        let txs = vec![
+            (build_tx(0), Some(suspended(100))),
+            (build_tx(1), None),
+            (build_tx(2), Some(suspended(200))),
+        ];
+        let data = encode_suspended_txs(&txs).unwrap();
+        let (restored, incompatible) = decode_suspended_txs(&data).unwrap();
+        assert_eq!(incompatible, 0);
+        assert_eq!(restored.len(), txs.len());
+        for ((tx, suspended), (restored_tx, restored_suspended)) in txs.iter().zip(restored) {
+            assert_eq!(tx.hash(), restored_tx.hash());
+            match (suspended, restored_suspended) {
+                (Some(suspended), Some(restored)) => {
+                    assert_eq!(suspended.fee, restored.fee);
+                    assert_eq!(suspended.snap.current, restored.snap.current);
+                    assert_eq!(suspended.snap.current_cycles, restored.snap.current_cycles);
+                    assert_eq!(suspended.snap.limit_cycles, restored.snap.limit_cycles);
+                }
+                (None, None) => {}
+                _ => panic!("the suspended state of tx {} is not restored", tx.hash()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_suspended_txs_broken_data() {
+        let txs = vec![(build_tx(0), Some(suspended(100)))];
+        let data = encode_suspended_txs(&txs).unwrap();
+        assert!(decode_suspended_txs(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_incompatible_snapshot_is_dropped() {
+        let txs = vec![(build_tx(0), Some(suspended(100)))];
+        let mut data = encode_suspended_txs(&txs).unwrap();
+        // the snapshot version follows the tx, the fee and the snapshot length
+        let offset = 4 + txs[0].0.data().as_slice().len() + 8 + 4;
+        data[offset] = data[offset].wrapping_add(1);
+        let (restored, incompatible) = decode_suspended_txs(&data).unwrap();
+        assert_eq!(incompatible, 1);
+        assert_eq!(restored.len(), 1);
+        assert!(restored[0].1.is_none());
+    }
+
+    #[test]
+    fn test_found_blocks_roundtrip() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = payout_rotation_file(&tmp_dir.path().join("tx_pool_persisted_data"));
+        let target = |arg: u8, weight: u64| PayoutTarget {
+            lock: Script::new_builder().args(vec![arg].pack()).build(),
+            weight,
+        };
+        let targets = vec![target(1, 3), target(2, 1)];
+        assert_eq!(load_found_blocks(&path, &targets).unwrap(), 0);
+
+        save_found_blocks(&path, &targets, 5).unwrap();
+        assert_eq!(load_found_blocks(&path, &targets).unwrap(), 5);
+        // the rotation restarts when the targets are changed
+        assert_eq!(load_found_blocks(&path, &[target(1, 3)]).unwrap(), 0);
+        assert_eq!(
+            load_found_blocks(&path, &[target(1, 3), target(2, 2)]).unwrap(),
+            0
+        );
+        assert_eq!(load_found_blocks(&path, &[]).unwrap(), 0);
+
+        std::fs::write(&path, [0u8; 4]).unwrap();
+        assert!(load_found_blocks(&path, &targets).is_err());
     }
 }
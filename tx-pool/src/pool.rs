@@ -1,10 +1,14 @@
 //! Top-level Pool type, methods, and tests
-use super::component::{commit_txs_scanner::CommitTxsScanner, TxEntry};
+use super::component::{
+    commit_txs_scanner::CommitTxsScanner, fee_estimator::FeeEstimator, TxEntry,
+};
 use crate::callback::Callbacks;
 use crate::component::pending::PendingQueue;
 use crate::component::proposed::ProposedPool;
+use crate::component::rbf::check_replacement;
 use crate::component::recent_reject::RecentReject;
 use crate::error::Reject;
+use crate::selection::{AncestorFeeRatePolicy, TxSelectionPolicy};
 use crate::util::verify_rtx;
 use ckb_app_config::TxPoolConfig;
 use ckb_logger::{debug, error, trace, warn};
@@ -18,16 +22,19 @@ use ckb_types::{
             ResolveOptions, ResolvedTransaction,
         },
         tx_pool::{TxPoolEntryInfo, TxPoolIds},
-        Cycle, TransactionView, UncleBlockView,
+        Cycle, FeeRate, TransactionView, UncleBlockView,
     },
-    packed::{Byte32, ProposalShortId},
+    packed::{Byte32, OutPoint, ProposalShortId},
 };
 use ckb_verification::{cache::CacheEntry, TxVerifyEnv};
 use lru::LruCache;
+use std::cmp;
 use std::collections::HashSet;
 use std::sync::Arc;
 
 const COMMITTED_HASH_CACHE_SIZE: usize = 100_000;
+// The dynamic min fee rate raised by evictions halves every 30 minutes.
+const DYNAMIC_MIN_FEE_RATE_HALF_LIFE: u64 = 30 * 60 * 1000;
 
 /// Tx-pool implementation
 pub struct TxPool {
@@ -48,6 +55,12 @@ pub struct TxPool {
     pub(crate) snapshot: Arc<Snapshot>,
     /// record recent reject
     pub recent_reject: Option<RecentReject>,
+    /// fee rate estimator
+    pub(crate) fee_estimator: FeeEstimator,
+    // the min fee rate raised by evictions, it decays over time
+    pub(crate) dynamic_min_fee_rate: FeeRate,
+    // the unix timestamp in milliseconds when the dynamic min fee rate is raised
+    pub(crate) dynamic_min_fee_rate_updated_at: u64,
     // expiration milliseconds,
     pub(crate) expiry: u64,
 }
@@ -82,6 +95,10 @@ pub struct TxPoolInfo {
     pub total_tx_cycles: Cycle,
     /// Last updated time. This is the Unix timestamp in milliseconds.
     pub last_txs_updated_at: u64,
+    /// The min fee rate required to enter the pool.
+    ///
+    /// It rises when the pool is full and evicts transactions, then decays over time.
+    pub min_fee_rate: FeeRate,
 }
 
 impl TxPool {
@@ -89,6 +106,7 @@ impl TxPool {
     pub fn new(config: TxPoolConfig, snapshot: Arc<Snapshot>) -> TxPool {
         let recent_reject = build_recent_reject(&config);
         let expiry = config.expiry_hours as u64 * 60 * 60 * 1000;
+        let fee_estimator = FeeEstimator::new(snapshot.consensus().tx_proposal_window().closest());
         TxPool {
             pending: PendingQueue::new(),
             gap: PendingQueue::new(),
@@ -99,6 +117,9 @@ impl TxPool {
             config,
             snapshot,
             recent_reject,
+            fee_estimator,
+            dynamic_min_fee_rate: FeeRate::zero(),
+            dynamic_min_fee_rate_updated_at: 0,
             expiry,
         }
     }
@@ -123,6 +144,146 @@ impl TxPool {
         (self.total_tx_cycles + cycles) > self.config.max_cycles
     }
 
+    /// Returns the min fee rate required to enter the pool.
+    ///
+    /// It is the configured `min_fee_rate` normally. After the pool evicts transactions to make
+    /// room, it rises above the fee rates of the evicted transactions, then halves every 30
+    /// minutes until it drops back to the configured one.
+    pub fn min_fee_rate(&self) -> FeeRate {
+        let elapsed =
+            faketime::unix_time_as_millis().saturating_sub(self.dynamic_min_fee_rate_updated_at);
+        let decayed = self.dynamic_min_fee_rate.as_u64() as f64
+            * 0.5f64.powf(elapsed as f64 / DYNAMIC_MIN_FEE_RATE_HALF_LIFE as f64);
+        self.config
+            .min_fee_rate
+            .max(FeeRate::from_u64(decayed as u64))
+    }
+
+    // The transactions paying no more than the evicted ones should not enter the pool again
+    // soon, the configured min fee rate is used as the increment.
+    fn raise_min_fee_rate(&mut self, evicted_fee_rate: FeeRate) {
+        let raised = FeeRate::from_u64(
+            evicted_fee_rate
+                .as_u64()
+                .saturating_add(self.config.min_fee_rate.as_u64()),
+        );
+        if raised > self.min_fee_rate() {
+            debug!("raise the dynamic min fee rate to {}", raised);
+            self.dynamic_min_fee_rate = raised;
+            self.dynamic_min_fee_rate_updated_at = faketime::unix_time_as_millis();
+        }
+    }
+
+    /// Returns the lowest ancestors score fee rate of the pending transactions, which is the
+    /// fee rate a transaction must exceed to evict others when the pool is full.
+    pub(crate) fn min_evictable_fee_rate(&self) -> Option<FeeRate> {
+        self.pending.score_sorted_keys().next().map(|key| {
+            let (fee, vbytes) = key.min_fee_and_vbytes();
+            FeeRate::calculate(fee, vbytes as usize)
+        })
+    }
+
+    /// Evicts the pending transactions with the lowest ancestors score and their descendants
+    /// to make room for the entry when the pool reaches the size or cycles limit.
+    ///
+    /// Only the transactions whose ancestors score fee rate is lower than the entry's fee rate
+    /// are evicted, and the transactions which the entry, or a transaction in the gap, spends
+    /// or depends on are kept. The `replaced` pending transactions are going to be removed by
+    /// replace-by-fee, so the room they take is counted as free. Returns `Reject::Full` without
+    /// evicting anything if there is not enough room to make.
+    pub(crate) fn make_room_for(
+        &mut self,
+        entry: &TxEntry,
+        replaced: &HashSet<ProposalShortId>,
+        callbacks: &Callbacks,
+    ) -> Result<(), Reject> {
+        let (mut total_size, mut total_cycles) = (self.total_tx_size, self.total_tx_cycles);
+        for replaced_entry in replaced.iter().filter_map(|id| self.pending.get(id)) {
+            total_size = total_size.saturating_sub(replaced_entry.size);
+            total_cycles = total_cycles.saturating_sub(replaced_entry.cycles);
+        }
+        let full = if total_size + entry.size > self.config.max_mem_size {
+            Reject::Full("size".to_owned(), self.config.max_mem_size as u64)
+        } else if total_cycles + entry.cycles > self.config.max_cycles {
+            Reject::Full("cycles".to_owned(), self.config.max_cycles)
+        } else {
+            return Ok(());
+        };
+
+        let fee_rate = entry.fee_rate();
+        let protected: HashSet<ProposalShortId> = entry
+            .transaction()
+            .input_pts_iter()
+            .chain(entry.related_dep_out_points().cloned())
+            .map(|out_point| ProposalShortId::from_tx_hash(&out_point.tx_hash()))
+            .collect();
+        let is_used_by_gap = |out_point: &OutPoint| {
+            self.gap.inputs.contains_key(out_point) || self.gap.deps.contains_key(out_point)
+        };
+
+        let mut evicted = replaced.clone();
+        let mut candidates = Vec::new();
+        for key in self.pending.score_sorted_keys() {
+            if total_size + entry.size <= self.config.max_mem_size
+                && total_cycles + entry.cycles <= self.config.max_cycles
+            {
+                break;
+            }
+            let (fee, vbytes) = key.min_fee_and_vbytes();
+            let score = FeeRate::calculate(fee, vbytes as usize);
+            if score >= fee_rate {
+                return Err(full);
+            }
+            if evicted.contains(&key.id) {
+                continue;
+            }
+            let candidate = match self.pending.get(&key.id) {
+                Some(candidate) => candidate,
+                None => continue,
+            };
+            let mut removed = self.pending.get_descendants(candidate);
+            removed.insert(key.id.clone());
+            let removed_entries: Vec<_> = removed
+                .iter()
+                .filter_map(|id| self.pending.get(id))
+                .collect();
+            if removed.iter().any(|id| protected.contains(id))
+                || removed_entries.iter().any(|e| {
+                    e.transaction()
+                        .output_pts_iter()
+                        .any(|o| is_used_by_gap(&o))
+                })
+            {
+                continue;
+            }
+            for removed_entry in removed_entries {
+                if evicted.insert(removed_entry.proposal_short_id()) {
+                    total_size = total_size.saturating_sub(removed_entry.size);
+                    total_cycles = total_cycles.saturating_sub(removed_entry.cycles);
+                }
+            }
+            candidates.push((key.id.clone(), score));
+        }
+        if total_size + entry.size > self.config.max_mem_size
+            || total_cycles + entry.cycles > self.config.max_cycles
+        {
+            return Err(full);
+        }
+
+        for (id, score) in candidates {
+            for removed in self.pending.remove_entry_and_descendants(&id) {
+                debug!(
+                    "evict {} from pending to make room for {}",
+                    removed.transaction().hash(),
+                    entry.transaction().hash()
+                );
+                callbacks.call_reject(self, &removed, full.clone());
+            }
+            self.raise_min_fee_rate(score);
+        }
+        Ok(())
+    }
+
     /// Update size and cycles statics for add tx
     pub fn update_statics_for_add_tx(&mut self, tx_size: usize, cycles: Cycle) {
         self.total_tx_size += tx_size;
@@ -302,6 +463,25 @@ impl TxPool {
         }
     }
 
+    /// Checks whether the fresh entry can replace the conflicting pending transactions, and
+    /// returns the transactions to be replaced, see `check_replacement`.
+    ///
+    /// Always returns nothing when replace-by-fee is disabled, the conflicting transactions
+    /// coexist in the pending pool then.
+    pub(crate) fn check_rbf(&self, entry: &TxEntry) -> Result<HashSet<ProposalShortId>, Reject> {
+        if !self.config.enable_rbf || self.pending.contains_key(&entry.proposal_short_id()) {
+            return Ok(HashSet::new());
+        }
+
+        check_replacement(
+            &self.pending,
+            &self.gap,
+            entry,
+            self.config.min_rbf_fee_bump_percent,
+            self.config.min_rbf_fee_rate_bump_percent,
+        )
+    }
+
     pub(crate) fn remove_expired(&mut self, callbacks: &Callbacks) {
         let now_ms = faketime::unix_time_as_millis();
         let removed = self
@@ -353,17 +533,20 @@ impl TxPool {
         if !entries.is_empty() {
             for entry in entries {
                 self.update_statics_for_remove_tx(entry.size, entry.cycles);
+                self.fee_estimator.untrack_tx(&entry.transaction().hash());
             }
             return true;
         }
 
         if let Some(entry) = self.gap.remove_entry(id) {
             self.update_statics_for_remove_tx(entry.size, entry.cycles);
+            self.fee_estimator.untrack_tx(&entry.transaction().hash());
             return true;
         }
 
         if let Some(entry) = self.pending.remove_entry(id) {
             self.update_statics_for_remove_tx(entry.size, entry.cycles);
+            self.fee_estimator.untrack_tx(&entry.transaction().hash());
             return true;
         }
 
@@ -567,7 +750,7 @@ impl TxPool {
     }
 
     pub(crate) fn drain_all_transactions(&mut self) -> Vec<TransactionView> {
-        let mut txs = CommitTxsScanner::new(&self.proposed)
+        let mut txs = CommitTxsScanner::new(&self.proposed, &AncestorFeeRatePolicy)
             .txs_to_commit(self.total_tx_size, self.total_tx_cycles)
             .0
             .into_iter()
@@ -590,27 +773,48 @@ impl TxPool {
         self.committed_txs_hash_cache = LruCache::new(COMMITTED_HASH_CACHE_SIZE);
         self.total_tx_size = 0;
         self.total_tx_cycles = 0;
+        self.fee_estimator.clear_tracked();
     }
 
+    /// Selects the proposals from the pending and the gap pools, in the order they entered the
+    /// pool. The transactions with positive fee deltas go first and the negative ones go last.
     pub(crate) fn package_proposals(
         &self,
+        policy: &dyn TxSelectionPolicy,
         proposals_limit: u64,
         uncles: &[UncleBlockView],
     ) -> HashSet<ProposalShortId> {
-        let uncle_proposals = uncles
+        let uncle_proposals: HashSet<ProposalShortId> = uncles
             .iter()
             .flat_map(|u| u.data().proposals().into_iter())
             .collect();
-        self.get_proposals(proposals_limit as usize, &uncle_proposals)
+        if policy.is_noop() {
+            return self.get_proposals(proposals_limit as usize, &uncle_proposals);
+        }
+        let mut candidates: Vec<(i64, &ProposalShortId)> = self
+            .pending
+            .iter()
+            .chain(self.gap.iter())
+            .filter(|(id, entry)| !uncle_proposals.contains(*id) && policy.is_selectable(entry))
+            .map(|(id, entry)| (policy.fee_delta(entry), id))
+            .collect();
+        // the stable sort keeps the arrival order of the transactions with the same fee delta
+        candidates.sort_by_key(|(fee_delta, _)| cmp::Reverse(*fee_delta));
+        candidates
+            .into_iter()
+            .take(proposals_limit as usize)
+            .map(|(_, id)| id.clone())
+            .collect()
     }
 
     pub(crate) fn package_txs(
         &self,
+        policy: &dyn TxSelectionPolicy,
         max_block_cycles: Cycle,
         txs_size_limit: usize,
     ) -> (Vec<TxEntry>, usize, Cycle) {
-        let (entries, size, cycles) =
-            CommitTxsScanner::new(self.proposed()).txs_to_commit(txs_size_limit, max_block_cycles);
+        let (entries, size, cycles) = CommitTxsScanner::new(self.proposed(), policy)
+            .txs_to_commit(txs_size_limit, max_block_cycles);
 
         if !entries.is_empty() {
             ckb_logger::info!(
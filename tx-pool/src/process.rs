@@ -1,21 +1,23 @@
+use crate::block_assembler::{check_payout_targets, is_recommended_lock, PayoutTarget};
 use crate::callback::Callbacks;
 use crate::component::chunk::DEFAULT_MAX_CHUNK_TRANSACTIONS;
 use crate::component::entry::TxEntry;
 use crate::component::orphan::Entry as OrphanEntry;
-use crate::error::Reject;
+use crate::error::{BlockAssemblerError, Reject};
+use crate::persisted::SuspendedTx;
 use crate::pool::TxPool;
 use crate::service::{BlockAssemblerMessage, TxPoolService, TxVerificationResult};
 use crate::try_or_return_with_snapshot;
 use crate::util::{
-    after_delay_window, check_tx_cycle_limit, check_tx_fee, check_tx_size_limit,
-    check_txid_collision, is_missing_input, non_contextual_verify, time_relative_verify,
-    verify_rtx,
+    after_delay_window, check_tx_fee, check_tx_size_limit, check_txid_collision, is_missing_input,
+    non_contextual_verify, record_reject, time_relative_verify, verify_rtx,
 };
 use ckb_chain_spec::consensus::MAX_BLOCK_PROPOSALS_LIMIT;
 use ckb_error::{AnyError, InternalErrorKind};
 use ckb_jsonrpc_types::BlockTemplate;
 use ckb_logger::Level::Trace;
 use ckb_logger::{debug, error, info, log_enabled_target, trace_target};
+use ckb_metrics::{metrics, Timer};
 use ckb_network::PeerIndex;
 use ckb_snapshot::Snapshot;
 use ckb_store::ChainStore;
@@ -81,6 +83,63 @@ impl TxPoolService {
         }
     }
 
+    pub(crate) async fn get_payout_targets(&self) -> Result<Vec<PayoutTarget>, AnyError> {
+        let block_assembler = self
+            .block_assembler
+            .as_ref()
+            .ok_or(BlockAssemblerError::Disabled)?;
+        Ok(block_assembler.get_payout_targets().await)
+    }
+
+    pub(crate) async fn set_payout_targets(
+        &self,
+        payout_targets: Vec<PayoutTarget>,
+    ) -> Result<(), AnyError> {
+        let block_assembler = self
+            .block_assembler
+            .as_ref()
+            .ok_or(BlockAssemblerError::Disabled)?;
+        check_payout_targets(&payout_targets)?;
+        let snapshot = self.tx_pool.read().await.cloned_snapshot();
+        if !block_assembler.config.advanced
+            && !payout_targets
+                .iter()
+                .all(|target| is_recommended_lock(snapshot.consensus(), &target.lock))
+        {
+            return Err(BlockAssemblerError::InvalidParams(
+                "the payout targets should use the recommended lock, \
+                 or run ckb with `--ba-advanced` to use other lock scripts"
+                    .to_owned(),
+            )
+            .into());
+        }
+        block_assembler.set_payout_targets(payout_targets).await;
+
+        // Rebuild the current template, so the new cellbase lock takes effect immediately.
+        block_assembler.update_blank(snapshot).await?;
+        block_assembler.update_full(&self.tx_pool).await?;
+        block_assembler.notify().await;
+        Ok(())
+    }
+
+    pub(crate) async fn prioritise_transaction(
+        &self,
+        tx_hash: Byte32,
+        fee_delta: i64,
+    ) -> Result<(), AnyError> {
+        let block_assembler = self
+            .block_assembler
+            .as_ref()
+            .ok_or(BlockAssemblerError::Disabled)?;
+        block_assembler
+            .selection_policy
+            .prioritise(tx_hash, fee_delta)?;
+        // Re-rank both the proposals and the commitments in the block template.
+        self.notify_block_assembler(TxStatus::Fresh).await;
+        self.notify_block_assembler(TxStatus::Proposed).await;
+        Ok(())
+    }
+
     pub(crate) async fn fetch_tx_verify_cache(&self, hash: &Byte32) -> Option<CacheEntry> {
         let guard = self.txs_verify_cache.read().await;
         guard.peek(hash).cloned()
@@ -100,15 +159,12 @@ impl TxPoolService {
 
     pub(crate) async fn submit_entry(
         &self,
-        verified: Completed,
         pre_resolve_tip: Byte32,
         entry: TxEntry,
         mut status: TxStatus,
     ) -> (Result<(), Reject>, Arc<Snapshot>) {
         let (ret, snapshot) = self
             .with_tx_pool_write_lock(move |tx_pool, snapshot| {
-                check_tx_cycle_limit(tx_pool, verified.cycles)?;
-
                 // if snapshot changed by context switch
                 // we need redo time_relative verify
                 let tip_hash = snapshot.tip_hash();
@@ -207,14 +263,14 @@ impl TxPoolService {
         let (ret, snapshot) = self
             .with_tx_pool_read_lock(|tx_pool, snapshot| {
                 let tip_hash = snapshot.tip_hash();
-                check_tx_size_limit(tx_pool, tx_size)?;
-
                 check_txid_collision(tx_pool, tx)?;
 
                 let (rtx, status) = resolve_tx(tx_pool, snapshot, tx.clone())?;
 
                 let fee = check_tx_fee(tx_pool, snapshot, &rtx, tx_size)?;
 
+                check_tx_size_limit(tx_pool, tx_size, fee)?;
+
                 Ok((tip_hash, rtx, status, fee, tx_size))
             })
             .await;
@@ -233,6 +289,7 @@ impl TxPoolService {
                     self.ban_malformed(remote.1, format!("reject {}", reject));
                 }
             }
+            record_reject(&reject);
             return Err(reject);
         }
         Ok(())
@@ -247,7 +304,9 @@ impl TxPoolService {
         self.non_contextual_verify(&tx, None)?;
 
         if self.chunk_contains(&tx).await || self.orphan_contains(&tx).await {
-            return Err(Reject::Duplicated(tx.hash()));
+            let reject = Reject::Duplicated(tx.hash());
+            record_reject(&reject);
+            return Err(reject);
         }
 
         if let Some((ret, snapshot)) = self._resumeble_process_tx(tx.clone(), remote).await {
@@ -279,7 +338,9 @@ impl TxPoolService {
         self.non_contextual_verify(&tx, remote)?;
 
         if self.chunk_contains(&tx).await || self.orphan_contains(&tx).await {
-            return Err(Reject::Duplicated(tx.hash()));
+            let reject = Reject::Duplicated(tx.hash());
+            record_reject(&reject);
+            return Err(reject);
         }
 
         if let Some((ret, snapshot)) = self._process_tx(tx.clone(), remote.map(|r| r.0)).await {
@@ -336,6 +397,10 @@ impl TxPoolService {
     ) {
         let tx_hash = tx.hash();
 
+        if let Err(reject) = ret {
+            record_reject(reject);
+        }
+
         // log tx verification result for monitor node
         if log_enabled_target!("ckb_tx_monitor", Trace) {
             if let Ok(c) = ret {
@@ -567,6 +632,7 @@ impl TxPoolService {
             let data_provider = snapshot.as_data_provider();
             let is_chunk_full = self.is_chunk_full().await;
 
+            let timer = Timer::start();
             let ret = block_in_place(|| {
                 let verifier =
                     ContextualTransactionVerifier::new(&rtx, consensus, &data_provider, &tx_env);
@@ -597,6 +663,7 @@ impl TxPoolService {
                     }
                 }
             });
+            metrics!(timing, "ckb.tx_pool.verify", timer.stop());
 
             let entry = try_or_return_with_snapshot!(ret, snapshot);
             match entry {
@@ -613,7 +680,7 @@ impl TxPoolService {
 
         let entry = TxEntry::new(rtx, completed.cycles, fee, tx_size);
 
-        let (ret, submit_snapshot) = self.submit_entry(completed, tip_hash, entry, status).await;
+        let (ret, submit_snapshot) = self.submit_entry(tip_hash, entry, status).await;
         try_or_return_with_snapshot!(ret, submit_snapshot);
 
         self.notify_block_assembler(status).await;
@@ -688,7 +755,7 @@ impl TxPoolService {
 
         let entry = TxEntry::new(rtx, verified.cycles, fee, tx_size);
 
-        let (ret, submit_snapshot) = self.submit_entry(verified, tip_hash, entry, status).await;
+        let (ret, submit_snapshot) = self.submit_entry(tip_hash, entry, status).await;
         try_or_return_with_snapshot!(ret, submit_snapshot);
 
         self.notify_block_assembler(status).await;
@@ -728,11 +795,27 @@ impl TxPoolService {
             detached.extend(blk.transactions().into_iter().skip(1))
         }
 
+        let mut committed = Vec::with_capacity(attached_blocks.len());
         for blk in attached_blocks {
+            committed.push((
+                blk.number(),
+                blk.union_proposal_ids(),
+                blk.transactions()
+                    .iter()
+                    .skip(1)
+                    .map(|tx| tx.hash())
+                    .collect::<Vec<_>>(),
+            ));
             attached.extend(blk.transactions().into_iter().skip(1));
         }
         let retain: Vec<TransactionView> = detached.difference(&attached).cloned().collect();
 
+        if let Some(ref block_assembler) = self.block_assembler {
+            for tx in attached.iter() {
+                block_assembler.selection_policy.on_committed(&tx.hash());
+            }
+        }
+
         let fetched_cache = if is_in_delay_window {
             // If in delay_window, don't use the cache.
             HashMap::new()
@@ -768,6 +851,12 @@ impl TxPoolService {
                 mine_mode,
             );
 
+            for (number, proposals, tx_hashes) in committed {
+                tx_pool
+                    .fee_estimator
+                    .process_block(number, proposals, tx_hashes);
+            }
+
             // notice: readd_detached_tx don't update cache
             self.readd_detached_tx(&mut tx_pool, retain, fetched_cache);
 
@@ -887,10 +976,41 @@ impl TxPoolService {
     }
 
     pub(crate) async fn save_pool(&mut self) {
+        let suspended_txs: Vec<SuspendedTx> = {
+            let chunk = self.chunk.read().await;
+            let guard = self.txs_verify_cache.read().await;
+            chunk
+                .txs()
+                .map(|tx| match guard.peek(&tx.hash()) {
+                    Some(CacheEntry::Suspended(suspended)) => (tx.clone(), Some(suspended.clone())),
+                    _ => (tx.clone(), None),
+                })
+                .collect()
+        };
         let mut tx_pool = self.tx_pool.write().await;
         if let Err(err) = tx_pool.save_into_file() {
             error!("failed to save pool, error: {:?}", err)
         }
+        if let Err(err) = tx_pool.save_fee_estimator() {
+            error!("failed to save fee estimator, error: {:?}", err)
+        }
+        if let Err(err) = tx_pool.save_suspended_txs(&suspended_txs) {
+            error!("failed to save suspended txs, error: {:?}", err)
+        }
+    }
+
+    pub(crate) async fn restore_suspended_txs(&self, txs: Vec<SuspendedTx>) {
+        info!("Restoring {} suspended txs into the chunk queue", txs.len());
+        let mut chunk = self.chunk.write().await;
+        let mut guard = self.txs_verify_cache.write().await;
+        for (tx, suspended) in txs {
+            let tx_hash = tx.hash();
+            if chunk.add_tx(tx, None) {
+                if let Some(suspended) = suspended {
+                    guard.put(tx_hash, CacheEntry::Suspended(suspended));
+                }
+            }
+        }
     }
 }
 
@@ -971,16 +1091,42 @@ fn _submit_entry(
     callbacks: &Callbacks,
 ) -> Result<(), Reject> {
     let tx_hash = entry.transaction().hash();
+    let id = entry.proposal_short_id();
+    // Evict the pending transactions only after the entry is sure to enter the pool.
     match status {
         TxStatus::Fresh => {
+            if tx_pool.pending.contains_key(&id) || tx_pool.gap.contains_key(&id) {
+                return Err(Reject::Duplicated(tx_hash));
+            }
+            let replaced = tx_pool.check_rbf(&entry)?;
+            tx_pool.make_room_for(&entry, &replaced, callbacks)?;
+            if !replaced.is_empty() {
+                for (replaced, _) in tx_pool.pending.resolve_conflict(entry.transaction()) {
+                    debug!(
+                        "submit_entry {} replaces {}",
+                        tx_hash,
+                        replaced.transaction().hash()
+                    );
+                    let reject = Reject::RBFReplaced(tx_hash.clone());
+                    callbacks.call_reject(tx_pool, &replaced, reject);
+                }
+            }
             if tx_pool.add_pending(entry.clone()) {
                 debug!("submit_entry pending {}", tx_hash);
+                let tip_number = tx_pool.snapshot.tip_number();
+                tx_pool
+                    .fee_estimator
+                    .track_tx(tx_hash, entry.fee_rate(), tip_number);
                 callbacks.call_pending(tx_pool, &entry);
             } else {
                 return Err(Reject::Duplicated(tx_hash));
             }
         }
         TxStatus::Gap => {
+            if tx_pool.gap.contains_key(&id) {
+                return Err(Reject::Duplicated(tx_hash));
+            }
+            tx_pool.make_room_for(&entry, &HashSet::new(), callbacks)?;
             if tx_pool.add_gap(entry.clone()) {
                 debug!("submit_entry gap {}", tx_hash);
                 callbacks.call_pending(tx_pool, &entry);
@@ -989,6 +1135,10 @@ fn _submit_entry(
             }
         }
         TxStatus::Proposed => {
+            if tx_pool.proposed.contains_key(&id) {
+                return Err(Reject::Duplicated(tx_hash));
+            }
+            tx_pool.make_room_for(&entry, &HashSet::new(), callbacks)?;
             if tx_pool.add_proposed(entry.clone())? {
                 debug!("submit_entry proposed {}", tx_hash);
                 callbacks.call_proposed(tx_pool, &entry, true);
@@ -0,0 +1,224 @@
+//! The policies which select the transactions into the block templates.
+//!
+//! A policy works on top of the ancestor fee rate packing. It can leave out some transactions,
+//! along with their descendants, and rank the others with the fee deltas.
+use crate::component::entry::TxEntry;
+use crate::error::BlockAssemblerError;
+use ckb_app_config::{ExcludedScriptConfig, TxSelectionConfig};
+use ckb_types::{
+    core::ScriptHashType,
+    packed::{self, Byte32, CellOutput, Script},
+    prelude::*,
+};
+use ckb_util::RwLock;
+use faketime::unix_time_as_millis;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The max number of the transactions with fee deltas.
+pub const MAX_FEE_DELTAS: usize = 10_000;
+/// A fee delta expires if it is not updated in 24 hours, so the deltas of the transactions which
+/// never enter the pool or have been removed from the pool are dropped.
+pub const FEE_DELTA_EXPIRY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// Decides which transactions are packaged into the proposals and the commitments of the block
+/// templates, and in which order.
+pub trait TxSelectionPolicy: Send + Sync {
+    /// Returns true if the policy neither leaves out nor ranks any transaction at the moment, so
+    /// the default packing applies.
+    fn is_noop(&self) -> bool {
+        false
+    }
+
+    /// Returns false to leave the transaction out of the block templates.
+    fn is_selectable(&self, _entry: &TxEntry) -> bool {
+        true
+    }
+
+    /// Returns the shannons added to the fee of the transaction when it is ranked.
+    ///
+    /// The delta only affects the order of the transactions, the fee actually paid is unchanged.
+    fn fee_delta(&self, _entry: &TxEntry) -> i64 {
+        0
+    }
+
+    /// Adds the delta to the fee delta of the transaction.
+    ///
+    /// Returns an error if the policy does not support prioritising transactions.
+    fn prioritise(&self, _tx_hash: Byte32, _fee_delta: i64) -> Result<(), BlockAssemblerError> {
+        Err(BlockAssemblerError::InvalidParams(
+            "the tx selection policy does not support prioritising transactions".to_owned(),
+        ))
+    }
+
+    /// Notifies the policy that the transaction has been committed.
+    fn on_committed(&self, _tx_hash: &Byte32) {}
+}
+
+/// Builds the policy from the config.
+pub fn build_tx_selection_policy(config: &TxSelectionConfig) -> Arc<dyn TxSelectionPolicy> {
+    match config {
+        TxSelectionConfig::AncestorFeeRate => Arc::new(AncestorFeeRatePolicy),
+        TxSelectionConfig::Prioritised => Arc::new(PrioritisedPolicy::default()),
+        TxSelectionConfig::ExcludeScripts { scripts } => {
+            Arc::new(ExcludeScriptsPolicy::new(scripts))
+        }
+    }
+}
+
+/// Selects the transactions by the fee rate including their ancestors.
+#[derive(Default)]
+pub struct AncestorFeeRatePolicy;
+
+impl TxSelectionPolicy for AncestorFeeRatePolicy {
+    fn is_noop(&self) -> bool {
+        true
+    }
+}
+
+/// Ranks the transactions with the fee deltas set by the operator, like `prioritisetransaction`
+/// in bitcoind.
+///
+/// A fee delta can be set before the transaction enters the pool, and it is dropped once the
+/// transaction is committed or it expires. At most [`MAX_FEE_DELTAS`] transactions can have fee
+/// deltas.
+#[derive(Default)]
+pub struct PrioritisedPolicy {
+    fee_deltas: RwLock<HashMap<Byte32, FeeDelta>>,
+}
+
+struct FeeDelta {
+    total: i64,
+    updated_at: u64,
+}
+
+impl FeeDelta {
+    fn is_expired(&self, now: u64) -> bool {
+        now.saturating_sub(self.updated_at) >= FEE_DELTA_EXPIRY_MILLIS
+    }
+}
+
+impl PrioritisedPolicy {
+    /// Adds the delta at the time `now` in milliseconds, and drops the expired ones.
+    pub(crate) fn prioritise_at(
+        &self,
+        tx_hash: Byte32,
+        fee_delta: i64,
+        now: u64,
+    ) -> Result<(), BlockAssemblerError> {
+        let mut fee_deltas = self.fee_deltas.write();
+        fee_deltas.retain(|_, delta| !delta.is_expired(now));
+        if !fee_deltas.contains_key(&tx_hash) && fee_deltas.len() >= MAX_FEE_DELTAS {
+            return Err(BlockAssemblerError::InvalidParams(format!(
+                "at most {} transactions can be prioritised",
+                MAX_FEE_DELTAS
+            )));
+        }
+        let delta = fee_deltas.entry(tx_hash.clone()).or_insert(FeeDelta {
+            total: 0,
+            updated_at: now,
+        });
+        delta.total = delta.total.saturating_add(fee_delta);
+        delta.updated_at = now;
+        if delta.total == 0 {
+            fee_deltas.remove(&tx_hash);
+        }
+        Ok(())
+    }
+}
+
+impl TxSelectionPolicy for PrioritisedPolicy {
+    fn is_noop(&self) -> bool {
+        self.fee_deltas.read().is_empty()
+    }
+
+    fn fee_delta(&self, entry: &TxEntry) -> i64 {
+        let now = unix_time_as_millis();
+        self.fee_deltas
+            .read()
+            .get(&entry.transaction().hash())
+            .filter(|delta| !delta.is_expired(now))
+            .map(|delta| delta.total)
+            .unwrap_or(0)
+    }
+
+    fn prioritise(&self, tx_hash: Byte32, fee_delta: i64) -> Result<(), BlockAssemblerError> {
+        self.prioritise_at(tx_hash, fee_delta, unix_time_as_millis())
+    }
+
+    fn on_committed(&self, tx_hash: &Byte32) {
+        self.fee_deltas.write().remove(tx_hash);
+    }
+}
+
+struct ExcludedScript {
+    code_hash: Byte32,
+    hash_type: packed::Byte,
+    args: Option<Vec<u8>>,
+}
+
+impl ExcludedScript {
+    fn matches(&self, script: &Script) -> bool {
+        script.code_hash() == self.code_hash
+            && script.hash_type() == self.hash_type
+            && self
+                .args
+                .as_ref()
+                .map(|args| script.args().raw_data() == args[..])
+                .unwrap_or(true)
+    }
+}
+
+/// Leaves out the transactions which use the scripts as the lock or the type of any input or
+/// output.
+pub struct ExcludeScriptsPolicy {
+    scripts: Vec<ExcludedScript>,
+}
+
+impl ExcludeScriptsPolicy {
+    /// Creates the policy from the config.
+    pub fn new(scripts: &[ExcludedScriptConfig]) -> Self {
+        let scripts = scripts
+            .iter()
+            .map(|script| ExcludedScript {
+                code_hash: script.code_hash.pack(),
+                hash_type: {
+                    let hash_type: ScriptHashType = script.hash_type.clone().into();
+                    hash_type.into()
+                },
+                args: script.args.as_ref().map(|args| args.as_bytes().to_vec()),
+            })
+            .collect();
+        ExcludeScriptsPolicy { scripts }
+    }
+
+    fn is_excluded(&self, output: &CellOutput) -> bool {
+        let matches =
+            |script: &Script| self.scripts.iter().any(|excluded| excluded.matches(script));
+        matches(&output.lock())
+            || output
+                .type_()
+                .to_opt()
+                .map_or(false, |type_| matches(&type_))
+    }
+}
+
+impl TxSelectionPolicy for ExcludeScriptsPolicy {
+    fn is_noop(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    fn is_selectable(&self, entry: &TxEntry) -> bool {
+        let excluded_input = entry
+            .rtx
+            .resolved_inputs
+            .iter()
+            .any(|cell_meta| self.is_excluded(&cell_meta.cell_output));
+        let excluded_output = entry
+            .transaction()
+            .outputs()
+            .into_iter()
+            .any(|output| self.is_excluded(&output));
+        !(excluded_input || excluded_output)
+    }
+}
@@ -1,20 +1,23 @@
 //! Tx-pool background service
 
-use crate::block_assembler::{self, BlockAssembler};
+use crate::block_assembler::{self, BlockAssembler, PayoutTarget};
 use crate::callback::{Callback, Callbacks, ProposedCallback, RejectCallback};
 use crate::chunk_process::ChunkCommand;
-use crate::component::{chunk::ChunkQueue, orphan::OrphanPool};
+use crate::component::{chunk::ChunkQueue, fee_estimator::FeeRateEstimate, orphan::OrphanPool};
 use crate::error::{handle_recv_error, handle_send_cmd_error, handle_try_send_error};
+use crate::persisted::payout_rotation_file;
 use crate::pool::{TxPool, TxPoolInfo};
 use crate::util::after_delay_window;
 use ckb_app_config::{BlockAssemblerConfig, TxPoolConfig};
 use ckb_async_runtime::Handle;
 use ckb_chain_spec::consensus::Consensus;
-use ckb_channel::oneshot;
+use ckb_channel::{oneshot, RecvTimeoutError};
 use ckb_error::AnyError;
 use ckb_jsonrpc_types::{BlockTemplate, TransactionWithStatus, TxStatus};
 use ckb_logger::error;
 use ckb_logger::info;
+use ckb_logger::warn;
+use ckb_metrics::metrics;
 use ckb_network::{NetworkController, PeerIndex};
 use ckb_snapshot::Snapshot;
 use ckb_stop_handler::{SignalSender, StopHandler, WATCH_INIT};
@@ -43,6 +46,9 @@ use crate::{component::entry::TxEntry, process::PlugTarget};
 
 pub(crate) const DEFAULT_CHANNEL_SIZE: usize = 512;
 pub(crate) const BLOCK_ASSEMBLER_CHANNEL_SIZE: usize = 100;
+const METRICS_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+// The chunk process checks the suspend command after every step of `MIN_STEP_CYCLE` cycles.
+const CHUNK_SUSPEND_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub(crate) struct Request<A, R> {
     pub responder: oneshot::Sender<R>,
@@ -81,6 +87,8 @@ type GetTransactionWithStatusResult = Result<TransactionWithStatus, AnyError>;
 
 type FetchTxsWithCyclesResult = Vec<(ProposalShortId, (TransactionView, Cycle))>;
 
+type EstimateFeeRateArgs = (u64, u32);
+
 pub(crate) type ChainReorgArgs = (
     VecDeque<BlockView>,
     VecDeque<BlockView>,
@@ -106,6 +114,10 @@ pub(crate) enum Message {
     GetAllEntryInfo(Request<(), TxPoolEntryInfo>),
     GetAllIds(Request<(), TxPoolIds>),
     SavePool(Request<(), ()>),
+    EstimateFeeRate(Request<EstimateFeeRateArgs, Option<FeeRateEstimate>>),
+    GetPayoutTargets(Request<(), Result<Vec<PayoutTarget>, AnyError>>),
+    SetPayoutTargets(Request<Vec<PayoutTarget>, Result<(), AnyError>>),
+    PrioritiseTransaction(Request<(Byte32, i64), Result<(), AnyError>>),
 
     // test
     #[cfg(feature = "internal")]
@@ -130,6 +142,7 @@ pub struct TxPoolController {
     sender: mpsc::Sender<Message>,
     reorg_sender: mpsc::Sender<Notify<ChainReorgArgs>>,
     chunk_tx: Arc<watch::Sender<ChunkCommand>>,
+    chunk_suspended: ckb_channel::Receiver<()>,
     handle: Handle,
     stop: StopHandler<()>,
     started: Arc<AtomicBool>,
@@ -181,6 +194,60 @@ impl TxPoolController {
             .map_err(Into::into)
     }
 
+    /// Returns the payout targets of the block assembler.
+    pub fn get_payout_targets(&self) -> Result<Result<Vec<PayoutTarget>, AnyError>, AnyError> {
+        let (responder, response) = oneshot::channel();
+        let request = Request::call((), responder);
+        self.sender
+            .try_send(Message::GetPayoutTargets(request))
+            .map_err(|e| {
+                let (_m, e) = handle_try_send_error(e);
+                e
+            })?;
+        block_in_place(|| response.recv())
+            .map_err(handle_recv_error)
+            .map_err(Into::into)
+    }
+
+    /// Replaces the payout targets of the block assembler and rebuilds the block template.
+    pub fn set_payout_targets(
+        &self,
+        payout_targets: Vec<PayoutTarget>,
+    ) -> Result<Result<(), AnyError>, AnyError> {
+        let (responder, response) = oneshot::channel();
+        let request = Request::call(payout_targets, responder);
+        self.sender
+            .try_send(Message::SetPayoutTargets(request))
+            .map_err(|e| {
+                let (_m, e) = handle_try_send_error(e);
+                e
+            })?;
+        block_in_place(|| response.recv())
+            .map_err(handle_recv_error)
+            .map_err(Into::into)
+    }
+
+    /// Adds the fee delta to the transaction, which changes its rank in the block templates.
+    ///
+    /// It requires the `prioritised` tx selection policy.
+    pub fn prioritise_transaction(
+        &self,
+        tx_hash: Byte32,
+        fee_delta: i64,
+    ) -> Result<Result<(), AnyError>, AnyError> {
+        let (responder, response) = oneshot::channel();
+        let request = Request::call((tx_hash, fee_delta), responder);
+        self.sender
+            .try_send(Message::PrioritiseTransaction(request))
+            .map_err(|e| {
+                let (_m, e) = handle_try_send_error(e);
+                e
+            })?;
+        block_in_place(|| response.recv())
+            .map_err(handle_recv_error)
+            .map_err(Into::into)
+    }
+
     /// Notify new uncle
     pub fn notify_new_uncle(&self, uncle: UncleBlockView) -> Result<(), AnyError> {
         let notify = Notify::new(uncle);
@@ -457,6 +524,17 @@ impl TxPoolController {
     /// Saves tx pool into disk.
     pub fn save_pool(&self) -> Result<(), AnyError> {
         info!("Please be patient, tx-pool are saving data into disk ...");
+        // suspend the chunk process, so the progress of the tx being verified is kept in the
+        // verification cache and saved with the pool
+        while self.chunk_suspended.try_recv().is_ok() {}
+        self.suspend_chunk_process()?;
+        match block_in_place(|| self.chunk_suspended.recv_timeout(CHUNK_SUSPEND_TIMEOUT)) {
+            // the chunk process has exited, nothing is being verified
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => {}
+            Err(RecvTimeoutError::Timeout) => {
+                warn!("chunk process is not suspended in time, the tx being verified is not saved");
+            }
+        }
         let (responder, response) = oneshot::channel();
         let request = Request::call((), responder);
         self.sender
@@ -470,6 +548,28 @@ impl TxPoolController {
             .map_err(Into::into)
     }
 
+    /// Estimates the fee rate to get a transaction committed within `target_blocks` blocks, with
+    /// the `confidence` in percentage.
+    ///
+    /// Returns `None` when the estimator has not collected enough samples.
+    pub fn estimate_fee_rate(
+        &self,
+        target_blocks: u64,
+        confidence: u32,
+    ) -> Result<Option<FeeRateEstimate>, AnyError> {
+        let (responder, response) = oneshot::channel();
+        let request = Request::call((target_blocks, confidence), responder);
+        self.sender
+            .try_send(Message::EstimateFeeRate(request))
+            .map_err(|e| {
+                let (_m, e) = handle_try_send_error(e);
+                e
+            })?;
+        block_in_place(|| response.recv())
+            .map_err(handle_recv_error)
+            .map_err(Into::into)
+    }
+
     /// Load persisted txs into pool, assume that all txs are sorted
     fn load_persisted_data(&self, txs: Vec<TransactionView>) -> Result<(), AnyError> {
         if !txs.is_empty() {
@@ -539,6 +639,7 @@ pub struct TxPoolServiceBuilder {
     pub(crate) handle: Handle,
     pub(crate) tx_relay_sender: ckb_channel::Sender<TxVerificationResult>,
     pub(crate) chunk_rx: watch::Receiver<ChunkCommand>,
+    pub(crate) chunk_suspended_tx: ckb_channel::Sender<()>,
     pub(crate) chunk: Arc<RwLock<ChunkQueue>>,
     pub(crate) started: Arc<AtomicBool>,
     pub(crate) block_assembler_channel: (
@@ -562,6 +663,7 @@ impl TxPoolServiceBuilder {
         let (reorg_sender, reorg_receiver) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
         let (signal_sender, signal_receiver) = watch::channel(WATCH_INIT);
         let (chunk_tx, chunk_rx) = watch::channel(ChunkCommand::Resume);
+        let (chunk_suspended_tx, chunk_suspended) = ckb_channel::bounded(1);
         let chunk = Arc::new(RwLock::new(ChunkQueue::new()));
         let started = Arc::new(AtomicBool::new(false));
 
@@ -575,12 +677,18 @@ impl TxPoolServiceBuilder {
             reorg_sender,
             handle: handle.clone(),
             chunk_tx: Arc::new(chunk_tx),
+            chunk_suspended,
             stop,
             started: Arc::clone(&started),
         };
 
-        let block_assembler =
-            block_assembler_config.map(|config| BlockAssembler::new(config, Arc::clone(&snapshot)));
+        let block_assembler = block_assembler_config.map(|config| {
+            BlockAssembler::new(
+                config,
+                Arc::clone(&snapshot),
+                payout_rotation_file(&tx_pool_config.persisted_data),
+            )
+        });
         let builder = TxPoolServiceBuilder {
             tx_pool_config,
             tx_pool_controller: controller.clone(),
@@ -594,6 +702,7 @@ impl TxPoolServiceBuilder {
             handle: handle.clone(),
             tx_relay_sender,
             chunk_rx,
+            chunk_suspended_tx,
             chunk,
             started,
             block_assembler_channel,
@@ -627,7 +736,7 @@ impl TxPoolServiceBuilder {
         let consensus = self.snapshot.cloned_consensus();
 
         let after_delay_window = after_delay_window(&self.snapshot);
-        let tx_pool = TxPool::new(self.tx_pool_config, self.snapshot);
+        let mut tx_pool = TxPool::new(self.tx_pool_config, self.snapshot);
 
         let txs = match tx_pool.load_from_file() {
             Ok(txs) => txs,
@@ -637,6 +746,18 @@ impl TxPoolServiceBuilder {
                 Vec::new()
             }
         };
+        if let Err(e) = tx_pool.load_fee_estimator() {
+            error!("{}", e.to_string());
+            error!("Failed to load the fee estimator persisted data, start with empty history");
+        }
+        let suspended_txs = match tx_pool.load_suspended_txs() {
+            Ok(txs) => txs,
+            Err(e) => {
+                error!("{}", e.to_string());
+                error!("Failed to load the suspended txs persisted data, all suspended txs are ignored");
+                Vec::new()
+            }
+        };
 
         let (block_assembler_sender, mut block_assembler_receiver) = self.block_assembler_channel;
         let service = TxPoolService {
@@ -655,11 +776,18 @@ impl TxPoolServiceBuilder {
             consensus,
         };
 
+        if !suspended_txs.is_empty() {
+            let restore_service = service.clone();
+            self.handle
+                .spawn(async move { restore_service.restore_suspended_txs(suspended_txs).await });
+        }
+
         let signal_receiver = self.signal_receiver.clone();
         let chunk_process = crate::chunk_process::ChunkProcess::new(
             service.clone(),
             self.chunk_rx,
             signal_receiver,
+            self.chunk_suspended_tx,
         );
 
         self.handle.spawn(async move { chunk_process.run().await });
@@ -740,6 +868,18 @@ impl TxPoolServiceBuilder {
             }
         }
 
+        let metrics_service = service.clone();
+        let mut signal_receiver = self.signal_receiver.clone();
+        self.handle.spawn(async move {
+            let mut interval = tokio::time::interval(METRICS_UPDATE_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => metrics_service.update_metrics().await,
+                    _ = signal_receiver.changed() => break,
+                }
+            }
+        });
+
         let mut signal_receiver = self.signal_receiver;
         self.handle.spawn(async move {
             loop {
@@ -752,6 +892,7 @@ impl TxPoolServiceBuilder {
                         let detached_blocks_clone = detached_blocks.clone();
                         service.update_block_assembler_before_tx_pool_reorg(
                             detached_blocks_clone,
+                            &attached_blocks,
                             snapshot_clone
                         ).await;
 
@@ -1027,6 +1168,45 @@ async fn process(mut service: TxPoolService, message: Message) {
                 error!("responder send save_pool failed {:?}", e)
             };
         }
+        Message::EstimateFeeRate(Request {
+            responder,
+            arguments: (target_blocks, confidence),
+        }) => {
+            let estimate = {
+                let tx_pool = service.tx_pool.read().await;
+                let tip_number = tx_pool.snapshot.tip_number();
+                tx_pool
+                    .fee_estimator
+                    .estimate(target_blocks, confidence, tip_number)
+            };
+            if let Err(e) = responder.send(estimate) {
+                error!("responder send estimate_fee_rate failed {:?}", e)
+            };
+        }
+        Message::GetPayoutTargets(Request { responder, .. }) => {
+            let result = service.get_payout_targets().await;
+            if let Err(e) = responder.send(result) {
+                error!("responder send get_payout_targets failed {:?}", e)
+            };
+        }
+        Message::SetPayoutTargets(Request {
+            responder,
+            arguments: payout_targets,
+        }) => {
+            let result = service.set_payout_targets(payout_targets).await;
+            if let Err(e) = responder.send(result) {
+                error!("responder send set_payout_targets failed {:?}", e)
+            };
+        }
+        Message::PrioritiseTransaction(Request {
+            responder,
+            arguments: (tx_hash, fee_delta),
+        }) => {
+            let result = service.prioritise_transaction(tx_hash, fee_delta).await;
+            if let Err(e) = responder.send(result) {
+                error!("responder send prioritise_transaction failed {:?}", e)
+            };
+        }
         #[cfg(feature = "internal")]
         Message::PlugEntry(Request {
             responder,
@@ -1046,7 +1226,13 @@ async fn process(mut service: TxPoolService, message: Message) {
             let max_block_cycles = service.consensus.max_block_cycles();
             let max_block_bytes = service.consensus.max_block_bytes();
             let tx_pool = service.tx_pool.read().await;
+            let policy: Arc<dyn crate::selection::TxSelectionPolicy> = match service.block_assembler
+            {
+                Some(ref block_assembler) => Arc::clone(&block_assembler.selection_policy),
+                None => Arc::new(crate::selection::AncestorFeeRatePolicy),
+            };
             let (txs, _size, _cycles) = tx_pool.package_txs(
+                &*policy,
                 max_block_cycles,
                 bytes_limit.unwrap_or(max_block_bytes) as usize,
             );
@@ -1072,9 +1258,30 @@ impl TxPoolService {
             total_tx_size: tx_pool.total_tx_size,
             total_tx_cycles: tx_pool.total_tx_cycles,
             last_txs_updated_at: 0,
+            min_fee_rate: tx_pool.min_fee_rate(),
         }
     }
 
+    /// Updates the gauges of the pool sizes.
+    async fn update_metrics(&self) {
+        let orphan_size = self.orphan.read().await.len();
+        let tx_pool = self.tx_pool.read().await;
+        metrics!(gauge, "ckb.tx_pool.size", tx_pool.pending.size() as i64, "type" => "pending");
+        metrics!(gauge, "ckb.tx_pool.size", tx_pool.gap.size() as i64, "type" => "gap");
+        metrics!(gauge, "ckb.tx_pool.size", tx_pool.proposed.size() as i64, "type" => "proposed");
+        metrics!(gauge, "ckb.tx_pool.size", orphan_size as i64, "type" => "orphan");
+        metrics!(
+            gauge,
+            "ckb.tx_pool.total_tx_size",
+            tx_pool.total_tx_size as i64
+        );
+        metrics!(
+            gauge,
+            "ckb.tx_pool.total_tx_cycles",
+            tx_pool.total_tx_cycles as i64
+        );
+    }
+
     pub fn after_delay(&self) -> bool {
         self.after_delay.load(Ordering::Relaxed)
     }
@@ -1106,6 +1313,7 @@ impl TxPoolService {
     pub async fn update_block_assembler_before_tx_pool_reorg(
         &self,
         detached_blocks: VecDeque<BlockView>,
+        attached_blocks: &VecDeque<BlockView>,
         snapshot: Arc<Snapshot>,
     ) {
         if let Some(ref block_assembler) = self.block_assembler {
@@ -1115,6 +1323,9 @@ impl TxPoolService {
                     candidate_uncles.insert(detached_block.as_uncle());
                 }
             }
+            block_assembler
+                .record_found_blocks(attached_blocks.iter())
+                .await;
 
             if let Err(e) = block_assembler.update_blank(snapshot).await {
                 error!("block_assembler update_blank error {}", e);
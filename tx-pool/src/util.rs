@@ -2,9 +2,12 @@ use crate::error::Reject;
 use crate::pool::TxPool;
 use ckb_chain_spec::consensus::Consensus;
 use ckb_dao::DaoCalculator;
+use ckb_metrics::{metrics, Timer};
 use ckb_snapshot::Snapshot;
 use ckb_store::ChainStore;
-use ckb_types::core::{cell::ResolvedTransaction, Capacity, Cycle, EpochNumber, TransactionView};
+use ckb_types::core::{
+    cell::ResolvedTransaction, Capacity, Cycle, EpochNumber, FeeRate, TransactionView,
+};
 use ckb_verification::{
     cache::{CacheEntry, Completed},
     ContextualTransactionVerifier, NonContextualTransactionVerifier,
@@ -20,19 +23,22 @@ pub(crate) fn check_txid_collision(tx_pool: &TxPool, tx: &TransactionView) -> Re
     Ok(())
 }
 
-pub(crate) fn check_tx_size_limit(tx_pool: &TxPool, tx_size: usize) -> Result<(), Reject> {
+// When the pool is full, only the transaction paying more than the lowest ancestors score
+// pending transaction can make room for itself by eviction. The virtual bytes are unknown before
+// the verification, the size is used to filter out the hopeless transactions early.
+pub(crate) fn check_tx_size_limit(
+    tx_pool: &TxPool,
+    tx_size: usize,
+    fee: Capacity,
+) -> Result<(), Reject> {
     if tx_pool.reach_size_limit(tx_size) {
-        return Err(Reject::Full(
-            "size".to_owned(),
-            tx_pool.config.max_mem_size as u64,
-        ));
-    }
-    Ok(())
-}
-
-pub(crate) fn check_tx_cycle_limit(tx_pool: &TxPool, cycles: Cycle) -> Result<(), Reject> {
-    if tx_pool.reach_cycles_limit(cycles) {
-        return Err(Reject::Full("cycles".to_owned(), tx_pool.config.max_cycles));
+        let fee_rate = FeeRate::calculate(fee, tx_size);
+        if !matches!(tx_pool.min_evictable_fee_rate(), Some(evictable) if evictable < fee_rate) {
+            return Err(Reject::Full(
+                "size".to_owned(),
+                tx_pool.config.max_mem_size as u64,
+            ));
+        }
     }
     Ok(())
 }
@@ -46,11 +52,11 @@ pub(crate) fn check_tx_fee(
     let fee = DaoCalculator::new(snapshot.consensus(), &snapshot.as_data_provider())
         .transaction_fee(rtx)
         .map_err(|err| Reject::Malformed(format!("{}", err)))?;
-    let min_fee = tx_pool.config.min_fee_rate.fee(tx_size);
+    let min_fee_rate = tx_pool.min_fee_rate();
+    let min_fee = min_fee_rate.fee(tx_size);
     // reject txs which fee lower than min fee rate
     if fee < min_fee {
-        let reject =
-            Reject::LowFeeRate(tx_pool.config.min_fee_rate, min_fee.as_u64(), fee.as_u64());
+        let reject = Reject::LowFeeRate(min_fee_rate, min_fee.as_u64(), fee.as_u64());
         ckb_logger::debug!("reject tx {}", reject);
         return Err(reject);
     }
@@ -99,11 +105,14 @@ pub(crate) fn verify_rtx(
             .map_err(Reject::Verification),
         }
     } else {
-        block_in_place(|| {
+        let timer = Timer::start();
+        let ret = block_in_place(|| {
             ContextualTransactionVerifier::new(rtx, consensus, &snapshot.as_data_provider(), tx_env)
                 .verify(max_tx_verify_cycles, false)
                 .map_err(Reject::Verification)
-        })
+        });
+        metrics!(timing, "ckb.tx_pool.verify", timer.stop());
+        ret
     }
 }
 
@@ -118,6 +127,24 @@ pub(crate) fn time_relative_verify(
         .map_err(Reject::Verification)
 }
 
+/// Counts the reject by its kind.
+pub(crate) fn record_reject(reject: &Reject) {
+    let kind = match reject {
+        Reject::LowFeeRate(..) => "low_fee_rate",
+        Reject::ExceededMaximumAncestorsCount => "exceeded_maximum_ancestors_count",
+        Reject::Full(..) => "full",
+        Reject::Duplicated(..) => "duplicated",
+        Reject::Malformed(..) => "malformed",
+        Reject::DeclaredWrongCycles(..) => "declared_wrong_cycles",
+        Reject::Resolve(..) => "resolve",
+        Reject::Verification(..) => "verification",
+        Reject::Expiry(..) => "expiry",
+        Reject::RBFRejected(..) => "rbf_rejected",
+        Reject::RBFReplaced(..) => "rbf_replaced",
+    };
+    metrics!(counter, "ckb.tx_pool.reject", 1, "type" => kind);
+}
+
 pub(crate) fn is_missing_input(reject: &Reject) -> bool {
     matches!(reject, Reject::Resolve(out_point_err) if out_point_err.is_unknown())
 }
@@ -14,6 +14,7 @@ use ckb_chain_spec::ChainSpec;
 pub use ckb_logger_config::Config as LogConfig;
 pub use ckb_metrics_config::Config as MetricsConfig;
 use ckb_resource::Resource;
+use ckb_types::H256;
 
 use super::configs::*;
 #[cfg(feature = "with_sentry")]
@@ -88,6 +89,9 @@ pub struct CKBAppConfig {
     /// Notify config options.
     #[serde(default)]
     pub notify: NotifyConfig,
+    /// Indexer config options.
+    #[serde(default)]
+    pub indexer: IndexerConfig,
 }
 
 /// The miner config file for `ckb miner`. Usually it is the `ckb-miner.toml` in the CKB root
@@ -133,6 +137,9 @@ pub struct MinerAppConfig {
 pub struct ChainConfig {
     /// Specifies the chain spec.
     pub spec: Resource,
+    /// The pinned hash of the trusted snapshot, which is required by `ckb snapshot import`.
+    #[serde(default)]
+    pub snapshot_hash: Option<H256>,
 }
 
 impl AppConfig {
@@ -286,6 +293,9 @@ impl CKBAppConfig {
         let tx_pool_path = mkdir(self.data_dir.join("tx_pool"))?;
         self.tx_pool.adjust(root_dir, tx_pool_path);
 
+        let indexer_path = mkdir(self.data_dir.join("indexer"))?;
+        self.indexer.adjust(root_dir, indexer_path);
+
         if subcommand_name == cli::CMD_RESET_DATA {
             return Ok(self);
         }
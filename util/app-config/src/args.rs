@@ -2,7 +2,7 @@ use crate::{CKBAppConfig, MemoryTrackerConfig, MinerConfig};
 use ckb_chain_spec::consensus::Consensus;
 use ckb_jsonrpc_types::ScriptHashType;
 use ckb_pow::PowEngine;
-use ckb_types::packed::Byte32;
+use ckb_types::{packed::Byte32, H256};
 use faketime::unix_time_as_millis;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -15,6 +15,14 @@ pub struct ExportArgs {
     pub consensus: Consensus,
     /// The target directory to save the exported file.
     pub target: PathBuf,
+    /// The format of the exported file, `json` or `binary`.
+    pub format: String,
+    /// The compression of the binary format, `none`, `zstd` or `snappy`.
+    pub compression: String,
+    /// Specifies the starting block number. The default is 0.
+    pub from: Option<u64>,
+    /// Specifies the ending block number. The default is the tip block in the database.
+    pub to: Option<u64>,
 }
 
 /// Parsed command line arguments for `ckb import`.
@@ -27,6 +35,30 @@ pub struct ImportArgs {
     pub source: PathBuf,
 }
 
+/// Parsed command line arguments for `ckb snapshot export`.
+pub struct SnapshotExportArgs {
+    /// Parsed `ckb.toml`.
+    pub config: Box<CKBAppConfig>,
+    /// Loaded consensus.
+    pub consensus: Consensus,
+    /// The target directory to save the snapshot.
+    pub target: PathBuf,
+    /// The number of the snapshot block, the tip when it is `None`.
+    pub number: Option<u64>,
+}
+
+/// Parsed command line arguments for `ckb snapshot import`.
+pub struct SnapshotImportArgs {
+    /// Parsed `ckb.toml`.
+    pub config: Box<CKBAppConfig>,
+    /// Loaded consensus.
+    pub consensus: Consensus,
+    /// The path to the snapshot file.
+    pub source: PathBuf,
+    /// The trusted snapshot hash, from the command line or `ckb.toml`.
+    pub hash: Option<H256>,
+}
+
 /// Parsed command line arguments for `ckb run`.
 pub struct RunArgs {
     /// Parsed `ckb.toml`.
@@ -190,6 +222,22 @@ pub struct RepairArgs {
     pub config: Box<CKBAppConfig>,
 }
 
+/// Parsed command line arguments for `ckb db-check`.
+pub struct DbCheckArgs {
+    /// Parsed `ckb.toml`.
+    pub config: Box<CKBAppConfig>,
+    /// Loaded consensus.
+    pub consensus: Consensus,
+    /// Specifies the starting block number. The default is 0.
+    pub from: Option<u64>,
+    /// Specifies the ending block number. The default is the tip block in the database.
+    pub to: Option<u64>,
+    /// Recomputes the live cells created in the checked blocks.
+    pub check_cells: bool,
+    /// Rebuilds the corrupted indexes.
+    pub fix: bool,
+}
+
 impl CustomizeSpec {
     /// No specified parameters for chain spec.
     pub fn is_unset(&self) -> bool {
@@ -34,6 +34,10 @@ pub const CMD_FROM_SECRET: &str = "from-secret";
 pub const CMD_MIGRATE: &str = "migrate";
 /// Subcommand `db-repair`.
 pub const CMD_DB_REPAIR: &str = "db-repair";
+/// Subcommand `snapshot`.
+pub const CMD_SNAPSHOT: &str = "snapshot";
+/// Subcommand `db-check`.
+pub const CMD_DB_CHECK: &str = "db-check";
 
 /// Command line argument `--config-dir`.
 pub const ARG_CONFIG_DIR: &str = "config-dir";
@@ -79,6 +83,8 @@ pub const ARG_BA_ADVANCED: &str = "ba-advanced";
 pub const ARG_FROM: &str = "from";
 /// Command line argument `--to`.
 pub const ARG_TO: &str = "to";
+/// Command line argument `--number`.
+pub const ARG_NUMBER: &str = "number";
 /// Command line argument `--all`.
 pub const ARG_ALL: &str = "all";
 /// Command line argument `--limit`.
@@ -111,6 +117,14 @@ pub const ARG_OVERWRITE_CHAIN_SPEC: &str = "overwrite-spec";
 pub const ARG_ASSUME_VALID_TARGET: &str = "assume-valid-target";
 /// Command line argument `--check`.
 pub const ARG_MIGRATE_CHECK: &str = "check";
+/// Command line argument `--compression`.
+pub const ARG_COMPRESSION: &str = "compression";
+/// Command line argument `--hash`.
+pub const ARG_HASH: &str = "hash";
+/// Command line argument `--cells`.
+pub const ARG_CELLS: &str = "cells";
+/// Command line argument `--fix`.
+pub const ARG_FIX: &str = "fix";
 
 /// Command line arguments group `ba` for block assembler.
 const GROUP_BA: &str = "ba";
@@ -144,6 +158,8 @@ pub fn basic_app<'help>() -> Command<'help> {
         .subcommand(peer_id())
         .subcommand(migrate())
         .subcommand(db_repair())
+        .subcommand(snapshot())
+        .subcommand(db_check())
 }
 
 /// Parse the command line arguments by supplying the version information.
@@ -307,24 +323,103 @@ fn replay<'help>() -> Command<'help> {
 }
 
 fn export<'help>() -> Command<'help> {
-    Command::new(CMD_EXPORT).about("Exports ckb data").arg(
-        Arg::new(ARG_TARGET)
-            .short('t')
-            .long(ARG_TARGET)
-            .value_name("path")
-            .required(true)
-            .help("Specifies the export target path."),
-    )
+    Command::new(CMD_EXPORT)
+        .about("Exports ckb data")
+        .arg(
+            Arg::new(ARG_TARGET)
+                .short('t')
+                .long(ARG_TARGET)
+                .value_name("path")
+                .required(true)
+                .help("Specifies the export target path."),
+        )
+        .arg(
+            Arg::new(ARG_FORMAT)
+                .long(ARG_FORMAT)
+                .takes_value(true)
+                .possible_values(&["json", "binary"])
+                .default_value("json")
+                .help("Specifies the format of the exported file."),
+        )
+        .arg(
+            Arg::new(ARG_COMPRESSION)
+                .long(ARG_COMPRESSION)
+                .takes_value(true)
+                .possible_values(&["none", "zstd", "snappy"])
+                .default_value("none")
+                .help("Specifies the compression of the binary format."),
+        )
+        .arg(
+            Arg::new(ARG_FROM)
+                .long(ARG_FROM)
+                .takes_value(true)
+                .help("Specifies from block number. The default is 0."),
+        )
+        .arg(
+            Arg::new(ARG_TO)
+                .long(ARG_TO)
+                .takes_value(true)
+                .help("Specifies to block number. The default is the tip block."),
+        )
 }
 
 fn import<'help>() -> Command<'help> {
-    Command::new(CMD_IMPORT).about("Imports ckb data").arg(
-        Arg::new(ARG_SOURCE)
-            .index(1)
-            .value_name("path")
-            .required(true)
-            .help("Specifies the exported data path."),
-    )
+    Command::new(CMD_IMPORT)
+        .about("Imports ckb data, the blocks already in the database are skipped")
+        .arg(
+            Arg::new(ARG_SOURCE)
+                .index(1)
+                .value_name("path")
+                .required(true)
+                .help("Specifies the exported data path, in either the JSON or the binary format."),
+        )
+}
+
+fn snapshot<'help>() -> Command<'help> {
+    Command::new(CMD_SNAPSHOT)
+        .about("Exports or imports the snapshot of the live cell set")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new(CMD_EXPORT)
+                .about("Exports the snapshot at a main chain block and prints the snapshot hash")
+                .arg(
+                    Arg::new(ARG_TARGET)
+                        .short('t')
+                        .long(ARG_TARGET)
+                        .value_name("path")
+                        .required(true)
+                        .help("Specifies the export target path."),
+                )
+                .arg(
+                    Arg::new(ARG_NUMBER)
+                        .long(ARG_NUMBER)
+                        .value_name("number")
+                        .takes_value(true)
+                        .help("Specifies the number of the snapshot block [default: the tip]"),
+                ),
+        )
+        .subcommand(
+            Command::new(CMD_IMPORT)
+                .about("Bootstraps a fresh database from a trusted snapshot")
+                .arg(
+                    Arg::new(ARG_SOURCE)
+                        .index(1)
+                        .value_name("path")
+                        .required(true)
+                        .help("Specifies the snapshot file path."),
+                )
+                .arg(
+                    Arg::new(ARG_HASH)
+                        .long(ARG_HASH)
+                        .value_name("hash")
+                        .validator(is_h256)
+                        .takes_value(true)
+                        .help(
+                            "Specifies the trusted snapshot hash \
+                             [default: `snapshot_hash` in [chain] of ckb.toml]",
+                        ),
+                ),
+        )
 }
 
 fn migrate<'help>() -> Command<'help> {
@@ -347,6 +442,36 @@ fn db_repair<'help>() -> Command<'help> {
     Command::new(CMD_DB_REPAIR).about("Try repair ckb database")
 }
 
+fn db_check<'help>() -> Command<'help> {
+    Command::new(CMD_DB_CHECK)
+        .about(
+            "Checks the integrity of the chain data in the database and the freezer\n\
+             Example:\n\
+             ckb -C <dir> db-check --from 1000000 --cells",
+        )
+        .arg(
+            Arg::new(ARG_FROM)
+                .long(ARG_FROM)
+                .takes_value(true)
+                .help("Specifies from block number. The default is 0."),
+        )
+        .arg(
+            Arg::new(ARG_TO)
+                .long(ARG_TO)
+                .takes_value(true)
+                .help("Specifies to block number. The default is the tip block."),
+        )
+        .arg(Arg::new(ARG_CELLS).long(ARG_CELLS).help(
+            "Recomputes the live cells created in the checked blocks, \
+             which requires the bodies of all the blocks since the from block",
+        ))
+        .arg(
+            Arg::new(ARG_FIX)
+                .long(ARG_FIX)
+                .help("Rebuilds the corrupted indexes which can be recomputed from the blocks"),
+        )
+}
+
 fn list_hashes<'help>() -> Command<'help> {
     Command::new(CMD_LIST_HASHES)
         .about("Lists well known hashes")
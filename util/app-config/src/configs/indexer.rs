@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Indexer config options.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The indexer database directory path.
+    ///
+    /// By default, it is a subdirectory of 'indexer' subdirectory under the data directory.
+    #[serde(default)]
+    pub store: PathBuf,
+}
+
+impl Config {
+    /// Canonicalizes paths in the config options.
+    ///
+    /// If `self.store` is not set, set it to `indexer_dir / store`.
+    ///
+    /// If `self.store` is relative, convert it to absolute path using
+    /// `root_dir` as current working directory.
+    pub fn adjust<P: AsRef<Path>>(&mut self, root_dir: &Path, indexer_dir: P) {
+        if self.store.to_str().is_none() || self.store.to_str() == Some("") {
+            self.store = indexer_dir.as_ref().to_path_buf().join("store");
+        } else if self.store.is_relative() {
+            self.store = root_dir.to_path_buf().join(&self.store)
+        }
+    }
+}
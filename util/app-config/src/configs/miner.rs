@@ -10,7 +10,14 @@ pub struct Config {
     /// Miner connects to CKB node via RPC.
     pub client: ClientConfig,
     /// Miner workers config options.
+    #[serde(default)]
     pub workers: Vec<WorkerConfig>,
+    /// Stratum server config options.
+    ///
+    /// When it is set, miner also serves the work to the remote miners through the Stratum
+    /// protocol.
+    #[serde(default)]
+    pub stratum: Option<StratumConfig>,
 }
 
 /// RPC client config options.
@@ -26,9 +33,37 @@ pub struct ClientConfig {
     /// When this is enabled, miner will block until the submission RPC returns.
     pub block_on_submit: bool,
     /// listen block_template notify instead of loop poll
+    ///
+    /// The miner statistics are also served as JSON by `GET /stats` on this address.
     pub listen: Option<SocketAddr>,
 }
 
+/// Stratum server config options.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StratumConfig {
+    /// The address which the Stratum server listens on.
+    pub listen: SocketAddr,
+    /// The initial share difficulty of the workers.
+    ///
+    /// The share target is the max target divided by the difficulty.
+    #[serde(default = "default_stratum_difficulty")]
+    pub difficulty: u64,
+    /// The expected seconds between two shares of a worker.
+    ///
+    /// The share difficulty of each worker is adjusted to meet it.
+    #[serde(default = "default_stratum_share_interval")]
+    pub share_interval: u64,
+}
+
+const fn default_stratum_difficulty() -> u64 {
+    1024
+}
+
+const fn default_stratum_share_interval() -> u64 {
+    10
+}
+
 /// Miner worker config options.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "worker_type")]
@@ -37,6 +72,9 @@ pub enum WorkerConfig {
     Dummy(DummyConfig),
     /// Eaglesong worker which solves Eaglesong PoW.
     EaglesongSimple(EaglesongSimpleConfig),
+    /// Eaglesong worker whose threads share the work, check for the new work periodically and
+    /// report the hash rate.
+    EaglesongThreaded(EaglesongThreadedConfig),
 }
 
 /// Dummy worker config options.
@@ -83,6 +121,25 @@ pub struct EaglesongSimpleConfig {
     pub extra_hash_function: Option<ExtraHashFunction>,
 }
 
+/// Threaded Eaglesong worker config options.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EaglesongThreadedConfig {
+    /// Number of worker threads.
+    pub threads: usize,
+    /// Number of nonces hashed by a thread before it checks for the new work, rounded up to a
+    /// multiple of the nonces hashed at once.
+    #[serde(default = "default_eaglesong_check_interval")]
+    pub check_interval: usize,
+    /// Whether to perform an extra round of hash function on the Eaglesong output.
+    #[serde(default)]
+    pub extra_hash_function: Option<ExtraHashFunction>,
+}
+
+const fn default_eaglesong_check_interval() -> usize {
+    1024
+}
+
 /// Specifies the hash function.
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ExtraHashFunction {
@@ -1,4 +1,5 @@
 mod db;
+mod indexer;
 mod memory_tracker;
 mod miner;
 mod network;
@@ -9,19 +10,29 @@ mod store;
 mod tx_pool;
 
 pub use db::Config as DBConfig;
+pub use indexer::Config as IndexerConfig;
 pub use memory_tracker::Config as MemoryTrackerConfig;
 pub use miner::{
     ClientConfig as MinerClientConfig, Config as MinerConfig, DummyConfig, EaglesongSimpleConfig,
-    ExtraHashFunction, WorkerConfig as MinerWorkerConfig,
+    EaglesongThreadedConfig, ExtraHashFunction, StratumConfig as MinerStratumConfig,
+    WorkerConfig as MinerWorkerConfig,
 };
 pub use network::{
     default_support_all_protocols, Config as NetworkConfig, HeaderMapConfig, SupportProtocol,
     SyncConfig,
 };
 pub use network_alert::Config as NetworkAlertConfig;
-pub use notify::Config as NotifyConfig;
-pub use rpc::{Config as RpcConfig, Module as RpcModule};
+pub use notify::{
+    Config as NotifyConfig, WebhookConfig as NotifyWebhookConfig,
+    WebhookTopic as NotifyWebhookTopic,
+};
+pub use rpc::{
+    Config as RpcConfig, Credential as RpcCredential, Module as RpcModule,
+    RateLimitConfig as RpcRateLimitConfig,
+};
 pub use store::Config as StoreConfig;
-pub use tx_pool::{BlockAssemblerConfig, TxPoolConfig};
+pub use tx_pool::{
+    BlockAssemblerConfig, ExcludedScriptConfig, PayoutTargetConfig, TxPoolConfig, TxSelectionConfig,
+};
 
 pub(crate) use network::{generate_random_key, read_secret_key, write_secret_to_file};
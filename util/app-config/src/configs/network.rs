@@ -137,6 +137,8 @@ pub enum SupportProtocol {
     Relay,
     Time,
     Alert,
+    LightClient,
+    Filter,
 }
 
 #[allow(missing_docs)]
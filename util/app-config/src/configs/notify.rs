@@ -1,4 +1,6 @@
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use url::Url;
+
 /// Notify config options.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 #[serde(deny_unknown_fields)]
@@ -11,4 +13,88 @@ pub struct Config {
     ///
     /// The script is called with the alert message as the argument.
     pub network_alert_notify_script: Option<String>,
+    /// HTTP endpoints which the notifications are posted to.
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+/// The notifications which can be posted to a webhook.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookTopic {
+    /// A new block in the canonical chain.
+    NewBlock,
+    /// A new transaction committed in the tx-pool.
+    NewTransaction,
+    /// A transaction in the tx-pool which has been proposed.
+    ProposedTransaction,
+    /// A transaction which has been rejected by the tx-pool.
+    RejectedTransaction,
+    /// A network alert.
+    NetworkAlert,
+    /// A chain reorganization.
+    ChainReorg,
+}
+
+/// A webhook which the notifications are posted to.
+///
+/// The notifications are queued and posted one by one in a dedicated thread, so a slow receiver
+/// never blocks the node. The notifications are dropped when the queue is full. A failed
+/// notification is retried in the background, so it may arrive after the later ones.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// The HTTP endpoint, only `http` is supported since the notifications are posted without TLS.
+    #[serde(deserialize_with = "deserialize_http_url")]
+    pub url: Url,
+    /// The topics posted to the endpoint, all the topics when it is empty.
+    #[serde(default)]
+    pub topics: Vec<WebhookTopic>,
+    /// The timeout of each request.
+    #[serde(default = "default_webhook_timeout_millis")]
+    pub timeout_millis: u64,
+    /// How many times a failed notification is retried.
+    #[serde(default = "default_webhook_max_retries")]
+    pub max_retries: u32,
+    /// The interval before the first retry, which is doubled after each retry.
+    #[serde(default = "default_webhook_retry_interval_millis")]
+    pub retry_interval_millis: u64,
+    /// The max number of the notifications waiting to be posted.
+    #[serde(default = "default_webhook_queue_size")]
+    pub queue_size: usize,
+}
+
+impl WebhookConfig {
+    /// Whether the topic is posted to the endpoint.
+    pub fn accepts(&self, topic: WebhookTopic) -> bool {
+        self.topics.is_empty() || self.topics.contains(&topic)
+    }
+}
+
+fn deserialize_http_url<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Url, D::Error> {
+    let url = Url::deserialize(deserializer)?;
+    if url.scheme() == "http" {
+        Ok(url)
+    } else {
+        Err(de::Error::custom(format!(
+            "unsupported webhook url scheme `{}`, only `http` is supported",
+            url.scheme()
+        )))
+    }
+}
+
+const fn default_webhook_timeout_millis() -> u64 {
+    3000
+}
+
+const fn default_webhook_max_retries() -> u32 {
+    3
+}
+
+const fn default_webhook_retry_interval_millis() -> u64 {
+    500
+}
+
+const fn default_webhook_queue_size() -> usize {
+    256
 }
@@ -1,8 +1,9 @@
 use ckb_jsonrpc_types::Script;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// RPC modules.
-#[derive(Clone, Debug, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub enum Module {
     Net,
@@ -15,6 +16,57 @@ pub enum Module {
     Alert,
     Subscription,
     Debug,
+    Indexer,
+}
+
+/// An RPC credential and the methods it is allowed to call.
+///
+/// A request is authenticated by the `Authorization` header of HTTP, or by the `auth` method of
+/// the TCP and WebSocket connections, using either `Bearer <token>` or
+/// `Basic <base64(username:password)>`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Credential {
+    /// Bearer token.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// HTTP basic authentication user name.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// HTTP basic authentication password.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Modules whose methods are allowed.
+    #[serde(default)]
+    pub modules: Vec<Module>,
+    /// Methods which are allowed besides the ones in `modules`.
+    #[serde(default)]
+    pub methods: Vec<String>,
+}
+
+/// RPC rate limit options.
+///
+/// Every client has a token bucket which holds at most `burst` tokens and is refilled by
+/// `requests_per_second` tokens every second. A call takes the tokens of its method cost, and is
+/// rejected when the bucket does not hold enough tokens.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// Tokens refilled to each client every second.
+    pub requests_per_second: u32,
+    /// Max tokens a client can hold.
+    pub burst: u32,
+    /// Whether the address of an HTTP client is read from the last entry of the `X-Forwarded-For`
+    /// header, which is appended by the proxy.
+    ///
+    /// Only enable it when the RPC server is behind a trusted proxy. Otherwise all the HTTP
+    /// clients which are not authenticated share one token bucket, since the HTTP server does not
+    /// expose the client addresses.
+    #[serde(default)]
+    pub trust_forwarded_for: bool,
+    /// Method costs, which override the built-in ones. The cost of a method is 1 by default.
+    #[serde(default)]
+    pub method_costs: HashMap<String, u32>,
 }
 
 /// RPC config options.
@@ -53,6 +105,18 @@ pub struct Config {
     /// Customized extra well known type scripts.
     #[serde(default)]
     pub extra_well_known_type_scripts: Vec<Script>,
+    /// Credentials accepted by the RPC server.
+    ///
+    /// Authentication is disabled when it is empty, otherwise every request must be authenticated
+    /// by one of the credentials.
+    #[serde(default)]
+    pub credentials: Vec<Credential>,
+    /// Rate limits applied to every client.
+    ///
+    /// The clients are identified by their credentials when authentication is enabled, otherwise
+    /// by their addresses. Rate limiting is disabled when it is not set.
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
 }
 
 impl Config {
@@ -105,4 +169,14 @@ impl Config {
     pub fn debug_enable(&self) -> bool {
         self.modules.contains(&Module::Debug)
     }
+
+    /// Checks whether the RPC requests must be authenticated.
+    pub fn auth_enable(&self) -> bool {
+        !self.credentials.is_empty()
+    }
+
+    /// Checks whether the Indexer module is enabled.
+    pub fn indexer_enable(&self) -> bool {
+        self.modules.contains(&Module::Indexer)
+    }
 }
@@ -18,4 +18,10 @@ pub struct Config {
     pub block_extensions_cache_size: usize,
     /// whether enable freezer
     pub freezer_enable: bool,
+    /// Keeps only the bodies of the recent blocks within the depth from the tip, the older block
+    /// bodies, uncles, proposals and extensions are pruned.
+    ///
+    /// Pruning is disabled when it is `None`. It cannot be used with the RPC module `Indexer` or
+    /// the `Filter` protocol, which read the old block bodies.
+    pub prune_depth: Option<u64>,
 }
@@ -36,6 +36,17 @@ pub struct TxPoolConfig {
     pub recent_reject: PathBuf,
     /// The expiration time for pool transactions in hours
     pub expiry_hours: u8,
+    /// Whether a pending transaction can be replaced by a conflicting transaction which pays
+    /// more fee.
+    ///
+    /// When it is disabled, the conflicting transactions coexist in the pending pool.
+    pub enable_rbf: bool,
+    /// The replacement must pay at least this percentage more fee than the sum of the fees of
+    /// all the replaced transactions.
+    pub min_rbf_fee_bump_percent: u64,
+    /// The replacement fee rate must be at least this percentage higher than the fee rate of
+    /// every directly conflicting transaction.
+    pub min_rbf_fee_rate_bump_percent: u64,
 }
 
 /// Block assembler config options.
@@ -58,6 +69,10 @@ pub struct BlockAssemblerConfig {
     /// A field to store the block miner client version, non-configurable options.
     #[serde(skip)]
     pub binary_version: String,
+    /// Whether the locks other than the recommended secp256k1 lock are accepted, which is set by
+    /// `ckb run --ba-advanced`, non-configurable options.
+    #[serde(skip)]
+    pub advanced: bool,
     /// A field to control update interval millis
     #[serde(default = "default_update_interval_millis")]
     pub update_interval_millis: u64,
@@ -70,6 +85,69 @@ pub struct BlockAssemblerConfig {
     /// Notify timeout
     #[serde(default = "default_notify_timeout_millis")]
     pub notify_timeout_millis: u64,
+    /// The locks which receive the block rewards in turn, in proportion to their weights.
+    ///
+    /// This is a payout rotation, not a split of each reward: the consensus only allows the
+    /// cellbase to pay the whole block reward to a single lock, so fixed amounts and a lock for
+    /// the remainder are impossible. Each target receives the rewards of `weight` blocks in every
+    /// `sum(weight)` blocks found by this node. The found blocks are persisted next to the
+    /// tx-pool persisted data, so the rotation continues after restarting unless the targets are
+    /// changed. When it is empty, all the rewards go to the lock above.
+    #[serde(default)]
+    pub payout_targets: Vec<PayoutTargetConfig>,
+    /// The policy which selects the transactions into the proposals and the commitments.
+    #[serde(default)]
+    pub tx_selection: TxSelectionConfig,
+}
+
+/// The policy which selects the transactions into the block templates.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum TxSelectionConfig {
+    /// Selects the transactions by the fee rate including their ancestors.
+    AncestorFeeRate,
+    /// Same as `ancestor_fee_rate`, but ranks the transactions with the fee deltas set via the
+    /// RPC `prioritise_transaction`.
+    Prioritised,
+    /// Same as `ancestor_fee_rate`, but leaves out the transactions which use any of the scripts
+    /// as the lock or the type of an input or an output.
+    ExcludeScripts {
+        /// The excluded scripts.
+        scripts: Vec<ExcludedScriptConfig>,
+    },
+}
+
+impl Default for TxSelectionConfig {
+    fn default() -> Self {
+        TxSelectionConfig::AncestorFeeRate
+    }
+}
+
+/// A script excluded from the block templates.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExcludedScriptConfig {
+    /// The script code hash.
+    pub code_hash: H256,
+    /// The script hash type.
+    pub hash_type: ScriptHashType,
+    /// The script args, any args matches when it is absent.
+    #[serde(default)]
+    pub args: Option<JsonBytes>,
+}
+
+/// A lock which receives a share of the block rewards.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PayoutTargetConfig {
+    /// The lock script code hash.
+    pub code_hash: H256,
+    /// The lock script args.
+    pub args: JsonBytes,
+    /// The lock script hash type.
+    pub hash_type: ScriptHashType,
+    /// The number of blocks paid to this lock in every round of the targets.
+    pub weight: u64,
 }
 
 const fn default_use_binary_version_as_message_prefix() -> bool {
@@ -13,6 +13,10 @@ const DEFAULT_MAX_TX_VERIFY_CYCLES: Cycle = TWO_IN_TWO_OUT_CYCLES * 20;
 const DEFAULT_MAX_ANCESTORS_COUNT: usize = 125;
 // Default expiration time for pool transactions in hours
 const DEFAULT_EXPIRY_HOURS: u8 = 24;
+// Default min fee bump percentage of a replace-by-fee transaction
+const DEFAULT_MIN_RBF_FEE_BUMP_PERCENT: u64 = 10;
+// Default min fee rate bump percentage of a replace-by-fee transaction
+const DEFAULT_MIN_RBF_FEE_RATE_BUMP_PERCENT: u64 = 10;
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -36,6 +40,12 @@ pub(crate) struct TxPoolConfig {
     recent_reject: PathBuf,
     #[serde(default = "default_expiry_hours")]
     expiry_hours: u8,
+    #[serde(default)]
+    enable_rbf: bool,
+    #[serde(default = "default_min_rbf_fee_bump_percent")]
+    min_rbf_fee_bump_percent: u64,
+    #[serde(default = "default_min_rbf_fee_rate_bump_percent")]
+    min_rbf_fee_rate_bump_percent: u64,
 }
 
 fn default_keep_rejected_tx_hashes_days() -> u8 {
@@ -50,6 +60,14 @@ fn default_expiry_hours() -> u8 {
     DEFAULT_EXPIRY_HOURS
 }
 
+fn default_min_rbf_fee_bump_percent() -> u64 {
+    DEFAULT_MIN_RBF_FEE_BUMP_PERCENT
+}
+
+fn default_min_rbf_fee_rate_bump_percent() -> u64 {
+    DEFAULT_MIN_RBF_FEE_RATE_BUMP_PERCENT
+}
+
 impl Default for crate::TxPoolConfig {
     fn default() -> Self {
         TxPoolConfig::default().into()
@@ -72,6 +90,9 @@ impl Default for TxPoolConfig {
             persisted_data: Default::default(),
             recent_reject: Default::default(),
             expiry_hours: DEFAULT_EXPIRY_HOURS,
+            enable_rbf: false,
+            min_rbf_fee_bump_percent: DEFAULT_MIN_RBF_FEE_BUMP_PERCENT,
+            min_rbf_fee_rate_bump_percent: DEFAULT_MIN_RBF_FEE_RATE_BUMP_PERCENT,
         }
     }
 }
@@ -92,6 +113,9 @@ impl From<TxPoolConfig> for crate::TxPoolConfig {
             persisted_data,
             recent_reject,
             expiry_hours,
+            enable_rbf,
+            min_rbf_fee_bump_percent,
+            min_rbf_fee_rate_bump_percent,
         } = input;
         Self {
             max_mem_size,
@@ -104,6 +128,9 @@ impl From<TxPoolConfig> for crate::TxPoolConfig {
             persisted_data,
             recent_reject,
             expiry_hours,
+            enable_rbf,
+            min_rbf_fee_bump_percent,
+            min_rbf_fee_rate_bump_percent,
         }
     }
 }
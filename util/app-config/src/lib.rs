@@ -15,8 +15,8 @@ pub use app_config::{
     AppConfig, CKBAppConfig, ChainConfig, LogConfig, MetricsConfig, MinerAppConfig,
 };
 pub use args::{
-    ExportArgs, ImportArgs, InitArgs, MigrateArgs, MinerArgs, PeerIDArgs, RepairArgs, ReplayArgs,
-    ResetDataArgs, RunArgs, StatsArgs,
+    DbCheckArgs, ExportArgs, ImportArgs, InitArgs, MigrateArgs, MinerArgs, PeerIDArgs, RepairArgs,
+    ReplayArgs, ResetDataArgs, RunArgs, SnapshotExportArgs, SnapshotImportArgs, StatsArgs,
 };
 pub use configs::*;
 pub use exit_code::ExitCode;
@@ -122,6 +122,23 @@ impl Setup {
         Ok(RepairArgs { config })
     }
 
+    /// Executes `ckb db-check`.
+    pub fn db_check(self, matches: &ArgMatches) -> Result<DbCheckArgs, ExitCode> {
+        let consensus = self.consensus()?;
+        let config = self.config.into_ckb()?;
+        let from = option_value_t!(matches, cli::ARG_FROM, u64)?;
+        let to = option_value_t!(matches, cli::ARG_TO, u64)?;
+
+        Ok(DbCheckArgs {
+            config,
+            consensus,
+            from,
+            to,
+            check_cells: matches.is_present(cli::ARG_CELLS),
+            fix: matches.is_present(cli::ARG_FIX),
+        })
+    }
+
     /// Executes `ckb miner`.
     pub fn miner(self, matches: &ArgMatches) -> Result<MinerArgs, ExitCode> {
         let spec = self.chain_spec()?;
@@ -202,11 +219,52 @@ impl Setup {
         let consensus = self.consensus()?;
         let config = self.config.into_ckb()?;
         let target = matches.value_of_t(cli::ARG_TARGET)?;
+        let format = matches.value_of_t(cli::ARG_FORMAT)?;
+        let compression = matches.value_of_t(cli::ARG_COMPRESSION)?;
+        let from = option_value_t!(matches, cli::ARG_FROM, u64)?;
+        let to = option_value_t!(matches, cli::ARG_TO, u64)?;
 
         Ok(ExportArgs {
             config,
             consensus,
             target,
+            format,
+            compression,
+            from,
+            to,
+        })
+    }
+
+    /// Executes `ckb snapshot export`.
+    pub fn snapshot_export(self, matches: &ArgMatches) -> Result<SnapshotExportArgs, ExitCode> {
+        let consensus = self.consensus()?;
+        let config = self.config.into_ckb()?;
+        let target = matches.value_of_t(cli::ARG_TARGET)?;
+        let number = option_value_t!(matches, cli::ARG_NUMBER, u64)?;
+
+        Ok(SnapshotExportArgs {
+            config,
+            consensus,
+            target,
+            number,
+        })
+    }
+
+    /// Executes `ckb snapshot import`.
+    pub fn snapshot_import(self, matches: &ArgMatches) -> Result<SnapshotImportArgs, ExitCode> {
+        let consensus = self.consensus()?;
+        let config = self.config.into_ckb()?;
+        let source = matches.value_of_t(cli::ARG_SOURCE)?;
+        let hash = matches
+            .value_of(cli::ARG_HASH)
+            .and_then(|s| H256::from_str(&s[2..]).ok())
+            .or_else(|| config.chain.snapshot_hash.clone());
+
+        Ok(SnapshotImportArgs {
+            config,
+            consensus,
+            source,
+            hash,
         })
     }
 
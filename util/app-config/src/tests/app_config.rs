@@ -269,3 +269,24 @@ fn test_export_dev_config_files_assembly() {
         assert_eq!(miner_config.miner.client.rpc_url, "http://127.0.0.1:7000/");
     }
 }
+
+#[test]
+fn test_webhook_url_scheme() {
+    let config: crate::NotifyConfig = toml::from_str(
+        r#"
+        [[webhooks]]
+        url = "http://127.0.0.1:8080/notify"
+        "#,
+    )
+    .expect("deserialize config");
+    assert_eq!(config.webhooks.len(), 1);
+
+    let err = toml::from_str::<crate::NotifyConfig>(
+        r#"
+        [[webhooks]]
+        url = "https://127.0.0.1:8080/notify"
+        "#,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("only `http` is supported"));
+}
@@ -2,36 +2,53 @@
 use ckb_store::ChainStore;
 use ckb_types::{core::BlockNumber, core::BlockView};
 
+#[cfg(test)]
+mod tests;
+
 /// TODO(doc): @quake
 // An iterator over the entries of a `Chain`.
 pub struct ChainIterator<'a, S: ChainStore<'a>> {
     store: &'a S,
     current: Option<BlockView>,
+    from: BlockNumber,
     tip: BlockNumber,
 }
 
 impl<'a, S: ChainStore<'a>> ChainIterator<'a, S> {
     /// TODO(doc): @quake
     pub fn new(store: &'a S) -> Self {
-        let current = store.get_block_hash(0).and_then(|h| store.get_block(&h));
-        let tip = store.get_tip_header().expect("store inited").number();
+        Self::with_range(store, 0, None)
+    }
+
+    /// Creates an iterator over the main chain blocks from `from` to `to`, both inclusive.
+    ///
+    /// `to` defaults to the tip, and is capped at the tip.
+    pub fn with_range(store: &'a S, from: BlockNumber, to: Option<BlockNumber>) -> Self {
+        let tip_number = store.get_tip_header().expect("store inited").number();
+        let tip = to.map_or(tip_number, |to| to.min(tip_number));
+        let current = if from <= tip {
+            store.get_block_hash(from).and_then(|h| store.get_block(&h))
+        } else {
+            None
+        };
         ChainIterator {
             store,
             current,
+            from,
             tip,
         }
     }
 
     /// TODO(doc): @quake
     pub fn len(&self) -> u64 {
-        self.tip + 1
+        (self.tip + 1).saturating_sub(self.from)
     }
 
     /// Returns true if the ChainIterator has a length of 0.
-    // we always have genesis, this function may be meaningless
-    // but for convention, mute len-without-is-empty lint
+    ///
+    /// It is empty only when `from` is beyond the tip.
     pub fn is_empty(&self) -> bool {
-        false
+        self.len() == 0
     }
 }
 
@@ -42,14 +59,14 @@ impl<'a, S: ChainStore<'a>> Iterator for ChainIterator<'a, S> {
         let current = self.current.take();
 
         self.current = match current {
-            Some(ref b) => {
+            Some(ref b) if b.header().number() < self.tip => {
                 if let Some(block_hash) = self.store.get_block_hash(b.header().number() + 1) {
                     self.store.get_block(&block_hash)
                 } else {
                     None
                 }
             }
-            None => None,
+            _ => None,
         };
         current
     }
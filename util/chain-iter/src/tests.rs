@@ -0,0 +1,75 @@
+use crate::ChainIterator;
+use ckb_db::RocksDB;
+use ckb_db_schema::COLUMNS;
+use ckb_store::ChainDB;
+use ckb_types::{
+    core::{BlockBuilder, BlockNumber},
+    prelude::*,
+};
+use tempfile::TempDir;
+
+// Stores a chain of empty blocks from the genesis to the tip.
+fn prepare_store(tip: BlockNumber) -> (TempDir, ChainDB) {
+    let tmp_dir = TempDir::new().unwrap();
+    let db = RocksDB::open_in(&tmp_dir, COLUMNS);
+    let store = ChainDB::new(db, Default::default());
+    let txn = store.begin_transaction();
+    let mut parent_hash = Default::default();
+    for number in 0..=tip {
+        let block = BlockBuilder::default()
+            .parent_hash(parent_hash)
+            .number(number.pack())
+            .build();
+        txn.insert_block(&block).unwrap();
+        txn.attach_block(&block).unwrap();
+        txn.insert_tip_header(&block.header()).unwrap();
+        parent_hash = block.hash();
+    }
+    txn.commit().unwrap();
+    (tmp_dir, store)
+}
+
+fn numbers(iter: ChainIterator<'_, ChainDB>) -> Vec<BlockNumber> {
+    iter.map(|block| block.number()).collect()
+}
+
+#[test]
+fn test_with_range() {
+    let (_tmp_dir, store) = prepare_store(10);
+
+    let iter = ChainIterator::new(&store);
+    assert_eq!(iter.len(), 11);
+    assert_eq!(numbers(iter), (0..=10).collect::<Vec<_>>());
+
+    let iter = ChainIterator::with_range(&store, 3, Some(5));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(numbers(iter), vec![3, 4, 5]);
+
+    // `to` defaults to the tip
+    let iter = ChainIterator::with_range(&store, 8, None);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(numbers(iter), vec![8, 9, 10]);
+
+    // `to` is capped at the tip
+    let iter = ChainIterator::with_range(&store, 9, Some(100));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(numbers(iter), vec![9, 10]);
+
+    let iter = ChainIterator::with_range(&store, 10, Some(10));
+    assert_eq!(numbers(iter), vec![10]);
+}
+
+#[test]
+fn test_with_empty_range() {
+    let (_tmp_dir, store) = prepare_store(10);
+
+    // `from` is beyond the tip
+    let iter = ChainIterator::with_range(&store, 11, None);
+    assert!(iter.is_empty());
+    assert_eq!(numbers(iter), Vec::<BlockNumber>::new());
+
+    // `from` is beyond `to`
+    let iter = ChainIterator::with_range(&store, 6, Some(5));
+    assert!(iter.is_empty());
+    assert_eq!(numbers(iter), Vec::<BlockNumber>::new());
+}
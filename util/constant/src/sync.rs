@@ -46,6 +46,16 @@ pub const MAX_LOCATOR_SIZE: usize = 101;
 
 /// Block download timeout
 pub const BLOCK_DOWNLOAD_TIMEOUT: u64 = 30 * 1000; // 30s
+/// The minimum time before a block request to a slow peer is reassigned to the other peers
+pub const MIN_BLOCK_STALL_TIMEOUT: u64 = 2 * 1000; // 2s
+/// A block request is considered stalled when it takes this many times the measured latency of
+/// the peer
+pub const BLOCK_STALL_LATENCY_FACTOR: u64 = 4;
+/// Only the requests of this many lowest in-flight blocks are reassigned when they stall, since
+/// they block the verification of the blocks above them. The others wait for the download timeout
+pub const MAX_STALLED_BLOCKS_TO_REASSIGN: usize = 16;
+/// The period to measure the download throughput of each peer
+pub const BLOCK_THROUGHPUT_PERIOD: u64 = 10 * 1000; // 10s
 
 /// Block download window size
 // Size of the "block download window": how far ahead of our current height do we fetch?
@@ -0,0 +1,489 @@
+//! The indexer database which applies and reverts blocks of the canonical chain.
+use ckb_db::{iter::DBIterator, Direction, IteratorMode, RocksDB, RocksDBWriteBatch};
+use ckb_db_schema::Col;
+use ckb_error::Error;
+use ckb_types::{
+    bytes::Bytes,
+    core::{BlockNumber, BlockView},
+    packed::{self, Byte32, OutPoint},
+    prelude::*,
+};
+use std::collections::HashMap;
+
+/// Total column number of the indexer database
+pub const COLUMNS: u32 = 7;
+/// Column store live cells, keyed by the out point
+pub const COLUMN_CELL: Col = "0";
+/// Column store the live cells index by lock script
+pub const COLUMN_CELL_LOCK_SCRIPT: Col = "1";
+/// Column store the live cells index by type script
+pub const COLUMN_CELL_TYPE_SCRIPT: Col = "2";
+/// Column store the transactions index by the lock scripts of inputs and outputs
+pub const COLUMN_TX_LOCK_SCRIPT: Col = "3";
+/// Column store the transactions index by the type scripts of inputs and outputs
+pub const COLUMN_TX_TYPE_SCRIPT: Col = "4";
+/// Column store the indexed blocks, keyed by block number and block hash
+pub const COLUMN_BLOCK: Col = "5";
+/// Column store the cells consumed by the indexed blocks, used to rollback
+pub const COLUMN_CONSUMED_CELL: Col = "6";
+
+/// The number of the recent blocks which can be rolled back.
+pub const KEEP_NUM: BlockNumber = 10_000;
+
+// block_number | tx_index | output_index
+pub(crate) const CELL_KEY_SUFFIX_LEN: usize = 8 + 4 + 4;
+// block_number | tx_index | io_index | io_type
+pub(crate) const TX_KEY_SUFFIX_LEN: usize = 8 + 4 + 4 + 1;
+const OUT_POINT_LEN: usize = 36;
+
+/// Whether an indexed transaction is matched by an input or an output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CellType {
+    /// The transaction consumes the matched cell.
+    Input = 0,
+    /// The transaction creates the matched cell.
+    Output = 1,
+}
+
+/// A live cell stored in the indexer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cell {
+    /// The number of the block which committed the cell.
+    pub block_number: BlockNumber,
+    /// The position of the transaction in the block.
+    pub tx_index: u32,
+    /// The cell fields except the cell data.
+    pub output: packed::CellOutput,
+    /// The cell data.
+    pub output_data: Bytes,
+}
+
+impl Cell {
+    // block_number | tx_index | output (molecule table, self-sized) | output_data
+    fn to_vec(&self) -> Vec<u8> {
+        let output = self.output.as_slice();
+        let mut value = Vec::with_capacity(8 + 4 + output.len() + self.output_data.len());
+        value.extend_from_slice(&self.block_number.to_be_bytes());
+        value.extend_from_slice(&self.tx_index.to_be_bytes());
+        value.extend_from_slice(output);
+        value.extend_from_slice(&self.output_data);
+        value
+    }
+
+    fn from_slice(slice: &[u8]) -> Self {
+        let block_number = be_u64(&slice[0..8]);
+        let tx_index = be_u32(&slice[8..12]);
+        let mut output_size = [0u8; 4];
+        output_size.copy_from_slice(&slice[12..16]);
+        let output_end = 12 + u32::from_le_bytes(output_size) as usize;
+        let output =
+            packed::CellOutputReader::from_slice_should_be_ok(&slice[12..output_end]).to_entity();
+        Cell {
+            block_number,
+            tx_index,
+            output,
+            output_data: Bytes::copy_from_slice(&slice[output_end..]),
+        }
+    }
+}
+
+/// Serializes a script as `code_hash | hash_type | args`, which is the key prefix in the script
+/// index columns.
+///
+/// Because the args is not length-prefixed, any prefix of the args is also a prefix of the key.
+pub fn script_key(script: &packed::Script) -> Vec<u8> {
+    let args = script.args().raw_data();
+    let mut key = Vec::with_capacity(32 + 1 + args.len());
+    key.extend_from_slice(script.code_hash().as_slice());
+    key.extend_from_slice(script.hash_type().as_slice());
+    key.extend_from_slice(&args);
+    key
+}
+
+fn cell_script_key(
+    script: &packed::Script,
+    block_number: BlockNumber,
+    tx_index: u32,
+    output_index: u32,
+) -> Vec<u8> {
+    let mut key = script_key(script);
+    key.extend_from_slice(&block_number.to_be_bytes());
+    key.extend_from_slice(&tx_index.to_be_bytes());
+    key.extend_from_slice(&output_index.to_be_bytes());
+    key
+}
+
+fn tx_script_key(
+    script: &packed::Script,
+    block_number: BlockNumber,
+    tx_index: u32,
+    io_index: u32,
+    io_type: CellType,
+) -> Vec<u8> {
+    let mut key = script_key(script);
+    key.extend_from_slice(&block_number.to_be_bytes());
+    key.extend_from_slice(&tx_index.to_be_bytes());
+    key.extend_from_slice(&io_index.to_be_bytes());
+    key.push(io_type as u8);
+    key
+}
+
+fn block_key(block_number: BlockNumber, block_hash: &Byte32) -> Vec<u8> {
+    let mut key = Vec::with_capacity(8 + 32);
+    key.extend_from_slice(&block_number.to_be_bytes());
+    key.extend_from_slice(block_hash.as_slice());
+    key
+}
+
+fn consumed_cell_key(block_number: BlockNumber, tx_index: u32, input_index: u32) -> Vec<u8> {
+    let mut key = Vec::with_capacity(8 + 4 + 4);
+    key.extend_from_slice(&block_number.to_be_bytes());
+    key.extend_from_slice(&tx_index.to_be_bytes());
+    key.extend_from_slice(&input_index.to_be_bytes());
+    key
+}
+
+pub(crate) fn be_u64(slice: &[u8]) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(slice);
+    u64::from_be_bytes(buf)
+}
+
+pub(crate) fn be_u32(slice: &[u8]) -> u32 {
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(slice);
+    u32::from_be_bytes(buf)
+}
+
+/// The indexer database.
+///
+/// Blocks must be appended in the order of the canonical chain, and rolled back from the tip
+/// when the canonical chain is reorganized.
+#[derive(Clone)]
+pub struct Indexer {
+    db: RocksDB,
+}
+
+impl Indexer {
+    /// Creates the indexer on an opened database with `COLUMNS` columns.
+    pub fn new(db: RocksDB) -> Self {
+        Indexer { db }
+    }
+
+    pub(crate) fn db(&self) -> &RocksDB {
+        &self.db
+    }
+
+    /// Returns the number and the hash of the last indexed block.
+    pub fn tip(&self) -> Result<Option<(BlockNumber, Byte32)>, Error> {
+        Ok(self
+            .db
+            .iter(COLUMN_BLOCK, IteratorMode::End)?
+            .next()
+            .map(|(key, _value)| {
+                (
+                    be_u64(&key[0..8]),
+                    packed::Byte32Reader::from_slice_should_be_ok(&key[8..40]).to_entity(),
+                )
+            }))
+    }
+
+    /// Returns the live cell referenced by the out point.
+    pub fn get_cell(&self, out_point: &OutPoint) -> Result<Option<Cell>, Error> {
+        Ok(self
+            .db
+            .get_pinned(COLUMN_CELL, out_point.as_slice())?
+            .map(|slice| Cell::from_slice(&slice)))
+    }
+
+    /// Applies the block on top of the indexed chain.
+    pub fn append(&self, block: &BlockView) -> Result<(), Error> {
+        let mut batch = self.db.new_write_batch();
+        let block_number = block.number();
+        // Cells created in this block, which may be consumed by the following transactions in
+        // the same block before the batch is written.
+        let mut new_cells: HashMap<OutPoint, Cell> = HashMap::new();
+        let mut block_value = Vec::with_capacity(block.transactions().len() * (32 + 4));
+
+        for (tx_index, tx) in block.transactions().iter().enumerate() {
+            let tx_index = tx_index as u32;
+            let tx_hash = tx.hash();
+            block_value.extend_from_slice(tx_hash.as_slice());
+            block_value.extend_from_slice(&(tx.outputs().len() as u32).to_be_bytes());
+
+            // skip cellbase
+            if tx_index > 0 {
+                for (input_index, out_point) in tx.input_pts_iter().enumerate() {
+                    let input_index = input_index as u32;
+                    let cell = match new_cells.remove(&out_point) {
+                        Some(cell) => cell,
+                        None => match self.get_cell(&out_point)? {
+                            Some(cell) => cell,
+                            None => continue,
+                        },
+                    };
+                    let output_index: u32 = out_point.index().unpack();
+                    delete_live_cell(&mut batch, &out_point, output_index, &cell)?;
+
+                    let lock = cell.output.lock();
+                    batch.put(
+                        COLUMN_TX_LOCK_SCRIPT,
+                        &tx_script_key(&lock, block_number, tx_index, input_index, CellType::Input),
+                        tx_hash.as_slice(),
+                    )?;
+                    if let Some(type_script) = cell.output.type_().to_opt() {
+                        batch.put(
+                            COLUMN_TX_TYPE_SCRIPT,
+                            &tx_script_key(
+                                &type_script,
+                                block_number,
+                                tx_index,
+                                input_index,
+                                CellType::Input,
+                            ),
+                            tx_hash.as_slice(),
+                        )?;
+                    }
+
+                    let mut consumed = Vec::with_capacity(OUT_POINT_LEN + 64);
+                    consumed.extend_from_slice(out_point.as_slice());
+                    consumed.extend_from_slice(&cell.to_vec());
+                    batch.put(
+                        COLUMN_CONSUMED_CELL,
+                        &consumed_cell_key(block_number, tx_index, input_index),
+                        &consumed,
+                    )?;
+                }
+            }
+
+            for (output_index, (output, output_data)) in tx.outputs_with_data_iter().enumerate() {
+                let output_index = output_index as u32;
+                let out_point = OutPoint::new(tx_hash.clone(), output_index);
+                let cell = Cell {
+                    block_number,
+                    tx_index,
+                    output,
+                    output_data,
+                };
+                insert_live_cell(&mut batch, &out_point, output_index, &cell)?;
+
+                let lock = cell.output.lock();
+                batch.put(
+                    COLUMN_TX_LOCK_SCRIPT,
+                    &tx_script_key(
+                        &lock,
+                        block_number,
+                        tx_index,
+                        output_index,
+                        CellType::Output,
+                    ),
+                    tx_hash.as_slice(),
+                )?;
+                if let Some(type_script) = cell.output.type_().to_opt() {
+                    batch.put(
+                        COLUMN_TX_TYPE_SCRIPT,
+                        &tx_script_key(
+                            &type_script,
+                            block_number,
+                            tx_index,
+                            output_index,
+                            CellType::Output,
+                        ),
+                        tx_hash.as_slice(),
+                    )?;
+                }
+                new_cells.insert(out_point, cell);
+            }
+        }
+
+        batch.put(
+            COLUMN_BLOCK,
+            &block_key(block_number, &block.hash()),
+            &block_value,
+        )?;
+        self.db.write(&batch)
+    }
+
+    /// Reverts the last indexed block.
+    pub fn rollback(&self) -> Result<(), Error> {
+        let (block_number, block_hash, block_value) =
+            match self.db.iter(COLUMN_BLOCK, IteratorMode::End)?.next() {
+                Some((key, value)) => (
+                    be_u64(&key[0..8]),
+                    packed::Byte32Reader::from_slice_should_be_ok(&key[8..40]).to_entity(),
+                    value,
+                ),
+                None => return Ok(()),
+            };
+        let mut batch = self.db.new_write_batch();
+
+        // Restore the cells consumed by this block. Cells both created and consumed in this
+        // block are kept in `consumed_in_block` and removed with the other outputs below.
+        let mut consumed_in_block: HashMap<OutPoint, Cell> = HashMap::new();
+        let prefix = block_number.to_be_bytes();
+        for (key, value) in self
+            .db
+            .iter(
+                COLUMN_CONSUMED_CELL,
+                IteratorMode::From(&prefix, Direction::Forward),
+            )?
+            .take_while(|(key, _)| key.starts_with(&prefix))
+        {
+            let tx_index = be_u32(&key[8..12]);
+            let input_index = be_u32(&key[12..16]);
+            let out_point =
+                packed::OutPointReader::from_slice_should_be_ok(&value[..OUT_POINT_LEN])
+                    .to_entity();
+            let cell = Cell::from_slice(&value[OUT_POINT_LEN..]);
+
+            batch.delete(COLUMN_CONSUMED_CELL, &key)?;
+            batch.delete(
+                COLUMN_TX_LOCK_SCRIPT,
+                &tx_script_key(
+                    &cell.output.lock(),
+                    block_number,
+                    tx_index,
+                    input_index,
+                    CellType::Input,
+                ),
+            )?;
+            if let Some(type_script) = cell.output.type_().to_opt() {
+                batch.delete(
+                    COLUMN_TX_TYPE_SCRIPT,
+                    &tx_script_key(
+                        &type_script,
+                        block_number,
+                        tx_index,
+                        input_index,
+                        CellType::Input,
+                    ),
+                )?;
+            }
+
+            if cell.block_number == block_number {
+                consumed_in_block.insert(out_point, cell);
+            } else {
+                let output_index: u32 = out_point.index().unpack();
+                insert_live_cell(&mut batch, &out_point, output_index, &cell)?;
+            }
+        }
+
+        // Remove the cells created by this block.
+        for (tx_index, tx_value) in block_value.chunks(32 + 4).enumerate() {
+            let tx_index = tx_index as u32;
+            let tx_hash =
+                packed::Byte32Reader::from_slice_should_be_ok(&tx_value[..32]).to_entity();
+            let outputs_len = be_u32(&tx_value[32..36]);
+            for output_index in 0..outputs_len {
+                let out_point = OutPoint::new(tx_hash.clone(), output_index);
+                let cell = match consumed_in_block.remove(&out_point) {
+                    Some(cell) => cell,
+                    None => match self.get_cell(&out_point)? {
+                        Some(cell) => cell,
+                        None => continue,
+                    },
+                };
+                delete_live_cell(&mut batch, &out_point, output_index, &cell)?;
+                batch.delete(
+                    COLUMN_TX_LOCK_SCRIPT,
+                    &tx_script_key(
+                        &cell.output.lock(),
+                        block_number,
+                        tx_index,
+                        output_index,
+                        CellType::Output,
+                    ),
+                )?;
+                if let Some(type_script) = cell.output.type_().to_opt() {
+                    batch.delete(
+                        COLUMN_TX_TYPE_SCRIPT,
+                        &tx_script_key(
+                            &type_script,
+                            block_number,
+                            tx_index,
+                            output_index,
+                            CellType::Output,
+                        ),
+                    )?;
+                }
+            }
+        }
+
+        batch.delete(COLUMN_BLOCK, &block_key(block_number, &block_hash))?;
+        self.db.write(&batch)
+    }
+
+    /// Removes the rollback data of the blocks which are `KEEP_NUM` blocks older than the tip.
+    pub fn prune(&self) -> Result<(), Error> {
+        let tip_number = match self.tip()? {
+            Some((tip_number, _)) if tip_number > KEEP_NUM => tip_number,
+            _ => return Ok(()),
+        };
+        let prune_to = (tip_number - KEEP_NUM).to_be_bytes();
+        let mut batch = self.db.new_write_batch();
+        for col in [COLUMN_BLOCK, COLUMN_CONSUMED_CELL] {
+            for (key, _value) in self
+                .db
+                .iter(col, IteratorMode::Start)?
+                .take_while(|(key, _)| key[0..8] < prune_to[..])
+            {
+                batch.delete(col, &key)?;
+            }
+        }
+        self.db.write(&batch)
+    }
+}
+
+fn insert_live_cell(
+    batch: &mut RocksDBWriteBatch,
+    out_point: &OutPoint,
+    output_index: u32,
+    cell: &Cell,
+) -> Result<(), Error> {
+    let tx_hash = out_point.tx_hash();
+    batch.put(COLUMN_CELL, out_point.as_slice(), &cell.to_vec())?;
+    batch.put(
+        COLUMN_CELL_LOCK_SCRIPT,
+        &cell_script_key(
+            &cell.output.lock(),
+            cell.block_number,
+            cell.tx_index,
+            output_index,
+        ),
+        tx_hash.as_slice(),
+    )?;
+    if let Some(type_script) = cell.output.type_().to_opt() {
+        batch.put(
+            COLUMN_CELL_TYPE_SCRIPT,
+            &cell_script_key(&type_script, cell.block_number, cell.tx_index, output_index),
+            tx_hash.as_slice(),
+        )?;
+    }
+    Ok(())
+}
+
+fn delete_live_cell(
+    batch: &mut RocksDBWriteBatch,
+    out_point: &OutPoint,
+    output_index: u32,
+    cell: &Cell,
+) -> Result<(), Error> {
+    batch.delete(COLUMN_CELL, out_point.as_slice())?;
+    batch.delete(
+        COLUMN_CELL_LOCK_SCRIPT,
+        &cell_script_key(
+            &cell.output.lock(),
+            cell.block_number,
+            cell.tx_index,
+            output_index,
+        ),
+    )?;
+    if let Some(type_script) = cell.output.type_().to_opt() {
+        batch.delete(
+            COLUMN_CELL_TYPE_SCRIPT,
+            &cell_script_key(&type_script, cell.block_number, cell.tx_index, output_index),
+        )?;
+    }
+    Ok(())
+}
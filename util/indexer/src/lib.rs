@@ -0,0 +1,13 @@
+//! The built-in cell indexer.
+//!
+//! The indexer follows the canonical chain in a background thread, and indexes the live cells
+//! and the transactions by their lock scripts and type scripts. The blocks which are no longer
+//! in the canonical chain are rolled back.
+mod indexer;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use crate::indexer::{Cell, CellType, Indexer, COLUMNS, KEEP_NUM};
+pub use crate::service::{IndexerController, IndexerHandle, IndexerService};
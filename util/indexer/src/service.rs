@@ -0,0 +1,506 @@
+//! The indexer service which follows the canonical chain, and the handle to query it.
+use crate::indexer::{
+    be_u32, be_u64, script_key, Cell, Indexer, CELL_KEY_SUFFIX_LEN, COLUMNS,
+    COLUMN_CELL_LOCK_SCRIPT, COLUMN_CELL_TYPE_SCRIPT, COLUMN_TX_LOCK_SCRIPT, COLUMN_TX_TYPE_SCRIPT,
+    TX_KEY_SUFFIX_LEN,
+};
+use ckb_app_config::IndexerConfig;
+use ckb_channel::{self as channel, select, Receiver};
+use ckb_db::{iter::DBIterator, Direction, IteratorMode, RocksDB};
+use ckb_db_schema::Col;
+use ckb_error::Error;
+use ckb_jsonrpc_types::{
+    IndexerCell, IndexerCellType, IndexerCellsCapacity, IndexerOrder, IndexerPagination,
+    IndexerScriptSearchMode, IndexerScriptType, IndexerSearchKey, IndexerSearchKeyFilter,
+    IndexerTx, JsonBytes,
+};
+use ckb_logger::{error, info};
+use ckb_shared::Shared;
+use ckb_stop_handler::{SignalSender, StopHandler};
+use ckb_store::ChainStore;
+use ckb_types::{
+    bytes::Bytes,
+    core::{service::ChainReorg, BlockNumber, BlockView, Capacity},
+    packed::{self, OutPoint},
+    prelude::*,
+};
+use std::thread;
+
+/// Prune the rollback data once per this number of blocks.
+const PRUNE_INTERVAL: BlockNumber = 1_000;
+
+const SUBSCRIBER_NAME: &str = "indexer";
+
+/// The indexer service, which applies the blocks of the canonical chain to the indexer database
+/// in a background thread.
+///
+/// The service is woken up by the new block and the chain reorganization notifications.
+pub struct IndexerService {
+    shared: Shared,
+    indexer: Indexer,
+}
+
+impl IndexerService {
+    /// Opens the indexer database at `config.store`.
+    pub fn new(shared: Shared, config: &IndexerConfig) -> Self {
+        let db = RocksDB::open_in(&config.store, COLUMNS);
+        IndexerService {
+            shared,
+            indexer: Indexer::new(db),
+        }
+    }
+
+    /// Returns a handle to query the indexer.
+    pub fn handle(&self) -> IndexerHandle {
+        IndexerHandle::new(self.indexer.clone())
+    }
+
+    /// Starts the background thread.
+    pub fn start<S: ToString>(self, thread_name: Option<S>) -> IndexerController {
+        let (signal_sender, signal_receiver) = channel::bounded::<()>(1);
+        let handle = self.handle();
+        // Subscribe before the first sync, so no block attached after it is missed.
+        let notify_controller = self.shared.notify_controller();
+        let receivers = Receivers {
+            signal: signal_receiver,
+            new_block: notify_controller.subscribe_new_block(SUBSCRIBER_NAME),
+            chain_reorg: notify_controller.subscribe_chain_reorg(SUBSCRIBER_NAME),
+        };
+
+        let mut thread_builder = thread::Builder::new();
+        if let Some(name) = thread_name {
+            thread_builder = thread_builder.name(name.to_string());
+        }
+        let thread = thread_builder
+            .spawn(move || {
+                if let Err(e) = self.sync(&receivers) {
+                    error!("Indexer sync error {}", e);
+                }
+                loop {
+                    select! {
+                        recv(receivers.signal) -> _ => break,
+                        recv(receivers.new_block) -> msg => if msg.is_err() {
+                            break;
+                        },
+                        recv(receivers.chain_reorg) -> msg => match msg {
+                            Ok(reorg) => {
+                                if let Err(e) = self.rollback_to(reorg.ancestor.number()) {
+                                    error!("Indexer rollback error {}", e);
+                                }
+                            }
+                            Err(_) => break,
+                        },
+                    }
+                    if let Err(e) = self.sync(&receivers) {
+                        error!("Indexer sync error {}", e);
+                    }
+                }
+                info!("Indexer closing");
+            })
+            .expect("Start IndexerService failed");
+
+        let stop = StopHandler::new(
+            SignalSender::Crossbeam(signal_sender),
+            Some(thread),
+            "indexer".to_string(),
+        );
+        IndexerController { stop, handle }
+    }
+
+    /// Catches up with the canonical chain, rolling back the blocks which are no longer in it.
+    fn sync(&self, receivers: &Receivers) -> Result<(), Error> {
+        loop {
+            if !receivers.signal.is_empty() {
+                return Ok(());
+            }
+            // Each step reads a new snapshot, which already includes the notified blocks. Drain
+            // the notifications while catching up, so the notify service is never blocked by a slow indexer.
+            while receivers.new_block.try_recv().is_ok() {}
+            while let Ok(reorg) = receivers.chain_reorg.try_recv() {
+                self.rollback_to(reorg.ancestor.number())?;
+            }
+
+            if !self.append_next()? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Catches up with the canonical chain in the current thread.
+    ///
+    /// It is used to index the chain before the service is started, e.g., in tests.
+    pub fn index_chain(&self) -> Result<(), Error> {
+        while self.append_next()? {}
+        Ok(())
+    }
+
+    /// Applies the next block of the canonical chain, or rolls back the tip which is no longer
+    /// in it. Returns false when the indexer has caught up.
+    fn append_next(&self) -> Result<bool, Error> {
+        let snapshot = self.shared.snapshot();
+        match self.indexer.tip()? {
+            Some((tip_number, tip_hash)) => {
+                if snapshot.get_block_hash(tip_number).as_ref() != Some(&tip_hash) {
+                    info!("Indexer rollback block {} {}", tip_number, tip_hash);
+                    self.indexer.rollback()?;
+                    return Ok(true);
+                }
+                let next_number = tip_number + 1;
+                match snapshot
+                    .get_block_hash(next_number)
+                    .and_then(|hash| snapshot.get_block(&hash))
+                {
+                    Some(block) => {
+                        self.indexer.append(&block)?;
+                        if next_number % PRUNE_INTERVAL == 0 {
+                            self.indexer.prune()?;
+                        }
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            }
+            None => {
+                let genesis = snapshot
+                    .get_block_hash(0)
+                    .and_then(|hash| snapshot.get_block(&hash))
+                    .expect("genesis block stored");
+                self.indexer.append(&genesis)?;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Rolls back the indexed blocks above the common ancestor of a chain reorganization.
+    ///
+    /// The blocks of the new chain may have been attached at the same heights before the
+    /// indexer sees them, so it does not rely on comparing the tip hash alone.
+    fn rollback_to(&self, number: BlockNumber) -> Result<(), Error> {
+        while let Some((tip_number, tip_hash)) = self.indexer.tip()? {
+            if tip_number <= number {
+                break;
+            }
+            info!("Indexer rollback block {} {}", tip_number, tip_hash);
+            self.indexer.rollback()?;
+        }
+        Ok(())
+    }
+}
+
+struct Receivers {
+    signal: Receiver<()>,
+    new_block: Receiver<BlockView>,
+    chain_reorg: Receiver<ChainReorg>,
+}
+
+/// Keeps the indexer service running, and stops it when dropped.
+pub struct IndexerController {
+    stop: StopHandler<()>,
+    handle: IndexerHandle,
+}
+
+impl IndexerController {
+    /// Returns a handle to query the indexer.
+    pub fn handle(&self) -> IndexerHandle {
+        self.handle.clone()
+    }
+}
+
+impl Drop for IndexerController {
+    fn drop(&mut self) {
+        self.stop.try_send(());
+    }
+}
+
+/// The conditions of `IndexerSearchKeyFilter` in the indexer representation.
+struct Filter {
+    script: Option<Vec<u8>>,
+    output_data: Option<Bytes>,
+    output_data_len_range: Option<[u64; 2]>,
+    output_capacity_range: Option<[u64; 2]>,
+    block_range: Option<[u64; 2]>,
+}
+
+impl From<IndexerSearchKeyFilter> for Filter {
+    fn from(filter: IndexerSearchKeyFilter) -> Self {
+        Filter {
+            script: filter
+                .script
+                .map(|script| script_key(&packed::Script::from(script))),
+            output_data: filter.output_data.map(JsonBytes::into_bytes),
+            output_data_len_range: filter
+                .output_data_len_range
+                .map(|[start, end]| [start.value(), end.value()]),
+            output_capacity_range: filter
+                .output_capacity_range
+                .map(|[start, end]| [start.value(), end.value()]),
+            block_range: filter
+                .block_range
+                .map(|[start, end]| [start.value(), end.value()]),
+        }
+    }
+}
+
+impl Filter {
+    fn match_block_number(&self, block_number: BlockNumber) -> bool {
+        in_range(&self.block_range, block_number)
+    }
+
+    fn match_cell(&self, cell: &Cell, script_type: IndexerScriptType) -> bool {
+        if let Some(prefix) = &self.script {
+            let other_script = match script_type {
+                IndexerScriptType::Lock => cell.output.type_().to_opt(),
+                IndexerScriptType::Type => Some(cell.output.lock()),
+            };
+            match other_script {
+                Some(script) if script_key(&script).starts_with(prefix) => {}
+                _ => return false,
+            }
+        }
+        if let Some(prefix) = &self.output_data {
+            if !cell.output_data.starts_with(prefix) {
+                return false;
+            }
+        }
+        let capacity: Capacity = cell.output.capacity().unpack();
+        in_range(&self.output_data_len_range, cell.output_data.len() as u64)
+            && in_range(&self.output_capacity_range, capacity.as_u64())
+            && self.match_block_number(cell.block_number)
+    }
+}
+
+fn in_range(range: &Option<[u64; 2]>, value: u64) -> bool {
+    match range {
+        Some([start, end]) => *start <= value && value < *end,
+        None => true,
+    }
+}
+
+/// The handle to query the indexer.
+#[derive(Clone)]
+pub struct IndexerHandle {
+    indexer: Indexer,
+}
+
+impl IndexerHandle {
+    pub(crate) fn new(indexer: Indexer) -> Self {
+        IndexerHandle { indexer }
+    }
+
+    /// Returns the live cells matched by the search key.
+    ///
+    /// Returns at most `limit` cells after `after_cursor`, which is the `last_cursor` of the
+    /// previous page.
+    pub fn get_cells(
+        &self,
+        search_key: IndexerSearchKey,
+        order: IndexerOrder,
+        limit: usize,
+        after_cursor: Option<JsonBytes>,
+    ) -> Result<IndexerPagination<IndexerCell>, Error> {
+        let (prefix, exact) = search_prefix(&search_key);
+        let filter = search_key.filter.map(Filter::from);
+        let with_data = search_key.with_data.unwrap_or(true);
+        let col = match search_key.script_type {
+            IndexerScriptType::Lock => COLUMN_CELL_LOCK_SCRIPT,
+            IndexerScriptType::Type => COLUMN_CELL_TYPE_SCRIPT,
+        };
+
+        let mut cells = Vec::new();
+        let last_cursor = self.scan(col, &prefix, order, after_cursor, |key, value| {
+            if cells.len() >= limit {
+                return Ok(false);
+            }
+            if exact && key.len() != prefix.len() + CELL_KEY_SUFFIX_LEN {
+                return Ok(true);
+            }
+            let output_index = be_u32(&key[key.len() - 4..]);
+            let tx_hash = packed::Byte32Reader::from_slice_should_be_ok(value).to_entity();
+            let out_point = OutPoint::new(tx_hash, output_index);
+            let cell = match self.indexer.get_cell(&out_point)? {
+                Some(cell) => cell,
+                None => return Ok(true),
+            };
+            if let Some(filter) = &filter {
+                if !filter.match_cell(&cell, search_key.script_type) {
+                    return Ok(true);
+                }
+            }
+            cells.push(IndexerCell {
+                output: cell.output.into(),
+                output_data: if with_data {
+                    Some(JsonBytes::from_bytes(cell.output_data))
+                } else {
+                    None
+                },
+                out_point: out_point.into(),
+                block_number: cell.block_number.into(),
+                tx_index: cell.tx_index.into(),
+            });
+            Ok(true)
+        })?;
+
+        Ok(IndexerPagination::new(cells, last_cursor))
+    }
+
+    /// Returns the transactions which consume or create the cells matched by the search key.
+    ///
+    /// Only `block_range` of the filter is applied.
+    pub fn get_transactions(
+        &self,
+        search_key: IndexerSearchKey,
+        order: IndexerOrder,
+        limit: usize,
+        after_cursor: Option<JsonBytes>,
+    ) -> Result<IndexerPagination<IndexerTx>, Error> {
+        let (prefix, exact) = search_prefix(&search_key);
+        let filter = search_key.filter.map(Filter::from);
+        let col = match search_key.script_type {
+            IndexerScriptType::Lock => COLUMN_TX_LOCK_SCRIPT,
+            IndexerScriptType::Type => COLUMN_TX_TYPE_SCRIPT,
+        };
+
+        let mut txs = Vec::new();
+        let last_cursor = self.scan(col, &prefix, order, after_cursor, |key, value| {
+            if txs.len() >= limit {
+                return Ok(false);
+            }
+            if exact && key.len() != prefix.len() + TX_KEY_SUFFIX_LEN {
+                return Ok(true);
+            }
+            let suffix = &key[key.len() - TX_KEY_SUFFIX_LEN..];
+            let block_number = be_u64(&suffix[0..8]);
+            if let Some(filter) = &filter {
+                if !filter.match_block_number(block_number) {
+                    return Ok(true);
+                }
+            }
+            let tx_hash = packed::Byte32Reader::from_slice_should_be_ok(value).to_entity();
+            txs.push(IndexerTx {
+                tx_hash: tx_hash.unpack(),
+                block_number: block_number.into(),
+                tx_index: be_u32(&suffix[8..12]).into(),
+                io_index: be_u32(&suffix[12..16]).into(),
+                io_type: if suffix[16] == 0 {
+                    IndexerCellType::Input
+                } else {
+                    IndexerCellType::Output
+                },
+            });
+            Ok(true)
+        })?;
+
+        Ok(IndexerPagination::new(txs, last_cursor))
+    }
+
+    /// Returns the total capacity of the live cells matched by the search key.
+    ///
+    /// Returns `None` if the indexer has not indexed any block.
+    pub fn get_cells_capacity(
+        &self,
+        search_key: IndexerSearchKey,
+    ) -> Result<Option<IndexerCellsCapacity>, Error> {
+        let (block_number, block_hash) = match self.indexer.tip()? {
+            Some(tip) => tip,
+            None => return Ok(None),
+        };
+        let (prefix, exact) = search_prefix(&search_key);
+        let filter = search_key.filter.map(Filter::from);
+        let col = match search_key.script_type {
+            IndexerScriptType::Lock => COLUMN_CELL_LOCK_SCRIPT,
+            IndexerScriptType::Type => COLUMN_CELL_TYPE_SCRIPT,
+        };
+
+        let mut capacity = 0u64;
+        self.scan(col, &prefix, IndexerOrder::Asc, None, |key, value| {
+            if exact && key.len() != prefix.len() + CELL_KEY_SUFFIX_LEN {
+                return Ok(true);
+            }
+            let output_index = be_u32(&key[key.len() - 4..]);
+            let tx_hash = packed::Byte32Reader::from_slice_should_be_ok(value).to_entity();
+            let cell = match self
+                .indexer
+                .get_cell(&OutPoint::new(tx_hash, output_index))?
+            {
+                Some(cell) => cell,
+                None => return Ok(true),
+            };
+            if let Some(filter) = &filter {
+                if !filter.match_cell(&cell, search_key.script_type) {
+                    return Ok(true);
+                }
+            }
+            let cell_capacity: Capacity = cell.output.capacity().unpack();
+            capacity = capacity.saturating_add(cell_capacity.as_u64());
+            Ok(true)
+        })?;
+
+        Ok(Some(IndexerCellsCapacity {
+            capacity: capacity.into(),
+            block_hash: block_hash.unpack(),
+            block_number: block_number.into(),
+        }))
+    }
+
+    /// Iterates the keys starting with `prefix` in `order`, after `after_cursor` if it is
+    /// present, until `f` returns `false`.
+    ///
+    /// Returns the last key passed to `f` which returned `true`, or the start key if there is
+    /// none, so the next page starts exactly after it.
+    fn scan<F>(
+        &self,
+        col: Col,
+        prefix: &[u8],
+        order: IndexerOrder,
+        after_cursor: Option<JsonBytes>,
+        mut f: F,
+    ) -> Result<JsonBytes, Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<bool, Error>,
+    {
+        let cursor = after_cursor.map(|cursor| cursor.as_bytes().to_vec());
+        let start = match (&cursor, order) {
+            (Some(cursor), _) => Some(cursor.clone()),
+            (None, IndexerOrder::Asc) => Some(prefix.to_vec()),
+            (None, IndexerOrder::Desc) => prefix_successor(prefix),
+        };
+        let mode = match (&start, order) {
+            (Some(start), IndexerOrder::Asc) => IteratorMode::From(&start[..], Direction::Forward),
+            (Some(start), IndexerOrder::Desc) => IteratorMode::From(&start[..], Direction::Reverse),
+            (None, _) => IteratorMode::End,
+        };
+
+        let db = self.indexer.db();
+        let mut last_key = start.clone().unwrap_or_default();
+        for (key, value) in db
+            .iter(col, mode)?
+            .skip_while(|(key, _)| Some(&key[..]) == start.as_deref())
+            .take_while(|(key, _)| key.starts_with(prefix))
+        {
+            if !f(&key, &value)? {
+                break;
+            }
+            last_key = key.to_vec();
+        }
+        Ok(JsonBytes::from_vec(last_key))
+    }
+}
+
+fn search_prefix(search_key: &IndexerSearchKey) -> (Vec<u8>, bool) {
+    let script = packed::Script::from(search_key.script.clone());
+    let exact = search_key.script_search_mode.unwrap_or_default() == IndexerScriptSearchMode::Exact;
+    (script_key(&script), exact)
+}
+
+/// Returns the smallest key which is greater than all the keys starting with `prefix`, or `None`
+/// if there is no such key.
+fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut key = prefix.to_vec();
+    while let Some(last) = key.pop() {
+        if last < u8::MAX {
+            key.push(last + 1);
+            return Some(key);
+        }
+    }
+    None
+}
@@ -0,0 +1,282 @@
+use crate::{Indexer, IndexerHandle, COLUMNS};
+use ckb_db::RocksDB;
+use ckb_jsonrpc_types::{
+    IndexerCellType, IndexerOrder, IndexerScriptSearchMode, IndexerScriptType, IndexerSearchKey,
+    IndexerSearchKeyFilter, JsonBytes,
+};
+use ckb_types::{
+    bytes::Bytes,
+    core::{BlockBuilder, BlockView, Capacity, ScriptHashType, TransactionBuilder},
+    packed::{self, CellInput, CellOutput, OutPoint, Script},
+    prelude::*,
+};
+
+fn setup() -> (tempfile::TempDir, Indexer, IndexerHandle) {
+    let tmp_dir = tempfile::Builder::new().tempdir().unwrap();
+    let indexer = Indexer::new(RocksDB::open_in(tmp_dir.path(), COLUMNS));
+    let handle = IndexerHandle::new(indexer.clone());
+    (tmp_dir, indexer, handle)
+}
+
+fn script(code_hash: u8, args: &[u8]) -> Script {
+    Script::new_builder()
+        .code_hash(packed::Byte32::new([code_hash; 32]))
+        .hash_type(ScriptHashType::Data.into())
+        .args(Bytes::copy_from_slice(args).pack())
+        .build()
+}
+
+fn output(capacity: u64, lock: Script, type_: Option<Script>) -> CellOutput {
+    CellOutput::new_builder()
+        .capacity(Capacity::shannons(capacity).pack())
+        .lock(lock)
+        .type_(type_.pack())
+        .build()
+}
+
+fn search_key(script: Script, script_type: IndexerScriptType) -> IndexerSearchKey {
+    IndexerSearchKey {
+        script: script.into(),
+        script_type,
+        script_search_mode: None,
+        filter: None,
+        with_data: None,
+    }
+}
+
+fn cellbase(number: u64, lock: Script) -> ckb_types::core::TransactionView {
+    TransactionBuilder::default()
+        .input(CellInput::new_cellbase_input(number))
+        .output(output(1000, lock, None))
+        .output_data(Bytes::new().pack())
+        .build()
+}
+
+// genesis: cellbase -> lock_a
+// block 1: cellbase -> lock_b
+//          tx1: genesis cellbase#0 -> [lock_a 300 "0x01", lock_c with type_t 700]
+//          tx2: tx1#0 -> [lock_b 300]
+fn blocks() -> (BlockView, BlockView) {
+    let genesis_cellbase = cellbase(0, script(1, b"a"));
+    let genesis = BlockBuilder::default()
+        .number(0.pack())
+        .transaction(genesis_cellbase.clone())
+        .build();
+
+    let tx1 = TransactionBuilder::default()
+        .input(CellInput::new(OutPoint::new(genesis_cellbase.hash(), 0), 0))
+        .output(output(300, script(1, b"a"), None))
+        .output_data(Bytes::from(vec![1]).pack())
+        .output(output(700, script(1, b"c"), Some(script(2, b"t"))))
+        .output_data(Bytes::new().pack())
+        .build();
+    let tx2 = TransactionBuilder::default()
+        .input(CellInput::new(OutPoint::new(tx1.hash(), 0), 0))
+        .output(output(300, script(1, b"b"), None))
+        .output_data(Bytes::new().pack())
+        .build();
+    let block1 = BlockBuilder::default()
+        .number(1.pack())
+        .parent_hash(genesis.hash())
+        .transaction(cellbase(1, script(1, b"b")))
+        .transaction(tx1)
+        .transaction(tx2)
+        .build();
+
+    (genesis, block1)
+}
+
+#[test]
+fn append_and_rollback() {
+    let (_tmp_dir, indexer, handle) = setup();
+    let (genesis, block1) = blocks();
+
+    indexer.append(&genesis).unwrap();
+    assert_eq!(indexer.tip().unwrap(), Some((0, genesis.hash())));
+    let cells = handle
+        .get_cells(
+            search_key(script(1, b"a"), IndexerScriptType::Lock),
+            IndexerOrder::Asc,
+            10,
+            None,
+        )
+        .unwrap();
+    assert_eq!(cells.objects.len(), 1);
+
+    indexer.append(&block1).unwrap();
+    assert_eq!(indexer.tip().unwrap(), Some((1, block1.hash())));
+    // the genesis cell and the cell created by tx1 are both consumed
+    let cells = handle
+        .get_cells(
+            search_key(script(1, b"a"), IndexerScriptType::Lock),
+            IndexerOrder::Asc,
+            10,
+            None,
+        )
+        .unwrap();
+    assert!(cells.objects.is_empty());
+    let cells = handle
+        .get_cells(
+            search_key(script(1, b"b"), IndexerScriptType::Lock),
+            IndexerOrder::Asc,
+            10,
+            None,
+        )
+        .unwrap();
+    assert_eq!(cells.objects.len(), 2);
+    let cells = handle
+        .get_cells(
+            search_key(script(2, b"t"), IndexerScriptType::Type),
+            IndexerOrder::Asc,
+            10,
+            None,
+        )
+        .unwrap();
+    assert_eq!(cells.objects.len(), 1);
+
+    indexer.rollback().unwrap();
+    assert_eq!(indexer.tip().unwrap(), Some((0, genesis.hash())));
+    let cells = handle
+        .get_cells(
+            search_key(script(1, b"a"), IndexerScriptType::Lock),
+            IndexerOrder::Asc,
+            10,
+            None,
+        )
+        .unwrap();
+    assert_eq!(cells.objects.len(), 1);
+    assert_eq!(
+        cells.objects[0].out_point,
+        OutPoint::new(genesis.transactions()[0].hash(), 0).into()
+    );
+    for (matched_script, script_type) in &[
+        (script(1, b"b"), IndexerScriptType::Lock),
+        (script(1, b"c"), IndexerScriptType::Lock),
+        (script(2, b"t"), IndexerScriptType::Type),
+    ] {
+        let key = search_key(matched_script.clone(), *script_type);
+        let cells = handle
+            .get_cells(key.clone(), IndexerOrder::Asc, 10, None)
+            .unwrap();
+        assert!(cells.objects.is_empty());
+        let txs = handle
+            .get_transactions(key, IndexerOrder::Asc, 10, None)
+            .unwrap();
+        assert!(txs.objects.is_empty());
+    }
+}
+
+#[test]
+fn get_transactions() {
+    let (_tmp_dir, indexer, handle) = setup();
+    let (genesis, block1) = blocks();
+    indexer.append(&genesis).unwrap();
+    indexer.append(&block1).unwrap();
+
+    let txs = handle
+        .get_transactions(
+            search_key(script(1, b"a"), IndexerScriptType::Lock),
+            IndexerOrder::Asc,
+            10,
+            None,
+        )
+        .unwrap();
+    let io_types: Vec<_> = txs.objects.iter().map(|tx| tx.io_type).collect();
+    // genesis output, tx1 input, tx1 output, tx2 input
+    assert_eq!(
+        io_types,
+        vec![
+            IndexerCellType::Output,
+            IndexerCellType::Input,
+            IndexerCellType::Output,
+            IndexerCellType::Input,
+        ]
+    );
+
+    let mut key = search_key(script(1, b"a"), IndexerScriptType::Lock);
+    key.filter = Some(IndexerSearchKeyFilter {
+        block_range: Some([1u64.into(), 2u64.into()]),
+        ..Default::default()
+    });
+    let txs = handle
+        .get_transactions(key, IndexerOrder::Desc, 10, None)
+        .unwrap();
+    assert_eq!(txs.objects.len(), 3);
+    assert_eq!(txs.objects[0].io_type, IndexerCellType::Input);
+    assert_eq!(txs.objects[0].tx_index, 2u32.into());
+}
+
+#[test]
+fn search_mode_and_filter() {
+    let (_tmp_dir, indexer, handle) = setup();
+    let (genesis, block1) = blocks();
+    indexer.append(&genesis).unwrap();
+    indexer.append(&block1).unwrap();
+
+    // prefix mode matches both lock_b and lock_c
+    let key = search_key(script(1, b""), IndexerScriptType::Lock);
+    let cells = handle
+        .get_cells(key.clone(), IndexerOrder::Asc, 10, None)
+        .unwrap();
+    assert_eq!(cells.objects.len(), 3);
+
+    let mut exact_key = key.clone();
+    exact_key.script_search_mode = Some(IndexerScriptSearchMode::Exact);
+    let cells = handle
+        .get_cells(exact_key, IndexerOrder::Asc, 10, None)
+        .unwrap();
+    assert!(cells.objects.is_empty());
+
+    let mut filtered_key = key.clone();
+    filtered_key.filter = Some(IndexerSearchKeyFilter {
+        script: Some(script(2, b"t").into()),
+        ..Default::default()
+    });
+    let capacity = handle
+        .get_cells_capacity(filtered_key)
+        .unwrap()
+        .expect("indexed");
+    assert_eq!(capacity.capacity, 700u64.into());
+    assert_eq!(capacity.block_number, 1u64.into());
+
+    let mut filtered_key = key;
+    filtered_key.filter = Some(IndexerSearchKeyFilter {
+        output_capacity_range: Some([0u64.into(), 500u64.into()]),
+        ..Default::default()
+    });
+    filtered_key.with_data = Some(false);
+    let cells = handle
+        .get_cells(filtered_key, IndexerOrder::Asc, 10, None)
+        .unwrap();
+    assert_eq!(cells.objects.len(), 1);
+    assert_eq!(cells.objects[0].output_data, None);
+}
+
+#[test]
+fn pagination() {
+    let (_tmp_dir, indexer, handle) = setup();
+    let (genesis, block1) = blocks();
+    indexer.append(&genesis).unwrap();
+    indexer.append(&block1).unwrap();
+
+    let key = search_key(script(1, b""), IndexerScriptType::Lock);
+    for order in &[IndexerOrder::Asc, IndexerOrder::Desc] {
+        let all = handle
+            .get_cells(key.clone(), *order, 10, None)
+            .unwrap()
+            .objects;
+        let mut paged = Vec::new();
+        let mut cursor: Option<JsonBytes> = None;
+        loop {
+            let page = handle
+                .get_cells(key.clone(), *order, 2, cursor.take())
+                .unwrap();
+            if page.objects.is_empty() {
+                break;
+            }
+            paged.extend(page.objects);
+            cursor = Some(page.last_cursor);
+        }
+        assert_eq!(all, paged);
+    }
+}
@@ -1,8 +1,9 @@
+use crate::format::{Format, Header, SegmentWriter};
+use crate::progress::Progress;
 use ckb_chain_iter::ChainIterator;
 use ckb_jsonrpc_types::BlockView as JsonBlock;
 use ckb_shared::shared::Shared;
-#[cfg(feature = "progress_bar")]
-use indicatif::{ProgressBar, ProgressStyle};
+use ckb_types::core::BlockNumber;
 use std::error::Error;
 use std::fs;
 use std::io;
@@ -15,70 +16,93 @@ pub struct Export {
     pub target: PathBuf,
     /// CKB shared data.
     pub shared: Shared,
+    /// The format of the exported file.
+    pub format: Format,
+    /// The number of the first exported block.
+    pub from: BlockNumber,
+    /// The number of the last exported block, the tip when it is `None`.
+    pub to: Option<BlockNumber>,
 }
 
 impl Export {
     /// Creates the export job.
-    pub fn new(shared: Shared, target: PathBuf) -> Self {
-        Export { shared, target }
+    pub fn new(
+        shared: Shared,
+        target: PathBuf,
+        format: Format,
+        from: BlockNumber,
+        to: Option<BlockNumber>,
+    ) -> Self {
+        Export {
+            shared,
+            target,
+            format,
+            from,
+            to,
+        }
     }
 
     /// export file name
-    fn file_name(&self) -> String {
-        format!("{}.{}", self.shared.consensus().id, "json")
+    ///
+    /// The range is appended to the chain id unless the whole chain is exported.
+    fn file_name(&self, to: BlockNumber) -> String {
+        let name = if self.from == 0 && self.to.is_none() {
+            self.shared.consensus().id.clone()
+        } else {
+            format!("{}-{}-{}", self.shared.consensus().id, self.from, to)
+        };
+        match self.format {
+            Format::Json => format!("{}.{}", name, "json"),
+            Format::Binary(compression) => {
+                format!("{}.{}{}", name, "blocks", compression.extension())
+            }
+        }
     }
 
     /// Executes the export job.
     pub fn execute(self) -> Result<(), Box<dyn Error>> {
         fs::create_dir_all(&self.target)?;
-        self.write_to_json()
-    }
-
-    #[cfg(not(feature = "progress_bar"))]
-    pub fn write_to_json(self) -> Result<(), Box<dyn Error>> {
-        let f = fs::OpenOptions::new()
-            .create_new(true)
-            .read(true)
-            .write(true)
-            .open(&self.target.join(self.file_name()))?;
-        let mut writer = io::BufWriter::new(f);
         let snapshot = self.shared.snapshot();
-
-        for block in ChainIterator::new(snapshot.as_ref()) {
-            let block: JsonBlock = block.into();
-            let encoded = serde_json::to_vec(&block)?;
-            writer.write_all(&encoded)?;
-            writer.write_all(b"\n")?;
+        let blocks_iter = ChainIterator::with_range(snapshot.as_ref(), self.from, self.to);
+        if blocks_iter.is_empty() {
+            return Err(format!("no block to export from {}", self.from).into());
         }
-        Ok(())
-    }
-
-    /// Export the chain into JSON.
-    #[cfg(feature = "progress_bar")]
-    pub fn write_to_json(self) -> Result<(), Box<dyn Error>> {
+        let to = self.from + blocks_iter.len() - 1;
         let f = fs::OpenOptions::new()
             .create_new(true)
             .read(true)
             .write(true)
-            .open(&self.target.join(self.file_name()))?;
-
+            .open(&self.target.join(self.file_name(to)))?;
         let mut writer = io::BufWriter::new(f);
-        let snapshot = self.shared.snapshot();
-        let blocks_iter = ChainIterator::new(snapshot.as_ref());
-        let progress_bar = ProgressBar::new(blocks_iter.len());
-        progress_bar.set_style(
-            ProgressStyle::default_bar()
-                .template("[{elapsed_precise}] {bar:50.cyan/blue} {pos:>6}/{len:6} {msg}")
-                .progress_chars("##-"),
-        );
-        for block in blocks_iter {
-            let block: JsonBlock = block.into();
-            let encoded = serde_json::to_vec(&block)?;
-            writer.write_all(&encoded)?;
-            writer.write_all(b"\n")?;
-            progress_bar.inc(1);
+        let progress = Progress::blocks(blocks_iter.len());
+
+        match self.format {
+            Format::Json => {
+                for block in blocks_iter {
+                    let block: JsonBlock = block.into();
+                    let encoded = serde_json::to_vec(&block)?;
+                    writer.write_all(&encoded)?;
+                    writer.write_all(b"\n")?;
+                    progress.inc(1);
+                }
+                writer.flush()?;
+            }
+            Format::Binary(compression) => {
+                Header {
+                    compression,
+                    from: self.from,
+                    to,
+                }
+                .write_to(&mut writer)?;
+                let mut segments = SegmentWriter::new(compression.encoder(writer)?);
+                for block in blocks_iter {
+                    segments.push(&block)?;
+                    progress.inc(1);
+                }
+                segments.finish()?.finish()?.flush()?;
+            }
         }
-        progress_bar.finish_with_message("done!");
+        progress.finish("done!".to_string());
         Ok(())
     }
 }
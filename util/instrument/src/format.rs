@@ -0,0 +1,340 @@
+//! The formats of the exported chain data.
+//!
+//! The binary format starts with a [`Header`], followed by the segments, which are compressed as
+//! a whole stream when the compression is enabled. Each segment is laid out as:
+//!
+//! - the number of the blocks, `u32` in little endian, where 0 marks the end of the file;
+//! - the size of the payload, `u64` in little endian;
+//! - the payload, which is the molecule encoded blocks, each prefixed by its size in `u32`;
+//! - the blake2b hash of the payload.
+use ckb_hash::blake2b_256;
+use ckb_types::{
+    core::{BlockNumber, BlockView},
+    packed,
+    prelude::*,
+};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+
+/// The magic number at the beginning of the binary files.
+pub(crate) const MAGIC: &[u8; 8] = b"CKBCHAIN";
+const VERSION: u8 = 1;
+/// The max number of the blocks in a segment.
+pub(crate) const SEGMENT_BLOCKS: u32 = 1000;
+/// A segment is closed once its payload exceeds the size.
+const SEGMENT_SIZE: usize = 64 * 1024 * 1024;
+/// Guards the allocation when reading a corrupted file.
+const MAX_SEGMENT_SIZE: u64 = 256 * 1024 * 1024;
+
+/// The format of the exported file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Newline-delimited JSON, one block per line.
+    Json,
+    /// Molecule encoded blocks in checksummed segments.
+    Binary(Compression),
+}
+
+/// The compression of the binary format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// Not compressed.
+    None,
+    /// Compressed as a zstd stream.
+    Zstd,
+    /// Compressed as a snappy framed stream.
+    Snappy,
+}
+
+impl Compression {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Compression::None),
+            1 => Some(Compression::Zstd),
+            2 => Some(Compression::Snappy),
+            _ => None,
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            Compression::Zstd => 1,
+            Compression::Snappy => 2,
+        }
+    }
+
+    /// The extension appended to the file name.
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Zstd => ".zst",
+            Compression::Snappy => ".sz",
+        }
+    }
+
+    pub(crate) fn encoder<W: Write>(self, writer: W) -> io::Result<Encoder<W>> {
+        Ok(match self {
+            Compression::None => Encoder::Plain(writer),
+            Compression::Zstd => {
+                Encoder::Zstd(zstd::Encoder::new(writer, zstd::DEFAULT_COMPRESSION_LEVEL)?)
+            }
+            Compression::Snappy => Encoder::Snappy(snap::write::FrameEncoder::new(writer)),
+        })
+    }
+
+    pub(crate) fn decoder<'a, R: Read + 'a>(self, reader: R) -> io::Result<Box<dyn Read + 'a>> {
+        Ok(match self {
+            Compression::None => Box::new(reader),
+            Compression::Zstd => Box::new(zstd::Decoder::new(reader)?),
+            Compression::Snappy => Box::new(snap::read::FrameDecoder::new(reader)),
+        })
+    }
+}
+
+impl FromStr for Compression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Compression::None),
+            "zstd" => Ok(Compression::Zstd),
+            "snappy" => Ok(Compression::Snappy),
+            _ => Err(format!("unknown compression {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Compression::None => write!(f, "none"),
+            Compression::Zstd => write!(f, "zstd"),
+            Compression::Snappy => write!(f, "snappy"),
+        }
+    }
+}
+
+/// The writer which compresses the stream.
+pub(crate) enum Encoder<W: Write> {
+    Plain(W),
+    Zstd(zstd::Encoder<'static, W>),
+    Snappy(snap::write::FrameEncoder<W>),
+}
+
+impl<W: Write> Encoder<W> {
+    /// Finishes the compressed stream and returns the inner writer.
+    pub(crate) fn finish(self) -> io::Result<W> {
+        match self {
+            Encoder::Plain(writer) => Ok(writer),
+            Encoder::Zstd(encoder) => encoder.finish(),
+            Encoder::Snappy(encoder) => encoder
+                .into_inner()
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string())),
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Encoder::Plain(writer) => writer.write(buf),
+            Encoder::Zstd(encoder) => encoder.write(buf),
+            Encoder::Snappy(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Encoder::Plain(writer) => writer.flush(),
+            Encoder::Zstd(encoder) => encoder.flush(),
+            Encoder::Snappy(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// The header of the binary files, which is never compressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Header {
+    pub(crate) compression: Compression,
+    /// The number of the first block.
+    pub(crate) from: BlockNumber,
+    /// The number of the last block.
+    pub(crate) to: BlockNumber,
+}
+
+impl Header {
+    pub(crate) fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION, self.compression.to_byte()])?;
+        writer.write_all(&self.from.to_le_bytes())?;
+        writer.write_all(&self.to.to_le_bytes())
+    }
+
+    pub(crate) fn read_from<R: Read>(reader: &mut R) -> Result<Self, Box<dyn Error>> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err("not a binary export file".into());
+        }
+        let mut flags = [0u8; 2];
+        reader.read_exact(&mut flags)?;
+        if flags[0] != VERSION {
+            return Err(format!("unsupported binary format version {}", flags[0]).into());
+        }
+        let compression = Compression::from_byte(flags[1])
+            .ok_or_else(|| format!("unknown compression {}", flags[1]))?;
+        Ok(Header {
+            compression,
+            from: read_u64(reader)?,
+            to: read_u64(reader)?,
+        })
+    }
+}
+
+/// Writes the blocks into segments.
+pub(crate) struct SegmentWriter<W: Write> {
+    writer: W,
+    payload: Vec<u8>,
+    count: u32,
+}
+
+impl<W: Write> SegmentWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        SegmentWriter {
+            writer,
+            payload: Vec::new(),
+            count: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, block: &BlockView) -> io::Result<()> {
+        write_block(&mut self.payload, block);
+        self.count += 1;
+        if self.count >= SEGMENT_BLOCKS || self.payload.len() >= SEGMENT_SIZE {
+            self.flush_segment()?;
+        }
+        Ok(())
+    }
+
+    fn flush_segment(&mut self) -> io::Result<()> {
+        if self.count == 0 {
+            return Ok(());
+        }
+        self.writer.write_all(&self.count.to_le_bytes())?;
+        self.writer
+            .write_all(&(self.payload.len() as u64).to_le_bytes())?;
+        self.writer.write_all(&self.payload)?;
+        self.writer.write_all(&blake2b_256(&self.payload))?;
+        self.payload.clear();
+        self.count = 0;
+        Ok(())
+    }
+
+    /// Writes the last segment and the end mark, returns the inner writer.
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        self.flush_segment()?;
+        self.writer.write_all(&0u32.to_le_bytes())?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads the blocks from segments, and verifies the checksum of each segment.
+pub(crate) struct SegmentReader<R: Read> {
+    reader: R,
+    index: usize,
+}
+
+impl<R: Read> SegmentReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        SegmentReader { reader, index: 0 }
+    }
+
+    /// Returns the blocks of the next segment, or `None` at the end of the file.
+    pub(crate) fn next_segment(&mut self) -> Result<Option<Vec<BlockView>>, Box<dyn Error>> {
+        let index = self.index;
+        let truncated = |err: io::Error| -> Box<dyn Error> {
+            if err.kind() == io::ErrorKind::UnexpectedEof {
+                format!("the file is truncated in segment {}", index).into()
+            } else {
+                err.into()
+            }
+        };
+        let count = read_u32(&mut self.reader).map_err(truncated)?;
+        if count == 0 {
+            return Ok(None);
+        }
+        if count > SEGMENT_BLOCKS {
+            return Err(format!("segment {} has too many blocks: {}", index, count).into());
+        }
+        let size = read_u64(&mut self.reader).map_err(truncated)?;
+        if size > MAX_SEGMENT_SIZE {
+            return Err(format!("segment {} is too large: {} bytes", index, size).into());
+        }
+        let mut payload = vec![0u8; size as usize];
+        self.reader.read_exact(&mut payload).map_err(truncated)?;
+        let mut checksum = [0u8; 32];
+        self.reader.read_exact(&mut checksum).map_err(truncated)?;
+        if blake2b_256(&payload) != checksum {
+            return Err(format!("the checksum of segment {} mismatches", index).into());
+        }
+
+        let mut blocks = Vec::with_capacity(count as usize);
+        let mut rest = &payload[..];
+        for _ in 0..count {
+            let block = read_block(&mut rest)
+                .map_err(|err| format!("malformed block in segment {}: {}", index, err))?;
+            blocks.push(block);
+        }
+        if !rest.is_empty() {
+            return Err(format!("unexpected trailing data in segment {}", index).into());
+        }
+        self.index += 1;
+        Ok(Some(blocks))
+    }
+}
+
+/// Appends the molecule encoded block prefixed by its size.
+pub(crate) fn write_block(payload: &mut Vec<u8>, block: &BlockView) {
+    write_bytes(payload, block.data().as_slice());
+}
+
+/// Appends the bytes prefixed by its size.
+pub(crate) fn write_bytes(payload: &mut Vec<u8>, bytes: &[u8]) {
+    payload.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    payload.extend_from_slice(bytes);
+}
+
+pub(crate) fn read_block(payload: &mut &[u8]) -> Result<BlockView, Box<dyn Error>> {
+    let encoded = read_bytes(payload)?;
+    let block = packed::Block::from_slice(encoded)
+        .map_err(|err| err.to_string())?
+        .into_view();
+    Ok(block)
+}
+
+/// Reads the bytes prefixed by its size.
+pub(crate) fn read_bytes<'a>(payload: &mut &'a [u8]) -> Result<&'a [u8], Box<dyn Error>> {
+    let size = read_u32(payload)? as usize;
+    let data: &'a [u8] = *payload;
+    if data.len() < size {
+        return Err("the data exceeds the payload".into());
+    }
+    let (bytes, rest) = data.split_at(size);
+    *payload = rest;
+    Ok(bytes)
+}
+
+pub(crate) fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+pub(crate) fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
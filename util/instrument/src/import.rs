@@ -1,75 +1,112 @@
+use crate::format::{Header, SegmentReader, MAGIC};
+use crate::progress::Progress;
 use ckb_chain::chain::ChainController;
 use ckb_jsonrpc_types::BlockView as JsonBlock;
+use ckb_shared::shared::Shared;
+use ckb_store::ChainStore;
 use ckb_types::core;
-#[cfg(feature = "progress_bar")]
-use indicatif::{ProgressBar, ProgressStyle};
 use std::error::Error;
 use std::fs;
 use std::io;
-use std::io::BufRead;
+use std::io::{BufRead, Read};
 use std::path::PathBuf;
 use std::sync::Arc;
 
 /// Export block date from file to database.
+///
+/// The blocks which are already in the database are skipped, so an interrupted import can be
+/// resumed by importing the same file again.
 pub struct Import {
     /// source file contains block data
     source: PathBuf,
     chain: ChainController,
+    shared: Shared,
+    imported: u64,
+    skipped: u64,
 }
 
 impl Import {
     /// Creates a new import job.
-    pub fn new(chain: ChainController, source: PathBuf) -> Self {
-        Import { chain, source }
+    pub fn new(chain: ChainController, shared: Shared, source: PathBuf) -> Self {
+        Import {
+            chain,
+            shared,
+            source,
+            imported: 0,
+            skipped: 0,
+        }
     }
 
     /// Executes the import job.
-    pub fn execute(self) -> Result<(), Box<dyn Error>> {
-        self.read_from_json()
-    }
-
-    #[cfg(not(feature = "progress_bar"))]
-    pub fn read_from_json(&self) -> Result<(), Box<dyn Error>> {
+    ///
+    /// The format of the file is detected by its content.
+    pub fn execute(mut self) -> Result<(), Box<dyn Error>> {
         let f = fs::File::open(&self.source)?;
-        let reader = io::BufReader::new(f);
+        let len = f.metadata()?.len();
+        let mut reader = io::BufReader::new(f);
+        if reader.fill_buf()?.starts_with(MAGIC) {
+            self.read_from_binary(reader)
+        } else {
+            self.read_from_json(reader, len)
+        }
+    }
 
-        for line in reader.lines() {
+    fn read_from_json<R: BufRead>(&mut self, reader: R, len: u64) -> Result<(), Box<dyn Error>> {
+        let progress = Progress::bytes(len);
+        for (index, line) in reader.lines().enumerate() {
             let s = line?;
-            let block: JsonBlock = serde_json::from_str(&s)?;
-            let block: Arc<core::BlockView> = Arc::new(block.into());
-            if !block.is_genesis() {
-                self.chain
-                    .process_block(block)
-                    .expect("import occur malformation data");
-            }
+            let block: JsonBlock = serde_json::from_str(&s)
+                .map_err(|err| format!("malformed block at line {}: {}", index + 1, err))?;
+            self.import_block(block.into())?;
+            progress.inc(s.as_bytes().len() as u64 + 1);
         }
+        progress.finish(self.summary());
         Ok(())
     }
 
-    /// Imports the chain from the JSON file.
-    #[cfg(feature = "progress_bar")]
-    pub fn read_from_json(&self) -> Result<(), Box<dyn Error>> {
-        let metadata = fs::metadata(&self.source)?;
-        let f = fs::File::open(&self.source)?;
-        let reader = io::BufReader::new(f);
-        let progress_bar = ProgressBar::new(metadata.len() as u64);
-        progress_bar.set_style(
-            ProgressStyle::default_bar()
-                .template("[{elapsed_precise}] {bar:50.cyan/blue} {bytes:>6}/{total_bytes:6} {msg}")
-                .progress_chars("##-"),
-        );
-        for line in reader.lines() {
-            let s = line?;
-            let block: JsonBlock = serde_json::from_str(&s)?;
-            let block: Arc<core::BlockView> = Arc::new(block.into());
-            if !block.is_genesis() {
-                self.chain
-                    .process_block(block)
-                    .expect("import occur malformation data");
+    fn read_from_binary<R: Read>(&mut self, mut reader: R) -> Result<(), Box<dyn Error>> {
+        let header = Header::read_from(&mut reader)?;
+        let mut segments = SegmentReader::new(header.compression.decoder(reader)?);
+        let progress = Progress::blocks(header.to.saturating_sub(header.from) + 1);
+        while let Some(blocks) = segments.next_segment()? {
+            for block in blocks {
+                self.import_block(block)?;
+                progress.inc(1);
             }
-            progress_bar.inc(s.as_bytes().len() as u64);
         }
-        progress_bar.finish_with_message("done!");
+        progress.finish(self.summary());
         Ok(())
     }
+
+    fn import_block(&mut self, block: core::BlockView) -> Result<(), Box<dyn Error>> {
+        if self
+            .shared
+            .store()
+            .get_block_header(&block.hash())
+            .is_some()
+        {
+            self.skipped += 1;
+            return Ok(());
+        }
+        if block.is_genesis() {
+            return Err(format!(
+                "the genesis block {:#x} does not match the chain spec",
+                block.hash()
+            )
+            .into());
+        }
+        let (number, hash) = (block.number(), block.hash());
+        self.chain
+            .process_block(Arc::new(block))
+            .map_err(|err| format!("failed to import block {} {:#x}: {}", number, hash, err))?;
+        self.imported += 1;
+        Ok(())
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "done! {} blocks imported, {} known blocks skipped",
+            self.imported, self.skipped
+        )
+    }
 }
@@ -4,11 +4,23 @@
 //!
 //! - [`Export`] provides block data export function.
 //! - [`Import`] imports block data which export from `Export`.
+//! - [`SnapshotExport`] exports the live cell set at the tip, and [`SnapshotImport`] bootstraps a
+//!   fresh node from it.
+//!
+//! The blocks are exported either as newline-delimited JSON, or in a molecule encoded binary
+//! format with optional compression, see [`Format`].
 
 mod export;
+mod format;
 mod import;
+mod progress;
+mod snapshot;
+#[cfg(test)]
+mod tests;
 
 pub use crate::export::Export;
+pub use crate::format::{Compression, Format};
 pub use crate::import::Import;
+pub use crate::snapshot::{SnapshotExport, SnapshotImport, RECENT_BLOCKS};
 #[cfg(feature = "progress_bar")]
 pub use indicatif::{ProgressBar, ProgressStyle};
@@ -0,0 +1,50 @@
+//! Reports the progress of the export and import jobs, which is silent without the
+//! `progress_bar` feature.
+#[cfg(feature = "progress_bar")]
+use indicatif::{ProgressBar, ProgressStyle};
+
+pub(crate) struct Progress {
+    #[cfg(feature = "progress_bar")]
+    bar: ProgressBar,
+}
+
+#[cfg_attr(not(feature = "progress_bar"), allow(unused_variables))]
+impl Progress {
+    /// Counts the progress in blocks.
+    pub(crate) fn blocks(len: u64) -> Self {
+        Self::new(len, "{pos:>6}/{len:6}")
+    }
+
+    /// Counts the progress in bytes.
+    pub(crate) fn bytes(len: u64) -> Self {
+        Self::new(len, "{bytes:>6}/{total_bytes:6}")
+    }
+
+    fn new(len: u64, counter: &str) -> Self {
+        #[cfg(feature = "progress_bar")]
+        {
+            let bar = ProgressBar::new(len);
+            bar.set_style(
+                ProgressStyle::default_bar()
+                    .template(&format!(
+                        "[{{elapsed_precise}}] {{bar:50.cyan/blue}} {} {{msg}}",
+                        counter
+                    ))
+                    .progress_chars("##-"),
+            );
+            Progress { bar }
+        }
+        #[cfg(not(feature = "progress_bar"))]
+        Progress {}
+    }
+
+    pub(crate) fn inc(&self, delta: u64) {
+        #[cfg(feature = "progress_bar")]
+        self.bar.inc(delta);
+    }
+
+    pub(crate) fn finish(&self, message: String) {
+        #[cfg(feature = "progress_bar")]
+        self.bar.finish_with_message(message);
+    }
+}
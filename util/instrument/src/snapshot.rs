@@ -0,0 +1,597 @@
+//! The snapshot of the live cell set, which bootstraps a fresh node at the snapshot block without
+//! replaying the whole history.
+//!
+//! A snapshot contains:
+//!
+//! - the headers, the block exts and the epoch indices of all the main chain blocks, and all the
+//!   epoch exts;
+//! - the chain root MMR and the uncles index;
+//! - the live cell set, which is `COLUMN_CELL`, `COLUMN_CELL_DATA` and `COLUMN_CELL_DATA_HASH`;
+//! - the whole blocks of the recent [`RECENT_BLOCKS`] blocks, which are required to verify the
+//!   following blocks.
+//!
+//! The file starts with a header, followed by the chunks. Each chunk is laid out as:
+//!
+//! - the kind of the chunk, `u8`, where 0 marks the end of the file;
+//! - the number of the items, `u32` in little endian;
+//! - the size of the payload, `u64` in little endian;
+//! - the payload;
+//! - the blake2b hash of the payload.
+//!
+//! The snapshot hash is the blake2b hash of the header and the checksums of all the chunks. It is
+//! printed by the export, and must be pinned to import the snapshot.
+//!
+//! The snapshot is taken at the tip of the exporting node, or at a given main chain block, in
+//! which case the blocks above it are rolled back in a transaction which is never committed.
+//!
+//! The imported node trusts the pinned hash to start syncing from the snapshot block at once. It
+//! records the hash of the imported live cell set, and then downloads and fully verifies the
+//! history below the snapshot block in the background, in a separate database. The snapshot is
+//! marked as invalid if the history fails the verification or ends up with a different live cell
+//! set, and the node refuses to start until the data is reset.
+use crate::format::{read_block, read_bytes, read_u32, read_u64, write_block, write_bytes};
+use crate::progress::Progress;
+use ckb_chain_iter::ChainIterator;
+use ckb_db::iter::{Direction, IteratorMode};
+use ckb_db_schema::{
+    Col, COLUMN_BLOCK_EPOCH, COLUMN_BLOCK_EXT, COLUMN_BLOCK_HEADER, COLUMN_CELL, COLUMN_CELL_DATA,
+    COLUMN_CELL_DATA_HASH, COLUMN_CHAIN_ROOT_MMR, COLUMN_EPOCH, COLUMN_INDEX, COLUMN_META,
+    COLUMN_UNCLES, META_CURRENT_EPOCH_KEY, META_PRUNED_NUMBER_KEY,
+};
+use ckb_hash::{blake2b_256, new_blake2b, Blake2b};
+use ckb_shared::shared::Shared;
+use ckb_store::{detach_block_cell, ChainStore, StoreTransaction};
+use ckb_types::{
+    core::{BlockNumber, BlockView},
+    packed,
+    prelude::*,
+    utilities::leaf_index_to_mmr_size,
+    H256,
+};
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+const MAGIC: &[u8; 8] = b"CKBSNAPS";
+const VERSION: u8 = 1;
+/// The number of the recent blocks whose bodies are kept in the snapshot.
+pub const RECENT_BLOCKS: BlockNumber = 1000;
+/// A chunk is closed once its payload exceeds the size.
+const CHUNK_SIZE: usize = 16 * 1024 * 1024;
+/// Guards the allocation when reading a corrupted file.
+const MAX_CHUNK_SIZE: u64 = 256 * 1024 * 1024;
+
+const CHUNK_END: u8 = 0;
+const CHUNK_ENTRIES: u8 = 1;
+const CHUNK_BLOCKS: u8 = 2;
+
+/// The columns in the snapshot, a column is encoded as its index in the list.
+const COLUMNS: &[Col] = &[
+    COLUMN_INDEX,
+    COLUMN_BLOCK_HEADER,
+    COLUMN_BLOCK_EXT,
+    COLUMN_BLOCK_EPOCH,
+    COLUMN_EPOCH,
+    COLUMN_CHAIN_ROOT_MMR,
+    COLUMN_UNCLES,
+    COLUMN_CELL,
+    COLUMN_CELL_DATA,
+    COLUMN_CELL_DATA_HASH,
+    COLUMN_META,
+];
+
+/// The columns copied as a whole.
+const WHOLE_COLUMNS: &[Col] = &[
+    COLUMN_EPOCH,
+    COLUMN_CHAIN_ROOT_MMR,
+    COLUMN_UNCLES,
+    COLUMN_CELL,
+    COLUMN_CELL_DATA,
+    COLUMN_CELL_DATA_HASH,
+];
+
+struct Header {
+    genesis_hash: packed::Byte32,
+    tip_number: BlockNumber,
+    tip_hash: packed::Byte32,
+}
+
+impl Header {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(81);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(self.genesis_hash.as_slice());
+        bytes.extend_from_slice(&self.tip_number.to_le_bytes());
+        bytes.extend_from_slice(self.tip_hash.as_slice());
+        bytes
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> Result<(Self, Vec<u8>), Box<dyn Error>> {
+        let mut bytes = [0u8; 81];
+        reader.read_exact(&mut bytes)?;
+        if &bytes[..8] != MAGIC {
+            return Err("not a snapshot file".into());
+        }
+        if bytes[8] != VERSION {
+            return Err(format!("unsupported snapshot version {}", bytes[8]).into());
+        }
+        let header = Header {
+            genesis_hash: packed::Byte32::from_slice(&bytes[9..41]).expect("checked length"),
+            tip_number: read_u64(&mut &bytes[41..49])?,
+            tip_hash: packed::Byte32::from_slice(&bytes[49..81]).expect("checked length"),
+        };
+        Ok((header, bytes.to_vec()))
+    }
+}
+
+/// The blake2b hash of the chunk payload.
+type Checksum = [u8; 32];
+/// The column, the key and the value of an entry.
+type RawEntry<'a> = (Col, &'a [u8], &'a [u8]);
+
+enum Chunk {
+    Entries(Vec<(Col, Vec<u8>, Vec<u8>)>),
+    Blocks(Vec<BlockView>),
+}
+
+struct ChunkWriter<W: Write> {
+    writer: W,
+    hasher: Blake2b,
+    kind: u8,
+    count: u32,
+    payload: Vec<u8>,
+}
+
+impl<W: Write> ChunkWriter<W> {
+    fn new(mut writer: W, header: &Header) -> io::Result<Self> {
+        let header = header.to_bytes();
+        writer.write_all(&header)?;
+        let mut hasher = new_blake2b();
+        hasher.update(&header);
+        Ok(ChunkWriter {
+            writer,
+            hasher,
+            kind: CHUNK_ENTRIES,
+            count: 0,
+            payload: Vec::new(),
+        })
+    }
+
+    fn push_entry(&mut self, col: Col, key: &[u8], value: &[u8]) -> io::Result<()> {
+        let index = COLUMNS
+            .iter()
+            .position(|c| *c == col)
+            .expect("the column in the snapshot");
+        self.start(CHUNK_ENTRIES)?;
+        self.payload.push(index as u8);
+        write_bytes(&mut self.payload, key);
+        write_bytes(&mut self.payload, value);
+        self.count += 1;
+        self.flush_if_full()
+    }
+
+    fn push_block(&mut self, block: &BlockView) -> io::Result<()> {
+        self.start(CHUNK_BLOCKS)?;
+        write_block(&mut self.payload, block);
+        self.count += 1;
+        self.flush_if_full()
+    }
+
+    fn start(&mut self, kind: u8) -> io::Result<()> {
+        if self.kind != kind {
+            self.flush()?;
+            self.kind = kind;
+        }
+        Ok(())
+    }
+
+    fn flush_if_full(&mut self) -> io::Result<()> {
+        if self.payload.len() >= CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.count == 0 {
+            return Ok(());
+        }
+        let checksum = blake2b_256(&self.payload);
+        self.writer.write_all(&[self.kind])?;
+        self.writer.write_all(&self.count.to_le_bytes())?;
+        self.writer
+            .write_all(&(self.payload.len() as u64).to_le_bytes())?;
+        self.writer.write_all(&self.payload)?;
+        self.writer.write_all(&checksum)?;
+        self.hasher.update(&checksum);
+        self.payload.clear();
+        self.count = 0;
+        Ok(())
+    }
+
+    /// Writes the last chunk and the end mark, returns the snapshot hash.
+    fn finish(mut self) -> io::Result<H256> {
+        self.flush()?;
+        self.writer.write_all(&[CHUNK_END])?;
+        self.writer.flush()?;
+        let mut hash = [0u8; 32];
+        self.hasher.finalize(&mut hash);
+        Ok(H256(hash))
+    }
+}
+
+struct ChunkReader<R: Read> {
+    reader: R,
+    hasher: Blake2b,
+    index: usize,
+    /// The number of the bytes read, for the progress.
+    consumed: u64,
+}
+
+impl<R: Read> ChunkReader<R> {
+    fn new(mut reader: R) -> Result<(Self, Header), Box<dyn Error>> {
+        let (header, bytes) = Header::read_from(&mut reader)?;
+        let mut hasher = new_blake2b();
+        hasher.update(&bytes);
+        Ok((
+            ChunkReader {
+                reader,
+                hasher,
+                index: 0,
+                consumed: bytes.len() as u64,
+            },
+            header,
+        ))
+    }
+
+    /// Returns the next chunk and its checksum, or `None` at the end of the file.
+    fn next_chunk(&mut self) -> Result<Option<(Chunk, Checksum)>, Box<dyn Error>> {
+        let index = self.index;
+        let truncated = |err: io::Error| -> Box<dyn Error> {
+            if err.kind() == io::ErrorKind::UnexpectedEof {
+                format!("the file is truncated in chunk {}", index).into()
+            } else {
+                err.into()
+            }
+        };
+        let mut kind = [0u8; 1];
+        self.reader.read_exact(&mut kind).map_err(truncated)?;
+        if kind[0] == CHUNK_END {
+            return Ok(None);
+        }
+        let count = read_u32(&mut self.reader).map_err(truncated)?;
+        let size = read_u64(&mut self.reader).map_err(truncated)?;
+        if size > MAX_CHUNK_SIZE {
+            return Err(format!("chunk {} is too large: {} bytes", index, size).into());
+        }
+        let mut payload = vec![0u8; size as usize];
+        self.reader.read_exact(&mut payload).map_err(truncated)?;
+        let mut checksum = [0u8; 32];
+        self.reader.read_exact(&mut checksum).map_err(truncated)?;
+        if blake2b_256(&payload) != checksum {
+            return Err(format!("the checksum of chunk {} mismatches", index).into());
+        }
+        self.hasher.update(&checksum);
+
+        let malformed = |err: Box<dyn Error>| -> Box<dyn Error> {
+            format!("malformed chunk {}: {}", index, err).into()
+        };
+        let mut rest = &payload[..];
+        let chunk = match kind[0] {
+            // Each entry or block takes at least 4 bytes, which bounds the allocation by the
+            // payload size even if the count is corrupted.
+            CHUNK_ENTRIES => {
+                let mut entries = Vec::with_capacity((count as usize).min(payload.len() / 4));
+                for _ in 0..count {
+                    let (col, key, value) = read_entry(&mut rest).map_err(malformed)?;
+                    entries.push((col, key.to_vec(), value.to_vec()));
+                }
+                Chunk::Entries(entries)
+            }
+            CHUNK_BLOCKS => {
+                let mut blocks = Vec::with_capacity((count as usize).min(payload.len() / 4));
+                for _ in 0..count {
+                    blocks.push(read_block(&mut rest).map_err(malformed)?);
+                }
+                Chunk::Blocks(blocks)
+            }
+            kind => return Err(format!("unknown kind {} of chunk {}", kind, index).into()),
+        };
+        if !rest.is_empty() {
+            return Err(format!("unexpected trailing data in chunk {}", index).into());
+        }
+        self.index += 1;
+        self.consumed += 1 + 4 + 8 + size + 32;
+        Ok(Some((chunk, checksum)))
+    }
+
+    /// Returns the snapshot hash, which is only valid after all the chunks are read.
+    fn finish(self) -> H256 {
+        let mut hash = [0u8; 32];
+        self.hasher.finalize(&mut hash);
+        H256(hash)
+    }
+}
+
+fn read_entry<'a>(payload: &mut &'a [u8]) -> Result<RawEntry<'a>, Box<dyn Error>> {
+    let (index, rest) = payload.split_first().ok_or("the entry is empty")?;
+    *payload = rest;
+    let col = *COLUMNS
+        .get(*index as usize)
+        .ok_or_else(|| format!("unknown column {}", index))?;
+    Ok((col, read_bytes(payload)?, read_bytes(payload)?))
+}
+
+/// Exports the snapshot of the live cell set at a main chain block.
+pub struct SnapshotExport {
+    /// export target path
+    pub target: PathBuf,
+    /// CKB shared data.
+    pub shared: Shared,
+    /// The number of the snapshot block, the tip when it is `None`.
+    pub number: Option<BlockNumber>,
+}
+
+impl SnapshotExport {
+    /// Creates the snapshot export job.
+    pub fn new(shared: Shared, target: PathBuf, number: Option<BlockNumber>) -> Self {
+        SnapshotExport {
+            shared,
+            target,
+            number,
+        }
+    }
+
+    /// Executes the snapshot export job, returns the snapshot hash.
+    ///
+    /// All the data are read through a transaction which is never committed, the blocks above
+    /// the snapshot block are rolled back in it.
+    pub fn execute(self) -> Result<H256, Box<dyn Error>> {
+        fs::create_dir_all(&self.target)?;
+        let txn = self.shared.store().begin_transaction();
+        let tip_number = txn
+            .get_tip_header()
+            .ok_or("the database is not initialized")?
+            .number();
+        let number = self.number.unwrap_or(tip_number);
+        if number > tip_number {
+            return Err(format!("block {} is above the tip {}", number, tip_number).into());
+        }
+        if number < tip_number {
+            rollback(&txn, tip_number, number)?;
+        }
+        let header = Header {
+            genesis_hash: self.shared.consensus().genesis_hash(),
+            tip_number: number,
+            tip_hash: txn
+                .get_block_hash(number)
+                .ok_or_else(|| format!("the main chain block {} is not found", number))?,
+        };
+        let file_name = format!(
+            "{}-{}.snapshot",
+            self.shared.consensus().id,
+            header.tip_number
+        );
+        let f = fs::OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&self.target.join(file_name))?;
+        let mut writer = ChunkWriter::new(io::BufWriter::new(f), &header)?;
+
+        let progress = Progress::blocks(header.tip_number + 1);
+        for number in 0..=header.tip_number {
+            let hash = txn
+                .get_block_hash(number)
+                .ok_or_else(|| format!("the main chain block {} is not found", number))?;
+            let number_key: packed::Uint64 = number.pack();
+            writer.push_entry(COLUMN_INDEX, number_key.as_slice(), hash.as_slice())?;
+            writer.push_entry(COLUMN_INDEX, hash.as_slice(), number_key.as_slice())?;
+            for col in [COLUMN_BLOCK_HEADER, COLUMN_BLOCK_EXT, COLUMN_BLOCK_EPOCH] {
+                if let Some(value) = txn.get(col, hash.as_slice()) {
+                    writer.push_entry(col, hash.as_slice(), value.as_ref())?;
+                } else if col == COLUMN_BLOCK_HEADER {
+                    return Err(format!("the header of block {} is not found", number).into());
+                }
+            }
+            progress.inc(1);
+        }
+        for &col in WHOLE_COLUMNS {
+            for (key, value) in txn.get_iter(col, IteratorMode::Start) {
+                writer.push_entry(col, &key, &value)?;
+            }
+        }
+        if let Some(value) = txn.get(COLUMN_META, META_CURRENT_EPOCH_KEY) {
+            writer.push_entry(COLUMN_META, META_CURRENT_EPOCH_KEY, value.as_ref())?;
+        }
+        let recent_start = (header.tip_number + 1).saturating_sub(RECENT_BLOCKS);
+        for block in ChainIterator::with_range(&txn, recent_start, Some(header.tip_number)) {
+            writer.push_block(&block)?;
+        }
+
+        let hash = writer.finish()?;
+        progress.finish("done!".to_string());
+        Ok(hash)
+    }
+}
+
+/// Rolls the main chain in the transaction back to the block, as the chain service does in a
+/// reorganization.
+fn rollback(
+    txn: &StoreTransaction,
+    tip_number: BlockNumber,
+    number: BlockNumber,
+) -> Result<(), Box<dyn Error>> {
+    // The cells spent above the block are restored from the transactions creating them, which may
+    // be anywhere in the history.
+    if txn.get_pruned_number() > 0 {
+        return Err(
+            "the block bodies are pruned, the snapshot can only be taken at the tip".into(),
+        );
+    }
+    let tip_epoch = txn
+        .get_current_epoch_ext()
+        .ok_or("the current epoch is not found")?;
+    for n in ((number + 1)..=tip_number).rev() {
+        let block = txn
+            .get_block_hash(n)
+            .and_then(|hash| txn.get_block(&hash))
+            .ok_or_else(|| format!("the main chain block {} is not found", n))?;
+        txn.detach_block(&block)?;
+        detach_block_cell(txn, &block)?;
+    }
+
+    let hash = txn.get_block_hash(number).expect("main chain block");
+    let tip = txn.get_block_header(&hash).expect("main chain header");
+    let epoch = txn
+        .get_block_epoch(&hash)
+        .ok_or_else(|| format!("the epoch of block {} is not found", number))?;
+    for epoch_number in (epoch.number() + 1)..=tip_epoch.number() {
+        let key: packed::Uint64 = epoch_number.pack();
+        txn.delete(COLUMN_EPOCH, key.as_slice())?;
+    }
+    let mmr_size: packed::BeUint64 = leaf_index_to_mmr_size(number).pack();
+    let positions: Vec<_> = txn
+        .get_iter(
+            COLUMN_CHAIN_ROOT_MMR,
+            IteratorMode::From(mmr_size.as_slice(), Direction::Forward),
+        )
+        .map(|(key, _)| key)
+        .collect();
+    for position in positions {
+        txn.delete(COLUMN_CHAIN_ROOT_MMR, &position)?;
+    }
+    txn.insert_current_epoch_ext(&epoch)?;
+    txn.insert_tip_header(&tip)?;
+    Ok(())
+}
+
+/// Imports a snapshot into a fresh database.
+///
+/// The whole file is verified against the pinned hash before anything is written, and each
+/// chunk is checked against the verified checksums again before it is written. The tip is
+/// updated at last, so an interrupted import can be retried.
+pub struct SnapshotImport {
+    /// source file of the snapshot
+    source: PathBuf,
+    shared: Shared,
+    /// The pinned snapshot hash.
+    hash: H256,
+}
+
+impl SnapshotImport {
+    /// Creates the snapshot import job.
+    pub fn new(shared: Shared, source: PathBuf, hash: H256) -> Self {
+        SnapshotImport {
+            shared,
+            source,
+            hash,
+        }
+    }
+
+    /// Executes the snapshot import job.
+    pub fn execute(self) -> Result<(), Box<dyn Error>> {
+        let store = self.shared.store();
+        let tip = store
+            .get_tip_header()
+            .ok_or("the database is not initialized")?;
+        if tip.number() != 0 {
+            return Err(
+                "the snapshot can only be imported into a fresh database, run `ckb reset-data` first"
+                    .into(),
+            );
+        }
+
+        // verifies the snapshot hash before writing anything
+        let mut checksums = Vec::new();
+        let header = self.read(|_, checksum| {
+            checksums.push(checksum);
+            Ok(())
+        })?;
+        if header.genesis_hash != self.shared.consensus().genesis_hash() {
+            return Err("the genesis block of the snapshot does not match the chain spec".into());
+        }
+
+        // The fresh database has the genesis cells, which may have been spent before the snapshot
+        // block, so the columns copied as a whole are cleared before any entry is written.
+        let mut batch = store.new_write_batch();
+        for &col in WHOLE_COLUMNS {
+            for (key, _) in store.get_iter(col, IteratorMode::Start) {
+                batch.delete(col, &key)?;
+            }
+        }
+        store.write(&batch)?;
+
+        let mut index = 0;
+        self.read(|chunk, checksum| {
+            if checksums.get(index) != Some(&checksum) {
+                return Err(format!("chunk {} has changed since it was verified", index).into());
+            }
+            index += 1;
+            match chunk {
+                Chunk::Entries(entries) => {
+                    let mut batch = store.new_write_batch();
+                    for (col, key, value) in entries {
+                        batch.put(col, &key, &value)?;
+                    }
+                    store.write(&batch)?;
+                }
+                Chunk::Blocks(blocks) => {
+                    let txn = store.begin_transaction();
+                    for block in blocks {
+                        txn.insert_block(&block)?;
+                        txn.attach_block(&block)?;
+                    }
+                    txn.commit()?;
+                }
+            }
+            Ok(())
+        })?;
+
+        let tip = store
+            .get_block_header(&header.tip_hash)
+            .ok_or("the tip header is not in the snapshot")?;
+        // The history below the snapshot block is validated in the background against the hash.
+        let cells_hash = store.calc_live_cells_hash();
+        // The history below the recent blocks only has the headers, reports it as pruned.
+        let pruned_number: packed::Uint64 =
+            (header.tip_number + 1).saturating_sub(RECENT_BLOCKS).pack();
+        let txn = store.begin_transaction();
+        txn.insert_raw(
+            COLUMN_META,
+            META_PRUNED_NUMBER_KEY,
+            pruned_number.as_slice(),
+        )?;
+        txn.insert_unvalidated_snapshot(&header.tip_hash, &cells_hash)?;
+        txn.insert_tip_header(&tip)?;
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Reads through the file and verifies the snapshot hash, returns the header.
+    fn read<F>(&self, mut f: F) -> Result<Header, Box<dyn Error>>
+    where
+        F: FnMut(Chunk, Checksum) -> Result<(), Box<dyn Error>>,
+    {
+        let file = fs::File::open(&self.source)?;
+        let progress = Progress::bytes(file.metadata()?.len());
+        let (mut reader, header) = ChunkReader::new(io::BufReader::new(file))?;
+        let mut consumed = reader.consumed;
+        while let Some((chunk, checksum)) = reader.next_chunk()? {
+            f(chunk, checksum)?;
+            progress.inc(reader.consumed - consumed);
+            consumed = reader.consumed;
+        }
+        let hash = reader.finish();
+        progress.finish("done!".to_string());
+        if hash != self.hash {
+            return Err(format!(
+                "the snapshot hash {:#x} does not match the pinned hash {:#x}",
+                hash, self.hash
+            )
+            .into());
+        }
+        Ok(header)
+    }
+}
@@ -0,0 +1,108 @@
+use crate::format::{Compression, Header, SegmentReader, SegmentWriter, SEGMENT_BLOCKS};
+use ckb_types::{
+    core::{BlockBuilder, BlockNumber, BlockView},
+    packed::Byte32,
+    prelude::*,
+};
+use std::error::Error;
+
+fn blocks(count: BlockNumber) -> Vec<BlockView> {
+    (0..count)
+        .map(|number| BlockBuilder::default().number(number.pack()).build())
+        .collect()
+}
+
+fn hashes(blocks: &[BlockView]) -> Vec<Byte32> {
+    blocks.iter().map(BlockView::hash).collect()
+}
+
+fn write(compression: Compression, blocks: &[BlockView]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    Header {
+        compression,
+        from: 0,
+        to: blocks.len() as BlockNumber - 1,
+    }
+    .write_to(&mut buf)
+    .unwrap();
+    let mut segments = SegmentWriter::new(compression.encoder(buf).unwrap());
+    for block in blocks {
+        segments.push(block).unwrap();
+    }
+    segments.finish().unwrap().finish().unwrap()
+}
+
+fn read(mut bytes: &[u8]) -> Result<Vec<Vec<BlockView>>, Box<dyn Error>> {
+    let header = Header::read_from(&mut bytes)?;
+    let mut segments = SegmentReader::new(header.compression.decoder(bytes)?);
+    let mut result = Vec::new();
+    while let Some(blocks) = segments.next_segment()? {
+        result.push(blocks);
+    }
+    Ok(result)
+}
+
+#[test]
+fn test_segment_roundtrip() {
+    let blocks = blocks(1500);
+    for compression in [Compression::None, Compression::Zstd, Compression::Snappy] {
+        let bytes = write(compression, &blocks);
+        let header = Header::read_from(&mut &bytes[..]).unwrap();
+        assert_eq!(
+            header,
+            Header {
+                compression,
+                from: 0,
+                to: 1499,
+            }
+        );
+
+        let segments = read(&bytes).unwrap();
+        // a segment has at most 1000 blocks
+        let sizes: Vec<usize> = segments.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![1000, 500], "{}", compression);
+        let read_blocks: Vec<BlockView> = segments.into_iter().flatten().collect();
+        assert_eq!(hashes(&read_blocks), hashes(&blocks), "{}", compression);
+    }
+}
+
+#[test]
+fn test_checksum_mismatch() {
+    let mut bytes = write(Compression::None, &blocks(10));
+    // skips the header, the number of the blocks and the size of the payload
+    let payload_start = 26 + 4 + 8;
+    bytes[payload_start + 8] ^= 0xff;
+
+    let err = read(&bytes).unwrap_err();
+    assert_eq!(err.to_string(), "the checksum of segment 0 mismatches");
+}
+
+#[test]
+fn test_too_many_blocks_in_segment() {
+    let mut bytes = write(Compression::None, &blocks(10));
+    // the number of the blocks follows the header
+    bytes[26..30].copy_from_slice(&(SEGMENT_BLOCKS + 1).to_le_bytes());
+
+    let err = read(&bytes).unwrap_err();
+    assert_eq!(err.to_string(), "segment 0 has too many blocks: 1001");
+}
+
+#[test]
+fn test_truncated_trailing_segment() {
+    let blocks = blocks(1500);
+    let bytes = write(Compression::None, &blocks);
+    // cuts the end mark, the checksum and a part of the payload of the last segment
+    let truncated = &bytes[..bytes.len() - 40];
+
+    let mut reader = truncated;
+    let header = Header::read_from(&mut reader).unwrap();
+    let mut segments = SegmentReader::new(header.compression.decoder(reader).unwrap());
+    let first = segments.next_segment().unwrap().unwrap();
+    assert_eq!(hashes(&first), hashes(&blocks[..1000]));
+    let err = segments.next_segment().unwrap_err();
+    assert_eq!(err.to_string(), "the file is truncated in segment 1");
+
+    // the missing end mark is reported as truncated as well
+    let err = read(&bytes[..bytes.len() - 4]).unwrap_err();
+    assert_eq!(err.to_string(), "the file is truncated in segment 2");
+}
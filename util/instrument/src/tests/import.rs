@@ -0,0 +1,94 @@
+use crate::tests::{build_chain, start_chain};
+use crate::{Compression, Export, Format, Import};
+use ckb_shared::Shared;
+use ckb_store::ChainStore;
+use ckb_test_chain_utils::always_success_consensus;
+use ckb_types::core::BlockNumber;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// Exports the whole chain and returns the exported file.
+fn export(shared: &Shared, dir: &Path, format: Format) -> PathBuf {
+    Export::new(shared.clone(), dir.to_path_buf(), format, 0, None)
+        .execute()
+        .unwrap();
+    let mut files = fs::read_dir(dir).unwrap();
+    let file = files.next().unwrap().unwrap().path();
+    assert!(files.next().is_none());
+    file
+}
+
+fn tip_number(shared: &Shared) -> BlockNumber {
+    shared.snapshot().tip_number()
+}
+
+#[test]
+fn test_resume_binary_import() {
+    let consensus = always_success_consensus();
+    let (source, _source_chain) = build_chain(consensus.clone(), 30);
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let file = export(&source, tmp_dir.path(), Format::Binary(Compression::Zstd));
+
+    // the import was interrupted after the block 10
+    let (shared, chain_controller) = start_chain(consensus);
+    for number in 1..=10 {
+        let hash = source.store().get_block_hash(number).unwrap();
+        let block = source.store().get_block(&hash).unwrap();
+        chain_controller.process_block(Arc::new(block)).unwrap();
+    }
+
+    // the truncated file fails without importing any block of the broken segment
+    let bytes = fs::read(&file).unwrap();
+    let truncated = tmp_dir.path().join("truncated");
+    fs::write(&truncated, &bytes[..bytes.len() / 2]).unwrap();
+    let ret = Import::new(chain_controller.clone(), shared.clone(), truncated).execute();
+    assert!(ret.is_err());
+    assert_eq!(tip_number(&shared), 10);
+
+    // importing the same file again skips the known blocks
+    Import::new(chain_controller, shared.clone(), file)
+        .execute()
+        .unwrap();
+    assert_eq!(tip_number(&shared), 30);
+    assert_eq!(shared.snapshot().tip_hash(), source.snapshot().tip_hash());
+}
+
+#[test]
+fn test_resume_json_import() {
+    let consensus = always_success_consensus();
+    let (source, _source_chain) = build_chain(consensus.clone(), 30);
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let file = export(&source, tmp_dir.path(), Format::Json);
+
+    // a partial file which only has the blocks up to 15
+    let content = fs::read_to_string(&file).unwrap();
+    let partial: String = content
+        .lines()
+        .take(16)
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let partial_file = tmp_dir.path().join("partial");
+    fs::write(&partial_file, partial).unwrap();
+
+    let (shared, chain_controller) = start_chain(consensus);
+    Import::new(chain_controller.clone(), shared.clone(), partial_file)
+        .execute()
+        .unwrap();
+    assert_eq!(tip_number(&shared), 15);
+
+    // a line cut in the middle is reported with its line number
+    let broken_file = tmp_dir.path().join("broken");
+    let cut = content.lines().nth(16).unwrap();
+    fs::write(&broken_file, &cut[..cut.len() / 2]).unwrap();
+    let err = Import::new(chain_controller.clone(), shared.clone(), broken_file)
+        .execute()
+        .unwrap_err();
+    assert!(err.to_string().starts_with("malformed block at line 1:"));
+
+    Import::new(chain_controller, shared.clone(), file)
+        .execute()
+        .unwrap();
+    assert_eq!(tip_number(&shared), 30);
+    assert_eq!(shared.snapshot().tip_hash(), source.snapshot().tip_hash());
+}
@@ -0,0 +1,92 @@
+use ckb_chain::chain::{ChainController, ChainService};
+use ckb_chain_spec::consensus::Consensus;
+use ckb_dao::DaoCalculator;
+use ckb_launcher::SharedBuilder;
+use ckb_reward_calculator::RewardCalculator;
+use ckb_shared::{Shared, Snapshot};
+use ckb_store::ChainStore;
+use ckb_test_chain_utils::always_success_cellbase;
+use ckb_types::{
+    core::{cell::resolve_transaction, BlockBuilder, BlockView, HeaderView, TransactionView},
+    packed::ProposalShortId,
+    prelude::*,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+mod format;
+mod import;
+mod snapshot;
+
+// Starts a chain service on a fresh database.
+//
+// The genesis block of `always_success_consensus` has the current timestamp, so the chains which
+// share the blocks must be started with the same consensus.
+fn start_chain(consensus: Consensus) -> (Shared, ChainController) {
+    let (shared, mut pack) = SharedBuilder::with_temp_db()
+        .consensus(consensus)
+        .build()
+        .unwrap();
+    let chain_controller =
+        ChainService::new(shared.clone(), pack.take_proposal_table()).start::<&str>(None);
+    (shared, chain_controller)
+}
+
+// Construct the next block based the given `parent`
+fn next_block(shared: &Shared, parent: &HeaderView) -> BlockView {
+    next_block_with(shared, parent, vec![], vec![])
+}
+
+// Construct the next block which commits the `transactions` and proposes the `proposals`
+fn next_block_with(
+    shared: &Shared,
+    parent: &HeaderView,
+    transactions: Vec<TransactionView>,
+    proposals: Vec<ProposalShortId>,
+) -> BlockView {
+    let snapshot: &Snapshot = &shared.snapshot();
+    let epoch = shared
+        .consensus()
+        .next_epoch_ext(parent, &snapshot.as_data_provider())
+        .unwrap()
+        .epoch();
+    let (_, reward) = RewardCalculator::new(snapshot.consensus(), snapshot)
+        .block_reward_to_finalize(parent)
+        .unwrap();
+    let cellbase = always_success_cellbase(parent.number() + 1, reward.total, shared.consensus());
+
+    let dao = {
+        let mut seen_inputs = HashSet::new();
+        let resolved_txs: Vec<_> = std::iter::once(cellbase.clone())
+            .chain(transactions.iter().cloned())
+            .map(|tx| resolve_transaction(tx, &mut seen_inputs, snapshot, snapshot).unwrap())
+            .collect();
+        let data_loader = shared.store().as_data_provider();
+        DaoCalculator::new(shared.consensus(), &data_loader)
+            .dao_field(&resolved_txs, parent)
+            .unwrap()
+    };
+    BlockBuilder::default()
+        .transaction(cellbase)
+        .transactions(transactions)
+        .proposals(proposals)
+        .parent_hash(parent.hash())
+        .number((parent.number() + 1).pack())
+        .epoch(epoch.number_with_fraction(parent.number() + 1).pack())
+        .timestamp((parent.timestamp() + 1).pack())
+        .compact_target(epoch.compact_target().pack())
+        .dao(dao)
+        .build()
+}
+
+// Builds a chain with the blocks on top of the genesis block.
+fn build_chain(consensus: Consensus, blocks: u64) -> (Shared, ChainController) {
+    let (shared, chain_controller) = start_chain(consensus);
+    for _ in 0..blocks {
+        let block = next_block(&shared, &shared.snapshot().tip_header().clone());
+        chain_controller
+            .process_block(Arc::new(block))
+            .expect("processing new block should be ok");
+    }
+    (shared, chain_controller)
+}
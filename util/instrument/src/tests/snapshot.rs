@@ -0,0 +1,210 @@
+use super::{next_block, next_block_with, start_chain};
+use crate::snapshot::{SnapshotExport, SnapshotImport};
+use ckb_chain_spec::consensus::{Consensus, ConsensusBuilder};
+use ckb_dao_utils::genesis_dao_data;
+use ckb_db::iter::IteratorMode;
+use ckb_db_schema::{Col, COLUMN_CELL, COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH};
+use ckb_shared::Shared;
+use ckb_store::ChainStore;
+use ckb_test_chain_utils::{always_success_cell, always_success_consensus};
+use ckb_types::{
+    bytes::Bytes,
+    core::{
+        BlockBuilder, Capacity, DepType, EpochNumberWithFraction, TransactionBuilder,
+        TransactionView,
+    },
+    h256,
+    packed::{CellDep, CellInput, CellOutput, OutPoint},
+    prelude::*,
+};
+use std::sync::Arc;
+
+const CELL_COLUMNS: [Col; 3] = [COLUMN_CELL, COLUMN_CELL_DATA, COLUMN_CELL_DATA_HASH];
+
+// The genesis block has an extra always success cell besides the script binary, which can be
+// spent by the blocks.
+fn consensus_with_spendable_genesis_cell() -> Consensus {
+    let (always_success_cell, always_success_cell_data, always_success_script) =
+        always_success_cell();
+    let spendable = CellOutput::new_builder()
+        .capacity(Capacity::bytes(1000).unwrap().pack())
+        .lock(always_success_script.clone())
+        .build();
+    let genesis_tx = TransactionBuilder::default()
+        .input(CellInput::new(OutPoint::null(), 0))
+        .output(always_success_cell.clone())
+        .output_data(always_success_cell_data.pack())
+        .output(spendable)
+        .output_data(Bytes::new().pack())
+        .witness(always_success_script.clone().into_witness())
+        .build();
+    let base = always_success_consensus();
+    let genesis = BlockBuilder::default()
+        .timestamp(base.genesis_block().timestamp().pack())
+        .compact_target(base.genesis_block().compact_target().pack())
+        .dao(genesis_dao_data(vec![&genesis_tx]).unwrap())
+        .transaction(genesis_tx)
+        .build();
+    ConsensusBuilder::default()
+        .genesis_block(genesis)
+        .cellbase_maturity(EpochNumberWithFraction::new(0, 0, 1))
+        .build()
+}
+
+fn spend_genesis_cell(consensus: &Consensus) -> (TransactionView, OutPoint) {
+    let genesis_tx_hash = consensus.genesis_block().transactions()[0].hash();
+    let spent = OutPoint::new(genesis_tx_hash, 1);
+    (spend_cell(consensus, &spent), spent)
+}
+
+fn spend_cell(consensus: &Consensus, spent: &OutPoint) -> TransactionView {
+    let (_, _, always_success_script) = always_success_cell();
+    let genesis_tx_hash = consensus.genesis_block().transactions()[0].hash();
+    TransactionBuilder::default()
+        .cell_dep(
+            CellDep::new_builder()
+                .out_point(OutPoint::new(genesis_tx_hash, 0))
+                .dep_type(DepType::Code.into())
+                .build(),
+        )
+        .input(CellInput::new(spent.clone(), 0))
+        .output(
+            CellOutput::new_builder()
+                .capacity(Capacity::bytes(1000).unwrap().pack())
+                .lock(always_success_script.clone())
+                .build(),
+        )
+        .output_data(Bytes::new().pack())
+        .witness(always_success_script.clone().into_witness())
+        .build()
+}
+
+fn cell_columns(shared: &Shared) -> Vec<Vec<(Vec<u8>, Vec<u8>)>> {
+    CELL_COLUMNS
+        .iter()
+        .map(|&col| {
+            shared
+                .store()
+                .get_iter(col, IteratorMode::Start)
+                .map(|(key, value)| (key.to_vec(), value.to_vec()))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_snapshot_roundtrip() {
+    let consensus = consensus_with_spendable_genesis_cell();
+    let (tx, spent) = spend_genesis_cell(&consensus);
+    let (source, chain_controller) = start_chain(consensus.clone());
+
+    // proposes the transaction in block 2 and commits it in block 4
+    for number in 1..=10 {
+        let parent = source.snapshot().tip_header().clone();
+        let block = match number {
+            2 => next_block_with(&source, &parent, vec![], vec![tx.proposal_short_id()]),
+            4 => next_block_with(&source, &parent, vec![tx.clone()], vec![]),
+            _ => next_block(&source, &parent),
+        };
+        chain_controller
+            .process_block(Arc::new(block))
+            .expect("processing new block should be ok");
+    }
+    assert!(source.store().get_cell(&spent).is_none());
+
+    let dir = tempfile::tempdir().unwrap();
+    let hash = SnapshotExport::new(source.clone(), dir.path().to_path_buf(), None)
+        .execute()
+        .unwrap();
+    let file = dir.path().join(format!("{}-{}.snapshot", consensus.id, 10));
+
+    let (target, _chain_controller) = start_chain(consensus);
+    assert!(target.store().get_cell(&spent).is_some());
+
+    // a wrong hash is rejected before anything is written
+    let wrong_hash = h256!("0x1");
+    assert!(
+        SnapshotImport::new(target.clone(), file.clone(), wrong_hash)
+            .execute()
+            .is_err()
+    );
+    assert!(target.store().get_cell(&spent).is_some());
+    assert_eq!(target.store().get_tip_header().unwrap().number(), 0);
+
+    SnapshotImport::new(target.clone(), file, hash)
+        .execute()
+        .unwrap();
+    assert_eq!(
+        target.store().get_tip_header().unwrap().hash(),
+        source.store().get_tip_header().unwrap().hash()
+    );
+    assert!(target.store().get_cell(&spent).is_none());
+    assert_eq!(cell_columns(&target), cell_columns(&source));
+}
+
+#[test]
+fn test_snapshot_export_at_block() {
+    let consensus = consensus_with_spendable_genesis_cell();
+    let (tx, _) = spend_genesis_cell(&consensus);
+    let created = OutPoint::new(tx.hash(), 0);
+    let spending_tx = spend_cell(&consensus, &created);
+    let (source, chain_controller) = start_chain(consensus.clone());
+
+    // the cell created in block 4 is spent in block 14, above the snapshot block 10
+    let mut expected = None;
+    for number in 1..=20 {
+        let parent = source.snapshot().tip_header().clone();
+        let block = match number {
+            2 => next_block_with(&source, &parent, vec![], vec![tx.proposal_short_id()]),
+            4 => next_block_with(&source, &parent, vec![tx.clone()], vec![]),
+            12 => next_block_with(
+                &source,
+                &parent,
+                vec![],
+                vec![spending_tx.proposal_short_id()],
+            ),
+            14 => next_block_with(&source, &parent, vec![spending_tx.clone()], vec![]),
+            _ => next_block(&source, &parent),
+        };
+        chain_controller
+            .process_block(Arc::new(block))
+            .expect("processing new block should be ok");
+        if number == 10 {
+            expected = Some((
+                source.store().get_tip_header().unwrap().hash(),
+                source.store().calc_live_cells_hash(),
+                cell_columns(&source),
+            ));
+        }
+    }
+    let (tip_hash, cells_hash, columns) = expected.unwrap();
+    assert!(source.store().get_cell(&created).is_none());
+
+    let dir = tempfile::tempdir().unwrap();
+    let hash = SnapshotExport::new(source.clone(), dir.path().to_path_buf(), Some(10))
+        .execute()
+        .unwrap();
+    let file = dir.path().join(format!("{}-{}.snapshot", consensus.id, 10));
+    // the rollback is never committed
+    assert_eq!(source.store().get_tip_header().unwrap().number(), 20);
+    assert!(source.store().get_cell(&created).is_none());
+
+    let (target, _chain_controller) = start_chain(consensus);
+    SnapshotImport::new(target.clone(), file, hash)
+        .execute()
+        .unwrap();
+    assert_eq!(target.store().get_tip_header().unwrap().hash(), tip_hash);
+    assert!(target.store().get_cell(&created).is_some());
+    assert_eq!(cell_columns(&target), columns);
+    assert_eq!(
+        target.store().get_unvalidated_snapshot(),
+        Some((tip_hash, cells_hash))
+    );
+    assert_eq!(
+        target.store().get_current_epoch_ext(),
+        source
+            .store()
+            .get_block_hash(10)
+            .and_then(|hash| source.store().get_block_epoch(&hash))
+    );
+}
@@ -1,6 +1,6 @@
 use crate::{
     BlockNumber, Byte32, Cycle, EpochNumberWithFraction, Header, JsonBytes, ProposalShortId,
-    Timestamp, Transaction, Uint32, Uint64, Version,
+    Script, Timestamp, Transaction, Uint32, Uint64, Version,
 };
 use ckb_types::{packed, prelude::*, H256};
 use serde::{Deserialize, Serialize};
@@ -256,3 +256,16 @@ impl From<TransactionTemplate> for packed::Transaction {
         data.into()
     }
 }
+
+/// A lock which receives a share of the block rewards.
+///
+/// The cellbase pays the whole block reward to a single lock, so the payout targets take turns
+/// in the blocks found by the node. In every `sum(weight)` found blocks, each target receives the
+/// rewards of `weight` blocks. Fixed amounts and a lock for the remainder are not supported.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct PayoutTarget {
+    /// The lock script which receives the rewards.
+    pub lock: Script,
+    /// The number of found blocks paid to the lock in every `sum(weight)` found blocks.
+    pub weight: Uint64,
+}
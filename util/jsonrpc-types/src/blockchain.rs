@@ -1235,6 +1235,20 @@ pub struct MerkleProof {
     pub lemmas: Vec<H256>,
 }
 
+/// The compact block filter of a block.
+///
+/// The filter is a Golomb-coded set over the lock script hashes and the type script hashes of
+/// the input cells and the output cells in the block.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct BlockFilter {
+    /// The filter data.
+    pub data: JsonBytes,
+    /// The filter hash, which is `blake2b(parent_block_filter_hash | blake2b(data))`.
+    ///
+    /// The parent block filter hash of the genesis block is all zeros.
+    pub hash: H256,
+}
+
 /// Two protocol parameters `closest` and `farthest` define the closest
 /// and farthest on-chain distance between a transaction's proposal
 /// and commitment.
@@ -1348,6 +1362,7 @@ impl HardForkFeature {
             Self::new("0032", convert(switch.rfc_0032())),
             Self::new("0036", convert(switch.rfc_0036())),
             Self::new("0038", convert(switch.rfc_0038())),
+            Self::new("0044", convert(switch.rfc_0044())),
         ]
     }
 }
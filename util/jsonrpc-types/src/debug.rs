@@ -1,3 +1,5 @@
+use crate::BlockNumber;
+use ckb_types::H256;
 use serde::{Deserialize, Serialize};
 
 /// Runtime logger config for extra loggers.
@@ -55,3 +57,75 @@ pub struct MainLoggerConfig {
     /// **Optional**, null means keeping the current option unchanged.
     pub color: Option<bool>,
 }
+
+/// The report of the database integrity check.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DbCheckReport {
+    /// The number of the first checked block.
+    pub from: BlockNumber,
+    /// The number of the last checked block.
+    pub to: BlockNumber,
+    /// Whether the live cells created in the checked blocks have been recomputed.
+    pub cells_checked: bool,
+    /// The inconsistencies found.
+    pub findings: Vec<DbCheckFinding>,
+}
+
+/// An inconsistency found by the database integrity check.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DbCheckFinding {
+    /// The part of the database where the inconsistency is found.
+    pub kind: DbCheckFindingKind,
+    /// The number of the related block.
+    pub block_number: Option<BlockNumber>,
+    /// The hash of the related block.
+    pub block_hash: Option<H256>,
+    /// The description of the inconsistency.
+    pub message: String,
+    /// Whether the inconsistency only affects the indexes, which can be rebuilt by
+    /// `ckb db-check --fix`.
+    pub fixable: bool,
+}
+
+/// The part of the database where an inconsistency is found.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DbCheckFindingKind {
+    /// The main chain index.
+    Index,
+    /// The block headers and the block exts.
+    Header,
+    /// The block bodies, uncles and proposals.
+    Body,
+    /// The transaction index.
+    TransactionInfo,
+    /// The freezer files.
+    Freezer,
+    /// The live cell set.
+    Cell,
+}
+
+/// The state of the database integrity check started by `check_db`.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DbCheckState {
+    /// No check has been started since the node started.
+    Idle,
+    /// The check is running in the background.
+    Running,
+    /// The check has finished, the report is available.
+    Done,
+    /// The check has failed, the error is available.
+    Failed,
+}
+
+/// The status of the database integrity check started by `check_db`.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DbCheckStatus {
+    /// The state of the check.
+    pub state: DbCheckState,
+    /// The report of the finished check.
+    pub report: Option<DbCheckReport>,
+    /// The error of the failed check.
+    pub error: Option<String>,
+}
@@ -0,0 +1,172 @@
+use crate::{BlockNumber, Capacity, CellOutput, JsonBytes, OutPoint, Script, Uint32, Uint64};
+use ckb_types::H256;
+use serde::{Deserialize, Serialize};
+
+/// The search key of the indexer RPC methods.
+///
+/// ## Examples
+///
+/// ```
+/// # serde_json::from_str::<ckb_jsonrpc_types::IndexerSearchKey>(r#"
+/// {
+///   "script": {
+///     "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+///     "hash_type": "type",
+///     "args": "0x8211f1b938a107cd53b6302cc752a6fc3965638d"
+///   },
+///   "script_type": "lock",
+///   "filter": {
+///     "output_data_len_range": ["0x0", "0x1"]
+///   }
+/// }
+/// # "#).unwrap();
+/// ```
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct IndexerSearchKey {
+    /// The script to search the cells or transactions by.
+    pub script: Script,
+    /// Whether `script` is matched against the lock script or the type script.
+    pub script_type: IndexerScriptType,
+    /// How `script.args` is matched. (**Optional**, the default is `prefix`.)
+    #[serde(default)]
+    pub script_search_mode: Option<IndexerScriptSearchMode>,
+    /// Extra conditions applied to the matched cells or transactions. (**Optional**)
+    #[serde(default)]
+    pub filter: Option<IndexerSearchKeyFilter>,
+    /// Whether to return the cell data in `get_cells`. (**Optional**, the default is `true`.)
+    #[serde(default)]
+    pub with_data: Option<bool>,
+}
+
+/// The extra conditions of [`IndexerSearchKey`](struct.IndexerSearchKey.html).
+///
+/// All the ranges are half-open intervals `[start, end)`.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct IndexerSearchKeyFilter {
+    /// The other script of the cell, matched by prefix.
+    ///
+    /// It is the type script when `script_type` is `lock`, and the lock script when
+    /// `script_type` is `type`.
+    #[serde(default)]
+    pub script: Option<Script>,
+    /// The prefix of the cell data.
+    #[serde(default)]
+    pub output_data: Option<JsonBytes>,
+    /// The range of the cell data length in bytes.
+    #[serde(default)]
+    pub output_data_len_range: Option<[Uint64; 2]>,
+    /// The range of the cell capacity in shannons.
+    #[serde(default)]
+    pub output_capacity_range: Option<[Uint64; 2]>,
+    /// The range of the number of the block which committed the cell or the transaction.
+    #[serde(default)]
+    pub block_range: Option<[BlockNumber; 2]>,
+}
+
+/// The script field that is matched in [`IndexerSearchKey`](struct.IndexerSearchKey.html).
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexerScriptType {
+    /// Lock script.
+    Lock,
+    /// Type script.
+    Type,
+}
+
+/// How the script args are matched in [`IndexerSearchKey`](struct.IndexerSearchKey.html).
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexerScriptSearchMode {
+    /// The script args is a prefix of the args of the indexed script.
+    Prefix,
+    /// The script args equals to the args of the indexed script.
+    Exact,
+}
+
+impl Default for IndexerScriptSearchMode {
+    fn default() -> Self {
+        IndexerScriptSearchMode::Prefix
+    }
+}
+
+/// The order of the returned objects, sorted by block number, transaction index and io index.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexerOrder {
+    /// Descending order.
+    Desc,
+    /// Ascending order.
+    Asc,
+}
+
+/// A live cell returned by the indexer.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct IndexerCell {
+    /// The cell fields except the cell data.
+    pub output: CellOutput,
+    /// The cell data, it is null when `with_data` is `false` in the search key.
+    pub output_data: Option<JsonBytes>,
+    /// The reference to the cell.
+    pub out_point: OutPoint,
+    /// The number of the block which committed the cell.
+    pub block_number: BlockNumber,
+    /// The position of the transaction in the block.
+    pub tx_index: Uint32,
+}
+
+/// Whether a transaction is matched by an input or an output.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexerCellType {
+    /// The transaction consumes a matched cell.
+    Input,
+    /// The transaction creates a matched cell.
+    Output,
+}
+
+/// A transaction returned by the indexer.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct IndexerTx {
+    /// The transaction hash.
+    pub tx_hash: H256,
+    /// The number of the block which committed the transaction.
+    pub block_number: BlockNumber,
+    /// The position of the transaction in the block.
+    pub tx_index: Uint32,
+    /// The index of the matched input or output.
+    pub io_index: Uint32,
+    /// Whether the matched cell is an input or an output.
+    pub io_type: IndexerCellType,
+}
+
+/// A page of objects returned by the indexer.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct IndexerPagination<T> {
+    /// The objects in this page.
+    pub objects: Vec<T>,
+    /// Pass it as `after_cursor` to fetch the next page.
+    pub last_cursor: JsonBytes,
+}
+
+impl<T> IndexerPagination<T> {
+    /// Creates a page from the objects and the cursor of the last object.
+    pub fn new(objects: Vec<T>, last_cursor: JsonBytes) -> Self {
+        IndexerPagination {
+            objects,
+            last_cursor,
+        }
+    }
+}
+
+/// The total capacity of the live cells matched by a search key.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct IndexerCellsCapacity {
+    /// The total capacity.
+    pub capacity: Capacity,
+    /// The hash of the indexer tip block when the capacity is calculated.
+    pub block_hash: H256,
+    /// The number of the indexer tip block when the capacity is calculated.
+    pub block_number: BlockNumber,
+}
@@ -9,6 +9,7 @@ mod debug;
 mod experiment;
 mod fee_rate;
 mod fixed_bytes;
+mod indexer;
 mod net;
 mod pool;
 mod primitive;
@@ -21,31 +22,42 @@ mod tests;
 
 pub use self::alert::{Alert, AlertId, AlertMessage, AlertPriority};
 pub use self::block_template::{
-    BlockTemplate, CellbaseTemplate, TransactionTemplate, UncleTemplate,
+    BlockTemplate, CellbaseTemplate, PayoutTarget, TransactionTemplate, UncleTemplate,
 };
 pub use self::blockchain::{
-    Block, BlockEconomicState, BlockIssuance, BlockView, CellDep, CellInput, CellOutput, Consensus,
-    DepType, EpochView, HardForkFeature, Header, HeaderView, MerkleProof, MinerReward, OutPoint,
-    ProposalWindow, Script, ScriptHashType, Status, Transaction, TransactionProof, TransactionView,
-    TransactionWithStatus, TxStatus, UncleBlock, UncleBlockView,
+    Block, BlockEconomicState, BlockFilter, BlockIssuance, BlockView, CellDep, CellInput,
+    CellOutput, Consensus, DepType, EpochView, HardForkFeature, Header, HeaderView, MerkleProof,
+    MinerReward, OutPoint, ProposalWindow, Script, ScriptHashType, Status, Transaction,
+    TransactionProof, TransactionView, TransactionWithStatus, TxStatus, UncleBlock, UncleBlockView,
 };
 pub use self::bytes::JsonBytes;
 pub use self::cell::{CellData, CellInfo, CellWithStatus};
 pub use self::chain_info::ChainInfo;
-pub use self::debug::{ExtraLoggerConfig, MainLoggerConfig};
+pub use self::debug::{
+    DbCheckFinding, DbCheckFindingKind, DbCheckReport, DbCheckState, DbCheckStatus,
+    ExtraLoggerConfig, MainLoggerConfig,
+};
 pub use self::experiment::{DaoWithdrawingCalculationKind, DryRunResult};
 pub use self::fee_rate::FeeRateDef;
 pub use self::fixed_bytes::Byte32;
+pub use self::indexer::{
+    IndexerCell, IndexerCellType, IndexerCellsCapacity, IndexerOrder, IndexerPagination,
+    IndexerScriptSearchMode, IndexerScriptType, IndexerSearchKey, IndexerSearchKeyFilter,
+    IndexerTx,
+};
 pub use self::net::{
-    BannedAddr, LocalNode, LocalNodeProtocol, NodeAddress, PeerSyncState, RemoteNode,
-    RemoteNodeProtocol, SyncState,
+    BannedAddr, LocalNode, LocalNodeProtocol, NodeAddress, PeerDownloadState, PeerSyncState,
+    RemoteNode, RemoteNodeProtocol, SyncState,
 };
 pub use self::pool::{
-    OutputsValidator, PoolTransactionEntry, PoolTransactionReject, RawTxPool, TxPoolEntries,
-    TxPoolEntry, TxPoolIds, TxPoolInfo,
+    EstimatedFeeRate, OutputsValidator, PoolTransactionEntry, PoolTransactionReject, RawTxPool,
+    TxPoolEntries, TxPoolEntry, TxPoolIds, TxPoolInfo,
 };
 pub use self::proposal_short_id::ProposalShortId;
-pub use self::subscription::Topic;
+pub use self::subscription::{
+    BlockNumberAndHash, ChainReorg, MatchedBlock, MatchedCell, MatchedTransaction,
+    SubscriptionCursor, SubscriptionFilter, Topic,
+};
 pub use self::uints::{Uint128, Uint32, Uint64};
 pub use primitive::{
     AsEpochNumberWithFraction, BlockNumber, Capacity, Cycle, EpochNumber, EpochNumberWithFraction,
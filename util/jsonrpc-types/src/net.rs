@@ -283,4 +283,30 @@ pub struct SyncState {
     pub normal_time: Uint64,
     /// The download scheduler's time analysis data, the low is the 9/10 of the cut-off point, unit ms
     pub low_time: Uint64,
+    /// The block download statistics of the peers which the local node downloads blocks from.
+    pub download_peers: Vec<PeerDownloadState>,
+}
+
+/// The block download statistics of a remote node.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct PeerDownloadState {
+    /// The remote node ID which is derived from its P2P private key.
+    pub node_id: String,
+    /// The count of the blocks in transit.
+    pub inflight_count: Uint64,
+    /// The max count of the blocks in transit.
+    ///
+    /// It grows when the remote node responds fast, and shrinks when it responds slowly or the
+    /// requests stall.
+    pub window_size: Uint64,
+    /// The moving average of the block response time, unit ms.
+    pub latency: Uint64,
+    /// The moving average of the blocks received per minute.
+    pub throughput: Uint64,
+    /// The count of the received blocks.
+    pub received_count: Uint64,
+    /// The count of the block requests reassigned to the other remote nodes because they stalled.
+    pub stalled_count: Uint64,
+    /// The count of the block requests which timed out.
+    pub timeout_count: Uint64,
 }
@@ -1,4 +1,4 @@
-use crate::{BlockNumber, Capacity, Cycle, Timestamp, TransactionView, Uint64};
+use crate::{BlockNumber, Capacity, Cycle, Timestamp, TransactionView, Uint32, Uint64};
 use ckb_types::core::service::PoolTransactionEntry as CorePoolTransactionEntry;
 use ckb_types::core::tx_pool::{Reject, TxEntryInfo, TxPoolEntryInfo, TxPoolIds as CoreTxPoolIds};
 use ckb_types::prelude::Unpack;
@@ -36,12 +36,34 @@ pub struct TxPoolInfo {
     pub total_tx_cycles: Uint64,
     /// Fee rate threshold. The pool rejects transactions which fee rate is below this threshold.
     ///
+    /// It is the config option `tx_pool.min_fee_rate` normally. When the pool is full and evicts
+    /// transactions with low fee rates, the threshold rises above the fee rates of the evicted
+    /// transactions, then decays back to the config option over time.
+    ///
     /// The unit is Shannons per 1000 bytes transaction serialization size in the block.
     pub min_fee_rate: Uint64,
     /// Last updated time. This is the Unix timestamp in milliseconds.
     pub last_txs_updated_at: Timestamp,
 }
 
+/// The fee rate estimated from the recent transactions committed history.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct EstimatedFeeRate {
+    /// The estimated fee rate.
+    ///
+    /// The unit is Shannons per 1000 bytes transaction serialization size in the block.
+    pub fee_rate: Uint64,
+    /// The target blocks used in the estimation.
+    ///
+    /// It is greater than the requested one when the requested one is too small. A transaction
+    /// must be proposed first, and then it can only be committed after the closest proposal
+    /// window.
+    pub target_blocks: Uint64,
+    /// The percentage of the recent transactions around the estimated fee rate which were
+    /// committed within the target blocks.
+    pub confidence: Uint32,
+}
+
 /// The transaction entry in the pool.
 #[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
 pub struct PoolTransactionEntry {
@@ -210,6 +232,12 @@ pub enum PoolTransactionReject {
 
     /// Transaction expired
     Expiry(String),
+
+    /// Replace-by-fee rejected
+    RBFRejected(String),
+
+    /// Replaced by a conflicting transaction paying a higher fee
+    RBFReplaced(String),
 }
 
 impl From<Reject> for PoolTransactionReject {
@@ -226,6 +254,8 @@ impl From<Reject> for PoolTransactionReject {
             Reject::Resolve(_) => Self::Resolve(format!("{}", reject)),
             Reject::Verification(_) => Self::Verification(format!("{}", reject)),
             Reject::Expiry(_) => Self::Expiry(format!("{}", reject)),
+            Reject::RBFRejected(_) => Self::RBFRejected(format!("{}", reject)),
+            Reject::RBFReplaced(_) => Self::RBFReplaced(format!("{}", reject)),
         }
     }
 }
@@ -1,3 +1,9 @@
+use crate::{BlockNumber, CellOutput, JsonBytes, OutPoint, Script, Uint64};
+use ckb_types::{
+    core::{self, HeaderView},
+    prelude::*,
+    H256,
+};
 use serde::{Deserialize, Serialize};
 
 /// Specifies the topic which to be added as active subscription.
@@ -14,4 +20,169 @@ pub enum Topic {
     ProposedTransaction,
     /// Subscribe transactions which are abandoned by tx-pool.
     RejectedTransaction,
+    /// Subscribe chain reorganizations.
+    ChainReorg,
+}
+
+/// The starting point of a resumed subscription, which is the last event the subscriber has
+/// received.
+///
+/// The block topics `new_tip_header` and `new_tip_block` accept all the variants, where the
+/// sequence number is the block number, or the last one plus one after a reorganization. The
+/// other topics only accept `seq`.
+///
+/// ## Examples
+///
+/// ```
+/// # serde_json::from_str::<ckb_jsonrpc_types::SubscriptionCursor>(r#"
+/// {
+///   "block_hash": "0xa5f5c85987a15de25661e5a214f2c1449cd803f071acc7999820f25246471f40"
+/// }
+/// # "#).unwrap();
+/// ```
+///
+/// ```
+/// # serde_json::from_str::<ckb_jsonrpc_types::SubscriptionCursor>(r#"
+/// {
+///   "seq": "0x2a"
+/// }
+/// # "#).unwrap();
+/// ```
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionCursor {
+    /// Resumes after the main chain block with the number.
+    BlockNumber(BlockNumber),
+    /// Resumes after the block, which must be in the main chain.
+    BlockHash(H256),
+    /// Resumes after the event with the sequence number.
+    Seq(Uint64),
+}
+
+/// Filters the transactions and the cells pushed to a subscription.
+///
+/// A cell matches the filter when it matches all the given conditions. A transaction matches the
+/// filter when any of its outputs or the cells consumed by its inputs matches. At least one
+/// condition must be given.
+///
+/// The filtered subscriptions push the matched cells instead of the whole transactions, see
+/// [`MatchedTransaction`](struct.MatchedTransaction.html) and
+/// [`MatchedBlock`](struct.MatchedBlock.html).
+///
+/// ## Examples
+///
+/// ```
+/// # serde_json::from_str::<ckb_jsonrpc_types::SubscriptionFilter>(r#"
+/// {
+///   "lock": {
+///     "code_hash": "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8",
+///     "hash_type": "type",
+///     "args": "0x36c329ed630d6ce750712a477543672adab57f4c"
+///   },
+///   "type_code_hash_prefix": "0x82d76d1b75fe2fd9"
+/// }
+/// # "#).unwrap();
+/// ```
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionFilter {
+    /// Matches the cells whose lock script is the script.
+    pub lock: Option<Script>,
+    /// Matches the cells whose type script is the script.
+    #[serde(rename = "type")]
+    pub type_: Option<Script>,
+    /// Matches the cells which have a type script whose code hash starts with the prefix.
+    pub type_code_hash_prefix: Option<JsonBytes>,
+    /// Matches the cells whose data starts with the prefix.
+    pub output_data_prefix: Option<JsonBytes>,
+}
+
+impl SubscriptionFilter {
+    /// Whether no condition is given.
+    pub fn is_empty(&self) -> bool {
+        self.lock.is_none()
+            && self.type_.is_none()
+            && self.type_code_hash_prefix.is_none()
+            && self.output_data_prefix.is_none()
+    }
+}
+
+/// A cell matched by a subscription filter.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct MatchedCell {
+    /// Reference to the cell.
+    pub out_point: OutPoint,
+    /// The cell output.
+    pub output: CellOutput,
+    /// The cell data.
+    pub output_data: JsonBytes,
+}
+
+/// The cells of a transaction matched by a subscription filter.
+///
+/// The filtered transaction topics push it instead of the transaction.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct MatchedTransaction {
+    /// The transaction hash.
+    pub tx_hash: H256,
+    /// The matched cells consumed by the inputs.
+    ///
+    /// The consumed cells which cannot be resolved are never matched, for example, the cells
+    /// created by a transaction which has been pruned or has left the pool.
+    pub inputs: Vec<MatchedCell>,
+    /// The matched cells created by the outputs.
+    pub outputs: Vec<MatchedCell>,
+}
+
+/// The cells of a block matched by a subscription filter.
+///
+/// The filtered `new_tip_block` topic pushes it instead of the block.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct MatchedBlock {
+    /// The block header.
+    pub header: crate::HeaderView,
+    /// The transactions which have matched cells, in the order in the block.
+    pub transactions: Vec<MatchedTransaction>,
+}
+
+/// The number and the hash of a block.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct BlockNumberAndHash {
+    /// The block number.
+    pub number: BlockNumber,
+    /// The block hash.
+    pub hash: H256,
+}
+
+impl From<&HeaderView> for BlockNumberAndHash {
+    fn from(header: &HeaderView) -> Self {
+        BlockNumberAndHash {
+            number: header.number().into(),
+            hash: header.hash().unpack(),
+        }
+    }
+}
+
+/// A chain reorganization, where some blocks in the main chain are rolled back and replaced by the
+/// blocks in a fork with more accumulated difficulty.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct ChainReorg {
+    /// The latest common ancestor of the old and the new main chains.
+    pub ancestor: BlockNumberAndHash,
+    /// The blocks detached from the main chain, sorted by block number.
+    pub detached_blocks: Vec<BlockNumberAndHash>,
+    /// The blocks attached to the main chain, sorted by block number.
+    ///
+    /// The last one is the new tip.
+    pub attached_blocks: Vec<BlockNumberAndHash>,
+}
+
+impl From<core::service::ChainReorg> for ChainReorg {
+    fn from(reorg: core::service::ChainReorg) -> Self {
+        ChainReorg {
+            ancestor: (&reorg.ancestor).into(),
+            detached_blocks: reorg.detached_blocks.iter().map(Into::into).collect(),
+            attached_blocks: reorg.attached_blocks.iter().map(Into::into).collect(),
+        }
+    }
 }
@@ -11,12 +11,14 @@ mod shared_builder;
 #[cfg(test)]
 mod tests;
 
-use ckb_app_config::{BlockAssemblerConfig, ExitCode, RunArgs, SupportProtocol};
+use ckb_app_config::{BlockAssemblerConfig, DBConfig, ExitCode, RunArgs, SupportProtocol};
 use ckb_async_runtime::Handle;
+use ckb_block_filter::{BlockFilterController, BlockFilterService};
 use ckb_build_info::Version;
 use ckb_chain::chain::{ChainController, ChainService};
 use ckb_channel::Receiver;
-use ckb_jsonrpc_types::ScriptHashType;
+use ckb_indexer::{IndexerController, IndexerHandle, IndexerService};
+use ckb_jsonrpc_types::{JsonBytes, ScriptHashType};
 use ckb_logger::info;
 use ckb_network::{
     observe_listen_port_occupancy, CKBProtocol, DefaultExitHandler, NetworkController,
@@ -28,11 +30,18 @@ use ckb_resource::Resource;
 use ckb_rpc::{RpcServer, ServiceBuilder};
 use ckb_shared::Shared;
 use ckb_store::{ChainDB, ChainStore};
-use ckb_sync::{NetTimeProtocol, Relayer, SyncShared, Synchronizer};
-use ckb_tx_pool::service::TxVerificationResult;
-use ckb_types::prelude::*;
+use ckb_sync::{
+    BlockFilter, LightClientProtocol, NetTimeProtocol, Relayer, SnapshotValidation, SyncShared,
+    Synchronizer,
+};
+use ckb_tx_pool::{
+    block_assembler::{check_payout_targets, PayoutTarget},
+    service::TxVerificationResult,
+};
+use ckb_types::{packed, prelude::*, H256};
 use ckb_verification::GenesisVerifier;
 use ckb_verification_traits::Verifier;
+use std::fs;
 use std::sync::Arc;
 
 pub use crate::shared_builder::{SharedBuilder, SharedPackage};
@@ -68,7 +77,7 @@ impl Launcher {
             self.args.config.block_assembler.clone(),
         ) {
             (true, Some(mut block_assembler)) => {
-                let check_lock_code_hash = |code_hash| -> Result<bool, ExitCode> {
+                let check_lock_code_hash = |code_hash: &packed::Byte32| -> Result<bool, ExitCode> {
                     let secp_cell_data =
                         Resource::bundled("specs/cells/secp256k1_blake160_sighash_all".to_string())
                             .get()
@@ -94,11 +103,35 @@ impl Launcher {
                                     == Some(code_hash)
                         }))
                 };
-                if self.args.block_assembler_advanced
-                    || (block_assembler.hash_type == ScriptHashType::Type
-                        && block_assembler.args.len() == SECP256K1_BLAKE160_SIGHASH_ALL_ARG_LEN
-                        && check_lock_code_hash(&block_assembler.code_hash.pack())?)
-                {
+                let is_recommended_lock = |hash_type: &ScriptHashType,
+                                           args: &JsonBytes,
+                                           code_hash: &H256|
+                 -> Result<bool, ExitCode> {
+                    Ok(*hash_type == ScriptHashType::Type
+                        && args.len() == SECP256K1_BLAKE160_SIGHASH_ALL_ARG_LEN
+                        && check_lock_code_hash(&code_hash.pack())?)
+                };
+                let mut is_recommended = is_recommended_lock(
+                    &block_assembler.hash_type,
+                    &block_assembler.args,
+                    &block_assembler.code_hash,
+                )?;
+                for target in &block_assembler.payout_targets {
+                    is_recommended = is_recommended
+                        && is_recommended_lock(&target.hash_type, &target.args, &target.code_hash)?;
+                }
+                let payout_targets: Vec<PayoutTarget> = block_assembler
+                    .payout_targets
+                    .iter()
+                    .map(Into::into)
+                    .collect();
+                if let Err(err) = check_payout_targets(&payout_targets) {
+                    eprintln!("Invalid block_assembler.payout_targets: {}", err);
+                    return Err(ExitCode::Config);
+                }
+                if self.args.block_assembler_advanced || is_recommended {
+                    // the payout targets set via RPC are checked against the recommended lock too
+                    block_assembler.advanced = self.args.block_assembler_advanced;
                     if block_assembler.use_binary_version_as_message_prefix {
                         block_assembler.binary_version = self.version.long();
                     }
@@ -176,6 +209,35 @@ impl Launcher {
         Ok(())
     }
 
+    // the indexer and the block filter service read the old block bodies when they catch up with
+    // the chain, so they cannot run on a pruned node
+    fn check_prune_depth(&self) -> Result<(), ExitCode> {
+        if self.args.config.store.prune_depth.is_none() {
+            return Ok(());
+        }
+        if self.args.config.rpc.indexer_enable() {
+            eprintln!(
+                "store.prune_depth cannot be used with the RPC module Indexer, \
+                 disable one of them in ckb.toml"
+            );
+            return Err(ExitCode::Config);
+        }
+        if self
+            .args
+            .config
+            .network
+            .support_protocols
+            .contains(&SupportProtocol::Filter)
+        {
+            eprintln!(
+                "store.prune_depth cannot be used with the Filter protocol, \
+                 disable one of them in ckb.toml"
+            );
+            return Err(ExitCode::Config);
+        }
+        Ok(())
+    }
+
     fn verify_genesis(&self, shared: &Shared) -> Result<(), ExitCode> {
         GenesisVerifier::new()
             .verify(shared.consensus())
@@ -193,6 +255,7 @@ impl Launcher {
         self.async_handle.block_on(observe_listen_port_occupancy(
             &self.args.config.network.listen_addresses,
         ))?;
+        self.check_prune_depth()?;
 
         let shared_builder = SharedBuilder::new(
             &self.args.config.bin_name,
@@ -237,7 +300,111 @@ impl Launcher {
         chain_controller
     }
 
+    /// Start indexer service if the rpc module Indexer is enabled, return IndexerController
+    pub fn start_indexer(&self, shared: &Shared) -> Option<IndexerController> {
+        if !self.args.config.rpc.indexer_enable() {
+            return None;
+        }
+        let indexer_service = IndexerService::new(shared.clone(), &self.args.config.indexer);
+        let indexer_controller = indexer_service.start(Some("IndexerService"));
+        info!(
+            "indexer store: {}",
+            self.args.config.indexer.store.display()
+        );
+        Some(indexer_controller)
+    }
+
+    /// Start block filter service if the Filter protocol is enabled, return BlockFilterController
+    pub fn start_block_filter(&self, shared: &Shared) -> Option<BlockFilterController> {
+        if !self
+            .args
+            .config
+            .network
+            .support_protocols
+            .contains(&SupportProtocol::Filter)
+        {
+            return None;
+        }
+        let block_filter_service = BlockFilterService::new(shared.clone());
+        Some(block_filter_service.start(Some("BlockFilterService")))
+    }
+
+    /// Start the background validation of the history below the imported snapshot, if there is
+    /// one not validated yet
+    ///
+    /// The history is replayed in a separate database, which is removed once the snapshot is
+    /// validated. The node refuses to start if the snapshot turns out to be invalid.
+    pub fn start_snapshot_validation(
+        &self,
+        shared: &Shared,
+    ) -> Result<Option<SnapshotValidation>, ExitCode> {
+        let store = shared.store();
+        if let Some(hash) = store.get_invalid_snapshot() {
+            eprintln!(
+                "The history below the imported snapshot block {} is invalid, \
+                 run `ckb reset-data --database` and sync from the genesis block",
+                hash
+            );
+            return Err(ExitCode::Failure);
+        }
+        let root = self.args.config.data_dir.join("snapshot_validation");
+        let name = store.get_unvalidated_snapshot().map(|(hash, _)| {
+            let hash: H256 = hash.unpack();
+            format!("{:x}", hash)
+        });
+        // removes the databases of the validated snapshot and the ones imported before
+        if root.exists() {
+            let entries = fs::read_dir(&root).map_err(|err| {
+                eprintln!("read_dir {} error: {}", root.display(), err);
+                ExitCode::IO
+            })?;
+            for entry in entries.filter_map(Result::ok) {
+                if name.as_deref() != entry.file_name().to_str() {
+                    fs::remove_dir_all(entry.path()).map_err(|err| {
+                        eprintln!("remove_dir_all {} error: {}", entry.path().display(), err);
+                        ExitCode::IO
+                    })?;
+                }
+            }
+        }
+        let name = match name {
+            Some(name) => name,
+            None => return Ok(None),
+        };
+
+        let db_config = DBConfig {
+            path: root.join(name),
+            ..Default::default()
+        };
+        let (validation_shared, mut pack) = SharedBuilder::new(
+            &self.args.config.bin_name,
+            self.args.config.root_dir.as_path(),
+            &db_config,
+            None,
+            self.async_handle.clone(),
+        )?
+        .consensus(self.args.consensus.clone())
+        .build()?;
+        // the validation chain has no transaction pool
+        pack.take_tx_pool_builder();
+        pack.take_relay_tx_receiver();
+        let chain_service =
+            ChainService::new(validation_shared.clone(), pack.take_proposal_table());
+        let chain_controller = chain_service.start(Some("SnapshotValidationService"));
+
+        let validation =
+            SnapshotValidation::new(store.clone(), validation_shared, chain_controller);
+        if let Some(ref validation) = validation {
+            info!(
+                "validating the history below the imported snapshot block {} in the background",
+                validation.number()
+            );
+        }
+        Ok(validation)
+    }
+
     /// Start network service and rpc serve
+    #[allow(clippy::too_many_arguments)]
     pub fn start_network_and_rpc(
         &self,
         shared: &Shared,
@@ -245,6 +412,8 @@ impl Launcher {
         exit_handler: &DefaultExitHandler,
         miner_enable: bool,
         relay_tx_receiver: Receiver<TxVerificationResult>,
+        indexer_handle: Option<IndexerHandle>,
+        snapshot_validation: Option<SnapshotValidation>,
     ) -> (NetworkController, RpcServer) {
         let sync_shared = Arc::new(SyncShared::with_tmpdir(
             shared.clone(),
@@ -254,11 +423,13 @@ impl Launcher {
         ));
         let network_state = Arc::new(
             NetworkState::from_config(self.args.config.network.clone())
-                .expect("Init network state failed"),
+                .expect("Init network state failed")
+                .pruned(self.args.config.store.prune_depth.is_some()),
         );
 
         // Sync is a core protocol, user cannot disable it via config
-        let synchronizer = Synchronizer::new(chain_controller.clone(), Arc::clone(&sync_shared));
+        let synchronizer = Synchronizer::new(chain_controller.clone(), Arc::clone(&sync_shared))
+            .snapshot_validation(snapshot_validation.map(Arc::new));
         let mut protocols = vec![CKBProtocol::new_with_support_protocol(
             SupportProtocols::Sync,
             Box::new(synchronizer),
@@ -286,6 +457,24 @@ impl Launcher {
             ));
         }
 
+        if support_protocols.contains(&SupportProtocol::LightClient) {
+            let light_client = LightClientProtocol::new(Arc::clone(&sync_shared));
+            protocols.push(CKBProtocol::new_with_support_protocol(
+                SupportProtocols::LightClient,
+                Box::new(light_client),
+                Arc::clone(&network_state),
+            ));
+        }
+
+        if support_protocols.contains(&SupportProtocol::Filter) {
+            let filter = BlockFilter::new(Arc::clone(&sync_shared));
+            protocols.push(CKBProtocol::new_with_support_protocol(
+                SupportProtocols::Filter,
+                Box::new(filter),
+                Arc::clone(&network_state),
+            ));
+        }
+
         let alert_signature_config = self.args.config.alert_signature.clone().unwrap_or_default();
         let alert_relayer = AlertRelayer::new(
             self.version.to_string(),
@@ -320,7 +509,6 @@ impl Launcher {
             .enable_chain(shared.clone())
             .enable_pool(
                 shared.clone(),
-                self.args.config.tx_pool.min_fee_rate,
                 self.args.config.rpc.reject_ill_transactions,
                 self.args
                     .config
@@ -348,14 +536,11 @@ impl Launcher {
             .enable_experiment(shared.clone())
             .enable_integration_test(shared.clone(), network_controller.clone(), chain_controller)
             .enable_alert(alert_verifier, alert_notifier, network_controller.clone())
-            .enable_debug();
+            .enable_debug(shared.clone())
+            .enable_indexer(indexer_handle);
         let io_handler = builder.build();
 
-        let rpc_server = RpcServer::new(
-            self.args.config.rpc.clone(),
-            io_handler,
-            shared.notify_controller(),
-        );
+        let rpc_server = RpcServer::new(self.args.config.rpc.clone(), io_handler, shared);
 
         (network_controller, rpc_server)
     }
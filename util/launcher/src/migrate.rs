@@ -26,6 +26,8 @@ impl Migrate {
         migrations.add_migration(Box::new(migrations::AddNumberHashMapping)); // since v0.40.0
         migrations.add_migration(Box::new(migrations::AddExtraDataHash)); // since v0.43.0
         migrations.add_migration(Box::new(migrations::AddBlockExtensionColumnFamily)); // since v0.100.0
+        migrations.add_migration(Box::new(migrations::AddBlockFilterColumnFamily)); // since v0.104.0
+        migrations.add_migration(Box::new(migrations::AddChainRootMMR)); // since v0.104.0
 
         Migrate {
             migrations,
@@ -0,0 +1,33 @@
+use ckb_db::{Result, RocksDB};
+use ckb_db_migration::{Migration, ProgressBar};
+use std::sync::Arc;
+
+/// Adds the column families of the block filters.
+///
+/// The column families are created when the database is opened, this migration only bumps the
+/// database version. The filters of the existing blocks are not built here, because the bodies of
+/// the old blocks may have been moved into the freezer, which is not available to the migrations.
+/// The block filter service builds them in the background from the genesis block when the
+/// Filter protocol is enabled, and the `get_block_filter` RPC returns the `BlockFilterIsNotBuilt`
+/// error for the blocks whose filters are not built yet.
+pub struct AddBlockFilterColumnFamily;
+
+const VERSION: &str = "20220228100000";
+
+impl Migration for AddBlockFilterColumnFamily {
+    fn migrate(
+        &self,
+        db: RocksDB,
+        _pb: Arc<dyn Fn(u64) -> ProgressBar + Send + Sync>,
+    ) -> Result<RocksDB> {
+        Ok(db)
+    }
+
+    fn version(&self) -> &str {
+        VERSION
+    }
+
+    fn expensive(&self) -> bool {
+        false
+    }
+}
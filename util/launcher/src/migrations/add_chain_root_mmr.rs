@@ -0,0 +1,70 @@
+use ckb_app_config::StoreConfig;
+use ckb_db::{Result, RocksDB};
+use ckb_db_migration::{Migration, ProgressBar, ProgressStyle};
+use ckb_error::InternalErrorKind;
+use ckb_store::{ChainDB, ChainStore};
+use ckb_types::utilities::ChainRootMMR;
+use std::sync::Arc;
+
+/// Builds the chain root MMR of the existing main chain.
+///
+/// The MMR is built sequentially, since every leaf is merged with the peaks before it.
+pub struct AddChainRootMMR;
+
+const VERSION: &str = "20220425150000";
+
+const BATCH: u64 = 10_000;
+
+impl Migration for AddChainRootMMR {
+    fn migrate(
+        &self,
+        db: RocksDB,
+        pb: Arc<dyn Fn(u64) -> ProgressBar + Send + Sync>,
+    ) -> Result<RocksDB> {
+        let chain_db = ChainDB::new(db, StoreConfig::default());
+        let tip_number = chain_db
+            .get_tip_header()
+            .expect("db tip header index")
+            .number();
+
+        let pbi = pb(tip_number + 1);
+        pbi.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "{prefix:.bold.dim} {spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}",
+                )
+                .progress_chars("#>-"),
+        );
+        pbi.set_position(0);
+        pbi.enable_steady_tick(5000);
+
+        let mut block_number = 0;
+        let mut mmr_size = 0;
+        while block_number <= tip_number {
+            let db_txn = chain_db.begin_transaction();
+            let mut mmr = ChainRootMMR::new(mmr_size, &db_txn);
+            let end = ::std::cmp::min(block_number + BATCH, tip_number + 1);
+            for number in block_number..end {
+                let header = chain_db
+                    .get_block_hash(number)
+                    .and_then(|hash| chain_db.get_block_header(&hash))
+                    .expect("DB data integrity");
+                mmr.push(header.digest())
+                    .map_err(|err| InternalErrorKind::MMR.other(err))?;
+                pbi.inc(1);
+            }
+            mmr_size = mmr.mmr_size();
+            mmr.commit()
+                .map_err(|err| InternalErrorKind::MMR.other(err))?;
+            db_txn.commit()?;
+            block_number = end;
+        }
+
+        pbi.finish_with_message("done!");
+        Ok(chain_db.into_inner())
+    }
+
+    fn version(&self) -> &str {
+        VERSION
+    }
+}
@@ -1,10 +1,14 @@
 mod add_block_extension_cf;
+mod add_block_filter_cf;
+mod add_chain_root_mmr;
 mod add_extra_data_hash;
 mod add_number_hash_mapping;
 mod cell;
 mod table_to_struct;
 
 pub use add_block_extension_cf::AddBlockExtensionColumnFamily;
+pub use add_block_filter_cf::AddBlockFilterColumnFamily;
+pub use add_chain_root_mmr::AddChainRootMMR;
 pub use add_extra_data_hash::AddExtraDataHash;
 pub use add_number_hash_mapping::AddNumberHashMapping;
 pub use cell::CellMigration;
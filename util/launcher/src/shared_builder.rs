@@ -472,8 +472,8 @@ fn register_tx_pool_callback(tx_pool_builder: &mut TxPoolServiceBuilder, notify:
             tx_pool.update_statics_for_remove_tx(entry.size, entry.cycles);
 
             let tx_hash = entry.transaction().hash();
-            // record recent reject
-            if matches!(reject, Reject::Resolve(..)) {
+            // record recent reject, including the transactions evicted from the full pool
+            if matches!(reject, Reject::Resolve(..) | Reject::Full(..)) {
                 if let Some(ref mut recent_reject) = tx_pool.recent_reject {
                     if let Err(e) = recent_reject.put(&tx_hash, reject.clone()) {
                         error!("record recent_reject failed {} {} {}", tx_hash, reject, e);
@@ -4,7 +4,7 @@ use ckb_chain_spec::consensus::build_genesis_epoch_ext;
 use ckb_db::RocksDB;
 use ckb_db_schema::{
     COLUMN_BLOCK_BODY, COLUMN_BLOCK_EPOCH, COLUMN_BLOCK_EXT, COLUMN_BLOCK_HEADER,
-    COLUMN_BLOCK_PROPOSAL_IDS, COLUMN_BLOCK_UNCLE, COLUMN_EPOCH, COLUMN_META,
+    COLUMN_BLOCK_PROPOSAL_IDS, COLUMN_BLOCK_UNCLE, COLUMN_EPOCH, COLUMN_INDEX, COLUMN_META,
     META_CURRENT_EPOCH_KEY, META_TIP_HEADER_KEY,
 };
 use ckb_types::{
@@ -127,6 +127,25 @@ fn test_mock_migration() {
             .unwrap()
     }
 
+    // insert block index
+    {
+        let block_number: packed::Uint64 = genesis.header().number().pack();
+        db_txn
+            .put(
+                COLUMN_INDEX,
+                block_number.as_slice(),
+                genesis.header().hash().as_slice(),
+            )
+            .unwrap();
+        db_txn
+            .put(
+                COLUMN_INDEX,
+                genesis.header().hash().as_slice(),
+                block_number.as_slice(),
+            )
+            .unwrap()
+    }
+
     // insert_current_epoch_ext
     {
         db_txn
@@ -14,8 +14,21 @@ use serde::{Deserialize, Serialize};
 ///
 /// # An example which is used in `ckb.toml`:
 /// ```toml
+/// [metrics]
+/// labels = { chain = "ckb", node = "node-1" }
+/// histogram_buckets = [0.001, 0.01, 0.1, 1.0, 10.0]
+///
 /// [metrics.exporter.prometheus]
 /// target = { type = "prometheus", listen_address = "127.0.0.1:8100" }
+///
+/// [metrics.exporter.statsd]
+/// target = { type = "statsd", address = "127.0.0.1:8125", prefix = "ckb." }
+/// ```
+///
+/// And push the metrics to an OpenTelemetry collector, at most one OTLP target is allowed:
+/// ```toml
+/// [metrics.exporter.otlp]
+/// target = { type = "otlp", endpoint = "http://127.0.0.1:4317", interval = 10, headers = { "x-token" = "secret" } }
 /// ```
 ///
 /// [CKB metrics service]: ../ckb_metrics_service/index.html
@@ -25,6 +38,14 @@ pub struct Config {
     /// Stores all exporters configurations.
     #[serde(default)]
     pub exporter: HashMap<String, Exporter>,
+    /// The static labels attached to all the metrics, such as the chain and the node name.
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    /// The upper bounds of the histogram buckets, in seconds for the timing metrics.
+    ///
+    /// The default buckets of the exporter are used when it is not set.
+    #[serde(default)]
+    pub histogram_buckets: Option<Vec<f64>>,
 }
 
 /// The configuration of an exporter.
@@ -44,4 +65,33 @@ pub enum Target {
         /// The HTTP listen address.
         listen_address: String,
     },
+    /// Pushes the metrics data to an OpenTelemetry collector through OTLP over gRPC.
+    Otlp {
+        /// The gRPC endpoint of the collector.
+        endpoint: String,
+        /// The push interval in seconds.
+        #[serde(default = "default_push_interval")]
+        interval: u64,
+        /// The headers sent with each push, such as the authorization token.
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+    /// Sends the metrics data to a StatsD server over UDP.
+    Statsd {
+        /// The address of the StatsD server.
+        address: String,
+        /// The prefix prepended to the metric names.
+        #[serde(default)]
+        prefix: String,
+        /// The send interval in seconds.
+        #[serde(default = "default_push_interval")]
+        interval: u64,
+    },
 }
+
+const fn default_push_interval() -> u64 {
+    10
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,58 @@
+use crate::{Config, Target};
+
+#[test]
+fn test_parse_labels_and_buckets() {
+    let config: Config = toml::from_str(
+        r#"
+        labels = { chain = "ckb", node = "node-1" }
+        histogram_buckets = [0.001, 0.01, 0.1]
+
+        [exporter.prometheus]
+        target = { type = "prometheus", listen_address = "127.0.0.1:8100" }
+
+        [exporter.statsd]
+        target = { type = "statsd", address = "127.0.0.1:8125", prefix = "ckb." }
+
+        [exporter.otlp]
+        target = { type = "otlp", endpoint = "http://127.0.0.1:4317", headers = { "x-token" = "secret" } }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.labels.len(), 2);
+    assert_eq!(config.labels["chain"], "ckb");
+    assert_eq!(config.labels["node"], "node-1");
+    assert_eq!(config.histogram_buckets, Some(vec![0.001, 0.01, 0.1]));
+    assert_eq!(config.exporter.len(), 3);
+    match &config.exporter["statsd"].target {
+        Target::Statsd {
+            address,
+            prefix,
+            interval,
+        } => {
+            assert_eq!(address, "127.0.0.1:8125");
+            assert_eq!(prefix, "ckb.");
+            assert_eq!(*interval, 10);
+        }
+        target => panic!("unexpected target {:?}", target),
+    }
+    match &config.exporter["otlp"].target {
+        Target::Otlp {
+            interval, headers, ..
+        } => {
+            assert_eq!(*interval, 10);
+            assert_eq!(headers["x-token"], "secret");
+        }
+        target => panic!("unexpected target {:?}", target),
+    }
+}
+
+#[test]
+fn test_parse_defaults() {
+    let config: Config = toml::from_str("").unwrap();
+    assert!(config.exporter.is_empty());
+    assert!(config.labels.is_empty());
+    assert!(config.histogram_buckets.is_none());
+
+    assert!(toml::from_str::<Config>("unknown = 1").is_err());
+}
@@ -1,19 +1,32 @@
 //! The service which handles the metrics data in CKB.
 
-use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+mod statsd;
+#[cfg(test)]
+mod tests;
+
+use std::{collections::HashMap, convert::Infallible, net::SocketAddr, sync::Arc, time::Duration};
 
 use hyper::{
     header::CONTENT_TYPE,
     service::{make_service_fn, service_fn},
     Body, Error as HyperError, Method, Request, Response, Server,
 };
+use opentelemetry::{
+    global,
+    sdk::{metrics::selectors, Resource},
+    util::tokio_interval_stream,
+    KeyValue,
+};
+use opentelemetry_otlp::{ExporterConfig, Protocol, TonicConfig};
 use opentelemetry_prometheus::PrometheusExporter;
 use prometheus::Encoder as _;
 
 use ckb_async_runtime::Handle;
-use ckb_metrics_config::{Config, Exporter, Target};
+use ckb_metrics_config::{Config, Target};
 use ckb_util::strings;
 
+const OTLP_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Ensures the metrics service can shutdown gracefully.
 #[must_use]
 pub enum Guard {
@@ -21,46 +34,114 @@ pub enum Guard {
     Off,
     /// The metrics service is enabled.
     On,
+    /// The metrics service pushes the metrics data, which is stopped when the guard is dropped.
+    Push(opentelemetry::sdk::metrics::PushController),
 }
 
 /// Initializes the metrics service and lets it run in the background.
 ///
+/// The Prometheus and the StatsD targets share the same Prometheus registry, while the OTLP
+/// target pushes the metrics data by itself. When they are combined, the metrics are recorded to
+/// both of them.
+///
 /// Returns [Guard](enum.Guard.html) if succeeded, or an `String` to describes the reason for the failure.
 pub fn init(config: Config, handle: Handle) -> Result<Guard, String> {
     if config.exporter.is_empty() {
         return Ok(Guard::Off);
     }
 
-    for (name, exporter) in config.exporter {
-        check_exporter_name(&name)?;
-        run_exporter(exporter, &handle)?;
+    for name in config.exporter.keys() {
+        check_exporter_name(name)?;
+    }
+    if let Some(ref buckets) = config.histogram_buckets {
+        check_histogram_buckets(buckets)?;
+    }
+    let labels: Vec<KeyValue> = config
+        .labels
+        .into_iter()
+        .map(|(key, value)| KeyValue::new(key, value))
+        .collect();
+
+    let (otlp_targets, targets): (Vec<_>, Vec<_>) = config
+        .exporter
+        .into_values()
+        .map(|exporter| exporter.target)
+        .partition(|target| matches!(target, Target::Otlp { .. }));
+    if otlp_targets.len() > 1 {
+        return Err("only one otlp target is allowed".to_owned());
+    }
+
+    // Each exporter installs its own global meter provider, they are collected in turn.
+    let mut providers = Vec::new();
+    if !targets.is_empty() {
+        let exporter = {
+            let mut builder =
+                opentelemetry_prometheus::exporter().with_resource(Resource::new(labels.clone()));
+            if let Some(ref buckets) = config.histogram_buckets {
+                builder = builder.with_default_histogram_boundaries(buckets.clone());
+            }
+            let exporter = builder
+                .try_init()
+                .map_err(|err| format!("failed to init prometheus exporter because {}", err))?;
+            Arc::new(exporter)
+        };
+        for target in targets {
+            run_exporter(target, Arc::clone(&exporter), &handle)?;
+        }
+        providers.push(global::meter_provider());
+    }
+    let guard = match otlp_targets.into_iter().next() {
+        Some(Target::Otlp {
+            endpoint,
+            interval,
+            headers,
+        }) => {
+            let controller = run_otlp_exporter(
+                endpoint,
+                interval,
+                headers,
+                labels,
+                config.histogram_buckets,
+                &handle,
+            )?;
+            providers.push(global::meter_provider());
+            Guard::Push(controller)
+        }
+        _ => Guard::On,
+    };
+    if providers.len() > 1 && ckb_metrics::set_meter_providers(providers).is_err() {
+        return Err("the metrics service has been initialized".to_owned());
     }
 
-    Ok(Guard::On)
+    Ok(guard)
 }
 
 fn check_exporter_name(name: &str) -> Result<(), String> {
     strings::check_if_identifier_is_valid(name)
 }
 
-fn run_exporter(exporter: Exporter, handle: &Handle) -> Result<(), String> {
-    let Exporter { target } = exporter;
+fn check_histogram_buckets(buckets: &[f64]) -> Result<(), String> {
+    if buckets.is_empty() {
+        return Err("histogram_buckets should not be empty".to_owned());
+    }
+    if buckets.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err("histogram_buckets should be in increasing order".to_owned());
+    }
+    Ok(())
+}
+
+fn run_exporter(
+    target: Target,
+    prometheus_exporter: Arc<PrometheusExporter>,
+    handle: &Handle,
+) -> Result<(), String> {
     match target {
         Target::Prometheus { listen_address } => {
             let addr = listen_address
                 .parse::<SocketAddr>()
                 .map_err(|err| format!("failed to parse listen_address because {}", err))?;
-            // TODO Not allow to configure the prometheus exporter, since the API is not stable.
-            // If anyone who want to customize the configurations, update the follow code.
-            // Ref: https://docs.rs/opentelemetry-prometheus/*/opentelemetry_prometheus/struct.ExporterBuilder.html
-            let exporter = {
-                let exporter = opentelemetry_prometheus::exporter()
-                    .try_init()
-                    .map_err(|err| format!("failed to init prometheus exporter because {}", err))?;
-                Arc::new(exporter)
-            };
             let make_svc = make_service_fn(move |_conn| {
-                let exporter = Arc::clone(&exporter);
+                let exporter = Arc::clone(&prometheus_exporter);
                 async move {
                     Ok::<_, Infallible>(service_fn(move |req| {
                         start_prometheus_service(req, Arc::clone(&exporter))
@@ -75,10 +156,75 @@ fn run_exporter(exporter: Exporter, handle: &Handle) -> Result<(), String> {
                 }
             });
         }
+        Target::Statsd {
+            address,
+            prefix,
+            interval,
+        } => {
+            if interval == 0 {
+                return Err("the interval of the statsd target should not be zero".to_owned());
+            }
+            ckb_logger::info!("start statsd exporter to {}", address);
+            handle.spawn(statsd::run(
+                address,
+                prefix,
+                Duration::from_secs(interval),
+                prometheus_exporter,
+            ));
+        }
+        Target::Otlp { .. } => unreachable!("the otlp target is run by run_otlp_exporter"),
     }
     Ok(())
 }
 
+fn run_otlp_exporter(
+    endpoint: String,
+    interval: u64,
+    headers: HashMap<String, String>,
+    labels: Vec<KeyValue>,
+    histogram_buckets: Option<Vec<f64>>,
+    handle: &Handle,
+) -> Result<opentelemetry::sdk::metrics::PushController, String> {
+    if interval == 0 {
+        return Err("the interval of the otlp target should not be zero".to_owned());
+    }
+    let mut metadata = tonic::metadata::MetadataMap::new();
+    for (key, value) in headers {
+        let key = tonic::metadata::MetadataKey::from_bytes(key.as_bytes())
+            .map_err(|err| format!("invalid otlp header {} because {}", key, err))?;
+        let value = value
+            .parse()
+            .map_err(|err| format!("invalid otlp header value because {}", err))?;
+        metadata.insert(key, value);
+    }
+    let export_config = ExporterConfig {
+        endpoint: endpoint.clone(),
+        protocol: Protocol::Grpc,
+        timeout: OTLP_TIMEOUT,
+    };
+    let tonic_config = TonicConfig {
+        metadata: Some(metadata),
+    };
+    // Without the configured buckets, the timing metrics are exported as min, max, sum and count.
+    let selector = match histogram_buckets {
+        Some(buckets) => selectors::simple::Selector::Histogram(buckets),
+        None => selectors::simple::Selector::Inexpensive,
+    };
+    // The push controller spawns its worker and ticker on creation.
+    let controller = handle.enter(|| {
+        opentelemetry_otlp::new_metrics_pipeline(tokio::spawn, tokio_interval_stream)
+            .with_export_config(export_config)
+            .with_tonic_config(tonic_config)
+            .with_resource(labels)
+            .with_aggregator_selector(selector)
+            .with_period(Duration::from_secs(interval))
+            .build()
+            .map_err(|err| format!("failed to init otlp exporter because {}", err))
+    })?;
+    ckb_logger::info!("start otlp exporter to {}", endpoint);
+    Ok(controller)
+}
+
 async fn start_prometheus_service(
     req: Request<Body>,
     exporter: Arc<PrometheusExporter>,
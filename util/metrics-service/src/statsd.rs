@@ -0,0 +1,150 @@
+//! Sends the metrics data in the Prometheus registry to a StatsD server.
+//!
+//! The counters are cumulative in the registry, so they are sent as the StatsD counters of the
+//! increments since the last send. A histogram is sent as the counters of its sum and count. The
+//! other metrics are sent as the gauges of their current values. The labels are sent as the
+//! DogStatsD tags, which are understood by the most StatsD servers.
+
+use std::{collections::HashMap, fmt::Write as _, sync::Arc, time::Duration};
+
+use opentelemetry_prometheus::PrometheusExporter;
+use prometheus::proto::{MetricFamily, MetricType};
+use tokio::net::UdpSocket;
+
+/// Keeps the UDP packets below the common MTU.
+const MAX_PACKET_SIZE: usize = 1432;
+
+pub(crate) async fn run(
+    address: String,
+    prefix: String,
+    interval: Duration,
+    exporter: Arc<PrometheusExporter>,
+) {
+    let socket = match UdpSocket::bind("0.0.0.0:0").await {
+        Ok(socket) => socket,
+        Err(err) => {
+            ckb_logger::error!("statsd exporter failed to bind the socket: {}", err);
+            return;
+        }
+    };
+    if let Err(err) = socket.connect(&address).await {
+        ckb_logger::error!("statsd exporter failed to connect {}: {}", address, err);
+        return;
+    }
+    let mut formatter = Formatter::new(prefix);
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        let lines = exporter
+            .registry()
+            .gather()
+            .iter()
+            .flat_map(|family| formatter.format_family(family))
+            .collect::<Vec<_>>();
+        for packet in pack(lines) {
+            if let Err(err) = socket.send(packet.as_bytes()).await {
+                ckb_logger::debug!("statsd exporter failed to send: {}", err);
+            }
+        }
+    }
+}
+
+/// Formats the metrics into the StatsD lines.
+pub(crate) struct Formatter {
+    prefix: String,
+    // The last sent values of the cumulative metrics, keyed by the metric names with the tags.
+    counters: HashMap<String, f64>,
+}
+
+impl Formatter {
+    pub(crate) fn new(prefix: String) -> Self {
+        Formatter {
+            prefix,
+            counters: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn format_family(&mut self, family: &MetricFamily) -> Vec<String> {
+        let name = family.get_name();
+        let mut lines = Vec::new();
+        for metric in family.get_metric() {
+            let mut tags = String::new();
+            for (i, label) in metric.get_label().iter().enumerate() {
+                let sep = if i == 0 { "|#" } else { "," };
+                let _ = write!(tags, "{}{}:{}", sep, label.get_name(), label.get_value());
+            }
+            match family.get_field_type() {
+                MetricType::COUNTER => self.counter(
+                    &mut lines,
+                    name,
+                    "",
+                    &tags,
+                    metric.get_counter().get_value(),
+                ),
+                MetricType::GAUGE => {
+                    self.gauge(&mut lines, name, &tags, metric.get_gauge().get_value())
+                }
+                MetricType::UNTYPED => {
+                    self.gauge(&mut lines, name, &tags, metric.get_untyped().get_value())
+                }
+                MetricType::HISTOGRAM => {
+                    let histogram = metric.get_histogram();
+                    self.counter(&mut lines, name, "_sum", &tags, histogram.get_sample_sum());
+                    let count = histogram.get_sample_count() as f64;
+                    self.counter(&mut lines, name, "_count", &tags, count);
+                }
+                MetricType::SUMMARY => {
+                    let summary = metric.get_summary();
+                    self.counter(&mut lines, name, "_sum", &tags, summary.get_sample_sum());
+                    let count = summary.get_sample_count() as f64;
+                    self.counter(&mut lines, name, "_count", &tags, count);
+                }
+            }
+        }
+        lines
+    }
+
+    fn gauge(&self, lines: &mut Vec<String>, name: &str, tags: &str, value: f64) {
+        lines.push(format!("{}{}:{}|g{}", self.prefix, name, value, tags));
+    }
+
+    /// Sends the increment since the last send, nothing is sent if the counter is unchanged.
+    fn counter(
+        &mut self,
+        lines: &mut Vec<String>,
+        name: &str,
+        suffix: &str,
+        tags: &str,
+        value: f64,
+    ) {
+        let metric = format!("{}{}{}", self.prefix, name, suffix);
+        let last = self
+            .counters
+            .insert(format!("{}{}", metric, tags), value)
+            .unwrap_or(0.0);
+        // the counter is reset when it goes down
+        let delta = if value >= last { value - last } else { value };
+        if delta > 0.0 {
+            lines.push(format!("{}:{}|c{}", metric, delta, tags));
+        }
+    }
+}
+
+/// Joins the lines into the packets no larger than `MAX_PACKET_SIZE`, unless a single line is.
+pub(crate) fn pack(lines: Vec<String>) -> Vec<String> {
+    let mut packets = Vec::new();
+    let mut packet = String::new();
+    for line in lines {
+        if !packet.is_empty() && packet.len() + 1 + line.len() > MAX_PACKET_SIZE {
+            packets.push(std::mem::take(&mut packet));
+        }
+        if !packet.is_empty() {
+            packet.push('\n');
+        }
+        packet.push_str(&line);
+    }
+    if !packet.is_empty() {
+        packets.push(packet);
+    }
+    packets
+}
@@ -0,0 +1,112 @@
+use prometheus::proto::{Counter, Gauge, Histogram, LabelPair, Metric, MetricFamily, MetricType};
+
+use crate::check_histogram_buckets;
+use crate::statsd::{pack, Formatter};
+
+fn family(name: &str, field_type: MetricType, metric: Metric) -> MetricFamily {
+    let mut family = MetricFamily::default();
+    family.set_name(name.to_owned());
+    family.set_field_type(field_type);
+    family.mut_metric().push(metric);
+    family
+}
+
+fn label(name: &str, value: &str) -> LabelPair {
+    let mut label = LabelPair::default();
+    label.set_name(name.to_owned());
+    label.set_value(value.to_owned());
+    label
+}
+
+fn counter(value: f64, labels: Vec<LabelPair>) -> MetricFamily {
+    let mut counter = Counter::default();
+    counter.set_value(value);
+    let mut metric = Metric::default();
+    metric.set_counter(counter);
+    for label in labels {
+        metric.mut_label().push(label);
+    }
+    family("requests", MetricType::COUNTER, metric)
+}
+
+#[test]
+fn test_statsd_gauge() {
+    let mut gauge = Gauge::default();
+    gauge.set_value(42.0);
+    let mut metric = Metric::default();
+    metric.set_gauge(gauge);
+    let family = family("tx_pool_size", MetricType::GAUGE, metric);
+
+    let mut formatter = Formatter::new("ckb.".to_owned());
+    assert_eq!(
+        formatter.format_family(&family),
+        vec!["ckb.tx_pool_size:42|g"]
+    );
+    // the gauges are sent every time
+    assert_eq!(
+        formatter.format_family(&family),
+        vec!["ckb.tx_pool_size:42|g"]
+    );
+}
+
+#[test]
+fn test_statsd_counter_delta() {
+    let labels = || vec![label("method", "get_tip_header"), label("node", "node-1")];
+    let mut formatter = Formatter::new("ckb.".to_owned());
+    assert_eq!(
+        formatter.format_family(&counter(3.0, labels())),
+        vec!["ckb.requests:3|c|#method:get_tip_header,node:node-1"]
+    );
+    assert_eq!(
+        formatter.format_family(&counter(5.0, labels())),
+        vec!["ckb.requests:2|c|#method:get_tip_header,node:node-1"]
+    );
+    // nothing is sent when the counter is unchanged
+    assert!(formatter.format_family(&counter(5.0, labels())).is_empty());
+    // the counters with different labels are tracked separately
+    assert_eq!(
+        formatter.format_family(&counter(1.0, vec![label("method", "get_block")])),
+        vec!["ckb.requests:1|c|#method:get_block"]
+    );
+    // the counter is reset
+    assert_eq!(
+        formatter.format_family(&counter(4.0, labels())),
+        vec!["ckb.requests:4|c|#method:get_tip_header,node:node-1"]
+    );
+}
+
+#[test]
+fn test_statsd_histogram() {
+    let mut histogram = Histogram::default();
+    histogram.set_sample_sum(1.5);
+    histogram.set_sample_count(3);
+    let mut metric = Metric::default();
+    metric.set_histogram(histogram);
+    let family = family("latency", MetricType::HISTOGRAM, metric);
+
+    let mut formatter = Formatter::new(String::new());
+    assert_eq!(
+        formatter.format_family(&family),
+        vec!["latency_sum:1.5|c", "latency_count:3|c"]
+    );
+}
+
+#[test]
+fn test_statsd_pack() {
+    let line = "a".repeat(1000);
+    let packets = pack(vec![line.clone(), "b".to_owned(), line.clone()]);
+    assert_eq!(packets, vec![format!("{}\nb", line), line]);
+
+    // a line larger than the packet is sent alone
+    let large = "c".repeat(2000);
+    assert_eq!(pack(vec![large.clone()]), vec![large]);
+    assert!(pack(Vec::new()).is_empty());
+}
+
+#[test]
+fn test_check_histogram_buckets() {
+    assert!(check_histogram_buckets(&[0.001, 0.01, 0.1, 1.0]).is_ok());
+    assert!(check_histogram_buckets(&[]).is_err());
+    assert!(check_histogram_buckets(&[0.1, 0.1]).is_err());
+    assert!(check_histogram_buckets(&[1.0, 0.1]).is_err());
+}
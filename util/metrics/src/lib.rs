@@ -25,15 +25,45 @@
 //! }
 //! ```
 
-use opentelemetry::metrics::Meter;
+use once_cell::sync::OnceCell;
+use opentelemetry::{
+    global::GlobalMeterProvider,
+    metrics::{Meter, MeterProvider as _},
+};
 use std::time::{Duration, Instant};
 
 #[doc(hidden)]
 pub use opentelemetry as internal;
 
+const METER_NAME: &str = "ckb-metrics";
+
+static METER_PROVIDERS: OnceCell<Vec<GlobalMeterProvider>> = OnceCell::new();
+
 /// Returns a global meter.
 pub fn global_meter() -> Meter {
-    opentelemetry::global::meter("ckb-metrics")
+    opentelemetry::global::meter(METER_NAME)
+}
+
+/// Records the metrics to all the providers instead of the global one.
+///
+/// The exporters which install their own global providers can work together in this way. The
+/// providers can only be set once, returns the providers back if they have been set.
+pub fn set_meter_providers(
+    providers: Vec<GlobalMeterProvider>,
+) -> Result<(), Vec<GlobalMeterProvider>> {
+    METER_PROVIDERS.set(providers)
+}
+
+/// Returns the meters which the metrics are recorded to.
+#[doc(hidden)]
+pub fn meters() -> Vec<Meter> {
+    match METER_PROVIDERS.get() {
+        Some(providers) => providers
+            .iter()
+            .map(|provider| provider.meter(METER_NAME, None))
+            .collect(),
+        None => vec![global_meter()],
+    }
 }
 
 /// A simple timer which is used to time how much time elapsed.
@@ -60,22 +90,25 @@ impl Timer {
 // Since the APIs of opentelemetry<=0.15.0 is not stable, so just let them be compatible with metrics=0.12.1.
 #[macro_export(local_inner_macros)]
 macro_rules! metrics {
-    (counter, $label:literal, $value:expr $(, $span_name:expr => $span_value:expr )* $(,)?) => {
-        $crate::global_meter()
-            .u64_counter($label)
-            .init()
-            .add($value, &[$( $crate::internal::KeyValue::new($span_name, $span_value), )*]);
-    };
-    (gauge, $label:literal, $value:expr $(, $span_name:expr => $span_value:expr )* $(,)?) => {
-        $crate::global_meter()
-            .i64_value_recorder($label)
-            .init()
-            .record($value, &[$( $crate::internal::KeyValue::new($span_name, $span_value), )*]);
-    };
-    (timing, $label:literal, $duration:expr $(, $span_name:expr => $span_value:expr )* $(,)?) => {
-        $crate::global_meter()
-            .f64_value_recorder($label)
-            .init()
-            .record($duration.as_secs_f64(), &[$( $crate::internal::KeyValue::new($span_name, $span_value), )*]);
-    };
+    (counter, $label:literal, $value:expr $(, $span_name:expr => $span_value:expr )* $(,)?) => {{
+        let value = $value;
+        let labels: &[$crate::internal::KeyValue] = &[$( $crate::internal::KeyValue::new($span_name, $span_value), )*];
+        for meter in $crate::meters() {
+            meter.u64_counter($label).init().add(value, labels);
+        }
+    }};
+    (gauge, $label:literal, $value:expr $(, $span_name:expr => $span_value:expr )* $(,)?) => {{
+        let value = $value;
+        let labels: &[$crate::internal::KeyValue] = &[$( $crate::internal::KeyValue::new($span_name, $span_value), )*];
+        for meter in $crate::meters() {
+            meter.i64_value_recorder($label).init().record(value, labels);
+        }
+    }};
+    (timing, $label:literal, $duration:expr $(, $span_name:expr => $span_value:expr )* $(,)?) => {{
+        let value = $duration.as_secs_f64();
+        let labels: &[$crate::internal::KeyValue] = &[$( $crate::internal::KeyValue::new($span_name, $span_value), )*];
+        for meter in $crate::meters() {
+            meter.f64_value_recorder($label).init().record(value, labels);
+        }
+    }};
 }
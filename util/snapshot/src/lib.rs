@@ -17,7 +17,8 @@ use ckb_types::{
         cell::{CellChecker, CellProvider, CellStatus, HeaderChecker},
         BlockNumber, EpochExt, HeaderView,
     },
-    packed::{Byte32, OutPoint},
+    packed::{Byte32, HeaderDigest, OutPoint},
+    utilities::{leaf_index_to_mmr_size, ChainRootMMR, MMRError, MMRResult, MMRStore},
     U256,
 };
 use std::hash::{Hash, Hasher};
@@ -156,6 +157,12 @@ impl Snapshot {
     pub fn total_difficulty(&self) -> &U256 {
         &self.total_difficulty
     }
+
+    /// Return the chain root MMR whose leaves are the main chain headers from genesis to the block
+    pub fn chain_root_mmr(&self, block_number: BlockNumber) -> ChainRootMMR<&Self> {
+        let mmr_size = leaf_index_to_mmr_size(block_number);
+        ChainRootMMR::new(mmr_size, self)
+    }
 }
 
 impl<'a> ChainStore<'a> for Snapshot {
@@ -245,3 +252,15 @@ impl ConsensusProvider for Snapshot {
         self.consensus()
     }
 }
+
+impl MMRStore<HeaderDigest> for &Snapshot {
+    fn get_elem(&self, position: u64) -> MMRResult<Option<HeaderDigest>> {
+        Ok(self.get_header_digest(position))
+    }
+
+    fn append(&mut self, _position: u64, _digests: Vec<HeaderDigest>) -> MMRResult<()> {
+        Err(MMRError::StoreError(
+            "the snapshot is read-only".to_string(),
+        ))
+    }
+}
@@ -96,7 +96,9 @@ impl_conversion_for_entity_unpack!(core::EpochNumberWithFraction, Uint64);
 impl_conversion_for_option!(H256, Byte32Opt, Byte32OptReader);
 impl_conversion_for_vector!(Capacity, Uint64Vec, Uint64VecReader);
 impl_conversion_for_vector!(Bytes, BytesVec, BytesVecReader);
+impl_conversion_for_vector!(U256, Uint256Vec, Uint256VecReader);
 impl_conversion_for_packed_optional_pack!(Byte32, Byte32Opt);
+impl_conversion_for_packed_optional_pack!(Bytes, BytesOpt);
 impl_conversion_for_packed_optional_pack!(CellOutput, CellOutputOpt);
 impl_conversion_for_packed_optional_pack!(Script, ScriptOpt);
 impl_conversion_for_packed_iterator_pack!(ProposalShortId, ProposalShortIdVec);
@@ -2,3 +2,6 @@ use crate::{packed, prelude::*};
 
 impl_conversion_for_packed_iterator_pack!(IndexTransaction, IndexTransactionVec);
 impl_conversion_for_packed_iterator_pack!(RelayTransaction, RelayTransactionVec);
+impl_conversion_for_packed_iterator_pack!(HeaderDigest, HeaderDigestVec);
+impl_conversion_for_packed_iterator_pack!(VerifiableHeader, VerifiableHeaderVec);
+impl_conversion_for_packed_iterator_pack!(FilteredBlock, FilteredBlockVec);
@@ -101,6 +101,7 @@ pub struct HardForkSwitch {
     rfc_0032: EpochNumber,
     rfc_0036: EpochNumber,
     rfc_0038: EpochNumber,
+    rfc_0044: EpochNumber,
 }
 
 /// Builder for [`HardForkSwitch`].
@@ -147,6 +148,13 @@ pub struct HardForkSwitchBuilder {
     ///
     /// Ref: CKB RFC 0038
     pub rfc_0038: Option<EpochNumber>,
+    /// Commit the chain root in the block extension.
+    ///
+    /// The first 32 bytes of the block extension have to be the hash of the chain root of
+    /// all the blocks before this block, which makes the headers verifiable by light clients.
+    ///
+    /// Ref: CKB RFC 0044
+    pub rfc_0044: Option<EpochNumber>,
 }
 
 impl HardForkSwitch {
@@ -165,6 +173,7 @@ impl HardForkSwitch {
             .rfc_0032(self.rfc_0032())
             .rfc_0036(self.rfc_0036())
             .rfc_0038(self.rfc_0038())
+            .rfc_0044(self.rfc_0044())
     }
 
     /// Creates a new instance that all hard fork features are disabled forever.
@@ -178,6 +187,7 @@ impl HardForkSwitch {
             .disable_rfc_0032()
             .disable_rfc_0036()
             .disable_rfc_0038()
+            .disable_rfc_0044()
             .build()
             .unwrap()
     }
@@ -244,6 +254,13 @@ define_methods!(
     disable_rfc_0038,
     "RFC PR 0038"
 );
+define_methods!(
+    rfc_0044,
+    commit_chain_root_in_extension,
+    is_commit_chain_root_in_extension_enabled,
+    disable_rfc_0044,
+    "RFC PR 0044"
+);
 
 impl HardForkSwitchBuilder {
     /// Build a new [`HardForkSwitch`].
@@ -267,6 +284,14 @@ impl HardForkSwitchBuilder {
         let rfc_0032 = try_find!(rfc_0032);
         let rfc_0036 = try_find!(rfc_0036);
         let rfc_0038 = try_find!(rfc_0038);
+        let rfc_0044 = try_find!(rfc_0044);
+        if rfc_0044 < rfc_0031 {
+            return Err(
+                "The feature rfc_0044 commits the chain root in the block extension, \
+                it can't be enabled before the feature rfc_0031."
+                    .to_owned(),
+            );
+        }
 
         Ok(HardForkSwitch {
             rfc_0028,
@@ -276,6 +301,7 @@ impl HardForkSwitchBuilder {
             rfc_0032,
             rfc_0036,
             rfc_0038,
+            rfc_0044,
         })
     }
 }
@@ -2,7 +2,7 @@
 //!
 //! A CKB service acts as an actor, which processes requests from a channel and sends back the
 //! response via one shot channel.
-use crate::core::{Capacity, Cycle, TransactionView};
+use crate::core::{Capacity, Cycle, HeaderView, TransactionView};
 use ckb_channel::Sender;
 use std::sync::mpsc;
 
@@ -45,3 +45,14 @@ pub struct PoolTransactionEntry {
     /// The unix timestamp when entering the Txpool, unit: Millisecond
     pub timestamp: u64,
 }
+
+/// Notify chain reorganization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainReorg {
+    /// The latest common ancestor of the old and the new main chains
+    pub ancestor: HeaderView,
+    /// Blocks detached from the main chain, sorted by block number
+    pub detached_blocks: Vec<HeaderView>,
+    /// Blocks attached to the main chain, sorted by block number
+    pub attached_blocks: Vec<HeaderView>,
+}
@@ -47,6 +47,14 @@ pub enum Reject {
     /// Expired
     #[error("Expiry transaction, timestamp {0}")]
     Expiry(u64),
+
+    /// Replace-by-fee rejected
+    #[error("RBF rejected: {0}")]
+    RBFRejected(String),
+
+    /// Replaced by a conflicting transaction paying a higher fee
+    #[error("Replaced by transaction({0}) which pays a higher fee")]
+    RBFReplaced(Byte32),
 }
 
 fn is_malformed_from_verification(error: &Error) -> bool {
@@ -0,0 +1,111 @@
+//! The chain root MMR, a Merkle Mountain Range over the digests of all headers in the chain.
+//!
+//! The leaf at the index `n` is the digest of the header of the block `n`, so the root of the MMR
+//! which has the leaves `0..n` commits the whole chain before the block `n`. The light clients
+//! use the root and the proofs to verify the sampled headers without downloading the chain.
+use ckb_hash::new_blake2b;
+use ckb_merkle_mountain_range::{Merge, MerkleProof, MMR};
+
+use crate::{
+    core::{self, ExtraHashView},
+    packed,
+    prelude::*,
+    U256,
+};
+
+pub use ckb_merkle_mountain_range::{
+    leaf_index_to_mmr_size, leaf_index_to_pos, Error as MMRError, MMRStore, Result as MMRResult,
+};
+
+/// Merges two adjacent header digests into their parent node.
+pub struct MergeHeaderDigest;
+
+/// The chain root MMR.
+pub type ChainRootMMR<S> = MMR<packed::HeaderDigest, MergeHeaderDigest, S>;
+
+/// The proof of the chain root MMR.
+pub type MMRProof = MerkleProof<packed::HeaderDigest, MergeHeaderDigest>;
+
+impl Merge for MergeHeaderDigest {
+    type Item = packed::HeaderDigest;
+
+    fn merge(lhs: &Self::Item, rhs: &Self::Item) -> Self::Item {
+        // The peaks are bagged from the right, so the later range can be passed first.
+        let (lhs, rhs) = if Unpack::<u64>::unpack(&lhs.start_number())
+            > Unpack::<u64>::unpack(&rhs.end_number())
+        {
+            (rhs, lhs)
+        } else {
+            (lhs, rhs)
+        };
+        let children_hash = {
+            let mut hasher = new_blake2b();
+            let mut hash = [0u8; 32];
+            hasher.update(lhs.calc_mmr_hash().as_slice());
+            hasher.update(rhs.calc_mmr_hash().as_slice());
+            hasher.finalize(&mut hash);
+            hash
+        };
+        let lhs_difficulty: U256 = lhs.total_difficulty().unpack();
+        let rhs_difficulty: U256 = rhs.total_difficulty().unpack();
+        packed::HeaderDigest::new_builder()
+            .children_hash(children_hash.pack())
+            .total_difficulty((lhs_difficulty + rhs_difficulty).pack())
+            .start_number(lhs.start_number())
+            .start_epoch(lhs.start_epoch())
+            .start_timestamp(lhs.start_timestamp())
+            .start_compact_target(lhs.start_compact_target())
+            .end_number(rhs.end_number())
+            .end_epoch(rhs.end_epoch())
+            .end_timestamp(rhs.end_timestamp())
+            .end_compact_target(rhs.end_compact_target())
+            .build()
+    }
+}
+
+impl core::HeaderView {
+    /// Gets the digest of the header, which is the leaf of the chain root MMR.
+    pub fn digest(&self) -> packed::HeaderDigest {
+        packed::HeaderDigest::new_builder()
+            .children_hash(self.hash())
+            .total_difficulty(self.difficulty().pack())
+            .start_number(self.number().pack())
+            .end_number(self.number().pack())
+            .start_epoch(self.epoch().pack())
+            .end_epoch(self.epoch().pack())
+            .start_timestamp(self.timestamp().pack())
+            .end_timestamp(self.timestamp().pack())
+            .start_compact_target(self.compact_target().pack())
+            .end_compact_target(self.compact_target().pack())
+            .build()
+    }
+}
+
+impl packed::VerifiableHeader {
+    /// Checks whether the uncles hash and the extension match the extra hash in the header.
+    ///
+    /// The parent chain root is checked by [`is_chain_root_committed`] for the blocks which
+    /// commit it, the others can only be checked against a chain root MMR proof.
+    ///
+    /// [`is_chain_root_committed`]: #method.is_chain_root_committed
+    pub fn is_valid(&self) -> bool {
+        let extension_hash = self
+            .extension()
+            .to_opt()
+            .map(|extension| extension.calc_raw_data_hash());
+        let extra_hash = ExtraHashView::new(self.uncles_hash(), extension_hash).extra_hash();
+        self.header().raw().extra_hash() == extra_hash
+    }
+
+    /// Checks whether the parent chain root is committed in the first 32 bytes of the
+    /// extension, which is required since RFC 0044.
+    pub fn is_chain_root_committed(&self) -> bool {
+        self.extension()
+            .to_opt()
+            .map(|extension| {
+                extension.raw_data().get(..32)
+                    == Some(self.parent_chain_root().calc_mmr_hash().as_slice())
+            })
+            .unwrap_or(false)
+    }
+}
@@ -1,5 +1,6 @@
 //! Types utilities.
 mod difficulty;
+mod merkle_mountain_range;
 mod merkle_tree;
 
 #[cfg(test)]
@@ -8,4 +9,8 @@ mod tests;
 pub use difficulty::{
     compact_to_difficulty, compact_to_target, difficulty_to_compact, target_to_compact, DIFF_TWO,
 };
+pub use merkle_mountain_range::{
+    leaf_index_to_mmr_size, leaf_index_to_pos, ChainRootMMR, MMRError, MMRProof, MMRResult,
+    MMRStore, MergeHeaderDigest,
+};
 pub use merkle_tree::{merkle_root, MergeByte32, MerkleProof, CBMT};
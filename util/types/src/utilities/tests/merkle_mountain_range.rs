@@ -0,0 +1,77 @@
+use ckb_merkle_mountain_range::util::MemStore;
+
+use crate::{
+    core::{EpochNumberWithFraction, HeaderBuilder, HeaderView},
+    packed,
+    prelude::*,
+    utilities::{leaf_index_to_mmr_size, leaf_index_to_pos, ChainRootMMR, MMRProof},
+    U256,
+};
+
+fn build_headers(count: u64) -> Vec<HeaderView> {
+    (0..count)
+        .map(|number| {
+            HeaderBuilder::default()
+                .number(number.pack())
+                .epoch(EpochNumberWithFraction::new(number / 10, number % 10, 10).pack())
+                .timestamp((1_000 + number).pack())
+                .compact_target(0x2000_0100u32.pack())
+                .build()
+        })
+        .collect()
+}
+
+#[test]
+fn test_chain_root_digest() {
+    let headers = build_headers(13);
+    let store = MemStore::default();
+    let mut mmr = ChainRootMMR::new(0, &store);
+    for header in &headers {
+        mmr.push(header.digest()).unwrap();
+    }
+    assert_eq!(mmr.mmr_size(), leaf_index_to_mmr_size(12));
+
+    let root = mmr.get_root().unwrap();
+    let total_difficulty = headers
+        .iter()
+        .fold(U256::zero(), |total, header| total + header.difficulty());
+    let root_difficulty: U256 = root.total_difficulty().unpack();
+    assert_eq!(root_difficulty, total_difficulty);
+    assert_eq!(Unpack::<u64>::unpack(&root.start_number()), 0);
+    assert_eq!(Unpack::<u64>::unpack(&root.end_number()), 12);
+    assert_eq!(
+        Unpack::<EpochNumberWithFraction>::unpack(&root.start_epoch()),
+        headers[0].epoch()
+    );
+    assert_eq!(
+        Unpack::<u64>::unpack(&root.end_timestamp()),
+        headers[12].timestamp()
+    );
+}
+
+#[test]
+fn test_chain_root_proof() {
+    let headers = build_headers(20);
+    let store = MemStore::default();
+    let mut mmr = ChainRootMMR::new(0, &store);
+    for header in &headers {
+        mmr.push(header.digest()).unwrap();
+    }
+    let root = mmr.get_root().unwrap();
+
+    let numbers = [0u64, 7, 15, 19];
+    let positions = numbers.iter().map(|n| leaf_index_to_pos(*n)).collect();
+    let proof = mmr.gen_proof(positions).unwrap();
+    let proof_items: packed::HeaderDigestVec = proof.proof_items().iter().cloned().pack();
+
+    let received = MMRProof::new(proof.mmr_size(), proof_items.into_iter().collect());
+    let leaves = numbers
+        .iter()
+        .map(|n| (leaf_index_to_pos(*n), headers[*n as usize].digest()))
+        .collect::<Vec<_>>();
+    assert!(received.verify(root.clone(), leaves).unwrap());
+
+    let tampered = vec![(leaf_index_to_pos(7), headers[8].digest())];
+    let proof = mmr.gen_proof(vec![leaf_index_to_pos(7)]).unwrap();
+    assert!(!proof.verify(root, tampered).unwrap());
+}
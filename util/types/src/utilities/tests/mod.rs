@@ -1 +1,2 @@
 mod difficulty;
+mod merkle_mountain_range;
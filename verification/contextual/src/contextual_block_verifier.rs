@@ -3,7 +3,7 @@ use ckb_async_runtime::Handle;
 use ckb_chain_spec::consensus::{Consensus, ConsensusProvider};
 use ckb_dao::DaoCalculator;
 use ckb_dao_utils::DaoError;
-use ckb_error::Error;
+use ckb_error::{Error, InternalErrorKind};
 use ckb_logger::error_target;
 use ckb_reward_calculator::RewardCalculator;
 use ckb_store::ChainStore;
@@ -14,8 +14,9 @@ use ckb_types::{
         cell::{HeaderChecker, ResolvedTransaction},
         BlockReward, BlockView, Capacity, Cycle, EpochExt, HeaderView, TransactionView,
     },
-    packed::{Byte32, CellOutput, Script},
+    packed::{Byte32, CellOutput, HeaderDigest, Script},
     prelude::*,
+    utilities::{leaf_index_to_mmr_size, ChainRootMMR, MMRError, MMRResult, MMRStore},
 };
 use ckb_verification::cache::{
     TxVerificationCache, {CacheEntry, Completed},
@@ -470,6 +471,68 @@ impl<'a, CS: ChainStore<'a>> BlockTxsVerifier<'a, CS> {
         }
     }
 }
+
+// The chain root MMR in the store, which is never appended during the verification.
+struct ChainRootMMRStore<'a, CS> {
+    store: &'a CS,
+}
+
+impl<'a, CS: ChainStore<'a>> MMRStore<HeaderDigest> for ChainRootMMRStore<'a, CS> {
+    fn get_elem(&self, position: u64) -> MMRResult<Option<HeaderDigest>> {
+        Ok(self.store.get_header_digest(position))
+    }
+
+    fn append(&mut self, _position: u64, _digests: Vec<HeaderDigest>) -> MMRResult<()> {
+        Err(MMRError::StoreError(
+            "the chain root MMR is read-only during the verification".to_string(),
+        ))
+    }
+}
+
+/// ChainRootVerifier
+///
+/// Check the chain root committed in the block extension, which is the root of the chain root
+/// MMR over all the blocks before it.
+pub struct ChainRootVerifier<'a, CS> {
+    context: &'a VerifyContext<'a, CS>,
+    block: &'a BlockView,
+}
+
+impl<'a, CS: ChainStore<'a>> ChainRootVerifier<'a, CS> {
+    pub fn new(context: &'a VerifyContext<'a, CS>, block: &'a BlockView) -> Self {
+        ChainRootVerifier { context, block }
+    }
+
+    pub fn verify(&self) -> Result<(), Error> {
+        if self.block.is_genesis()
+            || !self
+                .context
+                .consensus
+                .hardfork_switch()
+                .is_commit_chain_root_in_extension_enabled(self.block.epoch().number())
+        {
+            return Ok(());
+        }
+        let extension = self
+            .block
+            .extension()
+            .ok_or(BlockErrorKind::NoBlockExtension)?;
+        let mmr_size = leaf_index_to_mmr_size(self.block.number() - 1);
+        let chain_root = ChainRootMMR::new(
+            mmr_size,
+            ChainRootMMRStore {
+                store: self.context.store,
+            },
+        )
+        .get_root()
+        .map_err(|err| InternalErrorKind::MMR.other(err))?;
+        if extension.raw_data().get(..32) != Some(chain_root.calc_mmr_hash().as_slice()) {
+            return Err(BlockErrorKind::InvalidChainRoot.into());
+        }
+        Ok(())
+    }
+}
+
 /// EpochVerifier
 ///
 /// Check for block epoch
@@ -511,6 +574,7 @@ impl<'a> EpochVerifier<'a> {
 ///
 /// Contains:
 /// - [`EpochVerifier`](./struct.EpochVerifier.html)
+/// - [`ChainRootVerifier`](./struct.ChainRootVerifier.html)
 /// - [`UnclesVerifier`](./struct.UnclesVerifier.html)
 /// - [`TwoPhaseCommitVerifier`](./struct.TwoPhaseCommitVerifier.html)
 /// - [`DaoHeaderVerifier`](./struct.DaoHeaderVerifier.html)
@@ -561,6 +625,8 @@ impl<'a, CS: ChainStore<'a>> ContextualBlockVerifier<'a, CS> {
             EpochVerifier::new(&epoch_ext, block).verify()?;
         }
 
+        ChainRootVerifier::new(self.context, block).verify()?;
+
         if !switch.disable_uncles() {
             let uncle_verifier_context = UncleVerifierContext::new(self.context, &epoch_ext);
             UnclesVerifier::new(uncle_verifier_context, block).verify()?;
@@ -282,6 +282,17 @@ impl<'a> BlockExtensionVerifier<'a> {
             return Err(BlockErrorKind::UnknownFields.into());
         }
 
+        // The chain root itself is checked in the contextual verification.
+        if !block.is_genesis()
+            && hardfork_switch.is_commit_chain_root_in_extension_enabled(epoch_number)
+        {
+            match block.extension() {
+                Some(extension) if extension.len() >= 32 => {}
+                Some(_) => return Err(BlockErrorKind::InvalidChainRoot.into()),
+                None => return Err(BlockErrorKind::NoBlockExtension.into()),
+            }
+        }
+
         let actual_extra_hash = block.calc_extra_hash().extra_hash();
         if actual_extra_hash != block.extra_hash() {
             return Err(BlockErrorKind::InvalidExtraHash.into());
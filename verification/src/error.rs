@@ -117,6 +117,13 @@ pub enum BlockErrorKind {
 
     /// The calculated extra-hash does not match with the one in the header.
     InvalidExtraHash,
+
+    /// The block extension is required to commit the chain root.
+    NoBlockExtension,
+
+    /// The chain root in the block extension does not match with the one calculated from the
+    /// chain.
+    InvalidChainRoot,
 }
 
 def_error_base_on_kind!(
@@ -550,3 +550,64 @@ fn test_block_extension_verifier() {
         assert_error_eq!(result.unwrap_err(), BlockErrorKind::InvalidExtraHash);
     }
 }
+
+#[test]
+fn test_block_extension_verifier_with_chain_root() {
+    let fork_at = 10;
+    let epoch = EpochNumberWithFraction::new(fork_at, 0, 1);
+    let header = HeaderBuilder::default()
+        .number(1u64.pack())
+        .epoch(epoch.pack())
+        .build();
+    let block = BlockBuilder::default().header(header).build();
+    // extension has only 31 bytes
+    let block1 = block
+        .as_advanced_builder()
+        .extension(Some(vec![0u8; 31].pack()))
+        .build();
+    // extension has 32 bytes
+    let block2 = block
+        .as_advanced_builder()
+        .extension(Some(vec![0u8; 32].pack()))
+        .build();
+
+    {
+        // Before the chain root is committed
+        let hardfork_switch = HardForkSwitch::new_without_any_enabled()
+            .as_builder()
+            .rfc_0031(fork_at)
+            .rfc_0044(fork_at + 1)
+            .build()
+            .unwrap();
+        let consensus = ConsensusBuilder::default()
+            .hardfork_switch(hardfork_switch)
+            .build();
+
+        let result = BlockExtensionVerifier::new(&consensus).verify(&block);
+        assert!(result.is_ok(), "result = {:?}", result);
+
+        let result = BlockExtensionVerifier::new(&consensus).verify(&block1);
+        assert!(result.is_ok(), "result = {:?}", result);
+    }
+    {
+        // After the chain root is committed
+        let hardfork_switch = HardForkSwitch::new_without_any_enabled()
+            .as_builder()
+            .rfc_0031(fork_at)
+            .rfc_0044(fork_at)
+            .build()
+            .unwrap();
+        let consensus = ConsensusBuilder::default()
+            .hardfork_switch(hardfork_switch)
+            .build();
+
+        let result = BlockExtensionVerifier::new(&consensus).verify(&block);
+        assert_error_eq!(result.unwrap_err(), BlockErrorKind::NoBlockExtension);
+
+        let result = BlockExtensionVerifier::new(&consensus).verify(&block1);
+        assert_error_eq!(result.unwrap_err(), BlockErrorKind::InvalidChainRoot);
+
+        let result = BlockExtensionVerifier::new(&consensus).verify(&block2);
+        assert!(result.is_ok(), "result = {:?}", result);
+    }
+}
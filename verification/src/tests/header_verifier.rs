@@ -10,8 +10,9 @@ use ckb_test_chain_utils::{MockMedianTime, MOCK_MEDIAN_TIME_COUNT};
 use ckb_types::{
     constants::BLOCK_VERSION,
     core::{EpochNumberWithFraction, HeaderBuilder},
-    packed::Header,
+    packed::{Byte32, Header},
     prelude::*,
+    U256,
 };
 use faketime::unix_time_as_millis;
 
@@ -247,6 +248,10 @@ impl PowEngine for FakePowEngine {
     fn verify(&self, _header: &Header) -> bool {
         false
     }
+
+    fn verify_target(&self, _pow_hash: &Byte32, _nonce: u128, _target: &U256) -> bool {
+        false
+    }
 }
 
 #[test]